@@ -280,19 +280,17 @@ pub async fn loop_consume_events(
 
         let find_accounts = || async {
             let mut num_of_events = 0;
-            let mut event_queue: EventQueue = client
+            let event_queue_account = client
                 .client
-                .rpc_anchor_account(&perp_market.event_queue)
+                .rpc_async()
+                .get_account(&perp_market.event_queue)
                 .await?;
+            let event_queue = EventQueue::from_account_data(&event_queue_account.data)?;
 
             // TODO: future, choose better constant of how many max events to pack
             // TODO: future, choose better constant of how many max mango accounts to pack
             let mut set = HashSet::new();
-            for _ in 0..10 {
-                let event = match event_queue.peek_front() {
-                    None => break,
-                    Some(e) => e,
-                };
+            for event in event_queue.iter().take(10) {
                 match EventType::try_from(event.event_type)? {
                     EventType::Fill => {
                         let fill: &FillEvent = cast_ref(event);
@@ -305,7 +303,6 @@ pub async fn loop_consume_events(
                     }
                     EventType::Liquidate => {}
                 }
-                event_queue.pop_front()?;
                 num_of_events += 1;
             }
 
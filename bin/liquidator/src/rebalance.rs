@@ -912,7 +912,8 @@ impl Rebalancer {
                 Side::Bid => perp.bids,
                 Side::Ask => perp.asks,
             };
-            let bookside = Box::new(self.account_fetcher.fetch::<BookSide>(&opposite_side_key)?);
+            let bookside_account = self.account_fetcher.fetch_raw(&opposite_side_key)?;
+            let bookside = BookSide::from_account_data(bookside_account.data())?;
             if bookside.quantity_at_price(price_lots, now_ts, oracle_price_lots) <= 0 {
                 warn!(
                     other_side = ?side.invert_side(),
@@ -80,7 +80,10 @@ mod tests {
                     &OracleConfig {
                         conf_filter: Default::default(),
                         max_staleness_slots: 0,
-                        reserved: [0; 72],
+                        max_staleness_seconds: 0,
+                        risk_increasing_max_staleness_slots: 0,
+                        risk_increasing_max_staleness_seconds: 0,
+                        reserved: [0; 48],
                     },
                     None,
                 )
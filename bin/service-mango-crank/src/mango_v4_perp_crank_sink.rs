@@ -1,5 +1,4 @@
 use std::{
-    borrow::BorrowMut,
     collections::{BTreeMap, BTreeSet},
     convert::TryFrom,
 };
@@ -16,8 +15,6 @@ use solana_sdk::{
 
 use bytemuck::cast_ref;
 
-use anchor_lang::AccountDeserialize;
-
 const MAX_BACKLOG: usize = 2;
 
 pub struct MangoV4PerpCrankSink {
@@ -44,8 +41,7 @@ impl MangoV4PerpCrankSink {
 impl AccountWriteSink for MangoV4PerpCrankSink {
     async fn process(&self, pk: &Pubkey, account: &AccountData) -> Result<(), String> {
         let account = &account.account;
-        let event_queue: mango_v4::state::EventQueue =
-            mango_v4::state::EventQueue::try_deserialize(account.data().borrow_mut()).unwrap();
+        let event_queue = mango_v4::state::EventQueue::from_account_data(account.data()).unwrap();
 
         // only crank if at least 1 fill or a sufficient events of other categories are buffered
         let contains_fill_events = event_queue
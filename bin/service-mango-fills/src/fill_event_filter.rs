@@ -12,7 +12,6 @@ use solana_sdk::{
     pubkey::Pubkey,
 };
 use std::{
-    borrow::BorrowMut,
     cmp::max,
     collections::{HashMap, HashSet},
     iter::FromIterator,
@@ -23,39 +22,38 @@ use std::{
 };
 
 use crate::metrics::MetricU64;
-use anchor_lang::AccountDeserialize;
 use mango_v4::state::{
     AnyEvent, EventQueue, EventQueueHeader, EventType, FillEvent as PerpFillEvent,
-    OutEvent as PerpOutEvent, QueueHeader, MAX_NUM_EVENTS,
+    OutEvent as PerpOutEvent, QueueHeader,
 };
 use service_mango_fills::*;
 
-// couldn't compile the correct struct size / math on m1, fixed sizes resolve this issue
-type EventQueueEvents = [AnyEvent; MAX_NUM_EVENTS as usize];
-
 #[allow(clippy::too_many_arguments)]
 fn publish_changes_perp(
     slot: u64,
     write_version: u64,
     mkt: &(Pubkey, MarketConfig),
     header: &EventQueueHeader,
-    events: &EventQueueEvents,
+    events: &[AnyEvent],
     prev_seq_num: u64,
     prev_head: usize,
-    prev_events: &EventQueueEvents,
+    prev_events: &[AnyEvent],
     fill_update_sender: &async_channel::Sender<FillEventFilterMessage>,
     metric_events_new: &mut MetricU64,
     metric_events_change: &mut MetricU64,
     metric_events_drop: &mut MetricU64,
     metric_head_update: &mut MetricU64,
 ) {
+    // the event queue capacity is configurable per market, callers ensure that
+    // events and prev_events have the same length
+    let capacity = events.len() as u64;
     // seq_num = N means that events (N-QUEUE_LEN) until N-1 are available
-    let start_seq_num = max(prev_seq_num, header.seq_num).saturating_sub(MAX_NUM_EVENTS as u64);
+    let start_seq_num = max(prev_seq_num, header.seq_num).saturating_sub(capacity);
     let mut checkpoint = Vec::new();
     let mkt_pk_string = mkt.0.to_string();
     let evq_pk_string = mkt.1.event_queue.to_string();
     for seq_num in start_seq_num..header.seq_num {
-        let idx = (seq_num % MAX_NUM_EVENTS as u64) as usize;
+        let idx = (seq_num % capacity) as usize;
 
         // there are three possible cases:
         // 1) the event is past the old seq num, hence guaranteed new event
@@ -141,7 +139,7 @@ fn publish_changes_perp(
 
     // in case queue size shrunk due to a fork we need revoke all previous fills
     for seq_num in header.seq_num..prev_seq_num {
-        let idx = (seq_num % MAX_NUM_EVENTS as u64) as usize;
+        let idx = (seq_num % capacity) as usize;
         debug!(
             "found dropped event {} idx {} seq_num {} header seq num {} old seq num {} slot {} write_version {}",
             mkt_pk_string, idx, seq_num, header.seq_num, prev_seq_num, slot, write_version
@@ -437,7 +435,7 @@ pub async fn init(
 
     let mut chain_cache = ChainData::new();
     let mut chain_data_metrics = ChainDataMetrics::new(&metrics_sender);
-    let mut perp_events_cache: HashMap<String, EventQueueEvents> = HashMap::new();
+    let mut perp_events_cache: HashMap<String, Vec<AnyEvent>> = HashMap::new();
     let mut serum_events_cache: HashMap<String, Vec<serum_dex::state::Event>> = HashMap::new();
     let mut seq_num_cache = HashMap::new();
     let mut head_cache = HashMap::new();
@@ -531,7 +529,7 @@ pub async fn init(
                         let is_perp = mango_v4::check_id(account.owner());
                         if is_perp {
                             let event_queue =
-                                EventQueue::try_deserialize(account.data().borrow_mut()).unwrap();
+                                EventQueue::from_account_data(account.data()).unwrap();
 
                             match (
                                 seq_num_cache.get(&evq_pk_string),
@@ -540,6 +538,12 @@ pub async fn init(
                                 (Some(prev_seq_num), Some(prev_head)) => match perp_events_cache
                                     .get(&evq_pk_string)
                                 {
+                                    // a resized queue can't be compared to the cached events
+                                    Some(prev_events)
+                                        if prev_events.len() != event_queue.buf.len() =>
+                                    {
+                                        info!("event queue {} was resized", evq_pk_string)
+                                    }
                                     Some(prev_events) => publish_changes_perp(
                                         account_info.slot,
                                         account_info.write_version,
@@ -564,7 +568,8 @@ pub async fn init(
 
                             seq_num_cache.insert(evq_pk_string.clone(), event_queue.header.seq_num);
                             head_cache.insert(evq_pk_string.clone(), event_queue.header.head());
-                            perp_events_cache.insert(evq_pk_string.clone(), event_queue.buf);
+                            perp_events_cache
+                                .insert(evq_pk_string.clone(), event_queue.buf.to_vec());
                         } else {
                             let inner_data = &account.data()[5..&account.data().len() - 7];
                             let header_span = std::mem::size_of::<SerumEventQueueHeader>();
@@ -1,4 +1,3 @@
-use anchor_lang::AccountDeserialize;
 use fixed::types::I80F48;
 use itertools::Itertools;
 use log::*;
@@ -30,7 +29,6 @@ use solana_sdk::{
     clock::Epoch,
     pubkey::Pubkey,
 };
-use std::borrow::BorrowMut;
 use std::{
     collections::{HashMap, HashSet},
     mem::size_of,
@@ -366,6 +364,7 @@ pub async fn init(
                             let oracle_config = OracleConfigParams {
                                 conf_filter: 100_000.0, // use a large value to never fail the confidence check
                                 max_staleness_slots: None, // don't check oracle staleness to get an orderbook
+                                ..Default::default()
                             };
 
                             if let Ok(unchecked_oracle_state) = oracle_state_unchecked(
@@ -381,9 +380,8 @@ pub async fn init(
                                 {
                                     let oracle_price = unchecked_oracle_state.price;
                                     let account = &side_info.account;
-                                    let bookside: BookSide = BookSide::try_deserialize(
-                                        solana_sdk::account::ReadableAccount::data(account)
-                                            .borrow_mut(),
+                                    let bookside = BookSide::from_account_data(
+                                        solana_sdk::account::ReadableAccount::data(account),
                                     )
                                     .unwrap();
                                     let side = match bookside.nodes.order_tree_type() {
@@ -5,10 +5,11 @@ use std::time::SystemTime;
 use anchor_lang::__private::bytemuck;
 
 use mango_v4::{
-    accounts_zerocopy::{KeyedAccountReader, KeyedAccountSharedData},
+    accounts_zerocopy::{KeyedAccountReader, KeyedAccountSharedData, LoadZeroCopy},
     state::{
-        determine_oracle_type, load_orca_pool_state, load_raydium_pool_state,
-        oracle_state_unchecked, Group, MangoAccountValue, OracleAccountInfos, OracleConfig,
+        determine_oracle_type, load_meteora_dlmm_state, load_orca_pool_state,
+        load_raydium_pool_state, oracle_state_unchecked, quote_mint_oracle, ClmmTwapOracle, Group,
+        MangoAccountValue, OpenbookV2MidOracle, OracleAccountInfos, OracleConfig,
         OracleConfigParams, OracleType, PerpMarketIndex, Serum3MarketIndex, TokenIndex, MAX_BANKS,
     },
 };
@@ -38,6 +39,8 @@ pub struct TokenContext {
     pub mint_info_address: Pubkey,
     pub decimals: u8,
     pub oracle_config: OracleConfig,
+    /// Banks of isolated collateral tokens must be writable in health accounts
+    pub isolated_collateral: bool,
 }
 
 impl TokenContext {
@@ -185,7 +188,7 @@ impl ComputeEstimates {
         self.health_for_counts(
             account.active_token_positions().count(),
             account.active_perp_positions().count(),
-            account.active_serum3_orders().count(),
+            account.active_serum3_orders().count() + account.active_openbook_v2_orders().count(),
             num_fallbacks,
         )
     }
@@ -284,6 +287,7 @@ impl MangoGroupContext {
                             quote_key: Pubkey::default(),
                         },
                         oracle_config: OracleConfigParams::default().to_oracle_config(),
+                        isolated_collateral: false,
                         group: mi.group,
                         mint: mi.mint,
                     },
@@ -307,6 +311,7 @@ impl MangoGroupContext {
             token.name = bank.name().into();
             token.decimals = bank.mint_decimals;
             token.oracle_config = bank.oracle_config;
+            token.isolated_collateral = bank.is_isolated_collateral();
             let (key, acc_info) = fallback_oracle_accounts[index].clone();
             token.fallback_context.quote_key =
                 get_fallback_quote_key(&KeyedAccountSharedData::new(key, acc_info));
@@ -442,7 +447,8 @@ impl MangoGroupContext {
             let token = self.token(position.token_index);
             banks.push((
                 token.first_bank(),
-                writable_banks.iter().any(|&ti| ti == position.token_index),
+                token.isolated_collateral
+                    || writable_banks.iter().any(|&ti| ti == position.token_index),
             ));
             oracles.push(token.oracle);
             if let Some(fallback_context) = fallback_contexts.get(&token.oracle) {
@@ -451,6 +457,7 @@ impl MangoGroupContext {
         }
 
         let serum_oos = account.active_serum3_orders().map(|&s| s.open_orders);
+        let openbook_oos = account.active_openbook_v2_orders().map(|&s| s.open_orders);
         let perp_markets = account
             .active_perp_positions()
             .map(|&pa| self.perp_market_address(pa.market_index));
@@ -483,6 +490,7 @@ impl MangoGroupContext {
             .chain(perp_markets.map(to_account_meta))
             .chain(perp_oracles.map(to_account_meta))
             .chain(serum_oos.map(to_account_meta))
+            .chain(openbook_oos.map(to_account_meta))
             .chain(fallback_oracles.into_iter().map(to_account_meta))
             .collect();
 
@@ -515,7 +523,8 @@ impl MangoGroupContext {
 
         for token_index in token_indexes {
             let token = self.token(token_index);
-            let writable_bank = writable_banks.iter().contains(&token_index);
+            let writable_bank =
+                token.isolated_collateral || writable_banks.iter().contains(&token_index);
             banks.push((token.first_bank(), writable_bank));
             oracles.push(token.oracle);
             if let Some(fallback_context) = fallback_contexts.get(&token.oracle) {
@@ -527,6 +536,10 @@ impl MangoGroupContext {
             .active_serum3_orders()
             .chain(account1.active_serum3_orders())
             .map(|&s| s.open_orders);
+        let openbook_oos = account2
+            .active_openbook_v2_orders()
+            .chain(account1.active_openbook_v2_orders())
+            .map(|&s| s.open_orders);
         let perp_market_indexes = account2
             .active_perp_positions()
             .chain(account1.active_perp_positions())
@@ -565,6 +578,7 @@ impl MangoGroupContext {
             .chain(perp_markets.map(to_account_meta))
             .chain(perp_oracles.map(to_account_meta))
             .chain(serum_oos.map(to_account_meta))
+            .chain(openbook_oos.map(to_account_meta))
             .chain(fallback_oracles.into_iter().map(to_account_meta))
             .collect();
 
@@ -585,12 +599,12 @@ impl MangoGroupContext {
         let cu = self.compute_estimates.health_for_counts(
             account1_token_count,
             account1.active_perp_positions().count(),
-            account1.active_serum3_orders().count(),
+            account1.active_serum3_orders().count() + account1.active_openbook_v2_orders().count(),
             fallbacks_len,
         ) + self.compute_estimates.health_for_counts(
             account2_token_count,
             account2.active_perp_positions().count(),
-            account2.active_serum3_orders().count(),
+            account2.active_serum3_orders().count() + account2.active_openbook_v2_orders().count(),
             fallbacks_len,
         );
 
@@ -742,6 +756,18 @@ fn get_fallback_quote_key(acc_info: &impl KeyedAccountReader) -> Pubkey {
                 Some(pool) => pool.get_quote_oracle().ok(),
                 None => None,
             },
+            OracleType::ClmmTwap => match acc_info.load::<ClmmTwapOracle>().ok() {
+                Some(twap) => quote_mint_oracle(&twap.quote_mint).ok(),
+                None => None,
+            },
+            OracleType::MeteoraDLMM => match load_meteora_dlmm_state(acc_info).ok() {
+                Some(pair) => pair.get_quote_oracle().ok(),
+                None => None,
+            },
+            OracleType::OpenbookV2Mid => match acc_info.load::<OpenbookV2MidOracle>().ok() {
+                Some(mid) => quote_mint_oracle(&mid.quote_mint).ok(),
+                None => None,
+            },
             _ => None,
         },
         None => None,
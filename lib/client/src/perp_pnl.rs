@@ -92,10 +92,16 @@ pub async fn fetch_top(
             } else {
                 I80F48::ZERO
             };
-            let perp_max_settle =
-                crate::health_cache::new(context, fallback_config, account_fetcher, &acc)
-                    .await?
-                    .perp_max_settle(perp_market.settle_token_index)?;
+            let health_cache =
+                crate::health_cache::new(context, fallback_config, account_fetcher, &acc).await?;
+            // Isolated positions only pay losses out of their own collateral
+            let perp_max_settle = if acc.perp_position(perp_market_index)?.is_isolated() {
+                health_cache
+                    .isolated_perp_bucket_cache(perp_market_index)?
+                    .perp_max_settle(perp_market.settle_token_index)?
+            } else {
+                health_cache.perp_max_settle(perp_market.settle_token_index)?
+            };
             let settleable_pnl = if perp_max_settle > 0 {
                 (*pnl).max(-perp_max_settle)
             } else {
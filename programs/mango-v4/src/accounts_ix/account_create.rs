@@ -15,7 +15,7 @@ pub struct AccountCreate<'info> {
         seeds = [b"MangoAccount".as_ref(), group.key().as_ref(), owner.key().as_ref(), &account_num.to_le_bytes()],
         bump,
         payer = payer,
-        space = MangoAccount::space(token_count, serum3_count, perp_count, perp_oo_count, 0),
+        space = MangoAccount::space(token_count, serum3_count, perp_count, perp_oo_count, 0, 0, 0, 0),
     )]
     pub account: AccountLoader<'info, MangoAccountFixed>,
     pub owner: Signer<'info>,
@@ -39,7 +39,55 @@ pub struct AccountCreateV2<'info> {
         seeds = [b"MangoAccount".as_ref(), group.key().as_ref(), owner.key().as_ref(), &account_num.to_le_bytes()],
         bump,
         payer = payer,
-        space = MangoAccount::space(token_count, serum3_count, perp_count, perp_oo_count, token_conditional_swap_count),
+        space = MangoAccount::space(token_count, serum3_count, perp_count, perp_oo_count, token_conditional_swap_count, 0, 0, 0),
+    )]
+    pub account: AccountLoader<'info, MangoAccountFixed>,
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(account_num: u32, token_count: u8, serum3_count: u8, perp_count: u8, perp_oo_count: u8, token_conditional_swap_count: u8, openbook_v2_count: u8)]
+pub struct AccountCreateV3<'info> {
+    #[account(
+        constraint = group.load()?.is_ix_enabled(IxGate::AccountCreate) @ MangoError::IxIsDisabled,
+    )]
+    pub group: AccountLoader<'info, Group>,
+
+    #[account(
+        init,
+        seeds = [b"MangoAccount".as_ref(), group.key().as_ref(), owner.key().as_ref(), &account_num.to_le_bytes()],
+        bump,
+        payer = payer,
+        space = MangoAccount::space(token_count, serum3_count, perp_count, perp_oo_count, token_conditional_swap_count, openbook_v2_count, 0, 0),
+    )]
+    pub account: AccountLoader<'info, MangoAccountFixed>,
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(account_num: u32, token_count: u8, serum3_count: u8, perp_count: u8, perp_oo_count: u8, token_conditional_swap_count: u8, openbook_v2_count: u8, fixed_term_count: u8, perp_scheduled_order_count: u8)]
+pub struct AccountCreateV4<'info> {
+    #[account(
+        constraint = group.load()?.is_ix_enabled(IxGate::AccountCreate) @ MangoError::IxIsDisabled,
+    )]
+    pub group: AccountLoader<'info, Group>,
+
+    #[account(
+        init,
+        seeds = [b"MangoAccount".as_ref(), group.key().as_ref(), owner.key().as_ref(), &account_num.to_le_bytes()],
+        bump,
+        payer = payer,
+        space = MangoAccount::space(token_count, serum3_count, perp_count, perp_oo_count, token_conditional_swap_count, openbook_v2_count, fixed_term_count, perp_scheduled_order_count),
     )]
     pub account: AccountLoader<'info, MangoAccountFixed>,
     pub owner: Signer<'info>,
@@ -0,0 +1,22 @@
+use crate::error::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+/// Remaining accounts:
+/// - health accounts for the account
+#[derive(Accounts)]
+pub struct AccountSetEmodeCategory<'info> {
+    #[account(
+        constraint = group.load()?.is_ix_enabled(IxGate::AccountSetEmodeCategory) @ MangoError::IxIsDisabled,
+    )]
+    pub group: AccountLoader<'info, Group>,
+
+    #[account(
+        mut,
+        has_one = group,
+        has_one = owner,
+        constraint = account.load()?.is_operational() @ MangoError::AccountIsFrozen
+    )]
+    pub account: AccountLoader<'info, MangoAccountFixed>,
+    pub owner: Signer<'info>,
+}
@@ -0,0 +1,31 @@
+use crate::{error::MangoError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ClmmTwapOracleCreate<'info> {
+    #[account(
+        has_one = admin,
+        constraint = group.load()?.is_ix_enabled(IxGate::ClmmTwapOracleCreate) @ MangoError::IxIsDisabled,
+    )]
+    pub group: AccountLoader<'info, Group>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<ClmmTwapOracle>(),
+    )]
+    pub oracle: AccountLoader<'info, ClmmTwapOracle>,
+
+    pub admin: Signer<'info>,
+
+    /// CHECK: Validated as a raydium clmm pool
+    pub pool: UncheckedAccount<'info>,
+
+    /// CHECK: Validated as the pool's observation account
+    pub observation: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
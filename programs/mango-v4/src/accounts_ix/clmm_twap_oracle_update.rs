@@ -0,0 +1,24 @@
+use crate::{error::MangoError, state::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ClmmTwapOracleUpdate<'info> {
+    #[account(
+        constraint = group.load()?.is_ix_enabled(IxGate::ClmmTwapOracleUpdate) @ MangoError::IxIsDisabled,
+    )]
+    pub group: AccountLoader<'info, Group>,
+
+    #[account(
+        mut,
+        has_one = group,
+        has_one = pool,
+        has_one = observation,
+    )]
+    pub oracle: AccountLoader<'info, ClmmTwapOracle>,
+
+    /// CHECK: Validated by the has_one constraint on oracle
+    pub pool: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the has_one constraint on oracle
+    pub observation: UncheckedAccount<'info>,
+}
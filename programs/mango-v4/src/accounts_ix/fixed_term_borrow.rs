@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::error::*;
+use crate::state::*;
+
+/// Health accounts for the account are expected in remaining_accounts.
+#[derive(Accounts)]
+pub struct FixedTermBorrow<'info> {
+    #[account(
+        constraint = group.load()?.is_ix_enabled(IxGate::FixedTermBorrow) @ MangoError::IxIsDisabled,
+    )]
+    pub group: AccountLoader<'info, Group>,
+
+    #[account(
+        mut,
+        has_one = group,
+        constraint = account.load()?.is_operational() @ MangoError::AccountIsFrozen,
+        constraint = account.load()?.is_owner_or_delegate(owner.key()),
+    )]
+    pub account: AccountLoader<'info, MangoAccountFixed>,
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = group,
+        constraint = fixed_term_market.load()?.token_index == bank.load()?.token_index,
+    )]
+    pub fixed_term_market: AccountLoader<'info, FixedTermMarket>,
+
+    #[account(mut, has_one = group, has_one = oracle)]
+    pub bank: AccountLoader<'info, Bank>,
+
+    /// CHECK: The oracle can be one of several different account types
+    pub oracle: UncheckedAccount<'info>,
+}
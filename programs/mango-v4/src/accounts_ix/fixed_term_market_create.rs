@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::error::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(market_index: FixedTermMarketIndex)]
+pub struct FixedTermMarketCreate<'info> {
+    #[account(
+        has_one = admin,
+        constraint = group.load()?.is_ix_enabled(IxGate::FixedTermMarketCreate) @ MangoError::IxIsDisabled,
+    )]
+    pub group: AccountLoader<'info, Group>,
+    pub admin: Signer<'info>,
+
+    #[account(has_one = group)]
+    pub bank: AccountLoader<'info, Bank>,
+
+    #[account(
+        init,
+        seeds = [b"FixedTermMarket".as_ref(), group.key().as_ref(), market_index.to_le_bytes().as_ref()],
+        bump,
+        payer = payer,
+        space = 8 + std::mem::size_of::<FixedTermMarket>(),
+    )]
+    pub fixed_term_market: AccountLoader<'info, FixedTermMarket>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
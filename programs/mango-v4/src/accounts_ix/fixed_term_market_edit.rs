@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct FixedTermMarketEdit<'info> {
+    #[account(
+        has_one = admin,
+    )]
+    pub group: AccountLoader<'info, Group>,
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = group
+    )]
+    pub fixed_term_market: AccountLoader<'info, FixedTermMarket>,
+}
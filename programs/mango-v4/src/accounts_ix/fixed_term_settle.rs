@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::error::*;
+use crate::state::*;
+
+/// Permissionless: anyone can settle a position after the market matured.
+///
+/// Settling the borrows of a liquidatable account before maturity needs its health
+/// accounts in remaining_accounts.
+#[derive(Accounts)]
+pub struct FixedTermSettle<'info> {
+    #[account(
+        constraint = group.load()?.is_ix_enabled(IxGate::FixedTermSettle) @ MangoError::IxIsDisabled,
+    )]
+    pub group: AccountLoader<'info, Group>,
+
+    #[account(
+        mut,
+        has_one = group,
+        constraint = account.load()?.is_operational() @ MangoError::AccountIsFrozen,
+    )]
+    pub account: AccountLoader<'info, MangoAccountFixed>,
+
+    #[account(
+        mut,
+        has_one = group,
+        constraint = fixed_term_market.load()?.token_index == bank.load()?.token_index,
+    )]
+    pub fixed_term_market: AccountLoader<'info, FixedTermMarket>,
+
+    #[account(mut, has_one = group)]
+    pub bank: AccountLoader<'info, Bank>,
+}
@@ -2,7 +2,10 @@ pub use account_buyback_fees_with_mngo::*;
 pub use account_close::*;
 pub use account_create::*;
 pub use account_edit::*;
+pub use account_edit_delegate_scope::*;
 pub use account_expand::*;
+pub use account_set_emode_category::*;
+pub use account_set_portfolio_margin::*;
 pub use account_size_migration::*;
 pub use account_toggle_freeze::*;
 pub use admin_perp_withdraw_fees::*;
@@ -10,7 +13,14 @@ pub use admin_token_withdraw_fees::*;
 pub use alt_extend::*;
 pub use alt_set::*;
 pub use benchmark::*;
+pub use clmm_twap_oracle_create::*;
+pub use clmm_twap_oracle_update::*;
 pub use compute_account_data::*;
+pub use fixed_term_borrow::*;
+pub use fixed_term_lend::*;
+pub use fixed_term_market_create::*;
+pub use fixed_term_market_edit::*;
+pub use fixed_term_settle::*;
 pub use flash_loan::*;
 pub use group_change_insurance_fund::*;
 pub use group_close::*;
@@ -26,24 +36,38 @@ pub use openbook_v2_create_open_orders::*;
 pub use openbook_v2_deregister_market::*;
 pub use openbook_v2_edit_market::*;
 pub use openbook_v2_liq_force_cancel_orders::*;
+pub use openbook_v2_mid_oracle_create::*;
+pub use openbook_v2_mid_oracle_update::*;
 pub use openbook_v2_place_order::*;
 pub use openbook_v2_place_take_order::*;
 pub use openbook_v2_register_market::*;
 pub use openbook_v2_settle_funds::*;
+pub use oracle_aggregator_create::*;
+pub use oracle_aggregator_edit::*;
+pub use oracle_aggregator_update::*;
 pub use perp_cancel_all_orders::*;
 pub use perp_cancel_all_orders_by_side::*;
 pub use perp_cancel_order::*;
 pub use perp_cancel_order_by_client_order_id::*;
+pub use perp_clear_opening_auction::*;
 pub use perp_close_market::*;
+pub use perp_conditional_order_create::*;
 pub use perp_consume_events::*;
 pub use perp_create_market::*;
 pub use perp_deactivate_position::*;
 pub use perp_edit_market::*;
 pub use perp_force_close_position::*;
+pub use perp_isolated_transfer::*;
 pub use perp_liq_base_or_positive_pnl::*;
 pub use perp_liq_force_cancel_orders::*;
 pub use perp_liq_negative_pnl_or_bankruptcy::*;
 pub use perp_place_order::*;
+pub use perp_place_spread_order::*;
+pub use perp_resize_orderbook::*;
+pub use perp_scheduled_order_cancel::*;
+pub use perp_scheduled_order_create::*;
+pub use perp_scheduled_order_trigger::*;
+pub use perp_set_isolated::*;
 pub use perp_settle_fees::*;
 pub use perp_settle_pnl::*;
 pub use perp_update_funding::*;
@@ -76,6 +100,7 @@ pub use token_liq_bankruptcy::*;
 pub use token_liq_with_token::*;
 pub use token_register::*;
 pub use token_register_trustless::*;
+pub use token_transfer::*;
 pub use token_update_index_and_rate::*;
 pub use token_withdraw::*;
 
@@ -83,7 +108,10 @@ mod account_buyback_fees_with_mngo;
 mod account_close;
 mod account_create;
 mod account_edit;
+mod account_edit_delegate_scope;
 mod account_expand;
+mod account_set_emode_category;
+mod account_set_portfolio_margin;
 mod account_size_migration;
 mod account_toggle_freeze;
 mod admin_perp_withdraw_fees;
@@ -91,7 +119,14 @@ mod admin_token_withdraw_fees;
 mod alt_extend;
 mod alt_set;
 mod benchmark;
+mod clmm_twap_oracle_create;
+mod clmm_twap_oracle_update;
 mod compute_account_data;
+mod fixed_term_borrow;
+mod fixed_term_lend;
+mod fixed_term_market_create;
+mod fixed_term_market_edit;
+mod fixed_term_settle;
 mod flash_loan;
 mod group_change_insurance_fund;
 mod group_close;
@@ -107,24 +142,38 @@ mod openbook_v2_create_open_orders;
 mod openbook_v2_deregister_market;
 mod openbook_v2_edit_market;
 mod openbook_v2_liq_force_cancel_orders;
+mod openbook_v2_mid_oracle_create;
+mod openbook_v2_mid_oracle_update;
 mod openbook_v2_place_order;
 mod openbook_v2_place_take_order;
 mod openbook_v2_register_market;
 mod openbook_v2_settle_funds;
+mod oracle_aggregator_create;
+mod oracle_aggregator_edit;
+mod oracle_aggregator_update;
 mod perp_cancel_all_orders;
 mod perp_cancel_all_orders_by_side;
 mod perp_cancel_order;
 mod perp_cancel_order_by_client_order_id;
+mod perp_clear_opening_auction;
 mod perp_close_market;
+mod perp_conditional_order_create;
 mod perp_consume_events;
 mod perp_create_market;
 mod perp_deactivate_position;
 mod perp_edit_market;
 mod perp_force_close_position;
+mod perp_isolated_transfer;
 mod perp_liq_base_or_positive_pnl;
 mod perp_liq_force_cancel_orders;
 mod perp_liq_negative_pnl_or_bankruptcy;
 mod perp_place_order;
+mod perp_place_spread_order;
+mod perp_resize_orderbook;
+mod perp_scheduled_order_cancel;
+mod perp_scheduled_order_create;
+mod perp_scheduled_order_trigger;
+mod perp_set_isolated;
 mod perp_settle_fees;
 mod perp_settle_pnl;
 mod perp_update_funding;
@@ -157,5 +206,6 @@ mod token_liq_bankruptcy;
 mod token_liq_with_token;
 mod token_register;
 mod token_register_trustless;
+mod token_transfer;
 mod token_update_index_and_rate;
 mod token_withdraw;
@@ -32,6 +32,15 @@ pub struct OpenbookV2CloseOpenOrders<'info> {
 
     pub openbook_v2_market_external: AccountLoader<'info, Market>,
 
+    #[account(
+        mut,
+        seeds = [b"OpenOrdersIndexer".as_ref(), group.key().as_ref()],
+        bump,
+        seeds::program = openbook_v2_program.key(),
+    )]
+    /// CHECK: Will be checked against seeds and by openbook v2
+    pub open_orders_indexer: UncheckedAccount<'info>,
+
     #[account(mut)]
     /// CHECK: Validated inline by checking against the pubkey stored in the account at #2
     pub open_orders: UncheckedAccount<'info>,
@@ -39,4 +48,9 @@ pub struct OpenbookV2CloseOpenOrders<'info> {
     #[account(mut)]
     /// CHECK: target for account rent needs no checks
     pub sol_destination: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
 }
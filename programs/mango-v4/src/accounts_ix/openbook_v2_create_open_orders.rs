@@ -32,10 +32,21 @@ pub struct OpenbookV2CreateOpenOrders<'info> {
 
     pub openbook_v2_market_external: AccountLoader<'info, Market>,
 
+    // initialized via cpi to openbook_v2 if it doesn't exist yet
+    #[account(
+        mut,
+        seeds = [b"OpenOrdersIndexer".as_ref(), group.key().as_ref()],
+        bump,
+        seeds::program = openbook_v2_program.key(),
+    )]
+    /// CHECK: Will be checked against seeds and will be initiated by openbook v2
+    pub open_orders_indexer: UncheckedAccount<'info>,
+
     // initialized by this instruction via cpi to openbook_v2
+    // account_num must be the next value of the indexer's created_counter
     #[account(
         mut,
-        seeds = [b"OpenOrders".as_ref(), openbook_v2_market.key().as_ref(), openbook_v2_market_external.key().as_ref(), &account_num.to_le_bytes()],
+        seeds = [b"OpenOrders".as_ref(), group.key().as_ref(), &account_num.to_le_bytes()],
         bump,
         seeds::program = openbook_v2_program.key(),
     )]
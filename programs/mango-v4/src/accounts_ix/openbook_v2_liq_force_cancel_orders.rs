@@ -33,9 +33,13 @@ pub struct OpenbookV2LiqForceCancelOrders<'info> {
     pub openbook_v2_program: Program<'info, OpenbookV2>,
 
     #[account(
+        mut,
         has_one = bids,
         has_one = asks,
         has_one = event_heap,
+        has_one = market_authority,
+        has_one = market_base_vault,
+        has_one = market_quote_vault,
     )]
     pub openbook_v2_market_external: AccountLoader<'info, Market>,
 
@@ -57,8 +61,8 @@ pub struct OpenbookV2LiqForceCancelOrders<'info> {
     #[account(mut)]
     pub market_quote_vault: Box<Account<'info, TokenAccount>>,
 
-    /// CHECK: Validated by the openbook_v2 cpi call
-    pub market_vault_signer: UncheckedAccount<'info>,
+    /// CHECK: Validated by the has_one on openbook_v2_market_external
+    pub market_authority: UncheckedAccount<'info>,
 
     // token_index and bank.vault == vault is validated inline at #3
     #[account(mut, has_one = group)]
@@ -70,5 +74,10 @@ pub struct OpenbookV2LiqForceCancelOrders<'info> {
     #[account(mut)]
     pub base_vault: Box<Account<'info, TokenAccount>>,
 
+    /// Pays openbook v2's penalty for settling fills that are still on the event heap
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
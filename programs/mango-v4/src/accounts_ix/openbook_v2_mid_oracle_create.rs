@@ -0,0 +1,34 @@
+use crate::{error::MangoError, state::*};
+use anchor_lang::prelude::*;
+use openbook_v2::state::{BookSide as OpenbookV2BookSide, Market};
+
+#[derive(Accounts)]
+pub struct OpenbookV2MidOracleCreate<'info> {
+    #[account(
+        has_one = admin,
+        constraint = group.load()?.is_ix_enabled(IxGate::OpenbookV2MidOracleCreate) @ MangoError::IxIsDisabled,
+    )]
+    pub group: AccountLoader<'info, Group>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<OpenbookV2MidOracle>(),
+    )]
+    pub oracle: AccountLoader<'info, OpenbookV2MidOracle>,
+
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = bids,
+        has_one = asks,
+    )]
+    pub market: AccountLoader<'info, Market>,
+    pub bids: AccountLoader<'info, OpenbookV2BookSide>,
+    pub asks: AccountLoader<'info, OpenbookV2BookSide>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
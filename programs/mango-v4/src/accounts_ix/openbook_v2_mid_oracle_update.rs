@@ -0,0 +1,24 @@
+use crate::{error::MangoError, state::*};
+use anchor_lang::prelude::*;
+use openbook_v2::state::{BookSide as OpenbookV2BookSide, Market};
+
+#[derive(Accounts)]
+pub struct OpenbookV2MidOracleUpdate<'info> {
+    #[account(
+        constraint = group.load()?.is_ix_enabled(IxGate::OpenbookV2MidOracleUpdate) @ MangoError::IxIsDisabled,
+    )]
+    pub group: AccountLoader<'info, Group>,
+
+    #[account(
+        mut,
+        has_one = group,
+        has_one = market,
+        has_one = bids,
+        has_one = asks,
+    )]
+    pub oracle: AccountLoader<'info, OpenbookV2MidOracle>,
+
+    pub market: AccountLoader<'info, Market>,
+    pub bids: AccountLoader<'info, OpenbookV2BookSide>,
+    pub asks: AccountLoader<'info, OpenbookV2BookSide>,
+}
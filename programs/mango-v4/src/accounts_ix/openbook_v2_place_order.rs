@@ -25,6 +25,11 @@ pub struct OpenbookV2PlaceOrder<'info> {
     /// CHECK: Validated inline by checking against the pubkey stored in the account at #2
     pub open_orders: UncheckedAccount<'info>,
 
+    #[account(
+        has_one = group,
+        has_one = openbook_v2_program,
+        has_one = openbook_v2_market_external,
+    )]
     pub openbook_v2_market: AccountLoader<'info, OpenbookV2Market>,
 
     pub openbook_v2_program: Program<'info, OpenbookV2>,
@@ -34,6 +39,8 @@ pub struct OpenbookV2PlaceOrder<'info> {
         has_one = bids,
         has_one = asks,
         has_one = event_heap,
+        has_one = market_base_vault,
+        has_one = market_quote_vault,
     )]
     pub openbook_v2_market_external: AccountLoader<'info, Market>,
 
@@ -50,16 +57,11 @@ pub struct OpenbookV2PlaceOrder<'info> {
     pub event_heap: UncheckedAccount<'info>,
 
     #[account(mut)]
-    /// CHECK: base vault will be checked by openbook_v2
     pub market_base_vault: Box<Account<'info, TokenAccount>>,
 
     #[account(mut)]
-    /// CHECK: quote vault will be checked by openbook_v2
     pub market_quote_vault: Box<Account<'info, TokenAccount>>,
 
-    /// CHECK: Validated by the openbook_v2 cpi call
-    pub market_vault_signer: UncheckedAccount<'info>,
-
     /// The bank that pays for the order, if necessary
     // token_index and payer_bank.vault == payer_vault is validated inline at #3
     #[account(mut, has_one = group)]
@@ -21,6 +21,10 @@ pub struct OpenbookV2PlaceTakeOrder<'info> {
 
     pub authority: Signer<'info>,
 
+    #[account(mut)]
+    /// CHECK: Validated inline by checking against the pubkey stored in the account at #2
+    pub open_orders: UncheckedAccount<'info>,
+
     #[account(
         has_one = group,
         has_one = openbook_v2_program,
@@ -35,44 +39,33 @@ pub struct OpenbookV2PlaceTakeOrder<'info> {
         has_one = bids,
         has_one = asks,
         has_one = event_heap,
+        has_one = market_base_vault,
+        has_one = market_quote_vault,
     )]
     pub openbook_v2_market_external: AccountLoader<'info, Market>,
 
-    /// CHECK: Validated by the openbook_v2 cpi call
     #[account(mut)]
+    /// CHECK: bids will be checked by openbook_v2
     pub bids: UncheckedAccount<'info>,
 
     #[account(mut)]
-    /// CHECK: Validated by the openbook_v2 cpi call
+    /// CHECK: asks will be checked by openbook_v2
     pub asks: UncheckedAccount<'info>,
 
     #[account(mut)]
-    /// CHECK: Validated by the openbook_v2 cpi call
+    /// CHECK: event queue will be checked by openbook_v2
     pub event_heap: UncheckedAccount<'info>,
 
     #[account(mut)]
-    /// CHECK: Validated by the openbook_v2 cpi call
-    pub market_request_queue: UncheckedAccount<'info>,
-
-    #[account(
-        mut,
-        constraint = market_base_vault.mint == payer_vault.mint,
-    )]
-    /// CHECK: Validated by the openbook_v2 cpi call
     pub market_base_vault: Box<Account<'info, TokenAccount>>,
 
     #[account(mut)]
-    /// CHECK: Validated by the openbook_v2 cpi call
     pub market_quote_vault: Box<Account<'info, TokenAccount>>,
 
-    /// CHECK: Validated by the openbook_v2 cpi call
-    pub market_vault_signer: UncheckedAccount<'info>,
-
     /// The bank that pays for the order, if necessary
     // token_index and payer_bank.vault == payer_vault is validated inline at #3
     #[account(mut, has_one = group)]
     pub payer_bank: AccountLoader<'info, Bank>,
-
     /// The bank vault that pays for the order, if necessary
     #[account(mut)]
     pub payer_vault: Box<Account<'info, TokenAccount>>,
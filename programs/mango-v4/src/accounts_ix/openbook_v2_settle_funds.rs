@@ -20,6 +20,8 @@ pub struct OpenbookV2SettleFunds<'info> {
     )]
     pub account: AccountLoader<'info, MangoAccountFixed>,
 
+    /// Also pays openbook v2's penalty for settling fills that are still on the event heap
+    #[account(mut)]
     pub authority: Signer<'info>,
 
     #[account(mut)]
@@ -35,7 +37,12 @@ pub struct OpenbookV2SettleFunds<'info> {
 
     pub openbook_v2_program: Program<'info, OpenbookV2>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        has_one = market_authority,
+        has_one = market_base_vault,
+        has_one = market_quote_vault,
+    )]
     pub openbook_v2_market_external: AccountLoader<'info, Market>,
 
     #[account(
@@ -50,9 +57,8 @@ pub struct OpenbookV2SettleFunds<'info> {
     )]
     pub market_quote_vault: Box<Account<'info, TokenAccount>>,
 
-    /// needed for the automatic settle_funds call
-    /// CHECK: Validated by the openbook_v2 cpi call
-    pub market_vault_signer: UncheckedAccount<'info>,
+    /// CHECK: Validated by the has_one on openbook_v2_market_external
+    pub market_authority: UncheckedAccount<'info>,
 
     // token_index and bank.vault == vault is validated inline at #3
     #[account(mut, has_one = group)]
@@ -73,4 +79,5 @@ pub struct OpenbookV2SettleFunds<'info> {
     pub base_oracle: UncheckedAccount<'info>,
 
     pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
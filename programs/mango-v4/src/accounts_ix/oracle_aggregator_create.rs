@@ -0,0 +1,30 @@
+use crate::{error::MangoError, state::*};
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+/// Remaining accounts:
+/// - the source oracles
+#[derive(Accounts)]
+pub struct OracleAggregatorCreate<'info> {
+    #[account(
+        has_one = admin,
+        constraint = group.load()?.is_ix_enabled(IxGate::OracleAggregatorCreate) @ MangoError::IxIsDisabled,
+    )]
+    pub group: AccountLoader<'info, Group>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<OracleAggregator>(),
+    )]
+    pub oracle: AccountLoader<'info, OracleAggregator>,
+
+    pub admin: Signer<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
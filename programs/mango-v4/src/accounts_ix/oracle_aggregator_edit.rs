@@ -0,0 +1,21 @@
+use crate::{error::MangoError, state::*};
+use anchor_lang::prelude::*;
+
+/// Remaining accounts:
+/// - the new source oracles, if they are being replaced
+#[derive(Accounts)]
+pub struct OracleAggregatorEdit<'info> {
+    #[account(
+        has_one = admin,
+        constraint = group.load()?.is_ix_enabled(IxGate::OracleAggregatorEdit) @ MangoError::IxIsDisabled,
+    )]
+    pub group: AccountLoader<'info, Group>,
+
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = group
+    )]
+    pub oracle: AccountLoader<'info, OracleAggregator>,
+}
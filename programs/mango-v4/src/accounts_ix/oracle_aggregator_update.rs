@@ -0,0 +1,19 @@
+use crate::{error::MangoError, state::*};
+use anchor_lang::prelude::*;
+
+/// Remaining accounts:
+/// - the source oracles, in order
+/// - optionally the usdc and sol pyth oracles, if CLMM sources need them
+#[derive(Accounts)]
+pub struct OracleAggregatorUpdate<'info> {
+    #[account(
+        constraint = group.load()?.is_ix_enabled(IxGate::OracleAggregatorUpdate) @ MangoError::IxIsDisabled,
+    )]
+    pub group: AccountLoader<'info, Group>,
+
+    #[account(
+        mut,
+        has_one = group
+    )]
+    pub oracle: AccountLoader<'info, OracleAggregator>,
+}
@@ -1,5 +1,5 @@
 use crate::error::MangoError;
-use crate::state::{BookSide, Group, IxGate, MangoAccountFixed, PerpMarket};
+use crate::state::{BookSideAccount, Group, IxGate, MangoAccountFixed, PerpMarket};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -26,7 +26,7 @@ pub struct PerpCancelAllOrdersBySide<'info> {
     )]
     pub perp_market: AccountLoader<'info, PerpMarket>,
     #[account(mut)]
-    pub bids: AccountLoader<'info, BookSide>,
+    pub bids: AccountLoader<'info, BookSideAccount>,
     #[account(mut)]
-    pub asks: AccountLoader<'info, BookSide>,
+    pub asks: AccountLoader<'info, BookSideAccount>,
 }
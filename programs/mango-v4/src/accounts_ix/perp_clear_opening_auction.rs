@@ -0,0 +1,30 @@
+use crate::error::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct PerpClearOpeningAuction<'info> {
+    #[account(
+        constraint = group.load()?.is_ix_enabled(IxGate::PerpClearOpeningAuction) @ MangoError::IxIsDisabled,
+    )]
+    pub group: AccountLoader<'info, Group>,
+
+    #[account(
+        mut,
+        has_one = group,
+        has_one = bids,
+        has_one = asks,
+        has_one = event_queue,
+        has_one = oracle,
+    )]
+    pub perp_market: AccountLoader<'info, PerpMarket>,
+    #[account(mut)]
+    pub bids: AccountLoader<'info, BookSideAccount>,
+    #[account(mut)]
+    pub asks: AccountLoader<'info, BookSideAccount>,
+    #[account(mut)]
+    pub event_queue: AccountLoader<'info, EventQueueAccount>,
+
+    /// CHECK: The oracle can be one of several different account types and the pubkey is checked above
+    pub oracle: UncheckedAccount<'info>,
+}
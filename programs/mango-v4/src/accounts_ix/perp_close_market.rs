@@ -27,19 +27,19 @@ pub struct PerpCloseMarket<'info> {
         mut,
         close = sol_destination
     )]
-    pub bids: AccountLoader<'info, BookSide>,
+    pub bids: AccountLoader<'info, BookSideAccount>,
 
     #[account(
         mut,
         close = sol_destination
     )]
-    pub asks: AccountLoader<'info, BookSide>,
+    pub asks: AccountLoader<'info, BookSideAccount>,
 
     #[account(
         mut,
         close = sol_destination
     )]
-    pub event_queue: AccountLoader<'info, EventQueue>,
+    pub event_queue: AccountLoader<'info, EventQueueAccount>,
 
     #[account(mut)]
     /// CHECK: target for account rent needs no checks
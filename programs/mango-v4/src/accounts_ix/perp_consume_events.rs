@@ -17,5 +17,5 @@ pub struct PerpConsumeEvents<'info> {
     pub perp_market: AccountLoader<'info, PerpMarket>,
 
     #[account(mut)]
-    pub event_queue: AccountLoader<'info, EventQueue>,
+    pub event_queue: AccountLoader<'info, EventQueueAccount>,
 }
@@ -28,11 +28,11 @@ pub struct PerpCreateMarket<'info> {
     /// Accounts are initialised by client,
     /// anchor discriminator is set first when ix exits,
     #[account(zero)]
-    pub bids: AccountLoader<'info, BookSide>,
+    pub bids: AccountLoader<'info, BookSideAccount>,
     #[account(zero)]
-    pub asks: AccountLoader<'info, BookSide>,
+    pub asks: AccountLoader<'info, BookSideAccount>,
     #[account(zero)]
-    pub event_queue: AccountLoader<'info, EventQueue>,
+    pub event_queue: AccountLoader<'info, EventQueueAccount>,
 
     #[account(mut)]
     pub payer: Signer<'info>,
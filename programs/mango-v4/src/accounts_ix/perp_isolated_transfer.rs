@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::error::*;
+use crate::state::*;
+
+/// Remaining accounts:
+/// - health accounts for the account
+#[derive(Accounts)]
+pub struct PerpIsolatedTransfer<'info> {
+    #[account(
+        constraint = group.load()?.is_ix_enabled(IxGate::PerpIsolatedTransfer) @ MangoError::IxIsDisabled,
+    )]
+    pub group: AccountLoader<'info, Group>,
+
+    #[account(
+        mut,
+        has_one = group,
+        constraint = account.load()?.is_operational() @ MangoError::AccountIsFrozen
+        // owner is checked at #1
+    )]
+    pub account: AccountLoader<'info, MangoAccountFixed>,
+    pub owner: Signer<'info>,
+
+    #[account(has_one = group)]
+    pub perp_market: AccountLoader<'info, PerpMarket>,
+}
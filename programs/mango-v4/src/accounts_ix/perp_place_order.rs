@@ -28,12 +28,23 @@ pub struct PerpPlaceOrder<'info> {
     )]
     pub perp_market: AccountLoader<'info, PerpMarket>,
     #[account(mut)]
-    pub bids: AccountLoader<'info, BookSide>,
+    pub bids: AccountLoader<'info, BookSideAccount>,
     #[account(mut)]
-    pub asks: AccountLoader<'info, BookSide>,
+    pub asks: AccountLoader<'info, BookSideAccount>,
     #[account(mut)]
-    pub event_queue: AccountLoader<'info, EventQueue>,
+    pub event_queue: AccountLoader<'info, EventQueueAccount>,
 
     /// CHECK: The oracle can be one of several different account types and the pubkey is checked above
     pub oracle: UncheckedAccount<'info>,
 }
+
+/// One order of a PerpPlaceOrdersBatch instruction
+#[derive(Copy, Clone, Debug, AnchorSerialize, AnchorDeserialize)]
+pub struct PerpBatchOrder {
+    pub side: Side,
+    /// Price in lots, see perp_place_order
+    pub price_lots: i64,
+    pub max_base_lots: i64,
+    pub order_type: PlaceOrderType,
+    pub client_order_id: u64,
+}
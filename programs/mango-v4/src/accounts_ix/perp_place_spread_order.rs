@@ -0,0 +1,57 @@
+use crate::error::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct PerpPlaceSpreadOrder<'info> {
+    #[account(
+        constraint = group.load()?.is_ix_enabled(IxGate::PerpPlaceSpreadOrder) @ MangoError::IxIsDisabled,
+    )]
+    pub group: AccountLoader<'info, Group>,
+
+    #[account(
+        mut,
+        has_one = group,
+        constraint = account.load()?.is_operational() @ MangoError::AccountIsFrozen,
+        constraint = account.load()?.is_owner_or_delegate(owner.key()),
+    )]
+    pub account: AccountLoader<'info, MangoAccountFixed>,
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = group,
+        constraint = perp_market_a.load()?.bids == bids_a.key(),
+        constraint = perp_market_a.load()?.asks == asks_a.key(),
+        constraint = perp_market_a.load()?.event_queue == event_queue_a.key(),
+        constraint = perp_market_a.load()?.oracle == oracle_a.key(),
+    )]
+    pub perp_market_a: AccountLoader<'info, PerpMarket>,
+    #[account(mut)]
+    pub bids_a: AccountLoader<'info, BookSideAccount>,
+    #[account(mut)]
+    pub asks_a: AccountLoader<'info, BookSideAccount>,
+    #[account(mut)]
+    pub event_queue_a: AccountLoader<'info, EventQueueAccount>,
+    /// CHECK: The oracle can be one of several different account types and the pubkey is checked above
+    pub oracle_a: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = group,
+        constraint = perp_market_b.load()?.bids == bids_b.key(),
+        constraint = perp_market_b.load()?.asks == asks_b.key(),
+        constraint = perp_market_b.load()?.event_queue == event_queue_b.key(),
+        constraint = perp_market_b.load()?.oracle == oracle_b.key(),
+        constraint = perp_market_b.key() != perp_market_a.key(),
+    )]
+    pub perp_market_b: AccountLoader<'info, PerpMarket>,
+    #[account(mut)]
+    pub bids_b: AccountLoader<'info, BookSideAccount>,
+    #[account(mut)]
+    pub asks_b: AccountLoader<'info, BookSideAccount>,
+    #[account(mut)]
+    pub event_queue_b: AccountLoader<'info, EventQueueAccount>,
+    /// CHECK: The oracle can be one of several different account types and the pubkey is checked above
+    pub oracle_b: UncheckedAccount<'info>,
+}
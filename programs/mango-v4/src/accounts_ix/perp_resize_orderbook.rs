@@ -0,0 +1,33 @@
+use crate::error::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct PerpResizeOrderbook<'info> {
+    #[account(
+        has_one = admin,
+        constraint = group.load()?.is_ix_enabled(IxGate::PerpResizeOrderbook) @ MangoError::IxIsDisabled,
+    )]
+    pub group: AccountLoader<'info, Group>,
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = group,
+        has_one = bids,
+        has_one = asks,
+        has_one = event_queue,
+    )]
+    pub perp_market: AccountLoader<'info, PerpMarket>,
+
+    #[account(mut)]
+    pub bids: AccountLoader<'info, BookSideAccount>,
+    #[account(mut)]
+    pub asks: AccountLoader<'info, BookSideAccount>,
+    #[account(mut)]
+    pub event_queue: AccountLoader<'info, EventQueueAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
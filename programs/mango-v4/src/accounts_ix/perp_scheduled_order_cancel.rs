@@ -0,0 +1,32 @@
+use crate::error::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct PerpScheduledOrderCancel<'info> {
+    #[account(
+        constraint = group.load()?.is_ix_enabled(IxGate::PerpScheduledOrderCancel) @ MangoError::IxIsDisabled,
+    )]
+    pub group: AccountLoader<'info, Group>,
+
+    #[account(
+        mut,
+        has_one = group,
+        constraint = account.load()?.is_operational() @ MangoError::AccountIsFrozen,
+        constraint = account.load()?.is_owner_or_delegate(authority.key()),
+    )]
+    pub account: AccountLoader<'info, MangoAccountFixed>,
+    pub authority: Signer<'info>,
+
+    /// The market of the scheduled order, for cancelling a resting iceberg child order
+    #[account(
+        has_one = group,
+        has_one = bids,
+        has_one = asks,
+    )]
+    pub perp_market: AccountLoader<'info, PerpMarket>,
+    #[account(mut)]
+    pub bids: AccountLoader<'info, BookSideAccount>,
+    #[account(mut)]
+    pub asks: AccountLoader<'info, BookSideAccount>,
+}
@@ -0,0 +1,58 @@
+use crate::error::*;
+use crate::state::*;
+use anchor_lang::prelude::*;
+
+/// Places the next child order of a perp scheduled order.
+///
+/// Health accounts for `account` are passed as remaining accounts and must
+/// include the perp market's settle token bank.
+#[derive(Accounts)]
+pub struct PerpScheduledOrderTrigger<'info> {
+    #[account(
+        constraint = group.load()?.is_ix_enabled(IxGate::PerpScheduledOrderTrigger) @ MangoError::IxIsDisabled,
+    )]
+    pub group: AccountLoader<'info, Group>,
+
+    #[account(
+        mut,
+        has_one = group,
+        constraint = account.load()?.is_operational() @ MangoError::AccountIsFrozen
+    )]
+    pub account: AccountLoader<'info, MangoAccountFixed>,
+
+    #[account(
+        mut,
+        has_one = group,
+        constraint = caller.load()?.is_operational() @ MangoError::AccountIsFrozen,
+        constraint = caller.load()?.is_owner_or_delegate(caller_authority.key()),
+        constraint = caller.key() != account.key(),
+    )]
+    pub caller: AccountLoader<'info, MangoAccountFixed>,
+    pub caller_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = group,
+        has_one = bids,
+        has_one = asks,
+        has_one = event_queue,
+        has_one = oracle,
+    )]
+    pub perp_market: AccountLoader<'info, PerpMarket>,
+    #[account(mut)]
+    pub bids: AccountLoader<'info, BookSideAccount>,
+    #[account(mut)]
+    pub asks: AccountLoader<'info, BookSideAccount>,
+    #[account(mut)]
+    pub event_queue: AccountLoader<'info, EventQueueAccount>,
+
+    /// CHECK: The oracle can be one of several different account types and the pubkey is checked above
+    pub oracle: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = group,
+        constraint = settle_bank.load()?.token_index == perp_market.load()?.settle_token_index @ MangoError::InvalidBank
+    )]
+    pub settle_bank: AccountLoader<'info, Bank>,
+}
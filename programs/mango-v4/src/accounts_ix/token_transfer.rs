@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::error::*;
+use crate::state::*;
+
+/// Remaining accounts:
+/// - health accounts for the source account
+#[derive(Accounts)]
+pub struct TokenTransfer<'info> {
+    #[account(
+        constraint = group.load()?.is_ix_enabled(IxGate::TokenTransfer) @ MangoError::IxIsDisabled,
+    )]
+    pub group: AccountLoader<'info, Group>,
+
+    #[account(
+        mut,
+        has_one = group,
+        constraint = source.load()?.is_operational() @ MangoError::AccountIsFrozen,
+        constraint = source.load()?.is_owner_or_delegate(owner.key()),
+    )]
+    pub source: AccountLoader<'info, MangoAccountFixed>,
+
+    #[account(
+        mut,
+        has_one = group,
+        constraint = destination.load()?.is_operational() @ MangoError::AccountIsFrozen,
+        constraint = destination.load()?.owner == source.load()?.owner @ MangoError::SomeError,
+    )]
+    pub destination: AccountLoader<'info, MangoAccountFixed>,
+
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = group,
+        has_one = oracle,
+    )]
+    pub bank: AccountLoader<'info, Bank>,
+
+    /// CHECK: The oracle can be one of several different account types
+    pub oracle: UncheckedAccount<'info>,
+}
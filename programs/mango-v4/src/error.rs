@@ -151,6 +151,60 @@ pub enum MangoError {
     InvalidSequenceNumber,
     #[msg("invalid health")]
     InvalidHealth,
+    #[msg("no free openbook v2 open orders index")]
+    NoFreeOpenbookV2OpenOrdersIndex,
+    #[msg("openbook v2 open orders exist already")]
+    OpenbookV2OpenOrdersExistAlready,
+    #[msg("there are open or unsettled openbook v2 orders")]
+    HasOpenOrUnsettledOpenbookV2Orders,
+    #[msg("no free fixed term position index")]
+    NoFreeFixedTermPositionIndex,
+    #[msg("fixed term market has matured")]
+    FixedTermMarketMatured,
+    #[msg("fixed term market has not matured yet")]
+    FixedTermMarketNotMatured,
+    #[msg("fixed term market does not have enough lent tokens available")]
+    FixedTermMarketInsufficientLiquidity,
+    #[msg("fixed term market lend limit exceeded")]
+    FixedTermMarketLendLimitExceeded,
+    #[msg("perp scheduled order is not set")]
+    PerpScheduledOrderNotSet,
+    #[msg("perp scheduled order is expired")]
+    PerpScheduledOrderExpired,
+    #[msg("perp scheduled order can't be triggered yet")]
+    PerpScheduledOrderNotTriggerable,
+    #[msg("perp scheduled order index and id don't match")]
+    PerpScheduledOrderIndexIdMismatch,
+    #[msg("perp conditional order oracle price condition is not met")]
+    PerpConditionalOrderPriceNotTriggered,
+    #[msg("the second leg of a perp spread order could not be filled completely")]
+    PerpSpreadLegNotFilled,
+    #[msg("perp position is not isolated")]
+    PerpPositionNotIsolated,
+    #[msg("isolated perp position health must be positive or not decrease")]
+    IsolatedPerpHealthMustBePositiveOrIncrease,
+    #[msg("the delegate is not permitted to take this action")]
+    DelegatePermissionDenied,
+    #[msg("the order exceeds the delegate's notional limit")]
+    DelegateOrderNotionalExceeded,
+    #[msg("not enough fresh and agreeing source oracles")]
+    OracleQuorumNotReached,
+    #[msg("borrow crosses the token's borrow limit")]
+    BankBorrowLimit,
+    #[msg("isolated collateral cannot back borrows of this token")]
+    IsolatedCollateralBorrowNotAllowed,
+    #[msg("invalid e-mode category")]
+    InvalidEmodeCategory,
+    #[msg("invalid perp fee tier")]
+    InvalidPerpFeeTier,
+    #[msg("orders that can't rest on the book are not allowed during a perp opening auction")]
+    PerpOpeningAuctionOrderNotPostable,
+    #[msg("perp market is not in an opening auction")]
+    PerpOpeningAuctionNotActive,
+    #[msg("perp opening auction has not ended yet")]
+    PerpOpeningAuctionNotEnded,
+    #[msg("the token's isolated collateral backs the maximum amount of borrows")]
+    IsolatedDebtCeilingReached,
 }
 
 impl MangoError {
@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 use anchor_lang::ZeroCopy;
 
 use fixed::types::I80F48;
+use openbook_v2::state::OpenOrdersAccount;
 use serum_dex::state::OpenOrders;
 
 use std::cell::Ref;
@@ -9,11 +10,12 @@ use std::collections::HashMap;
 
 use crate::accounts_zerocopy::*;
 use crate::error::*;
+use crate::openbook_v2_cpi;
 use crate::serum3_cpi;
 use crate::state::pyth_mainnet_sol_oracle;
 use crate::state::pyth_mainnet_usdc_oracle;
-use crate::state::OracleAccountInfos;
 use crate::state::{Bank, MangoAccountRef, PerpMarket, PerpMarketIndex, TokenIndex};
+use crate::state::{OracleAccountInfos, OracleFreshness};
 
 /// This trait abstracts how to find accounts needed for the health computation.
 ///
@@ -38,6 +40,12 @@ pub trait AccountRetriever {
 
     fn serum_oo(&self, active_serum_oo_index: usize, key: &Pubkey) -> Result<&OpenOrders>;
 
+    fn openbook_oo(
+        &self,
+        active_openbook_oo_index: usize,
+        key: &Pubkey,
+    ) -> Result<&OpenOrdersAccount>;
+
     fn perp_market_and_oracle_price(
         &self,
         group: &Pubkey,
@@ -54,13 +62,15 @@ pub trait AccountRetriever {
 /// 3. PerpMarket accounts, in the order of account.perps.active_perp_positions()
 /// 4. PerpMarket oracle accounts, in the order of the perp market accounts
 /// 5. serum3 OpenOrders accounts, in the order of account.active_serum3_orders()
-/// 6. fallback oracle accounts, order and existence of accounts is not guaranteed
+/// 6. openbook_v2 OpenOrdersAccount accounts, in the order of account.active_openbook_v2_orders()
+/// 7. fallback oracle accounts, order and existence of accounts is not guaranteed
 pub struct FixedOrderAccountRetriever<T: KeyedAccountReader> {
     pub ais: Vec<T>,
     pub n_banks: usize,
     pub n_perps: usize,
     pub begin_perp: usize,
     pub begin_serum3: usize,
+    pub begin_openbook_v2: usize,
     pub now: Option<(u64, u64)>,
     pub begin_fallback_oracles: usize,
     pub usdc_oracle_index: Option<usize>,
@@ -120,14 +130,16 @@ pub fn new_fixed_order_account_retriever_inner<'a, 'info>(
     n_banks: usize,
 ) -> Result<FixedOrderAccountRetriever<AccountInfoRef<'a, 'info>>> {
     let active_serum3_len = account.active_serum3_orders().count();
+    let active_openbook_v2_len = account.active_openbook_v2_orders().count();
     let active_perp_len = account.active_perp_positions().count();
     let expected_ais = n_banks * 2 // banks + oracles
         + active_perp_len * 2 // PerpMarkets + Oracles
-        + active_serum3_len; // open_orders
+        + active_serum3_len // serum3 open_orders
+        + active_openbook_v2_len; // openbook_v2 open_orders
     require_msg_typed!(ais.len() >= expected_ais, MangoError::InvalidHealthAccountCount,
-        "received {} accounts but expected {} ({} banks, {} bank oracles, {} perp markets, {} perp oracles, {} serum3 oos)",
+        "received {} accounts but expected {} ({} banks, {} bank oracles, {} perp markets, {} perp oracles, {} serum3 oos, {} openbook_v2 oos)",
         ais.len(), expected_ais,
-        n_banks, n_banks, active_perp_len, active_perp_len, active_serum3_len
+        n_banks, n_banks, active_perp_len, active_perp_len, active_serum3_len, active_openbook_v2_len
     );
     let usdc_oracle_index = ais[..]
         .iter()
@@ -142,6 +154,7 @@ pub fn new_fixed_order_account_retriever_inner<'a, 'info>(
         n_perps: active_perp_len,
         begin_perp: n_banks * 2,
         begin_serum3: n_banks * 2 + active_perp_len * 2,
+        begin_openbook_v2: n_banks * 2 + active_perp_len * 2 + active_serum3_len,
         now: Some(now),
         begin_fallback_oracles: expected_ais,
         usdc_oracle_index,
@@ -174,6 +187,32 @@ impl<T: KeyedAccountReader> FixedOrderAccountRetriever<T> {
         ))
     }
 
+    /// Like bank_and_oracle(), but with the staleness limits for `freshness`
+    pub fn bank_and_oracle_for(
+        &self,
+        group: &Pubkey,
+        active_token_position_index: usize,
+        token_index: TokenIndex,
+        freshness: OracleFreshness,
+    ) -> Result<(&Bank, I80F48)> {
+        let (bank_account_index, bank) =
+            self.bank(group, active_token_position_index, token_index)?;
+
+        let oracle_index = self.n_banks + bank_account_index;
+        let oracle_acc_infos = &self.create_oracle_infos(oracle_index, &bank.fallback_oracle);
+        let oracle_price_result = bank.oracle_price_for(oracle_acc_infos, self.now, freshness);
+        let oracle_price = oracle_price_result.with_context(|| {
+            format!(
+                "getting oracle for bank with health account index {} and token index {}, passed account {}",
+                bank_account_index,
+                token_index,
+                self.ais[oracle_index].key(),
+            )
+        })?;
+
+        Ok((bank, oracle_price))
+    }
+
     fn perp_market(
         &self,
         group: &Pubkey,
@@ -229,22 +268,12 @@ impl<T: KeyedAccountReader> AccountRetriever for FixedOrderAccountRetriever<T> {
         active_token_position_index: usize,
         token_index: TokenIndex,
     ) -> Result<(&Bank, I80F48)> {
-        let (bank_account_index, bank) =
-            self.bank(group, active_token_position_index, token_index)?;
-
-        let oracle_index = self.n_banks + bank_account_index;
-        let oracle_acc_infos = &self.create_oracle_infos(oracle_index, &bank.fallback_oracle);
-        let oracle_price_result = bank.oracle_price(oracle_acc_infos, self.now);
-        let oracle_price = oracle_price_result.with_context(|| {
-            format!(
-                "getting oracle for bank with health account index {} and token index {}, passed account {}",
-                bank_account_index,
-                token_index,
-                self.ais[oracle_index].key(),
-            )
-        })?;
-
-        Ok((bank, oracle_price))
+        self.bank_and_oracle_for(
+            group,
+            active_token_position_index,
+            token_index,
+            OracleFreshness::Health,
+        )
     }
 
     fn perp_market_and_oracle_price(
@@ -292,6 +321,26 @@ impl<T: KeyedAccountReader> AccountRetriever for FixedOrderAccountRetriever<T> {
             )
         })
     }
+
+    fn openbook_oo(
+        &self,
+        active_openbook_oo_index: usize,
+        key: &Pubkey,
+    ) -> Result<&OpenOrdersAccount> {
+        let openbook_oo_index = self.begin_openbook_v2 + active_openbook_oo_index;
+        let ai = &self.ais[openbook_oo_index];
+        (|| {
+            require_keys_eq!(*key, *ai.key());
+            openbook_v2_cpi::load_open_orders(ai)
+        })()
+        .with_context(|| {
+            format!(
+                "loading openbook open orders with health account index {}, passed account {}",
+                openbook_oo_index,
+                ai.key(),
+            )
+        })
+    }
 }
 
 pub struct ScannedBanksAndOracles<'a, 'info> {
@@ -404,6 +453,7 @@ impl<'a, 'info> ScannedBanksAndOracles<'a, 'info> {
 /// - an unknown number of PerpMarket accounts
 /// - the same number of oracles in the same order as the perp markets
 /// - an unknown number of serum3 OpenOrders accounts
+/// - an unknown number of openbook_v2 OpenOrdersAccount accounts
 /// - an unknown number of fallback oracle accounts
 /// and retrieves accounts needed for the health computation by doing a linear
 /// scan for each request.
@@ -412,6 +462,7 @@ pub struct ScanningAccountRetriever<'a, 'info> {
     perp_markets: Vec<AccountInfoRef<'a, 'info>>,
     perp_oracles: Vec<AccountInfoRef<'a, 'info>>,
     serum3_oos: Vec<AccountInfoRef<'a, 'info>>,
+    openbook_v2_oos: Vec<AccountInfoRef<'a, 'info>>,
     perp_index_map: HashMap<PerpMarketIndex, usize>,
 }
 
@@ -501,7 +552,12 @@ impl<'a, 'info> ScanningAccountRetriever<'a, 'info> {
                     && serum3_cpi::has_serum_header(&x.data.borrow())
             })
             .count();
-        let fallback_oracles_start = serum3_start + n_serum3;
+        let openbook_v2_start = serum3_start + n_serum3;
+        let n_openbook_v2 = ais[openbook_v2_start..]
+            .iter()
+            .take_while(|x| x.owner == &openbook_v2::ID)
+            .count();
+        let fallback_oracles_start = openbook_v2_start + n_openbook_v2;
         let usd_oracle_index = ais[fallback_oracles_start..]
             .iter()
             .position(|o| o.key == &pyth_mainnet_usdc_oracle::ID);
@@ -521,7 +577,10 @@ impl<'a, 'info> ScanningAccountRetriever<'a, 'info> {
             },
             perp_markets: AccountInfoRef::borrow_slice(&ais[perps_start..perp_oracles_start])?,
             perp_oracles: AccountInfoRef::borrow_slice(&ais[perp_oracles_start..serum3_start])?,
-            serum3_oos: AccountInfoRef::borrow_slice(&ais[serum3_start..fallback_oracles_start])?,
+            serum3_oos: AccountInfoRef::borrow_slice(&ais[serum3_start..openbook_v2_start])?,
+            openbook_v2_oos: AccountInfoRef::borrow_slice(
+                &ais[openbook_v2_start..fallback_oracles_start],
+            )?,
             perp_index_map,
         })
     }
@@ -571,6 +630,15 @@ impl<'a, 'info> ScanningAccountRetriever<'a, 'info> {
         serum3_cpi::load_open_orders(oo)
     }
 
+    pub fn scanned_openbook_oo(&self, key: &Pubkey) -> Result<&OpenOrdersAccount> {
+        let oo = self
+            .openbook_v2_oos
+            .iter()
+            .find(|ai| ai.key == key)
+            .ok_or_else(|| error_msg!("no openbook_v2 open orders for key {}", key))?;
+        openbook_v2_cpi::load_open_orders(oo)
+    }
+
     pub fn into_banks_and_oracles(self) -> ScannedBanksAndOracles<'a, 'info> {
         self.banks_and_oracles
     }
@@ -602,6 +670,10 @@ impl<'a, 'info> AccountRetriever for ScanningAccountRetriever<'a, 'info> {
     fn serum_oo(&self, _account_index: usize, key: &Pubkey) -> Result<&OpenOrders> {
         self.scanned_serum_oo(key)
     }
+
+    fn openbook_oo(&self, _account_index: usize, key: &Pubkey) -> Result<&OpenOrdersAccount> {
+        self.scanned_openbook_oo(key)
+    }
 }
 
 #[cfg(test)]
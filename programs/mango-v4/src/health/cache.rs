@@ -11,7 +11,8 @@
  * new_health_cache() function. With it, the different health types can be
  * computed.
  *
- * The HealthCache holds the data it needs in TokenInfo, Serum3Info and PerpInfo.
+ * The HealthCache holds the data it needs in TokenInfo, Serum3Info, OpenbookV2Info
+ * and PerpInfo.
  */
 
 use anchor_lang::prelude::*;
@@ -20,10 +21,11 @@ use fixed::types::I80F48;
 
 use crate::error::*;
 use crate::i80f48::LowPrecisionDivision;
+use crate::openbook_v2_cpi;
 use crate::serum3_cpi::{OpenOrdersAmounts, OpenOrdersSlim};
 use crate::state::{
-    Bank, MangoAccountRef, PerpMarket, PerpMarketIndex, PerpPosition, Serum3MarketIndex,
-    Serum3Orders, TokenIndex,
+    Bank, MangoAccountRef, OpenbookV2MarketIndex, OpenbookV2Orders, PerpMarket, PerpMarketIndex,
+    PerpPosition, Serum3MarketIndex, Serum3Orders, TokenIndex,
 };
 
 use super::*;
@@ -176,11 +178,27 @@ pub struct TokenInfo {
 
     /// Freely available spot balance for the token.
     ///
-    /// Includes TokenPosition and free Serum3OpenOrders balances.
-    /// Does not include perp upnl or Serum3 reserved amounts.
+    /// Includes TokenPosition and free Serum3OpenOrders and OpenbookV2 open orders balances.
+    /// Does not include perp upnl or Serum3/OpenbookV2 reserved amounts.
     pub balance_spot: I80F48,
 
     pub allow_asset_liquidation: bool,
+
+    /// Set for isolated collateral tokens, see Bank::isolated_collateral
+    pub isolated_collateral: bool,
+    pub isolated_borrow_token_indexes: [TokenIndex; 7],
+
+    /// Boosted weights, set if the account uses the token's e-mode category
+    pub emode: Option<EmodeWeights>,
+}
+
+/// Weights of an e-mode category, see EmodeCategory
+#[derive(Clone, Debug)]
+pub struct EmodeWeights {
+    pub maint_asset_weight: I80F48,
+    pub init_asset_weight: I80F48,
+    pub maint_liab_weight: I80F48,
+    pub init_liab_weight: I80F48,
 }
 
 /// Temporary value used during health computations
@@ -192,8 +210,8 @@ pub struct TokenBalance {
 
 #[derive(Clone, Default)]
 pub struct TokenMaxReserved {
-    /// The sum of serum-reserved amounts over all markets
-    pub max_serum_reserved: I80F48,
+    /// The sum of serum3- and openbook_v2-reserved amounts over all markets
+    pub max_spot_reserved: I80F48,
 }
 
 impl TokenInfo {
@@ -225,6 +243,43 @@ impl TokenInfo {
         self.liab_weight(health_type) * self.prices.liab(health_type)
     }
 
+    /// Health contribution of balance with the e-mode weights, if available.
+    ///
+    /// The e-mode weights only apply where they are better than the regular ones. The
+    /// deposit and borrow weight scaling of init weights is kept.
+    fn emode_health_contribution(
+        &self,
+        health_type: HealthType,
+        balance: I80F48,
+    ) -> Option<I80F48> {
+        let emode = self.emode.as_ref()?;
+        let weight = if balance.is_negative() {
+            let weight = match health_type {
+                HealthType::Init => {
+                    emode.init_liab_weight * self.init_scaled_liab_weight / self.init_liab_weight
+                }
+                HealthType::LiquidationEnd => emode.init_liab_weight,
+                HealthType::Maint => emode.maint_liab_weight,
+            };
+            weight.min(self.liab_weight(health_type)) * self.prices.liab(health_type)
+        } else {
+            let weight = match health_type {
+                HealthType::Init if self.init_asset_weight.is_positive() => {
+                    emode.init_asset_weight * self.init_scaled_asset_weight / self.init_asset_weight
+                }
+                HealthType::Init | HealthType::LiquidationEnd => emode.init_asset_weight,
+                HealthType::Maint => emode.maint_asset_weight,
+            };
+            weight.max(self.asset_weight(health_type)) * self.prices.asset(health_type)
+        };
+        Some(balance * weight)
+    }
+
+    /// Can isolated collateral of this token back borrows of token_index?
+    pub fn isolated_collateral_backs(&self, token_index: TokenIndex) -> bool {
+        self.isolated_borrow_token_indexes.contains(&token_index)
+    }
+
     #[inline(always)]
     pub fn health_contribution(&self, health_type: HealthType, balance: I80F48) -> I80F48 {
         let weighted_price = if balance.is_negative() {
@@ -356,7 +411,7 @@ impl Serum3Info {
     /// To be able to do that, we compute `token_max_reserved` for each token, which is the maximum
     /// token amount that would be generated if open orders in all markets that deal with the token
     /// turn its way. (in the example above: the open orders in the SOL/USDC and SOL/BTC market
-    /// both produce SOL) See `compute_serum3_reservations()` below.
+    /// both produce SOL) See `compute_spot_reservations()` below.
     #[inline(always)]
     fn health_contribution(
         &self,
@@ -364,64 +419,204 @@ impl Serum3Info {
         token_infos: &[TokenInfo],
         token_balances: &[TokenBalance],
         token_max_reserved: &[TokenMaxReserved],
-        market_reserved: &Serum3Reserved,
+        market_reserved: &SpotMarketReserved,
     ) -> I80F48 {
-        if market_reserved.all_reserved_as_base.is_zero()
-            || market_reserved.all_reserved_as_quote.is_zero()
-        {
-            return I80F48::ZERO;
+        spot_market_health_contribution(
+            self.base_info_index,
+            self.quote_info_index,
+            health_type,
+            token_infos,
+            token_balances,
+            token_max_reserved,
+            market_reserved,
+        )
+    }
+}
+
+/// Information about reserved funds on OpenbookV2 open orders accounts.
+///
+/// Works exactly like Serum3Info: free funds are added directly to the token info,
+/// this only tracks the reserved funds.
+#[derive(Clone, Debug)]
+pub struct OpenbookV2Info {
+    // reserved amounts as stored on the open orders
+    pub reserved_base: I80F48,
+    pub reserved_quote: I80F48,
+
+    // Reserved amounts, converted to the opposite token, while using the most extreme order price
+    // May be zero if the extreme bid/ask price is not available
+    pub reserved_base_as_quote_lowest_ask: I80F48,
+    pub reserved_quote_as_base_highest_bid: I80F48,
+
+    // Index into TokenInfos _not_ a TokenIndex
+    pub base_info_index: usize,
+    pub quote_info_index: usize,
+
+    pub market_index: OpenbookV2MarketIndex,
+
+    /// The open orders account has no free or reserved funds
+    pub has_zero_funds: bool,
+}
+
+impl OpenbookV2Info {
+    fn new(
+        openbook_account: &OpenbookV2Orders,
+        open_orders: &impl OpenOrdersAmounts,
+        base_info_index: usize,
+        quote_info_index: usize,
+    ) -> Self {
+        // track the reserved amounts
+        let reserved_base = I80F48::from(open_orders.native_base_reserved());
+        let reserved_quote = I80F48::from(open_orders.native_quote_reserved());
+
+        let reserved_base_as_quote_lowest_ask =
+            reserved_base * I80F48::from_num(openbook_account.lowest_placed_ask);
+        let reserved_quote_as_base_highest_bid =
+            reserved_quote * I80F48::from_num(openbook_account.highest_placed_bid_inv);
+
+        Self {
+            reserved_base,
+            reserved_quote,
+            reserved_base_as_quote_lowest_ask,
+            reserved_quote_as_base_highest_bid,
+            base_info_index,
+            quote_info_index,
+            market_index: openbook_account.market_index,
+            has_zero_funds: open_orders.native_base_total() == 0
+                && open_orders.native_quote_total() == 0
+                && open_orders.native_rebates() == 0,
         }
+    }
 
-        let base_info = &token_infos[self.base_info_index];
-        let quote_info = &token_infos[self.quote_info_index];
-
-        // How much would health increase if the reserved balance were applied to the passed
-        // token info?
-        let compute_health_effect = |token_info: &TokenInfo,
-                                     balance: &TokenBalance,
-                                     max_reserved: &TokenMaxReserved,
-                                     market_reserved: I80F48| {
-            // This balance includes all possible reserved funds from markets that relate to the
-            // token, including this market itself: `market_reserved` is already included in `max_serum_reserved`.
-            let max_balance = balance.spot_and_perp + max_reserved.max_serum_reserved;
-
-            // For simplicity, we assume that `market_reserved` was added to `max_balance` last
-            // (it underestimates health because that gives the smallest effects): how much did
-            // health change because of it?
-            let (asset_part, liab_part) = if max_balance >= market_reserved {
-                (market_reserved, I80F48::ZERO)
-            } else if max_balance.is_negative() {
-                (I80F48::ZERO, market_reserved)
-            } else {
-                (max_balance, market_reserved - max_balance)
-            };
+    #[inline(always)]
+    fn all_reserved_as_base(
+        &self,
+        health_type: HealthType,
+        quote_info: &TokenInfo,
+        base_info: &TokenInfo,
+    ) -> I80F48 {
+        let quote_asset = quote_info.prices.asset(health_type);
+        let base_liab = base_info.prices.liab(health_type);
+        let reserved_quote_as_base_oracle = (self.reserved_quote * quote_asset)
+            .checked_div_f64_precision(base_liab)
+            .unwrap();
+        if self.reserved_quote_as_base_highest_bid != 0 {
+            self.reserved_base
+                + reserved_quote_as_base_oracle.min(self.reserved_quote_as_base_highest_bid)
+        } else {
+            self.reserved_base + reserved_quote_as_base_oracle
+        }
+    }
 
-            let asset_weight = token_info.asset_weight(health_type);
-            let liab_weight = token_info.liab_weight(health_type);
-            let asset_price = token_info.prices.asset(health_type);
-            let liab_price = token_info.prices.liab(health_type);
-            asset_part * asset_weight * asset_price + liab_part * liab_weight * liab_price
-        };
+    #[inline(always)]
+    fn all_reserved_as_quote(
+        &self,
+        health_type: HealthType,
+        quote_info: &TokenInfo,
+        base_info: &TokenInfo,
+    ) -> I80F48 {
+        let base_asset = base_info.prices.asset(health_type);
+        let quote_liab = quote_info.prices.liab(health_type);
+        let reserved_base_as_quote_oracle = (self.reserved_base * base_asset)
+            .checked_div_f64_precision(quote_liab)
+            .unwrap();
+        if self.reserved_base_as_quote_lowest_ask != 0 {
+            self.reserved_quote
+                + reserved_base_as_quote_oracle.min(self.reserved_base_as_quote_lowest_ask)
+        } else {
+            self.reserved_quote + reserved_base_as_quote_oracle
+        }
+    }
 
-        let health_base = compute_health_effect(
-            base_info,
-            &token_balances[self.base_info_index],
-            &token_max_reserved[self.base_info_index],
-            market_reserved.all_reserved_as_base,
-        );
-        let health_quote = compute_health_effect(
-            quote_info,
-            &token_balances[self.quote_info_index],
-            &token_max_reserved[self.quote_info_index],
-            market_reserved.all_reserved_as_quote,
-        );
-        health_base.min(health_quote)
+    /// Compute the health contribution from active open orders, see Serum3Info.
+    #[inline(always)]
+    fn health_contribution(
+        &self,
+        health_type: HealthType,
+        token_infos: &[TokenInfo],
+        token_balances: &[TokenBalance],
+        token_max_reserved: &[TokenMaxReserved],
+        market_reserved: &SpotMarketReserved,
+    ) -> I80F48 {
+        spot_market_health_contribution(
+            self.base_info_index,
+            self.quote_info_index,
+            health_type,
+            token_infos,
+            token_balances,
+            token_max_reserved,
+            market_reserved,
+        )
     }
 }
 
+/// Health contribution of the reserved funds of a serum3 or openbook_v2 market,
+/// see Serum3Info::health_contribution().
+#[inline(always)]
+fn spot_market_health_contribution(
+    base_info_index: usize,
+    quote_info_index: usize,
+    health_type: HealthType,
+    token_infos: &[TokenInfo],
+    token_balances: &[TokenBalance],
+    token_max_reserved: &[TokenMaxReserved],
+    market_reserved: &SpotMarketReserved,
+) -> I80F48 {
+    if market_reserved.all_reserved_as_base.is_zero()
+        || market_reserved.all_reserved_as_quote.is_zero()
+    {
+        return I80F48::ZERO;
+    }
+
+    let base_info = &token_infos[base_info_index];
+    let quote_info = &token_infos[quote_info_index];
+
+    // How much would health increase if the reserved balance were applied to the passed
+    // token info?
+    let compute_health_effect = |token_info: &TokenInfo,
+                                 balance: &TokenBalance,
+                                 max_reserved: &TokenMaxReserved,
+                                 market_reserved: I80F48| {
+        // This balance includes all possible reserved funds from markets that relate to the
+        // token, including this market itself: `market_reserved` is already included in `max_spot_reserved`.
+        let max_balance = balance.spot_and_perp + max_reserved.max_spot_reserved;
+
+        // For simplicity, we assume that `market_reserved` was added to `max_balance` last
+        // (it underestimates health because that gives the smallest effects): how much did
+        // health change because of it?
+        let (asset_part, liab_part) = if max_balance >= market_reserved {
+            (market_reserved, I80F48::ZERO)
+        } else if max_balance.is_negative() {
+            (I80F48::ZERO, market_reserved)
+        } else {
+            (max_balance, market_reserved - max_balance)
+        };
+
+        let asset_weight = token_info.asset_weight(health_type);
+        let liab_weight = token_info.liab_weight(health_type);
+        let asset_price = token_info.prices.asset(health_type);
+        let liab_price = token_info.prices.liab(health_type);
+        asset_part * asset_weight * asset_price + liab_part * liab_weight * liab_price
+    };
+
+    let health_base = compute_health_effect(
+        base_info,
+        &token_balances[base_info_index],
+        &token_max_reserved[base_info_index],
+        market_reserved.all_reserved_as_base,
+    );
+    let health_quote = compute_health_effect(
+        quote_info,
+        &token_balances[quote_info_index],
+        &token_max_reserved[quote_info_index],
+        market_reserved.all_reserved_as_quote,
+    );
+    health_base.min(health_quote)
+}
+
 #[derive(Clone)]
-pub(crate) struct Serum3Reserved {
-    /// base tokens when the serum3info.reserved_quote get converted to base and added to reserved_base
+pub(crate) struct SpotMarketReserved {
+    /// base tokens when the info.reserved_quote get converted to base and added to reserved_base
     all_reserved_as_base: I80F48,
     /// ditto the other way around
     all_reserved_as_quote: I80F48,
@@ -450,6 +645,15 @@ pub struct PerpInfo {
     pub base_prices: Prices,
     pub has_open_orders: bool,
     pub has_open_fills: bool,
+    /// Spot token that offsets this perp's base exposure for portfolio margin accounts
+    pub portfolio_margin_token_index: Option<TokenIndex>,
+    /// Isolated positions only use their own collateral, see HealthCache::isolated_perp_health()
+    pub isolated: bool,
+    /// Settle token native allocated to an isolated position
+    pub isolated_collateral: I80F48,
+    /// Init health of the isolated bucket when the health cache was created,
+    /// see HealthCache::check_isolated_perp_health_post()
+    pub isolated_init_health_pre: I80F48,
 }
 
 impl PerpInfo {
@@ -481,9 +685,29 @@ impl PerpInfo {
             base_prices,
             has_open_orders: perp_position.has_open_orders(),
             has_open_fills: perp_position.has_open_taker_fills(),
+            portfolio_margin_token_index: None,
+            isolated: perp_position.is_isolated(),
+            isolated_collateral: perp_position.isolated_collateral_native,
+            isolated_init_health_pre: I80F48::ZERO,
         })
     }
 
+    #[inline(always)]
+    fn base_asset_weight(&self, health_type: HealthType) -> I80F48 {
+        match health_type {
+            HealthType::Init | HealthType::LiquidationEnd => self.init_base_asset_weight,
+            HealthType::Maint => self.maint_base_asset_weight,
+        }
+    }
+
+    #[inline(always)]
+    fn base_liab_weight(&self, health_type: HealthType) -> I80F48 {
+        match health_type {
+            HealthType::Init | HealthType::LiquidationEnd => self.init_base_liab_weight,
+            HealthType::Maint => self.maint_base_liab_weight,
+        }
+    }
+
     /// The perp-risk (but not token-risk) adjusted upnl. Also called "hupnl".
     ///
     /// In settle token native units.
@@ -599,6 +823,7 @@ pub struct HealthCache {
     pub token_infos: Vec<TokenInfo>,
     pub(crate) serum3_infos: Vec<Serum3Info>,
     pub(crate) perp_infos: Vec<PerpInfo>,
+    pub(crate) openbook_v2_infos: Vec<OpenbookV2Info>,
     #[allow(unused)]
     pub(crate) being_liquidated: bool,
 }
@@ -670,6 +895,7 @@ impl HealthCache {
     ) -> (I80F48, I80F48) {
         let mut total_assets = I80F48::ZERO;
         let mut total_liabs = I80F48::ZERO;
+        let portfolio_margin_hedges = self.portfolio_margin_hedges();
         let add = |assets: &mut I80F48, liabs: &mut I80F48, value: I80F48| {
             if value > 0 {
                 *assets += value;
@@ -693,11 +919,24 @@ impl HealthCache {
                 token_info.balance_spot,
             );
 
-            for perp_info in self.perp_infos.iter() {
+            for (perp_info, hedge) in self.perp_infos.iter().zip(portfolio_margin_hedges.iter()) {
                 if perp_info.settle_token_index != token_info.token_index {
                     continue;
                 }
-                let health_unsettled = perp_info.health_unsettled_pnl(health_type);
+                if perp_info.isolated {
+                    // Same as in effective_token_balances()
+                    add(
+                        &mut asset_balance,
+                        &mut liab_balance,
+                        -perp_info.isolated_collateral,
+                    );
+                    continue;
+                }
+                let health_unsettled = Self::portfolio_margin_health_unsettled_pnl(
+                    perp_info,
+                    hedge.as_ref(),
+                    health_type,
+                );
                 add(&mut asset_balance, &mut liab_balance, health_unsettled);
             }
 
@@ -732,7 +971,8 @@ impl HealthCache {
         }
 
         let token_balances = self.effective_token_balances(health_type);
-        let (token_max_reserved, serum3_reserved) = self.compute_serum3_reservations(health_type);
+        let (token_max_reserved, serum3_reserved, openbook_v2_reserved) =
+            self.compute_spot_reservations(health_type);
         for (serum3_info, reserved) in self.serum3_infos.iter().zip(serum3_reserved.iter()) {
             let contrib = serum3_info.health_contribution(
                 health_type,
@@ -743,6 +983,33 @@ impl HealthCache {
             );
             add(&mut total_assets, &mut total_liabs, contrib);
         }
+        for (openbook_v2_info, reserved) in self
+            .openbook_v2_infos
+            .iter()
+            .zip(openbook_v2_reserved.iter())
+        {
+            let contrib = openbook_v2_info.health_contribution(
+                health_type,
+                &self.token_infos,
+                &token_balances,
+                &token_max_reserved,
+                reserved,
+            );
+            add(&mut total_assets, &mut total_liabs, contrib);
+        }
+
+        let isolated_adjustment = self.isolated_collateral_adjustment(health_type, &token_balances);
+        add(&mut total_assets, &mut total_liabs, isolated_adjustment);
+        add(
+            &mut total_assets,
+            &mut total_liabs,
+            self.portfolio_margin_spot_credit(health_type, &token_balances),
+        );
+        add(
+            &mut total_assets,
+            &mut total_liabs,
+            self.emode_credit(health_type, &token_balances),
+        );
 
         (total_assets, total_liabs)
     }
@@ -772,6 +1039,13 @@ impl HealthCache {
             assets += serum_info.reserved_quote * quote.prices.oracle;
         }
 
+        for openbook_info in self.openbook_v2_infos.iter() {
+            let quote = &self.token_infos[openbook_info.quote_info_index];
+            let base = &self.token_infos[openbook_info.base_info_index];
+            assets += openbook_info.reserved_base * base.prices.oracle;
+            assets += openbook_info.reserved_quote * quote.prices.oracle;
+        }
+
         for perp_info in self.perp_infos.iter() {
             let quote_price = self.token_infos[perp_info.settle_token_index as usize]
                 .prices
@@ -904,6 +1178,48 @@ impl HealthCache {
         Ok(())
     }
 
+    /// Recompute the cached information about an openbook_v2 market.
+    ///
+    /// WARNING: You must also call recompute_token_weights() after all bank
+    /// deposit/withdraw changes!
+    pub fn recompute_openbook_v2_info(
+        &mut self,
+        openbook_account: &OpenbookV2Orders,
+        open_orders: &openbook_v2_cpi::OpenOrdersSlim,
+        free_base_change: I80F48,
+        free_quote_change: I80F48,
+    ) -> Result<()> {
+        let openbook_info_index = self
+            .openbook_v2_infos
+            .iter_mut()
+            .position(|m| m.market_index == openbook_account.market_index)
+            .ok_or_else(|| {
+                error_msg!(
+                    "openbook_v2 market {} not found",
+                    openbook_account.market_index
+                )
+            })?;
+
+        let openbook_info = &self.openbook_v2_infos[openbook_info_index];
+        {
+            let base_entry = &mut self.token_infos[openbook_info.base_info_index];
+            base_entry.balance_spot += free_base_change;
+        }
+        {
+            let quote_entry = &mut self.token_infos[openbook_info.quote_info_index];
+            quote_entry.balance_spot += free_quote_change;
+        }
+
+        let openbook_info = &mut self.openbook_v2_infos[openbook_info_index];
+        *openbook_info = OpenbookV2Info::new(
+            openbook_account,
+            open_orders,
+            openbook_info.base_info_index,
+            openbook_info.quote_info_index,
+        );
+        Ok(())
+    }
+
     pub fn recompute_perp_info(
         &mut self,
         perp_position: &PerpPosition,
@@ -914,74 +1230,483 @@ impl HealthCache {
             .iter_mut()
             .find(|m| m.perp_market_index == perp_market.perp_market_index)
             .ok_or_else(|| error_msg!("perp market {} not found", perp_market.perp_market_index))?;
-        *perp_entry = PerpInfo::new(perp_position, perp_market, perp_entry.base_prices.clone())?;
+        let old_entry = perp_entry.clone();
+        *perp_entry = PerpInfo::new(perp_position, perp_market, old_entry.base_prices)?;
+        perp_entry.portfolio_margin_token_index = old_entry.portfolio_margin_token_index;
+        // Bucket caches treat their isolated perp as cross, see isolated_perp_bucket_cache()
+        perp_entry.isolated = old_entry.isolated;
+        perp_entry.isolated_init_health_pre = old_entry.isolated_init_health_pre;
         Ok(())
     }
 
-    /// Liquidatable spot assets mean: actual token deposits and also a positive effective token balance
-    /// and is available for asset liquidation
-    pub fn has_liq_spot_assets(&self) -> bool {
-        let health_token_balances = self.effective_token_balances(HealthType::LiquidationEnd);
-        self.token_infos
-            .iter()
-            .zip(health_token_balances.iter())
-            .any(|(ti, b)| {
-                // need 1 native token to use token_liq_with_token
-                ti.balance_spot >= 1 && b.spot_and_perp >= 1 && ti.allow_asset_liquidation
-            })
-    }
-
-    /// Liquidatable spot borrows mean: actual token borrows plus a negative effective token balance
-    pub fn has_liq_spot_borrows(&self) -> bool {
-        let health_token_balances = self.effective_token_balances(HealthType::LiquidationEnd);
-        self.token_infos
+    /// Returns the portfolio margin hedge of each perp info, if there is one.
+    ///
+    /// An entry is (token info index, hedged spot native, hedged perp base native), where
+    /// both amounts are positive and correspond to the same oracle value.
+    ///
+    /// The spot balance of a token is used up by the perp positions in order, so several
+    /// perp markets linked to the same token can't each offset the full balance.
+    fn portfolio_margin_hedges(&self) -> Vec<Option<(usize, I80F48, I80F48)>> {
+        let mut remaining_spot: Vec<I80F48> =
+            self.token_infos.iter().map(|ti| ti.balance_spot).collect();
+        self.perp_infos
             .iter()
-            .zip(health_token_balances.iter())
-            .any(|(ti, b)| ti.balance_spot < 0 && b.spot_and_perp < 0)
-    }
+            .map(|perp_info| {
+                if perp_info.isolated {
+                    return None;
+                }
+                let token_index = perp_info.portfolio_margin_token_index?;
+                let token_info_index = self.token_info_index(token_index).ok()?;
+                let token_info = &self.token_infos[token_info_index];
+                let spot = remaining_spot[token_info_index];
+
+                let spot_value = spot * token_info.prices.oracle;
+                let base_native = I80F48::from(perp_info.base_lots * perp_info.base_lot_size);
+                let perp_value = base_native * perp_info.base_prices.oracle;
+                if spot_value.is_zero()
+                    || perp_value.is_zero()
+                    || spot_value.is_negative() == perp_value.is_negative()
+                {
+                    return None;
+                }
 
-    // This function exists separately from has_liq_spot_assets and has_liq_spot_borrows for performance reasons
-    pub fn has_possible_spot_liquidations(&self) -> bool {
-        let health_token_balances = self.effective_token_balances(HealthType::LiquidationEnd);
-        let all_iter = || self.token_infos.iter().zip(health_token_balances.iter());
-        all_iter().any(|(ti, b)| ti.balance_spot < 0 && b.spot_and_perp < 0)
-            && all_iter().any(|(ti, b)| {
-                ti.balance_spot >= 1 && b.spot_and_perp >= 1 && ti.allow_asset_liquidation
+                let hedged_value = spot_value.abs().min(perp_value.abs());
+                let hedged_spot = (hedged_value / token_info.prices.oracle).min(spot.abs());
+                let hedged_base =
+                    (hedged_value / perp_info.base_prices.oracle).min(base_native.abs());
+                remaining_spot[token_info_index] -= hedged_spot * spot.signum();
+                Some((token_info_index, hedged_spot, hedged_base))
             })
+            .collect()
     }
 
-    pub fn has_serum3_open_orders_funds(&self) -> bool {
-        self.serum3_infos.iter().any(|si| !si.has_zero_funds)
-    }
-
-    pub fn has_perp_open_orders(&self) -> bool {
-        self.perp_infos.iter().any(|p| p.has_open_orders)
-    }
-
-    pub fn has_perp_base_positions(&self) -> bool {
-        self.perp_infos.iter().any(|p| p.base_lots != 0)
+    /// The perp info's hupnl, where the base weight of lots hedged by portfolio margin
+    /// is replaced by 1 before the overall asset weight is applied.
+    fn portfolio_margin_health_unsettled_pnl(
+        perp_info: &PerpInfo,
+        hedge: Option<&(usize, I80F48, I80F48)>,
+        health_type: HealthType,
+    ) -> I80F48 {
+        let unweighted = perp_info.unweighted_health_unsettled_pnl(health_type);
+        let hedged_base = match hedge {
+            Some((_, _, hedged_base)) => *hedged_base,
+            None => return perp_info.weigh_uhupnl_overall(unweighted, health_type),
+        };
+        let credit = if perp_info.base_lots < 0 {
+            hedged_base
+                * (perp_info.base_liab_weight(health_type) - I80F48::ONE)
+                * perp_info.base_prices.liab(health_type)
+        } else {
+            hedged_base
+                * (I80F48::ONE - perp_info.base_asset_weight(health_type))
+                * perp_info.base_prices.asset(health_type)
+        };
+        perp_info.weigh_uhupnl_overall(unweighted + credit.max(I80F48::ZERO), health_type)
     }
 
-    pub fn has_perp_open_fills(&self) -> bool {
-        self.perp_infos.iter().any(|p| p.has_open_fills)
-    }
+    /// Health credit from replacing the weights of hedged spot balances by 1 on portfolio
+    /// margin accounts.
+    ///
+    /// The perp side of the hedge is credited in effective_token_balances(), so the perp
+    /// overall asset weight and the settle token weights still apply to it.
+    pub(crate) fn portfolio_margin_spot_credit(
+        &self,
+        health_type: HealthType,
+        token_balances: &[TokenBalance],
+    ) -> I80F48 {
+        let mut hedged_spot = vec![I80F48::ZERO; self.token_infos.len()];
+        for (token_info_index, spot, _) in self.portfolio_margin_hedges().into_iter().flatten() {
+            hedged_spot[token_info_index] += spot;
+        }
 
-    pub fn has_perp_positive_pnl_no_base(&self) -> bool {
-        self.perp_infos
+        let mut credit = I80F48::ZERO;
+        for ((token_info, balance), hedged) in self
+            .token_infos
             .iter()
-            .any(|p| p.base_lots == 0 && p.quote > 0)
+            .zip(token_balances.iter())
+            .zip(hedged_spot.into_iter())
+        {
+            let balance = balance.spot_and_perp;
+            // Perp pnl in the same token may have reduced or flipped the balance
+            if hedged.is_zero() || balance.is_negative() != token_info.balance_spot.is_negative() {
+                continue;
+            }
+            let hedged = hedged.min(balance.abs());
+            let token_credit = if balance.is_negative() {
+                hedged
+                    * (token_info.liab_weighted_price(health_type)
+                        - token_info.prices.liab(health_type))
+            } else {
+                hedged
+                    * (token_info.prices.asset(health_type)
+                        - token_info.asset_weighted_price(health_type))
+            };
+            credit += token_credit.max(I80F48::ZERO);
+        }
+        credit
     }
 
-    pub fn has_perp_negative_pnl_no_base(&self) -> bool {
-        self.perp_infos
-            .iter()
-            .any(|p| p.base_lots == 0 && p.quote < 0)
+    /// Is all of the account's risk in its e-mode category?
+    ///
+    /// That's the case when it has balances in the category, all liabilities and all spot
+    /// markets with open orders use the category and there are no perp base positions or
+    /// perp orders.
+    ///
+    /// Deposits in other tokens don't matter, they keep their regular weights. Anyone can
+    /// deposit into an account, so they must not be able to end e-mode. Liabilities of
+    /// less than one native token are ignored as dust.
+    pub fn is_in_emode(&self, token_balances: &[TokenBalance]) -> bool {
+        let token_in_emode = |index: usize| self.token_infos[index].emode.is_some();
+        let mut any_emode = false;
+        for (i, balance) in token_balances.iter().enumerate() {
+            if balance.spot_and_perp.is_zero() {
+                continue;
+            }
+            if token_in_emode(i) {
+                any_emode = true;
+            } else if balance.spot_and_perp < -I80F48::ONE {
+                return false;
+            }
+        }
+        any_emode
+            && self.serum3_infos.iter().all(|info| {
+                token_in_emode(info.base_info_index) && token_in_emode(info.quote_info_index)
+            })
+            && self.openbook_v2_infos.iter().all(|info| {
+                token_in_emode(info.base_info_index) && token_in_emode(info.quote_info_index)
+            })
+            && !self.has_perp_base_positions()
+            && !self.has_perp_open_orders()
     }
 
-    /// Phase1 is spot/perp order cancellation and spot settlement since
+    /// Health credit from using the boosted e-mode weights, see is_in_emode().
+    ///
+    /// Only token balances get the boosted weights, reserved spot market funds keep the
+    /// regular weights.
+    ///
+    /// Liabilities only get the boosted weights for the part that deposits in the category
+    /// back. Deposits outside the category back the rest at the regular liability weights.
+    pub(crate) fn emode_credit(
+        &self,
+        health_type: HealthType,
+        token_balances: &[TokenBalance],
+    ) -> I80F48 {
+        if !self.is_in_emode(token_balances) {
+            return I80F48::ZERO;
+        }
+        let mut asset_credit = I80F48::ZERO;
+        let mut liab_credit = I80F48::ZERO;
+        let mut emode_assets = I80F48::ZERO;
+        let mut emode_liabs = I80F48::ZERO;
+        for (token_info, balance) in self.token_infos.iter().zip(token_balances.iter()) {
+            let balance = balance.spot_and_perp;
+            if let Some(emode_contrib) = token_info.emode_health_contribution(health_type, balance)
+            {
+                let contrib = token_info.health_contribution(health_type, balance);
+                let credit = (emode_contrib - contrib).max(I80F48::ZERO);
+                if balance.is_negative() {
+                    liab_credit += credit;
+                    emode_liabs -= emode_contrib;
+                } else {
+                    asset_credit += credit;
+                    emode_assets += emode_contrib;
+                }
+            }
+        }
+        if emode_liabs > emode_assets {
+            liab_credit = liab_credit * emode_assets / emode_liabs;
+        }
+        asset_credit + liab_credit
+    }
+
+    /// Removes the health contribution of isolated collateral that exceeds what it may back.
+    ///
+    /// Isolated collateral only backs borrows of the tokens on its allow-list. Each borrow
+    /// is backed at most once. The returned value is never positive.
+    ///
+    /// The total debt backed by a token is limited separately, see update_isolated_debt().
+    pub(crate) fn isolated_collateral_adjustment(
+        &self,
+        health_type: HealthType,
+        token_balances: &[TokenBalance],
+    ) -> I80F48 {
+        -self
+            .isolated_collateral_backing(health_type, token_balances)
+            .iter()
+            .map(|(_, contrib, backed)| contrib - backed)
+            .sum::<I80F48>()
+    }
+
+    /// Init-health-weighted borrows, in native quote, that each isolated collateral token
+    /// of the account backs.
+    pub fn isolated_debt_backed(&self) -> Vec<(TokenIndex, I80F48)> {
+        let token_balances = self.effective_token_balances(HealthType::Init);
+        self.isolated_collateral_backing(HealthType::Init, &token_balances)
+            .into_iter()
+            .map(|(token_index, _, backed)| (token_index, backed))
+            .collect()
+    }
+
+    /// Returns (token_index, health contribution, backed borrows) for each isolated
+    /// collateral token with a positive balance.
+    fn isolated_collateral_backing(
+        &self,
+        health_type: HealthType,
+        token_balances: &[TokenBalance],
+    ) -> Vec<(TokenIndex, I80F48, I80F48)> {
+        if !self.token_infos.iter().any(|ti| ti.isolated_collateral) {
+            return vec![];
+        }
+
+        // Weighted borrows that are still available to be backed by isolated collateral
+        let mut backable: Vec<I80F48> = self
+            .token_infos
+            .iter()
+            .zip(token_balances.iter())
+            .map(|(token_info, balance)| {
+                if balance.spot_and_perp.is_negative() {
+                    -token_info.health_contribution(health_type, balance.spot_and_perp)
+                } else {
+                    I80F48::ZERO
+                }
+            })
+            .collect();
+
+        let mut backing = vec![];
+        for (token_info, balance) in self.token_infos.iter().zip(token_balances.iter()) {
+            if !token_info.isolated_collateral || !balance.spot_and_perp.is_positive() {
+                continue;
+            }
+            let contrib = token_info.health_contribution(health_type, balance.spot_and_perp);
+            let mut backed = I80F48::ZERO;
+            for (borrow_info, borrow_backable) in self.token_infos.iter().zip(backable.iter_mut()) {
+                if !token_info.isolated_collateral_backs(borrow_info.token_index) {
+                    continue;
+                }
+                let amount = (*borrow_backable).min(contrib - backed);
+                backed += amount;
+                *borrow_backable -= amount;
+            }
+            backing.push((token_info.token_index, contrib, backed));
+        }
+        backing
+    }
+
+    /// Errors if the account holds isolated collateral that may not back borrows of token_index.
+    ///
+    /// Accounts with isolated collateral are restricted to borrowing the allow-listed tokens.
+    pub fn check_isolated_collateral_allows_borrow(&self, token_index: TokenIndex) -> Result<()> {
+        for token_info in self.token_infos.iter() {
+            if token_info.isolated_collateral
+                && token_info.balance_spot.is_positive()
+                && !token_info.isolated_collateral_backs(token_index)
+            {
+                return Err(error_msg_typed!(
+                    MangoError::IsolatedCollateralBorrowNotAllowed,
+                    "account holds isolated collateral token {}, which cannot back borrows of token {}",
+                    token_info.token_index,
+                    token_index
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Perp base lots that are not offset by a portfolio margin spot position.
+    ///
+    /// Liquidation reduces these first, since reducing hedged lots also removes health credit.
+    pub fn portfolio_margin_unhedged_base_lots(
+        &self,
+        perp_market_index: PerpMarketIndex,
+    ) -> Result<i64> {
+        let perp_info_index = self.perp_info_index(perp_market_index)?;
+        let perp_info = &self.perp_infos[perp_info_index];
+        let hedged_lots = match self.portfolio_margin_hedges()[perp_info_index] {
+            Some((_, _, hedged_base)) => (hedged_base / I80F48::from(perp_info.base_lot_size))
+                .floor()
+                .to_num::<i64>(),
+            None => 0,
+        };
+        Ok(perp_info.base_lots - perp_info.base_lots.signum() * hedged_lots)
+    }
+
+    /// Health of an isolated perp position's bucket.
+    ///
+    /// The bucket consists of the position's allocated collateral and its hupnl, valued like
+    /// a balance of the settle token. It doesn't contribute to account health: a negative
+    /// bucket is liquidated by itself, see isolated_perp_bucket_cache().
+    fn isolated_perp_bucket_health(&self, health_type: HealthType, perp_info: &PerpInfo) -> I80F48 {
+        let balance = perp_info.isolated_collateral + perp_info.health_unsettled_pnl(health_type);
+        // The settle token is always available, see new_health_cache_impl()
+        let settle_token_info = self.token_info(perp_info.settle_token_index).unwrap();
+        settle_token_info.health_contribution(health_type, balance)
+    }
+
+    /// Health of an isolated perp position, see isolated_perp_bucket_health()
+    pub fn isolated_perp_health(
+        &self,
+        health_type: HealthType,
+        perp_market_index: PerpMarketIndex,
+    ) -> Result<I80F48> {
+        let perp_info = self.perp_info(perp_market_index)?;
+        require!(perp_info.isolated, MangoError::PerpPositionNotIsolated);
+        Ok(self.isolated_perp_bucket_health(health_type, perp_info))
+    }
+
+    /// Whether any isolated perp position has negative health.
+    pub fn has_unhealthy_isolated_perp(&self, health_type: HealthType) -> bool {
+        self.perp_infos
+            .iter()
+            .filter(|perp_info| perp_info.isolated)
+            .any(|perp_info| self.isolated_perp_bucket_health(health_type, perp_info) < 0)
+    }
+
+    /// Errors if an action left an isolated perp bucket with negative init health and
+    /// decreased it, compared to when the health cache was created.
+    pub fn check_isolated_perp_health_post(&self) -> Result<()> {
+        for perp_info in self
+            .perp_infos
+            .iter()
+            .filter(|perp_info| perp_info.isolated)
+        {
+            let post = self.isolated_perp_bucket_health(HealthType::Init, perp_info);
+            require_msg_typed!(
+                post >= 0 || post >= perp_info.isolated_init_health_pre,
+                MangoError::IsolatedPerpHealthMustBePositiveOrIncrease,
+                "isolated perp {} init health {} is negative and decreased from {}",
+                perp_info.perp_market_index,
+                post,
+                perp_info.isolated_init_health_pre
+            );
+        }
+        Ok(())
+    }
+
+    /// Settle token native held as collateral by isolated perp positions.
+    ///
+    /// It's part of the token balance but may only be used by the positions.
+    pub fn isolated_perp_collateral(&self, token_index: TokenIndex) -> I80F48 {
+        self.perp_infos
+            .iter()
+            .filter(|perp_info| perp_info.isolated && perp_info.settle_token_index == token_index)
+            .map(|perp_info| perp_info.isolated_collateral)
+            .sum()
+    }
+
+    /// Health cache of an isolated perp position's bucket on its own.
+    ///
+    /// It holds only the settle token, with the position's collateral as its balance, and the
+    /// position as if it were cross margin. Liquidation and settlement of the position use it,
+    /// so that the bucket can never lose more than its collateral.
+    pub fn isolated_perp_bucket_cache(
+        &self,
+        perp_market_index: PerpMarketIndex,
+    ) -> Result<HealthCache> {
+        let perp_info = self.perp_info(perp_market_index)?;
+        require!(perp_info.isolated, MangoError::PerpPositionNotIsolated);
+
+        let mut settle_token_info = self.token_info(perp_info.settle_token_index)?.clone();
+        settle_token_info.balance_spot = perp_info.isolated_collateral;
+        settle_token_info.isolated_collateral = false;
+
+        let mut perp_info = perp_info.clone();
+        perp_info.isolated = false;
+        perp_info.portfolio_margin_token_index = None;
+
+        Ok(HealthCache {
+            token_infos: vec![settle_token_info],
+            serum3_infos: vec![],
+            perp_infos: vec![perp_info],
+            openbook_v2_infos: vec![],
+            being_liquidated: false,
+        })
+    }
+
+    /// Native spot amount of a token that offsets perp positions on a portfolio margin account.
+    pub fn portfolio_margin_hedged_spot(&self, token_index: TokenIndex) -> I80F48 {
+        self.portfolio_margin_hedges()
+            .into_iter()
+            .flatten()
+            .filter(|(token_info_index, _, _)| {
+                self.token_infos[*token_info_index].token_index == token_index
+            })
+            .map(|(_, hedged_spot, _)| hedged_spot)
+            .sum()
+    }
+
+    /// Liquidatable spot assets mean: actual token deposits and also a positive effective token balance
+    /// and is available for asset liquidation
+    pub fn has_liq_spot_assets(&self) -> bool {
+        let health_token_balances = self.effective_token_balances(HealthType::LiquidationEnd);
+        self.token_infos
+            .iter()
+            .zip(health_token_balances.iter())
+            .any(|(ti, b)| {
+                // need 1 native token to use token_liq_with_token
+                ti.balance_spot >= 1 && b.spot_and_perp >= 1 && ti.allow_asset_liquidation
+            })
+    }
+
+    /// Liquidatable spot borrows mean: actual token borrows plus a negative effective token balance
+    pub fn has_liq_spot_borrows(&self) -> bool {
+        let health_token_balances = self.effective_token_balances(HealthType::LiquidationEnd);
+        self.token_infos
+            .iter()
+            .zip(health_token_balances.iter())
+            .any(|(ti, b)| ti.balance_spot < 0 && b.spot_and_perp < 0)
+    }
+
+    // This function exists separately from has_liq_spot_assets and has_liq_spot_borrows for performance reasons
+    pub fn has_possible_spot_liquidations(&self) -> bool {
+        let health_token_balances = self.effective_token_balances(HealthType::LiquidationEnd);
+        let all_iter = || self.token_infos.iter().zip(health_token_balances.iter());
+        all_iter().any(|(ti, b)| ti.balance_spot < 0 && b.spot_and_perp < 0)
+            && all_iter().any(|(ti, b)| {
+                ti.balance_spot >= 1 && b.spot_and_perp >= 1 && ti.allow_asset_liquidation
+            })
+    }
+
+    pub fn has_serum3_open_orders_funds(&self) -> bool {
+        self.serum3_infos.iter().any(|si| !si.has_zero_funds)
+    }
+
+    pub fn has_openbook_v2_open_orders_funds(&self) -> bool {
+        self.openbook_v2_infos.iter().any(|oi| !oi.has_zero_funds)
+    }
+
+    /// Isolated perp positions are liquidated separately, see isolated_perp_bucket_cache()
+    fn cross_perp_infos(&self) -> impl Iterator<Item = &PerpInfo> {
+        self.perp_infos.iter().filter(|p| !p.isolated)
+    }
+
+    pub fn has_perp_open_orders(&self) -> bool {
+        self.cross_perp_infos().any(|p| p.has_open_orders)
+    }
+
+    pub fn has_perp_base_positions(&self) -> bool {
+        self.cross_perp_infos().any(|p| p.base_lots != 0)
+    }
+
+    pub fn has_perp_open_fills(&self) -> bool {
+        self.cross_perp_infos().any(|p| p.has_open_fills)
+    }
+
+    pub fn has_perp_positive_pnl_no_base(&self) -> bool {
+        self.cross_perp_infos()
+            .any(|p| p.base_lots == 0 && p.quote > 0)
+    }
+
+    pub fn has_perp_negative_pnl_no_base(&self) -> bool {
+        self.cross_perp_infos()
+            .any(|p| p.base_lots == 0 && p.quote < 0)
+    }
+
+    /// Phase1 is spot/perp order cancellation and spot settlement since
     /// neither of these come at a cost to the liqee
     pub fn has_phase1_liquidatable(&self) -> bool {
-        self.has_serum3_open_orders_funds() || self.has_perp_open_orders()
+        self.has_serum3_open_orders_funds()
+            || self.has_openbook_v2_open_orders_funds()
+            || self.has_perp_open_orders()
     }
 
     pub fn require_after_phase1_liquidation(&self) -> Result<()> {
@@ -989,6 +1714,10 @@ impl HealthCache {
             !self.has_serum3_open_orders_funds(),
             MangoError::HasOpenOrUnsettledSerum3Orders
         );
+        require!(
+            !self.has_openbook_v2_open_orders_funds(),
+            MangoError::HasOpenOrUnsettledOpenbookV2Orders
+        );
         require!(!self.has_perp_open_orders(), MangoError::HasOpenPerpOrders);
         Ok(())
     }
@@ -1047,10 +1776,15 @@ impl HealthCache {
             && self.has_phase3_liquidatable()
     }
 
-    pub(crate) fn compute_serum3_reservations(
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn compute_spot_reservations(
         &self,
         health_type: HealthType,
-    ) -> (Vec<TokenMaxReserved>, Vec<Serum3Reserved>) {
+    ) -> (
+        Vec<TokenMaxReserved>,
+        Vec<SpotMarketReserved>,
+        Vec<SpotMarketReserved>,
+    ) {
         let mut token_max_reserved = vec![TokenMaxReserved::default(); self.token_infos.len()];
 
         // For each serum market, compute what happened if reserved_base was converted to quote
@@ -1066,22 +1800,43 @@ impl HealthCache {
             let all_reserved_as_quote =
                 info.all_reserved_as_quote(health_type, quote_info, base_info);
 
-            token_max_reserved[info.base_info_index].max_serum_reserved += all_reserved_as_base;
-            token_max_reserved[info.quote_info_index].max_serum_reserved += all_reserved_as_quote;
+            token_max_reserved[info.base_info_index].max_spot_reserved += all_reserved_as_base;
+            token_max_reserved[info.quote_info_index].max_spot_reserved += all_reserved_as_quote;
 
-            serum3_reserved.push(Serum3Reserved {
+            serum3_reserved.push(SpotMarketReserved {
                 all_reserved_as_base,
                 all_reserved_as_quote,
             });
         }
 
-        (token_max_reserved, serum3_reserved)
+        // Same for each openbook_v2 market
+        let mut openbook_v2_reserved = Vec::with_capacity(self.openbook_v2_infos.len());
+
+        for info in self.openbook_v2_infos.iter() {
+            let quote_info = &self.token_infos[info.quote_info_index];
+            let base_info = &self.token_infos[info.base_info_index];
+
+            let all_reserved_as_base =
+                info.all_reserved_as_base(health_type, quote_info, base_info);
+            let all_reserved_as_quote =
+                info.all_reserved_as_quote(health_type, quote_info, base_info);
+
+            token_max_reserved[info.base_info_index].max_spot_reserved += all_reserved_as_base;
+            token_max_reserved[info.quote_info_index].max_spot_reserved += all_reserved_as_quote;
+
+            openbook_v2_reserved.push(SpotMarketReserved {
+                all_reserved_as_base,
+                all_reserved_as_quote,
+            });
+        }
+
+        (token_max_reserved, serum3_reserved, openbook_v2_reserved)
     }
 
     /// Returns token balances that account for spot and perp contributions
     ///
     /// Spot contributions are just the regular deposits or borrows, as well as from free
-    /// funds on serum3 open orders accounts.
+    /// funds on serum3 and openbook_v2 open orders accounts.
     ///
     /// Perp contributions come from perp positions in markets that use the token as a settle token:
     /// For these the hupnl is added to the total because that's the risk-adjusted expected to be
@@ -1102,10 +1857,17 @@ impl HealthCache {
     ) -> Vec<TokenBalance> {
         let mut token_balances = vec![TokenBalance::default(); self.token_infos.len()];
 
-        for perp_info in self.perp_infos.iter() {
+        let portfolio_margin_hedges = self.portfolio_margin_hedges();
+        for (perp_info, hedge) in self.perp_infos.iter().zip(portfolio_margin_hedges.iter()) {
             let settle_token_index = self.token_info_index(perp_info.settle_token_index).unwrap();
             let perp_settle_token = &mut token_balances[settle_token_index];
-            let health_unsettled = perp_info.health_unsettled_pnl(health_type);
+            if perp_info.isolated {
+                // Isolated collateral is part of the spot balance but only backs the position
+                perp_settle_token.spot_and_perp -= perp_info.isolated_collateral;
+                continue;
+            }
+            let health_unsettled =
+                Self::portfolio_margin_health_unsettled_pnl(perp_info, hedge.as_ref(), health_type);
             if !ignore_negative_perp || health_unsettled > 0 {
                 perp_settle_token.spot_and_perp += health_unsettled;
             }
@@ -1129,7 +1891,8 @@ impl HealthCache {
             action(contrib);
         }
 
-        let (token_max_reserved, serum3_reserved) = self.compute_serum3_reservations(health_type);
+        let (token_max_reserved, serum3_reserved, openbook_v2_reserved) =
+            self.compute_spot_reservations(health_type);
         for (serum3_info, reserved) in self.serum3_infos.iter().zip(serum3_reserved.iter()) {
             let contrib = serum3_info.health_contribution(
                 health_type,
@@ -1140,6 +1903,29 @@ impl HealthCache {
             );
             action(contrib);
         }
+        for (openbook_v2_info, reserved) in self
+            .openbook_v2_infos
+            .iter()
+            .zip(openbook_v2_reserved.iter())
+        {
+            let contrib = openbook_v2_info.health_contribution(
+                health_type,
+                &self.token_infos,
+                &token_balances,
+                &token_max_reserved,
+                reserved,
+            );
+            action(contrib);
+        }
+
+        action(self.portfolio_margin_spot_credit(health_type, token_balances));
+
+        action(self.emode_credit(health_type, token_balances));
+
+        action(self.isolated_collateral_adjustment(health_type, token_balances));
+
+        // Isolated perp buckets don't contribute: their losses are capped at their collateral,
+        // see isolated_perp_bucket_cache()
     }
 
     /// Returns how much pnl is settleable for a given settle token.
@@ -1190,13 +1976,15 @@ impl HealthCache {
         )
     }
 
-    pub fn total_serum3_potential(
+    /// Total amount of tokens that could flow into `token_index` from the reserved
+    /// funds of serum3 and openbook_v2 open orders accounts.
+    pub fn total_spot_potential(
         &self,
         health_type: HealthType,
         token_index: TokenIndex,
     ) -> Result<I80F48> {
         let target_token_info_index = self.token_info_index(token_index)?;
-        let total_reserved = self
+        let serum3_reserved: I80F48 = self
             .serum3_infos
             .iter()
             .filter_map(|info| {
@@ -1217,7 +2005,28 @@ impl HealthCache {
                 }
             })
             .sum();
-        Ok(total_reserved)
+        let openbook_v2_reserved: I80F48 = self
+            .openbook_v2_infos
+            .iter()
+            .filter_map(|info| {
+                if info.quote_info_index == target_token_info_index {
+                    Some(info.all_reserved_as_quote(
+                        health_type,
+                        &self.token_infos[info.quote_info_index],
+                        &self.token_infos[info.base_info_index],
+                    ))
+                } else if info.base_info_index == target_token_info_index {
+                    Some(info.all_reserved_as_base(
+                        health_type,
+                        &self.token_infos[info.quote_info_index],
+                        &self.token_infos[info.base_info_index],
+                    ))
+                } else {
+                    None
+                }
+            })
+            .sum();
+        Ok(serum3_reserved + openbook_v2_reserved)
     }
 }
 
@@ -1329,9 +2138,37 @@ fn new_health_cache_impl(
             prices,
             balance_spot: native,
             allow_asset_liquidation: bank.allows_asset_liquidation(),
+            isolated_collateral: bank.is_isolated_collateral(),
+            isolated_borrow_token_indexes: bank.isolated_borrow_token_indexes,
+            emode: (bank.emode_category != 0
+                && bank.emode_category == account.fixed.emode_category)
+                .then(|| EmodeWeights {
+                    maint_asset_weight: I80F48::from_num(bank.emode_maint_asset_weight),
+                    init_asset_weight: I80F48::from_num(bank.emode_init_asset_weight),
+                    maint_liab_weight: I80F48::from_num(bank.emode_maint_liab_weight),
+                    init_liab_weight: I80F48::from_num(bank.emode_init_liab_weight),
+                }),
         });
     }
 
+    // Fixed term positions are token balances that are locked until maturity
+    for position in account.active_fixed_term_positions() {
+        let balance = position.health_balance_native();
+        match find_token_info_index(&token_infos, position.token_index) {
+            Ok(info_index) => token_infos[info_index].balance_spot += balance,
+            Err(_) => {
+                // A skipped bank must not hide fixed term liabilities
+                require_msg_typed!(
+                    allow_skipping_banks && !balance.is_negative(),
+                    MangoError::InvalidBank,
+                    "fixed term market {} misses health accounts for bank {}",
+                    position.market_index,
+                    position.token_index,
+                );
+            }
+        }
+    }
+
     // Fill the TokenInfo balance with free funds in serum3 oo accounts and build Serum3Infos.
     let mut serum3_infos = Vec::with_capacity(account.active_serum3_orders().count());
     for (i, serum_account) in account.active_serum3_orders().enumerate() {
@@ -1374,6 +2211,53 @@ fn new_health_cache_impl(
         ));
     }
 
+    // Same for openbook_v2 open orders accounts
+    let mut openbook_v2_infos = Vec::with_capacity(account.active_openbook_v2_orders().count());
+    for (i, openbook_account) in account.active_openbook_v2_orders().enumerate() {
+        let oo = retriever.openbook_oo(i, &openbook_account.open_orders)?;
+        let oo = openbook_v2_cpi::OpenOrdersSlim::from_oo_with_lot_sizes(
+            oo,
+            openbook_account.base_lot_size,
+            openbook_account.quote_lot_size,
+        );
+
+        // find the TokenInfos for the market's base and quote tokens
+        // and potentially skip the whole openbook contribution if they are not available
+        let info_index_results = (
+            find_token_info_index(&token_infos, openbook_account.base_token_index),
+            find_token_info_index(&token_infos, openbook_account.quote_token_index),
+        );
+        let (base_info_index, quote_info_index) = match info_index_results {
+            (Ok(base), Ok(quote)) => (base, quote),
+            _ => {
+                require_msg_typed!(
+                    allow_skipping_banks,
+                    MangoError::InvalidBank,
+                    "openbook market {} misses health accounts for bank {} or {}",
+                    openbook_account.market_index,
+                    openbook_account.base_token_index,
+                    openbook_account.quote_token_index,
+                );
+                continue;
+            }
+        };
+
+        // add the amounts that are freely settleable immediately to token balances
+        let base_free = I80F48::from(oo.native_base_free());
+        let quote_free = I80F48::from(oo.native_quote_free());
+        let base_info = &mut token_infos[base_info_index];
+        base_info.balance_spot += base_free;
+        let quote_info = &mut token_infos[quote_info_index];
+        quote_info.balance_spot += quote_free;
+
+        openbook_v2_infos.push(OpenbookV2Info::new(
+            openbook_account,
+            &oo,
+            base_info_index,
+            quote_info_index,
+        ));
+    }
+
     // health contribution from perp accounts
     let mut perp_infos = Vec::with_capacity(account.active_perp_positions().count());
     for (i, perp_position) in account.active_perp_positions().enumerate() {
@@ -1388,22 +2272,35 @@ fn new_health_cache_impl(
             find_token_info_index(&token_infos, perp_market.settle_token_index)?;
         }
 
-        perp_infos.push(PerpInfo::new(
+        let mut perp_info = PerpInfo::new(
             perp_position,
             perp_market,
             Prices {
                 oracle: oracle_price,
                 stable: perp_market.stable_price(),
             },
-        )?);
+        )?;
+        if account.fixed.is_portfolio_margin() {
+            perp_info.portfolio_margin_token_index = perp_market.portfolio_margin_token_index();
+        }
+        perp_infos.push(perp_info);
     }
 
-    Ok(HealthCache {
+    let mut health_cache = HealthCache {
         token_infos,
         serum3_infos,
         perp_infos,
+        openbook_v2_infos,
         being_liquidated: account.fixed.being_liquidated(),
-    })
+    };
+    for i in 0..health_cache.perp_infos.len() {
+        let perp_info = &health_cache.perp_infos[i];
+        if perp_info.isolated {
+            let health = health_cache.isolated_perp_bucket_health(HealthType::Init, perp_info);
+            health_cache.perp_infos[i].isolated_init_health_pre = health;
+        }
+    }
+    Ok(health_cache)
 }
 
 #[cfg(test)]
@@ -1411,6 +2308,7 @@ mod tests {
     use super::super::test::*;
     use super::*;
     use crate::state::*;
+    use openbook_v2::state::OpenOrdersAccount;
     use serum_dex::state::OpenOrders;
     use std::str::FromStr;
 
@@ -1521,6 +2419,440 @@ mod tests {
         ));
     }
 
+    // Like test_health0, but with the same reserved funds on an openbook_v2 open orders account
+    #[test]
+    fn test_health_openbook_v2() {
+        let buffer = MangoAccount::default_for_tests().try_to_vec().unwrap();
+        let mut account = MangoAccountValue::from_bytes(&buffer).unwrap();
+
+        let group = Pubkey::new_unique();
+        account.fixed.group = group;
+
+        let (mut bank1, mut oracle1) = mock_bank_and_oracle(group, 0, 1.0, 0.2, 0.1);
+        let (mut bank2, mut oracle2) = mock_bank_and_oracle(group, 4, 5.0, 0.5, 0.3);
+        bank1
+            .data()
+            .deposit(
+                account.ensure_token_position(0).unwrap().0,
+                I80F48::from(100),
+                DUMMY_NOW_TS,
+            )
+            .unwrap();
+        bank2
+            .data()
+            .withdraw_without_fee(
+                account.ensure_token_position(4).unwrap().0,
+                I80F48::from(10),
+                DUMMY_NOW_TS,
+            )
+            .unwrap();
+
+        let mut oo1 = TestAccount::<OpenOrdersAccount>::new_zeroed();
+        let openbook_account = account.create_openbook_v2_orders(2).unwrap();
+        openbook_account.open_orders = oo1.pubkey;
+        openbook_account.base_token_index = 4;
+        openbook_account.quote_token_index = 0;
+        openbook_account.base_lot_size = 1;
+        openbook_account.quote_lot_size = 10;
+        {
+            let position = &mut oo1.data().position;
+            position.quote_free_native = 1;
+            // 20 reserved quote, including the locked maker fees
+            position.bids_quote_lots = 1;
+            position.locked_maker_fees = 10;
+            position.base_free_native = 3;
+            position.asks_base_lots = 15;
+            // not available for health
+            position.referrer_rebates_available = 2;
+        }
+
+        // for bank1/oracle1, including open orders (scenario: bids execute)
+        let openbook1 = 1.0 + (20.0 + 15.0 * 5.0);
+        let health1 = (100.0 + openbook1) * 0.8;
+        // for bank2/oracle2
+        let health2 = (-10.0 + 3.0) * 5.0 * 1.5;
+
+        let ais = vec![
+            bank1.as_account_info(),
+            bank2.as_account_info(),
+            oracle1.as_account_info(),
+            oracle2.as_account_info(),
+            oo1.as_account_info(),
+        ];
+
+        let retriever = ScanningAccountRetriever::new_with_staleness(&ais, &group, None).unwrap();
+        assert!(health_eq(
+            compute_health(&account.borrow(), HealthType::Init, &retriever, 0).unwrap(),
+            health1 + health2
+        ));
+
+        let retriever = new_fixed_order_account_retriever(&ais, &account.borrow(), (0, 0)).unwrap();
+        let health_cache = new_health_cache(&account.borrow(), &retriever, DUMMY_NOW_TS).unwrap();
+        assert!(health_eq(
+            health_cache.health(HealthType::Init),
+            health1 + health2
+        ));
+        assert!(health_cache.has_openbook_v2_open_orders_funds());
+        assert!(!health_cache.has_serum3_open_orders_funds());
+
+        // Without the base bank, the whole open orders contribution is skipped
+        let ais = vec![
+            bank1.as_account_info(),
+            oracle1.as_account_info(),
+            oo1.as_account_info(),
+        ];
+        let retriever =
+            new_fixed_order_account_retriever_with_optional_banks(&ais, &account.borrow(), (0, 0))
+                .unwrap();
+        let health_cache = new_health_cache_skipping_missing_banks_and_bad_oracles(
+            &account.borrow(),
+            &retriever,
+            DUMMY_NOW_TS,
+        )
+        .unwrap();
+        assert!(health_eq(
+            health_cache.health(HealthType::Init),
+            100.0 * 0.8
+        ));
+        assert!(new_health_cache(&account.borrow(), &retriever, DUMMY_NOW_TS).is_err());
+    }
+
+    #[test]
+    fn test_health_portfolio_margin() {
+        let buffer = MangoAccount::default_for_tests().try_to_vec().unwrap();
+        let mut account = MangoAccountValue::from_bytes(&buffer).unwrap();
+
+        let group = Pubkey::new_unique();
+
+        let (mut bank1, mut oracle1) = mock_bank_and_oracle(group, 0, 1.0, 0.2, 0.1);
+        let (mut bank2, mut oracle2) = mock_bank_and_oracle(group, 4, 5.0, 0.5, 0.3);
+        bank1
+            .data()
+            .deposit(
+                account.ensure_token_position(0).unwrap().0,
+                I80F48::from(100),
+                DUMMY_NOW_TS,
+            )
+            .unwrap();
+        bank2
+            .data()
+            .deposit(
+                account.ensure_token_position(4).unwrap().0,
+                I80F48::from(10),
+                DUMMY_NOW_TS,
+            )
+            .unwrap();
+
+        // short one lot (10 native base) at price 5, fully hedging the token 4 deposit
+        let mut perp1 = mock_perp_market(group, oracle2.pubkey, 5.0, 9, (0.2, 0.1), (0.05, 0.02));
+        perp1.data().set_portfolio_margin_token_index(Some(4));
+        let perpaccount = account.ensure_perp_position(9, 0).unwrap().0;
+        perpaccount.record_trade(perp1.data(), -1, I80F48::from(50));
+
+        let oracle2_ai = oracle2.as_account_info();
+        let ais = vec![
+            bank1.as_account_info(),
+            bank2.as_account_info(),
+            oracle1.as_account_info(),
+            oracle2_ai.clone(),
+            perp1.as_account_info(),
+            oracle2_ai,
+        ];
+        let retriever = ScanningAccountRetriever::new_with_staleness(&ais, &group, None).unwrap();
+
+        // token 0 with perp hupnl: (100 + 50 - 10 * 5 * 1.2) * 0.8
+        // token 4: 10 * 5 * 0.5
+        let regular_health = (100.0 + 50.0 - 60.0) * 0.8 + 25.0;
+        let health_cache = new_health_cache(&account.borrow(), &retriever, 0).unwrap();
+        assert!(health_eq(
+            health_cache.health(HealthType::Init),
+            regular_health
+        ));
+        assert_eq!(
+            health_cache.portfolio_margin_unhedged_base_lots(9).unwrap(),
+            -1
+        );
+
+        // the hedged part counts at full value on both sides
+        account.fixed.set_portfolio_margin(true);
+        let health_cache = new_health_cache(&account.borrow(), &retriever, 0).unwrap();
+        let spot_credit = 10.0 * 5.0 * 0.5;
+        // the perp credit goes to the settle token and gets its asset weight
+        let perp_credit = 10.0 * 5.0 * 0.2 * 0.8;
+        assert!(health_eq(
+            health_cache.health(HealthType::Init),
+            regular_health + spot_credit + perp_credit
+        ));
+        assert_eq!(
+            health_cache.portfolio_margin_unhedged_base_lots(9).unwrap(),
+            0
+        );
+        assert!(health_eq(
+            health_cache.portfolio_margin_hedged_spot(4),
+            10.0
+        ));
+
+        // a second perp market linked to the same token can't use the same spot balance
+        let mut perp2 = mock_perp_market(group, oracle2.pubkey, 5.0, 8, (0.2, 0.1), (0.05, 0.02));
+        perp2.data().set_portfolio_margin_token_index(Some(4));
+        let perpaccount = account.ensure_perp_position(8, 0).unwrap().0;
+        perpaccount.record_trade(perp2.data(), -1, I80F48::from(50));
+        let oracle2_ai = oracle2.as_account_info();
+        let ais = vec![
+            bank1.as_account_info(),
+            bank2.as_account_info(),
+            oracle1.as_account_info(),
+            oracle2_ai.clone(),
+            perp1.as_account_info(),
+            perp2.as_account_info(),
+            oracle2_ai.clone(),
+            oracle2_ai,
+        ];
+        let retriever = ScanningAccountRetriever::new_with_staleness(&ais, &group, None).unwrap();
+        let health_cache = new_health_cache(&account.borrow(), &retriever, 0).unwrap();
+        assert!(health_eq(
+            health_cache.portfolio_margin_hedged_spot(4),
+            10.0
+        ));
+        assert_eq!(
+            health_cache.portfolio_margin_unhedged_base_lots(9).unwrap(),
+            0
+        );
+        assert_eq!(
+            health_cache.portfolio_margin_unhedged_base_lots(8).unwrap(),
+            -1
+        );
+        // the second short is charged like without portfolio margin
+        assert!(health_eq(
+            health_cache.health(HealthType::Init),
+            regular_health + spot_credit + perp_credit - 10.0 * 0.8
+        ));
+
+        // a position on the same side gets no credit
+        let perpaccount = account.perp_position_mut(8).unwrap();
+        perpaccount.record_trade(perp2.data(), 1, -I80F48::from(50));
+        let perpaccount = account.perp_position_mut(9).unwrap();
+        perpaccount.record_trade(perp1.data(), 2, -I80F48::from(100));
+        let health_cache = new_health_cache(&account.borrow(), &retriever, 0).unwrap();
+        let token_balances = health_cache.effective_token_balances(HealthType::Init);
+        assert!(health_eq(
+            health_cache.portfolio_margin_spot_credit(HealthType::Init, &token_balances),
+            0.0
+        ));
+        assert_eq!(
+            health_cache.portfolio_margin_unhedged_base_lots(9).unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_health_isolated_perp() {
+        let buffer = MangoAccount::default_for_tests().try_to_vec().unwrap();
+        let mut account = MangoAccountValue::from_bytes(&buffer).unwrap();
+
+        let group = Pubkey::new_unique();
+
+        let (mut bank1, mut oracle1) = mock_bank_and_oracle(group, 0, 1.0, 0.2, 0.1);
+        let mut oracle2 = TestAccount::<StubOracle>::new_zeroed();
+        oracle2.data().price = I80F48::from_num(5.0);
+        bank1
+            .data()
+            .deposit(
+                account.ensure_token_position(0).unwrap().0,
+                I80F48::from(100),
+                DUMMY_NOW_TS,
+            )
+            .unwrap();
+
+        // short one lot at price 5: uhupnl = 50 - 10 * 5 * 1.2 = -10
+        let mut perp1 = mock_perp_market(group, oracle2.pubkey, 5.0, 9, (0.2, 0.1), (0.05, 0.02));
+        let perpaccount = account.ensure_perp_position(9, 0).unwrap().0;
+        perpaccount.record_trade(perp1.data(), -1, I80F48::from(50));
+        perpaccount.set_isolated(true);
+        perpaccount.isolated_collateral_native = I80F48::from(20);
+        let perp_market = *perp1.data();
+
+        let ais = vec![
+            bank1.as_account_info(),
+            oracle1.as_account_info(),
+            perp1.as_account_info(),
+            oracle2.as_account_info(),
+        ];
+        let retriever = ScanningAccountRetriever::new_with_staleness(&ais, &group, None).unwrap();
+
+        // the healthy bucket doesn't contribute, the rest of the account has 80 USDC
+        let health_cache = new_health_cache(&account.borrow(), &retriever, 0).unwrap();
+        assert!(health_eq(health_cache.health(HealthType::Init), 80.0 * 0.8));
+        assert!(health_eq(
+            health_cache
+                .isolated_perp_health(HealthType::Init, 9)
+                .unwrap(),
+            (20.0 - 10.0) * 0.8
+        ));
+        assert!(!health_cache.has_unhealthy_isolated_perp(HealthType::Init));
+        let (assets, liabs) = health_cache.health_assets_and_liabs_stable_assets(HealthType::Init);
+        assert!(health_eq(assets, 80.0 * 0.8));
+        assert!(health_eq(liabs, 0.0));
+
+        // an unhealthy bucket doesn't reduce account health either
+        let perpaccount = account.perp_position_mut(9).unwrap();
+        perpaccount.isolated_collateral_native = I80F48::from(5);
+        let mut health_cache = new_health_cache(&account.borrow(), &retriever, 0).unwrap();
+        assert!(health_eq(health_cache.health(HealthType::Init), 95.0 * 0.8));
+        let (assets, liabs) = health_cache.health_assets_and_liabs_stable_liabs(HealthType::Init);
+        assert!(health_eq(assets - liabs, 95.0 * 0.8));
+        assert!(health_cache.has_unhealthy_isolated_perp(HealthType::Init));
+        assert!(!health_cache.has_perp_base_positions());
+
+        // the bucket is liquidated by itself and can only lose its collateral
+        let bucket_cache = health_cache.isolated_perp_bucket_cache(9).unwrap();
+        assert!(health_eq(bucket_cache.health(HealthType::Init), -5.0 * 1.2));
+        assert!(bucket_cache.has_perp_base_positions());
+        assert!(health_eq(bucket_cache.perp_max_settle(0).unwrap(), 5.0));
+        assert!(health_cache.isolated_perp_bucket_cache(8).is_err());
+
+        // an unhealthy bucket may not get worse
+        health_cache.check_isolated_perp_health_post().unwrap();
+        let perpaccount = account.perp_position_mut(9).unwrap();
+        perpaccount.isolated_collateral_native = I80F48::from(4);
+        health_cache
+            .recompute_perp_info(account.perp_position(9).unwrap(), &perp_market)
+            .unwrap();
+        assert!(health_cache.check_isolated_perp_health_post().is_err());
+
+        // back to cross margin
+        let perpaccount = account.perp_position_mut(9).unwrap();
+        perpaccount.set_isolated(false);
+        let health_cache = new_health_cache(&account.borrow(), &retriever, 0).unwrap();
+        assert!(health_eq(
+            health_cache.health(HealthType::Init),
+            (100.0 - 10.0) * 0.8
+        ));
+    }
+
+    #[test]
+    fn test_health_isolated_collateral() {
+        fn health_cache(
+            account: &MangoAccountValue,
+            banks: &mut [TestAccount<Bank>],
+            oracles: &mut [TestAccount<StubOracle>],
+        ) -> HealthCache {
+            let group = banks[0].data().group;
+            let mut ais: Vec<AccountInfo> = banks.iter_mut().map(|b| b.as_account_info()).collect();
+            ais.extend(oracles.iter_mut().map(|o| o.as_account_info()));
+            let retriever =
+                ScanningAccountRetriever::new_with_staleness(&ais, &group, None).unwrap();
+            new_health_cache(&account.borrow(), &retriever, 0).unwrap()
+        }
+
+        let buffer = MangoAccount::default_for_tests().try_to_vec().unwrap();
+        let mut account = MangoAccountValue::from_bytes(&buffer).unwrap();
+
+        let group = Pubkey::new_unique();
+
+        let (bank0, oracle0) = mock_bank_and_oracle(group, 0, 1.0, 0.2, 0.1);
+        let (bank1, oracle1) = mock_bank_and_oracle(group, 1, 2.0, 0.5, 0.4);
+        let (bank2, oracle2) = mock_bank_and_oracle(group, 2, 10.0, 0.2, 0.1);
+        let mut banks = vec![bank0, bank1, bank2];
+        let mut oracles = vec![oracle0, oracle1, oracle2];
+
+        let mut borrow_token_indexes = [TokenIndex::MAX; 7];
+        borrow_token_indexes[0] = 0;
+        banks[1].data().isolated_collateral = 1;
+        banks[1].data().isolated_borrow_token_indexes = borrow_token_indexes;
+        banks[1].data().isolated_debt_ceiling_quote = 1000;
+
+        // isolated collateral worth 100 * 2 * 0.5 = 100, usdc borrows of 50 * 1.2 = 60
+        banks[1]
+            .data()
+            .deposit(
+                account.ensure_token_position(1).unwrap().0,
+                I80F48::from(100),
+                DUMMY_NOW_TS,
+            )
+            .unwrap();
+        banks[0]
+            .data()
+            .withdraw_without_fee(
+                account.ensure_token_position(0).unwrap().0,
+                I80F48::from(50),
+                DUMMY_NOW_TS,
+            )
+            .unwrap();
+
+        // the collateral only counts for backing the usdc borrow
+        let cache = health_cache(&account, &mut banks, &mut oracles);
+        assert!(health_eq(cache.health(HealthType::Init), 0.0));
+        cache.check_isolated_collateral_allows_borrow(0).unwrap();
+        cache
+            .check_isolated_collateral_allows_borrow(2)
+            .unwrap_err();
+
+        // borrows of other tokens are not backed
+        banks[2]
+            .data()
+            .withdraw_without_fee(
+                account.ensure_token_position(2).unwrap().0,
+                I80F48::from(2),
+                DUMMY_NOW_TS,
+            )
+            .unwrap();
+        let cache = health_cache(&account, &mut banks, &mut oracles);
+        assert!(health_eq(cache.health(HealthType::Init), -2.0 * 10.0 * 1.2));
+
+        // the collateral backs the weighted usdc borrows
+        let backed = cache.isolated_debt_backed();
+        assert_eq!(backed.len(), 1);
+        assert_eq!(backed[0].0, 1);
+        assert!(health_eq(backed[0].1, 60.0));
+
+        // the debt ceiling applies to the total backed by the token across accounts
+        banks[1].data().isolated_debt_ceiling_quote = 100;
+        banks[1].data().isolated_debt_quote = 50;
+        {
+            let mut ais: Vec<AccountInfo> = banks.iter_mut().map(|b| b.as_account_info()).collect();
+            ais.iter_mut().for_each(|ai| ai.is_writable = true);
+            account
+                .borrow_mut()
+                .update_isolated_debt(&cache, &ais)
+                .unwrap_err();
+        }
+        assert_eq!(banks[1].data().isolated_debt_quote, 50);
+
+        banks[1].data().isolated_debt_quote = 40;
+        {
+            let mut ais: Vec<AccountInfo> = banks.iter_mut().map(|b| b.as_account_info()).collect();
+            ais.iter_mut().for_each(|ai| ai.is_writable = true);
+            account
+                .borrow_mut()
+                .update_isolated_debt(&cache, &ais)
+                .unwrap();
+        }
+        assert_eq!(banks[1].data().isolated_debt_quote, 100);
+        assert_eq!(account.token_position(1).unwrap().isolated_debt_quote, 60);
+
+        // repaying the borrow releases the account's share
+        banks[0]
+            .data()
+            .deposit(
+                account.token_position_mut(0).unwrap().0,
+                I80F48::from(50),
+                DUMMY_NOW_TS,
+            )
+            .unwrap();
+        let cache = health_cache(&account, &mut banks, &mut oracles);
+        {
+            let mut ais: Vec<AccountInfo> = banks.iter_mut().map(|b| b.as_account_info()).collect();
+            ais.iter_mut().for_each(|ai| ai.is_writable = true);
+            account
+                .borrow_mut()
+                .update_isolated_debt(&cache, &ais)
+                .unwrap();
+        }
+        assert_eq!(banks[1].data().isolated_debt_quote, 40);
+        assert_eq!(account.token_position(1).unwrap().isolated_debt_quote, 0);
+    }
+
     #[derive(Default)]
     struct BankSettings {
         deposits: u64,
@@ -174,9 +174,9 @@ impl HealthCache {
         let source = &self.token_infos[source_index];
         let target = &self.token_infos[target_index];
 
-        let (tokens_max_reserved, _) = self.compute_serum3_reservations(health_type);
-        let source_reserved = tokens_max_reserved[source_index].max_serum_reserved;
-        let target_reserved = tokens_max_reserved[target_index].max_serum_reserved;
+        let (tokens_max_reserved, _, _) = self.compute_spot_reservations(health_type);
+        let source_reserved = tokens_max_reserved[source_index].max_spot_reserved;
+        let target_reserved = tokens_max_reserved[target_index].max_spot_reserved;
 
         let token_balances = self.effective_token_balances(health_type);
         let source_balance = token_balances[source_index].spot_and_perp;
@@ -449,6 +449,7 @@ impl HealthCache {
             let mut bank = bank.clone();
             bank.withdraw_with_fee(&mut position, amount, now_ts)?;
             bank.check_net_borrows(token.prices.oracle)?;
+            bank.check_borrow_limit()?;
 
             let mut resulting_cache = self.clone();
             resulting_cache.adjust_token_balance(&bank, -amount)?;
@@ -632,9 +633,11 @@ fn find_maximum(
 }
 
 fn ignore_net_borrow_limit_errors(maybe_cache: Result<HealthCache>) -> Result<Option<HealthCache>> {
-    // Special case net borrow errors: We want to be able to find a good
+    // Special case net borrow and borrow limit errors: We want to be able to find a good
     // swap amount even if the max swap is limited by the net borrow limit.
-    if maybe_cache.is_anchor_error_with_code(MangoError::BankNetBorrowsLimitReached.error_code()) {
+    if maybe_cache.is_anchor_error_with_code(MangoError::BankNetBorrowsLimitReached.error_code())
+        || maybe_cache.is_anchor_error_with_code(MangoError::BankBorrowLimit.error_code())
+    {
         return Ok(None);
     }
     maybe_cache.map(|c| Some(c))
@@ -686,6 +689,9 @@ mod tests {
             prices: Prices::new_single_price(I80F48::from_num(price)),
             balance_spot: I80F48::ZERO,
             allow_asset_liquidation: true,
+            isolated_collateral: false,
+            isolated_borrow_token_indexes: [TokenIndex::MAX; 7],
+            emode: None,
         }
     }
 
@@ -707,6 +713,10 @@ mod tests {
             base_prices: Prices::new_single_price(I80F48::from_num(price)),
             has_open_orders: false,
             has_open_fills: false,
+            portfolio_margin_token_index: None,
+            isolated: false,
+            isolated_collateral: I80F48::ZERO,
+            isolated_init_health_pre: I80F48::ZERO,
         }
     }
 
@@ -745,6 +755,7 @@ mod tests {
             ],
             serum3_infos: vec![],
             perp_infos: vec![],
+            openbook_v2_infos: vec![],
             being_liquidated: false,
         };
 
@@ -1169,6 +1180,7 @@ mod tests {
                 base_lot_size,
                 ..default_perp_info(0.3, 2.0)
             }],
+            openbook_v2_infos: vec![],
             being_liquidated: false,
         };
 
@@ -1453,6 +1465,7 @@ mod tests {
             ],
             serum3_infos: vec![],
             perp_infos: vec![],
+            openbook_v2_infos: vec![],
             being_liquidated: false,
         };
 
@@ -1477,6 +1490,7 @@ mod tests {
             let mut bank = bank.clone();
             bank.withdraw_with_fee(&mut position, amount, now_ts)?;
             bank.check_net_borrows(c.token_info(bank.token_index)?.prices.oracle)?;
+            bank.check_borrow_limit()?;
 
             let mut resulting_cache = c.clone();
             resulting_cache.adjust_token_balance(&bank, -amount)?;
@@ -1604,6 +1618,7 @@ mod tests {
                 settle_token_index: 0,
                 ..default_perp_info(0.3, 2.0)
             }],
+            openbook_v2_infos: vec![],
             being_liquidated: false,
         };
 
@@ -1653,6 +1668,7 @@ mod tests {
             ],
             serum3_infos: vec![],
             perp_infos: vec![],
+            openbook_v2_infos: vec![],
             being_liquidated: false,
         };
         assert!(leverage_eq(&health_cache, 0.0));
@@ -1673,6 +1689,7 @@ mod tests {
             ],
             serum3_infos: vec![],
             perp_infos: vec![],
+            openbook_v2_infos: vec![],
             being_liquidated: false,
         };
 
@@ -1699,6 +1716,7 @@ mod tests {
                 quote: I80F48::from_num(9.9),
                 ..default_perp_info(0.1, 1.1)
             }],
+            openbook_v2_infos: vec![],
             being_liquidated: false,
         };
         assert!(leverage_eq(&health_cache, 9.9));
@@ -1728,6 +1746,7 @@ mod tests {
                 has_zero_funds: true,
             }],
             perp_infos: vec![],
+            openbook_v2_infos: vec![],
             being_liquidated: false,
         };
 
@@ -18,6 +18,7 @@ pub trait MyZeroCopy: anchor_lang::ZeroCopy + Owner {}
 impl MyZeroCopy for StubOracle {}
 impl MyZeroCopy for Bank {}
 impl MyZeroCopy for PerpMarket {}
+impl MyZeroCopy for openbook_v2::state::OpenOrdersAccount {}
 
 #[derive(Clone)]
 pub struct TestAccount<T> {
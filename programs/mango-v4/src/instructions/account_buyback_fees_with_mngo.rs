@@ -187,6 +187,11 @@ pub fn account_buyback_fees_with_mngo(
         0,
         MangoError::SomeError
     );
+    require_eq!(
+        dao_account.active_openbook_v2_orders().count(),
+        0,
+        MangoError::SomeError
+    );
 
     Ok(())
 }
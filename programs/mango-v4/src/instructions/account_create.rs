@@ -14,6 +14,9 @@ pub fn account_create(
     perp_count: u8,
     perp_oo_count: u8,
     token_conditional_swap_count: u8,
+    openbook_v2_count: u8,
+    fixed_term_count: u8,
+    perp_scheduled_order_count: u8,
     name: String,
 ) -> Result<()> {
     let mut account = account_ai.load_full_init()?;
@@ -24,6 +27,9 @@ pub fn account_create(
         perp_count,
         perp_oo_count,
         token_conditional_swap_count,
+        openbook_v2_count,
+        fixed_term_count,
+        perp_scheduled_order_count,
     };
     header.check_resize_from(&MangoAccountDynamicHeader::zero())?;
 
@@ -46,6 +52,9 @@ pub fn account_create(
         perp_count,
         perp_oo_count,
         token_conditional_swap_count,
+        openbook_v2_count,
+        fixed_term_count,
+        perp_scheduled_order_count,
     )?;
 
     Ok(())
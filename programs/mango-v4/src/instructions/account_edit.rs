@@ -27,6 +27,10 @@ pub fn account_edit(
     }
 
     if let Some(delegate) = delegate_opt {
+        // a new delegate doesn't inherit the previous delegate's scope
+        if delegate != account.fixed.delegate {
+            account.fixed.delegate_scope = DelegateScope::default();
+        }
         account.fixed.delegate = delegate;
     }
 
@@ -34,6 +38,9 @@ pub fn account_edit(
         (Some(temporary_delegate), Some(temporary_delegate_expiry)) => {
             let now_ts: u64 = Clock::get().unwrap().unix_timestamp.try_into().unwrap();
             require_gt!(now_ts + ONE_WEEK_SECONDS, temporary_delegate_expiry);
+            if temporary_delegate != account.fixed.temporary_delegate {
+                account.fixed.temporary_delegate_scope = DelegateScope::default();
+            }
             account.fixed.temporary_delegate = temporary_delegate;
             account.fixed.temporary_delegate_expiry = temporary_delegate_expiry;
         }
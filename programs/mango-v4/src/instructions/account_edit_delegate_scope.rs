@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::state::*;
+
+/// Sets the restrictions for the delegate or the temporary delegate.
+///
+/// Passing permissions == 0 removes all restrictions, so no limits may be set along with it.
+pub fn account_edit_delegate_scope(
+    ctx: Context<AccountEditDelegateScope>,
+    temporary_delegate: bool,
+    scope: DelegateScope,
+) -> Result<()> {
+    require_msg!(
+        scope.has_valid_permissions(),
+        "unknown delegate permission bits: {:#x}",
+        scope.permissions
+    );
+    require_msg!(
+        !scope.is_unrestricted() || !scope.has_limits(),
+        "an unrestricted scope (permissions == 0) can't have market or notional limits"
+    );
+
+    let mut account = ctx.accounts.account.load_mut()?;
+
+    let scope = DelegateScope {
+        padding: Default::default(),
+        ..scope
+    };
+    msg!(
+        "setting {} scope to {:?}",
+        if temporary_delegate {
+            "temporary delegate"
+        } else {
+            "delegate"
+        },
+        scope
+    );
+    if temporary_delegate {
+        account.temporary_delegate_scope = scope;
+    } else {
+        account.delegate_scope = scope;
+    }
+
+    Ok(())
+}
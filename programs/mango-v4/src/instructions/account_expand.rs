@@ -10,6 +10,9 @@ pub fn account_expand(
     perp_count: u8,
     perp_oo_count: u8,
     token_conditional_swap_count: u8,
+    openbook_v2_count: u8,
+    fixed_term_count: u8,
+    perp_scheduled_order_count: u8,
 ) -> Result<()> {
     let new_size = MangoAccount::space(
         token_count,
@@ -17,6 +20,9 @@ pub fn account_expand(
         perp_count,
         perp_oo_count,
         token_conditional_swap_count,
+        openbook_v2_count,
+        fixed_term_count,
+        perp_scheduled_order_count,
     );
     let new_rent_minimum = Rent::get()?.minimum_balance(new_size);
 
@@ -64,6 +70,9 @@ pub fn account_expand(
             perp_count,
             perp_oo_count,
             token_conditional_swap_count,
+            openbook_v2_count,
+            fixed_term_count,
+            perp_scheduled_order_count,
         )?;
     }
 
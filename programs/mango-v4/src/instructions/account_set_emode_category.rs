@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::error::*;
+use crate::health::*;
+use crate::state::*;
+use crate::util::clock_now;
+
+/// Opts the account into an e-mode category, or out of e-mode with category 0.
+///
+/// Leaving a category removes the boosted weights, so the account must stay
+/// healthy without them.
+pub fn account_set_emode_category(
+    ctx: Context<AccountSetEmodeCategory>,
+    category: u8,
+) -> Result<()> {
+    if category != 0 {
+        let group = ctx.accounts.group.load()?;
+        require_msg_typed!(
+            group.emode_category(category).is_some(),
+            MangoError::InvalidEmodeCategory,
+            "e-mode category {} does not exist",
+            category
+        );
+    }
+
+    let mut account = ctx.accounts.account.load_full_mut()?;
+    if account.fixed.emode_category == category {
+        return Ok(());
+    }
+
+    let (now_ts, now_slot) = clock_now();
+    let retriever = new_fixed_order_account_retriever(
+        ctx.remaining_accounts,
+        &account.borrow(),
+        (now_ts, now_slot),
+    )?;
+
+    let pre_health_cache =
+        new_health_cache(&account.borrow(), &retriever, now_ts).context("pre init health")?;
+    let pre_init_health = account.check_health_pre(&pre_health_cache)?;
+
+    account.fixed.emode_category = category;
+    msg!("e-mode category set to {}", category);
+
+    let post_health_cache =
+        new_health_cache(&account.borrow(), &retriever, now_ts).context("post init health")?;
+    account.check_health_post(&post_health_cache, pre_init_health)?;
+
+    Ok(())
+}
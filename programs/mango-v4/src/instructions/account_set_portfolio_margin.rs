@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::health::*;
+use crate::state::*;
+use crate::util::clock_now;
+
+/// Opts the account in or out of portfolio margin.
+///
+/// Opting out removes the health credit for hedged spot and perp positions, so
+/// the account must stay healthy without it.
+pub fn account_set_portfolio_margin(
+    ctx: Context<AccountSetPortfolioMargin>,
+    enabled: bool,
+) -> Result<()> {
+    let mut account = ctx.accounts.account.load_full_mut()?;
+    if account.fixed.is_portfolio_margin() == enabled {
+        return Ok(());
+    }
+
+    let (now_ts, now_slot) = clock_now();
+    let retriever = new_fixed_order_account_retriever(
+        ctx.remaining_accounts,
+        &account.borrow(),
+        (now_ts, now_slot),
+    )?;
+
+    let pre_health_cache =
+        new_health_cache(&account.borrow(), &retriever, now_ts).context("pre init health")?;
+    let pre_init_health = account.check_health_pre(&pre_health_cache)?;
+
+    account.fixed.set_portfolio_margin(enabled);
+    msg!("portfolio margin set to {}", enabled);
+
+    let post_health_cache =
+        new_health_cache(&account.borrow(), &retriever, now_ts).context("post init health")?;
+    account.check_health_post(&post_health_cache, pre_init_health)?;
+
+    Ok(())
+}
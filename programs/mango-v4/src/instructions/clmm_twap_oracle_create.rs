@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::accounts_zerocopy::*;
+use crate::error::*;
+use crate::state::*;
+use crate::util::clock_now;
+
+pub fn clmm_twap_oracle_create(
+    ctx: Context<ClmmTwapOracleCreate>,
+    twap_window_seconds: u64,
+) -> Result<()> {
+    require_gt!(twap_window_seconds, 0);
+
+    let pool_ai = AccountInfoRef::borrow(ctx.accounts.pool.as_ref())?;
+    let observation_ai = AccountInfoRef::borrow(ctx.accounts.observation.as_ref())?;
+    let pool = load_raydium_pool_state(&pool_ai)?;
+    require!(pool.has_quote_token(), MangoError::InvalidCLMMOracle);
+
+    let mut oracle = ctx.accounts.oracle.load_init()?;
+    oracle.group = ctx.accounts.group.key();
+    oracle.mint = pool.base_mint();
+    oracle.quote_mint = pool.quote_mint();
+    oracle.pool = ctx.accounts.pool.key();
+    oracle.observation = ctx.accounts.observation.key();
+    oracle.twap_window_seconds = twap_window_seconds;
+
+    let (now_ts, now_slot) = clock_now();
+    oracle.update(&pool_ai, &observation_ai, now_ts, now_slot)?;
+
+    Ok(())
+}
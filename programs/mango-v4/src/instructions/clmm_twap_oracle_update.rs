@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::accounts_zerocopy::*;
+use crate::util::clock_now;
+
+pub fn clmm_twap_oracle_update(ctx: Context<ClmmTwapOracleUpdate>) -> Result<()> {
+    let (now_ts, now_slot) = clock_now();
+    let pool_ai = AccountInfoRef::borrow(ctx.accounts.pool.as_ref())?;
+    let observation_ai = AccountInfoRef::borrow(ctx.accounts.observation.as_ref())?;
+
+    let mut oracle = ctx.accounts.oracle.load_mut()?;
+    oracle.update(&pool_ai, &observation_ai, now_ts, now_slot)?;
+    msg!(
+        "twap price: {}, observed window: {}s",
+        oracle.price,
+        oracle.observed_window_seconds
+    );
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+use fixed::types::I80F48;
+
+use crate::accounts_ix::*;
+use crate::accounts_zerocopy::*;
+use crate::error::*;
+use crate::health::*;
+use crate::logs::{emit_stack, FixedTermLoanLog, TokenBalanceLog};
+use crate::state::*;
+use crate::util::clock_now;
+
+/// Borrows `amount` of lent tokens from the fixed term market into the account's token position.
+///
+/// The amount owed at maturity counts toward the bank's net borrow and borrow limits, like
+/// a token_withdraw borrow.
+pub fn fixed_term_borrow(ctx: Context<FixedTermBorrow>, amount: u64) -> Result<()> {
+    require_msg!(amount > 0, "borrow amount must be positive");
+
+    let (now_ts, now_slot) = clock_now();
+
+    let mut account = ctx.accounts.account.load_full_mut()?;
+    account
+        .fixed
+        .check_delegate_permission(ctx.accounts.owner.key(), DelegatePermission::FixedTerm)?;
+    let mut market = ctx.accounts.fixed_term_market.load_mut()?;
+    let mut bank = ctx.accounts.bank.load_mut()?;
+    let token_index = bank.token_index;
+
+    require!(
+        !bank.are_borrows_reduce_only(),
+        MangoError::TokenInReduceOnlyMode
+    );
+    require!(!market.is_reduce_only(), MangoError::MarketInReduceOnlyMode);
+    require_msg_typed!(
+        amount <= market.available_to_borrow_native(),
+        MangoError::FixedTermMarketInsufficientLiquidity,
+        "can't borrow {}, only {} is available",
+        amount,
+        market.available_to_borrow_native()
+    );
+    let amount_at_maturity = market.amount_at_maturity(amount, now_ts, true)?;
+
+    // Create the token position before the health check, so it's guaranteed to exist
+    let (_, raw_token_index, _) = account.ensure_token_position(token_index)?;
+
+    let pre_health_opt = if !account.fixed.is_in_health_region() {
+        let retriever = new_fixed_order_account_retriever_with_optional_banks(
+            ctx.remaining_accounts,
+            &account.borrow(),
+            (now_ts, now_slot),
+        )?;
+        let health_cache = new_health_cache_skipping_missing_banks_and_bad_oracles(
+            &account.borrow(),
+            &retriever,
+            now_ts,
+        )
+        .context("pre-borrow health cache")?;
+        let pre_init_health = account.check_health_pre(&health_cache)?;
+        Some((health_cache, pre_init_health))
+    } else {
+        None
+    };
+
+    let (position, created) =
+        account.ensure_fixed_term_position(market.market_index, token_index)?;
+    position.maturity_timestamp = market.maturity_timestamp;
+    position.borrowed_native += amount;
+    position.borrowed_at_maturity_native += amount_at_maturity;
+
+    // The token position must stay open while the fixed term position exists
+    let token_position = account.token_position_mut_by_raw_index(raw_token_index);
+    if created {
+        token_position.increment_in_use();
+    }
+    let native_position = token_position.native(&bank);
+    bank.deposit(token_position, I80F48::from(amount), now_ts)?;
+    let native_position_after = token_position.native(&bank);
+
+    market.total_borrowed_native += amount;
+    market.total_borrowed_at_maturity_native += amount_at_maturity;
+    market.total_borrow_interest_native += amount_at_maturity - amount;
+
+    bank.update_net_borrows(I80F48::from(amount_at_maturity), now_ts);
+    bank.fixed_term_borrows_native += amount_at_maturity;
+
+    emit_stack(TokenBalanceLog {
+        mango_group: ctx.accounts.group.key(),
+        mango_account: ctx.accounts.account.key(),
+        token_index,
+        indexed_position: token_position.indexed_position.to_bits(),
+        deposit_index: bank.deposit_index.to_bits(),
+        borrow_index: bank.borrow_index.to_bits(),
+    });
+
+    let post_health_cache_opt =
+        if let Some((mut health_cache, pre_init_health_lower_bound)) = pre_health_opt {
+            // Health decreases by the interest owed at maturity, so the bank must be known
+            require!(
+                health_cache.has_token_info(token_index),
+                MangoError::BorrowsRequireHealthAccountBank
+            );
+            health_cache.adjust_token_balance(
+                &bank,
+                native_position_after - native_position - I80F48::from(amount_at_maturity),
+            )?;
+            health_cache.check_isolated_collateral_allows_borrow(token_index)?;
+            account.check_health_post(&health_cache, pre_init_health_lower_bound)?;
+            Some(health_cache)
+        } else {
+            None
+        };
+
+    // The price has to be trustworthy for a reasonable net borrow check
+    let oracle_ref = &AccountInfoRef::borrow(ctx.accounts.oracle.as_ref())?;
+    let oracle_price = bank.oracle_price_for(
+        &OracleAccountInfos::from_reader(oracle_ref),
+        Some((now_ts, now_slot)),
+        OracleFreshness::RiskIncreasing,
+    )?;
+    bank.check_net_borrows(oracle_price)?;
+    bank.check_borrow_limit()?;
+
+    // The borrowed token's bank may be one of the collateral banks that get updated
+    drop(bank);
+    if let Some(health_cache) = post_health_cache_opt {
+        account.update_isolated_debt(&health_cache, ctx.remaining_accounts)?;
+    }
+
+    emit_stack(FixedTermLoanLog {
+        mango_group: ctx.accounts.group.key(),
+        mango_account: ctx.accounts.account.key(),
+        market_index: market.market_index,
+        token_index,
+        is_borrow: true,
+        amount,
+        amount_at_maturity,
+        maturity_timestamp: market.maturity_timestamp,
+    });
+
+    Ok(())
+}
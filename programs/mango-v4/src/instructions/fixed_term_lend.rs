@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use fixed::types::I80F48;
+
+use crate::accounts_ix::*;
+use crate::error::*;
+use crate::health::*;
+use crate::logs::{emit_stack, FixedTermLoanLog, TokenBalanceLog};
+use crate::state::*;
+use crate::util::clock_now;
+
+/// Moves `amount` of the account's deposits into the fixed term market.
+pub fn fixed_term_lend(ctx: Context<FixedTermLend>, amount: u64) -> Result<()> {
+    require_msg!(amount > 0, "lend amount must be positive");
+
+    let (now_ts, now_slot) = clock_now();
+
+    let mut account = ctx.accounts.account.load_full_mut()?;
+    account
+        .fixed
+        .check_delegate_permission(ctx.accounts.owner.key(), DelegatePermission::FixedTerm)?;
+    let mut market = ctx.accounts.fixed_term_market.load_mut()?;
+    let mut bank = ctx.accounts.bank.load_mut()?;
+    let token_index = bank.token_index;
+
+    require!(!market.is_reduce_only(), MangoError::MarketInReduceOnlyMode);
+    require_msg_typed!(
+        market.total_lent_native + amount <= market.max_lent_native,
+        MangoError::FixedTermMarketLendLimitExceeded,
+        "lending {} would exceed the limit: already lent {} of {}",
+        amount,
+        market.total_lent_native,
+        market.max_lent_native
+    );
+    let amount_at_maturity = market.amount_at_maturity(amount, now_ts, false)?;
+
+    let pre_health_opt = if !account.fixed.is_in_health_region() {
+        let retriever = new_fixed_order_account_retriever_with_optional_banks(
+            ctx.remaining_accounts,
+            &account.borrow(),
+            (now_ts, now_slot),
+        )?;
+        let health_cache = new_health_cache_skipping_missing_banks_and_bad_oracles(
+            &account.borrow(),
+            &retriever,
+            now_ts,
+        )
+        .context("pre-lend health cache")?;
+        let pre_init_health = account.check_health_pre(&health_cache)?;
+        Some((health_cache, pre_init_health))
+    } else {
+        None
+    };
+
+    // Only existing deposits can be lent out
+    let (_, raw_token_index) = account.token_position_mut(token_index)?;
+    let native_position = account
+        .token_position_mut_by_raw_index(raw_token_index)
+        .native(&bank);
+    require_msg!(
+        native_position >= I80F48::from(amount),
+        "can't lend {}, deposits are only {}",
+        amount,
+        native_position
+    );
+
+    let (position, created) =
+        account.ensure_fixed_term_position(market.market_index, token_index)?;
+    position.maturity_timestamp = market.maturity_timestamp;
+    position.lent_native += amount;
+    position.lent_at_maturity_native += amount_at_maturity;
+
+    // The token position must stay open while the fixed term position exists
+    let token_position = account.token_position_mut_by_raw_index(raw_token_index);
+    if created {
+        token_position.increment_in_use();
+    }
+    bank.withdraw_without_fee(token_position, I80F48::from(amount), now_ts)?;
+
+    market.total_lent_native += amount;
+    market.total_lent_at_maturity_native += amount_at_maturity;
+    market.total_lend_interest_native += amount_at_maturity - amount;
+
+    emit_stack(TokenBalanceLog {
+        mango_group: ctx.accounts.group.key(),
+        mango_account: ctx.accounts.account.key(),
+        token_index,
+        indexed_position: token_position.indexed_position.to_bits(),
+        deposit_index: bank.deposit_index.to_bits(),
+        borrow_index: bank.borrow_index.to_bits(),
+    });
+
+    // The token balance decrease is exactly offset by the lent principal, health
+    // can only change through rounding in the token position.
+    if let Some((mut health_cache, pre_init_health_lower_bound)) = pre_health_opt {
+        if health_cache.has_token_info(token_index) {
+            let native_position_after = token_position.native(&bank);
+            health_cache.adjust_token_balance(
+                &bank,
+                native_position_after - native_position + I80F48::from(amount),
+            )?;
+        }
+        account.check_health_post(&health_cache, pre_init_health_lower_bound)?;
+    }
+
+    bank.enforce_borrows_lte_deposits()?;
+
+    emit_stack(FixedTermLoanLog {
+        mango_group: ctx.accounts.group.key(),
+        mango_account: ctx.accounts.account.key(),
+        market_index: market.market_index,
+        token_index,
+        is_borrow: false,
+        amount,
+        amount_at_maturity,
+        maturity_timestamp: market.maturity_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use fixed::types::I80F48;
+
+use crate::accounts_ix::*;
+use crate::error::*;
+use crate::logs::{emit_stack, FixedTermMarketCreateLog};
+use crate::state::*;
+use crate::util::fill_from_str;
+
+pub fn fixed_term_market_create(
+    ctx: Context<FixedTermMarketCreate>,
+    market_index: FixedTermMarketIndex,
+    name: String,
+    maturity_timestamp: u64,
+    lend_rate: f32,
+    borrow_rate: f32,
+    max_lent_native: u64,
+) -> Result<()> {
+    let now_ts: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
+    require_msg!(
+        maturity_timestamp > now_ts,
+        "maturity {} must be in the future, now is {}",
+        maturity_timestamp,
+        now_ts
+    );
+    require_msg!(
+        lend_rate >= 0.0 && borrow_rate >= lend_rate,
+        "rates must satisfy 0 <= lend_rate ({}) <= borrow_rate ({})",
+        lend_rate,
+        borrow_rate
+    );
+
+    let token_index = ctx.accounts.bank.load()?.token_index;
+
+    let mut market = ctx.accounts.fixed_term_market.load_init()?;
+    *market = FixedTermMarket {
+        group: ctx.accounts.group.key(),
+        token_index,
+        market_index,
+        bump: *ctx
+            .bumps
+            .get("fixed_term_market")
+            .ok_or(MangoError::SomeError)?,
+        reduce_only: 0,
+        padding1: Default::default(),
+        name: fill_from_str(&name)?,
+        maturity_timestamp,
+        lend_rate: I80F48::from_num(lend_rate),
+        borrow_rate: I80F48::from_num(borrow_rate),
+        max_lent_native,
+        total_lent_native: 0,
+        total_borrowed_native: 0,
+        total_lent_at_maturity_native: 0,
+        total_borrowed_at_maturity_native: 0,
+        registration_time: now_ts,
+        total_lend_interest_native: 0,
+        total_borrow_interest_native: 0,
+        reserved: [0; 496],
+    };
+
+    emit_stack(FixedTermMarketCreateLog {
+        mango_group: ctx.accounts.group.key(),
+        fixed_term_market: ctx.accounts.fixed_term_market.key(),
+        market_index,
+        token_index,
+        maturity_timestamp,
+        lend_rate: market.lend_rate.to_bits(),
+        borrow_rate: market.borrow_rate.to_bits(),
+    });
+
+    Ok(())
+}
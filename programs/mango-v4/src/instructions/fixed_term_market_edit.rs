@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use fixed::types::I80F48;
+
+use crate::accounts_ix::*;
+use crate::util::fill_from_str;
+
+/// Rate changes only apply to new loans, existing positions keep their amounts at maturity.
+pub fn fixed_term_market_edit(
+    ctx: Context<FixedTermMarketEdit>,
+    name_opt: Option<String>,
+    lend_rate_opt: Option<f32>,
+    borrow_rate_opt: Option<f32>,
+    max_lent_native_opt: Option<u64>,
+    reduce_only_opt: Option<bool>,
+) -> Result<()> {
+    let mut market = ctx.accounts.fixed_term_market.load_mut()?;
+
+    if let Some(name) = name_opt.as_ref() {
+        msg!("Name: old - {:?}, new - {:?}", market.name(), name);
+        market.name = fill_from_str(name)?;
+    }
+
+    if let Some(lend_rate) = lend_rate_opt {
+        msg!(
+            "Lend rate: old - {:?}, new - {:?}",
+            market.lend_rate,
+            lend_rate
+        );
+        market.lend_rate = I80F48::from_num(lend_rate);
+    }
+
+    if let Some(borrow_rate) = borrow_rate_opt {
+        msg!(
+            "Borrow rate: old - {:?}, new - {:?}",
+            market.borrow_rate,
+            borrow_rate
+        );
+        market.borrow_rate = I80F48::from_num(borrow_rate);
+    }
+
+    require_msg!(
+        market.lend_rate >= 0 && market.borrow_rate >= market.lend_rate,
+        "rates must satisfy 0 <= lend_rate ({}) <= borrow_rate ({})",
+        market.lend_rate,
+        market.borrow_rate
+    );
+
+    if let Some(max_lent_native) = max_lent_native_opt {
+        msg!(
+            "Max lent native: old - {:?}, new - {:?}",
+            market.max_lent_native,
+            max_lent_native
+        );
+        market.max_lent_native = max_lent_native;
+    }
+
+    if let Some(reduce_only) = reduce_only_opt {
+        msg!(
+            "Reduce only: old - {:?}, new - {:?}",
+            market.reduce_only,
+            u8::from(reduce_only)
+        );
+        market.reduce_only = u8::from(reduce_only);
+    }
+
+    Ok(())
+}
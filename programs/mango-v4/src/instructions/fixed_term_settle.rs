@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use fixed::types::I80F48;
+
+use crate::accounts_ix::*;
+use crate::error::*;
+use crate::health::*;
+use crate::logs::{emit_stack, FixedTermSettleLog, TokenBalanceLog};
+use crate::state::*;
+use crate::util::clock_now;
+
+/// Moves the amounts owed at maturity of a fixed term position into its token position
+/// and closes the fixed term position.
+///
+/// Before maturity, only the borrowed side of a liquidatable account can be settled. The
+/// borrower pays the full amount owed at maturity, and the resulting token borrow can then
+/// be reduced by token liquidation.
+pub fn fixed_term_settle(ctx: Context<FixedTermSettle>) -> Result<()> {
+    let (now_ts, now_slot) = clock_now();
+
+    let mut account = ctx.accounts.account.load_full_mut()?;
+
+    let (is_matured, market_index) = {
+        let market = ctx.accounts.fixed_term_market.load()?;
+        (market.is_matured(now_ts), market.market_index)
+    };
+    if !is_matured {
+        let position = account.fixed_term_position(market_index)?;
+        require_msg_typed!(
+            position.borrowed_at_maturity_native > 0,
+            MangoError::FixedTermMarketNotMatured,
+            "fixed term market {} matures at {}, now is {}",
+            market_index,
+            position.maturity_timestamp,
+            now_ts
+        );
+
+        let retriever = new_fixed_order_account_retriever(
+            ctx.remaining_accounts,
+            &account.borrow(),
+            (now_ts, now_slot),
+        )?;
+        let health_cache = new_health_cache(&account.borrow(), &retriever, now_ts)
+            .context("create health cache")?;
+        require_msg_typed!(
+            account.check_liquidatable(&health_cache)? == CheckLiquidatable::Liquidatable,
+            MangoError::FixedTermMarketNotMatured,
+            "fixed term market {} is not matured and the account is not liquidatable",
+            market_index
+        );
+    }
+
+    let mut market = ctx.accounts.fixed_term_market.load_mut()?;
+    let mut bank = ctx.accounts.bank.load_mut()?;
+    let token_index = bank.token_index;
+
+    let mut position = *account.fixed_term_position(market.market_index)?;
+    let lent_at_maturity = if is_matured {
+        position.lent_native
+            + market
+                .lend_interest_at_maturity(position.lent_at_maturity_native - position.lent_native)
+    } else {
+        0
+    };
+
+    let (token_position, _) = account.token_position_mut(token_index)?;
+    let change =
+        I80F48::from(lent_at_maturity) - I80F48::from(position.borrowed_at_maturity_native);
+    if change.is_positive() {
+        bank.deposit(token_position, change, now_ts)?;
+    } else if change.is_negative() {
+        bank.withdraw_without_fee(token_position, -change, now_ts)?;
+    }
+
+    emit_stack(TokenBalanceLog {
+        mango_group: ctx.accounts.group.key(),
+        mango_account: ctx.accounts.account.key(),
+        token_index,
+        indexed_position: token_position.indexed_position.to_bits(),
+        deposit_index: bank.deposit_index.to_bits(),
+        borrow_index: bank.borrow_index.to_bits(),
+    });
+
+    market.total_borrowed_native -= position.borrowed_native;
+    market.total_borrowed_at_maturity_native -= position.borrowed_at_maturity_native;
+    bank.fixed_term_borrows_native = bank
+        .fixed_term_borrows_native
+        .saturating_sub(position.borrowed_at_maturity_native);
+    let borrowed_at_maturity = position.borrowed_at_maturity_native;
+
+    if is_matured {
+        market.total_lent_native -= position.lent_native;
+        market.total_lent_at_maturity_native -= position.lent_at_maturity_native;
+        account.deactivate_fixed_term_position(market.market_index)?;
+        account.token_decrement_dust_deactivate(&mut bank, now_ts, ctx.accounts.account.key())?;
+    } else {
+        position.borrowed_native = 0;
+        position.borrowed_at_maturity_native = 0;
+        if position.lent_native == 0 {
+            account.deactivate_fixed_term_position(market.market_index)?;
+            account.token_decrement_dust_deactivate(
+                &mut bank,
+                now_ts,
+                ctx.accounts.account.key(),
+            )?;
+        } else {
+            *account.fixed_term_position_mut(market.market_index)? = position;
+        }
+    }
+
+    emit_stack(FixedTermSettleLog {
+        mango_group: ctx.accounts.group.key(),
+        mango_account: ctx.accounts.account.key(),
+        market_index: market.market_index,
+        token_index,
+        lent_at_maturity,
+        borrowed_at_maturity,
+    });
+
+    Ok(())
+}
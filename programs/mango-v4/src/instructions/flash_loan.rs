@@ -38,6 +38,9 @@ pub fn flash_loan_begin<'key, 'accounts, 'remaining, 'info>(
         account.fixed.is_owner_or_delegate(*owner_pk),
         MangoError::SomeError
     );
+    account
+        .fixed
+        .check_delegate_permission(*owner_pk, DelegatePermission::Swap)?;
 
     require_eq!(remaining_accounts.len(), 3 * num_loans + 1);
     let banks = &remaining_accounts[..num_loans];
@@ -278,6 +281,9 @@ pub fn flash_loan_end<'key, 'accounts, 'remaining, 'info>(
         account.fixed.is_owner_or_delegate(ctx.accounts.owner.key()),
         MangoError::SomeError
     );
+    account
+        .fixed
+        .check_delegate_permission(ctx.accounts.owner.key(), DelegatePermission::Swap)?;
 
     let group = account.fixed.group;
 
@@ -419,15 +425,29 @@ pub fn flash_loan_end<'key, 'accounts, 'remaining, 'info>(
         // Sanity check
         health_cache.token_info_index(change.token_index)?;
 
-        oracle_prices.push(oracle_price);
+        // Borrows need the stricter risk-increasing freshness, but whether a change
+        // borrows is only known after fees are applied below
+        let risk_increasing_price = retriever
+            .bank_and_oracle_for(
+                &account.fixed.group,
+                change.bank_index,
+                change.token_index,
+                OracleFreshness::RiskIncreasing,
+            )
+            .map(|(_, price)| price);
+
+        oracle_prices.push((oracle_price, risk_increasing_price));
     }
     // Drop retriever as mut bank below uses health_ais
     drop(retriever);
 
     // Apply the vault diffs to the bank positions
     let mut deactivated_token_positions = vec![];
+    let mut borrowed_token_indexes = vec![];
     let mut token_loan_details = Vec::with_capacity(changes.len());
-    for (change, oracle_price) in changes.iter().zip(oracle_prices.iter()) {
+    for (change, (oracle_price, risk_increasing_price)) in
+        changes.iter().zip(oracle_prices.into_iter())
+    {
         let mut bank = health_ais[change.bank_index].load_mut::<Bank>()?;
 
         let position = account.token_position_mut_by_raw_index(change.raw_token_index);
@@ -477,8 +497,10 @@ pub fn flash_loan_end<'key, 'accounts, 'remaining, 'info>(
         }
 
         if change_amount < 0 && native_after_change < 0 {
+            borrowed_token_indexes.push(bank.token_index);
             bank.enforce_max_utilization_on_borrow()?;
-            bank.check_net_borrows(*oracle_price)?;
+            bank.check_net_borrows(risk_increasing_price?)?;
+            bank.check_borrow_limit()?;
         } else {
             bank.enforce_borrows_lte_deposits()?;
         }
@@ -530,8 +552,15 @@ pub fn flash_loan_end<'key, 'accounts, 'remaining, 'info>(
         &retriever,
         now_ts,
     )?;
+    for &token_index in borrowed_token_indexes.iter() {
+        health_cache.check_isolated_collateral_allows_borrow(token_index)?;
+    }
     account.check_health_post(&health_cache, pre_init_health)?;
 
+    // Drop retriever as the collateral banks are updated mutably
+    drop(retriever);
+    account.update_isolated_debt(&health_cache, health_ais)?;
+
     // Deactivate inactive token accounts after health check
     for raw_token_index in deactivated_token_positions {
         account.deactivate_token_position_and_log(raw_token_index, ctx.accounts.account.key());
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::accounts_zerocopy::*;
+use crate::error::*;
+use crate::state::*;
+
+/// Creates, changes or removes (with empty token_indexes) an e-mode category.
+///
+/// Remaining accounts: the banks of all tokens that were or will be in the category,
+/// writable. The category weights are copied to them.
+#[allow(clippy::too_many_arguments)]
+pub fn group_edit_emode_category(
+    ctx: Context<GroupEdit>,
+    category: u8,
+    token_indexes: Vec<TokenIndex>,
+    maint_asset_weight: f32,
+    init_asset_weight: f32,
+    maint_liab_weight: f32,
+    init_liab_weight: f32,
+) -> Result<()> {
+    let group_key = ctx.accounts.group.key();
+    let mut group = ctx.accounts.group.load_mut()?;
+
+    require_msg_typed!(
+        category >= 1 && category as usize <= EMODE_CATEGORY_COUNT,
+        MangoError::InvalidEmodeCategory,
+        "category id must be between 1 and {}",
+        EMODE_CATEGORY_COUNT
+    );
+    require_msg_typed!(
+        token_indexes.len() <= EMODE_CATEGORY_MAX_TOKENS,
+        MangoError::InvalidEmodeCategory,
+        "at most {} tokens per category",
+        EMODE_CATEGORY_MAX_TOKENS
+    );
+
+    let old = group.emode_categories[category as usize - 1];
+    let mut new = EmodeCategory {
+        token_indexes: [TokenIndex::MAX; EMODE_CATEGORY_MAX_TOKENS],
+        token_count: token_indexes.len() as u8,
+        padding: Default::default(),
+        maint_asset_weight,
+        init_asset_weight,
+        maint_liab_weight,
+        init_liab_weight,
+        reserved: [0; 28],
+    };
+    new.token_indexes[..token_indexes.len()].copy_from_slice(&token_indexes);
+    new.verify()?;
+    msg!("E-mode category {} old {:?}, new {:?}", category, old, new);
+
+    // Every token that enters or leaves the category needs its bank updated
+    let mut updated = vec![false; old.token_indexes().len() + new.token_indexes().len()];
+    for ai in ctx.remaining_accounts.iter() {
+        let mut bank = ai.load_mut::<Bank>()?;
+        require_keys_eq!(bank.group, group_key);
+
+        if new.contains(bank.token_index) {
+            require_msg_typed!(
+                bank.emode_category == 0 || bank.emode_category == category,
+                MangoError::InvalidEmodeCategory,
+                "token {} is already in e-mode category {}",
+                bank.token_index,
+                bank.emode_category
+            );
+            bank.set_emode_category(Some((category, &new)));
+        } else if bank.emode_category == category {
+            bank.set_emode_category(None);
+        } else {
+            return Err(error_msg!(
+                "bank for token {} is not affected by the change",
+                bank.token_index
+            ));
+        }
+
+        for (token_index, is_updated) in old
+            .token_indexes()
+            .iter()
+            .chain(new.token_indexes().iter())
+            .zip(updated.iter_mut())
+        {
+            *is_updated |= *token_index == bank.token_index;
+        }
+    }
+
+    if let Some(position) = updated.iter().position(|is_updated| !is_updated) {
+        let token_index = old
+            .token_indexes()
+            .iter()
+            .chain(new.token_indexes().iter())
+            .nth(position)
+            .unwrap();
+        return Err(error_msg!("missing bank for token {}", token_index));
+    }
+
+    group.emode_categories[category as usize - 1] = new;
+
+    Ok(())
+}
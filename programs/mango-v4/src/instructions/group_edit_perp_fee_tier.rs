@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::error::*;
+use crate::state::*;
+
+/// Creates, changes or removes (with min_volume_quote = 0) a perp fee tier.
+///
+/// Orders already on the book keep the tier id of their owner at placement time,
+/// the tier's new discounts apply to them as well.
+///
+/// The markets' base fees aren't known here, so a maker rebate that the lowest taker
+/// fee doesn't cover is capped when fees are computed, see
+/// PerpMarket::maker_fee_for_tier(). Markets check it on create and edit.
+pub fn group_edit_perp_fee_tier(
+    ctx: Context<GroupEdit>,
+    tier: u8,
+    min_volume_quote: u64,
+    maker_fee_discount: f32,
+    taker_fee_discount: f32,
+) -> Result<()> {
+    let mut group = ctx.accounts.group.load_mut()?;
+
+    require_msg_typed!(
+        tier >= 1 && tier as usize <= PERP_FEE_TIER_COUNT,
+        MangoError::InvalidPerpFeeTier,
+        "tier id must be between 1 and {}",
+        PERP_FEE_TIER_COUNT
+    );
+
+    let old = group.perp_fee_tiers[tier as usize - 1];
+    let new = PerpFeeTier {
+        min_volume_quote,
+        maker_fee_discount,
+        taker_fee_discount,
+        reserved: [0; 16],
+    };
+    new.verify()?;
+    msg!("Perp fee tier {} old {:?}, new {:?}", tier, old, new);
+
+    group.perp_fee_tiers[tier as usize - 1] = new;
+
+    Ok(())
+}
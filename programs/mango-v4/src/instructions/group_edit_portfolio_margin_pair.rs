@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::accounts_zerocopy::*;
+use crate::error::*;
+use crate::state::*;
+
+/// Links a perp market to a token with the same underlying for portfolio margining,
+/// or removes the link when token_index_opt is None.
+///
+/// Remaining accounts: the perp market, writable. The token index is copied to it.
+pub fn group_edit_portfolio_margin_pair(
+    ctx: Context<GroupEdit>,
+    perp_market_index: PerpMarketIndex,
+    token_index_opt: Option<TokenIndex>,
+) -> Result<()> {
+    let group_key = ctx.accounts.group.key();
+    let mut group = ctx.accounts.group.load_mut()?;
+
+    require_eq!(ctx.remaining_accounts.len(), 1);
+    let mut perp_market = ctx.remaining_accounts[0].load_mut::<PerpMarket>()?;
+    require_keys_eq!(perp_market.group, group_key);
+    require_eq!(perp_market.perp_market_index, perp_market_index);
+
+    if let Some(token_index) = token_index_opt {
+        require_msg!(
+            token_index != perp_market.settle_token_index,
+            "the settle token {} can't be a portfolio margin token",
+            token_index
+        );
+    }
+
+    let old = group.portfolio_margin_token_index(perp_market_index);
+    msg!(
+        "Portfolio margin token for perp market {}: old - {:?}, new - {:?}",
+        perp_market_index,
+        old,
+        token_index_opt
+    );
+
+    let existing = group
+        .portfolio_margin_pairs
+        .iter()
+        .position(|pair| pair.is_active() && pair.perp_market_index == perp_market_index);
+    let slot = match (existing, token_index_opt) {
+        (Some(slot), _) => slot,
+        (None, None) => return Ok(()),
+        (None, Some(_)) => group
+            .portfolio_margin_pairs
+            .iter()
+            .position(|pair| !pair.is_active())
+            .ok_or_else(|| {
+                error_msg!(
+                    "all {} portfolio margin pairs are in use",
+                    PORTFOLIO_MARGIN_PAIR_COUNT
+                )
+            })?,
+    };
+    group.portfolio_margin_pairs[slot] = PortfolioMarginPair {
+        token_index: token_index_opt.unwrap_or(0),
+        perp_market_index: if token_index_opt.is_some() {
+            perp_market_index
+        } else {
+            0
+        },
+        active: u8::from(token_index_opt.is_some()),
+        reserved: [0; 11],
+    };
+
+    perp_market.set_portfolio_margin_token_index(token_index_opt);
+
+    Ok(())
+}
@@ -20,6 +20,7 @@ pub fn health_region_begin<'key, 'accounts, 'remaining, 'info>(
         crate::instruction::PerpPlaceOrderV2::discriminator(),
         crate::instruction::PerpPlaceOrderPegged::discriminator(),
         crate::instruction::PerpPlaceOrderPeggedV2::discriminator(),
+        crate::instruction::PerpPlaceSpreadOrder::discriminator(),
         crate::instruction::Serum3CancelAllOrders::discriminator(),
         crate::instruction::Serum3CancelOrder::discriminator(),
         crate::instruction::Serum3PlaceOrder::discriminator(),
@@ -114,7 +115,17 @@ pub fn health_region_end<'key, 'accounts, 'remaining, 'info>(
 
     let pre_init_health = I80F48::from(account.fixed.health_region_begin_init_health);
     account.check_health_post(&health_cache, pre_init_health)?;
+
+    // The isolated bucket healths at the start of the region aren't known, so the fresh
+    // health cache can't tell whether they decreased: require them to be healthy.
+    require!(
+        !health_cache.has_unhealthy_isolated_perp(HealthType::Init),
+        MangoError::IsolatedPerpHealthMustBePositiveOrIncrease
+    );
     account.fixed.health_region_begin_init_health = 0;
 
+    drop(account_retriever);
+    account.update_isolated_debt(&health_cache, ctx.remaining_accounts)?;
+
     Ok(())
 }
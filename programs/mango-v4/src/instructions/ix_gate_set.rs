@@ -99,6 +99,30 @@ pub fn ix_gate_set(ctx: Context<IxGateSet>, ix_gate: u128) -> Result<()> {
     log_if_changed(&group, ix_gate, IxGate::SequenceCheck);
     log_if_changed(&group, ix_gate, IxGate::HealthCheck);
     log_if_changed(&group, ix_gate, IxGate::GroupChangeInsuranceFund);
+    log_if_changed(&group, ix_gate, IxGate::FixedTermMarketCreate);
+    log_if_changed(&group, ix_gate, IxGate::FixedTermLend);
+    log_if_changed(&group, ix_gate, IxGate::FixedTermBorrow);
+    log_if_changed(&group, ix_gate, IxGate::FixedTermSettle);
+    log_if_changed(&group, ix_gate, IxGate::PerpScheduledOrderCreate);
+    log_if_changed(&group, ix_gate, IxGate::PerpScheduledOrderCancel);
+    log_if_changed(&group, ix_gate, IxGate::PerpScheduledOrderTrigger);
+    log_if_changed(&group, ix_gate, IxGate::PerpConditionalOrderCreate);
+    log_if_changed(&group, ix_gate, IxGate::PerpPlaceSpreadOrder);
+    log_if_changed(&group, ix_gate, IxGate::AccountSetPortfolioMargin);
+    log_if_changed(&group, ix_gate, IxGate::PerpSetIsolated);
+    log_if_changed(&group, ix_gate, IxGate::PerpIsolatedTransfer);
+    log_if_changed(&group, ix_gate, IxGate::TokenTransfer);
+    log_if_changed(&group, ix_gate, IxGate::AccountEditDelegateScope);
+    log_if_changed(&group, ix_gate, IxGate::OracleAggregatorCreate);
+    log_if_changed(&group, ix_gate, IxGate::OracleAggregatorEdit);
+    log_if_changed(&group, ix_gate, IxGate::OracleAggregatorUpdate);
+    log_if_changed(&group, ix_gate, IxGate::ClmmTwapOracleCreate);
+    log_if_changed(&group, ix_gate, IxGate::ClmmTwapOracleUpdate);
+    log_if_changed(&group, ix_gate, IxGate::OpenbookV2MidOracleCreate);
+    log_if_changed(&group, ix_gate, IxGate::OpenbookV2MidOracleUpdate);
+    log_if_changed(&group, ix_gate, IxGate::AccountSetEmodeCategory);
+    log_if_changed(&group, ix_gate, IxGate::PerpResizeOrderbook);
+    log_if_changed(&group, ix_gate, IxGate::PerpClearOpeningAuction);
 
     group.ix_gate = ix_gate;
 
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::error::*;
+use crate::state::*;
+
+use super::emit_openbook_v2_balance_log;
+
+pub fn openbook_v2_cancel_all_orders(ctx: Context<OpenbookV2CancelOrder>, limit: u8) -> Result<()> {
+    let openbook_market = ctx.accounts.openbook_v2_market.load()?;
+
+    //
+    // Validation
+    //
+    {
+        require!(
+            ctx.accounts
+                .group
+                .load()?
+                .is_ix_enabled(IxGate::OpenbookV2CancelOrder),
+            MangoError::IxIsDisabled
+        );
+
+        let account = ctx.accounts.account.load_full()?;
+        // account constraint #1
+        require!(
+            account
+                .fixed
+                .is_owner_or_delegate(ctx.accounts.authority.key()),
+            MangoError::SomeError
+        );
+        account.fixed.check_delegate_permission(
+            ctx.accounts.authority.key(),
+            DelegatePermission::SpotCancel,
+        )?;
+        account.fixed.check_delegate_spot_market(
+            ctx.accounts.authority.key(),
+            openbook_market.market_index,
+        )?;
+
+        // Validate open_orders #2
+        require!(
+            account
+                .openbook_v2_orders(openbook_market.market_index)?
+                .open_orders
+                == ctx.accounts.open_orders.key(),
+            MangoError::SomeError
+        );
+    }
+
+    //
+    // Cancel
+    //
+    cpi_cancel_all_orders(ctx.accounts, limit)?;
+
+    emit_openbook_v2_balance_log(ctx.accounts, &openbook_market)?;
+
+    Ok(())
+}
+
+fn cpi_cancel_all_orders(ctx: &OpenbookV2CancelOrder, limit: u8) -> Result<()> {
+    let group = ctx.group.load()?;
+    let seeds = group_seeds!(group);
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.openbook_v2_program.to_account_info(),
+        openbook_v2::cpi::accounts::CancelOrder {
+            signer: ctx.group.to_account_info(),
+            open_orders_account: ctx.open_orders.to_account_info(),
+            market: ctx.openbook_v2_market_external.to_account_info(),
+            bids: ctx.bids.to_account_info(),
+            asks: ctx.asks.to_account_info(),
+        },
+        &[seeds],
+    );
+    openbook_v2::cpi::cancel_all_orders(cpi_ctx, None, limit)
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+
+use crate::error::*;
+use crate::state::*;
+
+use crate::accounts_ix::*;
+use crate::logs::{emit_stack, OpenbookV2OpenOrdersBalanceLog};
+use crate::openbook_v2_cpi::{load_open_orders_ref, OpenOrdersSlim};
+use crate::serum3_cpi::OpenOrdersAmounts;
+
+pub fn openbook_v2_cancel_order(
+    ctx: Context<OpenbookV2CancelOrder>,
+    _side: u8, // openbook v2 identifies orders by id alone
+    order_id: u128,
+) -> Result<()> {
+    let openbook_market = ctx.accounts.openbook_v2_market.load()?;
+
+    //
+    // Validation
+    //
+    {
+        require!(
+            ctx.accounts
+                .group
+                .load()?
+                .is_ix_enabled(IxGate::OpenbookV2CancelOrder),
+            MangoError::IxIsDisabled
+        );
+
+        let account = ctx.accounts.account.load_full()?;
+        // account constraint #1
+        require!(
+            account
+                .fixed
+                .is_owner_or_delegate(ctx.accounts.authority.key()),
+            MangoError::SomeError
+        );
+        account.fixed.check_delegate_permission(
+            ctx.accounts.authority.key(),
+            DelegatePermission::SpotCancel,
+        )?;
+        account.fixed.check_delegate_spot_market(
+            ctx.accounts.authority.key(),
+            openbook_market.market_index,
+        )?;
+
+        // Validate open_orders #2
+        require!(
+            account
+                .openbook_v2_orders(openbook_market.market_index)?
+                .open_orders
+                == ctx.accounts.open_orders.key(),
+            MangoError::SomeError
+        );
+    }
+
+    //
+    // Cancel
+    //
+    cpi_cancel_order(ctx.accounts, order_id)?;
+
+    emit_openbook_v2_balance_log(ctx.accounts, &openbook_market)?;
+
+    Ok(())
+}
+
+pub(crate) fn emit_openbook_v2_balance_log(
+    ctx: &OpenbookV2CancelOrder,
+    openbook_market: &OpenbookV2Market,
+) -> Result<()> {
+    let open_orders = load_open_orders_ref(ctx.open_orders.as_ref())?;
+    let market = ctx.openbook_v2_market_external.load()?;
+    let after_oo = OpenOrdersSlim::from_oo(&open_orders, &market);
+    emit_stack(OpenbookV2OpenOrdersBalanceLog {
+        mango_group: ctx.group.key(),
+        mango_account: ctx.account.key(),
+        market_index: openbook_market.market_index,
+        base_token_index: openbook_market.base_token_index,
+        quote_token_index: openbook_market.quote_token_index,
+        base_total: after_oo.native_base_total(),
+        base_free: after_oo.native_base_free(),
+        quote_total: after_oo.native_quote_total(),
+        quote_free: after_oo.native_quote_free(),
+        referrer_rebates_available: after_oo.native_rebates(),
+    });
+    Ok(())
+}
+
+fn cpi_cancel_order(ctx: &OpenbookV2CancelOrder, order_id: u128) -> Result<()> {
+    let group = ctx.group.load()?;
+    let seeds = group_seeds!(group);
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.openbook_v2_program.to_account_info(),
+        openbook_v2::cpi::accounts::CancelOrder {
+            signer: ctx.group.to_account_info(),
+            open_orders_account: ctx.open_orders.to_account_info(),
+            market: ctx.openbook_v2_market_external.to_account_info(),
+            bids: ctx.bids.to_account_info(),
+            asks: ctx.asks.to_account_info(),
+        },
+        &[seeds],
+    );
+    openbook_v2::cpi::cancel_order(cpi_ctx, order_id)
+}
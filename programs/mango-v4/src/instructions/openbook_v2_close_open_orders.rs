@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::error::MangoError;
+use crate::state::*;
+
+pub fn openbook_v2_close_open_orders(ctx: Context<OpenbookV2CloseOpenOrders>) -> Result<()> {
+    let openbook_market = ctx.accounts.openbook_v2_market.load()?;
+
+    //
+    // Validation
+    //
+    let mut account = ctx.accounts.account.load_full_mut()?;
+    // account constraint #1
+    require!(
+        account
+            .fixed
+            .is_owner_or_delegate(ctx.accounts.authority.key())
+            || openbook_market.is_force_close(),
+        MangoError::SomeError
+    );
+    if !openbook_market.is_force_close() {
+        account.fixed.check_delegate_permission(
+            ctx.accounts.authority.key(),
+            DelegatePermission::SpotCancel,
+        )?;
+    }
+
+    // Validate open_orders #2
+    require!(
+        account
+            .openbook_v2_orders(openbook_market.market_index)?
+            .open_orders
+            == ctx.accounts.open_orders.key(),
+        MangoError::SomeError
+    );
+
+    //
+    // close OO
+    //
+    cpi_close_open_orders(ctx.accounts)?;
+
+    // Reduce the in_use_count on the token positions - they no longer need to be forced open.
+    // We cannot immediately dust tiny positions because we don't have the banks.
+    let (base_position, _) = account.token_position_mut(openbook_market.base_token_index)?;
+    base_position.decrement_in_use();
+    let (quote_position, _) = account.token_position_mut(openbook_market.quote_token_index)?;
+    quote_position.decrement_in_use();
+
+    // Deactivate the open orders account itself
+    account.deactivate_openbook_v2_orders(openbook_market.market_index)?;
+
+    Ok(())
+}
+
+fn cpi_close_open_orders(ctx: &OpenbookV2CloseOpenOrders) -> Result<()> {
+    let group = ctx.group.load()?;
+    let seeds = group_seeds!(group);
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.openbook_v2_program.to_account_info(),
+        openbook_v2::cpi::accounts::CloseOpenOrdersAccount {
+            payer: ctx.payer.to_account_info(),
+            owner: ctx.group.to_account_info(),
+            open_orders_indexer: ctx.open_orders_indexer.to_account_info(),
+            open_orders_account: ctx.open_orders.to_account_info(),
+            sol_destination: ctx.sol_destination.to_account_info(),
+            system_program: ctx.system_program.to_account_info(),
+        },
+        &[seeds],
+    );
+    openbook_v2::cpi::close_open_orders_account(cpi_ctx)
+}
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::error::*;
+use crate::state::*;
+
+pub fn openbook_v2_create_open_orders(
+    ctx: Context<OpenbookV2CreateOpenOrders>,
+    _account_num: u32,
+) -> Result<()> {
+    // The group owns one open orders indexer that tracks all open orders accounts
+    if ctx.accounts.open_orders_indexer.data_is_empty() {
+        cpi_create_open_orders_indexer(ctx.accounts)?;
+    }
+    cpi_create_open_orders_account(ctx.accounts)?;
+
+    let openbook_market = ctx.accounts.openbook_v2_market.load()?;
+
+    let mut account = ctx.accounts.account.load_full_mut()?;
+    // account constraint #1
+    require!(
+        account
+            .fixed
+            .is_owner_or_delegate(ctx.accounts.authority.key()),
+        MangoError::SomeError
+    );
+    account
+        .fixed
+        .check_delegate_permission(ctx.accounts.authority.key(), DelegatePermission::SpotTrade)?;
+    account
+        .fixed
+        .check_delegate_spot_market(ctx.accounts.authority.key(), openbook_market.market_index)?;
+
+    let open_orders_account = account.create_openbook_v2_orders(openbook_market.market_index)?;
+    open_orders_account.open_orders = ctx.accounts.open_orders.key();
+    open_orders_account.base_token_index = openbook_market.base_token_index;
+    open_orders_account.quote_token_index = openbook_market.quote_token_index;
+    {
+        // Needed for converting reserved lots to native amounts in health computations
+        let external_market = ctx.accounts.openbook_v2_market_external.load()?;
+        open_orders_account.base_lot_size = external_market.base_lot_size;
+        open_orders_account.quote_lot_size = external_market.quote_lot_size;
+    }
+
+    // Make it so that the token_account_map for the base and quote currency
+    // stay permanently blocked. Otherwise users may end up in situations where
+    // they can't settle a market because they don't have free token_account_map!
+    let (quote_position, _, _) =
+        account.ensure_token_position(openbook_market.quote_token_index)?;
+    quote_position.increment_in_use();
+    let (base_position, _, _) = account.ensure_token_position(openbook_market.base_token_index)?;
+    base_position.increment_in_use();
+
+    Ok(())
+}
+
+fn cpi_create_open_orders_indexer(ctx: &OpenbookV2CreateOpenOrders) -> Result<()> {
+    let group = ctx.group.load()?;
+    let seeds = group_seeds!(group);
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.openbook_v2_program.to_account_info(),
+        openbook_v2::cpi::accounts::CreateOpenOrdersIndexer {
+            payer: ctx.payer.to_account_info(),
+            owner: ctx.group.to_account_info(),
+            open_orders_indexer: ctx.open_orders_indexer.to_account_info(),
+            system_program: ctx.system_program.to_account_info(),
+        },
+        &[seeds],
+    );
+    openbook_v2::cpi::create_open_orders_indexer(cpi_ctx)
+}
+
+fn cpi_create_open_orders_account(ctx: &OpenbookV2CreateOpenOrders) -> Result<()> {
+    let group = ctx.group.load()?;
+    let name = ctx.openbook_v2_market.load()?.name().to_string();
+    let seeds = group_seeds!(group);
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.openbook_v2_program.to_account_info(),
+        openbook_v2::cpi::accounts::CreateOpenOrdersAccount {
+            payer: ctx.payer.to_account_info(),
+            owner: ctx.group.to_account_info(),
+            delegate_account: None,
+            open_orders_indexer: ctx.open_orders_indexer.to_account_info(),
+            open_orders_account: ctx.open_orders.to_account_info(),
+            market: ctx.openbook_v2_market_external.to_account_info(),
+            system_program: ctx.system_program.to_account_info(),
+        },
+        &[seeds],
+    );
+    openbook_v2::cpi::create_open_orders_account(cpi_ctx, name)
+}
@@ -0,0 +1,6 @@
+use crate::accounts_ix::*;
+use anchor_lang::prelude::*;
+
+pub fn openbook_v2_deregister_market(_ctx: Context<OpenbookV2DeregisterMarket>) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::error::*;
+use crate::state::*;
+use crate::util::clock_now;
+
+pub fn openbook_v2_mid_oracle_create(ctx: Context<OpenbookV2MidOracleCreate>) -> Result<()> {
+    let market = ctx.accounts.market.load()?;
+    require!(
+        quote_mint_oracle(&market.quote_mint).is_ok(),
+        MangoError::MissingFeedForCLMMOracle
+    );
+
+    let mut oracle = ctx.accounts.oracle.load_init()?;
+    oracle.group = ctx.accounts.group.key();
+    oracle.mint = market.base_mint;
+    oracle.quote_mint = market.quote_mint;
+    oracle.market = ctx.accounts.market.key();
+    oracle.bids = market.bids;
+    oracle.asks = market.asks;
+
+    let (now_ts, now_slot) = clock_now();
+    oracle.update(
+        &market,
+        &*ctx.accounts.bids.load()?,
+        &*ctx.accounts.asks.load()?,
+        now_ts,
+        now_slot,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::util::clock_now;
+
+pub fn openbook_v2_mid_oracle_update(ctx: Context<OpenbookV2MidOracleUpdate>) -> Result<()> {
+    let (now_ts, now_slot) = clock_now();
+    let market = ctx.accounts.market.load()?;
+
+    let mut oracle = ctx.accounts.oracle.load_mut()?;
+    oracle.update(
+        &market,
+        &*ctx.accounts.bids.load()?,
+        &*ctx.accounts.asks.load()?,
+        now_ts,
+        now_slot,
+    )?;
+    msg!(
+        "mid price: {}, half spread: {}",
+        oracle.price,
+        oracle.deviation
+    );
+
+    Ok(())
+}
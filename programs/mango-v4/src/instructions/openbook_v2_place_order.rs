@@ -0,0 +1,702 @@
+use crate::accounts_zerocopy::*;
+use crate::error::*;
+use crate::health::*;
+use crate::i80f48::ClampToInt;
+use crate::state::*;
+
+use crate::accounts_ix::*;
+use crate::logs::{emit_stack, OpenbookV2OpenOrdersBalanceLog, TokenBalanceLog};
+use crate::openbook_v2_cpi::{load_open_orders_ref, OpenOrdersSlim};
+use crate::serum3_cpi::OpenOrdersAmounts;
+use crate::util::clock_now;
+use anchor_lang::prelude::*;
+
+use fixed::types::I80F48;
+use openbook_v2::state::{PlaceOrderType, SelfTradeBehavior, Side};
+
+#[allow(clippy::too_many_arguments)]
+pub fn openbook_v2_place_order<'info>(
+    accounts: &mut OpenbookV2PlaceOrder<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    side: u8,
+    limit_price_lots: u64,
+    max_base_qty: u64,
+    max_native_quote_qty_including_fees: u64,
+    self_trade_behavior: u8,
+    order_type: u8,
+    client_order_id: u64,
+    limit: u16,
+) -> Result<()> {
+    let side = Side::try_from(side).map_err(|_| error_msg!("invalid side {}", side))?;
+    let self_trade_behavior = SelfTradeBehavior::try_from(self_trade_behavior)
+        .map_err(|_| error_msg!("invalid self trade behavior {}", self_trade_behavior))?;
+    let order_type = PlaceOrderType::try_from(order_type)
+        .map_err(|_| error_msg!("invalid order type {}", order_type))?;
+
+    // Also required by openbook_v2's place order
+    require_gt!(limit_price_lots, 0);
+
+    let openbook_market = accounts.openbook_v2_market.load()?;
+    require!(
+        !openbook_market.is_reduce_only(),
+        MangoError::MarketInReduceOnlyMode
+    );
+
+    //
+    // Validation
+    //
+    let receiver_token_index;
+    let payer_token_index;
+    {
+        let account = accounts.account.load_full()?;
+        // account constraint #1
+        require!(
+            account.fixed.is_owner_or_delegate(accounts.authority.key()),
+            MangoError::SomeError
+        );
+        account
+            .fixed
+            .check_delegate_permission(accounts.authority.key(), DelegatePermission::SpotTrade)?;
+        account
+            .fixed
+            .check_delegate_spot_market(accounts.authority.key(), openbook_market.market_index)?;
+
+        // Validate open_orders #2
+        require!(
+            account
+                .openbook_v2_orders(openbook_market.market_index)?
+                .open_orders
+                == accounts.open_orders.key(),
+            MangoError::SomeError
+        );
+
+        // Validate bank and vault #3
+        let payer_bank = accounts.payer_bank.load()?;
+        require_keys_eq!(payer_bank.vault, accounts.payer_vault.key());
+        payer_token_index = match side {
+            Side::Bid => openbook_market.quote_token_index,
+            Side::Ask => openbook_market.base_token_index,
+        };
+        require_eq!(payer_bank.token_index, payer_token_index);
+
+        receiver_token_index = match side {
+            Side::Bid => openbook_market.base_token_index,
+            Side::Ask => openbook_market.quote_token_index,
+        };
+    }
+
+    //
+    // Pre-health computation
+    //
+    let mut account = accounts.account.load_full_mut()?;
+    let (now_ts, now_slot) = clock_now();
+    let retriever = new_fixed_order_account_retriever_with_optional_banks(
+        remaining_accounts,
+        &account.borrow(),
+        (now_ts, now_slot),
+    )?;
+    let mut health_cache = new_health_cache_skipping_missing_banks_and_bad_oracles(
+        &account.borrow(),
+        &retriever,
+        now_ts,
+    )
+    .context("pre init health")?;
+
+    // The payer and receiver token banks/oracles must be passed and be valid
+    health_cache.token_info_index(payer_token_index)?;
+    health_cache.token_info_index(receiver_token_index)?;
+
+    let pre_health_opt = if !account.fixed.is_in_health_region() {
+        let pre_init_health = account.check_health_pre(&health_cache)?;
+        Some(pre_init_health)
+    } else {
+        None
+    };
+
+    // Check if the bank for the token whose balance is increased is in reduce-only mode
+    let receiver_bank_ai;
+    let receiver_bank_reduce_only;
+    {
+        // The token position already exists, but we need the active_index.
+        let (_, _, active_index) = account.ensure_token_position(receiver_token_index)?;
+        let group_key = accounts.group.key();
+        let (receiver_bank, _) =
+            retriever.bank_and_oracle(&group_key, active_index, receiver_token_index)?;
+        receiver_bank_reduce_only = receiver_bank.are_deposits_reduce_only();
+
+        // The fixed_order account retriever can't give us mut references, so use the above
+        // call to .bank_and_oracle() as validation and then copy out the matching AccountInfo.
+        receiver_bank_ai = remaining_accounts[active_index].clone();
+        // Double-check that we got the right account
+        let receiver_bank2 = receiver_bank_ai.load::<Bank>()?;
+        assert_eq!(receiver_bank2.group, group_key);
+        assert_eq!(receiver_bank2.token_index, receiver_token_index);
+    }
+
+    drop(retriever);
+
+    require_msg_typed!(
+        receiver_bank_ai.is_writable,
+        MangoError::HealthAccountBankNotWritable,
+        "the receiver bank (token index {}) in the health account list must be writable",
+        receiver_token_index
+    );
+
+    //
+    // Before-order tracking
+    //
+
+    let before_vault = accounts.payer_vault.amount;
+
+    let before_had_bids;
+    let before_had_asks;
+    let before_oo = {
+        let open_orders = load_open_orders_ref(accounts.open_orders.as_ref())?;
+        let market = accounts.openbook_v2_market_external.load()?;
+        before_had_bids = open_orders.position.bids_base_lots != 0;
+        before_had_asks = open_orders.position.asks_base_lots != 0;
+        OpenOrdersSlim::from_oo(&open_orders, &market)
+    };
+
+    // Provide a readable error message in case the vault doesn't have enough tokens
+    let base_lot_size;
+    let quote_lot_size;
+    {
+        let market = accounts.openbook_v2_market_external.load()?;
+        base_lot_size = market.base_lot_size as u64;
+        quote_lot_size = market.quote_lot_size as u64;
+
+        // Quote value of the order at its limit price, for the delegate notional limit
+        let mut notional = I80F48::from(max_base_qty)
+            .saturating_mul(I80F48::from(limit_price_lots))
+            .saturating_mul(I80F48::from(quote_lot_size));
+        if matches!(side, Side::Bid) {
+            notional = notional.min(I80F48::from(max_native_quote_qty_including_fees));
+        }
+        account
+            .fixed
+            .check_delegate_order_notional(accounts.authority.key(), notional)?;
+
+        let needed_amount = match side {
+            Side::Ask => max_base_qty
+                .checked_mul(base_lot_size)
+                .ok_or_else(|| {
+                    error_msg_typed!(
+                        MangoError::MathError,
+                        "base quantity {} lots of size {} overflows",
+                        max_base_qty,
+                        base_lot_size
+                    )
+                })?
+                .saturating_sub(before_oo.native_base_free()),
+            Side::Bid => {
+                max_native_quote_qty_including_fees.saturating_sub(before_oo.native_quote_free())
+            }
+        };
+        if before_vault < needed_amount {
+            return err!(MangoError::InsufficentBankVaultFunds).with_context(|| {
+                format!(
+                    "bank vault does not have enough tokens, need {} but have {}",
+                    needed_amount, before_vault
+                )
+            });
+        }
+    }
+
+    //
+    // Apply the order to openbook_v2
+    //
+    let order = openbook_v2::PlaceOrderArgs {
+        side,
+        price_lots: limit_price_lots
+            .try_into()
+            .map_err(|_| error_msg!("limit price {} lots is too large", limit_price_lots))?,
+        max_base_lots: max_base_qty
+            .try_into()
+            .map_err(|_| error_msg!("max base quantity {} lots is too large", max_base_qty))?,
+        max_quote_lots_including_fees: (max_native_quote_qty_including_fees / quote_lot_size)
+            .try_into()
+            .map_err(|_| {
+                error_msg!(
+                    "max quote quantity {} native is too large",
+                    max_native_quote_qty_including_fees
+                )
+            })?,
+        client_order_id,
+        order_type,
+        expiry_timestamp: 0,
+        self_trade_behavior,
+        limit: limit.min(u8::MAX.into()) as u8,
+    };
+    let posted_order_id = cpi_place_order(accounts, order)?;
+
+    //
+    // After-order tracking
+    //
+    let after_oo = {
+        let open_orders = load_open_orders_ref(accounts.open_orders.as_ref())?;
+        let market = accounts.openbook_v2_market_external.load()?;
+        OpenOrdersSlim::from_oo(&open_orders, &market)
+    };
+    let oo_difference = OpenbookV2OODifference::new(&before_oo, &after_oo);
+
+    //
+    // Track the highest bid and lowest ask, to be able to evaluate worst-case health even
+    // when they cross the oracle
+    //
+    let openbook = account.openbook_v2_orders_mut(openbook_market.market_index)?;
+    if !before_had_bids {
+        // The 0 state means uninitialized/no value
+        openbook.highest_placed_bid_inv = 0.0;
+        openbook.lowest_placed_bid_inv = 0.0;
+    }
+    if !before_had_asks {
+        openbook.lowest_placed_ask = 0.0;
+        openbook.highest_placed_ask = 0.0;
+    }
+    // in the normal quote per base units
+    let limit_price = limit_price_lots as f64 * quote_lot_size as f64 / base_lot_size as f64;
+
+    // openbook_v2 returns the order id only if the order was posted to the book
+    if posted_order_id.is_some() {
+        match side {
+            Side::Ask => {
+                openbook.lowest_placed_ask = if openbook.lowest_placed_ask == 0.0 {
+                    limit_price
+                } else {
+                    openbook.lowest_placed_ask.min(limit_price)
+                };
+                openbook.highest_placed_ask = if openbook.highest_placed_ask == 0.0 {
+                    limit_price
+                } else {
+                    openbook.highest_placed_ask.max(limit_price)
+                }
+            }
+            Side::Bid => {
+                // in base per quote units, to avoid a division in health
+                let limit_price_inv = 1.0 / limit_price;
+                openbook.highest_placed_bid_inv = if openbook.highest_placed_bid_inv == 0.0 {
+                    limit_price_inv
+                } else {
+                    // the highest bid has the lowest _inv value
+                    openbook.highest_placed_bid_inv.min(limit_price_inv)
+                };
+                openbook.lowest_placed_bid_inv = if openbook.lowest_placed_bid_inv == 0.0 {
+                    limit_price_inv
+                } else {
+                    // lowest bid has max _inv value
+                    openbook.lowest_placed_bid_inv.max(limit_price_inv)
+                }
+            }
+        }
+    }
+
+    emit_stack(OpenbookV2OpenOrdersBalanceLog {
+        mango_group: accounts.group.key(),
+        mango_account: accounts.account.key(),
+        market_index: openbook_market.market_index,
+        base_token_index: openbook_market.base_token_index,
+        quote_token_index: openbook_market.quote_token_index,
+        base_total: after_oo.native_base_total(),
+        base_free: after_oo.native_base_free(),
+        quote_total: after_oo.native_quote_total(),
+        quote_free: after_oo.native_quote_free(),
+        referrer_rebates_available: after_oo.native_rebates(),
+    });
+
+    accounts.payer_vault.reload()?;
+    let after_vault = accounts.payer_vault.amount;
+
+    // Placing an order cannot increase vault balance
+    require_gte!(before_vault, after_vault);
+
+    let mut payer_bank = accounts.payer_bank.load_mut()?;
+
+    // Update the potential token tracking in banks
+    // (for init weight scaling, deposit limit checks)
+    {
+        let mut receiver_bank = receiver_bank_ai.load_mut::<Bank>()?;
+        let (base_bank, quote_bank) = match side {
+            Side::Bid => (&mut receiver_bank, &mut payer_bank),
+            Side::Ask => (&mut payer_bank, &mut receiver_bank),
+        };
+        update_bank_potential_tokens(openbook, base_bank, quote_bank, &after_oo);
+    }
+
+    // Track position before withdraw happens
+    let before_position_native = account
+        .token_position_mut(payer_bank.token_index)?
+        .0
+        .native(&payer_bank);
+
+    // Charge the difference in vault balance to the user's account
+    // (must be done before limit checks like deposit limit)
+    let vault_difference = apply_vault_difference(
+        accounts.account.key(),
+        &mut account.borrow_mut(),
+        openbook_market.market_index,
+        &mut payer_bank,
+        after_vault,
+        before_vault,
+    )?;
+
+    // Deposit limit check, receiver side:
+    // Placing an order can always increase the receiver bank deposits on fill.
+    {
+        let receiver_bank = receiver_bank_ai.load::<Bank>()?;
+        receiver_bank
+            .check_deposit_and_oo_limit()
+            .with_context(|| std::format!("on {}", receiver_bank.name()))?;
+    }
+
+    // Payer bank safety checks like reduce-only, net borrows, vault-to-deposits ratio
+    let payer_oracle_ref = &AccountInfoRef::borrow(&accounts.payer_oracle)?;
+    let payer_bank_oracle = payer_bank.oracle_price_for(
+        &OracleAccountInfos::from_reader(payer_oracle_ref),
+        Some((now_ts, now_slot)),
+        OracleFreshness::RiskIncreasing,
+    )?;
+    let withdrawn_from_vault = I80F48::from(before_vault - after_vault);
+    let is_borrow = withdrawn_from_vault > before_position_native;
+    if is_borrow {
+        require_msg_typed!(
+            !payer_bank.are_borrows_reduce_only(),
+            MangoError::TokenInReduceOnlyMode,
+            "the payer tokens cannot be borrowed"
+        );
+        payer_bank.enforce_max_utilization_on_borrow()?;
+        payer_bank.check_net_borrows(payer_bank_oracle)?;
+        payer_bank.check_borrow_limit()?;
+
+        // Deposit limit check, payer side:
+        // The payer bank deposits could increase when cancelling the order later,
+        // see serum3_place_order.
+        payer_bank
+            .check_deposit_and_oo_limit()
+            .with_context(|| std::format!("on {}", payer_bank.name()))?;
+    } else {
+        payer_bank.enforce_borrows_lte_deposits()?;
+    }
+
+    // Health cache updates for the changed account state
+    let receiver_bank = receiver_bank_ai.load::<Bank>()?;
+    // update scaled weights for receiver bank
+    health_cache.adjust_token_balance(&receiver_bank, I80F48::ZERO)?;
+    health_cache.adjust_token_balance(&payer_bank, vault_difference)?;
+    let openbook_account = account.openbook_v2_orders(openbook_market.market_index)?;
+    oo_difference.recompute_health_cache_openbook_v2_state(
+        &mut health_cache,
+        openbook_account,
+        &after_oo,
+    )?;
+
+    // Check the receiver's reduce only flag.
+    //
+    // Note that all orders on the book executing can still cause a net deposit. That's because
+    // the total spot potential amount assumes all reserved amounts convert at the current
+    // oracle price.
+    //
+    // This also requires that all serum3 and openbook_v2 oos that touch the receiver_token
+    // are avaliable in the health cache. We make this a general requirement to avoid surprises.
+    for serum3 in account.active_serum3_orders() {
+        if serum3.base_token_index == receiver_token_index
+            || serum3.quote_token_index == receiver_token_index
+        {
+            require_msg!(
+                health_cache.serum3_infos.iter().any(|s3| s3.market_index == serum3.market_index),
+                "health cache is missing serum3 info {} involving receiver token {}; passed banks and oracles?",
+                serum3.market_index, receiver_token_index
+            );
+        }
+    }
+    for openbook in account.active_openbook_v2_orders() {
+        if openbook.base_token_index == receiver_token_index
+            || openbook.quote_token_index == receiver_token_index
+        {
+            require_msg!(
+                health_cache.openbook_v2_infos.iter().any(|obv2| obv2.market_index == openbook.market_index),
+                "health cache is missing openbook v2 info {} involving receiver token {}; passed banks and oracles?",
+                openbook.market_index, receiver_token_index
+            );
+        }
+    }
+    if receiver_bank_reduce_only {
+        let balance = health_cache.token_info(receiver_token_index)?.balance_spot;
+        let potential =
+            health_cache.total_spot_potential(HealthType::Maint, receiver_token_index)?;
+        require_msg_typed!(
+            balance + potential < 1,
+            MangoError::TokenInReduceOnlyMode,
+            "receiver bank does not accept deposits"
+        );
+    }
+
+    //
+    // Health check
+    //
+    if is_borrow {
+        health_cache.check_isolated_collateral_allows_borrow(payer_token_index)?;
+    }
+    if let Some(pre_init_health) = pre_health_opt {
+        account.check_health_post(&health_cache, pre_init_health)?;
+
+        // The payer and receiver banks may be collateral banks that get updated
+        drop(payer_bank);
+        drop(receiver_bank);
+        account.update_isolated_debt(&health_cache, remaining_accounts)?;
+    }
+
+    Ok(())
+}
+
+pub struct OpenbookV2OODifference {
+    free_base_change: I80F48,
+    free_quote_change: I80F48,
+}
+
+impl OpenbookV2OODifference {
+    pub fn new(before_oo: &OpenOrdersSlim, after_oo: &OpenOrdersSlim) -> Self {
+        Self {
+            free_base_change: I80F48::from(after_oo.native_base_free())
+                - I80F48::from(before_oo.native_base_free()),
+            free_quote_change: I80F48::from(after_oo.native_quote_free())
+                - I80F48::from(before_oo.native_quote_free()),
+        }
+    }
+
+    pub fn recompute_health_cache_openbook_v2_state(
+        &self,
+        health_cache: &mut HealthCache,
+        openbook_account: &OpenbookV2Orders,
+        open_orders: &OpenOrdersSlim,
+    ) -> Result<()> {
+        health_cache.recompute_openbook_v2_info(
+            openbook_account,
+            open_orders,
+            self.free_base_change,
+            self.free_quote_change,
+        )
+    }
+}
+
+/// Called in apply_openbook_v2_settle_changes() and place_order to adjust token positions
+/// after changing the vault balances
+/// Also logs changes to token balances
+///
+/// Returns the native change of the token position.
+fn apply_vault_difference(
+    account_pk: Pubkey,
+    account: &mut MangoAccountRefMut,
+    market_index: OpenbookV2MarketIndex,
+    bank: &mut Bank,
+    vault_after: u64,
+    vault_before: u64,
+) -> Result<I80F48> {
+    let needed_change = I80F48::from(vault_after) - I80F48::from(vault_before);
+
+    let (position, _) = account.token_position_mut(bank.token_index)?;
+    let native_before = position.native(bank);
+    let now_ts = Clock::get()?.unix_timestamp.try_into().unwrap();
+    if needed_change >= 0 {
+        bank.deposit(position, needed_change, now_ts)?;
+    } else {
+        bank.withdraw_without_fee(position, -needed_change, now_ts)?;
+    }
+    let native_after = position.native(bank);
+    let native_change = native_after - native_before;
+    // amount of tokens transfered to the open orders account that were borrowed
+    let new_borrows = native_change
+        .max(native_after)
+        .min(I80F48::ZERO)
+        .abs()
+        .to_num::<u64>();
+
+    let indexed_position = position.indexed_position;
+    let market = account.openbook_v2_orders_mut(market_index).unwrap();
+    let borrows_without_fee;
+    if bank.token_index == market.base_token_index {
+        borrows_without_fee = &mut market.base_borrows_without_fee;
+    } else if bank.token_index == market.quote_token_index {
+        borrows_without_fee = &mut market.quote_borrows_without_fee;
+    } else {
+        return Err(error_msg!(
+            "assert failed: apply_vault_difference called with bad token index"
+        ));
+    };
+
+    // Only for place: Add to potential borrow amount
+    *borrows_without_fee += new_borrows;
+
+    // Only for settle/liq_force_cancel: Reduce the potential borrow amounts
+    if needed_change > 0 {
+        *borrows_without_fee = (*borrows_without_fee).saturating_sub(needed_change.to_num::<u64>());
+    }
+
+    emit_stack(TokenBalanceLog {
+        mango_group: bank.group,
+        mango_account: account_pk,
+        token_index: bank.token_index,
+        indexed_position: indexed_position.to_bits(),
+        deposit_index: bank.deposit_index.to_bits(),
+        borrow_index: bank.borrow_index.to_bits(),
+    });
+
+    Ok(native_change)
+}
+
+/// Uses the changes in OpenOrders and vaults to adjust the user token position,
+/// collect fees and optionally adjusts the HealthCache.
+#[allow(clippy::too_many_arguments)]
+pub fn apply_openbook_v2_settle_changes(
+    group: &Group,
+    account_pk: Pubkey,
+    account: &mut MangoAccountRefMut,
+    base_bank: &mut Bank,
+    quote_bank: &mut Bank,
+    openbook_market: &OpenbookV2Market,
+    before_base_vault: u64,
+    before_quote_vault: u64,
+    before_oo: &OpenOrdersSlim,
+    after_base_vault: u64,
+    after_quote_vault: u64,
+    after_oo: &OpenOrdersSlim,
+    health_cache: Option<&mut HealthCache>,
+    fees_to_dao: bool,
+    quote_oracle: Option<&AccountInfo>,
+) -> Result<()> {
+    let mut received_fees = 0;
+    if fees_to_dao {
+        // Example: rebates go from 100 -> 10. That means we credit 90 in fees.
+        received_fees = before_oo
+            .native_rebates()
+            .saturating_sub(after_oo.native_rebates());
+        quote_bank.collected_fees_native += I80F48::from(received_fees);
+
+        // Credit the buyback_fees at the current value of the quote token.
+        if let Some(quote_oracle_ai) = quote_oracle {
+            let clock = Clock::get()?;
+            let now_ts = clock.unix_timestamp.try_into().unwrap();
+
+            let quote_oracle_ref = &AccountInfoRef::borrow(quote_oracle_ai)?;
+            let quote_oracle_price = quote_bank.oracle_price(
+                &OracleAccountInfos::from_reader(quote_oracle_ref),
+                Some((now_ts, clock.slot)),
+            )?;
+            let quote_asset_price = quote_oracle_price.min(quote_bank.stable_price());
+            account
+                .fixed
+                .expire_buyback_fees(now_ts, group.buyback_fees_expiry_interval);
+            let fees_in_usd = I80F48::from(received_fees) * quote_asset_price;
+            account
+                .fixed
+                .accrue_buyback_fees(fees_in_usd.clamp_to_u64());
+        }
+    }
+
+    // Don't count the referrer rebate fees as part of the vault change that should be
+    // credited to the user.
+    let after_quote_vault_adjusted = after_quote_vault - received_fees;
+
+    // Settle cannot decrease vault balances
+    require_gte!(after_base_vault, before_base_vault);
+    require_gte!(after_quote_vault_adjusted, before_quote_vault);
+
+    // Credit the difference in vault balances to the user's account
+    let base_difference = apply_vault_difference(
+        account_pk,
+        account,
+        openbook_market.market_index,
+        base_bank,
+        after_base_vault,
+        before_base_vault,
+    )?;
+    let quote_difference = apply_vault_difference(
+        account_pk,
+        account,
+        openbook_market.market_index,
+        quote_bank,
+        after_quote_vault_adjusted,
+        before_quote_vault,
+    )?;
+
+    // Tokens were moved from open orders into banks again: also update the tracking
+    // for potential_serum_tokens on the banks.
+    {
+        let openbook_orders = account.openbook_v2_orders_mut(openbook_market.market_index)?;
+        update_bank_potential_tokens(openbook_orders, base_bank, quote_bank, after_oo);
+    }
+
+    if let Some(health_cache) = health_cache {
+        health_cache.adjust_token_balance(base_bank, base_difference)?;
+        health_cache.adjust_token_balance(quote_bank, quote_difference)?;
+
+        let openbook_account = account.openbook_v2_orders(openbook_market.market_index)?;
+        OpenbookV2OODifference::new(before_oo, after_oo).recompute_health_cache_openbook_v2_state(
+            health_cache,
+            openbook_account,
+            after_oo,
+        )?;
+    }
+
+    Ok(())
+}
+
+fn update_bank_potential_tokens(
+    openbook_orders: &mut OpenbookV2Orders,
+    base_bank: &mut Bank,
+    quote_bank: &mut Bank,
+    oo: &OpenOrdersSlim,
+) {
+    assert_eq!(openbook_orders.base_token_index, base_bank.token_index);
+    assert_eq!(openbook_orders.quote_token_index, quote_bank.token_index);
+
+    // Potential tokens are all tokens on the side, plus reserved on the other side
+    // converted at favorable price. This creates an overestimation of the potential
+    // base and quote tokens flowing out of this open orders account.
+    let new_base = oo.native_base_total()
+        + (oo.native_quote_reserved() as f64 * openbook_orders.lowest_placed_bid_inv) as u64;
+    let new_quote = oo.native_quote_total()
+        + (oo.native_base_reserved() as f64 * openbook_orders.highest_placed_ask) as u64;
+
+    let old_base = openbook_orders.potential_base_tokens;
+    let old_quote = openbook_orders.potential_quote_tokens;
+
+    base_bank.update_potential_serum_tokens(old_base, new_base);
+    quote_bank.update_potential_serum_tokens(old_quote, new_quote);
+
+    openbook_orders.potential_base_tokens = new_base;
+    openbook_orders.potential_quote_tokens = new_quote;
+}
+
+/// Returns the order id if the order was posted to the book
+fn cpi_place_order(
+    ctx: &OpenbookV2PlaceOrder,
+    order: openbook_v2::PlaceOrderArgs,
+) -> Result<Option<u128>> {
+    let market_vault = match order.side {
+        Side::Bid => ctx.market_quote_vault.to_account_info(),
+        Side::Ask => ctx.market_base_vault.to_account_info(),
+    };
+
+    let group = ctx.group.load()?;
+    let seeds = group_seeds!(group);
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.openbook_v2_program.to_account_info(),
+        openbook_v2::cpi::accounts::PlaceOrder {
+            signer: ctx.group.to_account_info(),
+            open_orders_account: ctx.open_orders.to_account_info(),
+            open_orders_admin: None,
+            user_token_account: ctx.payer_vault.to_account_info(),
+            market: ctx.openbook_v2_market_external.to_account_info(),
+            bids: ctx.bids.to_account_info(),
+            asks: ctx.asks.to_account_info(),
+            event_heap: ctx.event_heap.to_account_info(),
+            market_vault,
+            oracle_a: None,
+            oracle_b: None,
+            token_program: ctx.token_program.to_account_info(),
+        },
+        &[seeds],
+    );
+    Ok(openbook_v2::cpi::place_order(cpi_ctx, order)?.get())
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use openbook_v2::state::PlaceOrderType;
+
+use crate::accounts_ix::*;
+
+/// Places an immediate-or-cancel order through the account's open orders account.
+///
+/// Uses the same code path as openbook_v2_place_order, only the instruction gate differs.
+#[allow(clippy::too_many_arguments)]
+pub fn openbook_v2_place_take_order<'info>(
+    ctx: Context<'_, '_, '_, 'info, OpenbookV2PlaceTakeOrder<'info>>,
+    side: u8,
+    limit_price_lots: u64,
+    max_base_qty: u64,
+    max_native_quote_qty_including_fees: u64,
+    self_trade_behavior: u8,
+    client_order_id: u64,
+    limit: u16,
+) -> Result<()> {
+    let a = ctx.accounts;
+    let mut accounts = OpenbookV2PlaceOrder {
+        group: a.group.clone(),
+        account: a.account.clone(),
+        authority: a.authority.clone(),
+        open_orders: a.open_orders.clone(),
+        openbook_v2_market: a.openbook_v2_market.clone(),
+        openbook_v2_program: a.openbook_v2_program.clone(),
+        openbook_v2_market_external: a.openbook_v2_market_external.clone(),
+        bids: a.bids.clone(),
+        asks: a.asks.clone(),
+        event_heap: a.event_heap.clone(),
+        market_base_vault: a.market_base_vault.clone(),
+        market_quote_vault: a.market_quote_vault.clone(),
+        payer_bank: a.payer_bank.clone(),
+        payer_vault: a.payer_vault.clone(),
+        payer_oracle: a.payer_oracle.clone(),
+        token_program: a.token_program.clone(),
+    };
+    super::openbook_v2_place_order(
+        &mut accounts,
+        ctx.remaining_accounts,
+        side,
+        limit_price_lots,
+        max_base_qty,
+        max_native_quote_qty_including_fees,
+        self_trade_behavior,
+        PlaceOrderType::ImmediateOrCancel.into(),
+        client_order_id,
+        limit,
+    )
+}
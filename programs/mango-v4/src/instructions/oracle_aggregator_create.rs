@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::accounts_zerocopy::*;
+use crate::error::*;
+use crate::state::*;
+
+pub fn oracle_aggregator_create(
+    ctx: Context<OracleAggregatorCreate>,
+    min_quorum: u8,
+    max_divergence: f64,
+    source_oracle_config: OracleConfigParams,
+) -> Result<()> {
+    let source_oracles = validated_source_oracles(ctx.remaining_accounts)?;
+    require_msg!(max_divergence > 0.0, "max divergence must be positive");
+
+    let mut oracle = ctx.accounts.oracle.load_init()?;
+    oracle.group = ctx.accounts.group.key();
+    oracle.mint = ctx.accounts.mint.key();
+    oracle.mint_decimals = ctx.accounts.mint.decimals;
+    oracle.min_quorum = min_quorum;
+    oracle.max_divergence = max_divergence;
+    oracle.source_oracle_config = source_oracle_config.to_oracle_config();
+    oracle.set_source_oracles(&source_oracles)?;
+    require_gte!(oracle.num_oracles, min_quorum);
+
+    Ok(())
+}
+
+/// Source oracles must be of a known type and can't be aggregators themselves
+pub(crate) fn validated_source_oracles(ais: &[AccountInfo]) -> Result<Vec<Pubkey>> {
+    ais.iter()
+        .map(|ai| {
+            let oracle_type = determine_oracle_type(&AccountInfoRef::borrow(ai)?)?;
+            require_msg_typed!(
+                oracle_type != OracleType::Aggregated,
+                MangoError::UnexpectedOracle,
+                "source oracle {} is an aggregator",
+                ai.key
+            );
+            Ok(*ai.key)
+        })
+        .collect()
+}
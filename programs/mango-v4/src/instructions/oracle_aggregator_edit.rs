@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::state::*;
+
+use super::validated_source_oracles;
+
+pub fn oracle_aggregator_edit(
+    ctx: Context<OracleAggregatorEdit>,
+    min_quorum_opt: Option<u8>,
+    max_divergence_opt: Option<f64>,
+    source_oracle_config_opt: Option<OracleConfigParams>,
+    replace_source_oracles: bool,
+) -> Result<()> {
+    let mut oracle = ctx.accounts.oracle.load_mut()?;
+
+    if let Some(min_quorum) = min_quorum_opt {
+        msg!(
+            "Min quorum: old - {:?}, new - {:?}",
+            oracle.min_quorum,
+            min_quorum
+        );
+        oracle.min_quorum = min_quorum;
+    }
+    if let Some(max_divergence) = max_divergence_opt {
+        require_msg!(max_divergence > 0.0, "max divergence must be positive");
+        msg!(
+            "Max divergence: old - {:?}, new - {:?}",
+            oracle.max_divergence,
+            max_divergence
+        );
+        oracle.max_divergence = max_divergence;
+    }
+    if let Some(source_oracle_config) = source_oracle_config_opt {
+        msg!(
+            "Source oracle config: old - {:?}, new - {:?}",
+            oracle.source_oracle_config,
+            source_oracle_config
+        );
+        oracle.source_oracle_config = source_oracle_config.to_oracle_config();
+    }
+    if replace_source_oracles {
+        let source_oracles = validated_source_oracles(ctx.remaining_accounts)?;
+        msg!(
+            "Source oracles: old - {:?}, new - {:?}",
+            oracle.source_oracles(),
+            source_oracles
+        );
+        oracle.set_source_oracles(&source_oracles)?;
+    }
+
+    require_gte!(oracle.num_oracles, oracle.min_quorum);
+
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::accounts_zerocopy::*;
+use crate::util::clock_now;
+
+pub fn oracle_aggregator_update(ctx: Context<OracleAggregatorUpdate>) -> Result<()> {
+    let (now_ts, now_slot) = clock_now();
+    let ais = AccountInfoRef::borrow_slice(ctx.remaining_accounts)?;
+
+    let mut oracle = ctx.accounts.oracle.load_mut()?;
+    oracle.update(&ais, now_ts, now_slot)?;
+    msg!(
+        "aggregated price: {}, deviation: {}, last update slot: {}",
+        oracle.price,
+        oracle.deviation,
+        oracle.last_update_slot
+    );
+
+    Ok(())
+}
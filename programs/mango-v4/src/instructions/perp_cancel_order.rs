@@ -11,11 +11,18 @@ pub fn perp_cancel_order(ctx: Context<PerpCancelOrder>, order_id: u128) -> Resul
         account.fixed.is_owner_or_delegate(ctx.accounts.owner.key()),
         MangoError::SomeError
     );
+    account
+        .fixed
+        .check_delegate_permission(ctx.accounts.owner.key(), DelegatePermission::PerpCancel)?;
+    account.fixed.check_delegate_perp_market(
+        ctx.accounts.owner.key(),
+        ctx.accounts.perp_market.load()?.perp_market_index,
+    )?;
 
     let perp_market = ctx.accounts.perp_market.load_mut()?;
     let mut book = Orderbook {
-        bids: ctx.accounts.bids.load_mut()?,
-        asks: ctx.accounts.asks.load_mut()?,
+        bids: ctx.accounts.bids.load_full_mut()?,
+        asks: ctx.accounts.asks.load_full_mut()?,
     };
 
     let (slot, _) = account
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::accounts_zerocopy::*;
+use crate::error::*;
+use crate::logs::{emit_stack, PerpOpeningAuctionLog};
+use crate::state::*;
+
+/// Ends a perp market's opening auction once its end timestamp is reached.
+///
+/// Crossing orders are executed at a uniform clearing price, producing up to `limit`
+/// fill events. If not all crossing orders could be executed, the auction stays
+/// active and further calls continue at the same price.
+pub fn perp_clear_opening_auction(ctx: Context<PerpClearOpeningAuction>, limit: u8) -> Result<()> {
+    let group = ctx.accounts.group.load()?;
+    let clock = Clock::get()?;
+    let now_ts: u64 = clock.unix_timestamp.try_into().unwrap();
+
+    let mut perp_market = ctx.accounts.perp_market.load_mut()?;
+    require!(
+        perp_market.is_in_opening_auction(),
+        MangoError::PerpOpeningAuctionNotActive
+    );
+    require_msg_typed!(
+        now_ts >= perp_market.opening_auction_end_ts,
+        MangoError::PerpOpeningAuctionNotEnded,
+        "opening auction ends at {}, now {}",
+        perp_market.opening_auction_end_ts,
+        now_ts
+    );
+
+    let oracle_ref = &AccountInfoRef::borrow(ctx.accounts.oracle.as_ref())?;
+    let oracle_price = perp_market.oracle_price(
+        &OracleAccountInfos::from_reader(oracle_ref),
+        Some((now_ts, clock.slot)),
+    )?;
+
+    let mut book = Orderbook {
+        bids: ctx.accounts.bids.load_full_mut()?,
+        asks: ctx.accounts.asks.load_full_mut()?,
+    };
+    let mut event_queue = ctx.accounts.event_queue.load_full_mut()?;
+
+    let fills_before = event_queue.header.seq_num;
+    let done = book.clear_opening_auction(
+        &mut perp_market,
+        &mut event_queue,
+        oracle_price,
+        now_ts,
+        limit,
+        &group.perp_fee_tiers,
+    )?;
+
+    emit_stack(PerpOpeningAuctionLog {
+        mango_group: ctx.accounts.group.key(),
+        perp_market_index: perp_market.perp_market_index,
+        price_lots: perp_market.opening_auction_price_lots,
+        fills: event_queue.header.seq_num - fills_before,
+        done,
+    });
+
+    if done {
+        perp_market.end_opening_auction();
+    }
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::error::*;
+use crate::logs::{emit_stack, PerpConditionalOrderCreateLog};
+use crate::state::*;
+
+/// Creates a stop-loss or take-profit order that reduces the perp position
+/// once the market's oracle price crosses trigger_price_lots.
+///
+/// Pass max_base_lots = i64::MAX to close the whole position.
+#[allow(clippy::too_many_arguments)]
+pub fn perp_conditional_order_create(
+    ctx: Context<PerpConditionalOrderCreate>,
+    side: Side,
+    order_type: PerpScheduledOrderType,
+    max_base_lots: i64,
+    trigger_price_lots: i64,
+    max_slippage: f32,
+    expiry_timestamp: u64,
+) -> Result<()> {
+    require_msg!(
+        matches!(
+            order_type,
+            PerpScheduledOrderType::StopLoss | PerpScheduledOrderType::TakeProfit
+        ),
+        "order type must be StopLoss or TakeProfit"
+    );
+    require_gt!(max_base_lots, 0);
+    require_gt!(trigger_price_lots, 0);
+    require_msg!(
+        max_slippage >= 0.0 && max_slippage < 1.0,
+        "max slippage must be in [0, 1)"
+    );
+
+    let now_ts: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
+    if expiry_timestamp <= now_ts {
+        msg!("Already expired, ignoring");
+        return Ok(());
+    }
+
+    let perp_market = ctx.accounts.perp_market.load()?;
+    let mut account = ctx.accounts.account.load_full_mut()?;
+    // Conditional orders are always reduce-only, so the notional limit doesn't apply
+    let authority = ctx.accounts.authority.key();
+    account
+        .fixed
+        .check_delegate_permission(authority, DelegatePermission::PerpTrade)?;
+    account
+        .fixed
+        .check_delegate_perp_market(authority, perp_market.perp_market_index)?;
+
+    let id = account.fixed.next_perp_scheduled_order_id;
+    account.fixed.next_perp_scheduled_order_id =
+        account.fixed.next_perp_scheduled_order_id.wrapping_add(1);
+
+    let order = account.free_perp_scheduled_order_mut()?;
+    *order = PerpScheduledOrder {
+        id,
+        max_base_lots,
+        executed_base_lots: 0,
+        child_base_lots: max_base_lots,
+        next_trigger_timestamp: now_ts,
+        expiry_timestamp,
+        market_index: perp_market.perp_market_index,
+        is_configured: 1,
+        side: side.into(),
+        order_type: order_type.into(),
+        reduce_only: 1,
+        trigger_price_lots,
+        max_slippage,
+        ..PerpScheduledOrder::default()
+    };
+
+    emit_stack(PerpConditionalOrderCreateLog {
+        mango_group: ctx.accounts.group.key(),
+        mango_account: ctx.accounts.account.key(),
+        id,
+        market_index: order.market_index,
+        order_type: order.order_type,
+        side: order.side,
+        max_base_lots,
+        trigger_price_lots,
+        max_slippage,
+        expiry_timestamp,
+    });
+
+    Ok(())
+}
@@ -5,7 +5,7 @@ use crate::error::MangoError;
 use crate::state::*;
 
 use crate::accounts_ix::*;
-use crate::logs::{emit_perp_balances, emit_stack, FillLogV3};
+use crate::logs::{emit_perp_balances, emit_stack, FillLogV4};
 
 /// Load a mango account by key from the list of account infos.
 ///
@@ -52,7 +52,7 @@ pub fn perp_consume_events(ctx: Context<PerpConsumeEvents>, limit: usize) -> Res
 
     let mut perp_market = ctx.accounts.perp_market.load_mut()?;
     let perp_market_index = perp_market.perp_market_index;
-    let mut event_queue = ctx.accounts.event_queue.load_mut()?;
+    let mut event_queue = ctx.accounts.event_queue.load_full_mut()?;
     let mango_account_ais = &ctx.remaining_accounts;
 
     for _ in 0..limit {
@@ -80,11 +80,16 @@ pub fn perp_consume_events(ctx: Context<PerpConsumeEvents>, limit: usize) -> Res
                         fill,
                         &group,
                     )?;
-                    let taker_realized_pnl = maker_taker.execute_perp_taker(
-                        perp_market_index,
-                        &mut perp_market,
-                        fill,
-                    )?;
+                    let taker_realized_pnl = if fill.is_auction() {
+                        maker_taker.execute_perp_maker(
+                            perp_market_index,
+                            &mut perp_market,
+                            &fill.auction_taker_as_maker(),
+                            &group,
+                        )?
+                    } else {
+                        maker_taker.execute_perp_taker(perp_market_index, &mut perp_market, fill)?
+                    };
                     emit_perp_balances(
                         group_key,
                         fill.maker,
@@ -103,8 +108,17 @@ pub fn perp_consume_events(ctx: Context<PerpConsumeEvents>, limit: usize) -> Res
                         fill,
                         &group,
                     )?;
-                    let taker_realized_pnl =
-                        taker.execute_perp_taker(perp_market_index, &mut perp_market, fill)?;
+                    // In opening auctions the taker side was an order on the book as well
+                    let taker_realized_pnl = if fill.is_auction() {
+                        taker.execute_perp_maker(
+                            perp_market_index,
+                            &mut perp_market,
+                            &fill.auction_taker_as_maker(),
+                            &group,
+                        )?
+                    } else {
+                        taker.execute_perp_taker(perp_market_index, &mut perp_market, fill)?
+                    };
                     emit_perp_balances(
                         group_key,
                         fill.maker,
@@ -120,7 +134,7 @@ pub fn perp_consume_events(ctx: Context<PerpConsumeEvents>, limit: usize) -> Res
 
                     (maker_realized_pnl, taker_realized_pnl)
                 };
-                emit_stack(FillLogV3 {
+                emit_stack(FillLogV4 {
                     mango_group: group_key,
                     market_index: perp_market_index,
                     taker_side: fill.taker_side as u8,
@@ -131,10 +145,12 @@ pub fn perp_consume_events(ctx: Context<PerpConsumeEvents>, limit: usize) -> Res
                     maker: fill.maker,
                     maker_client_order_id: fill.maker_client_order_id,
                     maker_fee: fill.maker_fee,
+                    maker_fee_tier: fill.maker_fee_tier,
                     maker_timestamp: fill.maker_timestamp,
                     taker: fill.taker,
                     taker_client_order_id: fill.taker_client_order_id,
                     taker_fee: fill.taker_fee,
+                    taker_fee_tier: fill.taker_fee_tier,
                     price: fill.price,
                     quantity: fill.quantity,
                     maker_closed_pnl: maker_closed_pnl.to_num(),
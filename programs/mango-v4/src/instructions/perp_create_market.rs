@@ -40,9 +40,32 @@ pub fn perp_create_market(
     settle_pnl_limit_window_size_ts: u64,
     positive_pnl_liquidation_fee: f32,
     platform_liquidation_fee: f32,
+    bookside_capacity: u32,
+    event_queue_capacity: u32,
+    opening_auction_duration: u32,
 ) -> Result<()> {
     let now_ts: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
 
+    // The bookside and event queue accounts are created by the client, their sizes
+    // determine the capacities. Check that they were created as requested.
+    require_msg!(
+        bookside_capacity as usize >= MAX_ORDERTREE_NODES,
+        "bookside capacity must be at least {}",
+        MAX_ORDERTREE_NODES
+    );
+    require_msg!(
+        event_queue_capacity >= MAX_NUM_EVENTS,
+        "event queue capacity must be at least {}",
+        MAX_NUM_EVENTS
+    );
+    let bookside_size = BookSide::account_size(bookside_capacity as usize);
+    require_eq!(ctx.accounts.bids.as_ref().data_len(), bookside_size);
+    require_eq!(ctx.accounts.asks.as_ref().data_len(), bookside_size);
+    require_eq!(
+        ctx.accounts.event_queue.as_ref().data_len(),
+        EventQueue::account_size(event_queue_capacity as usize)
+    );
+
     let mut perp_market = ctx.accounts.perp_market.load_init()?;
     *perp_market = PerpMarket {
         group: ctx.accounts.group.key(),
@@ -95,8 +118,23 @@ pub fn perp_create_market(
         fees_withdrawn: 0,
         platform_liquidation_fee: I80F48::from_num(platform_liquidation_fee),
         accrued_liquidation_fees: I80F48::ZERO,
-        reserved: [0; 1848],
+        portfolio_margin_token_index: 0,
+        portfolio_margin_active: 0,
+        padding5: Default::default(),
+        funding_stable_price_weight: 0.0,
+        settle_stable_price_weight: 0.0,
+        opening_auction_duration,
+        padding6: Default::default(),
+        opening_auction_end_ts: 0,
+        opening_auction_price_lots: 0,
+        reserved: [0; 1808],
     };
+    perp_market.start_opening_auction(now_ts);
+    verify_perp_fees(
+        &ctx.accounts.group.load()?.perp_fee_tiers,
+        perp_market.maker_fee,
+        perp_market.taker_fee,
+    )?;
 
     let oracle_ref = &AccountInfoRef::borrow(ctx.accounts.oracle.as_ref())?;
     if let Ok(oracle_price) =
@@ -110,8 +148,8 @@ pub fn perp_create_market(
     }
 
     let mut orderbook = Orderbook {
-        bids: ctx.accounts.bids.load_init()?,
-        asks: ctx.accounts.asks.load_init()?,
+        bids: ctx.accounts.bids.load_full_init()?,
+        asks: ctx.accounts.asks.load_full_init()?,
     };
     orderbook.init();
 
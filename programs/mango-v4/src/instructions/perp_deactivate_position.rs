@@ -11,6 +11,13 @@ pub fn perp_deactivate_position(ctx: Context<PerpDeactivatePosition>) -> Result<
         account.fixed.is_owner_or_delegate(ctx.accounts.owner.key()),
         MangoError::SomeError
     );
+    account
+        .fixed
+        .check_delegate_permission(ctx.accounts.owner.key(), DelegatePermission::PerpTrade)?;
+    account.fixed.check_delegate_perp_market(
+        ctx.accounts.owner.key(),
+        ctx.accounts.perp_market.load()?.perp_market_index,
+    )?;
 
     let perp_market = ctx.accounts.perp_market.load()?;
     let perp_position = account.perp_position_mut(perp_market.perp_market_index)?;
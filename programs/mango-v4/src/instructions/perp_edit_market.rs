@@ -40,21 +40,23 @@ pub fn perp_edit_market(
     name_opt: Option<String>,
     force_close_opt: Option<bool>,
     platform_liquidation_fee_opt: Option<f32>,
+    funding_stable_price_weight_opt: Option<f32>,
+    settle_stable_price_weight_opt: Option<f32>,
+    opening_auction_duration_opt: Option<u32>,
 ) -> Result<()> {
     let group = ctx.accounts.group.load()?;
 
     let mut perp_market = ctx.accounts.perp_market.load_mut()?;
+    let was_reduce_only = perp_market.is_reduce_only();
 
     let mut require_group_admin = false;
 
     if let Some(oracle_config) = oracle_config_opt {
         msg!(
-        "Oracle config: old - conf_filter {:?}, max_staleness_slots {:?},  new - conf_filter {:?}, max_staleness_slots {:?}",
-        perp_market.oracle_config.conf_filter,
-        perp_market.oracle_config.max_staleness_slots,
-        oracle_config.conf_filter,
-        oracle_config.max_staleness_slots
-    );
+            "Oracle config: old - {:?},  new - {:?}",
+            perp_market.oracle_config,
+            oracle_config
+        );
         perp_market.oracle_config = oracle_config.to_oracle_config();
         require_group_admin = true;
     };
@@ -169,6 +171,13 @@ pub fn perp_edit_market(
         perp_market.taker_fee = I80F48::from_num(taker_fee);
         require_group_admin = true;
     }
+    if maker_fee_opt.is_some() || taker_fee_opt.is_some() {
+        verify_perp_fees(
+            &group.perp_fee_tiers,
+            perp_market.maker_fee,
+            perp_market.taker_fee,
+        )?;
+    }
 
     if let Some(min_funding) = min_funding_opt {
         msg!(
@@ -356,6 +365,56 @@ pub fn perp_edit_market(
         require_group_admin = true;
     };
 
+    if let Some(funding_stable_price_weight) = funding_stable_price_weight_opt {
+        msg!(
+            "Funding stable price weight: old - {:?}, new - {:?}",
+            perp_market.funding_stable_price_weight,
+            funding_stable_price_weight
+        );
+        require!(
+            (0.0..=1.0).contains(&funding_stable_price_weight),
+            MangoError::SomeError
+        );
+        perp_market.funding_stable_price_weight = funding_stable_price_weight;
+        require_group_admin = true;
+    };
+
+    if let Some(settle_stable_price_weight) = settle_stable_price_weight_opt {
+        msg!(
+            "Settle stable price weight: old - {:?}, new - {:?}",
+            perp_market.settle_stable_price_weight,
+            settle_stable_price_weight
+        );
+        require!(
+            (0.0..=1.0).contains(&settle_stable_price_weight),
+            MangoError::SomeError
+        );
+        perp_market.settle_stable_price_weight = settle_stable_price_weight;
+        require_group_admin = true;
+    };
+
+    if let Some(opening_auction_duration) = opening_auction_duration_opt {
+        msg!(
+            "Opening auction duration: old - {:?}, new - {:?}",
+            perp_market.opening_auction_duration,
+            opening_auction_duration
+        );
+        perp_market.opening_auction_duration = opening_auction_duration;
+        require_group_admin = true;
+    };
+
+    // Reopening a market after reduce-only or force-close starts an opening auction
+    if was_reduce_only && !perp_market.is_reduce_only() {
+        let now_ts: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
+        perp_market.start_opening_auction(now_ts);
+        if perp_market.is_in_opening_auction() {
+            msg!(
+                "Opening auction until {}",
+                perp_market.opening_auction_end_ts
+            );
+        }
+    }
+
     // account constraint #1
     if require_group_admin {
         require!(
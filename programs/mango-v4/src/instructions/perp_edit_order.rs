@@ -0,0 +1,166 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::error::*;
+use crate::state::*;
+use crate::util::clock_now;
+
+use super::perp_place_order_in_slot;
+
+/// Changes the price and size of an existing perp order.
+///
+/// The order is identified by exactly one of order_id_opt or client_order_id_opt.
+/// For oracle pegged orders, `price_lots` is the price offset to the oracle.
+///
+/// If only the size decreases, the order is changed in place and keeps its time
+/// priority. Otherwise it's cancelled and an order with the new price and size is
+/// placed, in the same open order slot and with the same client order id, post order
+/// type and expiry. The replacement uses the passed self trade behavior, reduce only
+/// flag and oracle staleness limit, since the book doesn't store them.
+///
+/// Returns the id of the order on the book, if any.
+pub fn perp_edit_order(
+    ctx: Context<PerpPlaceOrder>,
+    order_id_opt: Option<u128>,
+    client_order_id_opt: Option<u64>,
+    price_lots: i64,
+    max_base_lots: i64,
+    self_trade_behavior: SelfTradeBehavior,
+    reduce_only: bool,
+    max_oracle_staleness_slots: i32,
+    limit: u8,
+) -> Result<Option<u128>> {
+    require_gte!(max_base_lots, 0);
+
+    let (now_ts, _) = clock_now();
+    let account_pk = ctx.accounts.account.key();
+
+    let (slot, leaf, side_and_tree) = {
+        let mut account = ctx.accounts.account.load_full_mut()?;
+        // account constraint #1
+        require!(
+            account.fixed.is_owner_or_delegate(ctx.accounts.owner.key()),
+            MangoError::SomeError
+        );
+        let perp_market = ctx.accounts.perp_market.load()?;
+        let perp_market_index = perp_market.perp_market_index;
+        account
+            .fixed
+            .check_delegate_permission(ctx.accounts.owner.key(), DelegatePermission::PerpTrade)?;
+        account
+            .fixed
+            .check_delegate_perp_market(ctx.accounts.owner.key(), perp_market_index)?;
+
+        let (slot, oo) = match (order_id_opt, client_order_id_opt) {
+            (Some(order_id), None) => account
+                .perp_find_order_with_order_id(perp_market_index, order_id)
+                .ok_or_else(|| {
+                    error_msg_typed!(
+                        MangoError::PerpOrderIdNotFound,
+                        "could not find perp order with id {order_id} in user account"
+                    )
+                })?,
+            (None, Some(client_order_id)) => account
+                .perp_find_order_with_client_order_id(perp_market_index, client_order_id)
+                .ok_or_else(|| {
+                    error_msg_typed!(
+                        MangoError::PerpOrderIdNotFound,
+                        "could not find perp order with client order id {client_order_id} in user account"
+                    )
+                })?,
+            _ => {
+                return Err(error_msg!(
+                    "exactly one of order id and client order id must be provided"
+                ))
+            }
+        };
+        let side_and_tree = oo.side_and_tree();
+        let order_id = oo.id;
+
+        let mut book = Orderbook {
+            bids: ctx.accounts.bids.load_full_mut()?,
+            asks: ctx.accounts.asks.load_full_mut()?,
+        };
+        let leaf = *book
+            .bookside(side_and_tree.side())
+            .find_by_key(side_and_tree.order_tree(), order_id)
+            .ok_or_else(|| {
+                error_msg_typed!(
+                    MangoError::PerpOrderIdNotFound,
+                    "perp order with id {order_id} is not on the book, filled or expired already"
+                )
+            })?
+            .1;
+
+        let current_price_lots = match side_and_tree.order_tree() {
+            BookSideOrderTree::Fixed => fixed_price_lots(leaf.price_data()),
+            BookSideOrderTree::OraclePegged => oracle_pegged_price_offset(leaf.price_data()),
+        };
+
+        if price_lots == current_price_lots && max_base_lots > 0 && max_base_lots < leaf.quantity {
+            book.decrease_order_quantity_by_slot(
+                &mut account.borrow_mut(),
+                &account_pk,
+                slot,
+                perp_market_index,
+                max_base_lots,
+            )?;
+            msg!(
+                "decreased order_id={} quantity={} in place",
+                order_id,
+                max_base_lots
+            );
+            return Ok(Some(order_id));
+        }
+
+        book.cancel_order_by_slot(
+            &mut account.borrow_mut(),
+            &account_pk,
+            slot,
+            perp_market_index,
+        )?;
+        (slot, leaf, side_and_tree)
+    };
+
+    if max_base_lots == 0 {
+        msg!("order_id={} cancelled", leaf.key);
+        return Ok(None);
+    }
+
+    let time_in_force = if leaf.time_in_force == 0 {
+        0
+    } else {
+        let remaining = leaf.expiry().saturating_sub(now_ts);
+        if remaining == 0 {
+            msg!("order_id={} is expired and was cancelled", leaf.key);
+            return Ok(None);
+        }
+        remaining as u16
+    };
+
+    let order_type = PostOrderType::try_from(leaf.order_type)
+        .map_err(|_| error_msg!("bad order type {} on order {}", leaf.order_type, leaf.key))?;
+
+    let order = Order {
+        side: side_and_tree.side(),
+        max_base_lots,
+        max_quote_lots: i64::MAX,
+        client_order_id: leaf.client_order_id,
+        reduce_only,
+        time_in_force,
+        self_trade_behavior,
+        params: match side_and_tree.order_tree() {
+            BookSideOrderTree::Fixed => OrderParams::Fixed {
+                price_lots,
+                order_type,
+            },
+            BookSideOrderTree::OraclePegged => OrderParams::OraclePegged {
+                price_offset_lots: price_lots,
+                order_type,
+                peg_limit: leaf.peg_limit,
+                max_oracle_staleness_slots,
+            },
+        },
+    };
+    perp_place_order_in_slot(ctx, order, limit, Some(slot))
+}
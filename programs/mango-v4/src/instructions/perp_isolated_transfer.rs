@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use fixed::types::I80F48;
+
+use crate::accounts_ix::*;
+use crate::error::*;
+use crate::health::*;
+use crate::logs::{emit_stack, PerpIsolatedMarginLog};
+use crate::state::*;
+use crate::util::clock_now;
+
+/// Moves settle token collateral between the account and an isolated perp position.
+///
+/// A positive `amount` allocates collateral to the position, a negative one returns it.
+/// The funds stay in the account's settle token position either way.
+pub fn perp_isolated_transfer(ctx: Context<PerpIsolatedTransfer>, amount: i64) -> Result<()> {
+    require_neq!(amount, 0);
+
+    let mut account = ctx.accounts.account.load_full_mut()?;
+    // account constraint #1
+    require!(
+        account.fixed.is_owner_or_delegate(ctx.accounts.owner.key()),
+        MangoError::SomeError
+    );
+    account
+        .fixed
+        .check_delegate_permission(ctx.accounts.owner.key(), DelegatePermission::PerpTrade)?;
+    account.fixed.check_delegate_perp_market(
+        ctx.accounts.owner.key(),
+        ctx.accounts.perp_market.load()?.perp_market_index,
+    )?;
+
+    let perp_market = ctx.accounts.perp_market.load()?;
+    let perp_market_index = perp_market.perp_market_index;
+
+    let (now_ts, now_slot) = clock_now();
+    let retriever = new_fixed_order_account_retriever(
+        ctx.remaining_accounts,
+        &account.borrow(),
+        (now_ts, now_slot),
+    )?;
+    let mut health_cache =
+        new_health_cache(&account.borrow(), &retriever, now_ts).context("pre init health")?;
+    let pre_init_health = account.check_health_pre(&health_cache)?;
+
+    let perp_position = account.perp_position_mut(perp_market_index)?;
+    require!(
+        perp_position.is_isolated(),
+        MangoError::PerpPositionNotIsolated
+    );
+    let change = I80F48::from(amount);
+    require_msg!(
+        perp_position.isolated_collateral_native + change >= 0,
+        "can't withdraw more than the isolated collateral {}",
+        perp_position.isolated_collateral_native
+    );
+    perp_position.change_isolated_collateral(change);
+    let isolated_collateral = perp_position.isolated_collateral_native;
+
+    health_cache.recompute_perp_info(perp_position, &perp_market)?;
+    account.check_health_post(&health_cache, pre_init_health)?;
+
+    emit_stack(PerpIsolatedMarginLog {
+        mango_group: ctx.accounts.group.key(),
+        mango_account: ctx.accounts.account.key(),
+        market_index: perp_market_index,
+        isolated: true,
+        isolated_collateral: isolated_collateral.to_bits(),
+    });
+
+    Ok(())
+}
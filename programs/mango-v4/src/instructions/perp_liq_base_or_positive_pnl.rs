@@ -40,6 +40,10 @@ pub fn perp_liq_base_or_positive_pnl(
             .is_owner_or_delegate(ctx.accounts.liqor_owner.key()),
         MangoError::SomeError
     );
+    liqor.fixed.check_delegate_permission(
+        ctx.accounts.liqor_owner.key(),
+        DelegatePermission::Liquidate,
+    )?;
     require_msg_typed!(
         !liqor.fixed.being_liquidated(),
         MangoError::BeingLiquidated,
@@ -47,23 +51,35 @@ pub fn perp_liq_base_or_positive_pnl(
     );
 
     let mut liqee = ctx.accounts.liqee.load_full_mut()?;
+    let perp_market_index = ctx.accounts.perp_market.load()?.perp_market_index;
+    let liqee_isolated = liqee.perp_position(perp_market_index)?.is_isolated();
 
     // Initial liqee health check
     let mut liqee_health_cache = {
         let account_retriever = ScanningAccountRetriever::new(ctx.remaining_accounts, group_pk)
             .context("create account retriever")?;
-        new_health_cache(&liqee.borrow(), &account_retriever, now_ts)
-            .context("create liqee health cache")?
+        let health_cache = new_health_cache(&liqee.borrow(), &account_retriever, now_ts)
+            .context("create liqee health cache")?;
+        // Isolated positions are liquidated against their own collateral only
+        if liqee_isolated {
+            health_cache.isolated_perp_bucket_cache(perp_market_index)?
+        } else {
+            health_cache
+        }
     };
     let liqee_liq_end_health = liqee_health_cache.health(HealthType::LiquidationEnd);
     liqee_health_cache.require_after_phase1_liquidation()?;
 
-    if liqee.check_liquidatable(&liqee_health_cache)? != CheckLiquidatable::Liquidatable {
+    if liqee_isolated {
+        if liqee_health_cache.health(HealthType::Maint) >= 0 {
+            msg!("Liqee isolated perp position is not liquidatable");
+            return Ok(());
+        }
+    } else if liqee.check_liquidatable(&liqee_health_cache)? != CheckLiquidatable::Liquidatable {
         return Ok(());
     }
 
     let mut perp_market = ctx.accounts.perp_market.load_mut()?;
-    let perp_market_index = perp_market.perp_market_index;
     let settle_token_index = perp_market.settle_token_index;
 
     let mut settle_bank = ctx.accounts.settle_bank.load_mut()?;
@@ -91,6 +107,15 @@ pub fn perp_liq_base_or_positive_pnl(
     liqor_perp_position.settle_funding(&perp_market);
     liqee_perp_position.update_settle_limit(&perp_market, now_ts);
 
+    // On portfolio margin accounts, base lots that are offset by a spot position are
+    // only liquidated once the unhedged part of the position is gone.
+    let unhedged_base_lots =
+        liqee_health_cache.portfolio_margin_unhedged_base_lots(perp_market_index)?;
+    if unhedged_base_lots != 0 {
+        max_base_transfer =
+            max_base_transfer.signum() * max_base_transfer.abs().min(unhedged_base_lots.abs());
+    }
+
     //
     // Perform the liquidation
     //
@@ -177,9 +202,11 @@ pub fn perp_liq_base_or_positive_pnl(
 
     // Check liqee health again
     let liqee_liq_end_health_after = liqee_health_cache.health(HealthType::LiquidationEnd);
-    liqee
-        .fixed
-        .maybe_recover_from_being_liquidated(liqee_liq_end_health_after);
+    if !liqee_isolated {
+        liqee
+            .fixed
+            .maybe_recover_from_being_liquidated(liqee_liq_end_health_after);
+    }
     require_gte!(liqee_liq_end_health_after, liqee_liq_end_health);
     msg!(
         "liqee liq end health: {} -> {}",
@@ -626,6 +653,7 @@ pub(crate) fn liquidation_action(
             limit_transfer_oneshot,
         );
         liqee_perp_position.record_settle(pnl_transfer, &perp_market);
+        liqee_perp_position.change_isolated_collateral(token_transfer);
 
         // Update the accounts' perp_spot_transfer statistics.
         let transfer_i64 = token_transfer.round_to_zero().to_num::<i64>();
@@ -12,6 +12,11 @@ pub fn perp_liq_force_cancel_orders(
 ) -> Result<()> {
     let mut account = ctx.accounts.account.load_full_mut()?;
 
+    let perp_market_index = ctx.accounts.perp_market.load()?.perp_market_index;
+    let isolated = account
+        .perp_position(perp_market_index)
+        .map_or(false, |p| p.is_isolated());
+
     let (now_ts, now_slot) = clock_now();
     let mut health_cache = {
         let retriever = new_fixed_order_account_retriever(
@@ -19,7 +24,14 @@ pub fn perp_liq_force_cancel_orders(
             &account.borrow(),
             (now_ts, now_slot),
         )?;
-        new_health_cache(&account.borrow(), &retriever, now_ts).context("create health cache")?
+        let health_cache = new_health_cache(&account.borrow(), &retriever, now_ts)
+            .context("create health cache")?;
+        // Isolated positions are liquidated by themselves
+        if isolated {
+            health_cache.isolated_perp_bucket_cache(perp_market_index)?
+        } else {
+            health_cache
+        }
     };
 
     let mut perp_market = ctx.accounts.perp_market.load_mut()?;
@@ -27,10 +39,13 @@ pub fn perp_liq_force_cancel_orders(
     //
     // Early return if if liquidation is not allowed or if market is not in force close
     //
-    let liquidatable = account.check_liquidatable(&health_cache)?;
-    let can_force_cancel = !account.fixed.is_operational()
-        || liquidatable == CheckLiquidatable::Liquidatable
-        || perp_market.is_force_close();
+    let liquidatable = if isolated {
+        health_cache.health(HealthType::Maint) < 0
+    } else {
+        account.check_liquidatable(&health_cache)? == CheckLiquidatable::Liquidatable
+    };
+    let can_force_cancel =
+        !account.fixed.is_operational() || liquidatable || perp_market.is_force_close();
     if !can_force_cancel {
         return Ok(());
     }
@@ -40,8 +55,8 @@ pub fn perp_liq_force_cancel_orders(
     //
     {
         let mut book = Orderbook {
-            bids: ctx.accounts.bids.load_mut()?,
-            asks: ctx.accounts.asks.load_mut()?,
+            bids: ctx.accounts.bids.load_full_mut()?,
+            asks: ctx.accounts.asks.load_full_mut()?,
         };
 
         book.cancel_all_orders(
@@ -59,10 +74,12 @@ pub fn perp_liq_force_cancel_orders(
     //
     // Health check at the end
     //
-    let init_health = health_cache.health(HealthType::LiquidationEnd);
-    account
-        .fixed
-        .maybe_recover_from_being_liquidated(init_health);
+    if !isolated {
+        let init_health = health_cache.health(HealthType::LiquidationEnd);
+        account
+            .fixed
+            .maybe_recover_from_being_liquidated(init_health);
+    }
 
     Ok(())
 }
@@ -67,22 +67,37 @@ pub fn perp_liq_negative_pnl_or_bankruptcy(
             .is_owner_or_delegate(ctx.accounts.liqor_owner.key()),
         MangoError::SomeError
     );
+    liqor.fixed.check_delegate_permission(
+        ctx.accounts.liqor_owner.key(),
+        DelegatePermission::Liquidate,
+    )?;
     require_msg_typed!(
         !liqor.fixed.being_liquidated(),
         MangoError::BeingLiquidated,
         "liqor account"
     );
 
+    let liqee_isolated = liqee.perp_position(perp_market_index)?.is_isolated();
     let retriever = ScanningAccountRetriever::new(ctx.remaining_accounts, &mango_group)
         .context("create account retriever")?;
     let mut liqee_health_cache = new_health_cache(&liqee.borrow(), &retriever, now_ts)?;
     drop(retriever);
+    // Isolated positions are liquidated against their own collateral only: losses beyond it
+    // go to the insurance fund or are socialized
+    if liqee_isolated {
+        liqee_health_cache = liqee_health_cache.isolated_perp_bucket_cache(perp_market_index)?;
+    }
     let liqee_liq_end_health = liqee_health_cache.health(HealthType::LiquidationEnd);
 
     // Guarantees that perp base position is 0 and perp quote position is <= 0.
     liqee_health_cache.require_after_phase2_liquidation()?;
 
-    if liqee.check_liquidatable(&liqee_health_cache)? != CheckLiquidatable::Liquidatable {
+    if liqee_isolated {
+        if liqee_health_cache.health(HealthType::Maint) >= 0 {
+            msg!("Liqee isolated perp position is not liquidatable");
+            return Ok(());
+        }
+    } else if liqee.check_liquidatable(&liqee_health_cache)? != CheckLiquidatable::Liquidatable {
         return Ok(());
     }
 
@@ -191,9 +206,11 @@ pub fn perp_liq_negative_pnl_or_bankruptcy(
     // Check liqee health again: bankruptcy would improve health
     liqee_health_cache.recompute_perp_info(liqee_perp_position, &perp_market)?;
     let liqee_liq_end_health = liqee_health_cache.health(HealthType::LiquidationEnd);
-    liqee
-        .fixed
-        .maybe_recover_from_being_liquidated(liqee_liq_end_health);
+    if !liqee_isolated {
+        liqee
+            .fixed
+            .maybe_recover_from_being_liquidated(liqee_liq_end_health);
+    }
 
     drop(perp_market);
 
@@ -270,6 +287,7 @@ pub(crate) fn liquidation_action(
         if settlement > 0 {
             liqor_perp_position.record_liquidation_quote_change(-settlement);
             liqee_perp_position.record_settle(-settlement, &perp_market);
+            liqee_perp_position.change_isolated_collateral(-settlement);
 
             // Update the accounts' perp_spot_transfer statistics.
             let settlement_i64 = settlement.round_to_zero().to_num::<i64>();
@@ -10,9 +10,19 @@ use crate::util::clock_now;
 // TODO
 #[allow(clippy::too_many_arguments)]
 pub fn perp_place_order(
+    ctx: Context<PerpPlaceOrder>,
+    order: Order,
+    limit: u8,
+) -> Result<Option<u128>> {
+    perp_place_order_in_slot(ctx, order, limit, None)
+}
+
+/// Like perp_place_order(), but a posted order uses the given free open order slot
+pub(crate) fn perp_place_order_in_slot(
     ctx: Context<PerpPlaceOrder>,
     mut order: Order,
     limit: u8,
+    owner_slot: Option<usize>,
 ) -> Result<Option<u128>> {
     require_gte!(order.max_base_lots, 0);
     require_gte!(order.max_quote_lots, 0);
@@ -27,14 +37,17 @@ pub fn perp_place_order(
     {
         let mut perp_market = ctx.accounts.perp_market.load_mut()?;
         let book = Orderbook {
-            bids: ctx.accounts.bids.load_mut()?,
-            asks: ctx.accounts.asks.load_mut()?,
+            bids: ctx.accounts.bids.load_full_mut()?,
+            asks: ctx.accounts.asks.load_full_mut()?,
         };
 
         let oracle_ref = &AccountInfoRef::borrow(ctx.accounts.oracle.as_ref())?;
-        let oracle_state = perp_market.oracle_state(
+        // Reduce-only orders are checked for staleness in health, others need
+        // the stricter risk-increasing freshness
+        let oracle_state = perp_market.oracle_state_for(
             &OracleAccountInfos::from_reader(oracle_ref),
-            None, // staleness checked in health
+            (!order.reduce_only).then_some((now_ts, now_slot)),
+            OracleFreshness::RiskIncreasing,
         )?;
         oracle_price = oracle_state.price;
 
@@ -47,6 +60,28 @@ pub fn perp_place_order(
         account.fixed.is_owner_or_delegate(ctx.accounts.owner.key()),
         MangoError::SomeError
     );
+    {
+        let owner = ctx.accounts.owner.key();
+        let perp_market = ctx.accounts.perp_market.load()?;
+        account
+            .fixed
+            .check_delegate_permission(owner, DelegatePermission::PerpTrade)?;
+        account
+            .fixed
+            .check_delegate_perp_market(owner, perp_market.perp_market_index)?;
+        if !order.reduce_only {
+            account.fixed.check_delegate_order_notional(
+                owner,
+                perp_market.order_notional(
+                    order.side,
+                    order.limit_price_lots(perp_market.native_price_to_lot(oracle_price)),
+                    order.max_base_lots,
+                    order.max_quote_lots,
+                    oracle_price,
+                ),
+            )?;
+        }
+    }
 
     let account_pk = ctx.accounts.account.key();
 
@@ -90,11 +125,11 @@ pub fn perp_place_order(
 
     let mut perp_market = ctx.accounts.perp_market.load_mut()?;
     let mut book = Orderbook {
-        bids: ctx.accounts.bids.load_mut()?,
-        asks: ctx.accounts.asks.load_mut()?,
+        bids: ctx.accounts.bids.load_full_mut()?,
+        asks: ctx.accounts.asks.load_full_mut()?,
     };
 
-    let mut event_queue = ctx.accounts.event_queue.load_mut()?;
+    let mut event_queue = ctx.accounts.event_queue.load_full_mut()?;
     let group = ctx.accounts.group.load()?;
 
     let now_ts: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
@@ -117,7 +152,7 @@ pub fn perp_place_order(
     };
     order.max_base_lots = max_base_lots;
 
-    let order_id_opt = book.new_order(
+    let order_id_opt = book.new_order_in_slot(
         order,
         &mut perp_market,
         &mut event_queue,
@@ -126,6 +161,8 @@ pub fn perp_place_order(
         &account_pk,
         now_ts,
         limit,
+        &group.perp_fee_tiers,
+        owner_slot,
     )?;
 
     //
@@ -140,7 +177,11 @@ pub fn perp_place_order(
     Ok(order_id_opt)
 }
 
-fn reduce_only_max_base_lots(pp: &PerpPosition, order: &Order, market_reduce_only: bool) -> i64 {
+pub(crate) fn reduce_only_max_base_lots(
+    pp: &PerpPosition,
+    order: &Order,
+    market_reduce_only: bool,
+) -> i64 {
     let effective_pos = pp.effective_base_position_lots();
     msg!(
         "reduce only: current effective position: {} lots",
@@ -0,0 +1,223 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::accounts_zerocopy::*;
+use crate::error::*;
+use crate::health::*;
+use crate::logs::{emit_stack, PerpBatchOrderLog};
+use crate::state::*;
+use crate::util::clock_now;
+
+/// Maximum number of orders in a PerpPlaceOrdersBatch instruction
+pub const PERP_BATCH_ORDER_LIMIT: usize = 16;
+
+/// Places multiple perp orders, optionally cancelling all of the account's orders on
+/// the market first.
+///
+/// Like perp_place_order, but the health check is only done once for the whole batch.
+/// The result of each order is logged in a PerpBatchOrderLog.
+pub fn perp_place_orders_batch(
+    ctx: Context<PerpPlaceOrder>,
+    orders: Vec<PerpBatchOrder>,
+    cancel_all_first: bool,
+    expiry_timestamp: u64,
+    limit: u8,
+) -> Result<()> {
+    require_gte!(PERP_BATCH_ORDER_LIMIT, orders.len());
+    for order in orders.iter() {
+        require_gte!(order.price_lots, 0);
+        require_gte!(order.max_base_lots, 0);
+    }
+
+    let time_in_force = match Order::tif_from_expiry(expiry_timestamp) {
+        Some(t) => t,
+        None => {
+            msg!("Orders are already expired");
+            return Ok(());
+        }
+    };
+
+    let (now_ts, now_slot) = clock_now();
+    let oracle_price;
+
+    // Update funding if possible, see perp_place_order
+    {
+        let mut perp_market = ctx.accounts.perp_market.load_mut()?;
+        let book = Orderbook {
+            bids: ctx.accounts.bids.load_full_mut()?,
+            asks: ctx.accounts.asks.load_full_mut()?,
+        };
+
+        let oracle_ref = &AccountInfoRef::borrow(ctx.accounts.oracle.as_ref())?;
+        let oracle_state = perp_market.oracle_state_for(
+            &OracleAccountInfos::from_reader(oracle_ref),
+            Some((now_ts, now_slot)),
+            OracleFreshness::RiskIncreasing,
+        )?;
+        oracle_price = oracle_state.price;
+
+        perp_market.update_funding_and_stable_price(&book, &oracle_state, now_ts)?;
+    }
+
+    let mut account = ctx.accounts.account.load_full_mut()?;
+    // account constraint #1
+    require!(
+        account.fixed.is_owner_or_delegate(ctx.accounts.owner.key()),
+        MangoError::SomeError
+    );
+    {
+        let owner = ctx.accounts.owner.key();
+        let perp_market = ctx.accounts.perp_market.load()?;
+        require!(
+            !perp_market.is_reduce_only(),
+            MangoError::MarketInReduceOnlyMode
+        );
+        account
+            .fixed
+            .check_delegate_permission(owner, DelegatePermission::PerpTrade)?;
+        if cancel_all_first {
+            account
+                .fixed
+                .check_delegate_permission(owner, DelegatePermission::PerpCancel)?;
+        }
+        account
+            .fixed
+            .check_delegate_perp_market(owner, perp_market.perp_market_index)?;
+        for order in orders.iter() {
+            // market orders ignore price_lots and have no limit
+            let price_lots = if order.order_type == PlaceOrderType::Market {
+                i64::MAX
+            } else {
+                order.price_lots
+            };
+            account.fixed.check_delegate_order_notional(
+                owner,
+                perp_market.order_notional(
+                    order.side,
+                    price_lots,
+                    order.max_base_lots,
+                    i64::MAX,
+                    oracle_price,
+                ),
+            )?;
+        }
+    }
+
+    let account_pk = ctx.accounts.account.key();
+
+    let (perp_market_index, settle_token_index) = {
+        let perp_market = ctx.accounts.perp_market.load()?;
+        (
+            perp_market.perp_market_index,
+            perp_market.settle_token_index,
+        )
+    };
+
+    //
+    // Create the perp position if needed
+    //
+    account.ensure_perp_position(perp_market_index, settle_token_index)?;
+
+    //
+    // Pre-health computation, _after_ perp position is created
+    //
+    let pre_health_opt = if !account.fixed.is_in_health_region() {
+        let retriever = new_fixed_order_account_retriever_with_optional_banks(
+            ctx.remaining_accounts,
+            &account.borrow(),
+            (now_ts, now_slot),
+        )?;
+        let health_cache = new_health_cache_skipping_missing_banks_and_bad_oracles(
+            &account.borrow(),
+            &retriever,
+            now_ts,
+        )
+        .context("pre init health")?;
+
+        // The settle token banks/oracles must be passed and be valid
+        health_cache.token_info_index(settle_token_index)?;
+
+        let pre_init_health = account.check_health_pre(&health_cache)?;
+        Some((health_cache, pre_init_health))
+    } else {
+        None
+    };
+
+    let mut perp_market = ctx.accounts.perp_market.load_mut()?;
+    let mut book = Orderbook {
+        bids: ctx.accounts.bids.load_full_mut()?,
+        asks: ctx.accounts.asks.load_full_mut()?,
+    };
+
+    let mut event_queue = ctx.accounts.event_queue.load_full_mut()?;
+    let group = ctx.accounts.group.load()?;
+
+    account
+        .fixed
+        .expire_buyback_fees(now_ts, group.buyback_fees_expiry_interval);
+
+    if cancel_all_first {
+        book.cancel_all_orders(
+            &mut account.borrow_mut(),
+            &account_pk,
+            &mut perp_market,
+            u8::MAX,
+            None,
+        )?;
+    }
+
+    for (batch_index, batch_order) in orders.iter().enumerate() {
+        let order = Order {
+            side: batch_order.side,
+            max_base_lots: batch_order.max_base_lots,
+            max_quote_lots: i64::MAX,
+            client_order_id: batch_order.client_order_id,
+            reduce_only: false,
+            time_in_force,
+            self_trade_behavior: SelfTradeBehavior::default(),
+            params: match batch_order.order_type {
+                PlaceOrderType::Market => OrderParams::Market {},
+                PlaceOrderType::ImmediateOrCancel => OrderParams::ImmediateOrCancel {
+                    price_lots: batch_order.price_lots,
+                },
+                _ => OrderParams::Fixed {
+                    price_lots: batch_order.price_lots,
+                    order_type: batch_order.order_type.to_post_order_type()?,
+                },
+            },
+        };
+
+        let order_id_opt = book.new_order(
+            order,
+            &mut perp_market,
+            &mut event_queue,
+            oracle_price,
+            &mut account.borrow_mut(),
+            &account_pk,
+            now_ts,
+            limit,
+            &group.perp_fee_tiers,
+        )?;
+
+        emit_stack(PerpBatchOrderLog {
+            mango_group: ctx.accounts.group.key(),
+            mango_account: account_pk,
+            perp_market_index,
+            batch_index: batch_index as u8,
+            client_order_id: batch_order.client_order_id,
+            posted: order_id_opt.is_some(),
+            order_id: order_id_opt.unwrap_or(0),
+        });
+    }
+
+    //
+    // Health check, once for all orders
+    //
+    if let Some((mut health_cache, pre_init_health)) = pre_health_opt {
+        let perp_position = account.perp_position(perp_market_index)?;
+        health_cache.recompute_perp_info(perp_position, &perp_market)?;
+        account.check_health_post(&health_cache, pre_init_health)?;
+    }
+
+    Ok(())
+}
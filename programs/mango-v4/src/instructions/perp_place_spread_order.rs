@@ -0,0 +1,309 @@
+use anchor_lang::prelude::*;
+use fixed::types::I80F48;
+
+use crate::accounts_ix::*;
+use crate::accounts_zerocopy::*;
+use crate::error::*;
+use crate::health::*;
+use crate::logs::{emit_stack, PerpSpreadOrderLog};
+use crate::state::*;
+use crate::util::clock_now;
+
+/// Places two linked immediate-or-cancel orders on two perp markets.
+///
+/// The base lots filled on leg A, scaled by `leg_b_ratio`, determine the size of
+/// leg B, which must fill completely. Health is checked once for the combined
+/// result, so only the spread's net risk needs to be covered. Inside a health
+/// region the check is deferred to HealthRegionEnd, like for other perp orders.
+///
+/// Both legs must be perp markets. For a perp against a spot token, place the perp
+/// order and a serum3 or openbook_v2 immediate-or-cancel order inside a health
+/// region instead; the spot leg is then not sized by the perp fill.
+#[allow(clippy::too_many_arguments)]
+pub fn perp_place_spread_order(
+    ctx: Context<PerpPlaceSpreadOrder>,
+    side_a: Side,
+    price_lots_a: i64,
+    max_base_lots_a: i64,
+    side_b: Side,
+    price_lots_b: i64,
+    leg_b_ratio: f64,
+    limit: u8,
+) -> Result<()> {
+    require_gt!(max_base_lots_a, 0);
+    require_msg!(
+        leg_b_ratio.is_finite() && leg_b_ratio > 0.0,
+        "leg_b_ratio must be positive"
+    );
+
+    let (now_ts, now_slot) = clock_now();
+
+    // Update funding on both markets, like in perp_place_order
+    let oracle_price_a = update_funding(
+        &ctx.accounts.perp_market_a,
+        &ctx.accounts.bids_a,
+        &ctx.accounts.asks_a,
+        ctx.accounts.oracle_a.as_ref(),
+        now_ts,
+        now_slot,
+    )?;
+    let oracle_price_b = update_funding(
+        &ctx.accounts.perp_market_b,
+        &ctx.accounts.bids_b,
+        &ctx.accounts.asks_b,
+        ctx.accounts.oracle_b.as_ref(),
+        now_ts,
+        now_slot,
+    )?;
+
+    let mut account = ctx.accounts.account.load_full_mut()?;
+    let account_pk = ctx.accounts.account.key();
+
+    {
+        let owner = ctx.accounts.owner.key();
+        let perp_market_a = ctx.accounts.perp_market_a.load()?;
+        let perp_market_b = ctx.accounts.perp_market_b.load()?;
+        let max_base_lots_b = (max_base_lots_a as f64 * leg_b_ratio).round() as i64;
+        account
+            .fixed
+            .check_delegate_permission(owner, DelegatePermission::PerpTrade)?;
+        account
+            .fixed
+            .check_delegate_perp_market(owner, perp_market_a.perp_market_index)?;
+        account
+            .fixed
+            .check_delegate_perp_market(owner, perp_market_b.perp_market_index)?;
+        account.fixed.check_delegate_order_notional(
+            owner,
+            perp_market_a.order_notional(
+                side_a,
+                price_lots_a,
+                max_base_lots_a,
+                i64::MAX,
+                oracle_price_a,
+            ),
+        )?;
+        account.fixed.check_delegate_order_notional(
+            owner,
+            perp_market_b.order_notional(
+                side_b,
+                price_lots_b,
+                max_base_lots_b,
+                i64::MAX,
+                oracle_price_b,
+            ),
+        )?;
+    }
+
+    let (market_index_a, settle_token_index_a) = {
+        let perp_market = ctx.accounts.perp_market_a.load()?;
+        (
+            perp_market.perp_market_index,
+            perp_market.settle_token_index,
+        )
+    };
+    let (market_index_b, settle_token_index_b) = {
+        let perp_market = ctx.accounts.perp_market_b.load()?;
+        (
+            perp_market.perp_market_index,
+            perp_market.settle_token_index,
+        )
+    };
+
+    //
+    // Create the perp positions if needed
+    //
+    account.ensure_perp_position(market_index_a, settle_token_index_a)?;
+    account.ensure_perp_position(market_index_b, settle_token_index_b)?;
+
+    //
+    // Pre-health computation, _after_ perp positions are created
+    //
+    let pre_health_opt = if !account.fixed.is_in_health_region() {
+        let retriever = new_fixed_order_account_retriever_with_optional_banks(
+            ctx.remaining_accounts,
+            &account.borrow(),
+            (now_ts, now_slot),
+        )?;
+        let health_cache = new_health_cache_skipping_missing_banks_and_bad_oracles(
+            &account.borrow(),
+            &retriever,
+            now_ts,
+        )
+        .context("pre init health")?;
+
+        // The settle token banks/oracles must be passed and be valid
+        health_cache.token_info_index(settle_token_index_a)?;
+        health_cache.token_info_index(settle_token_index_b)?;
+
+        let pre_init_health = account.check_health_pre(&health_cache)?;
+        Some((health_cache, pre_init_health))
+    } else {
+        None
+    };
+
+    let group = ctx.accounts.group.load()?;
+    account
+        .fixed
+        .expire_buyback_fees(now_ts, group.buyback_fees_expiry_interval);
+
+    //
+    // Leg A
+    //
+    let mut perp_market_a = ctx.accounts.perp_market_a.load_mut()?;
+    let filled_base_lots_a = place_leg(
+        &mut account.borrow_mut(),
+        &account_pk,
+        &mut perp_market_a,
+        Orderbook {
+            bids: ctx.accounts.bids_a.load_full_mut()?,
+            asks: ctx.accounts.asks_a.load_full_mut()?,
+        },
+        &mut ctx.accounts.event_queue_a.load_full_mut()?,
+        oracle_price_a,
+        side_a,
+        price_lots_a,
+        max_base_lots_a,
+        now_ts,
+        limit,
+        &group.perp_fee_tiers,
+    )?;
+
+    //
+    // Leg B, scaled by the fill of leg A
+    //
+    let target_base_lots_b = (filled_base_lots_a as f64 * leg_b_ratio).round() as i64;
+    let mut perp_market_b = ctx.accounts.perp_market_b.load_mut()?;
+    let filled_base_lots_b = if target_base_lots_b > 0 {
+        place_leg(
+            &mut account.borrow_mut(),
+            &account_pk,
+            &mut perp_market_b,
+            Orderbook {
+                bids: ctx.accounts.bids_b.load_full_mut()?,
+                asks: ctx.accounts.asks_b.load_full_mut()?,
+            },
+            &mut ctx.accounts.event_queue_b.load_full_mut()?,
+            oracle_price_b,
+            side_b,
+            price_lots_b,
+            target_base_lots_b,
+            now_ts,
+            limit,
+            &group.perp_fee_tiers,
+        )?
+    } else {
+        0
+    };
+    require_msg_typed!(
+        filled_base_lots_b == target_base_lots_b,
+        MangoError::PerpSpreadLegNotFilled,
+        "leg b filled {} of {} base lots",
+        filled_base_lots_b,
+        target_base_lots_b
+    );
+
+    emit_stack(PerpSpreadOrderLog {
+        mango_group: ctx.accounts.group.key(),
+        mango_account: account_pk,
+        market_index_a,
+        market_index_b,
+        side_a: side_a.into(),
+        side_b: side_b.into(),
+        filled_base_lots_a,
+        filled_base_lots_b,
+    });
+
+    //
+    // Combined health check
+    //
+    if let Some((mut health_cache, pre_init_health)) = pre_health_opt {
+        let perp_position = account.perp_position(market_index_a)?;
+        health_cache.recompute_perp_info(perp_position, &perp_market_a)?;
+        let perp_position = account.perp_position(market_index_b)?;
+        health_cache.recompute_perp_info(perp_position, &perp_market_b)?;
+        account.check_health_post(&health_cache, pre_init_health)?;
+    }
+
+    Ok(())
+}
+
+fn update_funding(
+    perp_market: &AccountLoader<PerpMarket>,
+    bids: &AccountLoader<BookSideAccount>,
+    asks: &AccountLoader<BookSideAccount>,
+    oracle: &AccountInfo,
+    now_ts: u64,
+    now_slot: u64,
+) -> Result<I80F48> {
+    let mut perp_market = perp_market.load_mut()?;
+    let book = Orderbook {
+        bids: bids.load_full_mut()?,
+        asks: asks.load_full_mut()?,
+    };
+
+    let oracle_ref = &AccountInfoRef::borrow(oracle)?;
+    // Spread legs are never reduce-only, so both oracles need the stricter
+    // risk-increasing freshness
+    let oracle_state = perp_market.oracle_state_for(
+        &OracleAccountInfos::from_reader(oracle_ref),
+        Some((now_ts, now_slot)),
+        OracleFreshness::RiskIncreasing,
+    )?;
+    perp_market.update_funding_and_stable_price(&book, &oracle_state, now_ts)?;
+    Ok(oracle_state.price)
+}
+
+/// Places an immediate-or-cancel order and returns the filled base lots
+#[allow(clippy::too_many_arguments)]
+fn place_leg(
+    account: &mut MangoAccountRefMut,
+    account_pk: &Pubkey,
+    perp_market: &mut PerpMarket,
+    mut book: Orderbook,
+    event_queue: &mut EventQueue,
+    oracle_price: I80F48,
+    side: Side,
+    price_lots: i64,
+    max_base_lots: i64,
+    now_ts: u64,
+    limit: u8,
+    fee_tiers: &[PerpFeeTier],
+) -> Result<i64> {
+    require!(
+        !perp_market.is_reduce_only(),
+        MangoError::MarketInReduceOnlyMode
+    );
+
+    let market_index = perp_market.perp_market_index;
+    let pre_position = account
+        .perp_position(market_index)?
+        .effective_base_position_lots();
+
+    let order = Order {
+        side,
+        max_base_lots,
+        max_quote_lots: i64::MAX,
+        client_order_id: 0,
+        reduce_only: false,
+        time_in_force: 0,
+        self_trade_behavior: SelfTradeBehavior::AbortTransaction,
+        params: OrderParams::ImmediateOrCancel { price_lots },
+    };
+    book.new_order(
+        order,
+        perp_market,
+        event_queue,
+        oracle_price,
+        account,
+        account_pk,
+        now_ts,
+        limit,
+        fee_tiers,
+    )?;
+
+    let post_position = account
+        .perp_position(market_index)?
+        .effective_base_position_lots();
+    Ok((post_position - pre_position).abs())
+}
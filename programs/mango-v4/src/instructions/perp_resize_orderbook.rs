@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+
+use crate::accounts_ix::*;
+use crate::error::*;
+use crate::state::*;
+
+/// Grows a perp market's bookside and event queue accounts to new capacities.
+///
+/// An account can only grow by MAX_PERMITTED_DATA_INCREASE bytes per instruction, so
+/// large increases need several calls with the same arguments. Capacities can't shrink.
+pub fn perp_resize_orderbook(
+    ctx: Context<PerpResizeOrderbook>,
+    bookside_capacity: u32,
+    event_queue_capacity: u32,
+) -> Result<()> {
+    let bookside_size = BookSide::account_size(bookside_capacity as usize);
+    let event_queue_size = EventQueue::account_size(event_queue_capacity as usize);
+
+    let bids_ai = ctx.accounts.bids.as_ref();
+    let asks_ai = ctx.accounts.asks.as_ref();
+    let event_queue_ai = ctx.accounts.event_queue.as_ref();
+    require_msg!(
+        bids_ai.data_len() <= bookside_size && asks_ai.data_len() <= bookside_size,
+        "bookside capacity can't decrease"
+    );
+    require_msg!(
+        event_queue_ai.data_len() <= event_queue_size,
+        "event queue capacity can't decrease"
+    );
+
+    // New event slots are added at the end of the buffer, which is only correct if
+    // the events don't wrap around it
+    if event_queue_ai.data_len() < event_queue_size {
+        ctx.accounts.event_queue.load_full_mut()?.rotate_to_start();
+    }
+
+    for (account_ai, target_size) in [
+        (bids_ai, bookside_size),
+        (asks_ai, bookside_size),
+        (event_queue_ai, event_queue_size),
+    ] {
+        grow_account(
+            account_ai,
+            target_size,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+        )?;
+    }
+
+    msg!(
+        "bookside capacity: {}, event queue capacity: {}",
+        BookSide::capacity_for_account_size(bids_ai.data_len()),
+        EventQueue::capacity_for_account_size(event_queue_ai.data_len()),
+    );
+
+    Ok(())
+}
+
+/// Reallocs the account towards target_size, paying for the additional rent
+fn grow_account<'info>(
+    account_ai: &AccountInfo<'info>,
+    target_size: usize,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let current_size = account_ai.data_len();
+    if current_size >= target_size {
+        return Ok(());
+    }
+    let new_size = target_size.min(current_size + MAX_PERMITTED_DATA_INCREASE);
+
+    let new_rent_minimum = Rent::get()?.minimum_balance(new_size);
+    let current_lamports = account_ai.lamports();
+    if current_lamports < new_rent_minimum {
+        anchor_lang::system_program::transfer(
+            anchor_lang::context::CpiContext::new(
+                system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account_ai.clone(),
+                },
+            ),
+            new_rent_minimum - current_lamports,
+        )?;
+    }
+
+    // This is the only realloc of the account in this instruction, so the new data
+    // is zero initialized already
+    account_ai.realloc(new_size, false)?;
+
+    Ok(())
+}
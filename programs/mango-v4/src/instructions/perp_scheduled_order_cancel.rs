@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::error::MangoError;
+use crate::logs::{emit_stack, PerpScheduledOrderCancelLog};
+use crate::state::*;
+
+pub fn perp_scheduled_order_cancel(
+    ctx: Context<PerpScheduledOrderCancel>,
+    perp_scheduled_order_index: usize,
+    perp_scheduled_order_id: u64,
+) -> Result<()> {
+    let account_key = ctx.accounts.account.key();
+    let mut account = ctx.accounts.account.load_full_mut()?;
+    account
+        .fixed
+        .check_delegate_permission(ctx.accounts.authority.key(), DelegatePermission::PerpCancel)?;
+    let order = account.perp_scheduled_order_mut_by_index(perp_scheduled_order_index)?;
+
+    // If the scheduled order is already inactive, this just is a noop
+    if !order.is_configured() {
+        return Ok(());
+    }
+
+    require_eq!(
+        order.id,
+        perp_scheduled_order_id,
+        MangoError::PerpScheduledOrderIndexIdMismatch
+    );
+    let scheduled = order.clone();
+    *order = PerpScheduledOrder::default();
+
+    let perp_market = ctx.accounts.perp_market.load()?;
+    require_eq!(perp_market.perp_market_index, scheduled.market_index);
+    let mut book = Orderbook {
+        bids: ctx.accounts.bids.load_full_mut()?,
+        asks: ctx.accounts.asks.load_full_mut()?,
+    };
+    cancel_iceberg_child(
+        &mut account.borrow_mut(),
+        &account_key,
+        &scheduled,
+        &mut book,
+    )?;
+
+    emit_stack(PerpScheduledOrderCancelLog {
+        mango_group: ctx.accounts.group.key(),
+        mango_account: account_key,
+        id: perp_scheduled_order_id,
+    });
+
+    Ok(())
+}
+
+/// Cancels the child order an iceberg has resting on the book, if there is one.
+///
+/// The child must not outlive its parent, otherwise it could fill after the user
+/// cancelled the iceberg.
+pub(crate) fn cancel_iceberg_child(
+    account: &mut MangoAccountRefMut,
+    account_key: &Pubkey,
+    scheduled: &PerpScheduledOrder,
+    book: &mut Orderbook,
+) -> Result<()> {
+    if scheduled.order_type() != PerpScheduledOrderType::Iceberg {
+        return Ok(());
+    }
+    let slot_opt = account
+        .all_perp_orders()
+        .position(|oo| scheduled.is_child_order(oo));
+    if let Some(slot) = slot_opt {
+        book.cancel_order_by_slot(account, account_key, slot, scheduled.market_index)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::error::*;
+use crate::logs::{emit_stack, PerpScheduledOrderCreateLog};
+use crate::state::*;
+
+#[allow(clippy::too_many_arguments)]
+pub fn perp_scheduled_order_create(
+    ctx: Context<PerpScheduledOrderCreate>,
+    side: Side,
+    order_type: PerpScheduledOrderType,
+    max_base_lots: i64,
+    child_base_lots: i64,
+    price_lots: i64,
+    interval_seconds: u64,
+    expiry_timestamp: u64,
+    reduce_only: bool,
+) -> Result<()> {
+    require_gt!(max_base_lots, 0);
+    require_gt!(child_base_lots, 0);
+    require_gte!(max_base_lots, child_base_lots);
+    require_gt!(price_lots, 0);
+    require_msg!(
+        matches!(
+            order_type,
+            PerpScheduledOrderType::Twap | PerpScheduledOrderType::Iceberg
+        ),
+        "use perp_conditional_order_create for conditional orders"
+    );
+
+    let now_ts: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
+    if expiry_timestamp <= now_ts {
+        msg!("Already expired, ignoring");
+        return Ok(());
+    }
+
+    let perp_market = ctx.accounts.perp_market.load()?;
+    require!(
+        reduce_only || !perp_market.is_reduce_only(),
+        MangoError::MarketInReduceOnlyMode
+    );
+
+    let mut account = ctx.accounts.account.load_full_mut()?;
+    let authority = ctx.accounts.authority.key();
+    account
+        .fixed
+        .check_delegate_permission(authority, DelegatePermission::PerpTrade)?;
+    account
+        .fixed
+        .check_delegate_perp_market(authority, perp_market.perp_market_index)?;
+    if !reduce_only {
+        // there's no oracle account here, the stable price stands in for it
+        account.fixed.check_delegate_order_notional(
+            authority,
+            perp_market.order_notional(
+                side,
+                price_lots,
+                max_base_lots,
+                i64::MAX,
+                perp_market.stable_price(),
+            ),
+        )?;
+    }
+
+    let id = account.fixed.next_perp_scheduled_order_id;
+    account.fixed.next_perp_scheduled_order_id =
+        account.fixed.next_perp_scheduled_order_id.wrapping_add(1);
+
+    let order = account.free_perp_scheduled_order_mut()?;
+    *order = PerpScheduledOrder {
+        id,
+        max_base_lots,
+        executed_base_lots: 0,
+        child_base_lots,
+        price_lots,
+        interval_seconds,
+        next_trigger_timestamp: now_ts,
+        expiry_timestamp,
+        market_index: perp_market.perp_market_index,
+        is_configured: 1,
+        side: side.into(),
+        order_type: order_type.into(),
+        reduce_only: u8::from(reduce_only),
+        ..PerpScheduledOrder::default()
+    };
+
+    emit_stack(PerpScheduledOrderCreateLog {
+        mango_group: ctx.accounts.group.key(),
+        mango_account: ctx.accounts.account.key(),
+        id,
+        market_index: order.market_index,
+        side: order.side,
+        order_type: order.order_type,
+        reduce_only,
+        max_base_lots,
+        child_base_lots,
+        price_lots,
+        interval_seconds,
+        expiry_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,304 @@
+use anchor_lang::prelude::*;
+use fixed::types::I80F48;
+
+use crate::accounts_ix::*;
+use crate::accounts_zerocopy::*;
+use crate::error::*;
+use crate::health::*;
+use crate::i80f48::ClampToInt;
+use crate::logs::{
+    emit_stack, PerpConditionalOrderTriggerLog, PerpScheduledOrderCancelLog,
+    PerpScheduledOrderTriggerLog, TokenBalanceLog,
+};
+use crate::state::*;
+use crate::util::clock_now;
+
+use super::perp_place_order::reduce_only_max_base_lots;
+use super::perp_scheduled_order_cancel::cancel_iceberg_child;
+
+pub fn perp_scheduled_order_trigger(
+    ctx: Context<PerpScheduledOrderTrigger>,
+    perp_scheduled_order_index: usize,
+    perp_scheduled_order_id: u64,
+    limit: u8,
+) -> Result<()> {
+    let group_pk = ctx.accounts.group.key();
+    let account_key = ctx.accounts.account.key();
+    let caller_key = ctx.accounts.caller.key();
+    let (now_ts, now_slot) = clock_now();
+
+    let mut account = ctx.accounts.account.load_full_mut()?;
+    let mut caller = ctx.accounts.caller.load_full_mut()?;
+
+    let scheduled = account
+        .perp_scheduled_order_by_index(perp_scheduled_order_index)?
+        .clone();
+    require!(
+        scheduled.is_configured(),
+        MangoError::PerpScheduledOrderNotSet
+    );
+    require_eq!(
+        scheduled.id,
+        perp_scheduled_order_id,
+        MangoError::PerpScheduledOrderIndexIdMismatch
+    );
+
+    // Possibly wipe the scheduled order and exit, if it's already expired
+    if scheduled.is_expired(now_ts) {
+        msg!("PerpScheduledOrder is expired, removing");
+        *account.perp_scheduled_order_mut_by_index(perp_scheduled_order_index)? =
+            PerpScheduledOrder::default();
+
+        let perp_market = ctx.accounts.perp_market.load()?;
+        require_eq!(perp_market.perp_market_index, scheduled.market_index);
+        let mut book = Orderbook {
+            bids: ctx.accounts.bids.load_full_mut()?,
+            asks: ctx.accounts.asks.load_full_mut()?,
+        };
+        cancel_iceberg_child(
+            &mut account.borrow_mut(),
+            &account_key,
+            &scheduled,
+            &mut book,
+        )?;
+
+        emit_stack(PerpScheduledOrderCancelLog {
+            mango_group: group_pk,
+            mango_account: account_key,
+            id: perp_scheduled_order_id,
+        });
+        return Ok(());
+    }
+
+    let (perp_market_index, settle_token_index) = {
+        let perp_market = ctx.accounts.perp_market.load()?;
+        require_eq!(perp_market.perp_market_index, scheduled.market_index);
+        (
+            perp_market.perp_market_index,
+            perp_market.settle_token_index,
+        )
+    };
+
+    // An iceberg only places its next child once the previous one left the book
+    if scheduled.order_type() == PerpScheduledOrderType::Iceberg {
+        require_msg_typed!(
+            !account
+                .all_perp_orders()
+                .any(|oo| scheduled.is_child_order(oo)),
+            MangoError::PerpScheduledOrderNotTriggerable,
+            "previous child order is still on the book"
+        );
+    }
+
+    // Update funding if possible, like in perp_place_order.
+    let oracle_price;
+    let oracle_price_lots;
+    {
+        let mut perp_market = ctx.accounts.perp_market.load_mut()?;
+        let book = Orderbook {
+            bids: ctx.accounts.bids.load_full_mut()?,
+            asks: ctx.accounts.asks.load_full_mut()?,
+        };
+
+        let oracle_ref = &AccountInfoRef::borrow(ctx.accounts.oracle.as_ref())?;
+        let oracle_state = perp_market.oracle_state(
+            &OracleAccountInfos::from_reader(oracle_ref),
+            None, // staleness checked in health
+        )?;
+        oracle_price = oracle_state.price;
+        oracle_price_lots = perp_market.native_price_to_lot(oracle_price);
+
+        perp_market.update_funding_and_stable_price(&book, &oracle_state, now_ts)?;
+    }
+
+    scheduled.check_triggerable(now_ts, oracle_price_lots)?;
+
+    //
+    // Create the perp position if needed
+    //
+    account.ensure_perp_position(perp_market_index, settle_token_index)?;
+
+    //
+    // Pre-health computation, _after_ perp position is created
+    //
+    let mut health_cache = {
+        let retriever = new_fixed_order_account_retriever_with_optional_banks(
+            ctx.remaining_accounts,
+            &account.borrow(),
+            (now_ts, now_slot),
+        )?;
+        new_health_cache_skipping_missing_banks_and_bad_oracles(
+            &account.borrow(),
+            &retriever,
+            now_ts,
+        )
+        .context("pre init health")?
+    };
+    let pre_init_health = account.check_health_pre(&health_cache)?;
+
+    //
+    // Place the child order
+    //
+    let mut perp_market = ctx.accounts.perp_market.load_mut()?;
+    let mut book = Orderbook {
+        bids: ctx.accounts.bids.load_full_mut()?,
+        asks: ctx.accounts.asks.load_full_mut()?,
+    };
+    let mut event_queue = ctx.accounts.event_queue.load_full_mut()?;
+    let group = ctx.accounts.group.load()?;
+
+    account
+        .fixed
+        .expire_buyback_fees(now_ts, group.buyback_fees_expiry_interval);
+
+    let mut order = scheduled.child_order(oracle_price_lots);
+    let pp = account.perp_position(perp_market_index)?;
+    let pre_effective_pos = pp.effective_base_position_lots();
+    let max_base_lots = if order.reduce_only || perp_market.is_reduce_only() {
+        reduce_only_max_base_lots(pp, &order, perp_market.is_reduce_only())
+    } else {
+        order.max_base_lots
+    };
+    if perp_market.is_reduce_only() {
+        require!(
+            order.reduce_only || max_base_lots == order.max_base_lots,
+            MangoError::MarketInReduceOnlyMode
+        )
+    };
+    order.max_base_lots = max_base_lots;
+
+    let child_order_id = book.new_order(
+        order,
+        &mut perp_market,
+        &mut event_queue,
+        oracle_price,
+        &mut account.borrow_mut(),
+        &account_key,
+        now_ts,
+        limit,
+        &group.perp_fee_tiers,
+    )?;
+
+    let perp_position = account.perp_position(perp_market_index)?;
+    let child_base_lots = match scheduled.order_type() {
+        // Only the filled amount counts, the rest of the IOC order was dropped
+        PerpScheduledOrderType::Twap
+        | PerpScheduledOrderType::StopLoss
+        | PerpScheduledOrderType::TakeProfit => {
+            (perp_position.effective_base_position_lots() - pre_effective_pos).abs()
+        }
+        PerpScheduledOrderType::Iceberg => max_base_lots,
+    };
+    // Orders that take liquidity stay active until they can execute, empty triggers are rejected
+    if scheduled.order_type() != PerpScheduledOrderType::Iceberg {
+        require_msg_typed!(
+            child_base_lots > 0 || max_base_lots == 0,
+            MangoError::PerpScheduledOrderNotTriggerable,
+            "no liquidity within the slippage limit"
+        );
+    }
+    let position_closed = perp_position.effective_base_position_lots() == 0;
+    health_cache.recompute_perp_info(perp_position, &perp_market)?;
+
+    //
+    // Transfer the incentive to the caller, only for triggers that traded or placed lots
+    //
+    let incentive_native = if child_base_lots > 0 {
+        let mut settle_bank = ctx.accounts.settle_bank.load_mut()?;
+        // The settle token banks/oracles must be passed and be valid
+        let settle_oracle_price = health_cache.token_info(settle_token_index)?.prices.oracle;
+
+        let incentive = I80F48::from(PERP_SCHEDULED_ORDER_INCENTIVE) / settle_oracle_price;
+
+        let (account_token, _) = account.token_position_mut(settle_token_index)?;
+        let (caller_token, _, _) = caller.ensure_token_position(settle_token_index)?;
+
+        let account_pre_balance = account_token.native(&settle_bank);
+        settle_bank.checked_transfer_with_fee(
+            account_token,
+            incentive,
+            caller_token,
+            incentive,
+            now_ts,
+            settle_oracle_price,
+        )?;
+        let account_post_balance = account_token.native(&settle_bank);
+        health_cache
+            .adjust_token_balance(&settle_bank, account_post_balance - account_pre_balance)?;
+
+        emit_stack(TokenBalanceLog {
+            mango_group: group_pk,
+            mango_account: account_key,
+            token_index: settle_token_index,
+            indexed_position: account_token.indexed_position.to_bits(),
+            deposit_index: settle_bank.deposit_index.to_bits(),
+            borrow_index: settle_bank.borrow_index.to_bits(),
+        });
+        emit_stack(TokenBalanceLog {
+            mango_group: group_pk,
+            mango_account: caller_key,
+            token_index: settle_token_index,
+            indexed_position: caller_token.indexed_position.to_bits(),
+            deposit_index: settle_bank.deposit_index.to_bits(),
+            borrow_index: settle_bank.borrow_index.to_bits(),
+        });
+
+        incentive.clamp_to_u64()
+    } else {
+        0
+    };
+
+    //
+    // Update the scheduled order
+    //
+    let stored = account.perp_scheduled_order_mut_by_index(perp_scheduled_order_index)?;
+    stored.executed_base_lots += child_base_lots;
+    if scheduled.order_type() == PerpScheduledOrderType::Iceberg {
+        stored.set_child_order(scheduled.side(), child_order_id);
+    }
+    stored.next_trigger_timestamp = now_ts + scheduled.interval_seconds;
+    let executed_base_lots = stored.executed_base_lots;
+    // A reduce-only order that can't reduce anymore is done as well
+    if stored.remaining_base_lots() <= 0
+        || (scheduled.is_reduce_only() && (max_base_lots == 0 || position_closed))
+    {
+        *stored = PerpScheduledOrder::default();
+    }
+
+    if scheduled.is_conditional() {
+        emit_stack(PerpConditionalOrderTriggerLog {
+            mango_group: group_pk,
+            mango_account: account_key,
+            caller: caller_key,
+            id: scheduled.id,
+            market_index: perp_market_index,
+            order_type: scheduled.order_type,
+            side: scheduled.side,
+            oracle_price_lots,
+            trigger_price_lots: scheduled.trigger_price_lots,
+            limit_price_lots: scheduled.conditional_limit_price_lots(oracle_price_lots),
+            filled_base_lots: child_base_lots,
+            incentive_token_index: settle_token_index,
+            incentive_amount: incentive_native,
+        });
+    } else {
+        emit_stack(PerpScheduledOrderTriggerLog {
+            mango_group: group_pk,
+            mango_account: account_key,
+            caller: caller_key,
+            id: scheduled.id,
+            market_index: perp_market_index,
+            child_base_lots,
+            executed_base_lots,
+            incentive_token_index: settle_token_index,
+            incentive_amount: incentive_native,
+        });
+    }
+
+    //
+    // Health check
+    //
+    account.check_health_post(&health_cache, pre_init_health)?;
+
+    Ok(())
+}
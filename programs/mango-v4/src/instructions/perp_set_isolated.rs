@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::accounts_ix::*;
+use crate::error::*;
+use crate::logs::{emit_stack, PerpIsolatedMarginLog};
+use crate::state::*;
+
+/// Switches a perp position between cross and isolated margin.
+///
+/// This is only possible while the position is empty, so no pnl or risk moves between
+/// the isolated bucket and the rest of the account. Switching back to cross margin returns
+/// the remaining isolated collateral to the account.
+pub fn perp_set_isolated(ctx: Context<PerpSetIsolated>, isolated: bool) -> Result<()> {
+    let mut account = ctx.accounts.account.load_full_mut()?;
+    // account constraint #1
+    require!(
+        account.fixed.is_owner_or_delegate(ctx.accounts.owner.key()),
+        MangoError::SomeError
+    );
+    account
+        .fixed
+        .check_delegate_permission(ctx.accounts.owner.key(), DelegatePermission::PerpTrade)?;
+    account.fixed.check_delegate_perp_market(
+        ctx.accounts.owner.key(),
+        ctx.accounts.perp_market.load()?.perp_market_index,
+    )?;
+
+    let perp_market = ctx.accounts.perp_market.load()?;
+    let perp_position = account
+        .ensure_perp_position(
+            perp_market.perp_market_index,
+            perp_market.settle_token_index,
+        )?
+        .0;
+
+    perp_position.settle_funding(&perp_market);
+    require_msg!(
+        perp_position.base_position_lots() == 0,
+        "perp position still has base lots"
+    );
+    require_msg!(
+        perp_position.quote_position_native() == 0,
+        "perp position still has quote position"
+    );
+    require_msg!(
+        !perp_position.has_open_orders_or_fills(),
+        "perp position still has open orders or events on event queue"
+    );
+
+    perp_position.set_isolated(isolated);
+
+    emit_stack(PerpIsolatedMarginLog {
+        mango_group: ctx.accounts.group.key(),
+        mango_account: ctx.accounts.account.key(),
+        market_index: perp_market.perp_market_index,
+        isolated,
+        isolated_collateral: perp_position.isolated_collateral_native.to_bits(),
+    });
+
+    Ok(())
+}
@@ -63,13 +63,18 @@ pub fn perp_settle_fees(ctx: Context<PerpSettleFees>, max_settle_amount: u64) ->
     }
 
     // Settle for the maximum possible capped to max_settle_amount
-    let settlement = settleable_pnl
+    let mut settlement = settleable_pnl
         .abs()
         .min(perp_market.fees_accrued.abs())
         .min(I80F48::from(max_settle_amount));
+    // Isolated positions only pay losses out of their own collateral
+    if perp_position.is_isolated() {
+        settlement = settlement.min(perp_position.isolated_collateral_native.max(I80F48::ZERO));
+    }
     require!(settlement >= 0, MangoError::SettlementAmountMustBePositive);
 
     perp_position.record_settle(-settlement, &perp_market); // settle the negative pnl on the user perp position
+    perp_position.change_isolated_collateral(-settlement);
     perp_market.fees_accrued -= settlement;
 
     emit_perp_balances(
@@ -95,11 +100,15 @@ pub fn perp_settle_fees(ctx: Context<PerpSettleFees>, max_settle_amount: u64) ->
     let token_position = account
         .token_position_mut(perp_market.settle_token_index)?
         .0;
+    let before_borrows = settle_bank.indexed_borrows;
     settle_bank.withdraw_without_fee(
         token_position,
         settlement,
         Clock::get()?.unix_timestamp.try_into().unwrap(),
     )?;
+    if settle_bank.indexed_borrows > before_borrows + I80F48::DELTA {
+        settle_bank.check_borrow_limit()?;
+    }
     // Update the settled balance on the market itself
     perp_market.fees_settled += settlement;
 
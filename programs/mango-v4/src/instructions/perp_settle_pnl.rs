@@ -45,8 +45,15 @@ pub fn perp_settle_pnl(ctx: Context<PerpSettlePnl>) -> Result<()> {
         let retriever =
             ScanningAccountRetriever::new(ctx.remaining_accounts, &ctx.accounts.group.key())
                 .context("create account retriever")?;
-        b_max_settle = new_health_cache(&account_b.borrow(), &retriever, now_ts)?
-            .perp_max_settle(settle_token_index)?;
+        let b_cache = new_health_cache(&account_b.borrow(), &retriever, now_ts)?;
+        // Isolated positions only pay losses out of their own collateral
+        b_max_settle = if account_b.perp_position(perp_market_index)?.is_isolated() {
+            b_cache
+                .isolated_perp_bucket_cache(perp_market_index)?
+                .perp_max_settle(settle_token_index)?
+        } else {
+            b_cache.perp_max_settle(settle_token_index)?
+        };
         let a_cache = new_health_cache(&account_a.borrow(), &retriever, now_ts)?;
         a_liq_end_health = a_cache.health(HealthType::LiquidationEnd);
         a_maint_health = a_cache.health(HealthType::Maint);
@@ -78,8 +85,9 @@ pub fn perp_settle_pnl(ctx: Context<PerpSettlePnl>) -> Result<()> {
     let b_perp_position = account_b.perp_position_mut(perp_market_index)?;
     a_perp_position.settle_funding(&perp_market);
     b_perp_position.settle_funding(&perp_market);
-    let a_pnl = a_perp_position.unsettled_pnl(&perp_market, oracle_price)?;
-    let b_pnl = b_perp_position.unsettled_pnl(&perp_market, oracle_price)?;
+    let settle_price = perp_market.settle_price(oracle_price);
+    let a_pnl = a_perp_position.unsettled_pnl(&perp_market, settle_price)?;
+    let b_pnl = b_perp_position.unsettled_pnl(&perp_market, settle_price)?;
 
     // PnL must have opposite signs for there to be a settlement:
     // Account A must be profitable, and B must be unprofitable.
@@ -145,6 +153,7 @@ pub fn perp_settle_pnl(ctx: Context<PerpSettlePnl>) -> Result<()> {
 
     a_perp_position.record_settle(settlement, &perp_market);
     b_perp_position.record_settle(-settlement, &perp_market);
+    b_perp_position.change_isolated_collateral(-settlement);
     emit_perp_balances(
         ctx.accounts.group.key(),
         ctx.accounts.account_a.key(),
@@ -178,6 +187,7 @@ pub fn perp_settle_pnl(ctx: Context<PerpSettlePnl>) -> Result<()> {
     let fee_i64 = fee.round_to_zero().to_num::<i64>();
     (a_perp_position.perp_spot_transfers += settlement_i64 - fee_i64);
     (b_perp_position.perp_spot_transfers -= settlement_i64);
+    a_perp_position.change_isolated_collateral(settlement - fee);
     (account_a.fixed.perp_spot_transfers += settlement_i64 - fee_i64);
     (account_b.fixed.perp_spot_transfers -= settlement_i64);
 
@@ -185,11 +195,15 @@ pub fn perp_settle_pnl(ctx: Context<PerpSettlePnl>) -> Result<()> {
     // The fee is paid by the account with positive unsettled pnl
     let a_token_position = account_a.token_position_mut(settle_token_index)?.0;
     let b_token_position = account_b.token_position_mut(settle_token_index)?.0;
+    let before_borrows = settle_bank.indexed_borrows;
     settle_bank.deposit(a_token_position, settlement - fee, now_ts)?;
     // Don't charge loan origination fees on borrows created via settling:
     // Even small loan origination fees could accumulate if a perp position is
     // settled back and forth repeatedly.
     settle_bank.withdraw_without_fee(b_token_position, settlement, now_ts)?;
+    if settle_bank.indexed_borrows > before_borrows + I80F48::DELTA {
+        settle_bank.check_borrow_limit()?;
+    }
 
     emit_stack(TokenBalanceLog {
         mango_group: ctx.accounts.group.key(),
@@ -9,8 +9,8 @@ pub fn perp_update_funding(ctx: Context<PerpUpdateFunding>) -> Result<()> {
 
     let mut perp_market = ctx.accounts.perp_market.load_mut()?;
     let book = Orderbook {
-        bids: ctx.accounts.bids.load_mut()?,
-        asks: ctx.accounts.asks.load_mut()?,
+        bids: ctx.accounts.bids.load_full_mut()?,
+        asks: ctx.accounts.asks.load_full_mut()?,
     };
 
     let now_slot = Clock::get()?.slot;
@@ -17,6 +17,13 @@ pub fn serum3_cancel_all_orders(ctx: Context<Serum3CancelAllOrders>, limit: u8)
             account.fixed.is_owner_or_delegate(ctx.accounts.owner.key()),
             MangoError::SomeError
         );
+        account
+            .fixed
+            .check_delegate_permission(ctx.accounts.owner.key(), DelegatePermission::SpotCancel)?;
+        account.fixed.check_delegate_spot_market(
+            ctx.accounts.owner.key(),
+            ctx.accounts.serum_market.load()?.market_index,
+        )?;
 
         let serum_market = ctx.accounts.serum_market.load()?;
 
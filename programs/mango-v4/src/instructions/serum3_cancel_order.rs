@@ -26,6 +26,12 @@ pub fn serum3_cancel_order(
             account.fixed.is_owner_or_delegate(ctx.accounts.owner.key()),
             MangoError::SomeError
         );
+        account
+            .fixed
+            .check_delegate_permission(ctx.accounts.owner.key(), DelegatePermission::SpotCancel)?;
+        account
+            .fixed
+            .check_delegate_spot_market(ctx.accounts.owner.key(), serum_market.market_index)?;
 
         // Validate open_orders #2
         require!(
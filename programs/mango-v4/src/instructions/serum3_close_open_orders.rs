@@ -17,6 +17,11 @@ pub fn serum3_close_open_orders(ctx: Context<Serum3CloseOpenOrders>) -> Result<(
             || serum_market.is_force_close(),
         MangoError::SomeError
     );
+    if !serum_market.is_force_close() {
+        account
+            .fixed
+            .check_delegate_permission(ctx.accounts.owner.key(), DelegatePermission::SpotCancel)?;
+    }
 
     // Validate open_orders #2
     require!(
@@ -15,6 +15,12 @@ pub fn serum3_create_open_orders(ctx: Context<Serum3CreateOpenOrders>) -> Result
         account.fixed.is_owner_or_delegate(ctx.accounts.owner.key()),
         MangoError::SomeError
     );
+    account
+        .fixed
+        .check_delegate_permission(ctx.accounts.owner.key(), DelegatePermission::SpotTrade)?;
+    account
+        .fixed
+        .check_delegate_spot_market(ctx.accounts.owner.key(), serum_market.market_index)?;
 
     let serum_account = account.create_serum3_orders(serum_market.market_index)?;
     serum_account.open_orders = ctx.accounts.open_orders.key();
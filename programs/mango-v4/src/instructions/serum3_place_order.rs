@@ -49,6 +49,12 @@ pub fn serum3_place_order(
             account.fixed.is_owner_or_delegate(ctx.accounts.owner.key()),
             MangoError::SomeError
         );
+        account
+            .fixed
+            .check_delegate_permission(ctx.accounts.owner.key(), DelegatePermission::SpotTrade)?;
+        account
+            .fixed
+            .check_delegate_spot_market(ctx.accounts.owner.key(), serum_market.market_index)?;
 
         // Validate open_orders #2
         require!(
@@ -178,6 +184,17 @@ pub fn serum3_place_order(
         base_lot_size = market_state.coin_lot_size;
         quote_lot_size = market_state.pc_lot_size;
 
+        // Quote value of the order at its limit price, for the delegate notional limit
+        let mut notional = I80F48::from(max_base_qty)
+            .saturating_mul(I80F48::from(limit_price_lots))
+            .saturating_mul(I80F48::from(quote_lot_size));
+        if matches!(side, Serum3Side::Bid) {
+            notional = notional.min(I80F48::from(max_native_quote_qty_including_fees));
+        }
+        account
+            .fixed
+            .check_delegate_order_notional(ctx.accounts.owner.key(), notional)?;
+
         let needed_amount = match side {
             Serum3Side::Ask => {
                 (max_base_qty * base_lot_size).saturating_sub(before_oo.native_base_free())
@@ -342,10 +359,14 @@ pub fn serum3_place_order(
 
     // Payer bank safety checks like reduce-only, net borrows, vault-to-deposits ratio
     let payer_oracle_ref = &AccountInfoRef::borrow(&ctx.accounts.payer_oracle)?;
-    let payer_bank_oracle =
-        payer_bank.oracle_price(&OracleAccountInfos::from_reader(payer_oracle_ref), None)?;
+    let payer_bank_oracle = payer_bank.oracle_price_for(
+        &OracleAccountInfos::from_reader(payer_oracle_ref),
+        Some((now_ts, now_slot)),
+        OracleFreshness::RiskIncreasing,
+    )?;
     let withdrawn_from_vault = I80F48::from(before_vault - after_vault);
-    if withdrawn_from_vault > before_position_native {
+    let is_borrow = withdrawn_from_vault > before_position_native;
+    if is_borrow {
         require_msg_typed!(
             !payer_bank.are_borrows_reduce_only(),
             MangoError::TokenInReduceOnlyMode,
@@ -353,6 +374,7 @@ pub fn serum3_place_order(
         );
         payer_bank.enforce_max_utilization_on_borrow()?;
         payer_bank.check_net_borrows(payer_bank_oracle)?;
+        payer_bank.check_borrow_limit()?;
 
         // Deposit limit check, payer side:
         // The payer bank deposits could increase when cancelling the order later:
@@ -425,11 +447,11 @@ pub fn serum3_place_order(
     // Check the receiver's reduce only flag.
     //
     // Note that all orders on the book executing can still cause a net deposit. That's because
-    // the total serum3 potential amount assumes all reserved amounts convert at the current
+    // the total spot potential amount assumes all reserved amounts convert at the current
     // oracle price.
     //
-    // This also requires that all serum3 oos that touch the receiver_token are avaliable in the
-    // health cache. We make this a general requirement to avoid surprises.
+    // This also requires that all serum3 and openbook v2 oos that touch the receiver_token are
+    // avaliable in the health cache. We make this a general requirement to avoid surprises.
     for serum3 in account.active_serum3_orders() {
         if serum3.base_token_index == receiver_token_index
             || serum3.quote_token_index == receiver_token_index
@@ -441,10 +463,21 @@ pub fn serum3_place_order(
             );
         }
     }
+    for openbook in account.active_openbook_v2_orders() {
+        if openbook.base_token_index == receiver_token_index
+            || openbook.quote_token_index == receiver_token_index
+        {
+            require_msg!(
+                health_cache.openbook_v2_infos.iter().any(|obv2| obv2.market_index == openbook.market_index),
+                "health cache is missing openbook v2 info {} involving receiver token {}; passed banks and oracles?",
+                openbook.market_index, receiver_token_index
+            );
+        }
+    }
     if receiver_bank_reduce_only {
         let balance = health_cache.token_info(receiver_token_index)?.balance_spot;
         let potential =
-            health_cache.total_serum3_potential(HealthType::Maint, receiver_token_index)?;
+            health_cache.total_spot_potential(HealthType::Maint, receiver_token_index)?;
         require_msg_typed!(
             balance + potential < 1,
             MangoError::TokenInReduceOnlyMode,
@@ -455,8 +488,16 @@ pub fn serum3_place_order(
     //
     // Health check
     //
+    if is_borrow {
+        health_cache.check_isolated_collateral_allows_borrow(payer_token_index)?;
+    }
     if let Some(pre_init_health) = pre_health_opt {
         account.check_health_post(&health_cache, pre_init_health)?;
+
+        // The payer and receiver banks may be collateral banks that get updated
+        drop(payer_bank);
+        drop(receiver_bank);
+        account.update_isolated_debt(&health_cache, ctx.remaining_accounts)?;
     }
 
     Ok(())
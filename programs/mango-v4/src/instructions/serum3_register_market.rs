@@ -47,8 +47,8 @@ pub fn serum3_register_market(
 
         // C tier tokens (no borrows, no asset weight) allow wider bands if the quote token has
         // no deposit limits
-        let base_c_tier =
-            base_bank.are_borrows_reduce_only() && base_bank.maint_asset_weight.is_zero();
+        let base_c_tier = base_bank.are_borrows_configured_reduce_only()
+            && base_bank.maint_asset_weight.is_zero();
         let quote_has_no_deposit_limit = quote_bank.deposit_weight_scale_start_quote == f64::MAX
             && quote_bank.deposit_limit == 0;
         if base_c_tier && quote_has_no_deposit_limit {
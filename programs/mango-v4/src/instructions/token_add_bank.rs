@@ -11,6 +11,11 @@ pub fn token_add_bank(
     bank_num: u32,
 ) -> Result<()> {
     let existing_bank = ctx.accounts.existing_bank.load()?;
+    // borrow_limit is checked per bank and would no longer bound the token's borrows
+    require_msg!(
+        existing_bank.borrow_limit == 0,
+        "can't add banks to a token with a borrow limit"
+    );
     let mut bank = ctx.accounts.bank.load_init()?;
     let bump = *ctx.bumps.get("bank").ok_or(MangoError::SomeError)?;
     *bank = Bank::from_existing_bank(&existing_bank, ctx.accounts.vault.key(), bank_num, bump);
@@ -92,6 +92,10 @@ where
         token_balances[s3info.base_info_index].spot_and_perp += s3info.reserved_base;
         token_balances[s3info.quote_info_index].spot_and_perp += s3info.reserved_quote;
     }
+    for oinfo in health_cache.openbook_v2_infos.iter() {
+        token_balances[oinfo.base_info_index].spot_and_perp += oinfo.reserved_base;
+        token_balances[oinfo.quote_info_index].spot_and_perp += oinfo.reserved_quote;
+    }
 
     let mut total_liab_health = I80F48::ZERO;
     let mut total_asset_health = I80F48::ZERO;
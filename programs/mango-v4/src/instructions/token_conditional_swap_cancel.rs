@@ -15,6 +15,10 @@ pub fn token_conditional_swap_cancel(
     let mut sell_bank = ctx.accounts.sell_bank.load_mut()?;
 
     let mut account = ctx.accounts.account.load_full_mut()?;
+    account.fixed.check_delegate_permission(
+        ctx.accounts.authority.key(),
+        DelegatePermission::TokenConditionalSwap,
+    )?;
     let tcs = account.token_conditional_swap_mut_by_index(token_conditional_swap_index)?;
     require_eq!(tcs.buy_token_index, buy_bank.token_index);
     require_eq!(tcs.sell_token_index, sell_bank.token_index);
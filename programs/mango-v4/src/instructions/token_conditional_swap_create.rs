@@ -18,6 +18,10 @@ pub fn token_conditional_swap_create(
     }
 
     let mut account = ctx.accounts.account.load_full_mut()?;
+    account.fixed.check_delegate_permission(
+        ctx.accounts.authority.key(),
+        DelegatePermission::TokenConditionalSwap,
+    )?;
     {
         let buy_pos = account
             .ensure_token_position(token_conditional_swap.buy_token_index)?
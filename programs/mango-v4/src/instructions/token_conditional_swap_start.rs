@@ -22,6 +22,10 @@ pub fn token_conditional_swap_start(
     require!(!liqee.fixed.being_liquidated(), MangoError::BeingLiquidated,);
 
     let mut liqor = ctx.accounts.liqor.load_full_mut()?;
+    liqor.fixed.check_delegate_permission(
+        ctx.accounts.liqor_authority.key(),
+        DelegatePermission::Liquidate,
+    )?;
 
     let mut account_retriever = ScanningAccountRetriever::new(ctx.remaining_accounts, group_pk)
         .context("create account retriever")?;
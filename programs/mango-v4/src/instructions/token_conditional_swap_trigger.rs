@@ -33,6 +33,10 @@ pub fn token_conditional_swap_trigger(
     require_keys_neq!(liqee_key, liqor_key);
 
     let mut liqor = ctx.accounts.liqor.load_full_mut()?;
+    liqor.fixed.check_delegate_permission(
+        ctx.accounts.liqor_authority.key(),
+        DelegatePermission::Liquidate,
+    )?;
     require_msg_typed!(
         !liqor.fixed.being_liquidated(),
         MangoError::BeingLiquidated,
@@ -117,15 +121,24 @@ pub fn token_conditional_swap_trigger(
     );
 
     // Check liqor health, liqee health is checked inside (has to be, since tcs closure depends on it)
-    let liqor_health = compute_health(
-        &liqor.borrow(),
-        HealthType::Init,
-        &account_retriever,
-        now_ts,
-    )
-    .context("compute liqor health")?;
+    let liqor_health_cache = new_health_cache(&liqor.borrow(), &account_retriever, now_ts)
+        .context("create liqor health cache")?;
+    let liqor_health = liqor_health_cache.health(HealthType::Init);
     require!(liqor_health >= 0, MangoError::HealthMustBePositive);
 
+    // The liqor pays the buy token, which may have increased its borrows
+    if liqee_buy_change > 0
+        && liqor_health_cache.has_token_info(buy_token_index)
+        && liqor_health_cache.token_info(buy_token_index)?.balance_spot < 0
+    {
+        liqor_health_cache.check_isolated_collateral_allows_borrow(buy_token_index)?;
+    }
+
+    // Drop the retriever as the collateral banks are updated mutably
+    drop(account_retriever);
+    liqee.update_isolated_debt(&liqee_health_cache, ctx.remaining_accounts)?;
+    liqor.update_isolated_debt(&liqor_health_cache, ctx.remaining_accounts)?;
+
     Ok(())
 }
 
@@ -303,6 +316,9 @@ fn action(
         buy_token_price,
     )?;
     let liqor_buy_active = buy_transfer.source_is_active;
+    if buy_transfer.has_loan() {
+        buy_bank.check_borrow_limit()?;
+    }
 
     let post_liqee_buy_token = liqee_buy_token.native(&buy_bank);
     let post_liqor_buy_token = liqor_buy_token.native(&buy_bank);
@@ -322,6 +338,9 @@ fn action(
         sell_token_price,
     )?;
     let liqor_sell_active = sell_transfer.target_is_active;
+    if sell_transfer.has_loan() {
+        sell_bank.check_borrow_limit()?;
+    }
 
     sell_bank.collected_fees_native += I80F48::from(maker_fee + taker_fee);
 
@@ -407,6 +426,9 @@ fn action(
     let liqee_sell_change = post_liqee_sell_token - pre_liqee_sell_token;
     liqee_health_cache.adjust_token_balance(&buy_bank, liqee_buy_change)?;
     liqee_health_cache.adjust_token_balance(&sell_bank, liqee_sell_change)?;
+    if liqee_sell_change < 0 && post_liqee_sell_token < 0 {
+        liqee_health_cache.check_isolated_collateral_allows_borrow(tcs.sell_token_index)?;
+    }
 
     let liqee_post_init_health =
         liqee.check_health_post(&liqee_health_cache, liqee_pre_init_health)?;
@@ -731,7 +753,9 @@ mod tests {
             liqee_buffer.extend_from_slice(&[0u8; 512]);
             let mut liqee = MangoAccountValue::from_bytes(&liqee_buffer).unwrap();
             {
-                liqee.resize_dynamic_content(3, 5, 4, 6, 1).unwrap();
+                liqee
+                    .resize_dynamic_content(3, 5, 4, 6, 1, 0, 0, 0)
+                    .unwrap();
                 liqee.ensure_token_position(0).unwrap();
                 liqee.ensure_token_position(1).unwrap();
             }
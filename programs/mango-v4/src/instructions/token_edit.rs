@@ -57,6 +57,17 @@ pub fn token_edit(
     collateral_fee_per_day: Option<f32>,
     force_withdraw_opt: Option<bool>,
     tier_opt: Option<String>,
+    circuit_breaker_max_price_move_opt: Option<f32>,
+    circuit_breaker_cooldown_seconds_opt: Option<u32>,
+    reset_circuit_breaker: bool,
+    interest_rate_model_opt: Option<u8>,
+    interest_controller_kp_opt: Option<f32>,
+    interest_controller_ki_opt: Option<f32>,
+    interest_controller_max_daily_change_opt: Option<f32>,
+    borrow_limit_opt: Option<u64>,
+    isolated_collateral_opt: Option<bool>,
+    isolated_borrow_token_indexes_opt: Option<Vec<TokenIndex>>,
+    isolated_debt_ceiling_quote_opt: Option<u64>,
 ) -> Result<()> {
     let group = ctx.accounts.group.load()?;
 
@@ -69,11 +80,9 @@ pub fn token_edit(
 
         if let Some(oracle_config) = oracle_config_opt.as_ref() {
             msg!(
-                "Oracle config: old - conf_filter {:?}, max_staleness_slots {:?},  new - conf_filter {:?}, max_staleness_slots {:?}",
-                bank.oracle_config.conf_filter,
-                bank.oracle_config.max_staleness_slots,
-                oracle_config.conf_filter,
-                oracle_config.max_staleness_slots
+                "Oracle config: old - {:?},  new - {:?}",
+                bank.oracle_config,
+                oracle_config
             );
             bank.oracle_config = oracle_config.to_oracle_config();
             require_group_admin = true;
@@ -475,6 +484,58 @@ pub fn token_edit(
             require_group_admin = true;
         }
 
+        if let Some(borrow_limit) = borrow_limit_opt {
+            msg!(
+                "Borrow limit old {:?}, new {:?}",
+                bank.borrow_limit,
+                borrow_limit
+            );
+            // The limit is checked per bank, so it only bounds the token's borrows
+            // when there is a single bank.
+            require_msg!(
+                borrow_limit == 0 || mint_info.num_banks() == 1,
+                "borrow limits are only supported for tokens with a single bank"
+            );
+            bank.borrow_limit = borrow_limit;
+            require_group_admin = true;
+        }
+
+        if let Some(isolated_collateral) = isolated_collateral_opt {
+            msg!(
+                "Isolated collateral old {:?}, new {:?}",
+                bank.isolated_collateral,
+                isolated_collateral
+            );
+            bank.isolated_collateral = u8::from(isolated_collateral);
+            require_group_admin = true;
+        }
+
+        if let Some(borrow_token_indexes) = isolated_borrow_token_indexes_opt.as_ref() {
+            require_gte!(
+                bank.isolated_borrow_token_indexes.len(),
+                borrow_token_indexes.len()
+            );
+            msg!(
+                "Isolated borrow token indexes old {:?}, new {:?}",
+                bank.isolated_borrow_token_indexes,
+                borrow_token_indexes
+            );
+            bank.isolated_borrow_token_indexes = [TokenIndex::MAX; 7];
+            bank.isolated_borrow_token_indexes[..borrow_token_indexes.len()]
+                .copy_from_slice(borrow_token_indexes);
+            require_group_admin = true;
+        }
+
+        if let Some(debt_ceiling) = isolated_debt_ceiling_quote_opt {
+            msg!(
+                "Isolated debt ceiling quote old {:?}, new {:?}",
+                bank.isolated_debt_ceiling_quote,
+                debt_ceiling
+            );
+            bank.isolated_debt_ceiling_quote = debt_ceiling;
+            require_group_admin = true;
+        }
+
         if let Some(zero_util_rate) = zero_util_rate {
             msg!(
                 "Zero utilization rate old {:?}, new {:?}",
@@ -519,6 +580,80 @@ pub fn token_edit(
             require_group_admin = true;
         }
 
+        if let Some(max_price_move) = circuit_breaker_max_price_move_opt {
+            msg!(
+                "Circuit breaker max price move old {:?}, new {:?}",
+                bank.circuit_breaker_max_price_move,
+                max_price_move
+            );
+            bank.circuit_breaker_max_price_move = max_price_move;
+            require_group_admin = true;
+        }
+
+        if let Some(cooldown_seconds) = circuit_breaker_cooldown_seconds_opt {
+            msg!(
+                "Circuit breaker cooldown seconds old {:?}, new {:?}",
+                bank.circuit_breaker_cooldown_seconds,
+                cooldown_seconds
+            );
+            bank.circuit_breaker_cooldown_seconds = cooldown_seconds;
+            require_group_admin = true;
+        }
+
+        if reset_circuit_breaker {
+            msg!(
+                "Circuit breaker reset, was tripped until {:?}",
+                bank.circuit_breaker_tripped_until
+            );
+            bank.circuit_breaker_tripped_until = 0;
+            require_group_admin = true;
+        }
+
+        if let Some(interest_rate_model) = interest_rate_model_opt {
+            msg!(
+                "Interest rate model old {:?}, new {:?}",
+                bank.interest_rate_model,
+                interest_rate_model
+            );
+            if bank.interest_rate_model != interest_rate_model {
+                // the controller state is meaningless under a different model
+                bank.interest_controller_error = 0.0;
+                bank.interest_controller_integral = 0.0;
+            }
+            bank.interest_rate_model = interest_rate_model;
+            require_group_admin = true;
+        }
+
+        if let Some(kp) = interest_controller_kp_opt {
+            msg!(
+                "Interest controller kp old {:?}, new {:?}",
+                bank.interest_controller_kp,
+                kp
+            );
+            bank.interest_controller_kp = kp;
+            require_group_admin = true;
+        }
+
+        if let Some(ki) = interest_controller_ki_opt {
+            msg!(
+                "Interest controller ki old {:?}, new {:?}",
+                bank.interest_controller_ki,
+                ki
+            );
+            bank.interest_controller_ki = ki;
+            require_group_admin = true;
+        }
+
+        if let Some(max_daily_change) = interest_controller_max_daily_change_opt {
+            msg!(
+                "Interest controller max daily change old {:?}, new {:?}",
+                bank.interest_controller_max_daily_change,
+                max_daily_change
+            );
+            bank.interest_controller_max_daily_change = max_daily_change;
+            require_group_admin = true;
+        }
+
         if let Some(force_withdraw) = force_withdraw_opt {
             msg!(
                 "Force withdraw old {:?}, new {:?}",
@@ -30,6 +30,10 @@ pub fn token_force_close_borrows_with_token(
             .is_owner_or_delegate(ctx.accounts.liqor_owner.key()),
         MangoError::SomeError
     );
+    liqor.fixed.check_delegate_permission(
+        ctx.accounts.liqor_owner.key(),
+        DelegatePermission::Liquidate,
+    )?;
     require_msg_typed!(
         !liqor.fixed.being_liquidated(),
         MangoError::BeingLiquidated,
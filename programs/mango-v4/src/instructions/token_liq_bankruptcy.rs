@@ -54,6 +54,10 @@ pub fn token_liq_bankruptcy(
             .is_owner_or_delegate(ctx.accounts.liqor_owner.key()),
         MangoError::SomeError
     );
+    liqor.fixed.check_delegate_permission(
+        ctx.accounts.liqor_owner.key(),
+        DelegatePermission::Liquidate,
+    )?;
     require_msg_typed!(
         !liqor.fixed.being_liquidated(),
         MangoError::BeingLiquidated,
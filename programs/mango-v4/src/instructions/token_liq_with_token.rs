@@ -32,6 +32,10 @@ pub fn token_liq_with_token(
             .is_owner_or_delegate(ctx.accounts.liqor_owner.key()),
         MangoError::SomeError
     );
+    liqor.fixed.check_delegate_permission(
+        ctx.accounts.liqor_owner.key(),
+        DelegatePermission::Liquidate,
+    )?;
     require_msg_typed!(
         !liqor.fixed.being_liquidated(),
         MangoError::BeingLiquidated,
@@ -172,7 +176,7 @@ pub(crate) fn liquidation_action(
     let liqee_liab_health_balance = liqee_health_token_balances
         [liqee_health_cache.token_info_index(liab_token_index)?]
     .spot_and_perp;
-    let max_liab_liquidation = max_liab_transfer
+    let mut max_liab_liquidation = max_liab_transfer
         .min(-liqee_liab_native)
         .min(-liqee_liab_health_balance)
         .max(I80F48::ZERO);
@@ -183,10 +187,24 @@ pub(crate) fn liquidation_action(
     let liqee_asset_health_balance = liqee_health_token_balances
         [liqee_health_cache.token_info_index(asset_token_index)?]
     .spot_and_perp;
-    let max_asset_transfer = liqee_asset_native
+    let mut max_asset_transfer = liqee_asset_native
         .min(liqee_asset_health_balance)
         .max(I80F48::ZERO);
 
+    // On portfolio margin accounts, spot positions that offset a perp position are never
+    // liquidated here. The perp side gets liquidated instead, see
+    // perp_liq_base_or_positive_pnl, which releases the spot position.
+    let hedged_liab = liqee_health_cache.portfolio_margin_hedged_spot(liab_token_index);
+    max_liab_liquidation = max_liab_liquidation
+        .min(-liqee_liab_native - hedged_liab)
+        .max(I80F48::ZERO);
+    let hedged_asset = liqee_health_cache.portfolio_margin_hedged_spot(asset_token_index);
+    // Isolated perp collateral only backs its positions and is never taken either.
+    let isolated_asset = liqee_health_cache.isolated_perp_collateral(asset_token_index);
+    max_asset_transfer = max_asset_transfer
+        .min(liqee_asset_native - hedged_asset - isolated_asset)
+        .max(I80F48::ZERO);
+
     require_gt!(max_liab_liquidation, 0);
     require_gt!(max_asset_transfer, 0);
 
@@ -127,7 +127,31 @@ pub fn token_register(
         collected_collateral_fees: I80F48::ZERO,
         collateral_fee_per_day,
         tier: fill_from_str(&tier)?,
-        reserved: [0; 1900],
+        circuit_breaker_cooldown_seconds: 0,
+        circuit_breaker_tripped_until: 0,
+        circuit_breaker_max_price_move: 0.0,
+        interest_rate_model: InterestRateModel::CurveScaling.into(),
+        padding2: Default::default(),
+        interest_controller_kp: 0.0,
+        interest_controller_ki: 0.0,
+        interest_controller_max_daily_change: 0.0,
+        interest_controller_error: 0.0,
+        interest_controller_integral: 0.0,
+        borrow_limit: 0,
+        isolated_collateral: 0,
+        padding3: Default::default(),
+        isolated_borrow_token_indexes: [TokenIndex::MAX; 7],
+        isolated_debt_ceiling_quote: 0,
+        emode_category: 0,
+        padding4: Default::default(),
+        emode_maint_asset_weight: 0.0,
+        emode_init_asset_weight: 0.0,
+        emode_maint_liab_weight: 0.0,
+        emode_init_liab_weight: 0.0,
+        padding5: Default::default(),
+        fixed_term_borrows_native: 0,
+        isolated_debt_quote: 0,
+        reserved: [0; 1784],
     };
 
     let oracle_ref = &AccountInfoRef::borrow(ctx.accounts.oracle.as_ref())?;
@@ -47,7 +47,10 @@ pub fn token_register_trustless(
         oracle_config: OracleConfig {
             conf_filter: I80F48::from_num(1000.0), // effectively disabled
             max_staleness_slots: -1,
-            reserved: [0; 72],
+            max_staleness_seconds: 0,
+            risk_increasing_max_staleness_slots: 0,
+            risk_increasing_max_staleness_seconds: 0,
+            reserved: [0; 48],
         },
         stable_price_model: StablePriceModel::default(),
         deposit_index: INDEX_START,
@@ -111,7 +114,31 @@ pub fn token_register_trustless(
         collected_collateral_fees: I80F48::ZERO,
         collateral_fee_per_day: 0.0, // TODO
         tier: fill_from_str("C")?,
-        reserved: [0; 1900],
+        circuit_breaker_cooldown_seconds: 0,
+        circuit_breaker_tripped_until: 0,
+        circuit_breaker_max_price_move: 0.0,
+        interest_rate_model: InterestRateModel::CurveScaling.into(),
+        padding2: Default::default(),
+        interest_controller_kp: 0.0,
+        interest_controller_ki: 0.0,
+        interest_controller_max_daily_change: 0.0,
+        interest_controller_error: 0.0,
+        interest_controller_integral: 0.0,
+        borrow_limit: 0,
+        isolated_collateral: 0,
+        padding3: Default::default(),
+        isolated_borrow_token_indexes: [TokenIndex::MAX; 7],
+        isolated_debt_ceiling_quote: 0,
+        emode_category: 0,
+        padding4: Default::default(),
+        emode_maint_asset_weight: 0.0,
+        emode_init_asset_weight: 0.0,
+        emode_maint_liab_weight: 0.0,
+        emode_init_liab_weight: 0.0,
+        padding5: Default::default(),
+        fixed_term_borrows_native: 0,
+        isolated_debt_quote: 0,
+        reserved: [0; 1784],
     };
     let oracle_ref = &AccountInfoRef::borrow(ctx.accounts.oracle.as_ref())?;
     if let Ok(oracle_price) = bank.oracle_price(&OracleAccountInfos::from_reader(oracle_ref), None)
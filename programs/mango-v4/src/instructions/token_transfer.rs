@@ -0,0 +1,119 @@
+use crate::accounts_zerocopy::*;
+use crate::error::*;
+use crate::health::*;
+use crate::state::*;
+use crate::util::clock_now;
+use anchor_lang::prelude::*;
+use fixed::types::I80F48;
+
+use crate::accounts_ix::*;
+use crate::logs::{emit_stack, TokenBalanceLog, TokenTransferLog};
+
+/// Moves deposits between two accounts of the same owner.
+///
+/// No tokens leave the bank vault, so there are no loan origination fees and net borrow
+/// limits are unaffected. Transfers can't create borrows on the source account.
+pub fn token_transfer(ctx: Context<TokenTransfer>, amount: u64) -> Result<()> {
+    require_msg!(amount > 0, "transfer amount must be positive");
+    require_keys_neq!(ctx.accounts.source.key(), ctx.accounts.destination.key());
+
+    let (now_ts, now_slot) = clock_now();
+    let mut bank = ctx.accounts.bank.load_mut()?;
+    let token_index = bank.token_index;
+
+    let mut source = ctx.accounts.source.load_full_mut()?;
+    source
+        .fixed
+        .check_delegate_permission(ctx.accounts.owner.key(), DelegatePermission::TokenTransfer)?;
+    let mut destination = ctx.accounts.destination.load_full_mut()?;
+
+    let (source_position, source_raw_index) = source.token_position_mut(token_index)?;
+    let source_native = source_position.native(&bank);
+
+    // Handle amount special case for transferring everything
+    let amount = if amount == u64::MAX {
+        source_native.max(I80F48::ZERO).floor().to_num::<u64>()
+    } else {
+        amount
+    };
+    let amount_i80f48 = I80F48::from(amount);
+    require_msg_typed!(
+        amount_i80f48 <= source_native,
+        MangoError::SomeError,
+        "transfer of {} exceeds the source deposits of {}",
+        amount,
+        source_native
+    );
+
+    let source_position_active =
+        bank.withdraw_without_fee(source_position, amount_i80f48, now_ts)?;
+    let source_indexed_position = source_position.indexed_position;
+
+    let (destination_position, _, _) = destination.ensure_token_position(token_index)?;
+    bank.deposit(destination_position, amount_i80f48, now_ts)?;
+    let destination_indexed_position = destination_position.indexed_position;
+
+    emit_stack(TokenBalanceLog {
+        mango_group: ctx.accounts.group.key(),
+        mango_account: ctx.accounts.source.key(),
+        token_index,
+        indexed_position: source_indexed_position.to_bits(),
+        deposit_index: bank.deposit_index.to_bits(),
+        borrow_index: bank.borrow_index.to_bits(),
+    });
+    emit_stack(TokenBalanceLog {
+        mango_group: ctx.accounts.group.key(),
+        mango_account: ctx.accounts.destination.key(),
+        token_index,
+        indexed_position: destination_indexed_position.to_bits(),
+        deposit_index: bank.deposit_index.to_bits(),
+        borrow_index: bank.borrow_index.to_bits(),
+    });
+
+    // Get the oracle price, even if stale or unconfident: like withdrawals, transfers of
+    // deposits are fine as long as the source stays healthy.
+    let oracle_ref = &AccountInfoRef::borrow(ctx.accounts.oracle.as_ref())?;
+    let unsafe_oracle_state = oracle_state_unchecked(
+        &OracleAccountInfos::from_reader(oracle_ref),
+        bank.mint_decimals,
+    )?;
+
+    // Update the net deposits - adjust by price so different tokens are on the same basis (in USD terms)
+    let amount_usd = (amount_i80f48 * unsafe_oracle_state.price).to_num::<i64>();
+    source.fixed.net_deposits -= amount_usd;
+    destination.fixed.net_deposits += amount_usd;
+
+    // The health accounts include the bank, drop it before the health computation
+    drop(bank);
+
+    //
+    // Health check on the source
+    //
+    let retriever = new_fixed_order_account_retriever(
+        ctx.remaining_accounts,
+        &source.borrow(),
+        (now_ts, now_slot),
+    )?;
+    let health = compute_health(&source.borrow(), HealthType::Init, &retriever, now_ts)
+        .context("post-transfer source health")?;
+    require!(health >= 0, MangoError::HealthMustBePositive);
+
+    // Deactivate the position only after the health check because the user passed in
+    // remaining_accounts for all banks/oracles, including the account that will now be
+    // deactivated.
+    if !source_position_active {
+        source.deactivate_token_position_and_log(source_raw_index, ctx.accounts.source.key());
+    }
+
+    emit_stack(TokenTransferLog {
+        mango_group: ctx.accounts.group.key(),
+        source_account: ctx.accounts.source.key(),
+        destination_account: ctx.accounts.destination.key(),
+        signer: ctx.accounts.owner.key(),
+        token_index,
+        quantity: amount,
+        price: unsafe_oracle_state.price.to_bits(),
+    });
+
+    Ok(())
+}
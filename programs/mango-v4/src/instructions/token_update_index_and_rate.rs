@@ -2,8 +2,8 @@ use anchor_lang::prelude::*;
 
 use crate::accounts_ix::*;
 use crate::error::MangoError;
-use crate::logs::{emit_stack, UpdateIndexLog, UpdateRateLogV2};
-use crate::state::{OracleAccountInfos, HOUR};
+use crate::logs::{emit_stack, TokenCircuitBreakerLog, UpdateIndexLog, UpdateRateLogV3};
+use crate::state::{InterestRateModel, OracleAccountInfos, HOUR};
 use crate::{
     accounts_zerocopy::{AccountInfoRef, LoadMutZeroCopyRef, LoadZeroCopyRef},
     state::Bank,
@@ -119,6 +119,17 @@ pub fn token_update_index_and_rate(
             .update(now_ts as u64, price.to_num());
         let stable_price_model = some_bank.stable_price_model;
 
+        if some_bank.update_circuit_breaker(price.to_num(), now_ts) {
+            emit_stack(TokenCircuitBreakerLog {
+                mango_group: mint_info.group.key(),
+                token_index: mint_info.token_index,
+                price: price.to_bits(),
+                reference_price: stable_price_model.latest_delay_price(),
+                tripped_until: some_bank.circuit_breaker_tripped_until,
+            });
+        }
+        let circuit_breaker_tripped_until = some_bank.circuit_breaker_tripped_until;
+
         // If a maint weight shift is done, copy the target into the normal values
         // and clear the transition parameters.
         let maint_shift_done = some_bank.maint_weight_shift_duration_inv.is_positive()
@@ -160,6 +171,7 @@ pub fn token_update_index_and_rate(
             bank.avg_utilization = new_avg_utilization;
 
             bank.stable_price_model = stable_price_model;
+            bank.circuit_breaker_tripped_until = circuit_breaker_tripped_until;
 
             if maint_shift_done {
                 bank.maint_asset_weight = bank.maint_weight_shift_asset_target;
@@ -194,15 +206,26 @@ pub fn token_update_index_and_rate(
                 some_bank.max_rate *= descale_factor;
             }
 
-            some_bank.update_interest_rate_scaling();
+            let interest_rate_model = some_bank.interest_rate_model()?;
+            let controller_daily_change = match interest_rate_model {
+                InterestRateModel::CurveScaling => {
+                    some_bank.update_interest_rate_scaling();
+                    0.0
+                }
+                InterestRateModel::Controller => {
+                    some_bank.update_interest_rate_controller(diff_ts.to_num())
+                }
+            };
 
             let rate0 = some_bank.rate0;
             let rate1 = some_bank.rate1;
             let max_rate = some_bank.max_rate;
             let scaling = some_bank.interest_curve_scaling;
             let target_util = some_bank.interest_target_utilization;
+            let controller_error = some_bank.interest_controller_error;
+            let controller_integral = some_bank.interest_controller_integral;
 
-            emit_stack(UpdateRateLogV2 {
+            emit_stack(UpdateRateLogV3 {
                 mango_group: mint_info.group.key(),
                 token_index: mint_info.token_index,
                 rate0: rate0.to_bits(),
@@ -212,6 +235,10 @@ pub fn token_update_index_and_rate(
                 max_rate: max_rate.to_bits(),
                 curve_scaling: some_bank.interest_curve_scaling,
                 target_utilization: some_bank.interest_target_utilization,
+                interest_rate_model: interest_rate_model.into(),
+                controller_error,
+                controller_integral,
+                controller_daily_change,
             });
 
             drop(some_bank);
@@ -226,6 +253,8 @@ pub fn token_update_index_and_rate(
                 bank.rate0 = rate0;
                 bank.rate1 = rate1;
                 bank.max_rate = max_rate;
+                bank.interest_controller_error = controller_error;
+                bank.interest_controller_integral = controller_integral;
             }
         }
     }
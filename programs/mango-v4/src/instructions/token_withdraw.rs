@@ -22,6 +22,9 @@ pub fn token_withdraw(ctx: Context<TokenWithdraw>, amount: u64, allow_borrow: bo
 
     // Create the account's position for that token index
     let mut account = ctx.accounts.account.load_full_mut()?;
+    account
+        .fixed
+        .check_delegate_permission(ctx.accounts.owner.key(), DelegatePermission::Withdraw)?;
     let (_, raw_token_index, _) = account.ensure_token_position(token_index)?;
 
     // Health check _after_ the token position is guaranteed to exist
@@ -146,12 +149,18 @@ pub fn token_withdraw(ctx: Context<TokenWithdraw>, amount: u64, allow_borrow: bo
     //
     // Health check
     //
-    if let Some((mut health_cache, pre_init_health_lower_bound)) = pre_health_opt {
+    let post_health_cache_opt = if let Some((mut health_cache, pre_init_health_lower_bound)) =
+        pre_health_opt
+    {
         if health_cache.has_token_info(token_index) {
             // This is the normal case: the health cache knows about the token, we can
             // compute the health for the new state by adjusting its balance
             health_cache.adjust_token_balance(&bank, native_position_after - native_position)?;
+            if is_borrow {
+                health_cache.check_isolated_collateral_allows_borrow(token_index)?;
+            }
             account.check_health_post(&health_cache, pre_init_health_lower_bound)?;
+            Some(health_cache)
         } else {
             // The health cache does not know about the token! It has a bad oracle or wasn't
             // provided in the health accounts. Borrows are out of the question!
@@ -169,8 +178,18 @@ pub fn token_withdraw(ctx: Context<TokenWithdraw>, amount: u64, allow_borrow: bo
             // We don't know the true pre_init_health: So require that our lower bound on
             // post health is strictly good enough.
             account.check_health_post_checks_strict(post_init_health_lower_bound)?;
+            None
         }
+    } else {
+        None
+    };
+
+    // The withdrawn token's bank may be one of the collateral banks that get updated
+    drop(bank);
+    if let Some(health_cache) = post_health_cache_opt {
+        account.update_isolated_debt(&health_cache, ctx.remaining_accounts)?;
     }
+    let bank = ctx.accounts.bank.load()?;
 
     //
     // Deactivate the position only after the health check because the user passed in
@@ -210,7 +229,11 @@ pub fn token_withdraw(ctx: Context<TokenWithdraw>, amount: u64, allow_borrow: bo
         // net borrow check.
         let now_opt = Some(Clock::get().map(|c| (c.unix_timestamp as u64, c.slot as u64))?);
         unsafe_oracle_state
-            .check_confidence_and_maybe_staleness(&bank.oracle_config, now_opt)
+            .check_confidence_and_maybe_staleness_for(
+                &bank.oracle_config,
+                now_opt,
+                OracleFreshness::RiskIncreasing,
+            )
             .with_context(|| {
                 oracle_log_context(
                     bank.name(),
@@ -220,6 +243,7 @@ pub fn token_withdraw(ctx: Context<TokenWithdraw>, amount: u64, allow_borrow: bo
                 )
             })?;
         bank.check_net_borrows(unsafe_oracle_state.price)?;
+        bank.check_borrow_limit()?;
     } else {
         bank.enforce_borrows_lte_deposits()?;
     }
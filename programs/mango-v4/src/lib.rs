@@ -21,6 +21,7 @@ pub mod events;
 pub mod health;
 pub mod i80f48;
 pub mod logs;
+pub mod openbook_v2_cpi;
 pub mod serum3_cpi;
 pub mod state;
 pub mod types;
@@ -33,10 +34,10 @@ pub mod instructions;
 compile_error!("compiling the program entrypoint without 'enable-gpl' makes no sense, enable it or use the 'cpi' or 'client' features");
 
 use state::{
-    IxGate, OpenbookV2MarketIndex, OracleConfigParams, PerpMarketIndex, PlaceOrderType,
-    SelfTradeBehavior, Serum3MarketIndex, Side, TokenConditionalSwap,
-    TokenConditionalSwapDisplayPriceStyle, TokenConditionalSwapIntention, TokenConditionalSwapType,
-    TokenIndex, TCS_START_INCENTIVE,
+    DelegateScope, IxGate, OpenbookV2MarketIndex, OracleConfigParams, PerpMarketIndex,
+    PerpScheduledOrderType, PlaceOrderType, SelfTradeBehavior, Serum3MarketIndex, Side,
+    TokenConditionalSwap, TokenConditionalSwapDisplayPriceStyle, TokenConditionalSwapIntention,
+    TokenConditionalSwapType, TokenIndex, TCS_START_INCENTIVE,
 };
 
 declare_id!("4MangoMjqJ2firMokCjjGgoK8d4MXcrgL7XJaL3w6fVg");
@@ -106,6 +107,56 @@ pub mod mango_v4 {
         Ok(())
     }
 
+    pub fn group_edit_emode_category(
+        ctx: Context<GroupEdit>,
+        category: u8,
+        token_indexes: Vec<TokenIndex>,
+        maint_asset_weight: f32,
+        init_asset_weight: f32,
+        maint_liab_weight: f32,
+        init_liab_weight: f32,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::group_edit_emode_category(
+            ctx,
+            category,
+            token_indexes,
+            maint_asset_weight,
+            init_asset_weight,
+            maint_liab_weight,
+            init_liab_weight,
+        )?;
+        Ok(())
+    }
+
+    pub fn group_edit_perp_fee_tier(
+        ctx: Context<GroupEdit>,
+        tier: u8,
+        min_volume_quote: u64,
+        maker_fee_discount: f32,
+        taker_fee_discount: f32,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::group_edit_perp_fee_tier(
+            ctx,
+            tier,
+            min_volume_quote,
+            maker_fee_discount,
+            taker_fee_discount,
+        )?;
+        Ok(())
+    }
+
+    pub fn group_edit_portfolio_margin_pair(
+        ctx: Context<GroupEdit>,
+        perp_market_index: PerpMarketIndex,
+        token_index_opt: Option<TokenIndex>,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::group_edit_portfolio_margin_pair(ctx, perp_market_index, token_index_opt)?;
+        Ok(())
+    }
+
     pub fn group_withdraw_insurance_fund(
         ctx: Context<GroupWithdrawInsuranceFund>,
         amount: u64,
@@ -263,6 +314,17 @@ pub mod mango_v4 {
         collateral_fee_per_day_opt: Option<f32>,
         force_withdraw_opt: Option<bool>,
         tier_opt: Option<String>,
+        circuit_breaker_max_price_move_opt: Option<f32>,
+        circuit_breaker_cooldown_seconds_opt: Option<u32>,
+        reset_circuit_breaker: bool,
+        interest_rate_model_opt: Option<u8>,
+        interest_controller_kp_opt: Option<f32>,
+        interest_controller_ki_opt: Option<f32>,
+        interest_controller_max_daily_change_opt: Option<f32>,
+        borrow_limit_opt: Option<u64>,
+        isolated_collateral_opt: Option<bool>,
+        isolated_borrow_token_indexes_opt: Option<Vec<TokenIndex>>,
+        isolated_debt_ceiling_quote_opt: Option<u64>,
     ) -> Result<()> {
         #[cfg(feature = "enable-gpl")]
         instructions::token_edit(
@@ -309,6 +371,17 @@ pub mod mango_v4 {
             collateral_fee_per_day_opt,
             force_withdraw_opt,
             tier_opt,
+            circuit_breaker_max_price_move_opt,
+            circuit_breaker_cooldown_seconds_opt,
+            reset_circuit_breaker,
+            interest_rate_model_opt,
+            interest_controller_kp_opt,
+            interest_controller_ki_opt,
+            interest_controller_max_daily_change_opt,
+            borrow_limit_opt,
+            isolated_collateral_opt,
+            isolated_borrow_token_indexes_opt,
+            isolated_debt_ceiling_quote_opt,
         )?;
         Ok(())
     }
@@ -367,6 +440,9 @@ pub mod mango_v4 {
             perp_count,
             perp_oo_count,
             0,
+            0,
+            0,
+            0,
             name,
         )?;
         Ok(())
@@ -394,6 +470,73 @@ pub mod mango_v4 {
             perp_count,
             perp_oo_count,
             token_conditional_swap_count,
+            0,
+            0,
+            0,
+            name,
+        )?;
+        Ok(())
+    }
+
+    pub fn account_create_v3(
+        ctx: Context<AccountCreateV3>,
+        account_num: u32,
+        token_count: u8,
+        serum3_count: u8,
+        perp_count: u8,
+        perp_oo_count: u8,
+        token_conditional_swap_count: u8,
+        openbook_v2_count: u8,
+        name: String,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::account_create(
+            &ctx.accounts.account,
+            *ctx.bumps.get("account").ok_or(MangoError::SomeError)?,
+            ctx.accounts.group.key(),
+            ctx.accounts.owner.key(),
+            account_num,
+            token_count,
+            serum3_count,
+            perp_count,
+            perp_oo_count,
+            token_conditional_swap_count,
+            openbook_v2_count,
+            0,
+            0,
+            name,
+        )?;
+        Ok(())
+    }
+
+    pub fn account_create_v4(
+        ctx: Context<AccountCreateV4>,
+        account_num: u32,
+        token_count: u8,
+        serum3_count: u8,
+        perp_count: u8,
+        perp_oo_count: u8,
+        token_conditional_swap_count: u8,
+        openbook_v2_count: u8,
+        fixed_term_count: u8,
+        perp_scheduled_order_count: u8,
+        name: String,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::account_create(
+            &ctx.accounts.account,
+            *ctx.bumps.get("account").ok_or(MangoError::SomeError)?,
+            ctx.accounts.group.key(),
+            ctx.accounts.owner.key(),
+            account_num,
+            token_count,
+            serum3_count,
+            perp_count,
+            perp_oo_count,
+            token_conditional_swap_count,
+            openbook_v2_count,
+            fixed_term_count,
+            perp_scheduled_order_count,
             name,
         )?;
         Ok(())
@@ -407,7 +550,17 @@ pub mod mango_v4 {
         perp_oo_count: u8,
     ) -> Result<()> {
         #[cfg(feature = "enable-gpl")]
-        instructions::account_expand(ctx, token_count, serum3_count, perp_count, perp_oo_count, 0)?;
+        instructions::account_expand(
+            ctx,
+            token_count,
+            serum3_count,
+            perp_count,
+            perp_oo_count,
+            0,
+            0,
+            0,
+            0,
+        )?;
         Ok(())
     }
 
@@ -427,6 +580,59 @@ pub mod mango_v4 {
             perp_count,
             perp_oo_count,
             token_conditional_swap_count,
+            0,
+            0,
+            0,
+        )?;
+        Ok(())
+    }
+
+    pub fn account_expand_v3(
+        ctx: Context<AccountExpand>,
+        token_count: u8,
+        serum3_count: u8,
+        perp_count: u8,
+        perp_oo_count: u8,
+        token_conditional_swap_count: u8,
+        openbook_v2_count: u8,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::account_expand(
+            ctx,
+            token_count,
+            serum3_count,
+            perp_count,
+            perp_oo_count,
+            token_conditional_swap_count,
+            openbook_v2_count,
+            0,
+            0,
+        )?;
+        Ok(())
+    }
+
+    pub fn account_expand_v4(
+        ctx: Context<AccountExpand>,
+        token_count: u8,
+        serum3_count: u8,
+        perp_count: u8,
+        perp_oo_count: u8,
+        token_conditional_swap_count: u8,
+        openbook_v2_count: u8,
+        fixed_term_count: u8,
+        perp_scheduled_order_count: u8,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::account_expand(
+            ctx,
+            token_count,
+            serum3_count,
+            perp_count,
+            perp_oo_count,
+            token_conditional_swap_count,
+            openbook_v2_count,
+            fixed_term_count,
+            perp_scheduled_order_count,
         )?;
         Ok(())
     }
@@ -461,6 +667,34 @@ pub mod mango_v4 {
         Ok(())
     }
 
+    pub fn account_set_portfolio_margin(
+        ctx: Context<AccountSetPortfolioMargin>,
+        enabled: bool,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::account_set_portfolio_margin(ctx, enabled)?;
+        Ok(())
+    }
+
+    pub fn account_set_emode_category(
+        ctx: Context<AccountSetEmodeCategory>,
+        category: u8,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::account_set_emode_category(ctx, category)?;
+        Ok(())
+    }
+
+    pub fn account_edit_delegate_scope(
+        ctx: Context<AccountEditDelegateScope>,
+        temporary_delegate: bool,
+        scope: DelegateScope,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::account_edit_delegate_scope(ctx, temporary_delegate, scope)?;
+        Ok(())
+    }
+
     pub fn account_close(ctx: Context<AccountClose>, force_close: bool) -> Result<()> {
         #[cfg(feature = "enable-gpl")]
         instructions::account_close(ctx, force_close)?;
@@ -497,6 +731,73 @@ pub mod mango_v4 {
     // because generic anchor clients won't know how to deal with it
     // and it's tricky to use in typescript generally
     // lets do an interface pass later
+    pub fn oracle_aggregator_create(
+        ctx: Context<OracleAggregatorCreate>,
+        min_quorum: u8,
+        max_divergence: f64,
+        source_oracle_config: OracleConfigParams,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::oracle_aggregator_create(
+            ctx,
+            min_quorum,
+            max_divergence,
+            source_oracle_config,
+        )?;
+        Ok(())
+    }
+
+    pub fn oracle_aggregator_edit(
+        ctx: Context<OracleAggregatorEdit>,
+        min_quorum_opt: Option<u8>,
+        max_divergence_opt: Option<f64>,
+        source_oracle_config_opt: Option<OracleConfigParams>,
+        replace_source_oracles: bool,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::oracle_aggregator_edit(
+            ctx,
+            min_quorum_opt,
+            max_divergence_opt,
+            source_oracle_config_opt,
+            replace_source_oracles,
+        )?;
+        Ok(())
+    }
+
+    pub fn oracle_aggregator_update(ctx: Context<OracleAggregatorUpdate>) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::oracle_aggregator_update(ctx)?;
+        Ok(())
+    }
+
+    pub fn clmm_twap_oracle_create(
+        ctx: Context<ClmmTwapOracleCreate>,
+        twap_window_seconds: u64,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::clmm_twap_oracle_create(ctx, twap_window_seconds)?;
+        Ok(())
+    }
+
+    pub fn clmm_twap_oracle_update(ctx: Context<ClmmTwapOracleUpdate>) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::clmm_twap_oracle_update(ctx)?;
+        Ok(())
+    }
+
+    pub fn openbook_v2_mid_oracle_create(ctx: Context<OpenbookV2MidOracleCreate>) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::openbook_v2_mid_oracle_create(ctx)?;
+        Ok(())
+    }
+
+    pub fn openbook_v2_mid_oracle_update(ctx: Context<OpenbookV2MidOracleUpdate>) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::openbook_v2_mid_oracle_update(ctx)?;
+        Ok(())
+    }
+
     pub fn stub_oracle_create(ctx: Context<StubOracleCreate>, price: I80F48) -> Result<()> {
         #[cfg(feature = "enable-gpl")]
         instructions::stub_oracle_create(ctx, price)?;
@@ -552,6 +853,12 @@ pub mod mango_v4 {
         Ok(())
     }
 
+    pub fn token_transfer(ctx: Context<TokenTransfer>, amount: u64) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::token_transfer(ctx, amount)?;
+        Ok(())
+    }
+
     pub fn flash_loan_begin<'key, 'accounts, 'remaining, 'info>(
         ctx: Context<'key, 'accounts, 'remaining, 'info, FlashLoanBegin<'info>>,
         loan_amounts: Vec<u64>,
@@ -894,6 +1201,9 @@ pub mod mango_v4 {
         settle_pnl_limit_window_size_ts: u64,
         positive_pnl_liquidation_fee: f32,
         platform_liquidation_fee: f32,
+        bookside_capacity: u32,
+        event_queue_capacity: u32,
+        opening_auction_duration: u32,
     ) -> Result<()> {
         #[cfg(feature = "enable-gpl")]
         instructions::perp_create_market(
@@ -926,6 +1236,9 @@ pub mod mango_v4 {
             settle_pnl_limit_window_size_ts,
             positive_pnl_liquidation_fee,
             platform_liquidation_fee,
+            bookside_capacity,
+            event_queue_capacity,
+            opening_auction_duration,
         )?;
         Ok(())
     }
@@ -964,6 +1277,9 @@ pub mod mango_v4 {
         name_opt: Option<String>,
         force_close_opt: Option<bool>,
         platform_liquidation_fee_opt: Option<f32>,
+        funding_stable_price_weight_opt: Option<f32>,
+        settle_stable_price_weight_opt: Option<f32>,
+        opening_auction_duration_opt: Option<u32>,
     ) -> Result<()> {
         #[cfg(feature = "enable-gpl")]
         instructions::perp_edit_market(
@@ -999,10 +1315,23 @@ pub mod mango_v4 {
             name_opt,
             force_close_opt,
             platform_liquidation_fee_opt,
+            funding_stable_price_weight_opt,
+            settle_stable_price_weight_opt,
+            opening_auction_duration_opt,
         )?;
         Ok(())
     }
 
+    pub fn perp_resize_orderbook(
+        ctx: Context<PerpResizeOrderbook>,
+        bookside_capacity: u32,
+        event_queue_capacity: u32,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::perp_resize_orderbook(ctx, bookside_capacity, event_queue_capacity)?;
+        Ok(())
+    }
+
     pub fn perp_close_market(ctx: Context<PerpCloseMarket>) -> Result<()> {
         #[cfg(feature = "enable-gpl")]
         instructions::perp_close_market(ctx)?;
@@ -1015,6 +1344,18 @@ pub mod mango_v4 {
         Ok(())
     }
 
+    pub fn perp_set_isolated(ctx: Context<PerpSetIsolated>, isolated: bool) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::perp_set_isolated(ctx, isolated)?;
+        Ok(())
+    }
+
+    pub fn perp_isolated_transfer(ctx: Context<PerpIsolatedTransfer>, amount: i64) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::perp_isolated_transfer(ctx, amount)?;
+        Ok(())
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn perp_place_order(
         ctx: Context<PerpPlaceOrder>,
@@ -1289,6 +1630,99 @@ pub mod mango_v4 {
         Ok(None)
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn perp_place_spread_order(
+        ctx: Context<PerpPlaceSpreadOrder>,
+        side_a: Side,
+        price_lots_a: i64,
+        max_base_lots_a: i64,
+        side_b: Side,
+        price_lots_b: i64,
+
+        // Base lots of leg B to trade per filled base lot of leg A.
+        leg_b_ratio: f64,
+
+        // Maximum number of orders from each book to fill.
+        limit: u8,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::perp_place_spread_order(
+            ctx,
+            side_a,
+            price_lots_a,
+            max_base_lots_a,
+            side_b,
+            price_lots_b,
+            leg_b_ratio,
+            limit,
+        )?;
+        Ok(())
+    }
+
+    pub fn perp_place_orders_batch(
+        ctx: Context<PerpPlaceOrder>,
+        orders: Vec<PerpBatchOrder>,
+
+        // Cancel all of the account's orders on the market before placing the new ones
+        cancel_all_first: bool,
+
+        // Timestamp of when the orders expire, see perp_place_order
+        expiry_timestamp: u64,
+
+        // Maximum number of orders from the book to fill, for each order
+        limit: u8,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::perp_place_orders_batch(
+            ctx,
+            orders,
+            cancel_all_first,
+            expiry_timestamp,
+            limit,
+        )?;
+        Ok(())
+    }
+
+    pub fn perp_edit_order(
+        ctx: Context<PerpPlaceOrder>,
+
+        // Identifies the order to edit, exactly one must be set
+        order_id_opt: Option<u128>,
+        client_order_id_opt: Option<u64>,
+
+        // The new price in lots, or the new price offset for oracle pegged orders.
+        price_lots: i64,
+
+        // The new size. Decreasing the size at an unchanged price keeps the order's
+        // time priority, zero cancels the order.
+        max_base_lots: i64,
+
+        // Used for the replacement order, like in perp_place_order_v2 and
+        // perp_place_order_pegged_v2. Not stored on the book, so they can't be kept.
+        self_trade_behavior: SelfTradeBehavior,
+        reduce_only: bool,
+        max_oracle_staleness_slots: i32,
+
+        // Maximum number of orders from the book to fill, when the order is replaced.
+        limit: u8,
+    ) -> Result<Option<u128>> {
+        #[cfg(feature = "enable-gpl")]
+        return instructions::perp_edit_order(
+            ctx,
+            order_id_opt,
+            client_order_id_opt,
+            price_lots,
+            max_base_lots,
+            self_trade_behavior,
+            reduce_only,
+            max_oracle_staleness_slots,
+            limit,
+        );
+
+        #[cfg(not(feature = "enable-gpl"))]
+        Ok(None)
+    }
+
     pub fn perp_cancel_order(ctx: Context<PerpCancelOrder>, order_id: u128) -> Result<()> {
         #[cfg(feature = "enable-gpl")]
         instructions::perp_cancel_order(ctx, order_id)?;
@@ -1332,6 +1766,15 @@ pub mod mango_v4 {
         Ok(())
     }
 
+    pub fn perp_clear_opening_auction(
+        ctx: Context<PerpClearOpeningAuction>,
+        limit: u8,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::perp_clear_opening_auction(ctx, limit)?;
+        Ok(())
+    }
+
     pub fn perp_settle_pnl(ctx: Context<PerpSettlePnl>) -> Result<()> {
         #[cfg(feature = "enable-gpl")]
         instructions::perp_settle_pnl(ctx)?;
@@ -1695,6 +2138,8 @@ pub mod mango_v4 {
         market_index: OpenbookV2MarketIndex,
         name: String,
     ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::openbook_v2_register_market(ctx, market_index, name)?;
         Ok(())
     }
 
@@ -1703,10 +2148,14 @@ pub mod mango_v4 {
         reduce_only_opt: Option<bool>,
         force_close_opt: Option<bool>,
     ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::openbook_v2_edit_market(ctx, reduce_only_opt, force_close_opt)?;
         Ok(())
     }
 
     pub fn openbook_v2_deregister_market(ctx: Context<OpenbookV2DeregisterMarket>) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::openbook_v2_deregister_market(ctx)?;
         Ok(())
     }
 
@@ -1714,16 +2163,20 @@ pub mod mango_v4 {
         ctx: Context<OpenbookV2CreateOpenOrders>,
         account_num: u32,
     ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::openbook_v2_create_open_orders(ctx, account_num)?;
         Ok(())
     }
 
     pub fn openbook_v2_close_open_orders(ctx: Context<OpenbookV2CloseOpenOrders>) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::openbook_v2_close_open_orders(ctx)?;
         Ok(())
     }
 
     #[allow(clippy::too_many_arguments)]
-    pub fn openbook_v2_place_order(
-        ctx: Context<OpenbookV2PlaceOrder>,
+    pub fn openbook_v2_place_order<'key, 'accounts, 'remaining, 'info>(
+        ctx: Context<'key, 'accounts, 'remaining, 'info, OpenbookV2PlaceOrder<'info>>,
         side: u8, // openbook_v2::state::Side
         limit_price: u64,
         max_base_qty: u64,
@@ -1733,12 +2186,25 @@ pub mod mango_v4 {
         client_order_id: u64,
         limit: u16,
     ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::openbook_v2_place_order(
+            ctx.accounts,
+            ctx.remaining_accounts,
+            side,
+            limit_price,
+            max_base_qty,
+            max_native_quote_qty_including_fees,
+            self_trade_behavior,
+            order_type,
+            client_order_id,
+            limit,
+        )?;
         Ok(())
     }
 
     #[allow(clippy::too_many_arguments)]
-    pub fn openbook_v2_place_taker_order(
-        ctx: Context<OpenbookV2PlaceTakeOrder>,
+    pub fn openbook_v2_place_taker_order<'key, 'accounts, 'remaining, 'info>(
+        ctx: Context<'key, 'accounts, 'remaining, 'info, OpenbookV2PlaceTakeOrder<'info>>,
         side: u8, // openbook_v2::state::Side
         limit_price: u64,
         max_base_qty: u64,
@@ -1747,6 +2213,17 @@ pub mod mango_v4 {
         client_order_id: u64,
         limit: u16,
     ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::openbook_v2_place_take_order(
+            ctx,
+            side,
+            limit_price,
+            max_base_qty,
+            max_native_quote_qty_including_fees,
+            self_trade_behavior,
+            client_order_id,
+            limit,
+        )?;
         Ok(())
     }
 
@@ -1755,6 +2232,8 @@ pub mod mango_v4 {
         side: u8, // openbook_v2::state::Side
         order_id: u128,
     ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::openbook_v2_cancel_order(ctx, side, order_id)?;
         Ok(())
     }
 
@@ -1762,6 +2241,8 @@ pub mod mango_v4 {
         ctx: Context<OpenbookV2SettleFunds>,
         fees_to_dao: bool,
     ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::openbook_v2_settle_funds(ctx, fees_to_dao)?;
         Ok(())
     }
 
@@ -1769,6 +2250,8 @@ pub mod mango_v4 {
         ctx: Context<OpenbookV2LiqForceCancelOrders>,
         limit: u8,
     ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::openbook_v2_liq_force_cancel_orders(ctx, limit)?;
         Ok(())
     }
 
@@ -1776,6 +2259,159 @@ pub mod mango_v4 {
         ctx: Context<OpenbookV2CancelOrder>,
         limit: u8,
     ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::openbook_v2_cancel_all_orders(ctx, limit)?;
+        Ok(())
+    }
+
+    ///
+    /// Fixed term lending
+    ///
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn fixed_term_market_create(
+        ctx: Context<FixedTermMarketCreate>,
+        market_index: FixedTermMarketIndex,
+        name: String,
+        maturity_timestamp: u64,
+        lend_rate: f32,
+        borrow_rate: f32,
+        max_lent_native: u64,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::fixed_term_market_create(
+            ctx,
+            market_index,
+            name,
+            maturity_timestamp,
+            lend_rate,
+            borrow_rate,
+            max_lent_native,
+        )?;
+        Ok(())
+    }
+
+    pub fn fixed_term_market_edit(
+        ctx: Context<FixedTermMarketEdit>,
+        name_opt: Option<String>,
+        lend_rate_opt: Option<f32>,
+        borrow_rate_opt: Option<f32>,
+        max_lent_native_opt: Option<u64>,
+        reduce_only_opt: Option<bool>,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::fixed_term_market_edit(
+            ctx,
+            name_opt,
+            lend_rate_opt,
+            borrow_rate_opt,
+            max_lent_native_opt,
+            reduce_only_opt,
+        )?;
+        Ok(())
+    }
+
+    pub fn fixed_term_lend(ctx: Context<FixedTermLend>, amount: u64) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::fixed_term_lend(ctx, amount)?;
+        Ok(())
+    }
+
+    pub fn fixed_term_borrow(ctx: Context<FixedTermBorrow>, amount: u64) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::fixed_term_borrow(ctx, amount)?;
+        Ok(())
+    }
+
+    pub fn fixed_term_settle(ctx: Context<FixedTermSettle>) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::fixed_term_settle(ctx)?;
+        Ok(())
+    }
+
+    ///
+    /// Perp scheduled orders
+    ///
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn perp_scheduled_order_create(
+        ctx: Context<PerpScheduledOrderCreate>,
+        side: Side,
+        order_type: PerpScheduledOrderType,
+        max_base_lots: i64,
+        child_base_lots: i64,
+        price_lots: i64,
+        interval_seconds: u64,
+        expiry_timestamp: u64,
+        reduce_only: bool,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::perp_scheduled_order_create(
+            ctx,
+            side,
+            order_type,
+            max_base_lots,
+            child_base_lots,
+            price_lots,
+            interval_seconds,
+            expiry_timestamp,
+            reduce_only,
+        )?;
+        Ok(())
+    }
+
+    /// Stop-loss and take-profit orders use the scheduled order storage and
+    /// are cancelled and triggered with the scheduled order instructions.
+    #[allow(clippy::too_many_arguments)]
+    pub fn perp_conditional_order_create(
+        ctx: Context<PerpConditionalOrderCreate>,
+        side: Side,
+        order_type: PerpScheduledOrderType,
+        max_base_lots: i64,
+        trigger_price_lots: i64,
+        max_slippage: f32,
+        expiry_timestamp: u64,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::perp_conditional_order_create(
+            ctx,
+            side,
+            order_type,
+            max_base_lots,
+            trigger_price_lots,
+            max_slippage,
+            expiry_timestamp,
+        )?;
+        Ok(())
+    }
+
+    pub fn perp_scheduled_order_cancel(
+        ctx: Context<PerpScheduledOrderCancel>,
+        perp_scheduled_order_index: u8,
+        perp_scheduled_order_id: u64,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::perp_scheduled_order_cancel(
+            ctx,
+            perp_scheduled_order_index.into(),
+            perp_scheduled_order_id,
+        )?;
+        Ok(())
+    }
+
+    pub fn perp_scheduled_order_trigger(
+        ctx: Context<PerpScheduledOrderTrigger>,
+        perp_scheduled_order_index: u8,
+        perp_scheduled_order_id: u64,
+        limit: u8,
+    ) -> Result<()> {
+        #[cfg(feature = "enable-gpl")]
+        instructions::perp_scheduled_order_trigger(
+            ctx,
+            perp_scheduled_order_index.into(),
+            perp_scheduled_order_id,
+            limit,
+        )?;
         Ok(())
     }
 
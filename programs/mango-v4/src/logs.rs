@@ -258,6 +258,35 @@ pub struct FillLogV3 {
     pub taker_closed_pnl: f64, // settle-token-native units
 }
 
+#[event]
+pub struct FillLogV4 {
+    pub mango_group: Pubkey,
+    pub market_index: u16,
+    pub taker_side: u8, // side from the taker's POV
+    pub maker_slot: u8,
+    pub maker_out: bool, // true if maker order quantity == 0
+    pub timestamp: u64,
+    pub seq_num: u64, // note: usize same as u64
+
+    pub maker: Pubkey,
+    pub maker_client_order_id: u64,
+    pub maker_fee: f32,
+    pub maker_fee_tier: u8, // perp fee tier id, 0 if none
+
+    // Timestamp of when the maker order was placed; copied over from the LeafNode
+    pub maker_timestamp: u64,
+
+    pub taker: Pubkey,
+    pub taker_client_order_id: u64,
+    pub taker_fee: f32,
+    pub taker_fee_tier: u8, // perp fee tier id, 0 if none
+
+    pub price: i64,
+    pub quantity: i64,         // number of base lots
+    pub maker_closed_pnl: f64, // settle-token-native units
+    pub taker_closed_pnl: f64, // settle-token-native units
+}
+
 #[event]
 pub struct PerpUpdateFundingLog {
     pub mango_group: Pubkey,
@@ -330,6 +359,23 @@ pub struct UpdateRateLogV2 {
     pub target_utilization: f32,
 }
 
+#[event]
+pub struct UpdateRateLogV3 {
+    pub mango_group: Pubkey,
+    pub token_index: u16,
+    pub rate0: i128,    // I80F48
+    pub util0: i128,    // I80F48
+    pub rate1: i128,    // I80F48
+    pub util1: i128,    // I80F48
+    pub max_rate: i128, // I80F48
+    pub curve_scaling: f64,
+    pub target_utilization: f32,
+    pub interest_rate_model: u8,
+    pub controller_error: f32,
+    pub controller_integral: f64,
+    pub controller_daily_change: f64,
+}
+
 #[event]
 pub struct TokenLiqWithTokenLog {
     pub mango_group: Pubkey,
@@ -398,6 +444,7 @@ pub enum LoanOriginationFeeInstruction {
     Serum3SettleFunds,
     TokenWithdraw,
     TokenConditionalSwapTrigger,
+    OpenbookV2SettleFunds,
 }
 
 #[event]
@@ -616,6 +663,26 @@ pub struct PerpTakerTradeLog {
     pub fee_penalty: i128,          // in native quote units
 }
 
+#[event]
+pub struct PerpBatchOrderLog {
+    pub mango_group: Pubkey,
+    pub mango_account: Pubkey,
+    pub perp_market_index: u16,
+    pub batch_index: u8, // position of the order in the batch
+    pub client_order_id: u64,
+    pub posted: bool,   // whether the order was (partially) posted on the book
+    pub order_id: u128, // id of the posted order, 0 if not posted
+}
+
+#[event]
+pub struct PerpOpeningAuctionLog {
+    pub mango_group: Pubkey,
+    pub perp_market_index: u16,
+    pub price_lots: i64, // clearing price, 0 if no orders crossed
+    pub fills: u64,      // number of fill events produced by this call
+    pub done: bool,      // whether the auction ended
+}
+
 #[event]
 pub struct PerpForceClosePositionLog {
     pub mango_group: Pubkey,
@@ -815,3 +882,170 @@ pub struct ForceWithdrawLog {
     pub price: i128, // I80F48
     pub to_token_account: Pubkey,
 }
+
+#[event]
+pub struct OpenbookV2RegisterMarketLog {
+    pub mango_group: Pubkey,
+    pub openbook_market: Pubkey,
+    pub market_index: u16,
+    pub base_token_index: u16,
+    pub quote_token_index: u16,
+    pub openbook_program: Pubkey,
+    pub openbook_market_external: Pubkey,
+}
+
+#[event]
+pub struct OpenbookV2OpenOrdersBalanceLog {
+    pub mango_group: Pubkey,
+    pub mango_account: Pubkey,
+    pub market_index: u16,
+    pub base_token_index: u16,
+    pub quote_token_index: u16,
+    pub base_total: u64,
+    pub base_free: u64,
+    pub quote_total: u64,
+    pub quote_free: u64,
+    pub referrer_rebates_available: u64,
+}
+
+#[event]
+pub struct FixedTermMarketCreateLog {
+    pub mango_group: Pubkey,
+    pub fixed_term_market: Pubkey,
+    pub market_index: u16,
+    pub token_index: u16,
+    pub maturity_timestamp: u64,
+    pub lend_rate: i128,   // I80F48
+    pub borrow_rate: i128, // I80F48
+}
+
+#[event]
+pub struct FixedTermLoanLog {
+    pub mango_group: Pubkey,
+    pub mango_account: Pubkey,
+    pub market_index: u16,
+    pub token_index: u16,
+    pub is_borrow: bool,
+    pub amount: u64,
+    pub amount_at_maturity: u64,
+    pub maturity_timestamp: u64,
+}
+
+#[event]
+pub struct FixedTermSettleLog {
+    pub mango_group: Pubkey,
+    pub mango_account: Pubkey,
+    pub market_index: u16,
+    pub token_index: u16,
+    pub lent_at_maturity: u64,
+    pub borrowed_at_maturity: u64,
+}
+
+#[event]
+pub struct PerpScheduledOrderCreateLog {
+    pub mango_group: Pubkey,
+    pub mango_account: Pubkey,
+    pub id: u64,
+    pub market_index: u16,
+    pub side: u8,
+    pub order_type: u8,
+    pub reduce_only: bool,
+    pub max_base_lots: i64,
+    pub child_base_lots: i64,
+    pub price_lots: i64,
+    pub interval_seconds: u64,
+    pub expiry_timestamp: u64,
+}
+
+#[event]
+pub struct PerpScheduledOrderCancelLog {
+    pub mango_group: Pubkey,
+    pub mango_account: Pubkey,
+    pub id: u64,
+}
+
+#[event]
+pub struct PerpScheduledOrderTriggerLog {
+    pub mango_group: Pubkey,
+    pub mango_account: Pubkey,
+    pub caller: Pubkey,
+    pub id: u64,
+    pub market_index: u16,
+    pub child_base_lots: i64,
+    pub executed_base_lots: i64,
+    pub incentive_token_index: u16,
+    pub incentive_amount: u64,
+}
+
+#[event]
+pub struct PerpConditionalOrderCreateLog {
+    pub mango_group: Pubkey,
+    pub mango_account: Pubkey,
+    pub id: u64,
+    pub market_index: u16,
+    pub order_type: u8,
+    pub side: u8,
+    pub max_base_lots: i64,
+    pub trigger_price_lots: i64,
+    pub max_slippage: f32,
+    pub expiry_timestamp: u64,
+}
+
+#[event]
+pub struct PerpConditionalOrderTriggerLog {
+    pub mango_group: Pubkey,
+    pub mango_account: Pubkey,
+    pub caller: Pubkey,
+    pub id: u64,
+    pub market_index: u16,
+    pub order_type: u8,
+    pub side: u8,
+    pub oracle_price_lots: i64,
+    pub trigger_price_lots: i64,
+    pub limit_price_lots: i64,
+    pub filled_base_lots: i64,
+    pub incentive_token_index: u16,
+    pub incentive_amount: u64,
+}
+
+#[event]
+pub struct PerpSpreadOrderLog {
+    pub mango_group: Pubkey,
+    pub mango_account: Pubkey,
+    pub market_index_a: u16,
+    pub market_index_b: u16,
+    pub side_a: u8,
+    pub side_b: u8,
+    pub filled_base_lots_a: i64,
+    pub filled_base_lots_b: i64,
+}
+
+#[event]
+pub struct PerpIsolatedMarginLog {
+    pub mango_group: Pubkey,
+    pub mango_account: Pubkey,
+    pub market_index: u16,
+    pub isolated: bool,
+    pub isolated_collateral: i128, // I80F48
+}
+
+#[event]
+pub struct TokenTransferLog {
+    pub mango_group: Pubkey,
+    pub source_account: Pubkey,
+    pub destination_account: Pubkey,
+    pub signer: Pubkey,
+    pub token_index: u16,
+    pub quantity: u64,
+    pub price: i128, // I80F48
+}
+
+#[event]
+pub struct TokenCircuitBreakerLog {
+    pub mango_group: Pubkey,
+    pub token_index: u16,
+    pub price: i128, // I80F48
+    pub reference_price: f64,
+    /// Zero when the circuit breaker was released
+    pub tripped_until: u64,
+}
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use openbook_v2::state::{Market, OpenOrdersAccount};
+
+use std::cell::Ref;
+use std::mem::size_of;
+
+use crate::accounts_zerocopy::AccountReader;
+use crate::serum3_cpi::OpenOrdersAmounts;
+
+/// Loads an openbook v2 OpenOrdersAccount, checking owner and discriminator
+pub fn load_open_orders_ref<'a>(acc: &'a AccountInfo) -> Result<Ref<'a, OpenOrdersAccount>> {
+    require_keys_eq!(*acc.owner, openbook_v2::ID);
+    let data = acc.try_borrow_data()?;
+    require!(
+        data.len() >= 8 + size_of::<OpenOrdersAccount>(),
+        ErrorCode::AccountDidNotDeserialize
+    );
+    require!(
+        data[0..8] == OpenOrdersAccount::discriminator(),
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+    Ok(Ref::map(data, |data| {
+        bytemuck::from_bytes(&data[8..8 + size_of::<OpenOrdersAccount>()])
+    }))
+}
+
+/// Like load_open_orders_ref, but for AccountReaders such as the ones used in health computations
+pub fn load_open_orders(acc: &impl AccountReader) -> Result<&OpenOrdersAccount> {
+    require_keys_eq!(*acc.owner(), openbook_v2::ID);
+    let data = acc.data();
+    require!(
+        data.len() >= 8 + size_of::<OpenOrdersAccount>(),
+        ErrorCode::AccountDidNotDeserialize
+    );
+    require!(
+        data[0..8] == OpenOrdersAccount::discriminator(),
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+    Ok(bytemuck::from_bytes(
+        &data[8..8 + size_of::<OpenOrdersAccount>()],
+    ))
+}
+
+/// For loan origination fees bookkeeping purposes
+///
+/// All amounts are native: openbook v2 tracks the reserved amounts in lots.
+#[derive(Debug)]
+pub struct OpenOrdersSlim {
+    native_base_free: u64,
+    native_base_reserved: u64,
+    native_quote_free: u64,
+    native_quote_reserved: u64,
+    referrer_rebates_available: u64,
+}
+impl OpenOrdersSlim {
+    pub fn from_oo(oo: &OpenOrdersAccount, market: &Market) -> Self {
+        Self::from_oo_with_lot_sizes(oo, market.base_lot_size, market.quote_lot_size)
+    }
+
+    pub fn from_oo_with_lot_sizes(
+        oo: &OpenOrdersAccount,
+        base_lot_size: i64,
+        quote_lot_size: i64,
+    ) -> Self {
+        let position = &oo.position;
+        let base_lot_size = base_lot_size as u64;
+        let quote_lot_size = quote_lot_size as u64;
+        Self {
+            native_base_free: position.base_free_native,
+            native_base_reserved: position.asks_base_lots as u64 * base_lot_size,
+            native_quote_free: position.quote_free_native,
+            // bids also lock up the maker fees they may need to pay
+            native_quote_reserved: position.bids_quote_lots as u64 * quote_lot_size
+                + position.locked_maker_fees,
+            referrer_rebates_available: position.referrer_rebates_available,
+        }
+    }
+
+    pub fn has_bids(&self) -> bool {
+        self.native_quote_reserved > 0
+    }
+
+    pub fn has_asks(&self) -> bool {
+        self.native_base_reserved > 0
+    }
+}
+
+impl OpenOrdersAmounts for OpenOrdersSlim {
+    fn native_base_reserved(&self) -> u64 {
+        self.native_base_reserved
+    }
+    fn native_quote_reserved(&self) -> u64 {
+        self.native_quote_reserved
+    }
+    fn native_base_free(&self) -> u64 {
+        self.native_base_free
+    }
+    fn native_quote_free(&self) -> u64 {
+        self.native_quote_free
+    }
+    fn native_base_total(&self) -> u64 {
+        self.native_base_free + self.native_base_reserved
+    }
+    fn native_quote_total(&self) -> u64 {
+        self.native_quote_free + self.native_quote_reserved
+    }
+    fn native_rebates(&self) -> u64 {
+        self.referrer_rebates_available
+    }
+}
@@ -19,12 +19,27 @@ pub mod raydium_mainnet {
     declare_id!("CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK");
 }
 
+pub mod meteora_dlmm_mainnet {
+    use solana_program::declare_id;
+    declare_id!("LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo");
+}
+
 pub const ORCA_WHIRLPOOL_LEN: usize = 653;
 pub const ORCA_WHIRLPOOL_DISCRIMINATOR: [u8; 8] = [63, 149, 209, 12, 225, 128, 99, 9];
 
 pub const RAYDIUM_POOL_LEN: usize = 1544;
 pub const RAYDIUM_POOL_DISCRIMINATOR: [u8; 8] = [247, 237, 227, 245, 215, 195, 222, 70];
 
+pub const METEORA_LB_PAIR_LEN: usize = 904;
+pub const METEORA_LB_PAIR_DISCRIMINATOR: [u8; 8] = [33, 11, 49, 98, 181, 101, 177, 13];
+
+pub const RAYDIUM_OBSERVATION_NUM: usize = 100;
+const RAYDIUM_OBSERVATION_SIZE: usize = 44;
+const RAYDIUM_OBSERVATIONS_OFFSET: usize = 51;
+pub const RAYDIUM_OBSERVATION_LEN: usize =
+    RAYDIUM_OBSERVATIONS_OFFSET + RAYDIUM_OBSERVATION_NUM * RAYDIUM_OBSERVATION_SIZE + 32;
+pub const RAYDIUM_OBSERVATION_DISCRIMINATOR: [u8; 8] = [122, 174, 197, 53, 129, 9, 165, 132];
+
 pub struct CLMMPoolState {
     // Q64.64
     pub sqrt_price: u128,     // 16
@@ -34,9 +49,7 @@ pub struct CLMMPoolState {
 
 impl CLMMPoolState {
     pub fn is_inverted(&self) -> bool {
-        self.token_mint_a == usdc_mint_mainnet::ID
-            || (self.token_mint_a == sol_mint_mainnet::ID
-                && self.token_mint_b != usdc_mint_mainnet::ID)
+        is_inverted_pair(&self.token_mint_a, &self.token_mint_b)
     }
 
     pub fn get_clmm_price(&self) -> I80F48 {
@@ -54,58 +67,79 @@ impl CLMMPoolState {
         &self,
         acc_infos: &OracleAccountInfos<T>,
     ) -> Result<OracleState> {
-        if self.is_inverted() {
-            self.quote_state_inner(acc_infos, &self.token_mint_a)
-        } else {
-            self.quote_state_inner(acc_infos, &self.token_mint_b)
-        }
+        quote_mint_state_unchecked(acc_infos, &self.quote_mint())
     }
 
-    fn quote_state_inner<T: KeyedAccountReader>(
-        &self,
-        acc_infos: &OracleAccountInfos<T>,
-        quote_mint: &Pubkey,
-    ) -> Result<OracleState> {
-        if quote_mint == &usdc_mint_mainnet::ID {
-            let usd_feed = acc_infos
-                .usdc_opt
-                .ok_or_else(|| error!(MangoError::MissingFeedForCLMMOracle))?;
-            let usd_state = get_pyth_state(usd_feed, QUOTE_DECIMALS as u8)?;
-            return Ok(usd_state);
-        } else if quote_mint == &sol_mint_mainnet::ID {
-            let sol_feed = acc_infos
-                .sol_opt
-                .ok_or_else(|| error!(MangoError::MissingFeedForCLMMOracle))?;
-            let sol_state = get_pyth_state(sol_feed, SOL_DECIMALS as u8)?;
-            return Ok(sol_state);
-        } else {
-            return Err(MangoError::MissingFeedForCLMMOracle.into());
-        }
+    pub fn get_quote_oracle(&self) -> Result<Pubkey> {
+        quote_mint_oracle(&self.quote_mint())
     }
 
-    pub fn get_quote_oracle(&self) -> Result<Pubkey> {
-        let mint = if self.is_inverted() {
+    /// The pool token that is priced by a usdc or sol oracle
+    pub fn quote_mint(&self) -> Pubkey {
+        if self.is_inverted() {
             self.token_mint_a
         } else {
             self.token_mint_b
-        };
+        }
+    }
 
-        if mint == usdc_mint_mainnet::ID {
-            return Ok(pyth_mainnet_usdc_oracle::ID);
-        } else if mint == sol_mint_mainnet::ID {
-            return Ok(pyth_mainnet_sol_oracle::ID);
+    /// The pool token that is priced by this pool
+    pub fn base_mint(&self) -> Pubkey {
+        if self.is_inverted() {
+            self.token_mint_b
         } else {
-            return Err(MangoError::MissingFeedForCLMMOracle.into());
+            self.token_mint_a
         }
     }
 
     pub fn has_quote_token(&self) -> bool {
-        let has_usdc_token = self.token_mint_a == usdc_mint_mainnet::ID
-            || self.token_mint_b == usdc_mint_mainnet::ID;
-        let has_sol_token =
-            self.token_mint_a == sol_mint_mainnet::ID || self.token_mint_b == sol_mint_mainnet::ID;
+        has_quote_token_pair(&self.token_mint_a, &self.token_mint_b)
+    }
+}
+
+/// Whether the first mint of a pool pair is the usdc or sol quote token
+fn is_inverted_pair(mint_a: &Pubkey, mint_b: &Pubkey) -> bool {
+    mint_a == &usdc_mint_mainnet::ID
+        || (mint_a == &sol_mint_mainnet::ID && mint_b != &usdc_mint_mainnet::ID)
+}
+
+fn has_quote_token_pair(mint_a: &Pubkey, mint_b: &Pubkey) -> bool {
+    let has_usdc_token = mint_a == &usdc_mint_mainnet::ID || mint_b == &usdc_mint_mainnet::ID;
+    let has_sol_token = mint_a == &sol_mint_mainnet::ID || mint_b == &sol_mint_mainnet::ID;
 
-        has_usdc_token || has_sol_token
+    has_usdc_token || has_sol_token
+}
+
+/// Price of the usdc or sol quote token of a CLMM pool
+pub fn quote_mint_state_unchecked<T: KeyedAccountReader>(
+    acc_infos: &OracleAccountInfos<T>,
+    quote_mint: &Pubkey,
+) -> Result<OracleState> {
+    if quote_mint == &usdc_mint_mainnet::ID {
+        let usd_feed = acc_infos
+            .usdc_opt
+            .ok_or_else(|| error!(MangoError::MissingFeedForCLMMOracle))?;
+        let usd_state = get_pyth_state(usd_feed, QUOTE_DECIMALS as u8)?;
+        return Ok(usd_state);
+    } else if quote_mint == &sol_mint_mainnet::ID {
+        let sol_feed = acc_infos
+            .sol_opt
+            .ok_or_else(|| error!(MangoError::MissingFeedForCLMMOracle))?;
+        let sol_state = get_pyth_state(sol_feed, SOL_DECIMALS as u8)?;
+        return Ok(sol_state);
+    } else {
+        return Err(MangoError::MissingFeedForCLMMOracle.into());
+    }
+}
+
+/// The oracle that prices a CLMM pool's quote token
+pub fn quote_mint_oracle(quote_mint: &Pubkey) -> Result<Pubkey> {
+    if quote_mint == &usdc_mint_mainnet::ID {
+        return Ok(pyth_mainnet_usdc_oracle::ID);
+    } else if quote_mint == &sol_mint_mainnet::ID {
+        return Ok(pyth_mainnet_sol_oracle::ID);
+    } else {
+        return Err(MangoError::MissingFeedForCLMMOracle.into());
     }
 }
 
@@ -166,3 +200,191 @@ pub fn load_raydium_pool_state(acc_info: &impl KeyedAccountReader) -> Result<CLM
         token_mint_b: mint_b,
     })
 }
+
+/// The observation account that belongs to a raydium pool
+pub fn load_raydium_pool_observation_key(acc_info: &impl KeyedAccountReader) -> Result<Pubkey> {
+    load_raydium_pool_state(acc_info)?;
+    let data = &acc_info.data();
+    let key: &[u8; 32] = &(&data[201..233]).try_into().unwrap();
+    Ok(Pubkey::from(*key))
+}
+
+/// Tick accumulator history of a raydium pool
+pub struct RaydiumObservations {
+    pub pool: Pubkey,
+    /// Index of the most recent observation
+    pub latest_index: usize,
+    /// (block_timestamp, tick_cumulative) pairs
+    pub observations: Vec<(u32, i64)>,
+}
+
+pub fn load_raydium_observations(
+    acc_info: &impl KeyedAccountReader,
+) -> Result<RaydiumObservations> {
+    let data = &acc_info.data();
+    require!(
+        data[0..8] == RAYDIUM_OBSERVATION_DISCRIMINATOR[..],
+        MangoError::InvalidCLMMOracle
+    );
+    require!(
+        data.len() == RAYDIUM_OBSERVATION_LEN,
+        MangoError::InvalidCLMMOracle
+    );
+    require!(
+        acc_info.owner() == &raydium_mainnet::ID,
+        MangoError::InvalidCLMMOracle
+    );
+
+    let latest_index = u16::from_le_bytes(data[17..19].try_into().unwrap()) as usize;
+    require_gt!(RAYDIUM_OBSERVATION_NUM, latest_index);
+    let pool: &[u8; 32] = &(&data[19..51]).try_into().unwrap();
+    let observations = (0..RAYDIUM_OBSERVATION_NUM)
+        .map(|i| {
+            let start = RAYDIUM_OBSERVATIONS_OFFSET + i * RAYDIUM_OBSERVATION_SIZE;
+            let timestamp = u32::from_le_bytes(data[start..start + 4].try_into().unwrap());
+            let tick_cumulative =
+                i64::from_le_bytes(data[start + 4..start + 12].try_into().unwrap());
+            (timestamp, tick_cumulative)
+        })
+        .collect();
+
+    Ok(RaydiumObservations {
+        pool: Pubkey::from(*pool),
+        latest_index,
+        observations,
+    })
+}
+
+impl RaydiumObservations {
+    /// Time-weighted average tick over at least the last `window_seconds` of history,
+    /// ending at the most recent observation.
+    ///
+    /// Returns the average tick and the length of the window that was actually used.
+    /// Only recorded observations are used, so swaps in the current transaction can't
+    /// move the result.
+    pub fn twap_tick(&self, window_seconds: u64) -> Result<(f64, u64)> {
+        let n = self.observations.len();
+        let (latest_ts, latest_cumulative) = self.observations[self.latest_index];
+        require_msg_typed!(
+            latest_ts != 0,
+            MangoError::InvalidCLMMOracle,
+            "no observations recorded"
+        );
+        let target_ts = (latest_ts as u64).saturating_sub(window_seconds);
+
+        let mut start = None;
+        for k in 1..n {
+            let (ts, cumulative) = self.observations[(self.latest_index + n - k) % n];
+            if ts == 0 || ts >= latest_ts {
+                // uninitialized, or wrapped around to newer observations
+                break;
+            }
+            start = Some((ts, cumulative));
+            if ts as u64 <= target_ts {
+                break;
+            }
+        }
+
+        let (start_ts, start_cumulative) = start.unwrap_or((latest_ts, latest_cumulative));
+        let observed_window = (latest_ts - start_ts) as u64;
+        require_msg_typed!(
+            observed_window > 0 && observed_window >= window_seconds,
+            MangoError::InvalidCLMMOracle,
+            "observation history covers {}s, need {}s",
+            observed_window,
+            window_seconds
+        );
+
+        let avg_tick = (latest_cumulative - start_cumulative) as f64 / observed_window as f64;
+        Ok((avg_tick, observed_window))
+    }
+}
+
+/// Price of token a in token b, in native units, for a clmm tick
+pub fn clmm_tick_to_price(tick: f64) -> f64 {
+    1.0001f64.powf(tick)
+}
+
+/// The active bin of a meteora DLMM pair
+///
+/// Every bin has a fixed price of (1 + bin_step / 10_000)^bin_id token y native
+/// per token x native, so the active bin prices the pair directly.
+pub struct DLMMPoolState {
+    pub active_id: i32,
+    /// In basis points
+    pub bin_step: u16,
+    pub token_mint_x: Pubkey,
+    pub token_mint_y: Pubkey,
+}
+
+impl DLMMPoolState {
+    pub fn is_inverted(&self) -> bool {
+        is_inverted_pair(&self.token_mint_x, &self.token_mint_y)
+    }
+
+    /// Relative width of a single bin
+    pub fn bin_width(&self) -> f64 {
+        self.bin_step as f64 / 10_000.0
+    }
+
+    pub fn get_dlmm_price(&self) -> I80F48 {
+        let price = (1.0 + self.bin_width()).powi(self.active_id);
+        if self.is_inverted() {
+            I80F48::from_num(1.0f64 / price)
+        } else {
+            I80F48::from_num(price)
+        }
+    }
+
+    pub fn quote_state_unchecked<T: KeyedAccountReader>(
+        &self,
+        acc_infos: &OracleAccountInfos<T>,
+    ) -> Result<OracleState> {
+        quote_mint_state_unchecked(acc_infos, &self.quote_mint())
+    }
+
+    pub fn get_quote_oracle(&self) -> Result<Pubkey> {
+        quote_mint_oracle(&self.quote_mint())
+    }
+
+    /// The pair token that is priced by a usdc or sol oracle
+    pub fn quote_mint(&self) -> Pubkey {
+        if self.is_inverted() {
+            self.token_mint_x
+        } else {
+            self.token_mint_y
+        }
+    }
+
+    pub fn has_quote_token(&self) -> bool {
+        has_quote_token_pair(&self.token_mint_x, &self.token_mint_y)
+    }
+}
+
+pub fn load_meteora_dlmm_state(acc_info: &impl KeyedAccountReader) -> Result<DLMMPoolState> {
+    let data = &acc_info.data();
+    require!(
+        data[0..8] == METEORA_LB_PAIR_DISCRIMINATOR[..],
+        MangoError::InvalidCLMMOracle
+    );
+    require!(
+        data.len() == METEORA_LB_PAIR_LEN,
+        MangoError::InvalidCLMMOracle
+    );
+    require!(
+        acc_info.owner() == &meteora_dlmm_mainnet::ID,
+        MangoError::InvalidCLMMOracle
+    );
+
+    let active_id = i32::from_le_bytes(data[76..80].try_into().unwrap());
+    let bin_step = u16::from_le_bytes(data[80..82].try_into().unwrap());
+    let x: &[u8; 32] = &(&data[88..120]).try_into().unwrap();
+    let y: &[u8; 32] = &(&data[120..152]).try_into().unwrap();
+
+    Ok(DLMMPoolState {
+        active_id,
+        bin_step,
+        token_mint_x: Pubkey::from(*x),
+        token_mint_y: Pubkey::from(*y),
+    })
+}
@@ -1,4 +1,6 @@
-use super::{OracleAccountInfos, OracleConfig, TokenIndex, TokenPosition};
+use super::{
+    EmodeCategory, OracleAccountInfos, OracleConfig, OracleFreshness, TokenIndex, TokenPosition,
+};
 use crate::accounts_zerocopy::KeyedAccountReader;
 use crate::error::*;
 use crate::i80f48::ClampToInt;
@@ -8,6 +10,7 @@ use crate::util;
 use anchor_lang::prelude::*;
 use derivative::Derivative;
 use fixed::types::I80F48;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
 use oracle::oracle_log_context;
 use static_assertions::const_assert_eq;
 
@@ -19,6 +22,20 @@ pub const DAY_I80F48: I80F48 = I80F48::from_bits(86_400 * I80F48::ONE.to_bits())
 pub const ONE_BPS: I80F48 = I80F48::from_bits(28147497671);
 pub const YEAR_I80F48: I80F48 = I80F48::from_bits(31_536_000 * I80F48::ONE.to_bits());
 
+/// The rate controller never moves rate0 and rate1 below this, so they can always recover
+pub const MIN_CONTROLLER_RATE: I80F48 = ONE_BPS;
+
+/// How a bank adjusts its interest rate curve over time
+#[derive(Clone, Copy, PartialEq, Eq, Debug, TryFromPrimitive, IntoPrimitive)]
+#[repr(u8)]
+pub enum InterestRateModel {
+    /// Scale the whole curve with interest_curve_scaling based on adjustment_factor
+    CurveScaling = 0,
+
+    /// Move rate0 and rate1 with a proportional-integral controller on utilization
+    Controller = 1,
+}
+
 #[derive(Derivative)]
 #[derivative(Debug)]
 #[account(zero_copy)]
@@ -231,8 +248,91 @@ pub struct Bank {
     /// The daily collateral fees rate for fully utilized collateral.
     pub collateral_fee_per_day: f32,
 
+    /// How long the bank stays reduce-only after the circuit breaker trips.
+    pub circuit_breaker_cooldown_seconds: u32,
+
+    /// While non-zero, the circuit breaker is tripped and borrows are reduce-only.
+    /// Cleared by token_update_index_and_rate once this timestamp has passed.
+    pub circuit_breaker_tripped_until: u64,
+
+    /// Maximal relative difference between the oracle price and the latest delay price
+    /// of the stable price model before the circuit breaker trips. Zero disables it.
+    pub circuit_breaker_max_price_move: f32,
+
+    /// Selects how rate0 and rate1 are adjusted over time, see InterestRateModel
+    pub interest_rate_model: u8,
+
+    #[derivative(Debug = "ignore")]
+    pub padding2: [u8; 3],
+
+    /// Gains of the rate controller: the relative daily change of rate0 and rate1 is
+    /// kp * error + ki * integral, with error = avg_utilization - interest_target_utilization.
+    pub interest_controller_kp: f32,
+    pub interest_controller_ki: f32,
+
+    /// Bound on the relative change of rate0 and rate1 per day, must be less than 1
+    pub interest_controller_max_daily_change: f32,
+
+    /// Utilization error at the last controller update
+    pub interest_controller_error: f32,
+
+    /// Accumulated utilization error, in utilization * days, bounded to [-1, 1]
+    pub interest_controller_integral: f64,
+
+    /// Limit on the total native borrows of the bank, zero means none.
+    ///
+    /// The limit is checked per bank. It can only be set on tokens with a single
+    /// bank and no banks can be added while it is set, so it bounds the
+    /// outstanding borrows of the whole token.
+    pub borrow_limit: u64,
+
+    /// If set, deposits of this token only count as collateral for borrows of the
+    /// tokens in isolated_borrow_token_indexes, up to isolated_debt_ceiling_quote.
+    pub isolated_collateral: u8,
+
+    #[derivative(Debug = "ignore")]
+    pub padding3: [u8; 1],
+
+    /// Tokens that isolated collateral may back, unused entries are TokenIndex::MAX
+    pub isolated_borrow_token_indexes: [TokenIndex; 7],
+
+    /// Maximal health-weighted value of borrows, in native quote, that isolated
+    /// collateral of this token may back across all accounts, see isolated_debt_quote.
+    ///
+    /// Combine with deposit_limit to bound the total exposure to the token.
+    pub isolated_debt_ceiling_quote: u64,
+
+    /// E-mode category of the token, zero means none.
+    ///
+    /// The category's boosted weights are copied from the group, see EmodeCategory.
+    pub emode_category: u8,
+
+    #[derivative(Debug = "ignore")]
+    pub padding4: [u8; 3],
+
+    pub emode_maint_asset_weight: f32,
+    pub emode_init_asset_weight: f32,
+    pub emode_maint_liab_weight: f32,
+    pub emode_init_liab_weight: f32,
+
+    #[derivative(Debug = "ignore")]
+    pub padding5: [u8; 4],
+
+    /// Amount owed at maturity to fixed term markets of this token.
+    ///
+    /// Counts toward borrow_limit like regular borrows.
+    pub fixed_term_borrows_native: u64,
+
+    /// Health-weighted value of borrows, in native quote, backed by isolated collateral
+    /// of this token, summed over all accounts.
+    ///
+    /// Each account's share is recorded in its token position and refreshed whenever
+    /// a borrow or withdraw checks its health, see update_isolated_debt(). Can't grow
+    /// beyond isolated_debt_ceiling_quote.
+    pub isolated_debt_quote: u64,
+
     #[derivative(Debug = "ignore")]
-    pub reserved: [u8; 1900],
+    pub reserved: [u8; 1784],
 }
 const_assert_eq!(
     size_of::<Bank>(),
@@ -271,7 +371,25 @@ const_assert_eq!(
         + 8
         + 16 * 4
         + 4
-        + 1900
+        + 4
+        + 8
+        + 4
+        + 1
+        + 3
+        + 4 * 4
+        + 8
+        + 8
+        + 1
+        + 1
+        + 2 * 7
+        + 8
+        + 1
+        + 3
+        + 4 * 4
+        + 4
+        + 8
+        + 8
+        + 1784
 );
 const_assert_eq!(size_of::<Bank>(), 3064);
 const_assert_eq!(size_of::<Bank>() % 8, 0);
@@ -382,7 +500,32 @@ impl Bank {
             zero_util_rate: existing_bank.zero_util_rate,
             platform_liquidation_fee: existing_bank.platform_liquidation_fee,
             collateral_fee_per_day: existing_bank.collateral_fee_per_day,
-            reserved: [0; 1900],
+            circuit_breaker_cooldown_seconds: existing_bank.circuit_breaker_cooldown_seconds,
+            circuit_breaker_tripped_until: existing_bank.circuit_breaker_tripped_until,
+            circuit_breaker_max_price_move: existing_bank.circuit_breaker_max_price_move,
+            interest_rate_model: existing_bank.interest_rate_model,
+            padding2: Default::default(),
+            interest_controller_kp: existing_bank.interest_controller_kp,
+            interest_controller_ki: existing_bank.interest_controller_ki,
+            interest_controller_max_daily_change: existing_bank
+                .interest_controller_max_daily_change,
+            interest_controller_error: existing_bank.interest_controller_error,
+            interest_controller_integral: existing_bank.interest_controller_integral,
+            borrow_limit: existing_bank.borrow_limit,
+            isolated_collateral: existing_bank.isolated_collateral,
+            padding3: Default::default(),
+            isolated_borrow_token_indexes: existing_bank.isolated_borrow_token_indexes,
+            isolated_debt_ceiling_quote: existing_bank.isolated_debt_ceiling_quote,
+            emode_category: existing_bank.emode_category,
+            padding4: Default::default(),
+            emode_maint_asset_weight: existing_bank.emode_maint_asset_weight,
+            emode_init_asset_weight: existing_bank.emode_init_asset_weight,
+            emode_maint_liab_weight: existing_bank.emode_maint_liab_weight,
+            emode_init_liab_weight: existing_bank.emode_init_liab_weight,
+            padding5: Default::default(),
+            fixed_term_borrows_native: 0,
+            isolated_debt_quote: 0,
+            reserved: [0; 1784],
         }
     }
 
@@ -421,19 +564,38 @@ impl Bank {
         require_gte!(self.maint_weight_shift_liab_target, 0.0);
         require_gte!(self.zero_util_rate, I80F48::ZERO);
         require_gte!(self.platform_liquidation_fee, 0.0);
+        // check the configured reduce_only, the circuit breaker is only temporary
         if !self.allows_asset_liquidation() {
-            require!(self.are_borrows_reduce_only(), MangoError::SomeError);
+            require!(
+                self.reduce_only == 1 || self.reduce_only == 2,
+                MangoError::SomeError
+            );
             require_eq!(self.maint_asset_weight, I80F48::ZERO);
         }
         require_gte!(self.collateral_fee_per_day, 0.0);
+        require_gte!(self.circuit_breaker_max_price_move, 0.0);
+        if self.circuit_breaker_max_price_move > 0.0 {
+            require_gt!(self.circuit_breaker_cooldown_seconds, 0);
+        }
+        self.interest_rate_model()?;
+        require_gte!(self.interest_controller_kp, 0.0);
+        require_gte!(self.interest_controller_ki, 0.0);
+        require_gte!(self.interest_controller_max_daily_change, 0.0);
+        require_gt!(1.0, self.interest_controller_max_daily_change);
+        require_gte!(1, self.isolated_collateral);
         if self.is_force_withdraw() {
-            require!(self.are_deposits_reduce_only(), MangoError::SomeError);
+            require_eq!(self.reduce_only, 1);
             require!(!self.allows_asset_liquidation(), MangoError::SomeError);
             require_eq!(self.maint_asset_weight, I80F48::ZERO);
         }
         Ok(())
     }
 
+    pub fn interest_rate_model(&self) -> Result<InterestRateModel> {
+        InterestRateModel::try_from(self.interest_rate_model)
+            .map_err(|_| error_msg!("invalid interest rate model {}", self.interest_rate_model))
+    }
+
     pub fn name(&self) -> &str {
         std::str::from_utf8(&self.name)
             .unwrap()
@@ -450,10 +612,42 @@ impl Bank {
         self.reduce_only == 1
     }
 
+    /// Borrows are restricted by the reduce_only setting or a tripped circuit breaker
     pub fn are_borrows_reduce_only(&self) -> bool {
+        self.are_borrows_configured_reduce_only() || self.is_circuit_breaker_tripped()
+    }
+
+    /// Like are_borrows_reduce_only(), but ignoring the circuit breaker
+    pub fn are_borrows_configured_reduce_only(&self) -> bool {
         self.reduce_only == 1 || self.reduce_only == 2
     }
 
+    pub fn is_circuit_breaker_tripped(&self) -> bool {
+        self.circuit_breaker_tripped_until != 0
+    }
+
+    /// Trips the circuit breaker if the oracle price moved too far from the latest
+    /// delay price, or releases it once the cooldown has passed.
+    ///
+    /// Returns true if the circuit breaker was tripped or released.
+    pub fn update_circuit_breaker(&mut self, oracle_price: f64, now_ts: u64) -> bool {
+        let was_tripped = self.is_circuit_breaker_tripped();
+        if was_tripped && now_ts >= self.circuit_breaker_tripped_until {
+            self.circuit_breaker_tripped_until = 0;
+        }
+
+        let reference_price = self.stable_price_model.latest_delay_price();
+        if self.circuit_breaker_max_price_move > 0.0 && reference_price > 0.0 {
+            let price_move = (oracle_price / reference_price - 1.0).abs();
+            if price_move > self.circuit_breaker_max_price_move as f64 {
+                self.circuit_breaker_tripped_until =
+                    now_ts + self.circuit_breaker_cooldown_seconds as u64;
+            }
+        }
+
+        was_tripped != self.is_circuit_breaker_tripped()
+    }
+
     pub fn is_force_close(&self) -> bool {
         self.force_close == 1
     }
@@ -466,6 +660,30 @@ impl Bank {
         self.disable_asset_liquidation == 0
     }
 
+    pub fn is_isolated_collateral(&self) -> bool {
+        self.isolated_collateral == 1
+    }
+
+    /// Set the token's e-mode category and weights, or clear them if category is None
+    pub fn set_emode_category(&mut self, category: Option<(u8, &EmodeCategory)>) {
+        match category {
+            Some((id, category)) => {
+                self.emode_category = id;
+                self.emode_maint_asset_weight = category.maint_asset_weight;
+                self.emode_init_asset_weight = category.init_asset_weight;
+                self.emode_maint_liab_weight = category.maint_liab_weight;
+                self.emode_init_liab_weight = category.init_liab_weight;
+            }
+            None => {
+                self.emode_category = 0;
+                self.emode_maint_asset_weight = 0.0;
+                self.emode_init_asset_weight = 0.0;
+                self.emode_maint_liab_weight = 0.0;
+                self.emode_init_liab_weight = 0.0;
+            }
+        }
+    }
+
     #[inline(always)]
     pub fn native_borrows(&self) -> I80F48 {
         self.borrow_index * self.indexed_borrows
@@ -898,6 +1116,7 @@ impl Bank {
         // Adding DELTA here covers the case where we add slightly more than we withdraw
         if self.indexed_borrows > before_borrows + I80F48::DELTA {
             self.check_net_borrows(oracle_price)?;
+            self.check_borrow_limit()?;
         }
         if self.indexed_deposits > before_deposits + I80F48::DELTA {
             self.check_deposit_and_oo_limit()?;
@@ -999,6 +1218,34 @@ impl Bank {
         Ok(())
     }
 
+    pub fn remaining_borrows_until_limit(&self) -> I80F48 {
+        if self.borrow_limit == 0 {
+            return I80F48::MAX;
+        }
+
+        I80F48::from(self.borrow_limit)
+            - self.native_borrows()
+            - I80F48::from(self.fixed_term_borrows_native)
+    }
+
+    /// Errors if the bank's native borrows and fixed term borrows exceed borrow_limit.
+    ///
+    /// Call after operations that may have increased borrows.
+    pub fn check_borrow_limit(&self) -> Result<()> {
+        let remaining = self.remaining_borrows_until_limit();
+        if remaining < 0 {
+            return Err(error_msg_typed!(
+                MangoError::BankBorrowLimit,
+                "borrow limit exceeded: remaining: {}, borrows: {}, limit: {}",
+                remaining,
+                self.native_borrows(),
+                self.borrow_limit,
+            ));
+        }
+
+        Ok(())
+    }
+
     pub fn update_cumulative_interest(
         &self,
         position: &mut TokenPosition,
@@ -1186,23 +1433,70 @@ impl Bank {
         self.interest_curve_scaling = (self.interest_curve_scaling * adjustment).max(1.0)
     }
 
+    /// Adjusts rate0 and rate1 with a proportional-integral controller that moves
+    /// avg_utilization towards interest_target_utilization.
+    ///
+    /// Returns the relative daily change that was applied.
+    pub fn update_interest_rate_controller(&mut self, diff_ts: u64) -> f64 {
+        let target_util = self.interest_target_utilization as f64;
+        let avg_util = self.avg_utilization.to_num::<f64>().max(0.0).min(1.0);
+        let error = avg_util - target_util;
+
+        // limit the timestep like for index updates, so a long downtime doesn't cause a jump
+        let dt_days = diff_ts.min(DAY as u64) as f64 / DAY as f64;
+        let integral = (self.interest_controller_integral + error * dt_days).clamp(-1.0, 1.0);
+
+        let max_change = self.interest_controller_max_daily_change as f64;
+        let daily_change = (self.interest_controller_kp as f64 * error
+            + self.interest_controller_ki as f64 * integral)
+            .clamp(-max_change, max_change);
+        let factor = I80F48::from_num((1.0 + daily_change * dt_days).max(0.0));
+
+        self.rate1 = (self.rate1 * factor)
+            .max(MIN_CONTROLLER_RATE)
+            .min(self.max_rate);
+        self.rate0 = (self.rate0 * factor)
+            .max(MIN_CONTROLLER_RATE)
+            .min(self.rate1);
+        self.interest_controller_error = error as f32;
+        self.interest_controller_integral = integral;
+
+        daily_change
+    }
+
     /// Tries to return the primary oracle price, and if there is a confidence or staleness issue returns the fallback oracle price if possible.
     pub fn oracle_price<T: KeyedAccountReader>(
         &self,
         oracle_acc_infos: &OracleAccountInfos<T>,
         now: Option<(u64, u64)>, // (now_ts, now_slot)
+    ) -> Result<I80F48> {
+        self.oracle_price_for(oracle_acc_infos, now, OracleFreshness::Health)
+    }
+
+    /// Like oracle_price(), but with the staleness limits for `freshness`
+    pub fn oracle_price_for<T: KeyedAccountReader>(
+        &self,
+        oracle_acc_infos: &OracleAccountInfos<T>,
+        now: Option<(u64, u64)>, // (now_ts, now_slot)
+        freshness: OracleFreshness,
     ) -> Result<I80F48> {
         require_keys_eq!(self.oracle, *oracle_acc_infos.oracle.key());
         let primary_state = oracle::oracle_state_unchecked(oracle_acc_infos, self.mint_decimals)?;
-        let primary_ok =
-            primary_state.check_confidence_and_maybe_staleness(&self.oracle_config, now);
+        let primary_ok = primary_state.check_confidence_and_maybe_staleness_for(
+            &self.oracle_config,
+            now,
+            freshness,
+        );
         if primary_ok.is_oracle_error() && oracle_acc_infos.fallback_opt.is_some() {
             let fallback_oracle_acc = oracle_acc_infos.fallback_opt.unwrap();
             require_keys_eq!(self.fallback_oracle, *fallback_oracle_acc.key());
             let fallback_state =
                 oracle::fallback_oracle_state_unchecked(&oracle_acc_infos, self.mint_decimals)?;
-            let fallback_ok =
-                fallback_state.check_confidence_and_maybe_staleness(&self.oracle_config, now);
+            let fallback_ok = fallback_state.check_confidence_and_maybe_staleness_for(
+                &self.oracle_config,
+                now,
+                freshness,
+            );
             fallback_ok.with_context(|| {
                 format!(
                     "{} {}",
@@ -1228,8 +1522,14 @@ impl Bank {
     /// If max_collateral is 0, then the scaled init weight will be 0.
     /// Otherwise the weight is unadjusted until max_collateral and then scaled down
     /// such that scaled_init_weight * deposits remains constant.
+    ///
+    /// While the circuit breaker is tripped the weight is 0, so deposits made at
+    /// the extreme price can't be used as new collateral.
     #[inline(always)]
     pub fn scaled_init_asset_weight(&self, price: I80F48) -> I80F48 {
+        if self.is_circuit_breaker_tripped() {
+            return I80F48::ZERO;
+        }
         if self.deposit_weight_scale_start_quote == f64::MAX {
             return self.init_asset_weight;
         }
@@ -1334,8 +1634,9 @@ mod tests {
             cumulative_deposit_interest: 0.0,
             cumulative_borrow_interest: 0.0,
             previous_index: I80F48::ZERO,
+            isolated_debt_quote: 0,
             padding: Default::default(),
-            reserved: [0; 128],
+            reserved: [0; 120],
         };
 
         account.indexed_position = indexed(I80F48::from_num(start), &bank);
@@ -1461,8 +1762,9 @@ mod tests {
             cumulative_deposit_interest: 0.0,
             cumulative_borrow_interest: 0.0,
             previous_index: I80F48::ZERO,
+            isolated_debt_quote: 0,
             padding: Default::default(),
-            reserved: [0; 128],
+            reserved: [0; 120],
         };
 
         //
@@ -1801,4 +2103,58 @@ mod tests {
         assert!(d(interest(0.75 + delta), 7.0) <= eps);
         assert!(d(interest(1.0 - delta), 13.0) <= eps);
     }
+
+    #[test]
+    fn test_interest_rate_controller() {
+        let mut bank = Bank::zeroed();
+        bank.interest_rate_model = InterestRateModel::Controller.into();
+        bank.interest_target_utilization = 0.5;
+        bank.interest_controller_kp = 1.0;
+        bank.interest_controller_ki = 0.5;
+        bank.interest_controller_max_daily_change = 0.2;
+        bank.rate0 = I80F48::from_num(0.1);
+        bank.rate1 = I80F48::from_num(0.2);
+        bank.max_rate = I80F48::from_num(0.21);
+        let d = |a: f64, b: f64| (a - b).abs();
+
+        // utilization above target: rates rise
+        bank.avg_utilization = I80F48::from_num(0.6);
+        let change = bank.update_interest_rate_controller(HOUR as u64);
+        let integral = 0.1 / 24.0;
+        assert!(d(change, 0.1 + 0.5 * integral) < 1e-9);
+        assert!(d(bank.interest_controller_error as f64, 0.1) < 1e-6);
+        assert!(d(bank.interest_controller_integral, integral) < 1e-9);
+        let factor = 1.0 + change / 24.0;
+        assert!(d(bank.rate0.to_num(), 0.1 * factor) < 1e-9);
+        assert!(d(bank.rate1.to_num(), 0.2 * factor) < 1e-9);
+
+        // daily change is bounded, rate1 by max_rate and rate0 by rate1
+        bank.avg_utilization = I80F48::from_num(1.0);
+        let change = bank.update_interest_rate_controller(DAY as u64);
+        assert!(d(change, 0.2) < 1e-9);
+        assert!(d(bank.rate1.to_num(), 0.21) < 1e-9);
+        assert!(bank.rate0 <= bank.rate1);
+
+        // long gaps are treated like a day, the integral stays bounded
+        bank.avg_utilization = I80F48::from_num(0.0);
+        for _ in 0..10 {
+            bank.update_interest_rate_controller(10 * DAY as u64);
+        }
+        assert!(d(bank.interest_controller_integral, -1.0) < 1e-9);
+        assert!(bank.rate0 > 0);
+        assert!(bank.rate1 > 0);
+
+        // rates don't decay below the minimum
+        for _ in 0..100 {
+            bank.update_interest_rate_controller(DAY as u64);
+        }
+        assert_eq!(bank.rate0, MIN_CONTROLLER_RATE);
+        assert_eq!(bank.rate1, MIN_CONTROLLER_RATE);
+
+        // and recover from it when utilization rises again
+        bank.avg_utilization = I80F48::from_num(1.0);
+        bank.update_interest_rate_controller(DAY as u64);
+        assert!(bank.rate0 > MIN_CONTROLLER_RATE);
+        assert!(bank.rate1 > MIN_CONTROLLER_RATE);
+    }
 }
@@ -0,0 +1,87 @@
+use std::mem::size_of;
+
+use anchor_lang::prelude::*;
+use fixed::types::I80F48;
+use static_assertions::const_assert_eq;
+
+use crate::accounts_zerocopy::*;
+use crate::error::*;
+
+use super::{
+    clmm_tick_to_price, load_raydium_observations, load_raydium_pool_observation_key,
+    load_raydium_pool_state,
+};
+
+/// A time-weighted average price derived from a raydium CLMM pool's observations.
+///
+/// The pool's sqrt_price can be moved freely within a transaction, so the instantaneous
+/// CLMM oracles are easy to manipulate. This oracle instead averages the pool's recorded
+/// tick accumulator over `twap_window_seconds`. It is refreshed permissionlessly with
+/// clmm_twap_oracle_update and then reads like the CLMM oracles: `price` is multiplied by
+/// the usdc or sol oracle price of `quote_mint`.
+///
+/// Orca whirlpools don't record an observation history and can't back this oracle.
+#[account(zero_copy)]
+pub struct ClmmTwapOracle {
+    // ABI: Clients rely on this being at offset 8
+    pub group: Pubkey,
+    // ABI: Clients rely on this being at offset 40
+    pub mint: Pubkey,
+
+    pub pool: Pubkey,
+    pub observation: Pubkey,
+
+    /// The pool token whose usdc or sol oracle converts `price` to usd
+    pub quote_mint: Pubkey,
+
+    /// Price of one native `mint` token in native `quote_mint` tokens
+    pub price: I80F48,
+
+    pub twap_window_seconds: u64,
+
+    /// Length of the window used for the last update, at least twap_window_seconds
+    pub observed_window_seconds: u64,
+
+    pub last_update_slot: u64,
+    pub last_update_ts: u64,
+
+    pub reserved: [u8; 144],
+}
+const_assert_eq!(size_of::<ClmmTwapOracle>(), 32 * 5 + 16 + 8 * 4 + 144);
+const_assert_eq!(size_of::<ClmmTwapOracle>(), 352);
+const_assert_eq!(size_of::<ClmmTwapOracle>() % 8, 0);
+
+impl ClmmTwapOracle {
+    pub fn update(
+        &mut self,
+        pool_ai: &impl KeyedAccountReader,
+        observation_ai: &impl KeyedAccountReader,
+        now_ts: u64,
+        now_slot: u64,
+    ) -> Result<()> {
+        require_keys_eq!(*pool_ai.key(), self.pool);
+        require_keys_eq!(*observation_ai.key(), self.observation);
+        require_keys_eq!(
+            load_raydium_pool_observation_key(pool_ai)?,
+            self.observation,
+            MangoError::InvalidCLMMOracle
+        );
+        let pool = load_raydium_pool_state(pool_ai)?;
+        let observations = load_raydium_observations(observation_ai)?;
+        require_keys_eq!(observations.pool, self.pool, MangoError::InvalidCLMMOracle);
+
+        let (avg_tick, observed_window) = observations.twap_tick(self.twap_window_seconds)?;
+        let price_a_in_b = clmm_tick_to_price(avg_tick);
+        let price = if pool.is_inverted() {
+            1.0 / price_a_in_b
+        } else {
+            price_a_in_b
+        };
+
+        self.price = I80F48::from_num(price);
+        self.observed_window_seconds = observed_window;
+        self.last_update_slot = now_slot;
+        self.last_update_ts = now_ts;
+        Ok(())
+    }
+}
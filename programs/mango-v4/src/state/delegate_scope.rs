@@ -0,0 +1,199 @@
+use anchor_lang::prelude::*;
+
+use derivative::Derivative;
+use fixed::types::I80F48;
+use static_assertions::const_assert_eq;
+use std::mem::size_of;
+
+use crate::error::MangoError;
+use crate::state::*;
+
+/// Actions a scoped delegate may be allowed to take, stored as bits in DelegateScope::permissions.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, AnchorSerialize, AnchorDeserialize)]
+#[repr(u8)]
+pub enum DelegatePermission {
+    /// Place perp orders, including spread, scheduled and conditional orders. Implies PerpCancel.
+    PerpTrade = 0,
+    /// Cancel perp orders and scheduled orders
+    PerpCancel = 1,
+    /// Place serum3 and openbook v2 orders. Implies SpotCancel.
+    SpotTrade = 2,
+    /// Cancel serum3 and openbook v2 orders
+    SpotCancel = 3,
+    /// Create and cancel token conditional swaps
+    TokenConditionalSwap = 4,
+    /// Swap tokens through flash loans
+    Swap = 5,
+    /// Lend and borrow on fixed-term markets
+    FixedTerm = 6,
+    /// Move deposits to other accounts of the same owner
+    TokenTransfer = 7,
+    /// Use the account as a liquidator
+    Liquidate = 8,
+    /// Withdraw tokens to the owner's token accounts
+    Withdraw = 9,
+}
+
+/// Restrictions that apply to a delegate of a MangoAccount.
+///
+/// A scope with permissions == 0 is unrestricted. That's the default for delegates that were
+/// set up before scopes existed.
+#[zero_copy]
+#[derive(AnchorDeserialize, AnchorSerialize, Derivative, PartialEq)]
+#[derivative(Debug)]
+pub struct DelegateScope {
+    /// Bitmask of DelegatePermission values
+    pub permissions: u16,
+
+    /// Perp market the delegate may use, PerpMarketIndex::MAX for any
+    pub perp_market_index: PerpMarketIndex,
+
+    /// Serum3 or openbook v2 market the delegate may use, u16::MAX for any
+    pub spot_market_index: u16,
+
+    #[derivative(Debug = "ignore")]
+    pub padding: [u8; 2],
+
+    /// Maximum size of each order placed by the delegate, in native units of the
+    /// market's quote or settle token. Zero for no limit.
+    ///
+    /// Orders are checked one at a time: this doesn't limit the delegate's total exposure.
+    pub max_notional_per_order: u64,
+}
+const_assert_eq!(size_of::<DelegateScope>(), 2 + 2 + 2 + 2 + 8);
+const_assert_eq!(size_of::<DelegateScope>(), 16);
+const_assert_eq!(size_of::<DelegateScope>() % 8, 0);
+
+impl Default for DelegateScope {
+    fn default() -> Self {
+        Self {
+            permissions: 0,
+            perp_market_index: PerpMarketIndex::MAX,
+            spot_market_index: u16::MAX,
+            padding: Default::default(),
+            max_notional_per_order: 0,
+        }
+    }
+}
+
+impl DelegateScope {
+    /// Only bits for existing DelegatePermission variants may be set
+    pub fn has_valid_permissions(&self) -> bool {
+        self.permissions >> (DelegatePermission::Withdraw as u16 + 1) == 0
+    }
+
+    pub fn is_unrestricted(&self) -> bool {
+        self.permissions == 0
+    }
+
+    /// Whether any market or notional limit is set
+    pub fn has_limits(&self) -> bool {
+        self.perp_market_index != PerpMarketIndex::MAX
+            || self.spot_market_index != u16::MAX
+            || self.max_notional_per_order != 0
+    }
+
+    fn has_bit(&self, permission: DelegatePermission) -> bool {
+        self.permissions & (1 << permission as u16) != 0
+    }
+
+    pub fn allows(&self, permission: DelegatePermission) -> bool {
+        if self.is_unrestricted() || self.has_bit(permission) {
+            return true;
+        }
+        match permission {
+            DelegatePermission::PerpCancel => self.has_bit(DelegatePermission::PerpTrade),
+            DelegatePermission::SpotCancel => self.has_bit(DelegatePermission::SpotTrade),
+            _ => false,
+        }
+    }
+
+    pub fn require_permission(&self, permission: DelegatePermission) -> Result<()> {
+        require_msg_typed!(
+            self.allows(permission),
+            MangoError::DelegatePermissionDenied,
+            "delegate lacks permission {:?}",
+            permission
+        );
+        Ok(())
+    }
+
+    pub fn require_perp_market(&self, perp_market_index: PerpMarketIndex) -> Result<()> {
+        require_msg_typed!(
+            self.is_unrestricted()
+                || self.perp_market_index == PerpMarketIndex::MAX
+                || self.perp_market_index == perp_market_index,
+            MangoError::DelegatePermissionDenied,
+            "delegate may not use perp market {}",
+            perp_market_index
+        );
+        Ok(())
+    }
+
+    pub fn require_spot_market(&self, spot_market_index: u16) -> Result<()> {
+        require_msg_typed!(
+            self.is_unrestricted()
+                || self.spot_market_index == u16::MAX
+                || self.spot_market_index == spot_market_index,
+            MangoError::DelegatePermissionDenied,
+            "delegate may not use spot market {}",
+            spot_market_index
+        );
+        Ok(())
+    }
+
+    pub fn require_order_notional(&self, notional: I80F48) -> Result<()> {
+        require_msg_typed!(
+            self.is_unrestricted()
+                || self.max_notional_per_order == 0
+                || notional <= I80F48::from(self.max_notional_per_order),
+            MangoError::DelegateOrderNotionalExceeded,
+            "order notional {} exceeds the delegate limit {}",
+            notional,
+            self.max_notional_per_order
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delegate_scope() {
+        let unrestricted = DelegateScope::default();
+        assert!(!unrestricted.has_limits());
+        assert!(unrestricted.allows(DelegatePermission::Swap));
+        assert!(unrestricted.require_perp_market(3).is_ok());
+        assert!(unrestricted
+            .require_order_notional(I80F48::from(u64::MAX))
+            .is_ok());
+
+        let scope = DelegateScope {
+            permissions: 1 << DelegatePermission::PerpTrade as u16,
+            perp_market_index: 2,
+            max_notional_per_order: 1000,
+            ..DelegateScope::default()
+        };
+        assert!(scope.has_limits());
+        assert!(scope.allows(DelegatePermission::PerpTrade));
+        assert!(scope.allows(DelegatePermission::PerpCancel));
+        assert!(!scope.allows(DelegatePermission::SpotCancel));
+        assert!(!scope.allows(DelegatePermission::Swap));
+        assert!(scope.require_perp_market(2).is_ok());
+        assert!(scope.require_perp_market(3).is_err());
+        assert!(scope.require_spot_market(7).is_ok());
+        assert!(scope.require_order_notional(I80F48::from(1000)).is_ok());
+        assert!(scope.require_order_notional(I80F48::from(1001)).is_err());
+
+        let cancel_only = DelegateScope {
+            permissions: (1 << DelegatePermission::PerpCancel as u16)
+                | (1 << DelegatePermission::SpotCancel as u16),
+            ..DelegateScope::default()
+        };
+        assert!(cancel_only.allows(DelegatePermission::PerpCancel));
+        assert!(!cancel_only.allows(DelegatePermission::PerpTrade));
+        assert!(!cancel_only.allows(DelegatePermission::SpotTrade));
+    }
+}
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+
+use derivative::Derivative;
+use static_assertions::const_assert_eq;
+use std::mem::size_of;
+
+use crate::error::MangoError;
+use crate::state::*;
+
+/// Number of e-mode categories a group can have. Category ids are 1-based, 0 means none.
+pub const EMODE_CATEGORY_COUNT: usize = 4;
+
+/// Maximal number of tokens in an e-mode category
+pub const EMODE_CATEGORY_MAX_TOKENS: usize = 8;
+
+/// A group of correlated tokens, like SOL and its liquid staking tokens.
+///
+/// Accounts that opted into a category and only have exposure to its tokens use the
+/// category's boosted weights for health, see HealthCache::emode_credit().
+///
+/// The weights are copied to the banks of the tokens, since health computations only
+/// have access to banks.
+#[zero_copy]
+#[derive(Derivative, PartialEq)]
+#[derivative(Debug)]
+pub struct EmodeCategory {
+    /// Tokens in the category, only the first token_count entries are used
+    pub token_indexes: [TokenIndex; EMODE_CATEGORY_MAX_TOKENS],
+    pub token_count: u8,
+
+    #[derivative(Debug = "ignore")]
+    pub padding: [u8; 3],
+
+    pub maint_asset_weight: f32,
+    pub init_asset_weight: f32,
+    pub maint_liab_weight: f32,
+    pub init_liab_weight: f32,
+
+    #[derivative(Debug = "ignore")]
+    pub reserved: [u8; 28],
+}
+const_assert_eq!(size_of::<EmodeCategory>(), 2 * 8 + 1 + 3 + 4 * 4 + 28);
+const_assert_eq!(size_of::<EmodeCategory>(), 64);
+const_assert_eq!(size_of::<EmodeCategory>() % 8, 0);
+
+impl EmodeCategory {
+    pub fn is_empty(&self) -> bool {
+        self.token_count == 0
+    }
+
+    pub fn token_indexes(&self) -> &[TokenIndex] {
+        &self.token_indexes[..self.token_count as usize]
+    }
+
+    pub fn contains(&self, token_index: TokenIndex) -> bool {
+        self.token_indexes().contains(&token_index)
+    }
+
+    pub fn verify(&self) -> Result<()> {
+        require_gte!(EMODE_CATEGORY_MAX_TOKENS, self.token_count as usize);
+        if self.is_empty() {
+            return Ok(());
+        }
+        require_msg_typed!(
+            0.0 <= self.init_asset_weight
+                && self.init_asset_weight <= self.maint_asset_weight
+                && self.maint_asset_weight <= 1.0,
+            MangoError::InvalidEmodeCategory,
+            "asset weights must satisfy 0 <= init <= maint <= 1"
+        );
+        require_msg_typed!(
+            1.0 <= self.maint_liab_weight && self.maint_liab_weight <= self.init_liab_weight,
+            MangoError::InvalidEmodeCategory,
+            "liab weights must satisfy 1 <= maint <= init"
+        );
+        let tokens = self.token_indexes();
+        for (i, token_index) in tokens.iter().enumerate() {
+            require_msg_typed!(
+                !tokens[i + 1..].contains(token_index),
+                MangoError::InvalidEmodeCategory,
+                "duplicate token index {}",
+                token_index
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+use fixed::types::I80F48;
+use static_assertions::const_assert_eq;
+use std::mem::size_of;
+
+use crate::error::*;
+use crate::state::*;
+
+pub type FixedTermMarketIndex = u16;
+
+/// A market for lending or borrowing one token at a fixed rate until a fixed maturity.
+///
+/// Lenders move deposits out of their token position into the market, borrowers take
+/// the lent tokens into their token position. Both sides lock in the amount that is
+/// owed at maturity when they enter, so later rate changes only affect new loans.
+///
+/// After maturity, anyone can settle the positions back into the regular token positions.
+/// Lender interest is funded by borrower interest only, see lend_interest_at_maturity().
+/// The difference between borrower and lender interest stays in the bank vault.
+#[account(zero_copy)]
+#[derive(Debug)]
+pub struct FixedTermMarket {
+    // ABI: Clients rely on this being at offset 8
+    pub group: Pubkey,
+    // ABI: Clients rely on this being at offset 40
+    pub token_index: TokenIndex,
+    // ABI: Clients rely on this being at offset 42
+    pub market_index: FixedTermMarketIndex,
+
+    pub bump: u8,
+    pub reduce_only: u8,
+    pub padding1: [u8; 2],
+
+    pub name: [u8; 16],
+
+    /// Timestamp after which positions can be settled
+    pub maturity_timestamp: u64,
+
+    /// Annualized simple interest rate earned by lenders
+    pub lend_rate: I80F48,
+    /// Annualized simple interest rate paid by borrowers, always >= lend_rate
+    pub borrow_rate: I80F48,
+
+    /// Limit on total_lent_native, to bound how much the bank may owe to lenders
+    pub max_lent_native: u64,
+
+    /// Outstanding principal lent into the market
+    pub total_lent_native: u64,
+    /// Outstanding principal borrowed from the market, always <= total_lent_native
+    pub total_borrowed_native: u64,
+    /// Outstanding amount owed to lenders at maturity
+    pub total_lent_at_maturity_native: u64,
+    /// Outstanding amount owed by borrowers at maturity
+    pub total_borrowed_at_maturity_native: u64,
+
+    pub registration_time: u64,
+
+    /// Interest promised to all lenders, never decreases
+    pub total_lend_interest_native: u64,
+    /// Interest owed by all borrowers, never decreases
+    pub total_borrow_interest_native: u64,
+
+    pub reserved: [u8; 496],
+}
+const_assert_eq!(
+    size_of::<FixedTermMarket>(),
+    32 + 2 + 2 + 1 + 1 + 2 + 16 + 8 + 16 * 2 + 8 * 6 + 8 * 2 + 496
+);
+const_assert_eq!(size_of::<FixedTermMarket>(), 656);
+const_assert_eq!(size_of::<FixedTermMarket>() % 8, 0);
+
+impl FixedTermMarket {
+    pub fn name(&self) -> &str {
+        std::str::from_utf8(&self.name)
+            .unwrap()
+            .trim_matches(char::from(0))
+    }
+
+    pub fn is_reduce_only(&self) -> bool {
+        self.reduce_only == 1
+    }
+
+    pub fn is_matured(&self, now_ts: u64) -> bool {
+        now_ts >= self.maturity_timestamp
+    }
+
+    /// Principal that was lent but not yet borrowed
+    pub fn available_to_borrow_native(&self) -> u64 {
+        self.total_lent_native - self.total_borrowed_native
+    }
+
+    /// Interest credited at maturity to a lender that was promised `promised_interest`.
+    ///
+    /// When borrowers owe less interest than lenders were promised, for example because
+    /// not all lent principal was borrowed, each lender gets the same share of its
+    /// promised interest. No new loans are possible after maturity, so the share is fixed
+    /// when positions are settled.
+    pub fn lend_interest_at_maturity(&self, promised_interest: u64) -> u64 {
+        if self.total_borrow_interest_native >= self.total_lend_interest_native {
+            return promised_interest;
+        }
+        (u128::from(promised_interest) * u128::from(self.total_borrow_interest_native)
+            / u128::from(self.total_lend_interest_native)) as u64
+    }
+
+    /// Amount owed at maturity for `principal` entering the market at `now_ts`.
+    ///
+    /// Uses simple interest from now until maturity. Rounds down for lenders and
+    /// up for borrowers.
+    pub fn amount_at_maturity(&self, principal: u64, now_ts: u64, is_borrow: bool) -> Result<u64> {
+        require_msg_typed!(
+            !self.is_matured(now_ts),
+            MangoError::FixedTermMarketMatured,
+            "fixed term market {} matured at {}",
+            self.market_index,
+            self.maturity_timestamp
+        );
+        let rate = if is_borrow {
+            self.borrow_rate
+        } else {
+            self.lend_rate
+        };
+        let duration = I80F48::from(self.maturity_timestamp - now_ts);
+        let principal = I80F48::from(principal);
+        let amount = principal + principal * rate * duration / YEAR_I80F48;
+        let amount = if is_borrow {
+            amount.ceil()
+        } else {
+            amount.floor()
+        };
+        Ok(amount.to_num::<u64>())
+    }
+}
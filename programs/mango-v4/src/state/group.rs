@@ -2,6 +2,11 @@ use anchor_lang::prelude::*;
 use static_assertions::const_assert_eq;
 use std::mem::size_of;
 
+use super::{
+    EmodeCategory, PerpFeeTier, PerpMarketIndex, PortfolioMarginPair, EMODE_CATEGORY_COUNT,
+    PERP_FEE_TIER_COUNT, PORTFOLIO_MARGIN_PAIR_COUNT,
+};
+
 // TODO: Assuming we allow up to 65536 different tokens
 pub type TokenIndex = u16;
 
@@ -98,7 +103,17 @@ pub struct Group {
     /// Intervals in which collateral fee is applied
     pub collateral_fee_interval: u64,
 
-    pub reserved: [u8; 1800],
+    /// E-mode categories, the category with id i is stored at index i - 1
+    pub emode_categories: [EmodeCategory; EMODE_CATEGORY_COUNT],
+
+    /// Perp fee tiers selected by trailing account volume, the tier with id i is
+    /// stored at index i - 1
+    pub perp_fee_tiers: [PerpFeeTier; PERP_FEE_TIER_COUNT],
+
+    /// Tokens and perp markets with the same underlying, for portfolio margin accounts
+    pub portfolio_margin_pairs: [PortfolioMarginPair; PORTFOLIO_MARGIN_PAIR_COUNT],
+
+    pub reserved: [u8; 1160],
 }
 const_assert_eq!(
     size_of::<Group>(),
@@ -118,7 +133,10 @@ const_assert_eq!(
         + 2 * 2
         + 4
         + 8
-        + 1800
+        + 64 * 4
+        + 32 * 4
+        + 16 * 16
+        + 1160
 );
 const_assert_eq!(size_of::<Group>(), 2736);
 const_assert_eq!(size_of::<Group>() % 8, 0);
@@ -132,6 +150,25 @@ impl Group {
         self.testing == 1
     }
 
+    /// Returns the e-mode category with the given id, or None if it doesn't exist
+    pub fn emode_category(&self, category: u8) -> Option<&EmodeCategory> {
+        let index = (category as usize).checked_sub(1)?;
+        self.emode_categories
+            .get(index)
+            .filter(|category| !category.is_empty())
+    }
+
+    /// Returns the token linked to a perp market for portfolio margining
+    pub fn portfolio_margin_token_index(
+        &self,
+        perp_market_index: PerpMarketIndex,
+    ) -> Option<TokenIndex> {
+        self.portfolio_margin_pairs
+            .iter()
+            .find(|pair| pair.is_active() && pair.perp_market_index == perp_market_index)
+            .map(|pair| pair.token_index)
+    }
+
     pub fn multiple_banks_supported(&self) -> bool {
         self.is_testing() || self.version > 1
     }
@@ -244,6 +281,30 @@ pub enum IxGate {
     SequenceCheck = 73,
     HealthCheck = 74,
     GroupChangeInsuranceFund = 76,
+    FixedTermMarketCreate = 77,
+    FixedTermLend = 78,
+    FixedTermBorrow = 79,
+    FixedTermSettle = 80,
+    PerpScheduledOrderCreate = 81,
+    PerpScheduledOrderCancel = 82,
+    PerpScheduledOrderTrigger = 83,
+    PerpConditionalOrderCreate = 84,
+    PerpPlaceSpreadOrder = 85,
+    AccountSetPortfolioMargin = 86,
+    PerpSetIsolated = 87,
+    PerpIsolatedTransfer = 88,
+    TokenTransfer = 89,
+    AccountEditDelegateScope = 90,
+    OracleAggregatorCreate = 91,
+    OracleAggregatorEdit = 92,
+    OracleAggregatorUpdate = 93,
+    ClmmTwapOracleCreate = 94,
+    ClmmTwapOracleUpdate = 95,
+    OpenbookV2MidOracleCreate = 96,
+    OpenbookV2MidOracleUpdate = 97,
+    AccountSetEmodeCategory = 98,
+    PerpResizeOrderbook = 99,
+    PerpClearOpeningAuction = 100,
     // NOTE: Adding new variants requires matching changes in ts and the ix_gate_set instruction.
 }
 
@@ -11,14 +11,18 @@ use fixed::types::I80F48;
 use solana_program::program_memory::sol_memmove;
 use static_assertions::const_assert_eq;
 
+use crate::accounts_zerocopy::*;
 use crate::error::*;
 use crate::health::{HealthCache, HealthType};
+use crate::i80f48::ClampToInt;
 use crate::logs::{emit_stack, DeactivatePerpPositionLog, DeactivateTokenPositionLog};
 use crate::util;
 
+use super::Bank;
 use super::BookSideOrderTree;
 use super::FillEvent;
 use super::LeafNode;
+use super::OpenbookV2MarketIndex;
 use super::PerpMarket;
 use super::PerpMarketIndex;
 use super::PerpOpenOrder;
@@ -27,7 +31,9 @@ use super::TokenConditionalSwap;
 use super::TokenIndex;
 use super::FREE_ORDER_SLOT;
 use super::{dynamic_account::*, Group};
-use super::{PerpPosition, Serum3Orders, TokenPosition};
+use super::{perp_fee_tier_for_volume, PerpFeeTier, PERP_FEE_TIER_VOLUME_WINDOW};
+use super::{DelegatePermission, DelegateScope};
+use super::{OpenbookV2Orders, PerpPosition, Serum3Orders, TokenPosition};
 use super::{Side, SideAndOrderTree};
 
 type BorshVecLength = u32;
@@ -79,9 +85,16 @@ impl MangoAccountPdaSeeds {
 //       Users will migrate to this version when resizing their accounts. Also the
 //       AccountSizeMigration instruction was used to bring all accounts to
 //       this version after v0.20.0 was deployed.
+// - v4: Introduced in v0.25.0 to add openbook v2 open orders after the token conditional swaps.
+//       Older accounts read as having zero openbook v2 slots. Resizing the account (or calling
+//       AccountSizeMigration) brings them to this version.
+// - v5: Introduced in v0.25.0 to add fixed term lending positions after the openbook v2 open
+//       orders. Works like v4: older accounts read as having zero fixed term slots.
+// - v6: Introduced in v0.25.0 to add perp scheduled orders after the fixed term positions.
+//       Works like v4: older accounts read as having zero scheduled order slots.
 //
 // Version v0.22.0 drops idl support for v1 and v2 accounts by extending the MangoAccount idl with the
-// new fields.
+// new fields. Similarly, v0.25.0 drops idl support for v3 accounts.
 //
 // When not reading via idl, MangoAccount binary data is backwards compatible: when ignoring trailing bytes,
 // a v2 account can be read as a v1 account and a v3 account can be read as v1 or v2 etc.
@@ -156,8 +169,35 @@ pub struct MangoAccount {
     /// Time at which the last collateral fee was charged
     pub last_collateral_fee_charge: u64,
 
+    /// Next id to use when adding a perp scheduled order
+    pub next_perp_scheduled_order_id: u64,
+
+    /// Opt-in portfolio margining: spot balances and perp positions on the same
+    /// underlying (see PerpMarket::portfolio_margin_token_index) are netted for health.
+    pub portfolio_margin: u8,
+
+    /// Opt-in e-mode category, zero means none, see Group::emode_categories
+    pub emode_category: u8,
+
     #[derivative(Debug = "ignore")]
-    pub reserved: [u8; 152],
+    pub padding12: [u8; 6],
+
+    /// Restrictions on what the delegate may do, see DelegateScope
+    pub delegate_scope: DelegateScope,
+
+    /// Restrictions on what the temporary delegate may do
+    pub temporary_delegate_scope: DelegateScope,
+
+    /// Perp volume (quote native) in the current fee tier volume window.
+    /// Used for selecting the account's perp fee tier, see Group::perp_fee_tiers.
+    pub perp_fee_volume_current: u64,
+    /// Perp volume (quote native) in the previous fee tier volume window.
+    pub perp_fee_volume_previous: u64,
+    /// End timestamp of the current fee tier volume window.
+    pub perp_fee_volume_window_end: u64,
+
+    #[derivative(Debug = "ignore")]
+    pub reserved: [u8; 80],
 
     // dynamic
     pub header_version: u8,
@@ -183,6 +223,17 @@ pub struct MangoAccount {
     #[derivative(Debug = "ignore")]
     pub padding8: u32,
     pub token_conditional_swaps: Vec<TokenConditionalSwap>,
+    #[derivative(Debug = "ignore")]
+    pub padding9: u32,
+    // Maps openbook_v2_market_index -> open orders for each openbook v2 market
+    // that is active on this MangoAccount.
+    pub openbook_v2: Vec<OpenbookV2Orders>,
+    #[derivative(Debug = "ignore")]
+    pub padding10: u32,
+    pub fixed_term_positions: Vec<FixedTermPosition>,
+    #[derivative(Debug = "ignore")]
+    pub padding11: u32,
+    pub perp_scheduled_orders: Vec<PerpScheduledOrder>,
 
     #[derivative(Debug = "ignore")]
     pub reserved_dynamic: [u8; 64],
@@ -211,7 +262,16 @@ impl MangoAccount {
             temporary_delegate: Pubkey::default(),
             temporary_delegate_expiry: 0,
             last_collateral_fee_charge: 0,
-            reserved: [0; 152],
+            next_perp_scheduled_order_id: 0,
+            portfolio_margin: 0,
+            emode_category: 0,
+            padding12: Default::default(),
+            delegate_scope: DelegateScope::default(),
+            temporary_delegate_scope: DelegateScope::default(),
+            perp_fee_volume_current: 0,
+            perp_fee_volume_previous: 0,
+            perp_fee_volume_window_end: 0,
+            reserved: [0; 80],
             header_version: DEFAULT_MANGO_ACCOUNT_VERSION,
             padding3: Default::default(),
             padding4: Default::default(),
@@ -224,17 +284,27 @@ impl MangoAccount {
             perp_open_orders: vec![PerpOpenOrder::default(); 6],
             padding8: Default::default(),
             token_conditional_swaps: vec![TokenConditionalSwap::default(); 2],
+            padding9: Default::default(),
+            openbook_v2: vec![OpenbookV2Orders::default(); 5],
+            padding10: Default::default(),
+            fixed_term_positions: vec![FixedTermPosition::default(); 2],
+            padding11: Default::default(),
+            perp_scheduled_orders: vec![PerpScheduledOrder::default(); 2],
             reserved_dynamic: [0; 64],
         }
     }
 
     /// Number of bytes needed for the MangoAccount, including the discriminator
+    #[allow(clippy::too_many_arguments)]
     pub fn space(
         token_count: u8,
         serum3_count: u8,
         perp_count: u8,
         perp_oo_count: u8,
         token_conditional_swap_count: u8,
+        openbook_v2_count: u8,
+        fixed_term_count: u8,
+        perp_scheduled_order_count: u8,
     ) -> usize {
         8 + size_of::<MangoAccountFixed>()
             + Self::dynamic_size(
@@ -243,6 +313,9 @@ impl MangoAccount {
                 perp_count,
                 perp_oo_count,
                 token_conditional_swap_count,
+                openbook_v2_count,
+                fixed_term_count,
+                perp_scheduled_order_count,
             )
     }
 
@@ -280,7 +353,7 @@ impl MangoAccount {
             + BORSH_VEC_PADDING_BYTES
     }
 
-    pub fn dynamic_reserved_bytes_offset(
+    pub fn dynamic_openbook_v2_vec_offset(
         token_count: u8,
         serum3_count: u8,
         perp_count: u8,
@@ -294,14 +367,80 @@ impl MangoAccount {
             perp_oo_count,
         ) + (BORSH_VEC_SIZE_BYTES
             + size_of::<TokenConditionalSwap>() * usize::from(token_conditional_swap_count))
+            + BORSH_VEC_PADDING_BYTES
+    }
+
+    pub fn dynamic_fixed_term_vec_offset(
+        token_count: u8,
+        serum3_count: u8,
+        perp_count: u8,
+        perp_oo_count: u8,
+        token_conditional_swap_count: u8,
+        openbook_v2_count: u8,
+    ) -> usize {
+        Self::dynamic_openbook_v2_vec_offset(
+            token_count,
+            serum3_count,
+            perp_count,
+            perp_oo_count,
+            token_conditional_swap_count,
+        ) + (BORSH_VEC_SIZE_BYTES + size_of::<OpenbookV2Orders>() * usize::from(openbook_v2_count))
+            + BORSH_VEC_PADDING_BYTES
+    }
+
+    pub fn dynamic_perp_scheduled_order_vec_offset(
+        token_count: u8,
+        serum3_count: u8,
+        perp_count: u8,
+        perp_oo_count: u8,
+        token_conditional_swap_count: u8,
+        openbook_v2_count: u8,
+        fixed_term_count: u8,
+    ) -> usize {
+        Self::dynamic_fixed_term_vec_offset(
+            token_count,
+            serum3_count,
+            perp_count,
+            perp_oo_count,
+            token_conditional_swap_count,
+            openbook_v2_count,
+        ) + (BORSH_VEC_SIZE_BYTES + size_of::<FixedTermPosition>() * usize::from(fixed_term_count))
+            + BORSH_VEC_PADDING_BYTES
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn dynamic_reserved_bytes_offset(
+        token_count: u8,
+        serum3_count: u8,
+        perp_count: u8,
+        perp_oo_count: u8,
+        token_conditional_swap_count: u8,
+        openbook_v2_count: u8,
+        fixed_term_count: u8,
+        perp_scheduled_order_count: u8,
+    ) -> usize {
+        Self::dynamic_perp_scheduled_order_vec_offset(
+            token_count,
+            serum3_count,
+            perp_count,
+            perp_oo_count,
+            token_conditional_swap_count,
+            openbook_v2_count,
+            fixed_term_count,
+        ) + (BORSH_VEC_SIZE_BYTES
+            + size_of::<PerpScheduledOrder>() * usize::from(perp_scheduled_order_count))
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn dynamic_size(
         token_count: u8,
         serum3_count: u8,
         perp_count: u8,
         perp_oo_count: u8,
         token_conditional_swap_count: u8,
+        openbook_v2_count: u8,
+        fixed_term_count: u8,
+        perp_scheduled_order_count: u8,
     ) -> usize {
         Self::dynamic_reserved_bytes_offset(
             token_count,
@@ -309,6 +448,9 @@ impl MangoAccount {
             perp_count,
             perp_oo_count,
             token_conditional_swap_count,
+            openbook_v2_count,
+            fixed_term_count,
+            perp_scheduled_order_count,
         ) + DYNAMIC_RESERVED_BYTES
     }
 }
@@ -336,11 +478,20 @@ pub struct MangoAccountFixed {
     pub temporary_delegate: Pubkey,
     pub temporary_delegate_expiry: u64,
     pub last_collateral_fee_charge: u64,
-    pub reserved: [u8; 152],
+    pub next_perp_scheduled_order_id: u64,
+    portfolio_margin: u8,
+    pub emode_category: u8,
+    pub padding12: [u8; 6],
+    pub delegate_scope: DelegateScope,
+    pub temporary_delegate_scope: DelegateScope,
+    pub perp_fee_volume_current: u64,
+    pub perp_fee_volume_previous: u64,
+    pub perp_fee_volume_window_end: u64,
+    pub reserved: [u8; 80],
 }
 const_assert_eq!(
     size_of::<MangoAccountFixed>(),
-    32 * 4 + 8 + 8 * 8 + 32 + 8 + 8 + 152
+    32 * 4 + 8 + 8 * 8 + 32 + 8 + 8 + 8 + 1 + 1 + 6 + 16 + 16 + 3 * 8 + 80
 );
 const_assert_eq!(size_of::<MangoAccountFixed>(), 400);
 const_assert_eq!(size_of::<MangoAccountFixed>() % 8, 0);
@@ -374,6 +525,60 @@ impl MangoAccountFixed {
         self.temporary_delegate == ix_signer
     }
 
+    /// Scope restrictions for the signer, None if the signer is the owner.
+    ///
+    /// Only call this after is_owner_or_delegate() has been checked.
+    pub fn delegate_scope(&self, ix_signer: Pubkey) -> Option<&DelegateScope> {
+        if self.owner == ix_signer {
+            None
+        } else if self.delegate == ix_signer {
+            Some(&self.delegate_scope)
+        } else {
+            Some(&self.temporary_delegate_scope)
+        }
+    }
+
+    pub fn check_delegate_permission(
+        &self,
+        ix_signer: Pubkey,
+        permission: DelegatePermission,
+    ) -> Result<()> {
+        match self.delegate_scope(ix_signer) {
+            Some(scope) => scope.require_permission(permission),
+            None => Ok(()),
+        }
+    }
+
+    pub fn check_delegate_perp_market(
+        &self,
+        ix_signer: Pubkey,
+        perp_market_index: PerpMarketIndex,
+    ) -> Result<()> {
+        match self.delegate_scope(ix_signer) {
+            Some(scope) => scope.require_perp_market(perp_market_index),
+            None => Ok(()),
+        }
+    }
+
+    pub fn check_delegate_spot_market(
+        &self,
+        ix_signer: Pubkey,
+        spot_market_index: u16,
+    ) -> Result<()> {
+        match self.delegate_scope(ix_signer) {
+            Some(scope) => scope.require_spot_market(spot_market_index),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks a new order against the delegate's per-order notional limit
+    pub fn check_delegate_order_notional(&self, ix_signer: Pubkey, notional: I80F48) -> Result<()> {
+        match self.delegate_scope(ix_signer) {
+            Some(scope) => scope.require_order_notional(notional),
+            None => Ok(()),
+        }
+    }
+
     pub fn being_liquidated(&self) -> bool {
         self.being_liquidated == 1
     }
@@ -390,6 +595,14 @@ impl MangoAccountFixed {
         self.in_health_region = u8::from(b);
     }
 
+    pub fn is_portfolio_margin(&self) -> bool {
+        self.portfolio_margin == 1
+    }
+
+    pub fn set_portfolio_margin(&mut self, b: bool) {
+        self.portfolio_margin = u8::from(b);
+    }
+
     pub fn maybe_recover_from_being_liquidated(&mut self, liq_end_health: I80F48) -> bool {
         // This is used as threshold to flip flag instead of 0 because of dust issues
         let one_native_usdc = I80F48::ONE;
@@ -429,6 +642,52 @@ impl MangoAccountFixed {
             self.buyback_fees_accrued_current.saturating_add(amount);
     }
 
+    /// Updates the perp_fee_volume_* fields when a new volume window starts.
+    pub fn expire_perp_fee_volume(&mut self, now_ts: u64) {
+        let interval = PERP_FEE_TIER_VOLUME_WINDOW;
+        if now_ts < self.perp_fee_volume_window_end {
+            return;
+        } else if now_ts < self.perp_fee_volume_window_end + interval {
+            self.perp_fee_volume_previous = self.perp_fee_volume_current;
+        } else {
+            self.perp_fee_volume_previous = 0;
+        }
+        self.perp_fee_volume_current = 0;
+        self.perp_fee_volume_window_end = (now_ts / interval + 1) * interval;
+    }
+
+    /// Add perp volume that counts towards the account's perp fee tier.
+    pub fn record_perp_fee_volume(&mut self, now_ts: u64, volume_quote: u64) {
+        self.expire_perp_fee_volume(now_ts);
+        self.perp_fee_volume_current = self.perp_fee_volume_current.saturating_add(volume_quote);
+    }
+
+    /// Estimate of the perp volume over the last PERP_FEE_TIER_VOLUME_WINDOW seconds.
+    ///
+    /// The volume of the previous window is weighted by the fraction of it that is
+    /// still within the trailing window.
+    pub fn trailing_perp_fee_volume(&self, now_ts: u64) -> u64 {
+        let interval = PERP_FEE_TIER_VOLUME_WINDOW;
+        let weighted = |volume: u64, elapsed: u64| {
+            (volume as u128 * interval.saturating_sub(elapsed) as u128 / interval as u128) as u64
+        };
+        if now_ts >= self.perp_fee_volume_window_end {
+            // the current window is over and would become the previous window
+            let elapsed = now_ts - self.perp_fee_volume_window_end;
+            weighted(self.perp_fee_volume_current, elapsed)
+        } else {
+            let window_start = self.perp_fee_volume_window_end.saturating_sub(interval);
+            let elapsed = now_ts.saturating_sub(window_start);
+            self.perp_fee_volume_current
+                .saturating_add(weighted(self.perp_fee_volume_previous, elapsed))
+        }
+    }
+
+    /// The perp fee tier id that applies to the account, 0 if none.
+    pub fn perp_fee_tier(&self, tiers: &[PerpFeeTier], now_ts: u64) -> u8 {
+        perp_fee_tier_for_volume(tiers, self.trailing_perp_fee_volume(now_ts))
+    }
+
     /// Reduce the available buyback fees amount because it was used up.
     ///
     /// Panics if `amount` exceeds the available accrued amount
@@ -472,6 +731,9 @@ pub struct MangoAccountDynamicHeader {
     pub perp_count: u8,
     pub perp_oo_count: u8,
     pub token_conditional_swap_count: u8,
+    pub openbook_v2_count: u8,
+    pub fixed_term_count: u8,
+    pub perp_scheduled_order_count: u8,
 }
 
 impl DynamicHeader for MangoAccountDynamicHeader {
@@ -528,12 +790,83 @@ impl DynamicHeader for MangoAccountDynamicHeader {
                     0
                 };
 
+                // Accounts from before v4 have zeroed reserved bytes where the openbook v2
+                // vec length would be, leading to a zero count.
+                let openbook_v2_vec_offset = MangoAccount::dynamic_openbook_v2_vec_offset(
+                    token_count,
+                    serum3_count,
+                    perp_count,
+                    perp_oo_count,
+                    token_conditional_swap_count,
+                );
+                let openbook_v2_count =
+                    if dynamic_data.len() > openbook_v2_vec_offset + BORSH_VEC_SIZE_BYTES {
+                        u8::try_from(BorshVecLength::from_le_bytes(*array_ref![
+                            dynamic_data,
+                            openbook_v2_vec_offset,
+                            BORSH_VEC_SIZE_BYTES
+                        ]))
+                        .unwrap()
+                    } else {
+                        0
+                    };
+
+                // Accounts from before v5 have zeroed reserved bytes where the fixed term
+                // vec length would be, same as for openbook v2 above.
+                let fixed_term_vec_offset = MangoAccount::dynamic_fixed_term_vec_offset(
+                    token_count,
+                    serum3_count,
+                    perp_count,
+                    perp_oo_count,
+                    token_conditional_swap_count,
+                    openbook_v2_count,
+                );
+                let fixed_term_count =
+                    if dynamic_data.len() > fixed_term_vec_offset + BORSH_VEC_SIZE_BYTES {
+                        u8::try_from(BorshVecLength::from_le_bytes(*array_ref![
+                            dynamic_data,
+                            fixed_term_vec_offset,
+                            BORSH_VEC_SIZE_BYTES
+                        ]))
+                        .unwrap()
+                    } else {
+                        0
+                    };
+
+                // Accounts from before v6 have zeroed reserved bytes where the scheduled
+                // order vec length would be, same as for openbook v2 above.
+                let perp_scheduled_order_vec_offset =
+                    MangoAccount::dynamic_perp_scheduled_order_vec_offset(
+                        token_count,
+                        serum3_count,
+                        perp_count,
+                        perp_oo_count,
+                        token_conditional_swap_count,
+                        openbook_v2_count,
+                        fixed_term_count,
+                    );
+                let perp_scheduled_order_count = if dynamic_data.len()
+                    > perp_scheduled_order_vec_offset + BORSH_VEC_SIZE_BYTES
+                {
+                    u8::try_from(BorshVecLength::from_le_bytes(*array_ref![
+                        dynamic_data,
+                        perp_scheduled_order_vec_offset,
+                        BORSH_VEC_SIZE_BYTES
+                    ]))
+                    .unwrap()
+                } else {
+                    0
+                };
+
                 Ok(Self {
                     token_count,
                     serum3_count,
                     perp_count,
                     perp_oo_count,
                     token_conditional_swap_count,
+                    openbook_v2_count,
+                    fixed_term_count,
+                    perp_scheduled_order_count,
                 })
             }
             _ => err!(MangoError::NotImplementedError).context("unexpected header version number"),
@@ -563,6 +896,9 @@ impl MangoAccountDynamicHeader {
             self.perp_count,
             self.perp_oo_count,
             self.token_conditional_swap_count,
+            self.openbook_v2_count,
+            self.fixed_term_count,
+            self.perp_scheduled_order_count,
         )
     }
 
@@ -608,6 +944,45 @@ impl MangoAccountDynamicHeader {
             + raw_index * size_of::<TokenConditionalSwap>()
     }
 
+    // offset into dynamic data where 1st OpenbookV2Orders would be found
+    fn openbook_v2_offset(&self, raw_index: usize) -> usize {
+        MangoAccount::dynamic_openbook_v2_vec_offset(
+            self.token_count,
+            self.serum3_count,
+            self.perp_count,
+            self.perp_oo_count,
+            self.token_conditional_swap_count,
+        ) + BORSH_VEC_SIZE_BYTES
+            + raw_index * size_of::<OpenbookV2Orders>()
+    }
+
+    // offset into dynamic data where 1st FixedTermPosition would be found
+    fn fixed_term_offset(&self, raw_index: usize) -> usize {
+        MangoAccount::dynamic_fixed_term_vec_offset(
+            self.token_count,
+            self.serum3_count,
+            self.perp_count,
+            self.perp_oo_count,
+            self.token_conditional_swap_count,
+            self.openbook_v2_count,
+        ) + BORSH_VEC_SIZE_BYTES
+            + raw_index * size_of::<FixedTermPosition>()
+    }
+
+    // offset into dynamic data where 1st PerpScheduledOrder would be found
+    fn perp_scheduled_order_offset(&self, raw_index: usize) -> usize {
+        MangoAccount::dynamic_perp_scheduled_order_vec_offset(
+            self.token_count,
+            self.serum3_count,
+            self.perp_count,
+            self.perp_oo_count,
+            self.token_conditional_swap_count,
+            self.openbook_v2_count,
+            self.fixed_term_count,
+        ) + BORSH_VEC_SIZE_BYTES
+            + raw_index * size_of::<PerpScheduledOrder>()
+    }
+
     fn reserved_bytes_offset(&self) -> usize {
         MangoAccount::dynamic_reserved_bytes_offset(
             self.token_count,
@@ -615,6 +990,9 @@ impl MangoAccountDynamicHeader {
             self.perp_count,
             self.perp_oo_count,
             self.token_conditional_swap_count,
+            self.openbook_v2_count,
+            self.fixed_term_count,
+            self.perp_scheduled_order_count,
         )
     }
 
@@ -633,6 +1011,15 @@ impl MangoAccountDynamicHeader {
     pub fn token_conditional_swap_count(&self) -> usize {
         self.token_conditional_swap_count.into()
     }
+    pub fn openbook_v2_count(&self) -> usize {
+        self.openbook_v2_count.into()
+    }
+    pub fn fixed_term_count(&self) -> usize {
+        self.fixed_term_count.into()
+    }
+    pub fn perp_scheduled_order_count(&self) -> usize {
+        self.perp_scheduled_order_count.into()
+    }
 
     pub fn zero() -> Self {
         Self {
@@ -641,11 +1028,17 @@ impl MangoAccountDynamicHeader {
             perp_count: 0,
             perp_oo_count: 0,
             token_conditional_swap_count: 0,
+            openbook_v2_count: 0,
+            fixed_term_count: 0,
+            perp_scheduled_order_count: 0,
         }
     }
 
     pub fn expected_health_accounts(&self) -> usize {
-        self.token_count() * 2 + self.serum3_count() + self.perp_count() * 2
+        self.token_count() * 2
+            + self.serum3_count()
+            + self.perp_count() * 2
+            + self.openbook_v2_count()
     }
 
     pub fn max_health_accounts() -> usize {
@@ -656,7 +1049,7 @@ impl MangoAccountDynamicHeader {
     ///
     /// - Check that the total health accounts stay limited
     ///   (this coverers token, perp, serum position limits)
-    /// - Check that if perp oo/tcs size increases, it is bounded by the limits
+    /// - Check that if perp oo/tcs/fixed term/scheduled order size increases, it is bounded by the limits
     /// - If a field doesn't change, don't error if it exceeds the limits
     ///   (might have been expanded earlier when it was valid to do)
     pub fn check_resize_from(&self, prev: &Self) -> Result<()> {
@@ -674,6 +1067,14 @@ impl MangoAccountDynamicHeader {
             require_gte!(64, self.token_conditional_swap_count);
         }
 
+        if self.fixed_term_count > prev.fixed_term_count {
+            require_gte!(16, self.fixed_term_count);
+        }
+
+        if self.perp_scheduled_order_count > prev.perp_scheduled_order_count {
+            require_gte!(16, self.perp_scheduled_order_count);
+        }
+
         Ok(())
     }
 }
@@ -819,6 +1220,112 @@ impl<
             .filter(|serum3_order| serum3_order.is_active())
     }
 
+    pub fn openbook_v2_orders(
+        &self,
+        market_index: OpenbookV2MarketIndex,
+    ) -> Result<&OpenbookV2Orders> {
+        self.all_openbook_v2_orders()
+            .find(|p| p.is_active_for_market(market_index))
+            .ok_or_else(|| {
+                error_msg!(
+                    "openbook v2 orders for market index {} not found",
+                    market_index
+                )
+            })
+    }
+
+    pub(crate) fn openbook_v2_orders_by_raw_index_unchecked(
+        &self,
+        raw_index: usize,
+    ) -> &OpenbookV2Orders {
+        get_helper(self.dynamic(), self.header().openbook_v2_offset(raw_index))
+    }
+
+    pub fn openbook_v2_orders_by_raw_index(&self, raw_index: usize) -> Result<&OpenbookV2Orders> {
+        require_gt!(self.header().openbook_v2_count(), raw_index);
+        Ok(self.openbook_v2_orders_by_raw_index_unchecked(raw_index))
+    }
+
+    pub fn all_openbook_v2_orders(&self) -> impl Iterator<Item = &OpenbookV2Orders> + '_ {
+        (0..self.header().openbook_v2_count())
+            .map(|i| self.openbook_v2_orders_by_raw_index_unchecked(i))
+    }
+
+    pub fn active_openbook_v2_orders(&self) -> impl Iterator<Item = &OpenbookV2Orders> + '_ {
+        self.all_openbook_v2_orders().filter(|oo| oo.is_active())
+    }
+
+    pub fn fixed_term_position(
+        &self,
+        market_index: FixedTermMarketIndex,
+    ) -> Result<&FixedTermPosition> {
+        self.all_fixed_term_positions()
+            .find(|p| p.is_active_for_market(market_index))
+            .ok_or_else(|| {
+                error_msg!(
+                    "fixed term position for market index {} not found",
+                    market_index
+                )
+            })
+    }
+
+    pub(crate) fn fixed_term_position_by_raw_index_unchecked(
+        &self,
+        raw_index: usize,
+    ) -> &FixedTermPosition {
+        get_helper(self.dynamic(), self.header().fixed_term_offset(raw_index))
+    }
+
+    pub fn fixed_term_position_by_raw_index(&self, raw_index: usize) -> Result<&FixedTermPosition> {
+        require_gt!(self.header().fixed_term_count(), raw_index);
+        Ok(self.fixed_term_position_by_raw_index_unchecked(raw_index))
+    }
+
+    pub fn all_fixed_term_positions(&self) -> impl Iterator<Item = &FixedTermPosition> + '_ {
+        (0..self.header().fixed_term_count())
+            .map(|i| self.fixed_term_position_by_raw_index_unchecked(i))
+    }
+
+    pub fn active_fixed_term_positions(&self) -> impl Iterator<Item = &FixedTermPosition> + '_ {
+        self.all_fixed_term_positions().filter(|p| p.is_active())
+    }
+
+    fn perp_scheduled_order_by_index_unchecked(&self, index: usize) -> &PerpScheduledOrder {
+        get_helper(
+            self.dynamic(),
+            self.header().perp_scheduled_order_offset(index),
+        )
+    }
+
+    pub fn perp_scheduled_order_by_index(&self, index: usize) -> Result<&PerpScheduledOrder> {
+        require_gt!(self.header().perp_scheduled_order_count(), index);
+        Ok(self.perp_scheduled_order_by_index_unchecked(index))
+    }
+
+    pub fn perp_scheduled_order_by_id(&self, id: u64) -> Result<(usize, &PerpScheduledOrder)> {
+        let index = self
+            .all_perp_scheduled_orders()
+            .position(|so| so.is_configured() && so.id == id)
+            .ok_or_else(|| error_msg!("perp scheduled order with id {} not found", id))?;
+        Ok((index, self.perp_scheduled_order_by_index_unchecked(index)))
+    }
+
+    pub fn all_perp_scheduled_orders(&self) -> impl Iterator<Item = &PerpScheduledOrder> {
+        (0..self.header().perp_scheduled_order_count())
+            .map(|i| self.perp_scheduled_order_by_index_unchecked(i))
+    }
+
+    pub fn active_perp_scheduled_orders(&self) -> impl Iterator<Item = &PerpScheduledOrder> {
+        self.all_perp_scheduled_orders()
+            .filter(|so| so.is_configured())
+    }
+
+    pub fn perp_scheduled_order_free_index(&self) -> Result<usize> {
+        self.all_perp_scheduled_orders()
+            .position(|so| !so.is_configured())
+            .ok_or_else(|| error_msg!("no free perp scheduled order index"))
+    }
+
     pub fn perp_position(&self, market_index: PerpMarketIndex) -> Result<&PerpPosition> {
         self.all_perp_positions()
             .find(|p| p.is_active_for_market(market_index))
@@ -1014,8 +1521,9 @@ impl<
                     cumulative_deposit_interest: 0.0,
                     cumulative_borrow_interest: 0.0,
                     previous_index: I80F48::ZERO,
+                    isolated_debt_quote: 0,
                     padding: Default::default(),
-                    reserved: [0; 128],
+                    reserved: [0; 120],
                 };
             }
             Ok((v, raw_index, bank_index))
@@ -1121,6 +1629,133 @@ impl<
             .ok_or_else(|| error_msg!("serum3 orders for market index {} not found", market_index))
     }
 
+    // get mut OpenbookV2Orders at raw_index
+    pub fn openbook_v2_orders_mut_by_raw_index(
+        &mut self,
+        raw_index: usize,
+    ) -> &mut OpenbookV2Orders {
+        let offset = self.header().openbook_v2_offset(raw_index);
+        get_helper_mut(self.dynamic_mut(), offset)
+    }
+
+    pub fn create_openbook_v2_orders(
+        &mut self,
+        market_index: OpenbookV2MarketIndex,
+    ) -> Result<&mut OpenbookV2Orders> {
+        if self.openbook_v2_orders(market_index).is_ok() {
+            return err!(MangoError::OpenbookV2OpenOrdersExistAlready);
+        }
+
+        let raw_index_opt = self.all_openbook_v2_orders().position(|p| !p.is_active());
+        if let Some(raw_index) = raw_index_opt {
+            *(self.openbook_v2_orders_mut_by_raw_index(raw_index)) = OpenbookV2Orders {
+                market_index,
+                ..OpenbookV2Orders::default()
+            };
+            Ok(self.openbook_v2_orders_mut_by_raw_index(raw_index))
+        } else {
+            err!(MangoError::NoFreeOpenbookV2OpenOrdersIndex)
+        }
+    }
+
+    pub fn deactivate_openbook_v2_orders(
+        &mut self,
+        market_index: OpenbookV2MarketIndex,
+    ) -> Result<()> {
+        let raw_index = self
+            .all_openbook_v2_orders()
+            .position(|p| p.is_active_for_market(market_index))
+            .ok_or_else(|| {
+                error_msg!("openbook v2 open orders index {} not found", market_index)
+            })?;
+        self.openbook_v2_orders_mut_by_raw_index(raw_index)
+            .market_index = OpenbookV2MarketIndex::MAX;
+        Ok(())
+    }
+
+    pub fn openbook_v2_orders_mut(
+        &mut self,
+        market_index: OpenbookV2MarketIndex,
+    ) -> Result<&mut OpenbookV2Orders> {
+        let raw_index_opt = self
+            .all_openbook_v2_orders()
+            .position(|p| p.is_active_for_market(market_index));
+        raw_index_opt
+            .map(|raw_index| self.openbook_v2_orders_mut_by_raw_index(raw_index))
+            .ok_or_else(|| {
+                error_msg!(
+                    "openbook v2 orders for market index {} not found",
+                    market_index
+                )
+            })
+    }
+
+    // get mut FixedTermPosition at raw_index
+    pub fn fixed_term_position_mut_by_raw_index(
+        &mut self,
+        raw_index: usize,
+    ) -> &mut FixedTermPosition {
+        let offset = self.header().fixed_term_offset(raw_index);
+        get_helper_mut(self.dynamic_mut(), offset)
+    }
+
+    /// Returns the position for the market, creating it if needed
+    ///
+    /// The bool is true if the position was newly created.
+    pub fn ensure_fixed_term_position(
+        &mut self,
+        market_index: FixedTermMarketIndex,
+        token_index: TokenIndex,
+    ) -> Result<(&mut FixedTermPosition, bool)> {
+        if let Some(raw_index) = self
+            .all_fixed_term_positions()
+            .position(|p| p.is_active_for_market(market_index))
+        {
+            return Ok((self.fixed_term_position_mut_by_raw_index(raw_index), false));
+        }
+
+        let raw_index_opt = self.all_fixed_term_positions().position(|p| !p.is_active());
+        if let Some(raw_index) = raw_index_opt {
+            *(self.fixed_term_position_mut_by_raw_index(raw_index)) = FixedTermPosition {
+                market_index,
+                token_index,
+                ..FixedTermPosition::default()
+            };
+            Ok((self.fixed_term_position_mut_by_raw_index(raw_index), true))
+        } else {
+            err!(MangoError::NoFreeFixedTermPositionIndex)
+        }
+    }
+
+    pub fn deactivate_fixed_term_position(
+        &mut self,
+        market_index: FixedTermMarketIndex,
+    ) -> Result<()> {
+        let raw_index = self
+            .all_fixed_term_positions()
+            .position(|p| p.is_active_for_market(market_index))
+            .ok_or_else(|| error_msg!("fixed term position index {} not found", market_index))?;
+        *self.fixed_term_position_mut_by_raw_index(raw_index) = FixedTermPosition::default();
+        Ok(())
+    }
+
+    pub fn fixed_term_position_mut(
+        &mut self,
+        market_index: FixedTermMarketIndex,
+    ) -> Result<&mut FixedTermPosition> {
+        let raw_index_opt = self
+            .all_fixed_term_positions()
+            .position(|p| p.is_active_for_market(market_index));
+        raw_index_opt
+            .map(|raw_index| self.fixed_term_position_mut_by_raw_index(raw_index))
+            .ok_or_else(|| {
+                error_msg!(
+                    "fixed term position for market index {} not found",
+                    market_index
+                )
+            })
+    }
+
     // get mut PerpPosition at raw_index
     pub fn perp_position_mut_by_raw_index(&mut self, raw_index: usize) -> &mut PerpPosition {
         let offset = self.header().perp_offset(raw_index);
@@ -1266,6 +1901,22 @@ impl<
         Ok(())
     }
 
+    /// Reduces the quantity of an active perp order and updates the maker bids/asks tracking
+    pub fn reduce_perp_order_quantity(&mut self, slot: usize, quantity: i64) -> Result<()> {
+        let oo = self.perp_order_by_raw_index(slot)?;
+        require_neq!(oo.market, FREE_ORDER_SLOT);
+        let perp_market_index = oo.market;
+        let order_side = oo.side_and_tree().side();
+
+        let perp_account = self.perp_position_mut(perp_market_index)?;
+        perp_account.adjust_maker_lots(order_side, -quantity);
+
+        let oo = self.perp_order_mut_by_raw_index(slot);
+        oo.quantity = (oo.quantity - quantity).max(0);
+
+        Ok(())
+    }
+
     /// Returns amount of realized trade pnl for the maker
     pub fn execute_perp_maker(
         &mut self,
@@ -1284,6 +1935,11 @@ impl<
             f.expire_buyback_fees(now_ts, group.buyback_fees_expiry_interval);
             f.accrue_buyback_fees(fees.floor().to_num::<u64>());
         }
+        // Self trades don't count towards fee tiers
+        if fill.maker != fill.taker {
+            self.fixed_mut()
+                .record_perp_fee_volume(fill.timestamp, quote.abs().to_num::<u64>());
+        }
         let pa = self.perp_position_mut(perp_market_index)?;
         pa.settle_funding(perp_market);
         pa.record_trading_fee(fees);
@@ -1340,6 +1996,11 @@ impl<
 
         pa.taker_volume += quote_change_native.abs().to_num::<u64>();
 
+        if fill.maker != fill.taker {
+            self.fixed_mut()
+                .record_perp_fee_volume(fill.timestamp, quote_change_native.abs().to_num::<u64>());
+        }
+
         Ok(realized_pnl)
     }
 
@@ -1390,6 +2051,21 @@ impl<
         Ok(tcs)
     }
 
+    pub fn perp_scheduled_order_mut_by_index(
+        &mut self,
+        index: usize,
+    ) -> Result<&mut PerpScheduledOrder> {
+        let count: usize = self.header().perp_scheduled_order_count.into();
+        require_gt!(count, index);
+        let offset = self.header().perp_scheduled_order_offset(index);
+        Ok(get_helper_mut(self.dynamic_mut(), offset))
+    }
+
+    pub fn free_perp_scheduled_order_mut(&mut self) -> Result<&mut PerpScheduledOrder> {
+        let index = self.perp_scheduled_order_free_index()?;
+        self.perp_scheduled_order_mut_by_index(index)
+    }
+
     pub fn check_health_pre(&mut self, health_cache: &HealthCache) -> Result<I80F48> {
         let pre_init_health = health_cache.health(HealthType::Init);
         msg!("pre_init_health: {}", pre_init_health);
@@ -1426,6 +2102,10 @@ impl<
         let post_init_health = health_cache.health(HealthType::Init);
         msg!("post_init_health: {}", post_init_health);
         self.check_health_post_checks(pre_init_health, post_init_health)?;
+
+        // Isolated perp positions may only use their own collateral, so their buckets are
+        // checked separately from the account.
+        health_cache.check_isolated_perp_health_post()?;
         Ok(post_init_health)
     }
 
@@ -1457,6 +2137,80 @@ impl<
         Ok(())
     }
 
+    /// Records the borrows that the account's isolated collateral backs on the collateral banks.
+    ///
+    /// Call after a borrow or withdraw passed the health check. The banks are looked up in
+    /// `bank_ais`, usually the health accounts, and must be writable when the account's share
+    /// changes. Errors if the total backed by a token would grow beyond its
+    /// Bank::isolated_debt_ceiling_quote.
+    ///
+    /// Shares of tokens that the health cache skipped, for example because of a bad oracle,
+    /// are left unchanged.
+    pub fn update_isolated_debt(
+        &mut self,
+        health_cache: &HealthCache,
+        bank_ais: &[AccountInfo],
+    ) -> Result<()> {
+        let backed = health_cache.isolated_debt_backed();
+        let group = self.fixed().group;
+
+        let changes = self
+            .active_token_positions()
+            .filter(|position| health_cache.has_token_info(position.token_index))
+            .filter_map(|position| {
+                let new_debt = backed
+                    .iter()
+                    .find(|(token_index, _)| *token_index == position.token_index)
+                    .map_or(0, |(_, debt)| debt.ceil().clamp_to_u64());
+                (new_debt != position.isolated_debt_quote).then_some((
+                    position.token_index,
+                    position.isolated_debt_quote,
+                    new_debt,
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        for (token_index, old_debt, new_debt) in changes {
+            let bank_ai = bank_ais
+                .iter()
+                .find(|ai| {
+                    ai.load::<Bank>().map_or(false, |bank| {
+                        bank.group == group && bank.token_index == token_index && bank.bank_num == 0
+                    })
+                })
+                .ok_or_else(|| {
+                    error_msg!(
+                        "bank for isolated collateral token {} not found in health accounts",
+                        token_index
+                    )
+                })?;
+            require_msg_typed!(
+                bank_ai.is_writable,
+                MangoError::HealthAccountBankNotWritable,
+                "the bank for isolated collateral token {} must be writable",
+                token_index
+            );
+            let mut bank = bank_ai.load_mut::<Bank>()?;
+            let total_debt = bank
+                .isolated_debt_quote
+                .saturating_sub(old_debt)
+                .saturating_add(new_debt);
+            if new_debt > old_debt {
+                require_msg_typed!(
+                    total_debt <= bank.isolated_debt_ceiling_quote,
+                    MangoError::IsolatedDebtCeilingReached,
+                    "isolated collateral token {} would back {} of borrows, ceiling is {}",
+                    token_index,
+                    total_debt,
+                    bank.isolated_debt_ceiling_quote
+                );
+            }
+            bank.isolated_debt_quote = total_debt;
+            self.token_position_mut(token_index)?.0.isolated_debt_quote = new_debt;
+        }
+        Ok(())
+    }
+
     /// A stricter version of check_health_post_checks() that requires >=0 health, it not getting
     /// worse is not sufficient
     pub fn check_health_post_checks_strict(&mut self, post_init_health: I80F48) -> Result<()> {
@@ -1517,15 +2271,33 @@ impl<
         self.write_borsh_vec_length_and_padding(offset, count)
     }
 
-    fn write_perp_oo_length(&mut self) {
-        let offset = self.header().perp_oo_offset(0);
-        let count = self.header().perp_oo_count;
+    fn write_perp_oo_length(&mut self) {
+        let offset = self.header().perp_oo_offset(0);
+        let count = self.header().perp_oo_count;
+        self.write_borsh_vec_length_and_padding(offset, count)
+    }
+
+    fn write_token_conditional_swap_length(&mut self) {
+        let offset = self.header().token_conditional_swap_offset(0);
+        let count = self.header().token_conditional_swap_count;
+        self.write_borsh_vec_length_and_padding(offset, count)
+    }
+
+    fn write_openbook_v2_length(&mut self) {
+        let offset = self.header().openbook_v2_offset(0);
+        let count = self.header().openbook_v2_count;
+        self.write_borsh_vec_length_and_padding(offset, count)
+    }
+
+    fn write_fixed_term_length(&mut self) {
+        let offset = self.header().fixed_term_offset(0);
+        let count = self.header().fixed_term_count;
         self.write_borsh_vec_length_and_padding(offset, count)
     }
 
-    fn write_token_conditional_swap_length(&mut self) {
-        let offset = self.header().token_conditional_swap_offset(0);
-        let count = self.header().token_conditional_swap_count;
+    fn write_perp_scheduled_order_length(&mut self) {
+        let offset = self.header().perp_scheduled_order_offset(0);
+        let count = self.header().perp_scheduled_order_count;
         self.write_borsh_vec_length_and_padding(offset, count)
     }
 
@@ -1536,6 +2308,9 @@ impl<
         new_perp_count: u8,
         new_perp_oo_count: u8,
         new_token_conditional_swap_count: u8,
+        new_openbook_v2_count: u8,
+        new_fixed_term_count: u8,
+        new_perp_scheduled_order_count: u8,
     ) -> Result<()> {
         let new_header = MangoAccountDynamicHeader {
             token_count: new_token_count,
@@ -1543,6 +2318,9 @@ impl<
             perp_count: new_perp_count,
             perp_oo_count: new_perp_oo_count,
             token_conditional_swap_count: new_token_conditional_swap_count,
+            openbook_v2_count: new_openbook_v2_count,
+            fixed_term_count: new_fixed_term_count,
+            perp_scheduled_order_count: new_perp_scheduled_order_count,
         };
         let old_header = self.header().clone();
 
@@ -1663,12 +2441,78 @@ impl<
             active_tcs += 1;
         }
 
+        let mut active_openbook_v2_orders = 0;
+        for i in 0..old_header.openbook_v2_count() {
+            let src = old_header.openbook_v2_offset(i);
+            let pos: &OpenbookV2Orders = get_helper(dynamic, src);
+            if !pos.is_active() {
+                continue;
+            }
+            if i != active_openbook_v2_orders {
+                let dst = old_header.openbook_v2_offset(active_openbook_v2_orders);
+                unsafe {
+                    sol_memmove(
+                        &mut dynamic[dst],
+                        &mut dynamic[src],
+                        size_of::<OpenbookV2Orders>(),
+                    );
+                }
+            }
+            active_openbook_v2_orders += 1;
+        }
+
+        let mut active_fixed_term_positions = 0;
+        for i in 0..old_header.fixed_term_count() {
+            let src = old_header.fixed_term_offset(i);
+            let pos: &FixedTermPosition = get_helper(dynamic, src);
+            if !pos.is_active() {
+                continue;
+            }
+            if i != active_fixed_term_positions {
+                let dst = old_header.fixed_term_offset(active_fixed_term_positions);
+                unsafe {
+                    sol_memmove(
+                        &mut dynamic[dst],
+                        &mut dynamic[src],
+                        size_of::<FixedTermPosition>(),
+                    );
+                }
+            }
+            active_fixed_term_positions += 1;
+        }
+
+        let mut active_perp_scheduled_orders = 0;
+        for i in 0..old_header.perp_scheduled_order_count() {
+            let src = old_header.perp_scheduled_order_offset(i);
+            let so: &PerpScheduledOrder = get_helper(dynamic, src);
+            if !so.is_configured() {
+                continue;
+            }
+            if i != active_perp_scheduled_orders {
+                let dst = old_header.perp_scheduled_order_offset(active_perp_scheduled_orders);
+                unsafe {
+                    sol_memmove(
+                        &mut dynamic[dst],
+                        &mut dynamic[src],
+                        size_of::<PerpScheduledOrder>(),
+                    );
+                }
+            }
+            active_perp_scheduled_orders += 1;
+        }
+
         // Check that the new allocations can fit the existing data
         require_gte!(new_header.token_count(), active_token_positions);
         require_gte!(new_header.serum3_count(), active_serum3_orders);
         require_gte!(new_header.perp_count(), active_perp_positions);
         require_gte!(new_header.perp_oo_count(), blocked_perp_oo);
         require_gte!(new_header.token_conditional_swap_count(), active_tcs);
+        require_gte!(new_header.openbook_v2_count(), active_openbook_v2_orders);
+        require_gte!(new_header.fixed_term_count(), active_fixed_term_positions);
+        require_gte!(
+            new_header.perp_scheduled_order_count(),
+            active_perp_scheduled_orders
+        );
 
         // First move pass: go left-to-right and move any blocks that need to be moved
         // to the left. This will never overwrite other data, because:
@@ -1726,6 +2570,42 @@ impl<
                     );
                 }
             }
+
+            let old_openbook_v2_start = old_header.openbook_v2_offset(0);
+            let new_openbook_v2_start = new_header.openbook_v2_offset(0);
+            if new_openbook_v2_start < old_openbook_v2_start && active_openbook_v2_orders > 0 {
+                unsafe {
+                    sol_memmove(
+                        &mut dynamic[new_openbook_v2_start],
+                        &mut dynamic[old_openbook_v2_start],
+                        size_of::<OpenbookV2Orders>() * active_openbook_v2_orders,
+                    );
+                }
+            }
+
+            let old_fixed_term_start = old_header.fixed_term_offset(0);
+            let new_fixed_term_start = new_header.fixed_term_offset(0);
+            if new_fixed_term_start < old_fixed_term_start && active_fixed_term_positions > 0 {
+                unsafe {
+                    sol_memmove(
+                        &mut dynamic[new_fixed_term_start],
+                        &mut dynamic[old_fixed_term_start],
+                        size_of::<FixedTermPosition>() * active_fixed_term_positions,
+                    );
+                }
+            }
+
+            let old_scheduled_start = old_header.perp_scheduled_order_offset(0);
+            let new_scheduled_start = new_header.perp_scheduled_order_offset(0);
+            if new_scheduled_start < old_scheduled_start && active_perp_scheduled_orders > 0 {
+                unsafe {
+                    sol_memmove(
+                        &mut dynamic[new_scheduled_start],
+                        &mut dynamic[old_scheduled_start],
+                        size_of::<PerpScheduledOrder>() * active_perp_scheduled_orders,
+                    );
+                }
+            }
         }
 
         // Second move pass: Go right-to-left and move everything to the right if needed.
@@ -1735,6 +2615,42 @@ impl<
         // - if the block to the right was moved to the left, we know that its start will
         //   be >= our block's end
         {
+            let old_scheduled_start = old_header.perp_scheduled_order_offset(0);
+            let new_scheduled_start = new_header.perp_scheduled_order_offset(0);
+            if new_scheduled_start > old_scheduled_start && active_perp_scheduled_orders > 0 {
+                unsafe {
+                    sol_memmove(
+                        &mut dynamic[new_scheduled_start],
+                        &mut dynamic[old_scheduled_start],
+                        size_of::<PerpScheduledOrder>() * active_perp_scheduled_orders,
+                    );
+                }
+            }
+
+            let old_fixed_term_start = old_header.fixed_term_offset(0);
+            let new_fixed_term_start = new_header.fixed_term_offset(0);
+            if new_fixed_term_start > old_fixed_term_start && active_fixed_term_positions > 0 {
+                unsafe {
+                    sol_memmove(
+                        &mut dynamic[new_fixed_term_start],
+                        &mut dynamic[old_fixed_term_start],
+                        size_of::<FixedTermPosition>() * active_fixed_term_positions,
+                    );
+                }
+            }
+
+            let old_openbook_v2_start = old_header.openbook_v2_offset(0);
+            let new_openbook_v2_start = new_header.openbook_v2_offset(0);
+            if new_openbook_v2_start > old_openbook_v2_start && active_openbook_v2_orders > 0 {
+                unsafe {
+                    sol_memmove(
+                        &mut dynamic[new_openbook_v2_start],
+                        &mut dynamic[old_openbook_v2_start],
+                        size_of::<OpenbookV2Orders>() * active_openbook_v2_orders,
+                    );
+                }
+            }
+
             let old_tcs_start = old_header.token_conditional_swap_offset(0);
             let new_tcs_start = new_header.token_conditional_swap_offset(0);
             if new_tcs_start > old_tcs_start && active_tcs > 0 {
@@ -1804,6 +2720,18 @@ impl<
                 *get_helper_mut(dynamic, new_header.token_conditional_swap_offset(i)) =
                     TokenConditionalSwap::default();
             }
+            for i in active_openbook_v2_orders..new_header.openbook_v2_count() {
+                *get_helper_mut(dynamic, new_header.openbook_v2_offset(i)) =
+                    OpenbookV2Orders::default();
+            }
+            for i in active_fixed_term_positions..new_header.fixed_term_count() {
+                *get_helper_mut(dynamic, new_header.fixed_term_offset(i)) =
+                    FixedTermPosition::default();
+            }
+            for i in active_perp_scheduled_orders..new_header.perp_scheduled_order_count() {
+                *get_helper_mut(dynamic, new_header.perp_scheduled_order_offset(i)) =
+                    PerpScheduledOrder::default();
+            }
         }
         {
             let offset = new_header.reserved_bytes_offset();
@@ -1820,6 +2748,9 @@ impl<
         self.write_perp_length();
         self.write_perp_oo_length();
         self.write_token_conditional_swap_length();
+        self.write_openbook_v2_length();
+        self.write_fixed_term_length();
+        self.write_perp_scheduled_order_length();
 
         Ok(())
     }
@@ -1905,6 +2836,9 @@ mod tests {
             account.perps.len() as u8,
             account.perp_open_orders.len() as u8,
             account.token_conditional_swaps.len() as u8,
+            account.openbook_v2.len() as u8,
+            account.fixed_term_positions.len() as u8,
+            account.perp_scheduled_orders.len() as u8,
         );
         assert_eq!(expected_space, 8 + bytes.len());
 
@@ -1938,9 +2872,24 @@ mod tests {
             .token_conditional_swaps
             .resize(12, TokenConditionalSwap::default());
         account.token_conditional_swaps[0].buy_token_index = 14;
+        account.openbook_v2.resize(3, OpenbookV2Orders::default());
+        account.openbook_v2[0].market_index = 15;
+        account
+            .fixed_term_positions
+            .resize(2, FixedTermPosition::default());
+        account.fixed_term_positions[0].market_index = 16;
+        account.next_perp_scheduled_order_id = 17;
+        account.portfolio_margin = 1;
+        account
+            .perp_scheduled_orders
+            .resize(3, PerpScheduledOrder::default());
+        account.perp_scheduled_orders[0].market_index = 18;
 
         let account_bytes = AnchorSerialize::try_to_vec(&account).unwrap();
-        assert_eq!(8 + account_bytes.len(), MangoAccount::space(8, 8, 4, 8, 12));
+        assert_eq!(
+            8 + account_bytes.len(),
+            MangoAccount::space(8, 8, 4, 8, 12, 3, 2, 3)
+        );
 
         let account2 = MangoAccountValue::from_bytes(&account_bytes).unwrap();
         assert_eq!(account.group, account2.fixed.group);
@@ -2003,6 +2952,42 @@ mod tests {
                 .unwrap()
                 .buy_token_index
         );
+        assert_eq!(
+            account.openbook_v2.len(),
+            account2.all_openbook_v2_orders().count()
+        );
+        assert_eq!(
+            account.openbook_v2[0].market_index,
+            account2
+                .openbook_v2_orders_by_raw_index_unchecked(0)
+                .market_index
+        );
+        assert_eq!(
+            account.fixed_term_positions.len(),
+            account2.all_fixed_term_positions().count()
+        );
+        assert_eq!(
+            account.fixed_term_positions[0].market_index,
+            account2
+                .fixed_term_position_by_raw_index_unchecked(0)
+                .market_index
+        );
+        assert_eq!(
+            account.next_perp_scheduled_order_id,
+            account2.fixed.next_perp_scheduled_order_id
+        );
+        assert!(account2.fixed.is_portfolio_margin());
+        assert_eq!(
+            account.perp_scheduled_orders.len(),
+            account2.all_perp_scheduled_orders().count()
+        );
+        assert_eq!(
+            account.perp_scheduled_orders[0].market_index,
+            account2
+                .perp_scheduled_order_by_index(0)
+                .unwrap()
+                .market_index
+        );
     }
 
     #[test]
@@ -2114,6 +3099,89 @@ mod tests {
         assert!(account.serum3_orders_mut(7).is_err());
     }
 
+    #[test]
+    fn test_openbook_v2_orders() {
+        let mut account = make_test_account();
+        assert!(account.openbook_v2_orders(1).is_err());
+        assert!(account.openbook_v2_orders_mut(3).is_err());
+        assert_eq!(
+            account
+                .openbook_v2_orders_by_raw_index_unchecked(0)
+                .market_index,
+            OpenbookV2MarketIndex::MAX
+        );
+
+        assert_eq!(
+            account.create_openbook_v2_orders(1).unwrap().market_index,
+            1
+        );
+        assert_eq!(
+            account.create_openbook_v2_orders(7).unwrap().market_index,
+            7
+        );
+        assert!(account.create_openbook_v2_orders(7).is_err());
+        assert_eq!(account.active_openbook_v2_orders().count(), 2);
+
+        assert!(account.deactivate_openbook_v2_orders(7).is_ok());
+        assert!(account.openbook_v2_orders(7).is_err());
+        assert!(account.create_openbook_v2_orders(8).is_ok());
+        assert_eq!(
+            account
+                .openbook_v2_orders_by_raw_index_unchecked(1)
+                .market_index,
+            8
+        );
+        assert_eq!(account.active_openbook_v2_orders().count(), 2);
+
+        for i in 10..13 {
+            account.create_openbook_v2_orders(i).unwrap();
+        }
+        assert!(account.create_openbook_v2_orders(13).is_err());
+
+        assert_eq!(account.openbook_v2_orders_mut(8).unwrap().market_index, 8);
+    }
+
+    #[test]
+    fn test_fixed_term_positions() {
+        let mut account = make_test_account();
+        assert!(account.fixed_term_position(1).is_err());
+        assert!(account.fixed_term_position_mut(3).is_err());
+        assert_eq!(
+            account
+                .fixed_term_position_by_raw_index_unchecked(0)
+                .market_index,
+            FixedTermMarketIndex::MAX
+        );
+
+        {
+            let (pos, created) = account.ensure_fixed_term_position(1, 4).unwrap();
+            assert!(created);
+            assert_eq!(pos.market_index, 1);
+            assert_eq!(pos.token_index, 4);
+            pos.lent_native = 10;
+        }
+        {
+            let (pos, created) = account.ensure_fixed_term_position(1, 4).unwrap();
+            assert!(!created);
+            assert_eq!(pos.lent_native, 10);
+        }
+        assert!(account.ensure_fixed_term_position(7, 0).unwrap().1);
+        assert!(account.ensure_fixed_term_position(8, 0).is_err());
+        assert_eq!(account.active_fixed_term_positions().count(), 2);
+
+        assert!(account.deactivate_fixed_term_position(1).is_ok());
+        assert!(account.fixed_term_position(1).is_err());
+        assert_eq!(
+            account
+                .fixed_term_position_by_raw_index_unchecked(0)
+                .lent_native,
+            0
+        );
+        assert!(account.ensure_fixed_term_position(8, 0).unwrap().1);
+        assert_eq!(account.fixed_term_position_mut(8).unwrap().market_index, 8);
+        assert_eq!(account.active_fixed_term_positions().count(), 2);
+    }
+
     #[test]
     fn test_perp_positions() {
         let mut account = make_test_account();
@@ -2181,6 +3249,42 @@ mod tests {
         assert_eq!(account.active_perp_positions().count(), 2);
     }
 
+    #[test]
+    fn test_perp_fee_volume() {
+        let mut account = make_test_account();
+        let fixed = account.fixed_mut();
+        let w = PERP_FEE_TIER_VOLUME_WINDOW;
+        assert_eq!(fixed.trailing_perp_fee_volume(10 * w), 0);
+
+        fixed.record_perp_fee_volume(10 * w + w / 2, 1000);
+        assert_eq!(fixed.perp_fee_volume_window_end, 11 * w);
+        assert_eq!(fixed.trailing_perp_fee_volume(10 * w + w / 2), 1000);
+
+        // the previous window only counts for the part still in the trailing window
+        fixed.record_perp_fee_volume(11 * w + w / 4, 100);
+        assert_eq!(fixed.perp_fee_volume_window_end, 12 * w);
+        assert_eq!(fixed.perp_fee_volume_previous, 1000);
+        assert_eq!(fixed.perp_fee_volume_current, 100);
+        assert_eq!(fixed.trailing_perp_fee_volume(11 * w + w / 4), 850);
+
+        // without updates, the current window decays once it's over
+        assert_eq!(fixed.trailing_perp_fee_volume(12 * w + w / 2), 50);
+        assert_eq!(fixed.trailing_perp_fee_volume(13 * w), 0);
+
+        let tiers = [PerpFeeTier {
+            min_volume_quote: 800,
+            maker_fee_discount: 0.0,
+            taker_fee_discount: 0.0,
+            reserved: [0; 16],
+        }];
+        assert_eq!(fixed.perp_fee_tier(&tiers, 11 * w + w / 4), 1);
+        assert_eq!(fixed.perp_fee_tier(&tiers, 11 * w + w / 2), 0);
+
+        fixed.record_perp_fee_volume(14 * w, 1);
+        assert_eq!(fixed.perp_fee_volume_previous, 0);
+        assert_eq!(fixed.perp_fee_volume_current, 1);
+    }
+
     #[test]
     fn test_buyback_fees() {
         let mut account = make_test_account();
@@ -2300,22 +3404,22 @@ mod tests {
         account
             .perp_open_orders
             .resize(header.perp_oo_count(), PerpOpenOrder::default());
-        let mut bytes = AnchorSerialize::try_to_vec(&account).unwrap();
-
-        // The MangoAccount struct is missing some dynamic fields, add space for them
-        let expected_space = header.account_size();
-        bytes.extend(vec![0u8; expected_space - bytes.len()]);
-
-        // Set the length of these dynamic parts
-        let (fixed, dynamic) = bytes.split_at_mut(size_of::<MangoAccountFixed>());
-        let mut out_header = MangoAccountDynamicHeader::from_bytes(dynamic).unwrap();
-        out_header.token_conditional_swap_count = header.token_conditional_swap_count;
-        let mut account = MangoAccountRefMut {
-            header: &mut out_header,
-            fixed: bytemuck::from_bytes_mut(fixed),
-            dynamic,
-        };
-        account.write_token_conditional_swap_length();
+        account.token_conditional_swaps.resize(
+            header.token_conditional_swap_count(),
+            TokenConditionalSwap::default(),
+        );
+        account
+            .openbook_v2
+            .resize(header.openbook_v2_count(), OpenbookV2Orders::default());
+        account
+            .fixed_term_positions
+            .resize(header.fixed_term_count(), FixedTermPosition::default());
+        account.perp_scheduled_orders.resize(
+            header.perp_scheduled_order_count(),
+            PerpScheduledOrder::default(),
+        );
+        let bytes = AnchorSerialize::try_to_vec(&account).unwrap();
+        assert_eq!(8 + bytes.len(), header.account_size());
 
         MangoAccountValue::from_bytes(&bytes).unwrap()
     }
@@ -2415,6 +3519,78 @@ mod tests {
             );
         }
 
+        assert_eq!(
+            account.all_openbook_v2_orders().count(),
+            header.openbook_v2_count()
+        );
+        assert_eq!(
+            account.active_openbook_v2_orders().count(),
+            active.openbook_v2_count()
+        );
+        for i in 0..active.openbook_v2_count() {
+            assert_eq!(
+                account.openbook_v2_orders_by_raw_index(i)?.market_index,
+                i as OpenbookV2MarketIndex
+            );
+        }
+        for i in active.openbook_v2_count()..header.openbook_v2_count() {
+            let def = OpenbookV2Orders::default().try_to_vec().unwrap();
+            assert_eq!(
+                account
+                    .openbook_v2_orders_by_raw_index(i)?
+                    .try_to_vec()
+                    .unwrap(),
+                def
+            );
+        }
+
+        assert_eq!(
+            account.all_fixed_term_positions().count(),
+            header.fixed_term_count()
+        );
+        assert_eq!(
+            account.active_fixed_term_positions().count(),
+            active.fixed_term_count()
+        );
+        for i in 0..active.fixed_term_count() {
+            assert_eq!(
+                account.fixed_term_position_by_raw_index(i)?.market_index,
+                i as FixedTermMarketIndex
+            );
+        }
+        for i in active.fixed_term_count()..header.fixed_term_count() {
+            let def = FixedTermPosition::default().try_to_vec().unwrap();
+            assert_eq!(
+                account
+                    .fixed_term_position_by_raw_index(i)?
+                    .try_to_vec()
+                    .unwrap(),
+                def
+            );
+        }
+
+        assert_eq!(
+            account.all_perp_scheduled_orders().count(),
+            header.perp_scheduled_order_count()
+        );
+        assert_eq!(
+            account.active_perp_scheduled_orders().count(),
+            active.perp_scheduled_order_count()
+        );
+        for i in 0..active.perp_scheduled_order_count() {
+            assert_eq!(account.perp_scheduled_order_by_index(i)?.id, i as u64);
+        }
+        for i in active.perp_scheduled_order_count()..header.perp_scheduled_order_count() {
+            let def = PerpScheduledOrder::default().try_to_vec().unwrap();
+            assert_eq!(
+                account
+                    .perp_scheduled_order_by_index(i)?
+                    .try_to_vec()
+                    .unwrap(),
+                def
+            );
+        }
+
         assert!(account.dynamic_reserved_bytes().iter().all(|&v| v == 0));
 
         Ok(())
@@ -2428,6 +3604,9 @@ mod tests {
             perp_count: 6,
             perp_oo_count: 7,
             token_conditional_swap_count: 8,
+            openbook_v2_count: 3,
+            fixed_term_count: 3,
+            perp_scheduled_order_count: 3,
         };
         let mut account = make_resize_test_account(&header);
 
@@ -2466,12 +3645,27 @@ mod tests {
         make_tcs(2, 0);
         make_tcs(4, 1);
 
+        account.create_openbook_v2_orders(5)?;
+        account.create_openbook_v2_orders(0)?;
+        account.deactivate_openbook_v2_orders(5)?;
+
+        account.ensure_fixed_term_position(2, 0)?;
+        account.ensure_fixed_term_position(0, 0)?;
+        account.deactivate_fixed_term_position(2)?;
+
+        account.perp_scheduled_order_mut_by_index(0)?.is_configured = 1;
+        account.perp_scheduled_order_mut_by_index(2)?.is_configured = 1;
+        *account.perp_scheduled_order_mut_by_index(0)? = PerpScheduledOrder::default();
+
         let active = MangoAccountDynamicHeader {
             token_count: 2,
             serum3_count: 2,
             perp_count: 4,
             perp_oo_count: 5,
             token_conditional_swap_count: 2,
+            openbook_v2_count: 1,
+            fixed_term_count: 1,
+            perp_scheduled_order_count: 1,
         };
 
         // Resizing to the same size just removes the empty spaces
@@ -2483,6 +3677,9 @@ mod tests {
                 header.perp_count,
                 header.perp_oo_count,
                 header.token_conditional_swap_count,
+                header.openbook_v2_count,
+                header.fixed_term_count,
+                header.perp_scheduled_order_count,
             )?;
             check_account_active_and_order(&ta, &active)?;
         }
@@ -2496,6 +3693,9 @@ mod tests {
                 active.perp_count,
                 active.perp_oo_count,
                 active.token_conditional_swap_count,
+                active.openbook_v2_count,
+                active.fixed_term_count,
+                active.perp_scheduled_order_count,
             )?;
             check_account_active_and_order(&ta, &active)?;
         }
@@ -2509,6 +3709,9 @@ mod tests {
                 active.perp_count,
                 active.perp_oo_count,
                 active.token_conditional_swap_count,
+                active.openbook_v2_count,
+                active.fixed_term_count,
+                active.perp_scheduled_order_count,
             )
             .unwrap_err();
             ta.resize_dynamic_content(
@@ -2517,6 +3720,9 @@ mod tests {
                 active.perp_count,
                 active.perp_oo_count,
                 active.token_conditional_swap_count,
+                active.openbook_v2_count,
+                active.fixed_term_count,
+                active.perp_scheduled_order_count,
             )
             .unwrap_err();
             ta.resize_dynamic_content(
@@ -2525,6 +3731,9 @@ mod tests {
                 active.perp_count - 1,
                 active.perp_oo_count,
                 active.token_conditional_swap_count,
+                active.openbook_v2_count,
+                active.fixed_term_count,
+                active.perp_scheduled_order_count,
             )
             .unwrap_err();
             ta.resize_dynamic_content(
@@ -2533,6 +3742,9 @@ mod tests {
                 active.perp_count,
                 active.perp_oo_count - 1,
                 active.token_conditional_swap_count,
+                active.openbook_v2_count,
+                active.fixed_term_count,
+                active.perp_scheduled_order_count,
             )
             .unwrap_err();
             ta.resize_dynamic_content(
@@ -2541,6 +3753,42 @@ mod tests {
                 active.perp_count,
                 active.perp_oo_count,
                 active.token_conditional_swap_count - 1,
+                active.openbook_v2_count,
+                active.fixed_term_count,
+                active.perp_scheduled_order_count,
+            )
+            .unwrap_err();
+            ta.resize_dynamic_content(
+                active.token_count,
+                active.serum3_count,
+                active.perp_count,
+                active.perp_oo_count,
+                active.token_conditional_swap_count,
+                active.openbook_v2_count - 1,
+                active.fixed_term_count,
+                active.perp_scheduled_order_count,
+            )
+            .unwrap_err();
+            ta.resize_dynamic_content(
+                active.token_count,
+                active.serum3_count,
+                active.perp_count,
+                active.perp_oo_count,
+                active.token_conditional_swap_count,
+                active.openbook_v2_count,
+                active.fixed_term_count - 1,
+                active.perp_scheduled_order_count,
+            )
+            .unwrap_err();
+            ta.resize_dynamic_content(
+                active.token_count,
+                active.serum3_count,
+                active.perp_count,
+                active.perp_oo_count,
+                active.token_conditional_swap_count,
+                active.openbook_v2_count,
+                active.fixed_term_count,
+                active.perp_scheduled_order_count - 1,
             )
             .unwrap_err();
         }
@@ -2559,6 +3807,9 @@ mod tests {
                 perp_count: 4,
                 perp_oo_count: 8,
                 token_conditional_swap_count: 4,
+                openbook_v2_count: 4,
+                fixed_term_count: 4,
+                perp_scheduled_order_count: 4,
             };
             let mut account = make_resize_test_account(&header);
 
@@ -2569,6 +3820,9 @@ mod tests {
                 perp_oo_count: rng.gen_range(0..header.perp_oo_count + 1),
                 token_conditional_swap_count: rng
                     .gen_range(0..header.token_conditional_swap_count + 1),
+                openbook_v2_count: rng.gen_range(0..header.openbook_v2_count + 1),
+                fixed_term_count: rng.gen_range(0..header.fixed_term_count + 1),
+                perp_scheduled_order_count: rng.gen_range(0..header.perp_scheduled_order_count + 1),
             };
 
             let options = (0..header.token_count()).collect_vec();
@@ -2603,12 +3857,39 @@ mod tests {
                 tcs.id = i as u64;
             }
 
+            let options = (0..header.openbook_v2_count()).collect_vec();
+            let selected = options.choose_multiple(&mut rng, active.openbook_v2_count());
+            for (i, index) in selected.sorted().enumerate() {
+                account
+                    .openbook_v2_orders_mut_by_raw_index(*index)
+                    .market_index = i as OpenbookV2MarketIndex;
+            }
+
+            let options = (0..header.fixed_term_count()).collect_vec();
+            let selected = options.choose_multiple(&mut rng, active.fixed_term_count());
+            for (i, index) in selected.sorted().enumerate() {
+                account
+                    .fixed_term_position_mut_by_raw_index(*index)
+                    .market_index = i as FixedTermMarketIndex;
+            }
+
+            let options = (0..header.perp_scheduled_order_count()).collect_vec();
+            let selected = options.choose_multiple(&mut rng, active.perp_scheduled_order_count());
+            for (i, index) in selected.sorted().enumerate() {
+                let so = account.perp_scheduled_order_mut_by_index(*index).unwrap();
+                so.is_configured = 1;
+                so.id = i as u64;
+            }
+
             let target = MangoAccountDynamicHeader {
                 token_count: rng.gen_range(active.token_count..6),
                 serum3_count: rng.gen_range(active.serum3_count..7),
                 perp_count: rng.gen_range(active.perp_count..6),
                 perp_oo_count: rng.gen_range(active.perp_oo_count..16),
                 token_conditional_swap_count: rng.gen_range(active.token_conditional_swap_count..8),
+                openbook_v2_count: rng.gen_range(active.openbook_v2_count..6),
+                fixed_term_count: rng.gen_range(active.fixed_term_count..6),
+                perp_scheduled_order_count: rng.gen_range(active.perp_scheduled_order_count..6),
             };
 
             let target_size = target.account_size();
@@ -2625,6 +3906,9 @@ mod tests {
                     target.perp_count,
                     target.perp_oo_count,
                     target.token_conditional_swap_count,
+                    target.openbook_v2_count,
+                    target.fixed_term_count,
+                    target.perp_scheduled_order_count,
                 )
                 .unwrap();
 
@@ -2658,6 +3942,7 @@ mod tests {
             0,
             0,
             0,
+            0,
         );
         let side = Side::Bid;
         account.add_perp_order(0, side, BookSideOrderTree::Fixed, &order)?;
@@ -2674,9 +3959,11 @@ mod tests {
                 0,
                 I80F48::ZERO,
                 0,
+                0,
                 owner,
                 0,
                 I80F48::ZERO,
+                0,
                 1,
                 quantity,
             )
@@ -2887,6 +4174,30 @@ mod tests {
             let filename = format!("resources/test/{}.bin", fixture);
             let account_bytes = read_file(find_file(&filename).unwrap());
 
+            // Accounts from before v4 have no space for openbook v2 orders, fixed term
+            // positions or perp scheduled orders. The reserved bytes make them readable with zerocopy, but borsh
+            // needs a migration first.
+            let mut old_account = MangoAccountValue::from_bytes(&account_bytes[8..])?;
+            assert_eq!(old_account.header.openbook_v2_count(), 0);
+            assert_eq!(old_account.header.fixed_term_count(), 0);
+            assert_eq!(old_account.header.perp_scheduled_order_count(), 0);
+            let header = old_account.header.clone();
+            let new_dynamic_size = header.account_size() - 8 - size_of::<MangoAccountFixed>();
+            old_account.dynamic.resize(new_dynamic_size, 0);
+            old_account.resize_dynamic_content(
+                header.token_count,
+                header.serum3_count,
+                header.perp_count,
+                header.perp_oo_count,
+                header.token_conditional_swap_count,
+                header.openbook_v2_count,
+                header.fixed_term_count,
+                header.perp_scheduled_order_count,
+            )?;
+            let mut account_bytes = account_bytes[..8].to_vec();
+            account_bytes.extend_from_slice(bytemuck::bytes_of(&old_account.fixed));
+            account_bytes.extend_from_slice(&old_account.dynamic);
+
             // Read with borsh
             let mut account_bytes_slice: &[u8] = &account_bytes;
             let borsh_account = MangoAccount::try_deserialize(&mut account_bytes_slice)?;
@@ -2915,7 +4226,16 @@ mod tests {
                 temporary_delegate: fixed.temporary_delegate,
                 temporary_delegate_expiry: fixed.temporary_delegate_expiry,
                 last_collateral_fee_charge: fixed.last_collateral_fee_charge,
-                reserved: [0u8; 152],
+                next_perp_scheduled_order_id: fixed.next_perp_scheduled_order_id,
+                portfolio_margin: fixed.portfolio_margin,
+                emode_category: fixed.emode_category,
+                padding12: Default::default(),
+                delegate_scope: fixed.delegate_scope,
+                temporary_delegate_scope: fixed.temporary_delegate_scope,
+                perp_fee_volume_current: fixed.perp_fee_volume_current,
+                perp_fee_volume_previous: fixed.perp_fee_volume_previous,
+                perp_fee_volume_window_end: fixed.perp_fee_volume_window_end,
+                reserved: [0u8; 80],
 
                 header_version: *zerocopy_reader.header_version(),
                 padding3: Default::default(),
@@ -2938,6 +4258,24 @@ mod tests {
                     .cloned()
                     .collect_vec(),
 
+                padding9: Default::default(),
+                openbook_v2: zerocopy_reader
+                    .all_openbook_v2_orders()
+                    .cloned()
+                    .collect_vec(),
+
+                padding10: Default::default(),
+                fixed_term_positions: zerocopy_reader
+                    .all_fixed_term_positions()
+                    .cloned()
+                    .collect_vec(),
+
+                padding11: Default::default(),
+                perp_scheduled_orders: zerocopy_reader
+                    .all_perp_scheduled_orders()
+                    .cloned()
+                    .collect_vec(),
+
                 reserved_dynamic: zerocopy_reader.dynamic_reserved_bytes().try_into().unwrap(),
             };
 
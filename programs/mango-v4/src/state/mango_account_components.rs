@@ -42,13 +42,17 @@ pub struct TokenPosition {
     // Cumulative borrow interest in token native units
     pub cumulative_borrow_interest: f64,
 
+    /// For isolated collateral tokens: this account's share of Bank::isolated_debt_quote,
+    /// as of the last time it was updated, see update_isolated_debt().
+    pub isolated_debt_quote: u64,
+
     #[derivative(Debug = "ignore")]
-    pub reserved: [u8; 128],
+    pub reserved: [u8; 120],
 }
 
 const_assert_eq!(
     size_of::<TokenPosition>(),
-    16 + 2 + 2 + 4 + 16 + 8 + 8 + 128
+    16 + 2 + 2 + 4 + 16 + 8 + 8 + 8 + 120
 );
 const_assert_eq!(size_of::<TokenPosition>(), 184);
 const_assert_eq!(size_of::<TokenPosition>() % 8, 0);
@@ -62,8 +66,9 @@ impl Default for TokenPosition {
             cumulative_deposit_interest: 0.0,
             cumulative_borrow_interest: 0.0,
             previous_index: I80F48::ZERO,
+            isolated_debt_quote: 0,
             padding: Default::default(),
-            reserved: [0; 128],
+            reserved: [0; 120],
         }
     }
 }
@@ -202,6 +207,162 @@ impl Default for Serum3Orders {
     }
 }
 
+#[zero_copy]
+#[derive(AnchorSerialize, AnchorDeserialize, Derivative, PartialEq)]
+#[derivative(Debug)]
+pub struct OpenbookV2Orders {
+    pub open_orders: Pubkey,
+
+    /// Tracks the amount of borrows that have flowed into the open orders account.
+    /// These borrows did not have the loan origination fee applied, and that may happen
+    /// later (in openbook_v2_settle_funds) if we can guarantee that the funds were used.
+    /// In particular a place-on-book, cancel, settle should not cost fees.
+    pub base_borrows_without_fee: u64,
+    pub quote_borrows_without_fee: u64,
+
+    pub market_index: OpenbookV2MarketIndex,
+
+    /// Store the base/quote token index, so health computations don't need
+    /// to get passed the static OpenbookV2Market to find which tokens a market
+    /// uses and look up the correct oracles.
+    pub base_token_index: TokenIndex,
+    pub quote_token_index: TokenIndex,
+
+    #[derivative(Debug = "ignore")]
+    pub padding: [u8; 2],
+
+    /// Track the highest/lowest placed bid and ask, in native/native units.
+    ///
+    /// Works like the same fields on Serum3Orders. 0 is a special "unset" state.
+    pub highest_placed_bid_inv: f64,
+    pub lowest_placed_bid_inv: f64,
+    pub lowest_placed_ask: f64,
+    pub highest_placed_ask: f64,
+
+    /// An overestimate of the amount of tokens that might flow out of the open orders account.
+    ///
+    /// The bank still considers these amounts user deposits (see Bank::potential_serum_tokens)
+    /// and that value needs to be updated in conjunction with these numbers.
+    pub potential_base_tokens: u64,
+    pub potential_quote_tokens: u64,
+
+    /// Lot sizes of the openbook v2 market, copied on creation.
+    ///
+    /// The open orders account tracks reserved amounts in lots, so health needs these
+    /// to compute native amounts without being passed the market.
+    pub base_lot_size: i64,
+    pub quote_lot_size: i64,
+
+    #[derivative(Debug = "ignore")]
+    pub reserved: [u8; 96],
+}
+const_assert_eq!(
+    size_of::<OpenbookV2Orders>(),
+    32 + 8 * 2 + 2 * 3 + 2 + 8 * 8 + 96
+);
+const_assert_eq!(size_of::<OpenbookV2Orders>(), 216);
+const_assert_eq!(size_of::<OpenbookV2Orders>() % 8, 0);
+
+impl OpenbookV2Orders {
+    pub fn is_active(&self) -> bool {
+        self.market_index != OpenbookV2MarketIndex::MAX
+    }
+
+    pub fn is_active_for_market(&self, market_index: OpenbookV2MarketIndex) -> bool {
+        self.market_index == market_index
+    }
+}
+
+impl Default for OpenbookV2Orders {
+    fn default() -> Self {
+        Self {
+            open_orders: Pubkey::default(),
+            market_index: OpenbookV2MarketIndex::MAX,
+            base_token_index: TokenIndex::MAX,
+            quote_token_index: TokenIndex::MAX,
+            padding: Default::default(),
+            base_borrows_without_fee: 0,
+            quote_borrows_without_fee: 0,
+            highest_placed_bid_inv: 0.0,
+            lowest_placed_bid_inv: 0.0,
+            lowest_placed_ask: 0.0,
+            highest_placed_ask: 0.0,
+            potential_base_tokens: 0,
+            potential_quote_tokens: 0,
+            base_lot_size: 0,
+            quote_lot_size: 0,
+            reserved: [0; 96],
+        }
+    }
+}
+
+#[zero_copy]
+#[derive(AnchorSerialize, AnchorDeserialize, Derivative, PartialEq)]
+#[derivative(Debug)]
+pub struct FixedTermPosition {
+    pub market_index: FixedTermMarketIndex,
+
+    /// Store the token index, so health computations don't need to get passed
+    /// the FixedTermMarket to find which token a position is in.
+    pub token_index: TokenIndex,
+
+    #[derivative(Debug = "ignore")]
+    pub padding: [u8; 4],
+
+    /// Copied from the market, the position can be settled after this time
+    pub maturity_timestamp: u64,
+
+    /// Principal lent into the market
+    pub lent_native: u64,
+    /// Amount that will be credited to the token position at maturity
+    pub lent_at_maturity_native: u64,
+
+    /// Principal borrowed from the market
+    pub borrowed_native: u64,
+    /// Amount that will be debited from the token position at maturity
+    pub borrowed_at_maturity_native: u64,
+
+    #[derivative(Debug = "ignore")]
+    pub reserved: [u8; 64],
+}
+const_assert_eq!(size_of::<FixedTermPosition>(), 2 + 2 + 4 + 8 * 5 + 64);
+const_assert_eq!(size_of::<FixedTermPosition>(), 112);
+const_assert_eq!(size_of::<FixedTermPosition>() % 8, 0);
+
+impl FixedTermPosition {
+    pub fn is_active(&self) -> bool {
+        self.market_index != FixedTermMarketIndex::MAX
+    }
+
+    pub fn is_active_for_market(&self, market_index: FixedTermMarketIndex) -> bool {
+        self.market_index == market_index
+    }
+
+    /// Change to the token balance that health should assume for this position.
+    ///
+    /// Lent tokens count with their principal only, borrowed tokens count with the full
+    /// amount owed at maturity.
+    pub fn health_balance_native(&self) -> I80F48 {
+        I80F48::from(self.lent_native) - I80F48::from(self.borrowed_at_maturity_native)
+    }
+}
+
+impl Default for FixedTermPosition {
+    fn default() -> Self {
+        Self {
+            market_index: FixedTermMarketIndex::MAX,
+            token_index: TokenIndex::MAX,
+            padding: Default::default(),
+            maturity_timestamp: 0,
+            lent_native: 0,
+            lent_at_maturity_native: 0,
+            borrowed_native: 0,
+            borrowed_at_maturity_native: 0,
+            reserved: [0; 64],
+        }
+    }
+}
+
 #[zero_copy]
 #[derive(AnchorSerialize, AnchorDeserialize, Derivative, PartialEq)]
 #[derivative(Debug)]
@@ -323,12 +484,42 @@ pub struct PerpPosition {
     /// price and current price of the base position is the overall pnl.
     pub realized_pnl_for_position_native: I80F48,
 
+    /// Whether the position is isolated: it may only use isolated_collateral_native as
+    /// collateral and doesn't contribute to the health of the rest of the account.
+    isolated: u8,
+
     #[derivative(Debug = "ignore")]
-    pub reserved: [u8; 88],
+    pub padding2: [u8; 7],
+
+    /// Settle token native allocated to an isolated position.
+    ///
+    /// This is a part of the account's settle token balance that only backs this position.
+    /// Settling pnl of the position moves funds in and out of it.
+    pub isolated_collateral_native: I80F48,
+
+    #[derivative(Debug = "ignore")]
+    pub reserved: [u8; 64],
 }
 const_assert_eq!(
     size_of::<PerpPosition>(),
-    2 + 2 + 4 + 8 + 8 + 16 + 8 + 16 * 2 + 8 * 2 + 8 * 2 + 8 * 5 + 8 + 2 * 16 + 8 + 16 + 88
+    2 + 2
+        + 4
+        + 8
+        + 8
+        + 16
+        + 8
+        + 16 * 2
+        + 8 * 2
+        + 8 * 2
+        + 8 * 5
+        + 8
+        + 2 * 16
+        + 8
+        + 16
+        + 1
+        + 7
+        + 16
+        + 64
 );
 const_assert_eq!(size_of::<PerpPosition>(), 304);
 const_assert_eq!(size_of::<PerpPosition>() % 8, 0);
@@ -359,7 +550,10 @@ impl Default for PerpPosition {
             settle_pnl_limit_settled_in_current_window_native: 0,
             recurring_settle_pnl_allowance: 0,
             realized_pnl_for_position_native: I80F48::ZERO,
-            reserved: [0; 88],
+            isolated: 0,
+            padding2: Default::default(),
+            isolated_collateral_native: I80F48::ZERO,
+            reserved: [0; 64],
         }
     }
 }
@@ -421,6 +615,26 @@ impl PerpPosition {
         self.quote_position_native
     }
 
+    pub fn is_isolated(&self) -> bool {
+        self.isolated == 1
+    }
+
+    /// Returning an isolated position to cross margin gives its collateral back to the account.
+    pub fn set_isolated(&mut self, b: bool) {
+        self.isolated = u8::from(b);
+        if !b {
+            self.isolated_collateral_native = I80F48::ZERO;
+        }
+    }
+
+    /// Track settle token flowing into (positive) or out of (negative) the position's
+    /// isolated collateral. Does nothing for cross margin positions.
+    pub fn change_isolated_collateral(&mut self, change: I80F48) {
+        if self.is_isolated() {
+            self.isolated_collateral_native += change;
+        }
+    }
+
     /// This assumes settle_funding was already called
     fn change_base_position(&mut self, perp_market: &mut PerpMarket, base_change: i64) {
         let start = self.base_position_lots;
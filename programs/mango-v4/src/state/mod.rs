@@ -1,31 +1,49 @@
 pub use amm_cpi::*;
 pub use bank::*;
+pub use clmm_twap_oracle::*;
+pub use delegate_scope::*;
 pub use dynamic_account::*;
+pub use emode::*;
 pub use equity::*;
+pub use fixed_term_market::*;
 pub use group::*;
 pub use mango_account::*;
 pub use mango_account_components::*;
 pub use mint_info::*;
 pub use openbook_v2_market::*;
+pub use openbook_v2_mid_oracle::*;
 pub use oracle::*;
+pub use oracle_aggregator::*;
 pub use orderbook::*;
+pub use perp_fee_tier::*;
 pub use perp_market::*;
+pub use perp_scheduled_order::*;
+pub use portfolio_margin::*;
 pub use serum3_market::*;
 pub use stable_price::*;
 pub use token_conditional_swap::*;
 
 mod amm_cpi;
 mod bank;
+mod clmm_twap_oracle;
+mod delegate_scope;
 mod dynamic_account;
+mod emode;
 mod equity;
+mod fixed_term_market;
 mod group;
 mod mango_account;
 mod mango_account_components;
 mod mint_info;
 mod openbook_v2_market;
+mod openbook_v2_mid_oracle;
 mod oracle;
+mod oracle_aggregator;
 mod orderbook;
+mod perp_fee_tier;
 mod perp_market;
+mod perp_scheduled_order;
+mod portfolio_margin;
 mod serum3_market;
 mod stable_price;
 mod token_conditional_swap;
@@ -0,0 +1,109 @@
+use std::mem::size_of;
+
+use anchor_lang::prelude::*;
+use fixed::types::I80F48;
+use openbook_v2::state::{BookSide as OpenbookV2BookSide, Market as OpenbookV2Market};
+use static_assertions::const_assert_eq;
+
+/// The mid price of an openbook v2 market's best bid and ask.
+///
+/// The book is spread over the market, bids and asks accounts, so it can't be read from a
+/// single oracle account. This oracle caches the mid price instead and is refreshed
+/// permissionlessly with openbook_v2_mid_oracle_update. It reads like the CLMM oracles:
+/// `price` is multiplied by the usdc or sol oracle price of `quote_mint`. Half the spread
+/// is reported as the deviation, so the bank's conf_filter bounds the accepted spread.
+#[account(zero_copy)]
+pub struct OpenbookV2MidOracle {
+    // ABI: Clients rely on this being at offset 8
+    pub group: Pubkey,
+    // ABI: Clients rely on this being at offset 40
+    pub mint: Pubkey,
+
+    pub market: Pubkey,
+    pub bids: Pubkey,
+    pub asks: Pubkey,
+
+    /// The market's quote token, whose usdc or sol oracle converts `price` to usd
+    pub quote_mint: Pubkey,
+
+    /// Mid price of one native `mint` token in native `quote_mint` tokens
+    pub price: I80F48,
+
+    /// Half the bid-ask spread, in the same unit as `price`
+    pub deviation: I80F48,
+
+    pub last_update_slot: u64,
+    pub last_update_ts: u64,
+
+    pub reserved: [u8; 128],
+}
+const_assert_eq!(
+    size_of::<OpenbookV2MidOracle>(),
+    32 * 6 + 16 * 2 + 8 * 2 + 128
+);
+const_assert_eq!(size_of::<OpenbookV2MidOracle>(), 368);
+const_assert_eq!(size_of::<OpenbookV2MidOracle>() % 8, 0);
+
+impl OpenbookV2MidOracle {
+    pub fn update(
+        &mut self,
+        market: &OpenbookV2Market,
+        bids: &OpenbookV2BookSide,
+        asks: &OpenbookV2BookSide,
+        now_ts: u64,
+        now_slot: u64,
+    ) -> Result<()> {
+        let best_bid = bids
+            .best_price(now_ts, None)
+            .ok_or_else(|| error_msg!("openbook v2 market has no bids"))?;
+        let best_ask = asks
+            .best_price(now_ts, None)
+            .ok_or_else(|| error_msg!("openbook v2 market has no asks"))?;
+        let (price, deviation) = mid_price_and_deviation(
+            best_bid,
+            best_ask,
+            market.base_lot_size,
+            market.quote_lot_size,
+        )?;
+
+        self.price = price;
+        self.deviation = deviation;
+        self.last_update_slot = now_slot;
+        self.last_update_ts = now_ts;
+        Ok(())
+    }
+}
+
+/// Mid price and half spread in native quote per native base, from prices in lots
+pub fn mid_price_and_deviation(
+    best_bid_lots: i64,
+    best_ask_lots: i64,
+    base_lot_size: i64,
+    quote_lot_size: i64,
+) -> Result<(I80F48, I80F48)> {
+    require_gt!(best_bid_lots, 0);
+    require_gte!(best_ask_lots, best_bid_lots);
+    let lot_to_native = I80F48::from_num(quote_lot_size) / I80F48::from_num(base_lot_size);
+    let mid_lots = I80F48::from_num(best_bid_lots + best_ask_lots) / 2;
+    let half_spread_lots = I80F48::from_num(best_ask_lots - best_bid_lots) / 2;
+    Ok((mid_lots * lot_to_native, half_spread_lots * lot_to_native))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mid_price_and_deviation() {
+        let (price, deviation) = mid_price_and_deviation(99, 101, 100, 10).unwrap();
+        assert_eq!(price, I80F48::from_num(10));
+        assert_eq!(deviation, I80F48::from_num(0.1));
+
+        let (price, deviation) = mid_price_and_deviation(50, 50, 1, 1).unwrap();
+        assert_eq!(price, I80F48::from_num(50));
+        assert_eq!(deviation, I80F48::ZERO);
+
+        assert!(mid_price_and_deviation(0, 10, 1, 1).is_err());
+        assert!(mid_price_and_deviation(11, 10, 1, 1).is_err());
+    }
+}
@@ -1,9 +1,15 @@
 use std::mem::size_of;
 
-use super::{load_raydium_pool_state, orca_mainnet_whirlpool, raydium_mainnet};
+use super::{
+    load_meteora_dlmm_state, load_raydium_pool_state, meteora_dlmm_mainnet, orca_mainnet_whirlpool,
+    raydium_mainnet, OpenbookV2MidOracle,
+};
 use crate::accounts_zerocopy::*;
 use crate::error::*;
-use crate::state::load_orca_pool_state;
+use crate::state::{
+    load_orca_pool_state, quote_mint_oracle, quote_mint_state_unchecked, ClmmTwapOracle,
+    OracleAggregator,
+};
 use anchor_lang::prelude::*;
 use anchor_lang::{AnchorDeserialize, Discriminator};
 use derivative::Derivative;
@@ -93,11 +99,20 @@ pub mod sol_mint_mainnet {
 #[derivative(Debug)]
 pub struct OracleConfig {
     pub conf_filter: I80F48,
+    /// Negative values disable the staleness check
     pub max_staleness_slots: i64,
+    /// Maximum age of the oracle's publish time, for oracles that report one.
+    ///
+    /// Zero means the limit is derived from max_staleness_slots, assuming 450ms per slot.
+    pub max_staleness_seconds: i64,
+    /// Stricter limits applied on top of the ones above for risk-increasing actions,
+    /// like borrowing or placing orders. Zero means no additional limit.
+    pub risk_increasing_max_staleness_slots: i64,
+    pub risk_increasing_max_staleness_seconds: i64,
     #[derivative(Debug = "ignore")]
-    pub reserved: [u8; 72],
+    pub reserved: [u8; 48],
 }
-const_assert_eq!(size_of::<OracleConfig>(), 16 + 8 + 72);
+const_assert_eq!(size_of::<OracleConfig>(), 16 + 8 * 4 + 48);
 const_assert_eq!(size_of::<OracleConfig>(), 96);
 const_assert_eq!(size_of::<OracleConfig>() % 8, 0);
 
@@ -105,6 +120,9 @@ const_assert_eq!(size_of::<OracleConfig>() % 8, 0);
 pub struct OracleConfigParams {
     pub conf_filter: f32,
     pub max_staleness_slots: Option<u32>,
+    pub max_staleness_seconds: Option<u32>,
+    pub risk_increasing_max_staleness_slots: Option<u32>,
+    pub risk_increasing_max_staleness_seconds: Option<u32>,
 }
 
 impl OracleConfigParams {
@@ -112,11 +130,27 @@ impl OracleConfigParams {
         OracleConfig {
             conf_filter: I80F48::from_num(self.conf_filter),
             max_staleness_slots: self.max_staleness_slots.map(|v| v as i64).unwrap_or(-1),
-            reserved: [0; 72],
+            max_staleness_seconds: self.max_staleness_seconds.unwrap_or(0) as i64,
+            risk_increasing_max_staleness_slots: self
+                .risk_increasing_max_staleness_slots
+                .unwrap_or(0) as i64,
+            risk_increasing_max_staleness_seconds: self
+                .risk_increasing_max_staleness_seconds
+                .unwrap_or(0) as i64,
+            reserved: [0; 48],
         }
     }
 }
 
+/// Which staleness limits of an OracleConfig apply to an oracle read
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OracleFreshness {
+    /// Health checks and liquidations
+    Health,
+    /// Actions that can increase risk, like withdrawing, borrowing or placing orders
+    RiskIncreasing,
+}
+
 #[derive(Clone, Copy, PartialEq, AnchorSerialize, AnchorDeserialize)]
 pub enum OracleType {
     Pyth,
@@ -127,6 +161,14 @@ pub enum OracleType {
     RaydiumCLMM,
     SwitchboardOnDemand,
     PythV2,
+    /// Median of several source oracles, see OracleAggregator
+    Aggregated,
+    /// Time-weighted CLMM pool price, see ClmmTwapOracle
+    ClmmTwap,
+    /// Active bin price of a meteora DLMM pair
+    MeteoraDLMM,
+    /// Mid price of an openbook v2 book, see OpenbookV2MidOracle
+    OpenbookV2Mid,
 }
 
 pub struct OracleState {
@@ -143,30 +185,79 @@ impl OracleState {
         &self,
         config: &OracleConfig,
         now: Option<(u64, u64)>, // (now_ts, now_slot)
+    ) -> Result<()> {
+        self.check_confidence_and_maybe_staleness_for(config, now, OracleFreshness::Health)
+    }
+
+    #[inline]
+    pub fn check_confidence_and_maybe_staleness_for(
+        &self,
+        config: &OracleConfig,
+        now: Option<(u64, u64)>, // (now_ts, now_slot)
+        freshness: OracleFreshness,
     ) -> Result<()> {
         if let Some((now_ts, now_slot)) = now {
-            self.check_staleness(config, now_slot, now_ts)?;
+            self.check_staleness_for(config, freshness, now_slot, now_ts)?;
         }
         self.check_confidence(config)
     }
 
     pub fn check_staleness(&self, config: &OracleConfig, now_slot: u64, now_ts: u64) -> Result<()> {
-        if config.max_staleness_slots < 0 {
-            return Ok(());
+        self.check_staleness_for(config, OracleFreshness::Health, now_slot, now_ts)
+    }
+
+    pub fn check_staleness_for(
+        &self,
+        config: &OracleConfig,
+        freshness: OracleFreshness,
+        now_slot: u64,
+        now_ts: u64,
+    ) -> Result<()> {
+        let max_slots = if config.max_staleness_slots >= 0 {
+            config.max_staleness_slots as u64
+        } else {
+            u64::MAX
+        };
+        let max_ms = if config.max_staleness_seconds > 0 {
+            (config.max_staleness_seconds as u64) * 1000
+        } else if config.max_staleness_slots >= 0 {
+            (config.max_staleness_slots as u64) * 450
+        } else {
+            u64::MAX
+        };
+        self.check_staleness_limits(max_slots, max_ms, now_slot, now_ts)?;
+
+        if freshness == OracleFreshness::RiskIncreasing {
+            let max_slots = if config.risk_increasing_max_staleness_slots > 0 {
+                config.risk_increasing_max_staleness_slots as u64
+            } else {
+                u64::MAX
+            };
+            let max_ms = if config.risk_increasing_max_staleness_seconds > 0 {
+                (config.risk_increasing_max_staleness_seconds as u64) * 1000
+            } else {
+                u64::MAX
+            };
+            self.check_staleness_limits(max_slots, max_ms, now_slot, now_ts)?;
         }
 
-        if self
-            .last_update_slot
-            .saturating_add(config.max_staleness_slots as u64)
-            < now_slot
-        {
+        Ok(())
+    }
+
+    fn check_staleness_limits(
+        &self,
+        max_staleness_slots: u64,
+        max_acceptable_update_age_in_ms: u64,
+        now_slot: u64,
+        now_ts: u64,
+    ) -> Result<()> {
+        if self.last_update_slot.saturating_add(max_staleness_slots) < now_slot {
             return Err(MangoError::OracleStale.into());
         }
 
         if self.last_update_time.is_some() {
             let current_time_in_msecs = now_ts * 1000;
             let last_update_time_in_msecs = self.last_update_time.unwrap() * 1000;
-            let max_acceptable_update_age_in_ms = (config.max_staleness_slots as u64) * 450;
 
             let oldest_acceptable_time =
                 current_time_in_msecs.saturating_sub(max_acceptable_update_age_in_ms);
@@ -211,6 +302,12 @@ pub fn determine_oracle_type(acc_info: &impl KeyedAccountReader) -> Result<Oracl
         return Ok(OracleType::Pyth);
     } else if data[0..8] == StubOracle::discriminator() {
         return Ok(OracleType::Stub);
+    } else if data[0..8] == OracleAggregator::discriminator() && acc_info.owner() == &crate::ID {
+        return Ok(OracleType::Aggregated);
+    } else if data[0..8] == ClmmTwapOracle::discriminator() && acc_info.owner() == &crate::ID {
+        return Ok(OracleType::ClmmTwap);
+    } else if data[0..8] == OpenbookV2MidOracle::discriminator() && acc_info.owner() == &crate::ID {
+        return Ok(OracleType::OpenbookV2Mid);
     }
     // https://github.com/switchboard-xyz/switchboard-v2/blob/main/libraries/rs/src/aggregator.rs#L114
     // note: disc is not public, hence the copy pasta
@@ -230,6 +327,8 @@ pub fn determine_oracle_type(acc_info: &impl KeyedAccountReader) -> Result<Oracl
         return Ok(OracleType::OrcaCLMM);
     } else if acc_info.owner() == &raydium_mainnet::ID {
         return Ok(OracleType::RaydiumCLMM);
+    } else if acc_info.owner() == &meteora_dlmm_mainnet::ID {
+        return Ok(OracleType::MeteoraDLMM);
     } else if acc_info.owner() == &pyth_solana_receiver_sdk::ID {
         return Ok(OracleType::PythV2);
     }
@@ -251,6 +350,18 @@ pub fn check_is_valid_fallback_oracle(acc_info: &impl KeyedAccountReader) -> Res
             let pool = load_raydium_pool_state(acc_info)?;
             pool.has_quote_token()
         }
+        OracleType::ClmmTwap => {
+            let twap = acc_info.load::<ClmmTwapOracle>()?;
+            quote_mint_oracle(&twap.quote_mint).is_ok()
+        }
+        OracleType::MeteoraDLMM => {
+            let pair = load_meteora_dlmm_state(acc_info)?;
+            pair.has_quote_token()
+        }
+        OracleType::OpenbookV2Mid => {
+            let mid = acc_info.load::<OpenbookV2MidOracle>()?;
+            quote_mint_oracle(&mid.quote_mint).is_ok()
+        }
         _ => true,
     };
 
@@ -424,7 +535,31 @@ fn oracle_state_unchecked_inner<T: KeyedAccountReader>(
             }
         }
         OracleType::Pyth => get_pyth_state(oracle_info, base_decimals)?,
+        OracleType::Aggregated => {
+            let aggregator = oracle_info.load::<OracleAggregator>()?;
+            let decimal_adj = power_of_ten(aggregator.mint_decimals as i8 - base_decimals as i8);
+            OracleState {
+                price: aggregator.price * decimal_adj,
+                last_update_slot: aggregator.last_update_slot,
+                deviation: aggregator.deviation * decimal_adj,
+                oracle_type: OracleType::Aggregated,
+                last_update_time: aggregator.last_update_time(),
+            }
+        }
         OracleType::PythV2 => get_pyth_on_demand_state(oracle_info, base_decimals)?,
+        OracleType::ClmmTwap => {
+            let twap = oracle_info.load::<ClmmTwapOracle>()?;
+            let quote_oracle_state = quote_mint_state_unchecked(acc_infos, &twap.quote_mint)?;
+            OracleState {
+                price: twap.price * quote_oracle_state.price,
+                last_update_slot: twap
+                    .last_update_slot
+                    .min(quote_oracle_state.last_update_slot),
+                deviation: twap.price * quote_oracle_state.deviation,
+                oracle_type: OracleType::ClmmTwap,
+                last_update_time: None,
+            }
+        }
         OracleType::SwitchboardV2 => {
             fn from_foreign_error(e: impl std::fmt::Display) -> Error {
                 error_msg!("{}", e)
@@ -533,6 +668,35 @@ fn oracle_state_unchecked_inner<T: KeyedAccountReader>(
                 last_update_time: None,
             }
         }
+        OracleType::MeteoraDLMM => {
+            let pair = load_meteora_dlmm_state(oracle_info)?;
+            let dlmm_price = pair.get_dlmm_price();
+            let quote_oracle_state = pair.quote_state_unchecked(acc_infos)?;
+            let price = dlmm_price * quote_oracle_state.price;
+            // the active bin only locates the price to within one bin step
+            let bin_deviation = price * I80F48::from_num(pair.bin_width());
+            OracleState {
+                price,
+                last_update_slot: quote_oracle_state.last_update_slot,
+                deviation: bin_deviation + dlmm_price * quote_oracle_state.deviation,
+                oracle_type: OracleType::MeteoraDLMM,
+                last_update_time: None,
+            }
+        }
+        OracleType::OpenbookV2Mid => {
+            let mid = oracle_info.load::<OpenbookV2MidOracle>()?;
+            let quote_oracle_state = quote_mint_state_unchecked(acc_infos, &mid.quote_mint)?;
+            OracleState {
+                price: mid.price * quote_oracle_state.price,
+                last_update_slot: mid
+                    .last_update_slot
+                    .min(quote_oracle_state.last_update_slot),
+                deviation: mid.deviation * quote_oracle_state.price
+                    + mid.price * quote_oracle_state.deviation,
+                oracle_type: OracleType::OpenbookV2Mid,
+                last_update_time: None,
+            }
+        }
     })
 }
 
@@ -556,6 +720,10 @@ pub fn oracle_log_context(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::state::{
+        clmm_tick_to_price, load_raydium_pool_observation_key, DLMMPoolState, RaydiumObservations,
+        RAYDIUM_OBSERVATION_NUM,
+    };
     use solana_program_test::{find_file, read_file};
     use std::{cell::RefCell, path::PathBuf, str::FromStr};
 
@@ -890,6 +1058,78 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    pub fn test_meteora_dlmm_price() {
+        let mint = Pubkey::new_unique();
+        let mut pair = DLMMPoolState {
+            active_id: 100,
+            bin_step: 10,
+            token_mint_x: mint,
+            token_mint_y: usdc_mint_mainnet::ID,
+        };
+        let price = 1.001f64.powi(100);
+        assert!(!pair.is_inverted());
+        assert_eq!(pair.quote_mint(), usdc_mint_mainnet::ID);
+        assert!((pair.get_dlmm_price().to_num::<f64>() - price).abs() < 1e-9);
+        assert_eq!(
+            pair.get_quote_oracle().unwrap(),
+            pyth_mainnet_usdc_oracle::ID
+        );
+
+        pair.token_mint_x = usdc_mint_mainnet::ID;
+        pair.token_mint_y = mint;
+        assert!(pair.is_inverted());
+        assert_eq!(pair.quote_mint(), usdc_mint_mainnet::ID);
+        assert!((pair.get_dlmm_price().to_num::<f64>() - 1.0 / price).abs() < 1e-9);
+
+        pair.token_mint_x = Pubkey::new_unique();
+        assert!(!pair.has_quote_token());
+        assert!(pair.get_quote_oracle().is_err());
+    }
+
+    #[test]
+    pub fn test_raydium_twap() -> Result<()> {
+        let key = "Ds33rQ1d4AXwxqyeXX6Pc3G4pFNr6iWb3dd8YfBBQMPr";
+        let mut pool_data = read_file(find_file(&format!("resources/test/{}.bin", key)).unwrap());
+        let data = RefCell::new(&mut pool_data[..]);
+        let ai = &AccountInfoRef {
+            key: &Pubkey::from_str(key).unwrap(),
+            owner: &raydium_mainnet::ID,
+            data: data.borrow(),
+        };
+        assert_eq!(
+            load_raydium_pool_observation_key(ai)?,
+            Pubkey::from_str("GaWU7rFzxH6r5mDngd3hBgJCTeK9JSFB4PDEwnUhAgRz").unwrap()
+        );
+
+        // observations every 15s at a constant tick, wrapping around the ring buffer
+        let tick = -24825i64;
+        let mut observations = vec![(0u32, 0i64); RAYDIUM_OBSERVATION_NUM];
+        for k in 0..5 {
+            let ts = 1000 + 15 * k as u32;
+            observations[(RAYDIUM_OBSERVATION_NUM - 3 + k) % RAYDIUM_OBSERVATION_NUM] =
+                (ts, tick * ts as i64);
+        }
+        let obs = RaydiumObservations {
+            pool: *ai.key,
+            latest_index: 1,
+            observations,
+        };
+
+        let (avg_tick, window) = obs.twap_tick(30)?;
+        assert_eq!(avg_tick, tick as f64);
+        assert_eq!(window, 30);
+        let (_, window) = obs.twap_tick(20)?;
+        assert_eq!(window, 30);
+        assert!(obs.twap_tick(61).is_err());
+
+        // SOL in USDC, in native units
+        let price = clmm_tick_to_price(avg_tick);
+        assert!((price - 0.0835).abs() < 0.001);
+
+        Ok(())
+    }
+
     #[test]
     fn use_time_for_max_staleness_check() {
         let fixtures = vec![
@@ -905,7 +1145,10 @@ mod tests {
         let config = OracleConfig {
             conf_filter: Default::default(),
             max_staleness_slots: 100,
-            reserved: [0; 72],
+            max_staleness_seconds: 0,
+            risk_increasing_max_staleness_slots: 0,
+            risk_increasing_max_staleness_seconds: 0,
+            reserved: [0; 48],
         };
         for (now_ts, publish_ts, expect_error) in fixtures {
             let now_slot = 0;
@@ -925,4 +1168,115 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn explicit_and_risk_increasing_staleness() {
+        let config = OracleConfig {
+            conf_filter: I80F48::from_num(0.1),
+            max_staleness_slots: 100,
+            max_staleness_seconds: 120,
+            risk_increasing_max_staleness_slots: 10,
+            risk_increasing_max_staleness_seconds: 20,
+            reserved: [0; 48],
+        };
+        let now_ts = 100_000;
+        let now_slot = 1_000;
+
+        // (last_update_slot, publish_ts, health error, risk-increasing error)
+        let fixtures = vec![
+            (now_slot, now_ts, false, false),
+            (now_slot - 10, now_ts - 20, false, false),
+            (now_slot - 11, now_ts, false, true),
+            (now_slot, now_ts - 21, false, true),
+            // explicit seconds replace the 45s derived from max_staleness_slots
+            (now_slot, now_ts - 100, false, true),
+            (now_slot, now_ts - 121, true, true),
+            (now_slot - 101, now_ts, true, true),
+        ];
+        for (last_update_slot, publish_ts, health_error, risk_error) in fixtures {
+            let state = OracleState {
+                price: I80F48::ONE,
+                deviation: I80F48::ZERO,
+                last_update_slot,
+                last_update_time: Some(publish_ts),
+                oracle_type: OracleType::PythV2,
+            };
+            println!("test case: {}, {}", last_update_slot, publish_ts);
+            assert_eq!(
+                health_error,
+                state
+                    .check_staleness_for(&config, OracleFreshness::Health, now_slot, now_ts)
+                    .is_err()
+            );
+            assert_eq!(
+                risk_error,
+                state
+                    .check_staleness_for(&config, OracleFreshness::RiskIncreasing, now_slot, now_ts)
+                    .is_err()
+            );
+        }
+
+        // risk-increasing limits apply even if the general check is disabled
+        let config = OracleConfig {
+            max_staleness_slots: -1,
+            max_staleness_seconds: 0,
+            ..config
+        };
+        let state = OracleState {
+            price: I80F48::ONE,
+            deviation: I80F48::ZERO,
+            last_update_slot: now_slot - 50,
+            last_update_time: None,
+            oracle_type: OracleType::Pyth,
+        };
+        assert!(state.check_staleness(&config, now_slot, now_ts).is_ok());
+        assert!(state
+            .check_staleness_for(&config, OracleFreshness::RiskIncreasing, now_slot, now_ts)
+            .is_err());
+    }
+    #[test]
+    fn aggregated_oracle_staleness() -> Result<()> {
+        let now_ts = 100_000;
+        let now_slot = 1_000;
+        let config = OracleConfig {
+            conf_filter: I80F48::from_num(0.1),
+            max_staleness_slots: 100,
+            max_staleness_seconds: 60,
+            risk_increasing_max_staleness_slots: 0,
+            risk_increasing_max_staleness_seconds: 0,
+            reserved: [0; 48],
+        };
+
+        // (oldest source publish time, stale)
+        let fixtures = vec![(now_ts - 30, false), (now_ts - 61, true), (0, false)];
+        for (publish_ts, stale) in fixtures {
+            let mut aggregator: OracleAggregator = bytemuck::Zeroable::zeroed();
+            aggregator.price = I80F48::ONE;
+            aggregator.mint_decimals = 6;
+            aggregator.last_update_slot = now_slot;
+            aggregator.oldest_source_publish_ts = publish_ts;
+
+            let mut data = OracleAggregator::discriminator().to_vec();
+            data.extend_from_slice(bytemuck::bytes_of(&aggregator));
+            let data = RefCell::new(&mut data[..]);
+            let ai = &AccountInfoRef {
+                key: &Pubkey::new_unique(),
+                owner: &crate::ID,
+                data: data.borrow(),
+            };
+
+            let state = oracle_state_unchecked(&OracleAccountInfos::from_reader(ai), 6)?;
+            assert!(state.oracle_type == OracleType::Aggregated);
+            assert_eq!(
+                state.last_update_time,
+                (publish_ts > 0).then_some(publish_ts)
+            );
+            assert_eq!(
+                stale,
+                state.check_staleness(&config, now_slot, now_ts).is_err()
+            );
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,266 @@
+use std::mem::size_of;
+
+use anchor_lang::prelude::*;
+use fixed::types::I80F48;
+use static_assertions::const_assert_eq;
+
+use crate::accounts_zerocopy::*;
+use crate::error::*;
+
+use super::{
+    oracle_state_unchecked, pyth_mainnet_sol_oracle, pyth_mainnet_usdc_oracle, OracleAccountInfos,
+    OracleConfig,
+};
+
+pub const MAX_AGGREGATED_ORACLES: usize = 8;
+
+/// An oracle whose price is the median of several source oracles.
+///
+/// Banks and perp markets use it like any other oracle by setting it as their `oracle`.
+/// The price is refreshed permissionlessly with oracle_aggregator_update, which reads
+/// all source oracles, drops the stale ones and the ones that diverge too much from the
+/// median, and only publishes a price if at least `min_quorum` sources remain.
+#[account(zero_copy)]
+pub struct OracleAggregator {
+    // ABI: Clients rely on this being at offset 8
+    pub group: Pubkey,
+    // ABI: Clients rely on this being at offset 40
+    pub mint: Pubkey,
+
+    /// Price of one native base token in native quote tokens, for `mint_decimals`
+    pub price: I80F48,
+
+    /// Largest difference between the median and a source that was used
+    pub deviation: I80F48,
+
+    /// Oldest update slot of the sources that were used
+    pub last_update_slot: u64,
+    pub last_update_ts: u64,
+
+    /// Oldest publish time of the sources that were used and report one, 0 if none do
+    pub oldest_source_publish_ts: u64,
+
+    /// Confidence and staleness limits that each source must pass to be used
+    pub source_oracle_config: OracleConfig,
+
+    /// Sources further than this fraction from the median are ignored
+    pub max_divergence: f64,
+
+    /// Minimum number of fresh, agreeing sources needed to publish a price
+    pub min_quorum: u8,
+
+    pub num_oracles: u8,
+
+    pub mint_decimals: u8,
+
+    pub padding: [u8; 5],
+
+    /// Source oracles, only the first num_oracles are used
+    pub oracles: [Pubkey; MAX_AGGREGATED_ORACLES],
+
+    pub reserved: [u8; 120],
+}
+const_assert_eq!(
+    size_of::<OracleAggregator>(),
+    32 + 32 + 16 + 16 + 8 + 8 + 8 + 96 + 8 + 1 + 1 + 1 + 5 + 32 * MAX_AGGREGATED_ORACLES + 120
+);
+const_assert_eq!(size_of::<OracleAggregator>(), 608);
+const_assert_eq!(size_of::<OracleAggregator>() % 8, 0);
+
+impl OracleAggregator {
+    pub fn source_oracles(&self) -> &[Pubkey] {
+        &self.oracles[..self.num_oracles as usize]
+    }
+
+    /// Publish time for the staleness check, see oldest_source_publish_ts
+    pub fn last_update_time(&self) -> Option<u64> {
+        (self.oldest_source_publish_ts > 0).then_some(self.oldest_source_publish_ts)
+    }
+
+    pub fn set_source_oracles(&mut self, oracles: &[Pubkey]) -> Result<()> {
+        require_msg!(
+            !oracles.is_empty() && oracles.len() <= MAX_AGGREGATED_ORACLES,
+            "need between 1 and {} source oracles, got {}",
+            MAX_AGGREGATED_ORACLES,
+            oracles.len()
+        );
+        self.oracles = [Pubkey::default(); MAX_AGGREGATED_ORACLES];
+        self.oracles[..oracles.len()].copy_from_slice(oracles);
+        self.num_oracles = oracles.len() as u8;
+        Ok(())
+    }
+
+    /// Reads all source oracles from `ais` and publishes the aggregated price.
+    ///
+    /// The first num_oracles entries of `ais` must be the source oracles in order.
+    /// Any further accounts may provide the usdc and sol feeds needed by CLMM sources.
+    pub fn update<T: KeyedAccountReader>(
+        &mut self,
+        ais: &[T],
+        now_ts: u64,
+        now_slot: u64,
+    ) -> Result<()> {
+        let n = self.num_oracles as usize;
+        require_msg!(
+            ais.len() >= n,
+            "expected {} source oracle accounts, got {}",
+            n,
+            ais.len()
+        );
+        let usdc_opt = ais
+            .iter()
+            .find(|a| a.key() == &pyth_mainnet_usdc_oracle::ID);
+        let sol_opt = ais.iter().find(|a| a.key() == &pyth_mainnet_sol_oracle::ID);
+
+        let mut samples = Vec::with_capacity(n);
+        for (ai, expected_key) in ais[..n].iter().zip(self.source_oracles()) {
+            require_keys_eq!(*ai.key(), *expected_key);
+            let acc_infos = OracleAccountInfos {
+                oracle: ai,
+                fallback_opt: None,
+                usdc_opt,
+                sol_opt,
+            };
+            let state = match oracle_state_unchecked(&acc_infos, self.mint_decimals) {
+                Ok(state) => state,
+                Err(e) => {
+                    msg!("skipping source oracle {}: {:?}", ai.key(), e);
+                    continue;
+                }
+            };
+            if let Err(e) = state.check_confidence_and_maybe_staleness(
+                &self.source_oracle_config,
+                Some((now_ts, now_slot)),
+            ) {
+                msg!("skipping source oracle {}: {:?}", ai.key(), e);
+                continue;
+            }
+            samples.push((state.price, state.last_update_slot, state.last_update_time));
+        }
+
+        let (price, deviation, last_update_slot, last_update_time) = aggregate_oracle_prices(
+            &mut samples,
+            self.min_quorum,
+            I80F48::from_num(self.max_divergence),
+        )?;
+        self.price = price;
+        self.deviation = deviation;
+        self.last_update_slot = last_update_slot;
+        self.last_update_ts = now_ts;
+        self.oldest_source_publish_ts = last_update_time.unwrap_or(0);
+        Ok(())
+    }
+}
+
+/// Returns (median price, max distance to the median, oldest slot, oldest publish time)
+/// of the samples that are within `max_divergence` of the median of all samples.
+///
+/// Samples are (price, last_update_slot, last_update_time). Fails if fewer than
+/// `min_quorum` samples agree with the median.
+pub fn aggregate_oracle_prices(
+    samples: &mut [(I80F48, u64, Option<u64>)],
+    min_quorum: u8,
+    max_divergence: I80F48,
+) -> Result<(I80F48, I80F48, u64, Option<u64>)> {
+    let min_quorum = (min_quorum as usize).max(1);
+    require_msg_typed!(
+        samples.len() >= min_quorum,
+        MangoError::OracleQuorumNotReached,
+        "only {} usable source oracles, need {}",
+        samples.len(),
+        min_quorum
+    );
+
+    samples.sort_by_key(|(price, _, _)| *price);
+    let mid = samples.len() / 2;
+    let median = if samples.len() % 2 == 0 {
+        (samples[mid - 1].0 + samples[mid].0) / 2
+    } else {
+        samples[mid].0
+    };
+
+    let max_distance = median.abs() * max_divergence;
+    let mut agreeing = 0;
+    let mut deviation = I80F48::ZERO;
+    let mut last_update_slot = u64::MAX;
+    let mut last_update_time: Option<u64> = None;
+    for (price, slot, time) in samples.iter() {
+        let distance = (*price - median).abs();
+        if distance > max_distance {
+            continue;
+        }
+        agreeing += 1;
+        deviation = deviation.max(distance);
+        last_update_slot = last_update_slot.min(*slot);
+        if let Some(time) = time {
+            last_update_time = Some(last_update_time.map_or(*time, |t| t.min(*time)));
+        }
+    }
+    require_msg_typed!(
+        agreeing >= min_quorum,
+        MangoError::OracleQuorumNotReached,
+        "only {} of {} source oracles agree with the median {}, need {}",
+        agreeing,
+        samples.len(),
+        median,
+        min_quorum
+    );
+
+    Ok((median, deviation, last_update_slot, last_update_time))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(price: f64, slot: u64) -> (I80F48, u64, Option<u64>) {
+        (I80F48::from_num(price), slot, None)
+    }
+
+    fn timed_sample(price: f64, slot: u64, time: u64) -> (I80F48, u64, Option<u64>) {
+        (I80F48::from_num(price), slot, Some(time))
+    }
+
+    #[test]
+    fn test_aggregate_oracle_prices() {
+        let div = I80F48::from_num(0.05);
+
+        // odd number of samples: middle value, outlier ignored for deviation and slot
+        let mut s = vec![sample(1.0, 10), sample(1.02, 12), sample(5.0, 1)];
+        let (price, deviation, slot, time) = aggregate_oracle_prices(&mut s, 2, div).unwrap();
+        assert_eq!(price, I80F48::from_num(1.02));
+        assert!((deviation - I80F48::from_num(0.02)).abs() < 0.0001);
+        assert_eq!(slot, 10);
+        assert_eq!(time, None);
+
+        // oldest publish time of the used samples that have one, outlier ignored
+        let mut s = vec![
+            timed_sample(1.0, 10, 1000),
+            sample(1.01, 8),
+            timed_sample(1.02, 12, 900),
+            timed_sample(5.0, 1, 100),
+        ];
+        let (_, _, slot, time) = aggregate_oracle_prices(&mut s, 3, div).unwrap();
+        assert_eq!(slot, 8);
+        assert_eq!(time, Some(900));
+
+        // even number of samples: average of the middle values
+        let mut s = vec![
+            sample(2.0, 5),
+            sample(1.0, 5),
+            sample(1.02, 5),
+            sample(1.04, 5),
+        ];
+        let (price, _, _, _) = aggregate_oracle_prices(&mut s, 3, div).unwrap();
+        assert!((price - I80F48::from_num(1.03)).abs() < 0.0001);
+
+        // the outlier means the quorum isn't reached
+        let mut s = vec![sample(1.0, 10), sample(1.5, 12), sample(5.0, 1)];
+        assert!(aggregate_oracle_prices(&mut s, 2, div).is_err());
+
+        // not enough samples
+        let mut s = vec![sample(1.0, 10)];
+        assert!(aggregate_oracle_prices(&mut s, 2, div).is_err());
+        assert!(aggregate_oracle_prices(&mut [], 0, div).is_err());
+    }
+}
@@ -0,0 +1,254 @@
+use crate::logs::{emit_stack, FilledPerpOrderLog};
+use crate::state::{EventQueue, PerpFeeTier, PerpMarket};
+use anchor_lang::prelude::*;
+use bytemuck::cast;
+use fixed::types::I80F48;
+
+use super::*;
+
+/// Returns the uniform clearing price and the quantity executed at it, or None if
+/// the orders don't cross.
+///
+/// `bids` are (price_lots, quantity) in descending and `asks` in ascending price order.
+/// The price maximizes the executed quantity. Ties are broken by the smallest
+/// imbalance between bid and ask quantity at the price, then by the distance to
+/// `reference_price_lots`, then by the lower price.
+pub fn opening_auction_clearing_price(
+    bids: &[(i64, i64)],
+    asks: &[(i64, i64)],
+    reference_price_lots: i64,
+) -> Option<(i64, i64)> {
+    let mut prices: Vec<i64> = bids.iter().chain(asks.iter()).map(|&(p, _)| p).collect();
+    prices.sort_unstable();
+    prices.dedup();
+
+    let total_bid_quantity: i64 = bids.iter().map(|&(_, q)| q).sum();
+    let mut bid_quantity_below = 0;
+    let mut bid_index = bids.len();
+    let mut ask_quantity = 0;
+    let mut ask_index = 0;
+
+    // (price, quantity, imbalance, distance to reference)
+    let mut best: Option<(i64, i64, i64, i64)> = None;
+    for price in prices {
+        while bid_index > 0 && bids[bid_index - 1].0 < price {
+            bid_quantity_below += bids[bid_index - 1].1;
+            bid_index -= 1;
+        }
+        while ask_index < asks.len() && asks[ask_index].0 <= price {
+            ask_quantity += asks[ask_index].1;
+            ask_index += 1;
+        }
+        let bid_quantity = total_bid_quantity - bid_quantity_below;
+
+        let quantity = bid_quantity.min(ask_quantity);
+        if quantity == 0 {
+            continue;
+        }
+        let imbalance = (bid_quantity - ask_quantity).abs();
+        let distance = (price - reference_price_lots).abs();
+        let is_better = best.map_or(true, |(_, best_q, best_imbalance, best_distance)| {
+            (quantity, -imbalance, -distance) > (best_q, -best_imbalance, -best_distance)
+        });
+        if is_better {
+            best = Some((price, quantity, imbalance, distance));
+        }
+    }
+    best.map(|(price, quantity, _, _)| (price, quantity))
+}
+
+impl<'a> Orderbook<'a> {
+    /// Executes the crossing orders of an opening auction at a uniform price.
+    ///
+    /// The clearing price is computed on the first call and stored on the market, so
+    /// later calls keep using it when `limit` or the event queue space didn't allow
+    /// executing everything at once. Orders fill in price-time priority.
+    ///
+    /// Both sides of a fill were resting orders. The one that was placed later would
+    /// have crossed the book as a taker outside of the auction, so it pays its tier's
+    /// taker fee while the other side pays the maker fee. That way maker rebates stay
+    /// funded by taker fees like in regular matching.
+    ///
+    /// Returns true once no crossing orders remain.
+    pub fn clear_opening_auction(
+        &mut self,
+        market: &mut PerpMarket,
+        event_queue: &mut EventQueue,
+        oracle_price: I80F48,
+        now_ts: u64,
+        limit: u8,
+        fee_tiers: &[PerpFeeTier],
+    ) -> Result<bool> {
+        let oracle_price_lots = market.native_price_to_lot(oracle_price);
+
+        if market.opening_auction_price_lots == 0 {
+            let best_bid = self.bids.best_price(now_ts, oracle_price_lots);
+            let best_ask = self.asks.best_price(now_ts, oracle_price_lots);
+            let (best_bid, best_ask) = match (best_bid, best_ask) {
+                (Some(bid), Some(ask)) if bid >= ask => (bid, ask),
+                _ => return Ok(true),
+            };
+            let bids: Vec<(i64, i64)> = self
+                .bids
+                .iter_valid(now_ts, oracle_price_lots)
+                .take_while(|o| o.price_lots >= best_ask)
+                .map(|o| (o.price_lots, o.node.quantity))
+                .collect();
+            let asks: Vec<(i64, i64)> = self
+                .asks
+                .iter_valid(now_ts, oracle_price_lots)
+                .take_while(|o| o.price_lots <= best_bid)
+                .map(|o| (o.price_lots, o.node.quantity))
+                .collect();
+            match opening_auction_clearing_price(&bids, &asks, oracle_price_lots) {
+                Some((price_lots, _)) => market.opening_auction_price_lots = price_lots,
+                None => return Ok(true),
+            }
+        }
+        let price_lots = market.opening_auction_price_lots;
+
+        let bids: Vec<(BookSideOrderHandle, LeafNode)> = self
+            .bids
+            .iter_valid(now_ts, oracle_price_lots)
+            .take_while(|o| o.price_lots >= price_lots)
+            .map(|o| (o.handle, *o.node))
+            .collect();
+        let asks: Vec<(BookSideOrderHandle, LeafNode)> = self
+            .asks
+            .iter_valid(now_ts, oracle_price_lots)
+            .take_while(|o| o.price_lots <= price_lots)
+            .map(|o| (o.handle, *o.node))
+            .collect();
+        let bid_quantity: i64 = bids.iter().map(|(_, o)| o.quantity).sum();
+        let ask_quantity: i64 = asks.iter().map(|(_, o)| o.quantity).sum();
+        let mut remaining_quantity = bid_quantity.min(ask_quantity);
+
+        let mut bids_filled = vec![0i64; bids.len()];
+        let mut asks_filled = vec![0i64; asks.len()];
+        let mut bid_index = 0;
+        let mut ask_index = 0;
+        let mut fills = 0;
+        while remaining_quantity > 0 && fills < limit && !event_queue.full() {
+            let bid = &bids[bid_index].1;
+            let ask = &asks[ask_index].1;
+            let quantity = (bid.quantity - bids_filled[bid_index])
+                .min(ask.quantity - asks_filled[ask_index])
+                .min(remaining_quantity);
+            bids_filled[bid_index] += quantity;
+            asks_filled[ask_index] += quantity;
+            remaining_quantity -= quantity;
+            let bid_out = bids_filled[bid_index] == bid.quantity;
+            let ask_out = asks_filled[ask_index] == ask.quantity;
+
+            // no fees on self trades, like with SelfTradeBehavior::DecrementTake
+            let bid_is_later =
+                node_key_seq_num(Side::Bid, bid.key) > node_key_seq_num(Side::Ask, ask.key);
+            let (bid_fee, ask_fee) = if bid.owner == ask.owner {
+                (I80F48::ZERO, I80F48::ZERO)
+            } else if bid_is_later {
+                (
+                    market.taker_fee_for_tier(fee_tiers, bid.fee_tier),
+                    market.maker_fee_for_tier(fee_tiers, ask.fee_tier),
+                )
+            } else {
+                (
+                    market.maker_fee_for_tier(fee_tiers, bid.fee_tier),
+                    market.taker_fee_for_tier(fee_tiers, ask.fee_tier),
+                )
+            };
+            // Accrue fees immediately, see apply_fees()
+            let quote_native = I80F48::from(market.quote_lot_size * price_lots * quantity);
+            market.fees_accrued += quote_native * (bid_fee + ask_fee);
+
+            let seq_num = event_queue.header.seq_num;
+            let fill = FillEvent::new_auction(
+                bid, bid_out, bid_fee, ask, ask_out, ask_fee, now_ts, seq_num, price_lots, quantity,
+            );
+            event_queue.push_back(cast(fill)).unwrap();
+            fills += 1;
+
+            emit_stack(FilledPerpOrderLog {
+                mango_group: market.group.key(),
+                perp_market_index: market.perp_market_index,
+                seq_num,
+            });
+
+            if bid_out {
+                bid_index += 1;
+            }
+            if ask_out {
+                ask_index += 1;
+            }
+        }
+
+        for (side, orders, filled) in [
+            (Side::Bid, &bids, &bids_filled),
+            (Side::Ask, &asks, &asks_filled),
+        ] {
+            let bookside = self.bookside_mut(side);
+            // Apply changes before deletes: handles are invalidated on delete
+            for ((handle, order), &filled) in orders.iter().zip(filled.iter()) {
+                if filled > 0 && filled < order.quantity {
+                    bookside
+                        .node_mut(handle.node)
+                        .unwrap()
+                        .as_leaf_mut()
+                        .unwrap()
+                        .quantity -= filled;
+                }
+            }
+            for ((handle, order), &filled) in orders.iter().zip(filled.iter()) {
+                if filled == order.quantity {
+                    let _removed_leaf = bookside
+                        .remove_by_key(handle.order_tree, order.key)
+                        .unwrap();
+                }
+            }
+        }
+
+        Ok(remaining_quantity == 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opening_auction_clearing_price() {
+        // no cross
+        assert_eq!(
+            opening_auction_clearing_price(&[(99, 10)], &[(100, 10)], 100),
+            None
+        );
+
+        // the price between 100 and 102 that executes the most
+        let bids = [(105, 5), (102, 10), (100, 10)];
+        let asks = [(98, 10), (101, 10), (104, 20)];
+        // at 101: bids 15, asks 20; at 102: bids 15, asks 20; at 100: bids 25, asks 10
+        // 101 and 102 both execute 15 with imbalance 5, 101 is closer to the reference
+        assert_eq!(
+            opening_auction_clearing_price(&bids, &asks, 100),
+            Some((101, 15))
+        );
+        assert_eq!(
+            opening_auction_clearing_price(&bids, &asks, 110),
+            Some((102, 15))
+        );
+
+        // imbalance breaks ties before the reference price
+        let bids = [(103, 5), (101, 5)];
+        let asks = [(100, 5), (102, 10)];
+        // all prices execute 5, 100 and 101 with imbalance 5, 102 and 103 with 10
+        assert_eq!(
+            opening_auction_clearing_price(&bids, &asks, 103),
+            Some((101, 5))
+        );
+
+        // same price on both sides
+        assert_eq!(
+            opening_auction_clearing_price(&[(100, 3)], &[(100, 5)], 50),
+            Some((100, 3))
+        );
+    }
+}
@@ -1,7 +1,8 @@
 use crate::error::*;
 use crate::logs::{emit_stack, FilledPerpOrderLog, PerpTakerTradeLog};
 use crate::state::{
-    orderbook::bookside::*, EventQueue, MangoAccountRefMut, PerpMarket, PerpMarketIndex,
+    orderbook::bookside::*, EventQueue, MangoAccountRefMut, PerpFeeTier, PerpMarket,
+    PerpMarketIndex, FREE_ORDER_SLOT,
 };
 use anchor_lang::prelude::*;
 use bytemuck::cast;
@@ -41,6 +42,35 @@ impl<'a> Orderbook<'a> {
 
     #[allow(clippy::too_many_arguments)]
     pub fn new_order(
+        &mut self,
+        order: Order,
+        perp_market: &mut PerpMarket,
+        event_queue: &mut EventQueue,
+        oracle_price: I80F48,
+        mango_account: &mut MangoAccountRefMut,
+        mango_account_pk: &Pubkey,
+        now_ts: u64,
+        limit: u8,
+        fee_tiers: &[PerpFeeTier],
+    ) -> std::result::Result<Option<u128>, Error> {
+        self.new_order_in_slot(
+            order,
+            perp_market,
+            event_queue,
+            oracle_price,
+            mango_account,
+            mango_account_pk,
+            now_ts,
+            limit,
+            fee_tiers,
+            None,
+        )
+    }
+
+    /// Like new_order(), but if the order is posted it uses the given free open order
+    /// slot instead of the first free one.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_order_in_slot(
         &mut self,
         order: Order,
         perp_market: &mut PerpMarket,
@@ -50,6 +80,8 @@ impl<'a> Orderbook<'a> {
         mango_account_pk: &Pubkey,
         now_ts: u64,
         mut limit: u8,
+        fee_tiers: &[PerpFeeTier],
+        owner_slot: Option<usize>,
     ) -> std::result::Result<Option<u128>, Error> {
         let side = order.side;
         let other_side = side.invert_side();
@@ -57,6 +89,14 @@ impl<'a> Orderbook<'a> {
         let oracle_price_lots = market.native_price_to_lot(oracle_price);
         let post_only = order.is_post_only();
         let mut post_target = order.post_target();
+
+        // During an opening auction orders are only collected, see clear_opening_auction()
+        let in_auction = market.is_in_opening_auction();
+        require!(
+            !in_auction || post_target.is_some(),
+            MangoError::PerpOpeningAuctionOrderNotPostable
+        );
+
         let (price_lots, price_data) = order.price(now_ts, oracle_price_lots, self)?;
 
         // generate new order id
@@ -69,6 +109,11 @@ impl<'a> Orderbook<'a> {
             I80F48::ZERO
         };
 
+        // The account's fee tier applies to its taker fills now, and to its maker fills
+        // if the remainder gets posted to the book
+        let fee_tier = mango_account.fixed.perp_fee_tier(fee_tiers, now_ts);
+        let taker_fee = market.taker_fee_for_tier(fee_tiers, fee_tier);
+
         let perp_position = mango_account.perp_position_mut(market.perp_market_index)?;
 
         // Iterate through book and match against this new order.
@@ -79,13 +124,14 @@ impl<'a> Orderbook<'a> {
         let mut remaining_quote_lots = order.max_quote_lots;
         let mut decremented_base_lots = 0i64;
         let mut decremented_quote_lots = 0i64;
+        let mut maker_fees = I80F48::ZERO;
         let mut orders_to_change: Vec<(BookSideOrderHandle, i64)> = vec![];
         let mut orders_to_delete: Vec<(BookSideOrderTree, u128)> = vec![];
         let mut number_of_dropped_expired_orders = 0;
         let opposing_bookside = self.bookside_mut(other_side);
         for best_opposing in opposing_bookside.iter_all_including_invalid(now_ts, oracle_price_lots)
         {
-            if remaining_base_lots == 0 || remaining_quote_lots == 0 {
+            if in_auction || remaining_base_lots == 0 || remaining_quote_lots == 0 {
                 break;
             }
 
@@ -171,6 +217,11 @@ impl<'a> Orderbook<'a> {
             }
 
             // order_would_self_trade is only true in the DecrementTake case, in which we don't charge fees
+            let maker_fee_tier = best_opposing.node.fee_tier;
+            let maker_fee = market.maker_fee_for_tier(fee_tiers, maker_fee_tier);
+            if !order_would_self_trade {
+                maker_fees += I80F48::from(market.quote_lot_size * match_quote_lots) * maker_fee;
+            }
             let seq_num = event_queue.header.seq_num;
             let fill = FillEvent::new(
                 side,
@@ -184,8 +235,9 @@ impl<'a> Orderbook<'a> {
                 if order_would_self_trade {
                     I80F48::ZERO
                 } else {
-                    market.maker_fee
+                    maker_fee
                 },
+                maker_fee_tier,
                 best_opposing.node.timestamp,
                 *mango_account_pk,
                 order.client_order_id,
@@ -193,8 +245,9 @@ impl<'a> Orderbook<'a> {
                     I80F48::ZERO
                 } else {
                     // NOTE: this does not include the IOC penalty, but this value is not used to calculate fees
-                    market.taker_fee
+                    taker_fee
                 },
+                fee_tier,
                 best_opposing_price,
                 match_base_lots,
             );
@@ -221,6 +274,8 @@ impl<'a> Orderbook<'a> {
                 market,
                 mango_account,
                 total_quote_lots_taken - decremented_quote_lots,
+                taker_fee,
+                maker_fees,
             )?;
             emit_stack(PerpTakerTradeLog {
                 mango_group: market.group.key(),
@@ -298,7 +353,14 @@ impl<'a> Orderbook<'a> {
                 event_queue.push_back(cast(event)).unwrap();
             }
 
-            let owner_slot = mango_account.perp_next_order_slot()?;
+            let owner_slot = match owner_slot {
+                Some(slot) => {
+                    let oo = mango_account.perp_order_by_raw_index(slot)?;
+                    require_eq!(oo.market, FREE_ORDER_SLOT);
+                    slot
+                }
+                None => mango_account.perp_next_order_slot()?,
+            };
             let new_order = LeafNode::new(
                 owner_slot as u8,
                 order_id,
@@ -309,6 +371,7 @@ impl<'a> Orderbook<'a> {
                 order.time_in_force,
                 order.peg_limit(),
                 order.client_order_id,
+                fee_tier,
             );
             let _result = bookside.insert_leaf(order_tree_target, &new_order)?;
 
@@ -438,6 +501,48 @@ impl<'a> Orderbook<'a> {
         Ok(())
     }
 
+    /// Reduces the quantity of the order in an open order slot to `new_quantity`.
+    ///
+    /// The order keeps its position in the OrderTree and thus its time priority.
+    pub fn decrease_order_quantity_by_slot(
+        &mut self,
+        mango_account: &mut MangoAccountRefMut,
+        mango_account_pk: &Pubkey,
+        slot: usize,
+        perp_market_index: PerpMarketIndex,
+        new_quantity: i64,
+    ) -> Result<()> {
+        let oo = mango_account.perp_order_by_raw_index(slot)?;
+        if !oo.is_active_for_market(perp_market_index) {
+            return Err(error_msg_typed!(
+                MangoError::SomeError,
+                "perp orders at slot {slot} is not active for perp market {perp_market_index}"
+            ));
+        }
+
+        let side_and_tree = oo.side_and_tree();
+        let side = side_and_tree.side();
+        let book_component = side_and_tree.order_tree();
+        let order_id = oo.id;
+        let bookside = self.bookside_mut(side);
+        let (handle, _) = bookside
+            .find_by_key(book_component, order_id)
+            .ok_or_else(|| {
+                error_msg_typed!(MangoError::PerpOrderIdNotFound, "no perp order with id {order_id}, side {side:?}, component {book_component:?} found on the orderbook")
+            })?;
+        let leaf_node = bookside.node_mut(handle).unwrap().as_leaf_mut().unwrap();
+        require_eq!(leaf_node.owner_slot as usize, slot);
+        require_keys_eq!(leaf_node.owner, *mango_account_pk);
+        require_gt!(new_quantity, 0);
+        require_gt!(leaf_node.quantity, new_quantity);
+
+        let decrease = leaf_node.quantity - new_quantity;
+        leaf_node.quantity = new_quantity;
+        mango_account.reduce_perp_order_quantity(slot, decrease)?;
+
+        Ok(())
+    }
+
     /// Cancels an order on a side, removing it from the book and the mango account orders list
     pub fn cancel_order_by_id(
         &mut self,
@@ -463,18 +568,20 @@ impl<'a> Orderbook<'a> {
 
 /// Apply taker fees to the taker account and update the markets' fees_accrued for
 /// both the maker and taker fees.
+///
+/// The maker fees are the sum over all fills, since each maker may be in a different
+/// fee tier. They apply to the maker's account only when the fill event is consumed.
 fn apply_fees(
     market: &mut PerpMarket,
     account: &mut MangoAccountRefMut,
     quote_lots: i64,
+    taker_fee: I80F48,
+    maker_fees: I80F48,
 ) -> Result<I80F48> {
     assert!(quote_lots >= 0);
     let quote_native = I80F48::from_num(market.quote_lot_size * quote_lots);
 
-    // The maker fees apply to the maker's account only when the fill event is consumed.
-    let maker_fees = quote_native * market.maker_fee;
-
-    let taker_fees = quote_native * market.taker_fee;
+    let taker_fees = quote_native * taker_fee;
 
     // taker fees should never be negative
     require_gte!(taker_fees, 0);
@@ -1,8 +1,12 @@
 use anchor_lang::prelude::*;
+use anchor_lang::{Discriminator, ZeroCopy};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use static_assertions::const_assert_eq;
+use std::cell::{Ref, RefMut};
 
 use super::*;
+use crate::error::MangoError;
+use crate::error_msg;
 
 #[derive(
     Eq,
@@ -27,21 +31,192 @@ pub struct BookSideOrderHandle {
     pub order_tree: BookSideOrderTree,
 }
 
-#[account(zero_copy)]
-pub struct BookSide {
+/// One side of a perp market's orderbook.
+///
+/// Like OrderTreeNodes, this is generic over the node storage: `BookSide` (the default)
+/// has a runtime capacity and is what the program works with, see BookSideLoader.
+/// `BookSideAccount` is only the header without any nodes and is used for validating
+/// accounts, `DefaultBookSide` is a version with the default capacity.
+#[repr(C)]
+pub struct BookSide<Nodes: ?Sized = [AnyNode]> {
     pub roots: [OrderTreeRoot; 2],
     pub reserved_roots: [OrderTreeRoot; 4],
     pub reserved: [u8; 256],
-    pub nodes: OrderTreeNodes,
+    pub nodes: OrderTreeNodes<Nodes>,
 }
+
+/// The header of a bookside account, used with AccountLoader
+pub type BookSideAccount = BookSide<[AnyNode; 0]>;
+
+/// BookSide with the default capacity of MAX_ORDERTREE_NODES nodes
+pub type DefaultBookSide = BookSide<[AnyNode; MAX_ORDERTREE_NODES]>;
+
+const_assert_eq!(
+    std::mem::size_of::<BookSideAccount>(),
+    std::mem::size_of::<OrderTreeNodes<[AnyNode; 0]>>()
+        + 6 * std::mem::size_of::<OrderTreeRoot>()
+        + 256
+);
+const_assert_eq!(std::mem::size_of::<BookSideAccount>(), 832);
+const_assert_eq!(std::mem::size_of::<BookSideAccount>() % 8, 0);
 const_assert_eq!(
-    std::mem::size_of::<BookSide>(),
-    std::mem::size_of::<OrderTreeNodes>() + 6 * std::mem::size_of::<OrderTreeRoot>() + 256
+    std::mem::size_of::<DefaultBookSide>(),
+    std::mem::size_of::<DefaultOrderTreeNodes>() + 6 * std::mem::size_of::<OrderTreeRoot>() + 256
 );
-const_assert_eq!(std::mem::size_of::<BookSide>(), 123712);
-const_assert_eq!(std::mem::size_of::<BookSide>() % 8, 0);
+const_assert_eq!(std::mem::size_of::<DefaultBookSide>(), 123712);
+const_assert_eq!(std::mem::size_of::<DefaultBookSide>() % 8, 0);
+
+impl<const N: usize> Clone for BookSide<[AnyNode; N]> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<const N: usize> Copy for BookSide<[AnyNode; N]> {}
+unsafe impl<const N: usize> bytemuck::Zeroable for BookSide<[AnyNode; N]> {}
+unsafe impl<const N: usize> bytemuck::Pod for BookSide<[AnyNode; N]> {}
+
+impl<const N: usize> std::ops::Deref for BookSide<[AnyNode; N]> {
+    type Target = BookSide;
+    fn deref(&self) -> &BookSide {
+        self
+    }
+}
+
+impl<const N: usize> std::ops::DerefMut for BookSide<[AnyNode; N]> {
+    fn deref_mut(&mut self) -> &mut BookSide {
+        self
+    }
+}
+
+impl<const N: usize> Owner for BookSide<[AnyNode; N]> {
+    fn owner() -> Pubkey {
+        crate::ID
+    }
+}
+
+impl<const N: usize> Discriminator for BookSide<[AnyNode; N]> {
+    /// The anchor discriminator of the BookSide account, sha256("account:BookSide")[..8]
+    const DISCRIMINATOR: [u8; 8] = [72, 44, 225, 141, 178, 130, 97, 57];
+}
+
+impl<const N: usize> ZeroCopy for BookSide<[AnyNode; N]> {}
+
+impl<const N: usize> AccountDeserialize for BookSide<[AnyNode; N]> {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+        require!(buf.len() >= 8, ErrorCode::AccountDiscriminatorNotFound);
+        require!(
+            buf[..8] == Self::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        Self::try_deserialize_unchecked(buf)
+    }
+
+    /// Reads the first N nodes, bookside accounts may be larger than that
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+        let size = std::mem::size_of::<Self>();
+        require!(buf.len() >= 8 + size, ErrorCode::AccountDidNotDeserialize);
+        Ok(bytemuck::pod_read_unaligned(&buf[8..8 + size]))
+    }
+}
+
+/// Loads the full BookSide, with all nodes, from a bookside account
+pub trait BookSideLoader<'a> {
+    fn load_full(self) -> Result<Ref<'a, BookSide>>;
+    fn load_full_mut(self) -> Result<RefMut<'a, BookSide>>;
+    fn load_full_init(self) -> Result<RefMut<'a, BookSide>>;
+}
+
+impl<'a, 'info: 'a> BookSideLoader<'a> for &'a AccountLoader<'info, BookSideAccount> {
+    fn load_full(self) -> Result<Ref<'a, BookSide>> {
+        // Error checking
+        self.load()?;
+
+        let data = self.as_ref().try_borrow_data()?;
+        Ref::filter_map(data, |d| BookSide::from_bytes(&d[8..]).ok())
+            .map_err(|_| error_msg!("could not load bookside {}", self.key()))
+    }
+
+    fn load_full_mut(self) -> Result<RefMut<'a, BookSide>> {
+        // Error checking
+        self.load_mut()?;
+
+        let data = self.as_ref().try_borrow_mut_data()?;
+        RefMut::filter_map(data, |d| BookSide::from_bytes_mut(&mut d[8..]).ok())
+            .map_err(|_| error_msg!("could not load bookside {}", self.key()))
+    }
+
+    fn load_full_init(self) -> Result<RefMut<'a, BookSide>> {
+        // Error checking
+        self.load_init()?;
+
+        let data = self.as_ref().try_borrow_mut_data()?;
+        RefMut::filter_map(data, |d| BookSide::from_bytes_mut(&mut d[8..]).ok())
+            .map_err(|_| error_msg!("could not load bookside {}", self.key()))
+    }
+}
 
 impl BookSide {
+    /// Account size for a bookside with `capacity` nodes, including the discriminator
+    pub fn account_size(capacity: usize) -> usize {
+        8 + std::mem::size_of::<BookSideAccount>() + capacity * NODE_SIZE
+    }
+
+    /// Number of nodes that fit into a bookside account of the given size
+    pub fn capacity_for_account_size(account_size: usize) -> usize {
+        account_size.saturating_sub(8 + std::mem::size_of::<BookSideAccount>()) / NODE_SIZE
+    }
+
+    /// Number of nodes this bookside can hold
+    pub fn capacity(&self) -> usize {
+        self.nodes.capacity()
+    }
+
+    /// Interprets account data (after the discriminator) as a BookSide whose capacity
+    /// is the number of nodes that fit into it.
+    ///
+    /// Fails if the data is too small or not sufficiently aligned.
+    pub fn from_bytes(data: &[u8]) -> Result<&BookSide> {
+        let header_size = std::mem::size_of::<BookSideAccount>();
+        require_gte!(data.len(), header_size, MangoError::SomeError);
+        let capacity = (data.len() - header_size) / NODE_SIZE;
+        let data = &data[..header_size + capacity * NODE_SIZE];
+        // check alignment and size of both parts
+        let _: &BookSideAccount = bytemuck::try_from_bytes(&data[..header_size])
+            .map_err(|_| error_msg!("bad bookside data alignment"))?;
+        let _: &[AnyNode] = bytemuck::try_cast_slice(&data[header_size..])
+            .map_err(|_| error_msg!("bad bookside data alignment"))?;
+        let ptr = std::ptr::slice_from_raw_parts(data.as_ptr(), capacity) as *const BookSide;
+        // SAFETY: the size and alignment were checked above and all fields are Pod
+        Ok(unsafe { &*ptr })
+    }
+
+    /// Mutable version of from_bytes()
+    pub fn from_bytes_mut(data: &mut [u8]) -> Result<&mut BookSide> {
+        let header_size = std::mem::size_of::<BookSideAccount>();
+        require_gte!(data.len(), header_size, MangoError::SomeError);
+        let capacity = (data.len() - header_size) / NODE_SIZE;
+        let data = &mut data[..header_size + capacity * NODE_SIZE];
+        let _: &mut BookSideAccount = bytemuck::try_from_bytes_mut(&mut data[..header_size])
+            .map_err(|_| error_msg!("bad bookside data alignment"))?;
+        let _: &mut [AnyNode] = bytemuck::try_cast_slice_mut(&mut data[header_size..])
+            .map_err(|_| error_msg!("bad bookside data alignment"))?;
+        let ptr = std::ptr::slice_from_raw_parts_mut(data.as_mut_ptr(), capacity) as *mut BookSide;
+        // SAFETY: the size and alignment were checked above and all fields are Pod
+        Ok(unsafe { &mut *ptr })
+    }
+
+    /// Like from_bytes(), but for the full account data, including the discriminator.
+    ///
+    /// Useful for clients. Note that the data must be 8-byte aligned.
+    pub fn from_account_data(data: &[u8]) -> Result<&BookSide> {
+        require!(data.len() >= 8, ErrorCode::AccountDiscriminatorNotFound);
+        require!(
+            data[..8] == BookSideAccount::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        Self::from_bytes(&data[8..])
+    }
+
     /// Iterate over all entries in the book filtering out invalid orders
     ///
     /// smallest to highest for asks
@@ -128,6 +303,15 @@ impl BookSide {
         self.nodes.remove_one_expired(other_root, now_ts)
     }
 
+    pub fn find_by_key(
+        &self,
+        component: BookSideOrderTree,
+        search_key: u128,
+    ) -> Option<(NodeHandle, &LeafNode)> {
+        let root = &self.roots[component as usize];
+        self.nodes.find_by_key(root, search_key)
+    }
+
     pub fn remove_by_key(
         &mut self,
         component: BookSideOrderTree,
@@ -240,8 +424,8 @@ mod tests {
     use bytemuck::Zeroable;
     use std::collections::HashSet;
 
-    fn new_order_tree(order_tree_type: OrderTreeType) -> OrderTreeNodes {
-        let mut ot = OrderTreeNodes::zeroed();
+    fn new_order_tree(order_tree_type: OrderTreeType) -> DefaultOrderTreeNodes {
+        let mut ot = DefaultOrderTreeNodes::zeroed();
         ot.order_tree_type = order_tree_type.into();
         ot
     }
@@ -269,6 +453,7 @@ mod tests {
                 0,
                 -1,
                 0,
+                0,
             )
         };
 
@@ -312,7 +497,7 @@ mod tests {
                 .unwrap();
         }
 
-        let bookside = BookSide {
+        let bookside = DefaultBookSide {
             roots: [root_fixed, root_pegged],
             reserved_roots: [OrderTreeRoot::zeroed(); 4],
             reserved: [0; 256],
@@ -357,7 +542,7 @@ mod tests {
         }
     }
 
-    fn bookside_setup() -> BookSide {
+    fn bookside_setup() -> DefaultBookSide {
         bookside_setup_advanced(
             &[(100, 0), (120, 5)],
             &[(-10, 0, 100), (-15, 0, -1), (-20, 7, 95)],
@@ -369,7 +554,7 @@ mod tests {
         fixed: &[(i64, u16)],
         pegged: &[(i64, u16, i64)],
         side: Side,
-    ) -> BookSide {
+    ) -> DefaultBookSide {
         use std::cell::RefCell;
 
         let order_tree_type = match side {
@@ -391,6 +576,7 @@ mod tests {
                 tif,
                 peg_limit,
                 0,
+                0,
             )
         };
         let mut add_fixed = |price: i64, tif: u16| {
@@ -415,7 +601,7 @@ mod tests {
             add_pegged(*price_offset, *tif, *limit);
         }
 
-        BookSide {
+        DefaultBookSide {
             roots: [root_fixed, root_pegged],
             reserved_roots: [OrderTreeRoot::zeroed(); 4],
             reserved: [0; 256],
@@ -0,0 +1,55 @@
+//! Fixed-capacity layouts of the perp orderbook accounts, for the IDL.
+//!
+//! The program works with BookSide and EventQueue whose capacity depends on the account
+//! size, which the IDL can't express. These structs describe accounts with the default
+//! capacity, so clients keep their account and type definitions. Clients that read
+//! accounts of other capacities need to decode them by hand, like the TS client does.
+
+use anchor_lang::prelude::*;
+use static_assertions::const_assert_eq;
+
+use super::{
+    AnyEvent, AnyNode, EventQueueHeader, NodeHandle, OrderTreeRoot, MAX_NUM_EVENTS,
+    MAX_ORDERTREE_NODES,
+};
+
+#[account(zero_copy)]
+pub struct BookSide {
+    pub roots: [OrderTreeRoot; 2],
+    pub reserved_roots: [OrderTreeRoot; 4],
+    pub reserved: [u8; 256],
+    pub nodes: OrderTreeNodes,
+}
+const_assert_eq!(
+    std::mem::size_of::<BookSide>(),
+    std::mem::size_of::<super::DefaultBookSide>()
+);
+
+/// A binary tree on AnyNode::key()
+///
+/// The key encodes the price in the top 64 bits.
+#[zero_copy]
+pub struct OrderTreeNodes {
+    pub order_tree_type: u8, // OrderTreeType, but that's not POD
+    pub padding: [u8; 3],
+    pub bump_index: u32,
+    pub free_list_len: u32,
+    pub free_list_head: NodeHandle,
+    pub reserved: [u8; 512],
+    pub nodes: [AnyNode; MAX_ORDERTREE_NODES],
+}
+const_assert_eq!(
+    std::mem::size_of::<OrderTreeNodes>(),
+    std::mem::size_of::<super::DefaultOrderTreeNodes>()
+);
+
+#[account(zero_copy)]
+pub struct EventQueue {
+    pub header: EventQueueHeader,
+    pub buf: [AnyEvent; MAX_NUM_EVENTS as usize],
+    pub reserved: [u8; 64],
+}
+const_assert_eq!(
+    std::mem::size_of::<EventQueue>(),
+    std::mem::size_of::<super::DefaultEventQueue>() + 64
+);
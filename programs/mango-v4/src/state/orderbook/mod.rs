@@ -1,3 +1,4 @@
+pub use auction::*;
 pub use book::*;
 pub use bookside::*;
 pub use bookside_iterator::*;
@@ -8,9 +9,11 @@ pub use ordertree::*;
 pub use ordertree_iterator::*;
 pub use queue::*;
 
+mod auction;
 mod book;
 mod bookside;
 mod bookside_iterator;
+pub mod idl_layouts;
 mod nodes;
 mod order;
 mod order_type;
@@ -26,7 +29,7 @@ mod tests {
     use bytemuck::Zeroable;
     use fixed::types::I80F48;
     use solana_program::pubkey::Pubkey;
-    use std::cell::RefCell;
+    use std::cell::{RefCell, RefMut};
 
     fn order_tree_leaf_by_key(bookside: &BookSide, key: u128) -> Option<&LeafNode> {
         for component in [BookSideOrderTree::Fixed, BookSideOrderTree::OraclePegged] {
@@ -55,15 +58,15 @@ mod tests {
     }
 
     struct OrderbookAccounts {
-        bids: Box<RefCell<BookSide>>,
-        asks: Box<RefCell<BookSide>>,
+        bids: Box<RefCell<DefaultBookSide>>,
+        asks: Box<RefCell<DefaultBookSide>>,
     }
 
     impl OrderbookAccounts {
         fn new() -> Self {
             let s = Self {
-                bids: Box::new(RefCell::new(BookSide::zeroed())),
-                asks: Box::new(RefCell::new(BookSide::zeroed())),
+                bids: Box::new(RefCell::new(DefaultBookSide::zeroed())),
+                asks: Box::new(RefCell::new(DefaultBookSide::zeroed())),
             };
             s.bids.borrow_mut().nodes.order_tree_type = OrderTreeType::Bids.into();
             s.asks.borrow_mut().nodes.order_tree_type = OrderTreeType::Asks.into();
@@ -72,16 +75,16 @@ mod tests {
 
         fn orderbook(&self) -> Orderbook {
             Orderbook {
-                bids: self.bids.borrow_mut(),
-                asks: self.asks.borrow_mut(),
+                bids: RefMut::map(self.bids.borrow_mut(), |b| &mut **b),
+                asks: RefMut::map(self.asks.borrow_mut(), |b| &mut **b),
             }
         }
     }
 
-    fn test_setup(price: f64) -> (PerpMarket, I80F48, EventQueue, OrderbookAccounts) {
+    fn test_setup(price: f64) -> (PerpMarket, I80F48, DefaultEventQueue, OrderbookAccounts) {
         let book = OrderbookAccounts::new();
 
-        let event_queue = EventQueue::zeroed();
+        let event_queue = DefaultEventQueue::zeroed();
 
         let oracle_price = I80F48::from_num(price);
 
@@ -139,6 +142,7 @@ mod tests {
                 &Pubkey::default(),
                 now_ts,
                 u8::MAX,
+                &[],
             )
             .unwrap();
             account.perp_order_by_raw_index_unchecked(0).id
@@ -289,6 +293,7 @@ mod tests {
             &maker_pk,
             now_ts,
             u8::MAX,
+            &[],
         )
         .unwrap();
         let order =
@@ -370,6 +375,7 @@ mod tests {
             &taker_pk,
             now_ts,
             u8::MAX,
+            &[],
         )
         .unwrap();
         // the remainder of the maker order is still on the book
@@ -556,6 +562,7 @@ mod tests {
             &maker_pk,
             now_ts,
             u8::MAX,
+            &[],
         )
         .unwrap();
 
@@ -581,6 +588,7 @@ mod tests {
             &taker_pk,
             now_ts,
             u8::MAX,
+            &[],
         )
         .unwrap();
 
@@ -617,6 +625,7 @@ mod tests {
             &taker_pk,
             now_ts,
             u8::MAX,
+            &[],
         )
         .unwrap();
 
@@ -679,6 +688,7 @@ mod tests {
                 &Pubkey::default(),
                 0, // now_ts
                 u8::MAX,
+                &[],
             )
             .unwrap();
             account.perp_order_by_raw_index_unchecked(0).id
@@ -744,6 +754,7 @@ mod tests {
             &taker_pk,
             now_ts,
             u8::MAX,
+            &[],
         )
         .unwrap();
 
@@ -769,6 +780,7 @@ mod tests {
             &maker_pk,
             now_ts,
             u8::MAX,
+            &[],
         )
         .unwrap();
 
@@ -791,6 +803,7 @@ mod tests {
             &taker_pk,
             now_ts,
             u8::MAX,
+            &[],
         )
         .unwrap();
 
@@ -837,6 +850,7 @@ mod tests {
             &taker_pk,
             now_ts,
             u8::MAX,
+            &[],
         )
         .unwrap();
 
@@ -915,6 +929,7 @@ mod tests {
             &taker_pk,
             now_ts,
             u8::MAX,
+            &[],
         )
         .unwrap();
 
@@ -940,6 +955,7 @@ mod tests {
             &maker_pk,
             now_ts,
             u8::MAX,
+            &[],
         )
         .unwrap();
 
@@ -965,6 +981,7 @@ mod tests {
             &taker_pk,
             now_ts,
             u8::MAX,
+            &[],
         )
         .unwrap();
 
@@ -1036,6 +1053,7 @@ mod tests {
             &taker_pk,
             now_ts,
             u8::MAX,
+            &[],
         )
         .unwrap();
 
@@ -1058,9 +1076,38 @@ mod tests {
             &taker_pk,
             now_ts,
             u8::MAX,
+            &[],
         )
         .expect_err("should fail");
 
         Ok(())
     }
+
+    #[test]
+    fn test_from_bytes_checks_size_and_alignment() {
+        // u64 storage, so the data is 8-byte aligned
+        let bookside_words = (BookSide::account_size(3) - 8) / 8;
+        let mut storage = vec![0u64; bookside_words + 1];
+        let data: &mut [u8] = bytemuck::cast_slice_mut(&mut storage);
+
+        let bookside = BookSide::from_bytes(&data[..bookside_words * 8]).unwrap();
+        assert_eq!(bookside.capacity(), 3);
+        // trailing bytes that don't make up a full node are ignored
+        let bookside = BookSide::from_bytes(&data[..bookside_words * 8 + 7]).unwrap();
+        assert_eq!(bookside.capacity(), 3);
+        // too small for the header, or misaligned
+        assert!(BookSide::from_bytes(&data[..831]).is_err());
+        assert!(BookSide::from_bytes(&data[1..bookside_words * 8 + 1]).is_err());
+        assert!(BookSide::from_bytes_mut(&mut data[1..bookside_words * 8 + 1]).is_err());
+
+        let queue_words = (EventQueue::account_size(2) - 8) / 8;
+        let mut storage = vec![0u64; queue_words + 1];
+        let data: &mut [u8] = bytemuck::cast_slice_mut(&mut storage);
+
+        let queue = EventQueue::from_bytes(&data[..queue_words * 8]).unwrap();
+        assert_eq!(queue.capacity(), 2);
+        assert!(EventQueue::from_bytes(&data[..16 + 63]).is_err());
+        assert!(EventQueue::from_bytes(&data[1..queue_words * 8 + 1]).is_err());
+        assert!(EventQueue::from_bytes_mut(&mut data[1..queue_words * 8 + 1]).is_err());
+    }
 }
@@ -8,7 +8,7 @@ use static_assertions::const_assert_eq;
 use super::order_type::{PostOrderType, Side};
 
 pub type NodeHandle = u32;
-const NODE_SIZE: usize = 120;
+pub const NODE_SIZE: usize = 120;
 
 #[derive(IntoPrimitive, TryFromPrimitive)]
 #[repr(u8)]
@@ -36,6 +36,18 @@ pub fn new_node_key(side: Side, price_data: u64, seq_num: u64) -> u128 {
     upper | (seq_num as u128)
 }
 
+/// The sequence number an order key was created with
+///
+/// Reverse of new_node_key(). Later orders have higher sequence numbers on both sides.
+pub fn node_key_seq_num(side: Side, key: u128) -> u64 {
+    let seq_num = key as u64;
+    if side == Side::Bid {
+        !seq_num
+    } else {
+        seq_num
+    }
+}
+
 /// Creates price data for an oracle pegged order from the price offset
 ///
 /// Reverse of oracle_pegged_price_offset()
@@ -153,7 +165,9 @@ pub struct LeafNode {
     /// PostOrderType, this was added for TradingView move order
     pub order_type: u8,
 
-    pub padding: [u8; 1],
+    /// Perp fee tier of the owner at the time the order was placed, see
+    /// Group::perp_fee_tiers. Determines the maker fee when the order is filled.
+    pub fee_tier: u8,
 
     /// Time in seconds after `timestamp` at which the order expires.
     /// A value of 0 means no expiry.
@@ -203,12 +217,13 @@ impl LeafNode {
         time_in_force: u16,
         peg_limit: i64,
         client_order_id: u64,
+        fee_tier: u8,
     ) -> Self {
         Self {
             tag: NodeTag::LeafNode.into(),
             owner_slot,
             order_type: order_type.into(),
-            padding: Default::default(),
+            fee_tier,
             time_in_force,
             padding2: Default::default(),
             key,
@@ -440,5 +455,12 @@ mod tests {
             let r_key = new_node_key(Side::Ask, r_price_data, rhs.1);
             assert!(l_key < r_key);
         }
+
+        for side in [Side::Bid, Side::Ask] {
+            for seq_num in [0, 1, 42, u64::MAX] {
+                let key = new_node_key(side, oracle_pegged_price_data(-3), seq_num);
+                assert_eq!(node_key_seq_num(side, key), seq_num);
+            }
+        }
     }
 }
@@ -160,6 +160,20 @@ impl Order {
         Ok((price_lots, price_data))
     }
 
+    /// The most aggressive price_lots this order can currently fill at.
+    ///
+    /// Unlike price() this ignores PostOnlySlide, which only makes orders less aggressive.
+    pub fn limit_price_lots(&self, oracle_price_lots: i64) -> i64 {
+        match self.params {
+            OrderParams::Market => market_order_limit_for_side(self.side),
+            OrderParams::ImmediateOrCancel { price_lots } => price_lots,
+            OrderParams::Fixed { price_lots, .. } => price_lots,
+            OrderParams::OraclePegged {
+                price_offset_lots, ..
+            } => oracle_price_lots.saturating_add(price_offset_lots),
+        }
+    }
+
     /// pegging limit for oracle peg orders, otherwise -1
     pub fn peg_limit(&self) -> i64 {
         match self.params {
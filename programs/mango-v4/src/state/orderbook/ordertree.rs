@@ -56,27 +56,70 @@ impl OrderTreeRoot {
 /// A binary tree on AnyNode::key()
 ///
 /// The key encodes the price in the top 64 bits.
-#[zero_copy]
-pub struct OrderTreeNodes {
+///
+/// The node storage is the last field, so the struct is generic over its capacity:
+/// `OrderTreeNodes` (the default) has a runtime number of nodes, determined by the size
+/// of the account it lives in, while `OrderTreeNodes<[AnyNode; N]>` has a fixed capacity.
+#[repr(C)]
+pub struct OrderTreeNodes<Nodes: ?Sized = [AnyNode]> {
     pub order_tree_type: u8, // OrderTreeType, but that's not POD
     pub padding: [u8; 3],
     pub bump_index: u32,
     pub free_list_len: u32,
     pub free_list_head: NodeHandle,
     pub reserved: [u8; 512],
-    pub nodes: [AnyNode; MAX_ORDERTREE_NODES],
+    pub nodes: Nodes,
 }
+
+/// OrderTreeNodes with the default capacity that bookside accounts were created with
+/// before capacities became configurable
+pub type DefaultOrderTreeNodes = OrderTreeNodes<[AnyNode; MAX_ORDERTREE_NODES]>;
+
 const_assert_eq!(
-    std::mem::size_of::<OrderTreeNodes>(),
+    std::mem::size_of::<OrderTreeNodes<[AnyNode; 0]>>(),
+    1 + 3 + 4 * 2 + 4 + 512
+);
+const_assert_eq!(std::mem::size_of::<OrderTreeNodes<[AnyNode; 0]>>() % 8, 0);
+const_assert_eq!(
+    std::mem::size_of::<DefaultOrderTreeNodes>(),
     1 + 3 + 4 * 2 + 4 + 512 + 120 * 1024
 );
-const_assert_eq!(std::mem::size_of::<OrderTreeNodes>(), 123408);
-const_assert_eq!(std::mem::size_of::<OrderTreeNodes>() % 8, 0);
+const_assert_eq!(std::mem::size_of::<DefaultOrderTreeNodes>(), 123408);
+const_assert_eq!(std::mem::size_of::<DefaultOrderTreeNodes>() % 8, 0);
 
-impl OrderTreeNodes {
+impl<const N: usize> Clone for OrderTreeNodes<[AnyNode; N]> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<const N: usize> Copy for OrderTreeNodes<[AnyNode; N]> {}
+unsafe impl<const N: usize> bytemuck::Zeroable for OrderTreeNodes<[AnyNode; N]> {}
+unsafe impl<const N: usize> bytemuck::Pod for OrderTreeNodes<[AnyNode; N]> {}
+
+impl<const N: usize> std::ops::Deref for OrderTreeNodes<[AnyNode; N]> {
+    type Target = OrderTreeNodes;
+    fn deref(&self) -> &OrderTreeNodes {
+        self
+    }
+}
+
+impl<const N: usize> std::ops::DerefMut for OrderTreeNodes<[AnyNode; N]> {
+    fn deref_mut(&mut self) -> &mut OrderTreeNodes {
+        self
+    }
+}
+
+impl<Nodes: ?Sized> OrderTreeNodes<Nodes> {
     pub fn order_tree_type(&self) -> OrderTreeType {
         OrderTreeType::try_from(self.order_tree_type).unwrap()
     }
+}
+
+impl OrderTreeNodes {
+    /// Number of nodes this tree can hold
+    pub fn capacity(&self) -> usize {
+        self.nodes.len()
+    }
 
     /// Iterate over all entries, including invalid orders
     ///
@@ -191,6 +234,25 @@ impl OrderTreeNodes {
         }
     }
 
+    /// Returns the leaf with the given key and its handle, if it exists
+    pub fn find_by_key(
+        &self,
+        root: &OrderTreeRoot,
+        search_key: u128,
+    ) -> Option<(NodeHandle, &LeafNode)> {
+        let mut node_handle = root.node()?;
+        loop {
+            match self.node(node_handle)?.case()? {
+                NodeRef::Inner(inner) => {
+                    node_handle = inner.walk_down(search_key).0;
+                }
+                NodeRef::Leaf(leaf) => {
+                    return (leaf.key == search_key).then_some((node_handle, leaf));
+                }
+            }
+        }
+    }
+
     pub fn remove_by_key(
         &mut self,
         root: &mut OrderTreeRoot,
@@ -455,8 +517,8 @@ mod tests {
     use super::*;
     use bytemuck::Zeroable;
 
-    fn new_order_tree(order_tree_type: OrderTreeType) -> OrderTreeNodes {
-        let mut ot = OrderTreeNodes::zeroed();
+    fn new_order_tree(order_tree_type: OrderTreeType) -> DefaultOrderTreeNodes {
+        let mut ot = DefaultOrderTreeNodes::zeroed();
         ot.order_tree_type = order_tree_type.into();
         ot
     }
@@ -557,6 +619,7 @@ mod tests {
                 1,
                 -1,
                 0,
+                0,
             )
         };
 
@@ -650,6 +713,7 @@ mod tests {
                 1,
                 -1,
                 0,
+                0,
             )
         };
 
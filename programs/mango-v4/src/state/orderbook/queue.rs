@@ -1,9 +1,11 @@
 use crate::{error::Contextable, error::MangoError, error_msg};
 use anchor_lang::prelude::*;
+use anchor_lang::{Discriminator, ZeroCopy};
 use bytemuck::cast_ref;
 use fixed::types::I80F48;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use static_assertions::const_assert_eq;
+use std::cell::{Ref, RefMut};
 use std::mem::size_of;
 
 use super::{LeafNode, Side};
@@ -22,17 +24,202 @@ pub trait QueueHeader: bytemuck::Pod {
     fn decr_event_id(&mut self, n: u64);
 }
 
-#[account(zero_copy)]
-pub struct EventQueue {
+/// Unused bytes at the end of every event queue account, after the event buffer
+const EVENT_QUEUE_RESERVED_SIZE: usize = 64;
+
+/// A perp market's queue of fill and out events.
+///
+/// Generic over the event storage like BookSide: `EventQueue` (the default) has a
+/// runtime capacity and is what the program works with, see EventQueueLoader.
+/// `EventQueueAccount` is only the header and used for validating accounts,
+/// `DefaultEventQueue` is a version with the default capacity.
+#[repr(C)]
+pub struct EventQueue<Buf: ?Sized = [AnyEvent]> {
     pub header: EventQueueHeader,
-    pub buf: [AnyEvent; MAX_NUM_EVENTS as usize],
-    pub reserved: [u8; 64],
+    pub buf: Buf,
+}
+
+/// The header of an event queue account, used with AccountLoader
+pub type EventQueueAccount = EventQueue<[AnyEvent; 0]>;
+
+/// EventQueue with the default capacity of MAX_NUM_EVENTS events
+pub type DefaultEventQueue = EventQueue<[AnyEvent; MAX_NUM_EVENTS as usize]>;
+
+const_assert_eq!(std::mem::size_of::<EventQueueAccount>(), 16);
+const_assert_eq!(std::mem::size_of::<DefaultEventQueue>(), 16 + 488 * 208);
+const_assert_eq!(std::mem::size_of::<DefaultEventQueue>(), 101520);
+const_assert_eq!(std::mem::size_of::<DefaultEventQueue>() % 8, 0);
+
+impl<const N: usize> Clone for EventQueue<[AnyEvent; N]> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<const N: usize> Copy for EventQueue<[AnyEvent; N]> {}
+unsafe impl<const N: usize> bytemuck::Zeroable for EventQueue<[AnyEvent; N]> {}
+unsafe impl<const N: usize> bytemuck::Pod for EventQueue<[AnyEvent; N]> {}
+
+impl<const N: usize> std::ops::Deref for EventQueue<[AnyEvent; N]> {
+    type Target = EventQueue;
+    fn deref(&self) -> &EventQueue {
+        self
+    }
+}
+
+impl<const N: usize> std::ops::DerefMut for EventQueue<[AnyEvent; N]> {
+    fn deref_mut(&mut self) -> &mut EventQueue {
+        self
+    }
+}
+
+impl<const N: usize> Owner for EventQueue<[AnyEvent; N]> {
+    fn owner() -> Pubkey {
+        crate::ID
+    }
+}
+
+impl<const N: usize> Discriminator for EventQueue<[AnyEvent; N]> {
+    /// The anchor discriminator of the EventQueue account, sha256("account:EventQueue")[..8]
+    const DISCRIMINATOR: [u8; 8] = [41, 208, 116, 209, 173, 116, 141, 68];
+}
+
+impl<const N: usize> ZeroCopy for EventQueue<[AnyEvent; N]> {}
+
+impl<const N: usize> AccountDeserialize for EventQueue<[AnyEvent; N]> {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+        require!(buf.len() >= 8, ErrorCode::AccountDiscriminatorNotFound);
+        require!(
+            buf[..8] == Self::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        Self::try_deserialize_unchecked(buf)
+    }
+
+    /// Reads the first N events, event queue accounts may be larger than that
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+        let size = size_of::<Self>();
+        require!(buf.len() >= 8 + size, ErrorCode::AccountDidNotDeserialize);
+        Ok(bytemuck::pod_read_unaligned(&buf[8..8 + size]))
+    }
+}
+
+/// Loads the full EventQueue, with all events, from an event queue account
+pub trait EventQueueLoader<'a> {
+    fn load_full(self) -> Result<Ref<'a, EventQueue>>;
+    fn load_full_mut(self) -> Result<RefMut<'a, EventQueue>>;
+    fn load_full_init(self) -> Result<RefMut<'a, EventQueue>>;
+}
+
+impl<'a, 'info: 'a> EventQueueLoader<'a> for &'a AccountLoader<'info, EventQueueAccount> {
+    fn load_full(self) -> Result<Ref<'a, EventQueue>> {
+        // Error checking
+        self.load()?;
+
+        let data = self.as_ref().try_borrow_data()?;
+        Ref::filter_map(data, |d| EventQueue::from_bytes(&d[8..]).ok())
+            .map_err(|_| error_msg!("could not load event queue {}", self.key()))
+    }
+
+    fn load_full_mut(self) -> Result<RefMut<'a, EventQueue>> {
+        // Error checking
+        self.load_mut()?;
+
+        let data = self.as_ref().try_borrow_mut_data()?;
+        RefMut::filter_map(data, |d| EventQueue::from_bytes_mut(&mut d[8..]).ok())
+            .map_err(|_| error_msg!("could not load event queue {}", self.key()))
+    }
+
+    fn load_full_init(self) -> Result<RefMut<'a, EventQueue>> {
+        // Error checking
+        self.load_init()?;
+
+        let data = self.as_ref().try_borrow_mut_data()?;
+        RefMut::filter_map(data, |d| EventQueue::from_bytes_mut(&mut d[8..]).ok())
+            .map_err(|_| error_msg!("could not load event queue {}", self.key()))
+    }
 }
-const_assert_eq!(std::mem::size_of::<EventQueue>(), 16 + 488 * 208 + 64);
-const_assert_eq!(std::mem::size_of::<EventQueue>(), 101584);
-const_assert_eq!(std::mem::size_of::<EventQueue>() % 8, 0);
 
 impl EventQueue {
+    /// Account size for an event queue with `capacity` events, including the discriminator
+    pub fn account_size(capacity: usize) -> usize {
+        8 + size_of::<EventQueueAccount>() + capacity * EVENT_SIZE + EVENT_QUEUE_RESERVED_SIZE
+    }
+
+    /// Number of events that fit into an event queue account of the given size
+    pub fn capacity_for_account_size(account_size: usize) -> usize {
+        account_size.saturating_sub(8 + size_of::<EventQueueAccount>() + EVENT_QUEUE_RESERVED_SIZE)
+            / EVENT_SIZE
+    }
+
+    /// Number of events this queue can hold
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Interprets account data (after the discriminator) as an EventQueue whose capacity
+    /// is the number of events that fit into it.
+    ///
+    /// Fails if the data is too small or not sufficiently aligned.
+    pub fn from_bytes(data: &[u8]) -> Result<&EventQueue> {
+        let header_size = size_of::<EventQueueAccount>();
+        require_gte!(
+            data.len(),
+            header_size + EVENT_QUEUE_RESERVED_SIZE,
+            MangoError::SomeError
+        );
+        let capacity = (data.len() - header_size - EVENT_QUEUE_RESERVED_SIZE) / EVENT_SIZE;
+        let data = &data[..header_size + capacity * EVENT_SIZE];
+        // check alignment and size of both parts
+        let _: &EventQueueAccount = bytemuck::try_from_bytes(&data[..header_size])
+            .map_err(|_| error_msg!("bad event queue data alignment"))?;
+        let _: &[AnyEvent] = bytemuck::try_cast_slice(&data[header_size..])
+            .map_err(|_| error_msg!("bad event queue data alignment"))?;
+        let ptr = std::ptr::slice_from_raw_parts(data.as_ptr(), capacity) as *const EventQueue;
+        // SAFETY: the size and alignment were checked above and all fields are Pod
+        Ok(unsafe { &*ptr })
+    }
+
+    /// Mutable version of from_bytes()
+    pub fn from_bytes_mut(data: &mut [u8]) -> Result<&mut EventQueue> {
+        let header_size = size_of::<EventQueueAccount>();
+        require_gte!(
+            data.len(),
+            header_size + EVENT_QUEUE_RESERVED_SIZE,
+            MangoError::SomeError
+        );
+        let capacity = (data.len() - header_size - EVENT_QUEUE_RESERVED_SIZE) / EVENT_SIZE;
+        let data = &mut data[..header_size + capacity * EVENT_SIZE];
+        let _: &mut EventQueueAccount = bytemuck::try_from_bytes_mut(&mut data[..header_size])
+            .map_err(|_| error_msg!("bad event queue data alignment"))?;
+        let _: &mut [AnyEvent] = bytemuck::try_cast_slice_mut(&mut data[header_size..])
+            .map_err(|_| error_msg!("bad event queue data alignment"))?;
+        let ptr =
+            std::ptr::slice_from_raw_parts_mut(data.as_mut_ptr(), capacity) as *mut EventQueue;
+        // SAFETY: the size and alignment were checked above and all fields are Pod
+        Ok(unsafe { &mut *ptr })
+    }
+
+    /// Like from_bytes(), but for the full account data, including the discriminator.
+    ///
+    /// Useful for clients. Note that the data must be 8-byte aligned.
+    pub fn from_account_data(data: &[u8]) -> Result<&EventQueue> {
+        require!(data.len() >= 8, ErrorCode::AccountDiscriminatorNotFound);
+        require!(
+            data[..8] == EventQueueAccount::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        Self::from_bytes(&data[8..])
+    }
+
+    /// Moves the events so the oldest one is at the start of the buffer.
+    ///
+    /// Needed before the buffer can grow: the ring buffer wraps around at its end.
+    pub fn rotate_to_start(&mut self) {
+        let head = self.header.head();
+        self.buf.rotate_left(head);
+        self.header.set_head(0);
+    }
+
     pub fn len(&self) -> usize {
         self.header.count()
     }
@@ -154,8 +341,7 @@ impl QueueHeader for EventQueueHeader {
     }
 }
 
-#[allow(dead_code)]
-const EVENT_SIZE: usize = 208;
+pub const EVENT_SIZE: usize = 208;
 
 #[zero_copy]
 #[derive(Debug)]
@@ -194,7 +380,8 @@ pub struct FillEvent {
     pub maker_timestamp: u64,
 
     pub taker: Pubkey,
-    pub padding3: [u8; 16],
+    // Only set for auction fills, where the taker also had an order on the book
+    pub taker_order_id: u128,
     pub taker_client_order_id: u64,
     pub maker_order_id: u128,
 
@@ -203,7 +390,12 @@ pub struct FillEvent {
     pub maker_client_order_id: u64,
     pub maker_fee: f32,
     pub taker_fee: f32,
-    pub reserved: [u8; 8],
+    pub maker_fee_tier: u8, // Perp fee tier that determined maker_fee, 0 if none
+    pub taker_fee_tier: u8, // Perp fee tier that determined taker_fee, 0 if none
+    pub auction: u8,        // 1 if this is a fill of an opening auction
+    pub taker_slot: u8,     // Only set for auction fills, see taker_order_id
+    pub taker_out: u8,      // Only set for auction fills, 1 if taker order quantity == 0
+    pub reserved: [u8; 3],
 }
 const_assert_eq!(size_of::<FillEvent>() % 8, 0);
 const_assert_eq!(size_of::<FillEvent>(), EVENT_SIZE);
@@ -220,10 +412,12 @@ impl FillEvent {
         maker_order_id: u128,
         maker_client_order_id: u64,
         maker_fee: I80F48,
+        maker_fee_tier: u8,
         maker_timestamp: u64,
         taker: Pubkey,
         taker_client_order_id: u64,
         taker_fee: I80F48,
+        taker_fee_tier: u8,
         price: i64,
         quantity: i64,
     ) -> FillEvent {
@@ -242,12 +436,88 @@ impl FillEvent {
             taker,
             taker_client_order_id,
             taker_fee: taker_fee.to_num::<f32>(),
+            maker_fee_tier,
+            taker_fee_tier,
             price,
             quantity,
             padding: Default::default(),
             padding2: Default::default(),
-            padding3: Default::default(),
-            reserved: [0; 8],
+            taker_order_id: 0,
+            auction: 0,
+            taker_slot: 0,
+            taker_out: 0,
+            reserved: [0; 3],
+        }
+    }
+
+    /// A fill of an opening auction, where both sides were orders on the book.
+    ///
+    /// The ask is recorded as the maker and the bid as the taker, but the bid is
+    /// executed like a maker fill too, see auction_taker_as_maker().
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_auction(
+        bid: &LeafNode,
+        bid_out: bool,
+        bid_fee: I80F48,
+        ask: &LeafNode,
+        ask_out: bool,
+        ask_fee: I80F48,
+        timestamp: u64,
+        seq_num: u64,
+        price: i64,
+        quantity: i64,
+    ) -> FillEvent {
+        let mut fill = Self::new(
+            Side::Bid,
+            ask_out,
+            ask.owner_slot,
+            timestamp,
+            seq_num,
+            ask.owner,
+            ask.key,
+            ask.client_order_id,
+            ask_fee,
+            ask.fee_tier,
+            ask.timestamp,
+            bid.owner,
+            bid.client_order_id,
+            bid_fee,
+            bid.fee_tier,
+            price,
+            quantity,
+        );
+        fill.auction = 1;
+        fill.taker_order_id = bid.key;
+        fill.taker_slot = bid.owner_slot;
+        fill.taker_out = bid_out.into();
+        fill
+    }
+
+    pub fn is_auction(&self) -> bool {
+        self.auction == 1
+    }
+
+    /// For auction fills: the same fill from the point of view of the taker's order,
+    /// so it can be executed with execute_perp_maker().
+    pub fn auction_taker_as_maker(&self) -> FillEvent {
+        FillEvent {
+            taker_side: self.taker_side().invert_side().into(),
+            maker_out: self.taker_out,
+            maker_slot: self.taker_slot,
+            maker: self.taker,
+            maker_timestamp: 0,
+            taker: self.maker,
+            taker_order_id: self.maker_order_id,
+            taker_client_order_id: self.maker_client_order_id,
+            maker_order_id: self.taker_order_id,
+            maker_client_order_id: self.taker_client_order_id,
+            maker_fee: self.taker_fee,
+            taker_fee: self.maker_fee,
+            maker_fee_tier: self.taker_fee_tier,
+            taker_fee_tier: self.maker_fee_tier,
+            taker_slot: self.maker_slot,
+            taker_out: self.maker_out,
+            ..*self
         }
     }
 
@@ -0,0 +1,177 @@
+use anchor_lang::prelude::*;
+
+use derivative::Derivative;
+use fixed::types::I80F48;
+use static_assertions::const_assert_eq;
+use std::mem::size_of;
+
+use crate::error::MangoError;
+
+/// Number of perp fee tiers a group can have. Tier ids are 1-based, 0 means the
+/// market's base fees.
+pub const PERP_FEE_TIER_COUNT: usize = 4;
+
+/// Length of the window over which an account's perp volume is tracked for fee tiers
+pub const PERP_FEE_TIER_VOLUME_WINDOW: u64 = 30 * 24 * 60 * 60;
+
+/// A perp fee tier, selected by an account's trailing perp volume.
+///
+/// The discounts are subtracted from a market's maker_fee and taker_fee. Taker fees
+/// never become negative, maker fees may turn into (bigger) rebates.
+#[zero_copy]
+#[derive(Derivative, PartialEq)]
+#[derivative(Debug)]
+pub struct PerpFeeTier {
+    /// Trailing perp volume (in quote native) required for this tier.
+    /// A value of 0 means the tier is unused.
+    pub min_volume_quote: u64,
+
+    pub maker_fee_discount: f32,
+    pub taker_fee_discount: f32,
+
+    #[derivative(Debug = "ignore")]
+    pub reserved: [u8; 16],
+}
+const_assert_eq!(size_of::<PerpFeeTier>(), 8 + 4 + 4 + 16);
+const_assert_eq!(size_of::<PerpFeeTier>(), 32);
+const_assert_eq!(size_of::<PerpFeeTier>() % 8, 0);
+
+impl PerpFeeTier {
+    pub fn is_empty(&self) -> bool {
+        self.min_volume_quote == 0
+    }
+
+    pub fn maker_fee(&self, base_maker_fee: I80F48) -> I80F48 {
+        base_maker_fee - I80F48::from_num(self.maker_fee_discount)
+    }
+
+    pub fn taker_fee(&self, base_taker_fee: I80F48) -> I80F48 {
+        (base_taker_fee - I80F48::from_num(self.taker_fee_discount)).max(I80F48::ZERO)
+    }
+
+    pub fn verify(&self) -> Result<()> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        require_msg_typed!(
+            self.maker_fee_discount >= 0.0 && self.taker_fee_discount >= 0.0,
+            MangoError::InvalidPerpFeeTier,
+            "fee discounts must not be negative"
+        );
+        Ok(())
+    }
+}
+
+/// Returns the perp fee tier id for the given trailing volume, 0 if no tier applies.
+///
+/// The best tier is the one with the highest min_volume_quote that is reached.
+pub fn perp_fee_tier_for_volume(tiers: &[PerpFeeTier], volume_quote: u64) -> u8 {
+    let mut best: Option<(usize, u64)> = None;
+    for (i, tier) in tiers.iter().enumerate() {
+        if tier.is_empty() || volume_quote < tier.min_volume_quote {
+            continue;
+        }
+        if best.map_or(true, |(_, min_volume)| tier.min_volume_quote > min_volume) {
+            best = Some((i, tier.min_volume_quote));
+        }
+    }
+    best.map_or(0, |(i, _)| (i + 1) as u8)
+}
+
+/// Lowest maker fee and lowest taker fee over the base fees and all used tiers
+pub fn perp_lowest_fees(
+    tiers: &[PerpFeeTier],
+    base_maker_fee: I80F48,
+    base_taker_fee: I80F48,
+) -> (I80F48, I80F48) {
+    tiers.iter().filter(|tier| !tier.is_empty()).fold(
+        (base_maker_fee, base_taker_fee),
+        |(maker_fee, taker_fee), tier| {
+            (
+                maker_fee.min(tier.maker_fee(base_maker_fee)),
+                taker_fee.min(tier.taker_fee(base_taker_fee)),
+            )
+        },
+    )
+}
+
+/// Checks that the maker rebate of any tier is covered by the taker fee of any tier.
+///
+/// Otherwise wash trading between two accounts in different tiers would be profitable
+/// and the market's fees_accrued would go negative.
+pub fn verify_perp_fees(
+    tiers: &[PerpFeeTier],
+    base_maker_fee: I80F48,
+    base_taker_fee: I80F48,
+) -> Result<()> {
+    let (maker_fee, taker_fee) = perp_lowest_fees(tiers, base_maker_fee, base_taker_fee);
+    require_msg_typed!(
+        maker_fee + taker_fee >= 0,
+        MangoError::InvalidPerpFeeTier,
+        "lowest maker fee {} and lowest taker fee {} must not add up to less than zero",
+        maker_fee,
+        taker_fee
+    );
+    Ok(())
+}
+
+/// Returns the tier with the given id, or None if it doesn't exist or is unused
+pub fn perp_fee_tier(tiers: &[PerpFeeTier], tier: u8) -> Option<&PerpFeeTier> {
+    let index = (tier as usize).checked_sub(1)?;
+    tiers.get(index).filter(|tier| !tier.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tier(min_volume_quote: u64) -> PerpFeeTier {
+        PerpFeeTier {
+            min_volume_quote,
+            maker_fee_discount: 0.0001,
+            taker_fee_discount: 0.0002,
+            reserved: [0; 16],
+        }
+    }
+
+    #[test]
+    fn test_perp_fee_tier_for_volume() {
+        let tiers = [tier(1000), tier(0), tier(10000), tier(5000)];
+        assert_eq!(perp_fee_tier_for_volume(&tiers, 0), 0);
+        assert_eq!(perp_fee_tier_for_volume(&tiers, 999), 0);
+        assert_eq!(perp_fee_tier_for_volume(&tiers, 1000), 1);
+        assert_eq!(perp_fee_tier_for_volume(&tiers, 5000), 4);
+        assert_eq!(perp_fee_tier_for_volume(&tiers, 20000), 3);
+
+        assert!(perp_fee_tier(&tiers, 0).is_none());
+        assert!(perp_fee_tier(&tiers, 2).is_none());
+        assert!(perp_fee_tier(&tiers, 5).is_none());
+        assert_eq!(perp_fee_tier(&tiers, 3).unwrap().min_volume_quote, 10000);
+
+        let t = tier(1);
+        assert!((t.maker_fee(I80F48::ZERO) + I80F48::from_num(0.0001)).abs() < 1e-9);
+        assert_eq!(t.taker_fee(I80F48::from_num(0.0001)), I80F48::ZERO);
+    }
+
+    #[test]
+    fn test_verify_perp_fees() {
+        let fee = |v: f64| I80F48::from_num(v);
+
+        // no tiers: base rebate must be covered by the base taker fee
+        assert!(verify_perp_fees(&[], fee(-0.0001), fee(0.0002)).is_ok());
+        assert!(verify_perp_fees(&[], fee(-0.0003), fee(0.0002)).is_err());
+
+        // maker -0.0001 in the tier, taker 0.0002 in the tier and 0.0004 at base
+        let tiers = [tier(1000)];
+        assert!(verify_perp_fees(&tiers, fee(0.0), fee(0.0004)).is_ok());
+        // maker -0.0001 in the tier, taker 0.0001 at base
+        assert!(verify_perp_fees(&tiers, fee(0.0), fee(0.0001)).is_ok());
+        // maker -0.0001 in the tier, taker 0 in the tier
+        assert!(verify_perp_fees(&tiers, fee(0.0), fee(0.0002)).is_ok());
+        // maker -0.0002 in the tier, taker 0 in the tier
+        assert!(verify_perp_fees(&tiers, fee(-0.0001), fee(0.0002)).is_err());
+
+        let unused = [tier(0)];
+        assert!(verify_perp_fees(&unused, fee(-0.0001), fee(0.0002)).is_ok());
+    }
+}
@@ -15,8 +15,8 @@ use crate::state::{oracle, TokenIndex};
 use crate::util;
 
 use super::{
-    orderbook, OracleAccountInfos, OracleConfig, OracleState, Orderbook, StablePriceModel,
-    DAY_I80F48,
+    orderbook, perp_fee_tier, perp_lowest_fees, OracleAccountInfos, OracleConfig, OracleFreshness,
+    OracleState, Orderbook, PerpFeeTier, StablePriceModel, DAY_I80F48,
 };
 
 pub type PerpMarketIndex = u16;
@@ -198,8 +198,41 @@ pub struct PerpMarket {
     /// liquidation fees that happened. So never decreases (different to fees_accrued).
     pub accrued_liquidation_fees: I80F48,
 
+    /// Token with the same underlying as this market's base, copied from
+    /// Group::portfolio_margin_pairs. Only used if portfolio_margin_active is 1.
+    pub portfolio_margin_token_index: TokenIndex,
+    pub portfolio_margin_active: u8,
+
+    #[derivative(Debug = "ignore")]
+    pub padding5: [u8; 5],
+
+    /// Weight of the stable price in the reference price that funding is computed
+    /// against. Zero uses the oracle price only, one uses the stable price only.
+    pub funding_stable_price_weight: f32,
+
+    /// Weight of the stable price in the price that pnl is settled at, see
+    /// funding_stable_price_weight. The settle limit itself is always based on the
+    /// stable price.
+    pub settle_stable_price_weight: f32,
+
+    /// Length in seconds of the opening auction that starts when the market is created
+    /// or leaves reduce-only mode. Zero disables opening auctions.
+    pub opening_auction_duration: u32,
+
     #[derivative(Debug = "ignore")]
-    pub reserved: [u8; 1848],
+    pub padding6: [u8; 4],
+
+    /// While non-zero, the market is in an opening auction: orders are posted without
+    /// matching. After this timestamp perp_clear_opening_auction executes the crossing
+    /// orders at a uniform price and resets it to zero.
+    pub opening_auction_end_ts: u64,
+
+    /// Clearing price of the opening auction, zero until the first clearing call
+    /// computed it
+    pub opening_auction_price_lots: i64,
+
+    #[derivative(Debug = "ignore")]
+    pub reserved: [u8; 1808],
 }
 
 const_assert_eq!(
@@ -237,7 +270,15 @@ const_assert_eq!(
         + 3 * 16
         + 8
         + 2 * 16
-        + 1848
+        + 2
+        + 1
+        + 5
+        + 4 * 2
+        + 4
+        + 4
+        + 8
+        + 8
+        + 1808
 );
 const_assert_eq!(size_of::<PerpMarket>(), 2808);
 const_assert_eq!(size_of::<PerpMarket>() % 8, 0);
@@ -257,6 +298,39 @@ impl PerpMarket {
         self.force_close == 1
     }
 
+    /// Token that offsets this market's base positions on portfolio margin accounts
+    ///
+    /// The settle token is never used, even if configured.
+    pub fn portfolio_margin_token_index(&self) -> Option<TokenIndex> {
+        (self.portfolio_margin_active == 1
+            && self.portfolio_margin_token_index != self.settle_token_index)
+            .then_some(self.portfolio_margin_token_index)
+    }
+
+    /// Set the token linked for portfolio margining, or clear it if token_index is None
+    pub fn set_portfolio_margin_token_index(&mut self, token_index: Option<TokenIndex>) {
+        self.portfolio_margin_active = u8::from(token_index.is_some());
+        self.portfolio_margin_token_index = token_index.unwrap_or(0);
+    }
+
+    pub fn is_in_opening_auction(&self) -> bool {
+        self.opening_auction_end_ts != 0
+    }
+
+    /// Starts an opening auction if the market has an auction duration configured
+    pub fn start_opening_auction(&mut self, now_ts: u64) {
+        if self.opening_auction_duration == 0 {
+            return;
+        }
+        self.opening_auction_end_ts = now_ts + self.opening_auction_duration as u64;
+        self.opening_auction_price_lots = 0;
+    }
+
+    pub fn end_opening_auction(&mut self) {
+        self.opening_auction_end_ts = 0;
+        self.opening_auction_price_lots = 0;
+    }
+
     pub fn elligible_for_group_insurance_fund(&self) -> bool {
         self.group_insurance_fund == 1
     }
@@ -269,6 +343,21 @@ impl PerpMarket {
         I80F48::from_num(self.settle_pnl_limit_factor)
     }
 
+    /// The maker fee for an account in the given perp fee tier
+    pub fn maker_fee_for_tier(&self, tiers: &[PerpFeeTier], tier: u8) -> I80F48 {
+        let maker_fee =
+            perp_fee_tier(tiers, tier).map_or(self.maker_fee, |t| t.maker_fee(self.maker_fee));
+        // Tiers are edited on the group without the markets' base fees at hand, see
+        // verify_perp_fees(): never pay a rebate that the lowest taker fee doesn't cover
+        let (_, lowest_taker_fee) = perp_lowest_fees(tiers, self.maker_fee, self.taker_fee);
+        maker_fee.max(-lowest_taker_fee)
+    }
+
+    /// The taker fee for an account in the given perp fee tier
+    pub fn taker_fee_for_tier(&self, tiers: &[PerpFeeTier], tier: u8) -> I80F48 {
+        perp_fee_tier(tiers, tier).map_or(self.taker_fee, |t| t.taker_fee(self.taker_fee))
+    }
+
     pub fn gen_order_id(&mut self, side: Side, price_data: u64) -> u128 {
         self.seq_num += 1;
         orderbook::new_node_key(side, price_data, self.seq_num)
@@ -286,11 +375,21 @@ impl PerpMarket {
         &self,
         oracle_acc_infos: &OracleAccountInfos<T>,
         now: Option<(u64, u64)>,
+    ) -> Result<OracleState> {
+        self.oracle_state_for(oracle_acc_infos, now, OracleFreshness::Health)
+    }
+
+    /// Like oracle_state(), but with the staleness limits for `freshness`
+    pub fn oracle_state_for<T: KeyedAccountReader>(
+        &self,
+        oracle_acc_infos: &OracleAccountInfos<T>,
+        now: Option<(u64, u64)>,
+        freshness: OracleFreshness,
     ) -> Result<OracleState> {
         require_keys_eq!(self.oracle, *oracle_acc_infos.oracle.key());
         let state = oracle::oracle_state_unchecked(oracle_acc_infos, self.base_decimals)?;
         state
-            .check_confidence_and_maybe_staleness(&self.oracle_config, now)
+            .check_confidence_and_maybe_staleness_for(&self.oracle_config, now, freshness)
             .with_context(|| oracle_log_context(self.name(), &state, &self.oracle_config, now))?;
         Ok(state)
     }
@@ -299,6 +398,27 @@ impl PerpMarket {
         I80F48::from_num(self.stable_price_model.stable_price)
     }
 
+    /// Blend of the oracle and stable price, falling back to the oracle price
+    /// while there is no stable price yet
+    fn stable_price_blend(&self, oracle_price: I80F48, stable_weight: f32) -> I80F48 {
+        let stable_price = self.stable_price();
+        if stable_weight <= 0.0 || !stable_price.is_positive() {
+            return oracle_price;
+        }
+        let weight = I80F48::from_num(stable_weight.min(1.0));
+        oracle_price * (I80F48::ONE - weight) + stable_price * weight
+    }
+
+    /// The price that funding is computed against
+    pub fn funding_reference_price(&self, oracle_price: I80F48) -> I80F48 {
+        self.stable_price_blend(oracle_price, self.funding_stable_price_weight)
+    }
+
+    /// The price that unsettled pnl is valued at when settling
+    pub fn settle_price(&self, oracle_price: I80F48) -> I80F48 {
+        self.stable_price_blend(oracle_price, self.settle_stable_price_weight)
+    }
+
     /// Use current order book price and index price to update the instantaneous funding
     pub fn update_funding_and_stable_price(
         &mut self,
@@ -312,6 +432,7 @@ impl PerpMarket {
 
         let oracle_price = oracle_state.price;
         let oracle_price_lots = self.native_price_to_lot(oracle_price);
+        let reference_price = self.funding_reference_price(oracle_price);
 
         // Get current book price & compare it to index price
         let bid =
@@ -322,11 +443,13 @@ impl PerpMarket {
                 .impact_price(self.impact_quantity, now_ts, oracle_price_lots);
 
         let funding_rate = match (bid, ask) {
+            // the book may be crossed during an opening auction and doesn't reflect a price
+            _ if self.is_in_opening_auction() => I80F48::ZERO,
             (Some(bid), Some(ask)) => {
                 // calculate mid-market rate
                 let mid_price = (bid + ask) / 2;
                 let book_price = self.lot_to_native_price(mid_price);
-                let diff = book_price / oracle_price - I80F48::ONE;
+                let diff = book_price / reference_price - I80F48::ONE;
                 diff.clamp(self.min_funding, self.max_funding)
             }
             (Some(_bid), None) => self.max_funding,
@@ -346,7 +469,7 @@ impl PerpMarket {
         let base_lot_size = I80F48::from_num(self.base_lot_size);
 
         // The number of native quote that one base lot should pay in funding
-        let funding_delta = oracle_price * base_lot_size * funding_rate * time_factor;
+        let funding_delta = reference_price * base_lot_size * funding_rate * time_factor;
 
         self.long_funding += funding_delta;
         self.short_funding += funding_delta;
@@ -420,6 +543,36 @@ impl PerpMarket {
         Ok(socialized_loss)
     }
 
+    /// Settle token value of an order, for delegate notional limits.
+    ///
+    /// Bids can fill up to their limit price and are valued at the higher of the limit and
+    /// the oracle price, asks are valued at the oracle price. Orders may be limited by base
+    /// or by quote lots, whichever is smaller applies.
+    pub fn order_notional(
+        &self,
+        side: Side,
+        price_lots: i64,
+        max_base_lots: i64,
+        max_quote_lots: i64,
+        oracle_price: I80F48,
+    ) -> I80F48 {
+        let price = match side {
+            Side::Bid => {
+                let limit_price = I80F48::from(price_lots)
+                    .saturating_mul(I80F48::from(self.quote_lot_size))
+                    / I80F48::from(self.base_lot_size);
+                limit_price.max(oracle_price)
+            }
+            Side::Ask => oracle_price,
+        };
+        let base_notional = I80F48::from(max_base_lots)
+            .saturating_mul(I80F48::from(self.base_lot_size))
+            .saturating_mul(price);
+        let quote_notional =
+            I80F48::from(max_quote_lots).saturating_mul(I80F48::from(self.quote_lot_size));
+        base_notional.min(quote_notional)
+    }
+
     /// Returns the fee for settling `settlement` when the account with positive unsettled pnl
     /// has the given source pnl/position/health values.
     pub fn compute_settle_fee(
@@ -496,7 +649,10 @@ impl PerpMarket {
             oracle_config: OracleConfig {
                 conf_filter: I80F48::ZERO,
                 max_staleness_slots: -1,
-                reserved: [0; 72],
+                max_staleness_seconds: 0,
+                risk_increasing_max_staleness_slots: 0,
+                risk_increasing_max_staleness_seconds: 0,
+                reserved: [0; 48],
             },
             stable_price_model: StablePriceModel::default(),
             quote_lot_size: 1,
@@ -535,7 +691,41 @@ impl PerpMarket {
             fees_withdrawn: 0,
             platform_liquidation_fee: I80F48::ZERO,
             accrued_liquidation_fees: I80F48::ZERO,
-            reserved: [0; 1848],
+            portfolio_margin_token_index: 0,
+            portfolio_margin_active: 0,
+            padding5: Default::default(),
+            funding_stable_price_weight: 0.0,
+            settle_stable_price_weight: 0.0,
+            opening_auction_duration: 0,
+            padding6: Default::default(),
+            opening_auction_end_ts: 0,
+            opening_auction_price_lots: 0,
+            reserved: [0; 1808],
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stable_price_blend() {
+        let mut market = PerpMarket::default_for_tests();
+        let oracle_price = I80F48::from_num(120);
+
+        // no stable price yet
+        market.funding_stable_price_weight = 1.0;
+        assert_eq!(market.funding_reference_price(oracle_price), oracle_price);
+
+        market.stable_price_model.reset_to_price(100.0, 0);
+        assert_eq!(market.settle_price(oracle_price), oracle_price);
+        assert_eq!(
+            market.funding_reference_price(oracle_price),
+            I80F48::from_num(100)
+        );
+
+        market.settle_stable_price_weight = 0.25;
+        assert_eq!(market.settle_price(oracle_price), I80F48::from_num(115));
+    }
+}
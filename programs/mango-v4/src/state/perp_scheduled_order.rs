@@ -0,0 +1,375 @@
+use anchor_lang::prelude::*;
+
+use derivative::Derivative;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use static_assertions::const_assert_eq;
+use std::mem::size_of;
+
+use crate::error::MangoError;
+use crate::state::*;
+
+/// Incentive to pay to callers who trigger a child order, in $1e-6
+pub const PERP_SCHEDULED_ORDER_INCENTIVE: u64 = 1_000; // $0.001, same as TCS_START_INCENTIVE
+
+#[derive(
+    Eq,
+    PartialEq,
+    Copy,
+    Clone,
+    TryFromPrimitive,
+    IntoPrimitive,
+    Debug,
+    AnchorSerialize,
+    AnchorDeserialize,
+)]
+#[repr(u8)]
+pub enum PerpScheduledOrderType {
+    /// Every interval, take up to child_base_lots with an immediate-or-cancel order
+    Twap,
+    /// Keep a limit order of child_base_lots on the book, placing the next one
+    /// once the previous was filled or cancelled
+    Iceberg,
+    /// Reduce the position with an immediate-or-cancel order once the oracle price
+    /// moves against it, past trigger_price_lots
+    StopLoss,
+    /// Reduce the position with an immediate-or-cancel order once the oracle price
+    /// moves in its favor, past trigger_price_lots
+    TakeProfit,
+}
+
+/// A large perp order that gets split into child orders over time, or a
+/// conditional order that reduces a perp position once the oracle crosses a threshold.
+///
+/// Child orders are placed by permissionless callers of the perp_scheduled_order_trigger
+/// instruction, who receive PERP_SCHEDULED_ORDER_INCENTIVE in the market's settle token
+/// for each trigger that fills or places a non-zero amount.
+#[zero_copy]
+#[derive(AnchorDeserialize, AnchorSerialize, Derivative, PartialEq)]
+#[derivative(Debug)]
+pub struct PerpScheduledOrder {
+    pub id: u64,
+
+    /// Total base lots to execute over all child orders
+    pub max_base_lots: i64,
+
+    /// Base lots that were already filled (Twap) or placed on the book (Iceberg)
+    pub executed_base_lots: i64,
+
+    /// Maximum base lots per child order
+    pub child_base_lots: i64,
+
+    /// Limit price for all child orders, in lots (unused for conditional orders)
+    pub price_lots: i64,
+
+    /// Minimum time between two child orders
+    pub interval_seconds: u64,
+
+    /// Child orders can be triggered at or after this timestamp
+    pub next_trigger_timestamp: u64,
+
+    /// Timestamp until which the scheduled order is valid
+    pub expiry_timestamp: u64,
+
+    pub market_index: PerpMarketIndex,
+
+    /// If this struct is in use. (scheduled orders are stored in a static-length array)
+    pub is_configured: u8,
+
+    /// Stores a Side enum value
+    pub side: u8,
+
+    /// Stores a PerpScheduledOrderType enum value
+    pub order_type: u8,
+
+    pub reduce_only: u8,
+
+    /// Whether an iceberg child order may be resting on the book, see child_order_seq_num
+    pub has_child_order: u8,
+
+    pub padding: [u8; 1],
+
+    /// Oracle price at which conditional orders become triggerable, in lots
+    pub trigger_price_lots: i64,
+
+    /// Worst acceptable execution price of conditional orders, relative to the
+    /// oracle price at trigger time. 0.01 means 1%.
+    pub max_slippage: f32,
+
+    #[derivative(Debug = "ignore")]
+    pub padding2: [u8; 4],
+
+    /// Sequence number of the last iceberg child order placed on the book.
+    ///
+    /// Order ids are unique per market by their sequence number, see node_key_seq_num(),
+    /// so the child is found by it instead of by its client order id, which the user
+    /// may be using for other orders.
+    pub child_order_seq_num: u64,
+
+    #[derivative(Debug = "ignore")]
+    pub reserved: [u8; 40],
+}
+
+const_assert_eq!(
+    size_of::<PerpScheduledOrder>(),
+    8 * 8 + 2 + 1 * 5 + 1 + 8 + 4 + 4 + 8 + 40
+);
+const_assert_eq!(size_of::<PerpScheduledOrder>(), 136);
+const_assert_eq!(size_of::<PerpScheduledOrder>() % 8, 0);
+
+impl Default for PerpScheduledOrder {
+    fn default() -> Self {
+        Self {
+            id: 0,
+            max_base_lots: 0,
+            executed_base_lots: 0,
+            child_base_lots: 0,
+            price_lots: 0,
+            interval_seconds: 0,
+            next_trigger_timestamp: 0,
+            expiry_timestamp: u64::MAX,
+            market_index: PerpMarketIndex::MAX,
+            is_configured: 0,
+            side: Side::Bid.into(),
+            order_type: PerpScheduledOrderType::Twap.into(),
+            reduce_only: 0,
+            has_child_order: 0,
+            padding: Default::default(),
+            trigger_price_lots: 0,
+            max_slippage: 0.0,
+            padding2: Default::default(),
+            child_order_seq_num: 0,
+            reserved: [0; 40],
+        }
+    }
+}
+
+impl PerpScheduledOrder {
+    pub fn is_configured(&self) -> bool {
+        self.is_configured == 1
+    }
+
+    pub fn side(&self) -> Side {
+        self.side.try_into().unwrap()
+    }
+
+    pub fn order_type(&self) -> PerpScheduledOrderType {
+        self.order_type.try_into().unwrap()
+    }
+
+    /// Stop-loss and take-profit orders wait for an oracle price condition
+    pub fn is_conditional(&self) -> bool {
+        matches!(
+            self.order_type(),
+            PerpScheduledOrderType::StopLoss | PerpScheduledOrderType::TakeProfit
+        )
+    }
+
+    /// Checks the oracle price condition of conditional orders
+    ///
+    /// Asks close long positions: a stop-loss triggers when the price falls to
+    /// the trigger price, a take-profit when it rises to it. Bids are the reverse.
+    pub fn is_price_triggered(&self, oracle_price_lots: i64) -> bool {
+        let price_below = oracle_price_lots <= self.trigger_price_lots;
+        let price_above = oracle_price_lots >= self.trigger_price_lots;
+        match (self.order_type(), self.side()) {
+            (PerpScheduledOrderType::StopLoss, Side::Ask) => price_below,
+            (PerpScheduledOrderType::StopLoss, Side::Bid) => price_above,
+            (PerpScheduledOrderType::TakeProfit, Side::Ask) => price_above,
+            (PerpScheduledOrderType::TakeProfit, Side::Bid) => price_below,
+            _ => true,
+        }
+    }
+
+    /// Limit price of the child order of conditional orders
+    pub fn conditional_limit_price_lots(&self, oracle_price_lots: i64) -> i64 {
+        let factor = match self.side() {
+            Side::Bid => 1.0 + self.max_slippage as f64,
+            Side::Ask => 1.0 - self.max_slippage as f64,
+        };
+        ((oracle_price_lots as f64 * factor).round() as i64).max(1)
+    }
+
+    pub fn is_reduce_only(&self) -> bool {
+        self.reduce_only == 1
+    }
+
+    pub fn is_expired(&self, now_ts: u64) -> bool {
+        now_ts >= self.expiry_timestamp
+    }
+
+    pub fn remaining_base_lots(&self) -> i64 {
+        self.max_base_lots - self.executed_base_lots
+    }
+
+    /// Size of the next child order
+    pub fn next_child_base_lots(&self) -> i64 {
+        self.child_base_lots.min(self.remaining_base_lots())
+    }
+
+    /// Whether the perp open order is the iceberg child order that was placed last
+    pub fn is_child_order(&self, oo: &PerpOpenOrder) -> bool {
+        self.has_child_order == 1
+            && oo.is_active_for_market(self.market_index)
+            && node_key_seq_num(oo.side_and_tree().side(), oo.id) == self.child_order_seq_num
+    }
+
+    /// Remember the child order that was placed on the book, if any
+    pub fn set_child_order(&mut self, side: Side, order_id: Option<u128>) {
+        self.has_child_order = u8::from(order_id.is_some());
+        self.child_order_seq_num = order_id.map_or(0, |id| node_key_seq_num(side, id));
+    }
+
+    pub fn check_triggerable(&self, now_ts: u64, oracle_price_lots: i64) -> Result<()> {
+        require!(self.is_configured(), MangoError::PerpScheduledOrderNotSet);
+        require!(
+            !self.is_expired(now_ts),
+            MangoError::PerpScheduledOrderExpired
+        );
+        require_msg_typed!(
+            now_ts >= self.next_trigger_timestamp,
+            MangoError::PerpScheduledOrderNotTriggerable,
+            "next child order can be placed at {}, now is {}",
+            self.next_trigger_timestamp,
+            now_ts
+        );
+        require_msg_typed!(
+            self.is_price_triggered(oracle_price_lots),
+            MangoError::PerpConditionalOrderPriceNotTriggered,
+            "oracle price {} lots, trigger price {} lots",
+            oracle_price_lots,
+            self.trigger_price_lots
+        );
+        Ok(())
+    }
+
+    /// The child order to place when triggering
+    pub fn child_order(&self, oracle_price_lots: i64) -> Order {
+        let params = match self.order_type() {
+            PerpScheduledOrderType::Twap => OrderParams::ImmediateOrCancel {
+                price_lots: self.price_lots,
+            },
+            PerpScheduledOrderType::Iceberg => OrderParams::Fixed {
+                price_lots: self.price_lots,
+                order_type: PostOrderType::Limit,
+            },
+            PerpScheduledOrderType::StopLoss | PerpScheduledOrderType::TakeProfit => {
+                OrderParams::ImmediateOrCancel {
+                    price_lots: self.conditional_limit_price_lots(oracle_price_lots),
+                }
+            }
+        };
+        Order {
+            side: self.side(),
+            max_base_lots: self.next_child_base_lots(),
+            max_quote_lots: i64::MAX,
+            client_order_id: self.id,
+            reduce_only: self.is_reduce_only(),
+            time_in_force: 0,
+            self_trade_behavior: SelfTradeBehavior::DecrementTake,
+            params,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_child_orders() {
+        let order = PerpScheduledOrder {
+            is_configured: 1,
+            max_base_lots: 25,
+            executed_base_lots: 20,
+            child_base_lots: 10,
+            price_lots: 1000,
+            next_trigger_timestamp: 100,
+            expiry_timestamp: 200,
+            side: Side::Ask.into(),
+            order_type: PerpScheduledOrderType::Iceberg.into(),
+            ..PerpScheduledOrder::default()
+        };
+        assert_eq!(order.next_child_base_lots(), 5);
+        assert!(order.check_triggerable(99, 0).is_err());
+        assert!(order.check_triggerable(100, 0).is_ok());
+        assert!(order.check_triggerable(200, 0).is_err());
+
+        let child = order.child_order(0);
+        assert_eq!(child.side, Side::Ask);
+        assert_eq!(child.max_base_lots, 5);
+        assert!(matches!(
+            child.params,
+            OrderParams::Fixed {
+                price_lots: 1000,
+                order_type: PostOrderType::Limit
+            }
+        ));
+
+        // the child is found by its order id, not its client order id
+        let mut order = PerpScheduledOrder {
+            market_index: 0,
+            ..order
+        };
+        let oo = PerpOpenOrder {
+            side_and_tree: SideAndOrderTree::AskFixed.into(),
+            market: 0,
+            client_id: order.id,
+            id: new_node_key(Side::Ask, 1000, 7),
+            ..PerpOpenOrder::default()
+        };
+        assert!(!order.is_child_order(&oo));
+        order.set_child_order(Side::Ask, Some(new_node_key(Side::Ask, 1000, 8)));
+        assert!(!order.is_child_order(&oo));
+        order.set_child_order(Side::Ask, Some(oo.id));
+        assert!(order.is_child_order(&oo));
+        order.set_child_order(Side::Ask, None);
+        assert!(!order.is_child_order(&oo));
+    }
+
+    #[test]
+    fn test_conditional_orders() {
+        let order = |order_type: PerpScheduledOrderType, side: Side| PerpScheduledOrder {
+            is_configured: 1,
+            max_base_lots: 10,
+            child_base_lots: 10,
+            trigger_price_lots: 1000,
+            max_slippage: 0.01,
+            side: side.into(),
+            order_type: order_type.into(),
+            reduce_only: 1,
+            ..PerpScheduledOrder::default()
+        };
+
+        let long_stop_loss = order(PerpScheduledOrderType::StopLoss, Side::Ask);
+        assert!(long_stop_loss.is_conditional());
+        assert!(!long_stop_loss.is_price_triggered(1001));
+        assert!(long_stop_loss.is_price_triggered(1000));
+        assert!(long_stop_loss.check_triggerable(0, 999).is_ok());
+        assert!(long_stop_loss.check_triggerable(0, 1001).is_err());
+
+        let long_take_profit = order(PerpScheduledOrderType::TakeProfit, Side::Ask);
+        assert!(!long_take_profit.is_price_triggered(999));
+        assert!(long_take_profit.is_price_triggered(1001));
+
+        let short_stop_loss = order(PerpScheduledOrderType::StopLoss, Side::Bid);
+        assert!(!short_stop_loss.is_price_triggered(999));
+        assert!(short_stop_loss.is_price_triggered(1001));
+
+        let short_take_profit = order(PerpScheduledOrderType::TakeProfit, Side::Bid);
+        assert!(short_take_profit.is_price_triggered(999));
+        assert!(!short_take_profit.is_price_triggered(1001));
+
+        let child = long_stop_loss.child_order(900);
+        assert!(child.reduce_only);
+        assert_eq!(child.max_base_lots, 10);
+        assert!(matches!(
+            child.params,
+            OrderParams::ImmediateOrCancel { price_lots: 891 }
+        ));
+        let child = short_stop_loss.child_order(1100);
+        assert!(matches!(
+            child.params,
+            OrderParams::ImmediateOrCancel { price_lots: 1111 }
+        ));
+    }
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use derivative::Derivative;
+use static_assertions::const_assert_eq;
+use std::mem::size_of;
+
+use crate::state::*;
+
+/// Number of token/perp market pairs a group can link for portfolio margining
+pub const PORTFOLIO_MARGIN_PAIR_COUNT: usize = 16;
+
+/// Links a token to a perp market with the same underlying, like SOL and SOL-PERP.
+///
+/// For accounts that opted into portfolio margining, spot balances in the token offset
+/// opposite perp base positions before weights are applied, see
+/// HealthCache::portfolio_margin_hedges().
+///
+/// The token index is copied to the perp market, since health computations only have
+/// access to perp markets.
+#[zero_copy]
+#[derive(Derivative, PartialEq)]
+#[derivative(Debug)]
+pub struct PortfolioMarginPair {
+    pub token_index: TokenIndex,
+    pub perp_market_index: PerpMarketIndex,
+
+    /// 1 if the pair is in use. Zeroed entries would otherwise link token 0 and perp 0.
+    pub active: u8,
+
+    #[derivative(Debug = "ignore")]
+    pub reserved: [u8; 11],
+}
+const_assert_eq!(size_of::<PortfolioMarginPair>(), 2 + 2 + 1 + 11);
+const_assert_eq!(size_of::<PortfolioMarginPair>(), 16);
+const_assert_eq!(size_of::<PortfolioMarginPair>() % 8, 0);
+
+impl PortfolioMarginPair {
+    pub fn is_active(&self) -> bool {
+        self.active == 1
+    }
+}
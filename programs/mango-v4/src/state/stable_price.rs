@@ -95,6 +95,16 @@ impl StablePriceModel {
         self.reset_on_nonzero_price = if oracle_price > 0.0 { 0 } else { 1 };
     }
 
+    /// The delay price of the most recently completed delay interval
+    pub fn latest_delay_price(&self) -> f64 {
+        let index = if self.last_delay_interval_index == 0 {
+            self.delay_prices.len() - 1
+        } else {
+            self.last_delay_interval_index as usize - 1
+        };
+        self.delay_prices[index]
+    }
+
     pub fn delay_interval_index(&self, timestamp: u64) -> u8 {
         ((timestamp / self.delay_interval_seconds as u64) % self.delay_prices.len() as u64) as u8
     }
@@ -20,7 +20,9 @@ mod test_benchmark;
 mod test_borrow_limits;
 mod test_collateral_fees;
 mod test_delegate;
+mod test_emode;
 mod test_fees_buyback_with_mngo;
+mod test_fixed_term;
 mod test_force_close;
 mod test_health_check;
 mod test_health_compute;
@@ -32,9 +34,19 @@ mod test_liq_perps_force_cancel;
 mod test_liq_perps_positive_pnl;
 mod test_liq_tokens;
 mod test_margin_trade;
+mod test_openbook_v2;
+mod test_oracle_aggregator;
 mod test_perp;
+mod test_perp_edit_order;
+mod test_perp_fee_tiers;
+mod test_perp_opening_auction;
+mod test_perp_place_orders_batch;
+mod test_perp_resize_orderbook;
+mod test_perp_scheduled_order;
 mod test_perp_settle;
 mod test_perp_settle_fees;
+mod test_perp_stable_price;
+mod test_portfolio_margin;
 mod test_position_lifetime;
 mod test_reduce_only;
 mod test_replay;
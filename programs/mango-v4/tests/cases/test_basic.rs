@@ -38,6 +38,9 @@ async fn test_basic() -> Result<(), TransportError> {
             perp_count: 3,
             perp_oo_count: 3,
             token_conditional_swap_count: 3,
+            openbook_v2_count: 0,
+            fixed_term_count: 0,
+            perp_scheduled_order_count: 0,
             group,
             owner,
             payer,
@@ -339,6 +342,9 @@ async fn test_account_size_migration() -> Result<(), TransportError> {
             perp_count: 3,
             perp_oo_count: 3,
             token_conditional_swap_count: 3,
+            openbook_v2_count: 0,
+            fixed_term_count: 0,
+            perp_scheduled_order_count: 0,
             group,
             owner,
             payer,
@@ -688,6 +694,118 @@ async fn test_bank_deposit_limit() -> Result<(), TransportError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_bank_borrow_limit() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let solana = &context.solana.clone();
+
+    let admin = TestKeypair::new();
+    let owner = context.users[0].key;
+    let payer = context.users[1].key;
+    let mints = &context.mints[0..2];
+
+    let mango_setup::GroupWithTokens { group, .. } = mango_setup::GroupWithTokensConfig {
+        admin,
+        payer,
+        mints: mints.to_vec(),
+        zero_token_is_quote: true,
+        ..mango_setup::GroupWithTokensConfig::default()
+    }
+    .create(solana)
+    .await;
+
+    // provides liquidity in both tokens
+    create_funded_account(
+        &solana,
+        group,
+        owner,
+        0,
+        &context.users[1],
+        mints,
+        100_000,
+        0,
+    )
+    .await;
+
+    // has collateral in the first token and borrows the second
+    let account = create_funded_account(
+        &solana,
+        group,
+        owner,
+        1,
+        &context.users[1],
+        &mints[0..1],
+        100_000,
+        0,
+    )
+    .await;
+
+    send_tx(
+        solana,
+        TokenEdit {
+            group,
+            admin,
+            mint: mints[1].pubkey,
+            fallback_oracle: Pubkey::default(),
+            options: mango_v4::instruction::TokenEdit {
+                borrow_limit_opt: Some(1000),
+                ..token_edit_instruction_default()
+            },
+        },
+    )
+    .await
+    .unwrap();
+
+    let default_withdraw_ix = TokenWithdrawInstruction {
+        amount: 0,
+        allow_borrow: true,
+        account,
+        owner,
+        token_account: context.users[1].token_accounts[1],
+        bank_index: 0,
+    };
+
+    send_tx_expect_error!(
+        solana,
+        TokenWithdrawInstruction {
+            amount: 1001,
+            ..default_withdraw_ix
+        },
+        MangoError::BankBorrowLimit
+    );
+
+    send_tx(
+        solana,
+        TokenWithdrawInstruction {
+            amount: 900,
+            ..default_withdraw_ix
+        },
+    )
+    .await
+    .unwrap();
+
+    send_tx_expect_error!(
+        solana,
+        TokenWithdrawInstruction {
+            amount: 200,
+            ..default_withdraw_ix
+        },
+        MangoError::BankBorrowLimit
+    );
+
+    send_tx(
+        solana,
+        TokenWithdrawInstruction {
+            amount: 50,
+            ..default_withdraw_ix
+        },
+    )
+    .await
+    .unwrap();
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_withdraw_skip_bank() -> Result<(), TransportError> {
     let context = TestContext::new().await;
@@ -976,6 +1094,9 @@ async fn test_sequence_check() -> Result<(), TransportError> {
             perp_count: 3,
             perp_oo_count: 3,
             token_conditional_swap_count: 3,
+            openbook_v2_count: 0,
+            fixed_term_count: 0,
+            perp_scheduled_order_count: 0,
             group,
             owner,
             payer,
@@ -1049,3 +1170,115 @@ async fn test_sequence_check() -> Result<(), TransportError> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_token_transfer() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let solana = &context.solana.clone();
+
+    let admin = TestKeypair::new();
+    let owner = context.users[0].key;
+    let other_owner = context.users[1].key;
+    let payer = context.users[1].key;
+    let mints = &context.mints[0..2];
+
+    let mango_setup::GroupWithTokens { group, tokens, .. } = mango_setup::GroupWithTokensConfig {
+        admin,
+        payer,
+        mints: mints.to_vec(),
+        ..mango_setup::GroupWithTokensConfig::default()
+    }
+    .create(solana)
+    .await;
+    let bank = tokens[0].bank;
+
+    let source = create_funded_account(
+        &solana,
+        group,
+        owner,
+        0,
+        &context.users[1],
+        &mints[0..1],
+        1000,
+        0,
+    )
+    .await;
+    let destination =
+        create_funded_account(&solana, group, owner, 1, &context.users[1], &[], 0, 0).await;
+    let foreign =
+        create_funded_account(&solana, group, other_owner, 0, &context.users[1], &[], 0, 0).await;
+
+    //
+    // TEST: Transfer part of the deposits to another account of the same owner
+    //
+    send_tx(
+        solana,
+        TokenTransferInstruction {
+            amount: 400,
+            source,
+            destination,
+            owner,
+            mint: mints[0].pubkey,
+            bank_index: 0,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(account_position(solana, source, bank).await, 600);
+    assert_eq!(account_position(solana, destination, bank).await, 400);
+
+    //
+    // TEST: Transfers can't create borrows
+    //
+    assert!(send_tx(
+        solana,
+        TokenTransferInstruction {
+            amount: 601,
+            source,
+            destination,
+            owner,
+            mint: mints[0].pubkey,
+            bank_index: 0,
+        },
+    )
+    .await
+    .is_err());
+
+    //
+    // TEST: The destination must have the same owner
+    //
+    assert!(send_tx(
+        solana,
+        TokenTransferInstruction {
+            amount: 100,
+            source,
+            destination: foreign,
+            owner,
+            mint: mints[0].pubkey,
+            bank_index: 0,
+        },
+    )
+    .await
+    .is_err());
+
+    //
+    // TEST: Transfer everything, closing the source position
+    //
+    send_tx(
+        solana,
+        TokenTransferInstruction {
+            amount: u64::MAX,
+            source,
+            destination,
+            owner,
+            mint: mints[0].pubkey,
+            bank_index: 0,
+        },
+    )
+    .await
+    .unwrap();
+    assert!(account_position_closed(solana, source, bank).await);
+    assert_eq!(account_position(solana, destination, bank).await, 1000);
+
+    Ok(())
+}
@@ -137,3 +137,306 @@ async fn test_delegate() -> Result<(), TransportError> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_delegate_scope() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let solana = &context.solana.clone();
+
+    let admin = TestKeypair::new();
+    let owner = context.users[0].key;
+    let payer = context.users[1].key;
+    let delegate = context.users[1].key;
+    let mints = &context.mints[0..2];
+
+    //
+    // SETUP: Create a group, a perp market, an account with a delegate
+    //
+
+    let GroupWithTokens { group, tokens, .. } = GroupWithTokensConfig {
+        admin,
+        payer,
+        mints: mints.to_vec(),
+        ..GroupWithTokensConfig::default()
+    }
+    .create(solana)
+    .await;
+
+    let account = create_funded_account(
+        &solana,
+        group,
+        owner,
+        0,
+        &context.users[1],
+        mints,
+        100_000,
+        0,
+    )
+    .await;
+
+    let mango_v4::accounts::PerpCreateMarket { perp_market, .. } = send_tx(
+        solana,
+        PerpCreateMarketInstruction {
+            group,
+            admin,
+            payer,
+            perp_market_index: 0,
+            quote_lot_size: 10,
+            base_lot_size: 100,
+            maint_base_asset_weight: 0.975,
+            init_base_asset_weight: 0.95,
+            maint_base_liab_weight: 1.025,
+            init_base_liab_weight: 1.05,
+            base_liquidation_fee: 0.012,
+            maker_fee: 0.0,
+            taker_fee: 0.0,
+            ..PerpCreateMarketInstruction::with_new_book_and_queue(&solana, &tokens[1]).await
+        },
+    )
+    .await
+    .unwrap();
+    let price_lots = {
+        let perp_market = solana.get_account::<PerpMarket>(perp_market).await;
+        perp_market.native_price_to_lot(I80F48::ONE)
+    };
+
+    send_tx(
+        solana,
+        AccountEditInstruction {
+            delegate: delegate.pubkey(),
+            account_num: 0,
+            group,
+            owner,
+            name: "new_name".to_owned(),
+        },
+    )
+    .await
+    .unwrap();
+
+    let place_bid = |max_base_lots: i64| PerpPlaceOrderInstruction {
+        account,
+        perp_market,
+        owner: delegate,
+        side: Side::Bid,
+        price_lots,
+        max_base_lots,
+        client_order_id: 1,
+        ..PerpPlaceOrderInstruction::default()
+    };
+    let cancel_all = || PerpCancelAllOrdersInstruction {
+        account,
+        perp_market,
+        owner: delegate,
+        limit: 10,
+    };
+
+    //
+    // TEST: Restrict the delegate to perp trading with a notional limit
+    //
+    send_tx(
+        solana,
+        AccountEditDelegateScopeInstruction {
+            account,
+            owner,
+            temporary_delegate: false,
+            scope: DelegateScope {
+                permissions: 1 << DelegatePermission::PerpTrade as u16,
+                perp_market_index: 0,
+                max_notional_per_order: 1000,
+                ..DelegateScope::default()
+            },
+        },
+    )
+    .await
+    .unwrap();
+
+    // one lot is worth 100 native units of the settle token
+    let res = send_tx(solana, place_bid(11)).await;
+    assert_mango_error(
+        &res,
+        MangoError::DelegateOrderNotionalExceeded.into(),
+        "order above the notional limit".to_string(),
+    );
+    send_tx(solana, place_bid(10)).await.unwrap();
+    send_tx(solana, cancel_all()).await.unwrap();
+
+    // bids above the oracle price are valued at their limit price
+    let res = send_tx(
+        solana,
+        PerpPlaceOrderInstruction {
+            price_lots: price_lots * 2,
+            max_base_lots: 6,
+            ..place_bid(6)
+        },
+    )
+    .await;
+    assert_mango_error(
+        &res,
+        MangoError::DelegateOrderNotionalExceeded.into(),
+        "bid valued at its limit price".to_string(),
+    );
+
+    //
+    // TEST: The delegate can't use other permissions or edit its scope
+    //
+    let res = send_tx(
+        solana,
+        TokenWithdrawInstruction {
+            amount: 1,
+            allow_borrow: false,
+            account,
+            owner: delegate,
+            token_account: context.users[0].token_accounts[0],
+            bank_index: 0,
+        },
+    )
+    .await;
+    assert_mango_error(
+        &res,
+        MangoError::DelegatePermissionDenied.into(),
+        "withdraw not permitted".to_string(),
+    );
+
+    let res = send_tx(
+        solana,
+        AccountEditDelegateScopeInstruction {
+            account,
+            owner: delegate,
+            temporary_delegate: false,
+            scope: DelegateScope::default(),
+        },
+    )
+    .await;
+    assert!(res.is_err());
+
+    //
+    // TEST: Cancel-only delegates can't place orders
+    //
+    send_tx(solana, place_bid(5)).await.unwrap();
+    send_tx(
+        solana,
+        AccountEditDelegateScopeInstruction {
+            account,
+            owner,
+            temporary_delegate: false,
+            scope: DelegateScope {
+                permissions: 1 << DelegatePermission::PerpCancel as u16,
+                ..DelegateScope::default()
+            },
+        },
+    )
+    .await
+    .unwrap();
+
+    let res = send_tx(solana, place_bid(5)).await;
+    assert_mango_error(
+        &res,
+        MangoError::DelegatePermissionDenied.into(),
+        "placing not permitted".to_string(),
+    );
+    send_tx(solana, cancel_all()).await.unwrap();
+
+    //
+    // TEST: Unknown permission bits are rejected
+    //
+    let res = send_tx(
+        solana,
+        AccountEditDelegateScopeInstruction {
+            account,
+            owner,
+            temporary_delegate: false,
+            scope: DelegateScope {
+                permissions: 1 << 15,
+                ..DelegateScope::default()
+            },
+        },
+    )
+    .await;
+    assert!(res.is_err());
+
+    //
+    // TEST: Limits without permissions would do nothing and are rejected
+    //
+    for scope in [
+        DelegateScope {
+            perp_market_index: 0,
+            ..DelegateScope::default()
+        },
+        DelegateScope {
+            max_notional_per_order: 1000,
+            ..DelegateScope::default()
+        },
+    ] {
+        let res = send_tx(
+            solana,
+            AccountEditDelegateScopeInstruction {
+                account,
+                owner,
+                temporary_delegate: false,
+                scope,
+            },
+        )
+        .await;
+        assert!(res.is_err());
+    }
+
+    //
+    // TEST: Clearing the scope restores full delegate rights
+    //
+    send_tx(
+        solana,
+        AccountEditDelegateScopeInstruction {
+            account,
+            owner,
+            temporary_delegate: false,
+            scope: DelegateScope::default(),
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(solana, place_bid(20)).await.unwrap();
+
+    let mango_account = get_mango_account(solana, account).await;
+    assert_eq!(mango_account.fixed.delegate_scope, DelegateScope::default());
+
+    //
+    // TEST: Reassigning the delegate clears its scope
+    //
+    let scope = DelegateScope {
+        permissions: 1 << DelegatePermission::PerpCancel as u16,
+        ..DelegateScope::default()
+    };
+    send_tx(
+        solana,
+        AccountEditDelegateScopeInstruction {
+            account,
+            owner,
+            temporary_delegate: false,
+            scope,
+        },
+    )
+    .await
+    .unwrap();
+
+    // setting the same delegate again keeps the scope
+    let edit_delegate = |delegate: Pubkey| AccountEditInstruction {
+        delegate,
+        account_num: 0,
+        group,
+        owner,
+        name: "new_name".to_owned(),
+    };
+    send_tx(solana, edit_delegate(delegate.pubkey()))
+        .await
+        .unwrap();
+    let mango_account = get_mango_account(solana, account).await;
+    assert_eq!(mango_account.fixed.delegate_scope, scope);
+
+    send_tx(solana, edit_delegate(TestKeypair::new().pubkey()))
+        .await
+        .unwrap();
+    let mango_account = get_mango_account(solana, account).await;
+    assert_eq!(mango_account.fixed.delegate_scope, DelegateScope::default());
+
+    Ok(())
+}
@@ -0,0 +1,256 @@
+use super::*;
+
+#[tokio::test]
+async fn test_emode() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let solana = &context.solana.clone();
+
+    let admin = TestKeypair::new();
+    let owner = context.users[0].key;
+    let payer = context.users[1].key;
+    let mints = &context.mints[0..3];
+
+    //
+    // SETUP: Create a group and accounts, token 1 and 2 are correlated
+    //
+
+    let GroupWithTokens { group, tokens, .. } = GroupWithTokensConfig {
+        admin,
+        payer,
+        mints: mints.to_vec(),
+        zero_token_is_quote: true,
+        ..GroupWithTokensConfig::default()
+    }
+    .create(solana)
+    .await;
+
+    // provides liquidity
+    create_funded_account(
+        &solana,
+        group,
+        owner,
+        0,
+        &context.users[1],
+        mints,
+        100_000,
+        0,
+    )
+    .await;
+
+    // has 1000 of token 1 deposited
+    let account = create_funded_account(
+        &solana,
+        group,
+        owner,
+        1,
+        &context.users[1],
+        &mints[1..2],
+        1000,
+        0,
+    )
+    .await;
+    assert_eq!(account_init_health(solana, account).await.round(), 600.0);
+
+    let withdraw = |token: usize, amount: u64| TokenWithdrawInstruction {
+        amount,
+        allow_borrow: true,
+        account,
+        owner,
+        token_account: context.users[1].token_accounts[token],
+        bank_index: 0,
+    };
+
+    // with regular weights, 600 of token 2 can't be borrowed
+    assert!(send_tx(solana, withdraw(2, 600)).await.is_err());
+
+    //
+    // TEST: Category validation
+    //
+
+    let category = |category: u8, token_indexes: Vec<TokenIndex>, banks: Vec<Pubkey>| {
+        GroupEditEmodeCategoryInstruction {
+            group,
+            admin,
+            category,
+            token_indexes,
+            maint_asset_weight: 0.95,
+            init_asset_weight: 0.9,
+            maint_liab_weight: 1.05,
+            init_liab_weight: 1.1,
+            banks,
+        }
+    };
+
+    send_tx_expect_error!(
+        solana,
+        category(5, vec![1, 2], vec![tokens[1].bank, tokens[2].bank]),
+        MangoError::InvalidEmodeCategory,
+    );
+    send_tx_expect_error!(
+        solana,
+        GroupEditEmodeCategoryInstruction {
+            init_asset_weight: 0.99,
+            ..category(1, vec![1, 2], vec![tokens[1].bank, tokens[2].bank])
+        },
+        MangoError::InvalidEmodeCategory,
+    );
+    // all banks must be passed
+    assert!(
+        send_tx(solana, category(1, vec![1, 2], vec![tokens[1].bank]))
+            .await
+            .is_err()
+    );
+    send_tx_expect_error!(
+        solana,
+        AccountSetEmodeCategoryInstruction {
+            account,
+            owner,
+            category: 1,
+        },
+        MangoError::InvalidEmodeCategory,
+    );
+
+    send_tx(
+        solana,
+        category(1, vec![1, 2], vec![tokens[1].bank, tokens[2].bank]),
+    )
+    .await
+    .unwrap();
+
+    // a token can only be in one category
+    send_tx_expect_error!(
+        solana,
+        category(2, vec![0, 1], vec![tokens[0].bank, tokens[1].bank]),
+        MangoError::InvalidEmodeCategory,
+    );
+
+    let bank: Bank = solana.get_account(tokens[2].bank).await;
+    assert_eq!(bank.emode_category, 1);
+    assert_eq!(bank.emode_init_liab_weight, 1.1);
+
+    //
+    // TEST: Opting in boosts health while all exposure is in the category
+    //
+
+    send_tx(
+        solana,
+        AccountSetEmodeCategoryInstruction {
+            account,
+            owner,
+            category: 1,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(account_init_health(solana, account).await.round(), 900.0);
+
+    send_tx(solana, withdraw(2, 600)).await.unwrap();
+    let health = account_init_health(solana, account).await;
+    assert!((health - (900.0 - 600.3 * 1.1)).abs() < 1.0);
+
+    // borrowing a token outside the category loses the boost
+    assert!(send_tx(solana, withdraw(0, 10)).await.is_err());
+
+    // deposits outside the category keep it, with their regular weights
+    send_tx(
+        solana,
+        TokenDepositInstruction {
+            amount: 10,
+            reduce_only: false,
+            account,
+            owner,
+            token_account: context.users[1].token_accounts[0],
+            token_authority: payer,
+            bank_index: 0,
+        },
+    )
+    .await
+    .unwrap();
+    let health = account_init_health(solana, account).await;
+    assert!((health - (900.0 - 600.3 * 1.1 + 10.0)).abs() < 1.0);
+
+    // leaving e-mode would make the account unhealthy
+    send_tx_expect_error!(
+        solana,
+        AccountSetEmodeCategoryInstruction {
+            account,
+            owner,
+            category: 0,
+        },
+        MangoError::HealthMustBePositiveOrIncrease,
+    );
+
+    //
+    // TEST: Collateral outside the category doesn't get the boosted liability weights
+    //
+
+    let usdc_account = create_funded_account(
+        &solana,
+        group,
+        owner,
+        2,
+        &context.users[1],
+        &mints[0..1],
+        1000,
+        0,
+    )
+    .await;
+    send_tx(
+        solana,
+        AccountSetEmodeCategoryInstruction {
+            account: usdc_account,
+            owner,
+            category: 1,
+        },
+    )
+    .await
+    .unwrap();
+    let usdc_withdraw = |amount: u64| TokenWithdrawInstruction {
+        amount,
+        allow_borrow: true,
+        account: usdc_account,
+        owner,
+        token_account: context.users[1].token_accounts[2],
+        bank_index: 0,
+    };
+
+    // with the boosted weight of 1.1, 800 of token 2 could be borrowed
+    assert!(send_tx(solana, usdc_withdraw(800)).await.is_err());
+    send_tx(solana, usdc_withdraw(700)).await.unwrap();
+    let health = account_init_health(solana, usdc_account).await;
+    assert!((health - (1000.0 - 700.35 * 1.4)).abs() < 1.0);
+
+    //
+    // TEST: Removing the category clears the banks
+    //
+
+    send_tx(
+        solana,
+        TokenDepositInstruction {
+            amount: 700,
+            reduce_only: false,
+            account,
+            owner,
+            token_account: context.users[1].token_accounts[2],
+            token_authority: payer,
+            bank_index: 0,
+        },
+    )
+    .await
+    .unwrap();
+
+    send_tx(
+        solana,
+        category(1, vec![], vec![tokens[1].bank, tokens[2].bank]),
+    )
+    .await
+    .unwrap();
+    let bank: Bank = solana.get_account(tokens[2].bank).await;
+    assert_eq!(bank.emode_category, 0);
+
+    // the account keeps its category id, but the regular weights apply
+    let health = account_init_health(solana, account).await;
+    assert!((health - ((1000.0 + 99.7) * 0.6 + 10.0)).abs() < 1.0);
+
+    Ok(())
+}
@@ -0,0 +1,423 @@
+use super::*;
+
+#[tokio::test]
+async fn test_fixed_term_lend_borrow_settle() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let solana = &context.solana.clone();
+
+    let admin = TestKeypair::new();
+    let owner = context.users[0].key;
+    let payer = context.users[1].key;
+    let mints = &context.mints[0..2];
+
+    //
+    // SETUP: Create a group and accounts with space for fixed term positions
+    //
+
+    let GroupWithTokens { group, tokens, .. } = GroupWithTokensConfig {
+        admin,
+        payer,
+        mints: mints.to_vec(),
+        ..GroupWithTokensConfig::default()
+    }
+    .create(solana)
+    .await;
+    let bank = tokens[0].bank;
+
+    let lender = create_funded_account(
+        &solana,
+        group,
+        owner,
+        0,
+        &context.users[1],
+        &mints[0..1],
+        10_000,
+        0,
+    )
+    .await;
+    let borrower = create_funded_account(
+        &solana,
+        group,
+        owner,
+        1,
+        &context.users[1],
+        &mints[1..2],
+        10_000,
+        0,
+    )
+    .await;
+
+    for account_num in 0..2 {
+        send_tx(
+            solana,
+            AccountExpandInstruction {
+                account_num,
+                token_count: 8,
+                serum3_count: 4,
+                perp_count: 4,
+                perp_oo_count: 16,
+                token_conditional_swap_count: 1,
+                fixed_term_count: 2,
+                group,
+                owner,
+                payer,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    //
+    // SETUP: Create a market maturing in one year
+    //
+
+    let maturity = solana.clock_timestamp().await + 365 * 24 * 3600;
+    let market = send_tx(
+        solana,
+        FixedTermMarketCreateInstruction {
+            group,
+            admin,
+            payer,
+            bank,
+            market_index: 0,
+            maturity_timestamp: maturity,
+            lend_rate: 0.125,
+            borrow_rate: 0.25,
+            max_lent_native: 1000,
+        },
+    )
+    .await
+    .unwrap()
+    .fixed_term_market;
+
+    //
+    // TEST: Lending moves deposits into the market
+    //
+
+    send_tx(
+        solana,
+        FixedTermLendInstruction {
+            account: lender,
+            owner,
+            fixed_term_market: market,
+            amount: 1000,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(account_position(solana, lender, bank).await, 9000);
+    {
+        let account = get_mango_account(solana, lender).await;
+        let position = account.fixed_term_position(0).unwrap();
+        assert_eq!(position.lent_native, 1000);
+        assert_eq!(position.lent_at_maturity_native, 1125);
+    }
+
+    //
+    // TEST: Lending above the limit fails
+    //
+
+    let res = send_tx(
+        solana,
+        FixedTermLendInstruction {
+            account: lender,
+            owner,
+            fixed_term_market: market,
+            amount: 1,
+        },
+    )
+    .await;
+    assert_mango_error(
+        &res,
+        MangoError::FixedTermMarketLendLimitExceeded.into(),
+        "lend limit".into(),
+    );
+
+    //
+    // TEST: Can't borrow more than was lent
+    //
+
+    let res = send_tx(
+        solana,
+        FixedTermBorrowInstruction {
+            account: borrower,
+            owner,
+            fixed_term_market: market,
+            amount: 1001,
+        },
+    )
+    .await;
+    assert_mango_error(
+        &res,
+        MangoError::FixedTermMarketInsufficientLiquidity.into(),
+        "insufficient liquidity".into(),
+    );
+
+    //
+    // TEST: The amount owed at maturity counts toward the borrow limit
+    //
+
+    let set_borrow_limit = |borrow_limit: u64| TokenEdit {
+        group,
+        admin,
+        mint: mints[0].pubkey,
+        fallback_oracle: Pubkey::default(),
+        options: mango_v4::instruction::TokenEdit {
+            borrow_limit_opt: Some(borrow_limit),
+            ..token_edit_instruction_default()
+        },
+    };
+    send_tx(solana, set_borrow_limit(450)).await.unwrap();
+    send_tx_expect_error!(
+        solana,
+        FixedTermBorrowInstruction {
+            account: borrower,
+            owner,
+            fixed_term_market: market,
+            amount: 400,
+        },
+        MangoError::BankBorrowLimit
+    );
+    send_tx(solana, set_borrow_limit(0)).await.unwrap();
+
+    //
+    // TEST: Borrowing deposits into the token position
+    //
+
+    send_tx(
+        solana,
+        FixedTermBorrowInstruction {
+            account: borrower,
+            owner,
+            fixed_term_market: market,
+            amount: 400,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(account_position(solana, borrower, bank).await, 400);
+    {
+        let account = get_mango_account(solana, borrower).await;
+        let position = account.fixed_term_position(0).unwrap();
+        assert_eq!(position.borrowed_native, 400);
+        assert_eq!(position.borrowed_at_maturity_native, 500);
+    }
+
+    //
+    // TEST: Settling before maturity fails
+    //
+
+    let res = send_tx(
+        solana,
+        FixedTermSettleInstruction {
+            account: lender,
+            fixed_term_market: market,
+        },
+    )
+    .await;
+    assert_mango_error(
+        &res,
+        MangoError::FixedTermMarketNotMatured.into(),
+        "not matured".into(),
+    );
+
+    // healthy borrowers can't settle early either
+    let res = send_tx(
+        solana,
+        FixedTermSettleInstruction {
+            account: borrower,
+            fixed_term_market: market,
+        },
+    )
+    .await;
+    assert_mango_error(
+        &res,
+        MangoError::FixedTermMarketNotMatured.into(),
+        "not liquidatable".into(),
+    );
+
+    //
+    // TEST: Settling after maturity credits and debits the amounts at maturity
+    //
+
+    solana.set_clock_timestamp(maturity).await;
+
+    for account in [lender, borrower] {
+        send_tx(
+            solana,
+            FixedTermSettleInstruction {
+                account,
+                fixed_term_market: market,
+            },
+        )
+        .await
+        .unwrap();
+        let account_data = get_mango_account(solana, account).await;
+        assert_eq!(account_data.active_fixed_term_positions().count(), 0);
+    }
+    // lenders only get the 100 interest that borrowers pay, not the promised 125
+    assert_eq!(account_position(solana, lender, bank).await, 10_100);
+    assert_eq!(account_position(solana, borrower, bank).await, -100);
+
+    let market_data: FixedTermMarket = solana.get_account(market).await;
+    assert_eq!(market_data.total_lent_native, 0);
+    assert_eq!(market_data.total_borrowed_native, 0);
+    assert_eq!(market_data.total_lent_at_maturity_native, 0);
+    assert_eq!(market_data.total_borrowed_at_maturity_native, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fixed_term_settle_liquidatable() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let solana = &context.solana.clone();
+
+    let admin = TestKeypair::new();
+    let owner = context.users[0].key;
+    let payer = context.users[1].key;
+    let mints = &context.mints[0..2];
+
+    //
+    // SETUP: A lender and a borrower with a fixed term borrow of token 0
+    //
+
+    let GroupWithTokens { group, tokens, .. } = GroupWithTokensConfig {
+        admin,
+        payer,
+        mints: mints.to_vec(),
+        ..GroupWithTokensConfig::default()
+    }
+    .create(solana)
+    .await;
+    let bank = tokens[0].bank;
+
+    let lender = create_funded_account(
+        &solana,
+        group,
+        owner,
+        0,
+        &context.users[1],
+        &mints[0..1],
+        10_000,
+        0,
+    )
+    .await;
+    let borrower = create_funded_account(
+        &solana,
+        group,
+        owner,
+        1,
+        &context.users[1],
+        &mints[1..2],
+        1000,
+        0,
+    )
+    .await;
+
+    for account_num in 0..2 {
+        send_tx(
+            solana,
+            AccountExpandInstruction {
+                account_num,
+                token_count: 8,
+                serum3_count: 4,
+                perp_count: 4,
+                perp_oo_count: 16,
+                token_conditional_swap_count: 1,
+                fixed_term_count: 2,
+                group,
+                owner,
+                payer,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    let maturity = solana.clock_timestamp().await + 365 * 24 * 3600;
+    let market = send_tx(
+        solana,
+        FixedTermMarketCreateInstruction {
+            group,
+            admin,
+            payer,
+            bank,
+            market_index: 0,
+            maturity_timestamp: maturity,
+            lend_rate: 0.125,
+            borrow_rate: 0.25,
+            max_lent_native: 1000,
+        },
+    )
+    .await
+    .unwrap()
+    .fixed_term_market;
+
+    send_tx(
+        solana,
+        FixedTermLendInstruction {
+            account: lender,
+            owner,
+            fixed_term_market: market,
+            amount: 1000,
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        FixedTermBorrowInstruction {
+            account: borrower,
+            owner,
+            fixed_term_market: market,
+            amount: 400,
+        },
+    )
+    .await
+    .unwrap();
+
+    //
+    // TEST: Once the borrower is liquidatable, its borrow becomes a token borrow
+    //
+
+    set_bank_stub_oracle_price(solana, group, &tokens[1], admin, 0.1).await;
+    assert!(account_maint_health(solana, borrower).await < 0.0);
+
+    send_tx(
+        solana,
+        FixedTermSettleInstruction {
+            account: borrower,
+            fixed_term_market: market,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(account_position(solana, borrower, bank).await, -100);
+    let borrower_data = get_mango_account(solana, borrower).await;
+    assert_eq!(borrower_data.active_fixed_term_positions().count(), 0);
+
+    let market_data: FixedTermMarket = solana.get_account(market).await;
+    assert_eq!(market_data.total_borrowed_native, 0);
+    assert_eq!(market_data.total_borrowed_at_maturity_native, 0);
+    assert_eq!(market_data.total_lent_native, 1000);
+    let bank_data: Bank = solana.get_account(bank).await;
+    assert_eq!(bank_data.fixed_term_borrows_native, 0);
+
+    // the lender is unaffected and settles at maturity
+    solana.set_clock_timestamp(maturity).await;
+    send_tx(
+        solana,
+        FixedTermSettleInstruction {
+            account: lender,
+            fixed_term_market: market,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(account_position(solana, lender, bank).await, 10_100);
+
+    Ok(())
+}
@@ -0,0 +1,312 @@
+use super::*;
+
+use openbook_v2::state::{PlaceOrderType, SelfTradeBehavior, Side};
+
+#[tokio::test]
+async fn test_openbook_v2_register_market() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let solana = &context.solana.clone();
+
+    let admin = TestKeypair::new();
+    let payer = context.users[1].key;
+    let mints = &context.mints[0..2];
+
+    //
+    // SETUP: Create a group
+    //
+
+    let GroupWithTokens { group, tokens, .. } = GroupWithTokensConfig {
+        admin,
+        payer,
+        mints: mints.to_vec(),
+        ..GroupWithTokensConfig::default()
+    }
+    .create(solana)
+    .await;
+    let base_token = &tokens[0];
+    let quote_token = &tokens[1];
+
+    //
+    // TEST: Markets with an open orders admin can't be registered
+    //
+    let admin_market_cookie = context
+        .openbook_v2
+        .create_test_market(&base_token.mint, &quote_token.mint, |market| {
+            market.open_orders_admin = Some(Pubkey::new_unique()).into();
+        })
+        .await;
+    let result = send_tx(
+        solana,
+        OpenbookV2RegisterMarketInstruction {
+            group,
+            admin,
+            payer,
+            openbook_v2_program: context.openbook_v2.program_id,
+            openbook_v2_market_external: admin_market_cookie.market,
+            market_index: 0,
+            base_bank: base_token.bank,
+            quote_bank: quote_token.bank,
+        },
+    )
+    .await;
+    assert_mango_error(
+        &result,
+        MangoError::SomeError.into(),
+        "open orders admin".to_string(),
+    );
+
+    //
+    // TEST: Register a market
+    //
+    let market_cookie = context
+        .openbook_v2
+        .create_test_market(&base_token.mint, &quote_token.mint, |_| {})
+        .await;
+    let openbook_v2_market = send_tx(
+        solana,
+        OpenbookV2RegisterMarketInstruction {
+            group,
+            admin,
+            payer,
+            openbook_v2_program: context.openbook_v2.program_id,
+            openbook_v2_market_external: market_cookie.market,
+            market_index: 0,
+            base_bank: base_token.bank,
+            quote_bank: quote_token.bank,
+        },
+    )
+    .await
+    .unwrap()
+    .openbook_v2_market;
+
+    let market_data = solana
+        .get_account::<OpenbookV2Market>(openbook_v2_market)
+        .await;
+    assert_eq!(market_data.group, group);
+    assert_eq!(market_data.market_index, 0);
+    assert_eq!(market_data.base_token_index, base_token.index);
+    assert_eq!(market_data.quote_token_index, quote_token.index);
+    assert_eq!(
+        market_data.openbook_v2_market_external,
+        market_cookie.market
+    );
+    assert!(!market_data.is_reduce_only());
+
+    //
+    // TEST: The same market index can't be registered twice
+    //
+    let other_market_cookie = context
+        .openbook_v2
+        .create_test_market(&base_token.mint, &quote_token.mint, |_| {})
+        .await;
+    assert!(send_tx(
+        solana,
+        OpenbookV2RegisterMarketInstruction {
+            group,
+            admin,
+            payer,
+            openbook_v2_program: context.openbook_v2.program_id,
+            openbook_v2_market_external: other_market_cookie.market,
+            market_index: 0,
+            base_bank: base_token.bank,
+            quote_bank: quote_token.bank,
+        },
+    )
+    .await
+    .is_err());
+
+    //
+    // TEST: Edit and deregister the market
+    //
+    send_tx(
+        solana,
+        OpenbookV2EditMarketInstruction {
+            group,
+            admin,
+            market: openbook_v2_market,
+            reduce_only_opt: Some(true),
+            force_close_opt: Some(true),
+        },
+    )
+    .await
+    .unwrap();
+    let market_data = solana
+        .get_account::<OpenbookV2Market>(openbook_v2_market)
+        .await;
+    assert!(market_data.is_reduce_only());
+    assert!(market_data.is_force_close());
+
+    send_tx(
+        solana,
+        OpenbookV2DeregisterMarketInstruction {
+            group,
+            admin,
+            openbook_v2_market_external: market_cookie.market,
+            sol_destination: payer.pubkey(),
+        },
+    )
+    .await
+    .unwrap();
+    assert!(solana
+        .get_account_data(openbook_v2_market)
+        .await
+        .map_or(true, |data| data.is_empty()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_openbook_v2_place_order_checks() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let solana = &context.solana.clone();
+
+    let admin = TestKeypair::new();
+    let owner = context.users[0].key;
+    let payer = context.users[1].key;
+    let mints = &context.mints[0..2];
+
+    //
+    // SETUP: Create a group, a market and an account
+    //
+
+    let GroupWithTokens { group, tokens, .. } = GroupWithTokensConfig {
+        admin,
+        payer,
+        mints: mints.to_vec(),
+        ..GroupWithTokensConfig::default()
+    }
+    .create(solana)
+    .await;
+    let base_token = &tokens[0];
+    let quote_token = &tokens[1];
+
+    let market_cookie = context
+        .openbook_v2
+        .create_test_market(&base_token.mint, &quote_token.mint, |_| {})
+        .await;
+    let openbook_v2_market = send_tx(
+        solana,
+        OpenbookV2RegisterMarketInstruction {
+            group,
+            admin,
+            payer,
+            openbook_v2_program: context.openbook_v2.program_id,
+            openbook_v2_market_external: market_cookie.market,
+            market_index: 0,
+            base_bank: base_token.bank,
+            quote_bank: quote_token.bank,
+        },
+    )
+    .await
+    .unwrap()
+    .openbook_v2_market;
+
+    let account = send_tx(
+        solana,
+        AccountCreateInstruction {
+            account_num: 0,
+            openbook_v2_count: 2,
+            group,
+            owner,
+            payer,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap()
+    .account;
+    for &token in &[base_token, quote_token] {
+        send_tx(
+            solana,
+            TokenDepositInstruction {
+                amount: 100_000,
+                reduce_only: false,
+                account,
+                owner,
+                token_account: context.users[1].token_accounts[token.mint.index],
+                token_authority: payer,
+                bank_index: 0,
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    //
+    // TEST: Create an open orders account
+    //
+    let open_orders = send_tx(
+        solana,
+        OpenbookV2CreateOpenOrdersInstruction {
+            account,
+            openbook_v2_market,
+            owner,
+            payer,
+        },
+    )
+    .await
+    .unwrap()
+    .open_orders;
+
+    let account_data = get_mango_account(solana, account).await;
+    let orders = account_data.openbook_v2_orders(0).unwrap();
+    assert_eq!(orders.open_orders, open_orders);
+    assert_eq!(orders.base_token_index, base_token.index);
+    assert_eq!(orders.quote_token_index, quote_token.index);
+    assert_eq!(orders.base_lot_size, base_token.mint.base_lot as i64);
+    assert_eq!(orders.quote_lot_size, base_token.mint.quote_lot as i64);
+
+    let place_order = |limit_price: u64, max_base_qty: u64| OpenbookV2PlaceOrderInstruction {
+        side: Side::Ask,
+        limit_price,
+        max_base_qty,
+        max_native_quote_qty_including_fees: 0,
+        self_trade_behavior: SelfTradeBehavior::DecrementTake,
+        order_type: PlaceOrderType::Limit,
+        client_order_id: 0,
+        limit: 10,
+        account,
+        owner,
+        openbook_v2_market,
+    };
+
+    //
+    // TEST: Order quantities that overflow fail with an error
+    //
+    let overflowing_base_qty = u64::MAX / base_token.mint.base_lot as u64 + 1;
+    assert_mango_error(
+        &send_tx(solana, place_order(1, overflowing_base_qty)).await,
+        MangoError::MathError.into(),
+        "base quantity overflow".to_string(),
+    );
+
+    assert_mango_error(
+        &send_tx(solana, place_order(u64::MAX, 1)).await,
+        MangoError::SomeError.into(),
+        "limit price doesn't fit openbook_v2".to_string(),
+    );
+
+    //
+    // TEST: No orders in reduce only markets
+    //
+    send_tx(
+        solana,
+        OpenbookV2EditMarketInstruction {
+            group,
+            admin,
+            market: openbook_v2_market,
+            reduce_only_opt: Some(true),
+            force_close_opt: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_mango_error(
+        &send_tx(solana, place_order(1, 1)).await,
+        MangoError::MarketInReduceOnlyMode.into(),
+        "reduce only market".to_string(),
+    );
+
+    Ok(())
+}
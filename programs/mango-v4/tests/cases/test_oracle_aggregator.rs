@@ -0,0 +1,182 @@
+use super::*;
+
+#[tokio::test]
+async fn test_oracle_aggregator() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let solana = &context.solana.clone();
+
+    let admin = TestKeypair::new();
+    let owner = context.users[0].key;
+    let payer = context.users[1].key;
+    let mints = &context.mints[0..2];
+
+    //
+    // SETUP: Create a group, an account and three source oracles for mint 0
+    //
+
+    let GroupWithTokens { group, .. } = GroupWithTokensConfig {
+        admin,
+        payer,
+        mints: mints.to_vec(),
+        ..GroupWithTokensConfig::default()
+    }
+    .create(solana)
+    .await;
+
+    let account =
+        create_funded_account(&solana, group, owner, 0, &context.users[1], mints, 1000, 0).await;
+
+    let mut sources = vec![];
+    for price in [1.0, 1.02, 0.99] {
+        let oracle = send_tx(
+            solana,
+            StubOracleCreate {
+                oracle: TestKeypair::new(),
+                group,
+                mint: mints[0].pubkey,
+                admin,
+                payer,
+            },
+        )
+        .await
+        .unwrap()
+        .oracle;
+        send_tx(
+            solana,
+            StubOracleSetInstruction {
+                oracle,
+                group,
+                mint: mints[0].pubkey,
+                admin,
+                price,
+            },
+        )
+        .await
+        .unwrap();
+        sources.push(oracle);
+    }
+    let set_source_price = |index: usize, price: f64| StubOracleSetInstruction {
+        oracle: sources[index],
+        group,
+        mint: mints[0].pubkey,
+        admin,
+        price,
+    };
+
+    let aggregator = send_tx(
+        solana,
+        OracleAggregatorCreateInstruction {
+            oracle: TestKeypair::new(),
+            admin,
+            payer,
+            group,
+            mint: mints[0].pubkey,
+            source_oracles: sources.clone(),
+            min_quorum: 2,
+            max_divergence: 0.05,
+        },
+    )
+    .await
+    .unwrap()
+    .oracle;
+
+    //
+    // TEST: The aggregated price is the median
+    //
+    send_tx(
+        solana,
+        OracleAggregatorUpdateInstruction { oracle: aggregator },
+    )
+    .await
+    .unwrap();
+    let agg = solana.get_account::<OracleAggregator>(aggregator).await;
+    assert_eq!(agg.price, I80F48::from_num(1.0));
+
+    //
+    // TEST: A single bad source is ignored
+    //
+    send_tx(solana, set_source_price(2, 5.0)).await.unwrap();
+    send_tx(
+        solana,
+        OracleAggregatorUpdateInstruction { oracle: aggregator },
+    )
+    .await
+    .unwrap();
+    let agg = solana.get_account::<OracleAggregator>(aggregator).await;
+    assert_eq!(agg.price, I80F48::from_num(1.02));
+    assert!((agg.deviation - I80F48::from_num(0.02)).abs() < 0.0001);
+
+    //
+    // TEST: Without a quorum the price isn't updated
+    //
+    send_tx(solana, set_source_price(1, 3.0)).await.unwrap();
+    let res = send_tx(
+        solana,
+        OracleAggregatorUpdateInstruction { oracle: aggregator },
+    )
+    .await;
+    assert_mango_error(
+        &res,
+        MangoError::OracleQuorumNotReached.into(),
+        "no quorum".to_string(),
+    );
+    let agg = solana.get_account::<OracleAggregator>(aggregator).await;
+    assert_eq!(agg.price, I80F48::from_num(1.02));
+
+    //
+    // TEST: The quorum can't exceed the number of sources
+    //
+    let res = send_tx(
+        solana,
+        OracleAggregatorEditInstruction {
+            oracle: aggregator,
+            group,
+            admin,
+            min_quorum_opt: None,
+            source_oracles_opt: Some(vec![sources[0]]),
+        },
+    )
+    .await;
+    assert!(res.is_err());
+
+    //
+    // TEST: The aggregator can be used as a bank oracle
+    //
+    send_tx(solana, set_source_price(1, 1.0)).await.unwrap();
+    send_tx(
+        solana,
+        OracleAggregatorUpdateInstruction { oracle: aggregator },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        TokenEdit {
+            group,
+            admin,
+            mint: mints[0].pubkey,
+            fallback_oracle: Pubkey::default(),
+            options: mango_v4::instruction::TokenEdit {
+                oracle_opt: Some(aggregator),
+                ..token_edit_instruction_default()
+            },
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        TokenWithdrawInstruction {
+            amount: 10,
+            allow_borrow: false,
+            account,
+            owner,
+            token_account: context.users[0].token_accounts[0],
+            bank_index: 0,
+        },
+    )
+    .await
+    .unwrap();
+
+    Ok(())
+}
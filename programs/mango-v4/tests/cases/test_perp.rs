@@ -103,7 +103,7 @@ async fn test_perp_fixed() -> Result<(), TransportError> {
     .unwrap();
     check_prev_instruction_post_health(&solana, account_0).await;
 
-    let bids_data = solana.get_account_boxed::<BookSide>(bids).await;
+    let bids_data = solana.get_account_boxed::<BookSideAccount>(bids).await;
     assert_eq!(bids_data.roots[0].leaf_count, 1);
     let order_id_to_cancel = solana
         .get_account::<MangoAccount>(account_0)
@@ -552,7 +552,7 @@ async fn test_perp_oracle_peg() -> Result<(), TransportError> {
     .unwrap();
     check_prev_instruction_post_health(&solana, account_0).await;
 
-    let bids_data = solana.get_account_boxed::<BookSide>(bids).await;
+    let bids_data = solana.get_account_boxed::<BookSideAccount>(bids).await;
     assert_eq!(bids_data.roots[1].leaf_count, 1);
     let perp_order = solana
         .get_account::<MangoAccount>(account_0)
@@ -1713,6 +1713,440 @@ async fn test_perp_skip_bank() -> Result<(), TransportError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_perp_spread_order() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let solana = &context.solana.clone();
+
+    let admin = TestKeypair::new();
+    let owner = context.users[0].key;
+    let payer = context.users[1].key;
+    let mints = &context.mints[0..3];
+
+    //
+    // SETUP: Create a group, two perp markets and accounts
+    //
+
+    let GroupWithTokens { group, tokens, .. } = GroupWithTokensConfig {
+        admin,
+        payer,
+        mints: mints.to_vec(),
+        ..GroupWithTokensConfig::default()
+    }
+    .create(solana)
+    .await;
+
+    let deposit_amount = 100_000;
+    let maker = create_funded_account(
+        &solana,
+        group,
+        owner,
+        0,
+        &context.users[1],
+        &mints[0..1],
+        deposit_amount,
+        0,
+    )
+    .await;
+    let account = create_funded_account(
+        &solana,
+        group,
+        owner,
+        1,
+        &context.users[1],
+        &mints[0..1],
+        deposit_amount,
+        0,
+    )
+    .await;
+
+    let mut perp_markets = vec![];
+    for (perp_market_index, token) in [(0, &tokens[1]), (1, &tokens[2])] {
+        let mango_v4::accounts::PerpCreateMarket { perp_market, .. } = send_tx(
+            solana,
+            PerpCreateMarketInstruction {
+                group,
+                admin,
+                payer,
+                perp_market_index,
+                quote_lot_size: 10,
+                base_lot_size: 100,
+                maint_base_asset_weight: 0.975,
+                init_base_asset_weight: 0.95,
+                maint_base_liab_weight: 1.025,
+                init_base_liab_weight: 1.05,
+                base_liquidation_fee: 0.012,
+                maker_fee: 0.0,
+                taker_fee: 0.0,
+                ..PerpCreateMarketInstruction::with_new_book_and_queue(&solana, token).await
+            },
+        )
+        .await
+        .unwrap();
+        perp_markets.push(perp_market);
+    }
+    let (perp_market_a, perp_market_b) = (perp_markets[0], perp_markets[1]);
+
+    let price_lots = {
+        let perp_market = solana.get_account::<PerpMarket>(perp_market_a).await;
+        perp_market.native_price_to_lot(I80F48::ONE)
+    };
+
+    for (perp_market, side, max_base_lots) in
+        [(perp_market_a, Side::Ask, 3), (perp_market_b, Side::Bid, 5)]
+    {
+        send_tx(
+            solana,
+            PerpPlaceOrderInstruction {
+                account: maker,
+                perp_market,
+                owner,
+                side,
+                price_lots,
+                max_base_lots,
+                ..PerpPlaceOrderInstruction::default()
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    //
+    // TEST: Buy A, sell B scaled to the fill of A
+    //
+    send_tx(
+        solana,
+        PerpPlaceSpreadOrderInstruction {
+            account,
+            owner,
+            perp_market_a,
+            side_a: Side::Bid,
+            price_lots_a: price_lots,
+            max_base_lots_a: 5,
+            perp_market_b,
+            side_b: Side::Ask,
+            price_lots_b: price_lots,
+            leg_b_ratio: 1.0,
+        },
+    )
+    .await
+    .unwrap();
+    check_prev_instruction_post_health(&solana, account).await;
+
+    let account_data = get_mango_account(solana, account).await;
+    assert_eq!(
+        account_data
+            .perp_position(0)
+            .unwrap()
+            .effective_base_position_lots(),
+        3
+    );
+    assert_eq!(
+        account_data
+            .perp_position(1)
+            .unwrap()
+            .effective_base_position_lots(),
+        -3
+    );
+
+    //
+    // TEST: Fails if the second leg can't be filled completely
+    //
+    send_tx(
+        solana,
+        PerpPlaceOrderInstruction {
+            account: maker,
+            perp_market: perp_market_a,
+            owner,
+            side: Side::Ask,
+            price_lots,
+            max_base_lots: 1,
+            ..PerpPlaceOrderInstruction::default()
+        },
+    )
+    .await
+    .unwrap();
+    assert_mango_error(
+        &send_tx(
+            solana,
+            PerpPlaceSpreadOrderInstruction {
+                account,
+                owner,
+                perp_market_a,
+                side_a: Side::Bid,
+                price_lots_a: price_lots,
+                max_base_lots_a: 1,
+                perp_market_b,
+                side_b: Side::Ask,
+                price_lots_b: price_lots,
+                leg_b_ratio: 3.0,
+            },
+        )
+        .await,
+        MangoError::PerpSpreadLegNotFilled.into(),
+        "leg b not filled".to_string(),
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_perp_isolated_margin() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let solana = &context.solana.clone();
+
+    let admin = TestKeypair::new();
+    let owner = context.users[0].key;
+    let payer = context.users[1].key;
+    let mints = &context.mints[0..2];
+
+    //
+    // SETUP: Create a group, a perp market and accounts
+    //
+
+    let GroupWithTokens { group, tokens, .. } = GroupWithTokensConfig {
+        admin,
+        payer,
+        mints: mints.to_vec(),
+        ..GroupWithTokensConfig::default()
+    }
+    .create(solana)
+    .await;
+
+    let deposit_amount = 100_000;
+    let maker = create_funded_account(
+        &solana,
+        group,
+        owner,
+        0,
+        &context.users[1],
+        mints,
+        deposit_amount,
+        0,
+    )
+    .await;
+    let account = create_funded_account(
+        &solana,
+        group,
+        owner,
+        1,
+        &context.users[1],
+        &mints[0..1],
+        deposit_amount,
+        0,
+    )
+    .await;
+
+    let mango_v4::accounts::PerpCreateMarket { perp_market, .. } = send_tx(
+        solana,
+        PerpCreateMarketInstruction {
+            group,
+            admin,
+            payer,
+            perp_market_index: 0,
+            quote_lot_size: 10,
+            base_lot_size: 100,
+            maint_base_asset_weight: 0.975,
+            init_base_asset_weight: 0.95,
+            maint_base_liab_weight: 1.025,
+            init_base_liab_weight: 1.05,
+            base_liquidation_fee: 0.012,
+            maker_fee: 0.0,
+            taker_fee: 0.0,
+            ..PerpCreateMarketInstruction::with_new_book_and_queue(&solana, &tokens[1]).await
+        },
+    )
+    .await
+    .unwrap();
+
+    let price_lots = {
+        let perp_market = solana.get_account::<PerpMarket>(perp_market).await;
+        perp_market.native_price_to_lot(I80F48::ONE)
+    };
+    send_tx(
+        solana,
+        PerpPlaceOrderInstruction {
+            account: maker,
+            perp_market,
+            owner,
+            side: Side::Ask,
+            price_lots,
+            max_base_lots: 1000,
+            ..PerpPlaceOrderInstruction::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    //
+    // TEST: Isolate the position and allocate 1000 USDC to it
+    //
+    send_tx(
+        solana,
+        PerpSetIsolatedInstruction {
+            account,
+            perp_market,
+            owner,
+            isolated: true,
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        PerpIsolatedTransferInstruction {
+            account,
+            perp_market,
+            owner,
+            amount: 1000,
+        },
+    )
+    .await
+    .unwrap();
+
+    let account_data = get_mango_account(solana, account).await;
+    let perp_position = account_data.perp_position(0).unwrap();
+    assert!(perp_position.is_isolated());
+    assert_eq!(perp_position.isolated_collateral_native, 1000);
+
+    //
+    // TEST: Each lot costs 5 USDC of init health, the bucket can't afford 300 lots
+    // even though the rest of the account could
+    //
+    assert_mango_error(
+        &send_tx(
+            solana,
+            PerpPlaceOrderInstruction {
+                account,
+                perp_market,
+                owner,
+                side: Side::Bid,
+                price_lots,
+                max_base_lots: 300,
+                ..PerpPlaceOrderInstruction::default()
+            },
+        )
+        .await,
+        MangoError::IsolatedPerpHealthMustBePositiveOrIncrease.into(),
+        "bucket too small".to_string(),
+    );
+
+    send_tx(
+        solana,
+        PerpPlaceOrderInstruction {
+            account,
+            perp_market,
+            owner,
+            side: Side::Bid,
+            price_lots,
+            max_base_lots: 100,
+            ..PerpPlaceOrderInstruction::default()
+        },
+    )
+    .await
+    .unwrap();
+    check_prev_instruction_post_health(&solana, account).await;
+
+    //
+    // TEST: Collateral still backing the position can't be taken out
+    //
+    assert_mango_error(
+        &send_tx(
+            solana,
+            PerpIsolatedTransferInstruction {
+                account,
+                perp_market,
+                owner,
+                amount: -1000,
+            },
+        )
+        .await,
+        MangoError::IsolatedPerpHealthMustBePositiveOrIncrease.into(),
+        "bucket needs collateral".to_string(),
+    );
+    send_tx(
+        solana,
+        PerpIsolatedTransferInstruction {
+            account,
+            perp_market,
+            owner,
+            amount: -400,
+        },
+    )
+    .await
+    .unwrap();
+
+    //
+    // TEST: Isolation can only change while the position is empty
+    //
+    assert!(send_tx(
+        solana,
+        PerpSetIsolatedInstruction {
+            account,
+            perp_market,
+            owner,
+            isolated: false,
+        },
+    )
+    .await
+    .is_err());
+
+    //
+    // TEST: An unhealthy bucket is liquidated by itself and only loses its collateral
+    //
+    send_tx(
+        solana,
+        PerpConsumeEventsInstruction {
+            perp_market,
+            mango_accounts: vec![maker, account],
+        },
+    )
+    .await
+    .unwrap();
+    set_perp_stub_oracle_price(solana, group, perp_market, &tokens[1], admin, 0.9).await;
+
+    send_tx(
+        solana,
+        PerpLiqBaseOrPositivePnlInstruction {
+            liqor: maker,
+            liqor_owner: owner,
+            liqee: account,
+            perp_market,
+            max_base_transfer: i64::MAX,
+            max_pnl_transfer: 0,
+        },
+    )
+    .await
+    .unwrap();
+    let account_data = get_mango_account(solana, account).await;
+    assert_eq!(
+        account_data.perp_position(0).unwrap().base_position_lots(),
+        0
+    );
+    assert!(!account_data.being_liquidated());
+
+    send_tx(
+        solana,
+        PerpLiqNegativePnlOrBankruptcyInstruction {
+            liqor: maker,
+            liqor_owner: owner,
+            liqee: account,
+            perp_market,
+            max_liab_transfer: u64::MAX,
+        },
+    )
+    .await
+    .unwrap();
+    let account_data = get_mango_account(solana, account).await;
+    let perp_position = account_data.perp_position(0).unwrap();
+    assert!(perp_position.isolated_collateral_native < 1);
+    assert!(!account_data.being_liquidated());
+    let usdc_lost = deposit_amount as i64 - account_position(solana, account, tokens[0].bank).await;
+    assert!((usdc_lost - 600).abs() <= 1);
+
+    Ok(())
+}
+
 async fn assert_no_perp_orders(solana: &SolanaCookie, account_0: Pubkey) {
     let mango_account_0 = solana.get_account::<MangoAccount>(account_0).await;
 
@@ -0,0 +1,264 @@
+use super::*;
+
+#[tokio::test]
+async fn test_perp_edit_order() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let solana = &context.solana.clone();
+
+    let admin = TestKeypair::new();
+    let owner = context.users[0].key;
+    let payer = context.users[1].key;
+    let mints = &context.mints[0..2];
+
+    //
+    // SETUP: Create a group, accounts and a perp market
+    //
+
+    let GroupWithTokens { group, tokens, .. } = GroupWithTokensConfig {
+        admin,
+        payer,
+        mints: mints.to_vec(),
+        ..GroupWithTokensConfig::default()
+    }
+    .create(solana)
+    .await;
+
+    let deposit_amount = 1000;
+    let mut accounts = vec![];
+    for account_num in 0..3 {
+        accounts.push(
+            create_funded_account(
+                &solana,
+                group,
+                owner,
+                account_num,
+                &context.users[1],
+                mints,
+                deposit_amount,
+                0,
+            )
+            .await,
+        );
+    }
+    let (account_0, account_1, account_2) = (accounts[0], accounts[1], accounts[2]);
+
+    let mango_v4::accounts::PerpCreateMarket {
+        perp_market, bids, ..
+    } = send_tx(
+        solana,
+        PerpCreateMarketInstruction {
+            group,
+            admin,
+            payer,
+            perp_market_index: 0,
+            quote_lot_size: 10,
+            base_lot_size: 100,
+            maint_base_asset_weight: 0.975,
+            init_base_asset_weight: 0.95,
+            maint_base_liab_weight: 1.025,
+            init_base_liab_weight: 1.05,
+            base_liquidation_fee: 0.012,
+            maker_fee: 0.0,
+            taker_fee: 0.0,
+            ..PerpCreateMarketInstruction::with_new_book_and_queue(&solana, &tokens[0]).await
+        },
+    )
+    .await
+    .unwrap();
+
+    let price_lots = {
+        let perp_market = solana.get_account::<PerpMarket>(perp_market).await;
+        perp_market.native_price_to_lot(I80F48::ONE)
+    };
+
+    let place_bid = |account: Pubkey, price_lots: i64, max_base_lots: i64, client_order_id| {
+        send_tx(
+            solana,
+            PerpPlaceOrderInstruction {
+                account,
+                perp_market,
+                owner,
+                side: Side::Bid,
+                price_lots,
+                max_base_lots,
+                client_order_id,
+                ..PerpPlaceOrderInstruction::default()
+            },
+        )
+    };
+
+    // account_0: order A in slot 0 and order B in slot 1, account_1 bids behind A
+    place_bid(account_0, price_lots, 3, 1).await.unwrap();
+    place_bid(account_0, price_lots - 1, 1, 2).await.unwrap();
+    place_bid(account_1, price_lots, 1, 3).await.unwrap();
+
+    let mango_account_0 = solana.get_account::<MangoAccount>(account_0).await;
+    let order_a_id = mango_account_0.perp_open_orders[0].id;
+    let order_b_id = mango_account_0.perp_open_orders[1].id;
+    assert_eq!(mango_account_0.perps[0].bids_base_lots, 4);
+
+    //
+    // TEST: Decreasing the size keeps the order id and time priority
+    //
+    send_tx(
+        solana,
+        PerpEditOrderInstruction {
+            account: account_0,
+            perp_market,
+            owner,
+            client_order_id: Some(1),
+            price_lots,
+            max_base_lots: 2,
+            ..PerpEditOrderInstruction::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let mango_account_0 = solana.get_account::<MangoAccount>(account_0).await;
+    assert_eq!(mango_account_0.perp_open_orders[0].id, order_a_id);
+    assert_eq!(mango_account_0.perp_open_orders[0].quantity, 2);
+    assert_eq!(mango_account_0.perps[0].bids_base_lots, 3);
+    let bids_data = solana.get_account_boxed::<BookSideAccount>(bids).await;
+    assert_eq!(bids_data.roots[0].leaf_count, 3);
+
+    send_tx(
+        solana,
+        PerpPlaceOrderInstruction {
+            account: account_2,
+            perp_market,
+            owner,
+            side: Side::Ask,
+            price_lots,
+            max_base_lots: 1,
+            ..PerpPlaceOrderInstruction::default()
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        PerpConsumeEventsInstruction {
+            perp_market,
+            mango_accounts: vec![account_0, account_1, account_2],
+        },
+    )
+    .await
+    .unwrap();
+
+    // order A was still first in line
+    let mango_account_0 = solana.get_account::<MangoAccount>(account_0).await;
+    assert_eq!(mango_account_0.perps[0].base_position_lots(), 1);
+    assert_eq!(mango_account_0.perp_open_orders[0].quantity, 1);
+    let mango_account_1 = solana.get_account::<MangoAccount>(account_1).await;
+    assert_eq!(mango_account_1.perps[0].base_position_lots(), 0);
+    assert_eq!(mango_account_1.perps[0].bids_base_lots, 1);
+
+    //
+    // TEST: Changing the price replaces the order in the same slot
+    //
+    send_tx(
+        solana,
+        PerpCancelOrderInstruction {
+            account: account_0,
+            perp_market,
+            owner,
+            order_id: order_a_id,
+        },
+    )
+    .await
+    .unwrap();
+
+    send_tx(
+        solana,
+        PerpEditOrderInstruction {
+            account: account_0,
+            perp_market,
+            owner,
+            order_id: Some(order_b_id),
+            price_lots: price_lots - 2,
+            max_base_lots: 2,
+            ..PerpEditOrderInstruction::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let mango_account_0 = solana.get_account::<MangoAccount>(account_0).await;
+    assert_eq!(mango_account_0.perp_open_orders[0].market, FREE_ORDER_SLOT);
+    let oo = &mango_account_0.perp_open_orders[1];
+    assert_ne!(oo.id, order_b_id);
+    assert_eq!(oo.client_id, 2);
+    assert_eq!(oo.quantity, 2);
+    assert_eq!(mango_account_0.perps[0].bids_base_lots, 2);
+    let new_order_b_id = oo.id;
+
+    //
+    // TEST: Editing to zero size cancels
+    //
+    send_tx(
+        solana,
+        PerpEditOrderInstruction {
+            account: account_0,
+            perp_market,
+            owner,
+            order_id: Some(new_order_b_id),
+            price_lots: price_lots - 2,
+            max_base_lots: 0,
+            ..PerpEditOrderInstruction::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let mango_account_0 = solana.get_account::<MangoAccount>(account_0).await;
+    assert_eq!(mango_account_0.perp_open_orders[1].market, FREE_ORDER_SLOT);
+    assert_eq!(mango_account_0.perps[0].bids_base_lots, 0);
+
+    //
+    // TEST: The replacement order is reduce only if requested
+    //
+    // account_0 is long, so a reduce only bid can't be placed
+    place_bid(account_0, price_lots - 1, 1, 5).await.unwrap();
+    send_tx(
+        solana,
+        PerpEditOrderInstruction {
+            account: account_0,
+            perp_market,
+            owner,
+            client_order_id: Some(5),
+            price_lots: price_lots - 2,
+            max_base_lots: 1,
+            reduce_only: true,
+            ..PerpEditOrderInstruction::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let mango_account_0 = solana.get_account::<MangoAccount>(account_0).await;
+    assert_eq!(mango_account_0.perp_open_orders[0].market, FREE_ORDER_SLOT);
+    assert_eq!(mango_account_0.perps[0].bids_base_lots, 0);
+
+    //
+    // TEST: The order must be identified by exactly one id
+    //
+    place_bid(account_0, price_lots - 1, 1, 4).await.unwrap();
+    assert!(send_tx(
+        solana,
+        PerpEditOrderInstruction {
+            account: account_0,
+            perp_market,
+            owner,
+            order_id: Some(new_order_b_id),
+            client_order_id: Some(4),
+            price_lots,
+            max_base_lots: 1,
+            ..PerpEditOrderInstruction::default()
+        },
+    )
+    .await
+    .is_err());
+
+    Ok(())
+}
@@ -0,0 +1,286 @@
+use super::*;
+
+#[tokio::test]
+async fn test_perp_fee_tiers() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let solana = &context.solana.clone();
+
+    let admin = TestKeypair::new();
+    let owner = context.users[0].key;
+    let payer = context.users[1].key;
+    let mints = &context.mints[0..2];
+
+    //
+    // SETUP: Create a group, accounts and a perp market
+    //
+
+    let GroupWithTokens { group, tokens, .. } = GroupWithTokensConfig {
+        admin,
+        payer,
+        mints: mints.to_vec(),
+        ..GroupWithTokensConfig::default()
+    }
+    .create(solana)
+    .await;
+
+    let maker = create_funded_account(
+        &solana,
+        group,
+        owner,
+        0,
+        &context.users[1],
+        &mints[0..1],
+        1_000_000,
+        0,
+    )
+    .await;
+    let taker = create_funded_account(
+        &solana,
+        group,
+        owner,
+        1,
+        &context.users[1],
+        &mints[0..1],
+        1_000_000,
+        0,
+    )
+    .await;
+
+    let mango_v4::accounts::PerpCreateMarket { perp_market, .. } = send_tx(
+        solana,
+        PerpCreateMarketInstruction {
+            group,
+            admin,
+            payer,
+            perp_market_index: 0,
+            quote_lot_size: 10,
+            base_lot_size: 100,
+            maint_base_asset_weight: 0.975,
+            init_base_asset_weight: 0.95,
+            maint_base_liab_weight: 1.025,
+            init_base_liab_weight: 1.05,
+            base_liquidation_fee: 0.012,
+            maker_fee: 0.0002,
+            taker_fee: 0.0004,
+            ..PerpCreateMarketInstruction::with_new_book_and_queue(&solana, &tokens[1]).await
+        },
+    )
+    .await
+    .unwrap();
+
+    let price_lots = {
+        let perp_market = solana.get_account::<PerpMarket>(perp_market).await;
+        perp_market.native_price_to_lot(I80F48::from(1000))
+    };
+    set_bank_stub_oracle_price(solana, group, &tokens[1], admin, 1000.0).await;
+
+    //
+    // TEST: Tier ids are checked
+    //
+    send_tx_expect_error!(
+        solana,
+        GroupEditPerpFeeTierInstruction {
+            group,
+            admin,
+            tier: 0,
+            min_volume_quote: 150_000,
+            maker_fee_discount: 0.0003,
+            taker_fee_discount: 0.0002,
+        },
+        MangoError::InvalidPerpFeeTier
+    );
+
+    //
+    // TEST: Add a tier that applies after 150 USD of volume
+    //
+    send_tx(
+        solana,
+        GroupEditPerpFeeTierInstruction {
+            group,
+            admin,
+            tier: 1,
+            min_volume_quote: 150_000,
+            maker_fee_discount: 0.0003,
+            taker_fee_discount: 0.0002,
+        },
+    )
+    .await
+    .unwrap();
+
+    // Each trade is 1 base lot at price 1000, so 100_000 quote native
+    let trade = || async move {
+        send_tx(
+            solana,
+            PerpPlaceOrderInstruction {
+                account: maker,
+                perp_market,
+                owner,
+                side: Side::Bid,
+                price_lots,
+                max_base_lots: 1,
+                ..PerpPlaceOrderInstruction::default()
+            },
+        )
+        .await
+        .unwrap();
+        send_tx(
+            solana,
+            PerpPlaceOrderInstruction {
+                account: taker,
+                perp_market,
+                owner,
+                side: Side::Ask,
+                price_lots,
+                max_base_lots: 1,
+                ..PerpPlaceOrderInstruction::default()
+            },
+        )
+        .await
+        .unwrap();
+        send_tx(
+            solana,
+            PerpConsumeEventsInstruction {
+                perp_market,
+                mango_accounts: vec![maker, taker],
+            },
+        )
+        .await
+        .unwrap();
+    };
+
+    //
+    // TEST: Without enough volume, the base fees apply
+    //
+    trade().await;
+    trade().await;
+
+    let mango_account_0 = solana.get_account::<MangoAccount>(maker).await;
+    assert_eq!(mango_account_0.perp_fee_volume_current, 200_000);
+    assert_eq_fixed_f64!(
+        mango_account_0.perps[0].quote_position_native(),
+        -200_040.0,
+        0.01
+    );
+    let mango_account_1 = solana.get_account::<MangoAccount>(taker).await;
+    assert_eq!(mango_account_1.perp_fee_volume_current, 200_000);
+    assert_eq_fixed_f64!(
+        mango_account_1.perps[0].quote_position_native(),
+        199_920.0,
+        0.01
+    );
+
+    //
+    // TEST: Both accounts now qualify for the tier: the maker gets a rebate
+    // and the taker pays less
+    //
+    trade().await;
+
+    let mango_account_0 = solana.get_account::<MangoAccount>(maker).await;
+    assert_eq!(mango_account_0.perp_fee_volume_current, 300_000);
+    assert_eq_fixed_f64!(
+        mango_account_0.perps[0].quote_position_native(),
+        -200_040.0 - 100_000.0 + 10.0,
+        0.01
+    );
+    let mango_account_1 = solana.get_account::<MangoAccount>(taker).await;
+    assert_eq_fixed_f64!(
+        mango_account_1.perps[0].quote_position_native(),
+        199_920.0 + 100_000.0 - 20.0,
+        0.01
+    );
+
+    let perp_market_data = solana.get_account::<PerpMarket>(perp_market).await;
+    assert_eq_fixed_f64!(
+        perp_market_data.fees_accrued,
+        2.0 * (20.0 + 40.0) + (-10.0 + 20.0),
+        0.01
+    );
+
+    //
+    // TEST: Removing the tier restores base fees
+    //
+    send_tx(
+        solana,
+        GroupEditPerpFeeTierInstruction {
+            group,
+            admin,
+            tier: 1,
+            min_volume_quote: 0,
+            maker_fee_discount: 0.0,
+            taker_fee_discount: 0.0,
+        },
+    )
+    .await
+    .unwrap();
+
+    trade().await;
+
+    let mango_account_1 = solana.get_account::<MangoAccount>(taker).await;
+    assert_eq_fixed_f64!(
+        mango_account_1.perps[0].quote_position_native(),
+        299_900.0 + 100_000.0 - 40.0,
+        0.01
+    );
+
+    //
+    // TEST: Self trades don't count towards the fee tier volume
+    //
+    let volume_before = solana
+        .get_account::<MangoAccount>(maker)
+        .await
+        .perp_fee_volume_current;
+    for side in [Side::Bid, Side::Ask] {
+        send_tx(
+            solana,
+            PerpPlaceOrderInstruction {
+                account: maker,
+                perp_market,
+                owner,
+                side,
+                price_lots,
+                max_base_lots: 1,
+                self_trade_behavior: SelfTradeBehavior::DecrementTake,
+                ..PerpPlaceOrderInstruction::default()
+            },
+        )
+        .await
+        .unwrap();
+    }
+    send_tx(
+        solana,
+        PerpConsumeEventsInstruction {
+            perp_market,
+            mango_accounts: vec![maker],
+        },
+    )
+    .await
+    .unwrap();
+    let mango_account_0 = solana.get_account::<MangoAccount>(maker).await;
+    assert_eq!(mango_account_0.perp_fee_volume_current, volume_before);
+
+    //
+    // TEST: A tier whose maker rebate exceeds the lowest taker fee can't pay it out
+    //
+    send_tx(
+        solana,
+        GroupEditPerpFeeTierInstruction {
+            group,
+            admin,
+            tier: 1,
+            min_volume_quote: 1,
+            maker_fee_discount: 0.001,
+            taker_fee_discount: 0.0004,
+        },
+    )
+    .await
+    .unwrap();
+    let perp_market_data = solana.get_account::<PerpMarket>(perp_market).await;
+    // lowest taker fee is 0, so the rebate is capped to 0
+    assert_eq!(
+        perp_market_data
+            .maker_fee_for_tier(&solana.get_account::<Group>(group).await.perp_fee_tiers, 1),
+        I80F48::ZERO
+    );
+
+    Ok(())
+}
@@ -0,0 +1,443 @@
+use super::*;
+use mango_v4::accounts_ix::PerpBatchOrder;
+
+#[tokio::test]
+async fn test_perp_opening_auction() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let solana = &context.solana.clone();
+
+    let admin = TestKeypair::new();
+    let owner = context.users[0].key;
+    let payer = context.users[1].key;
+    let mints = &context.mints[0..2];
+
+    //
+    // SETUP: Create a group, two accounts and a perp market with an opening auction
+    //
+
+    let GroupWithTokens { group, tokens, .. } = GroupWithTokensConfig {
+        admin,
+        payer,
+        mints: mints.to_vec(),
+        ..GroupWithTokensConfig::default()
+    }
+    .create(solana)
+    .await;
+
+    let account_0 =
+        create_funded_account(&solana, group, owner, 0, &context.users[1], mints, 1000, 0).await;
+    let account_1 =
+        create_funded_account(&solana, group, owner, 1, &context.users[1], mints, 1000, 0).await;
+
+    let mango_v4::accounts::PerpCreateMarket {
+        perp_market,
+        bids,
+        asks,
+        ..
+    } = send_tx(
+        solana,
+        PerpCreateMarketInstruction {
+            group,
+            admin,
+            payer,
+            perp_market_index: 0,
+            quote_lot_size: 10,
+            base_lot_size: 100,
+            maint_base_asset_weight: 0.975,
+            init_base_asset_weight: 0.95,
+            maint_base_liab_weight: 1.025,
+            init_base_liab_weight: 1.05,
+            base_liquidation_fee: 0.012,
+            maker_fee: 0.0,
+            taker_fee: 0.0,
+            opening_auction_duration: 100,
+            ..PerpCreateMarketInstruction::with_new_book_and_queue(&solana, &tokens[0]).await
+        },
+    )
+    .await
+    .unwrap();
+
+    let (price_lots, auction_end_ts) = {
+        let perp_market = solana.get_account::<PerpMarket>(perp_market).await;
+        assert!(perp_market.is_in_opening_auction());
+        (
+            perp_market.native_price_to_lot(I80F48::ONE),
+            perp_market.opening_auction_end_ts,
+        )
+    };
+    assert!(auction_end_ts >= solana.clock_timestamp().await + 100);
+
+    let place = |account, side, price_lots, max_base_lots| PerpPlaceOrderInstruction {
+        account,
+        perp_market,
+        owner,
+        side,
+        price_lots,
+        max_base_lots,
+        ..PerpPlaceOrderInstruction::default()
+    };
+
+    //
+    // TEST: Crossing orders are collected without matching
+    //
+    send_tx(solana, place(account_0, Side::Bid, price_lots + 2, 2))
+        .await
+        .unwrap();
+    send_tx(solana, place(account_0, Side::Bid, price_lots, 1))
+        .await
+        .unwrap();
+    send_tx(solana, place(account_1, Side::Ask, price_lots - 1, 1))
+        .await
+        .unwrap();
+    send_tx(solana, place(account_1, Side::Ask, price_lots + 1, 2))
+        .await
+        .unwrap();
+
+    let event_queue = {
+        let perp_market = solana.get_account::<PerpMarket>(perp_market).await;
+        perp_market.event_queue
+    };
+    let seq_num = || async move {
+        solana
+            .get_account_boxed::<EventQueueAccount>(event_queue)
+            .await
+            .header
+            .seq_num
+    };
+    assert_eq!(seq_num().await, 0);
+    let leaf_count = |bookside| async move {
+        solana
+            .get_account_boxed::<BookSideAccount>(bookside)
+            .await
+            .roots[0]
+            .leaf_count
+    };
+    assert_eq!(leaf_count(bids).await, 2);
+    assert_eq!(leaf_count(asks).await, 2);
+
+    //
+    // TEST: Orders that can't rest on the book are rejected
+    //
+    let res = send_tx(
+        solana,
+        PerpPlaceOrdersBatchInstruction {
+            account: account_0,
+            perp_market,
+            owner,
+            orders: vec![PerpBatchOrder {
+                side: Side::Bid,
+                price_lots: price_lots + 1,
+                max_base_lots: 1,
+                order_type: PlaceOrderType::ImmediateOrCancel,
+                client_order_id: 0,
+            }],
+            cancel_all_first: false,
+            limit: 10,
+        },
+    )
+    .await;
+    assert_mango_error(
+        &res,
+        MangoError::PerpOpeningAuctionOrderNotPostable.into(),
+        "ioc order during auction".to_string(),
+    );
+
+    //
+    // TEST: The auction can't be cleared early
+    //
+    let res = send_tx(
+        solana,
+        PerpClearOpeningAuctionInstruction {
+            perp_market,
+            limit: 10,
+        },
+    )
+    .await;
+    assert_mango_error(
+        &res,
+        MangoError::PerpOpeningAuctionNotEnded.into(),
+        "auction still running".to_string(),
+    );
+
+    //
+    // TEST: Clearing executes at a uniform price, possibly over several calls
+    //
+    solana.advance_clock_to(auction_end_ts as i64).await;
+
+    // bids 2@+2, 1@+0 and asks 1@-1, 2@+1: 2 lots execute at +1
+    send_tx(
+        solana,
+        PerpClearOpeningAuctionInstruction {
+            perp_market,
+            limit: 1,
+        },
+    )
+    .await
+    .unwrap();
+    {
+        let perp_market = solana.get_account::<PerpMarket>(perp_market).await;
+        assert!(perp_market.is_in_opening_auction());
+        assert_eq!(perp_market.opening_auction_price_lots, price_lots + 1);
+    }
+    assert_eq!(seq_num().await, 1);
+
+    send_tx(
+        solana,
+        PerpClearOpeningAuctionInstruction {
+            perp_market,
+            limit: 10,
+        },
+    )
+    .await
+    .unwrap();
+    {
+        let perp_market = solana.get_account::<PerpMarket>(perp_market).await;
+        assert!(!perp_market.is_in_opening_auction());
+        assert_eq!(perp_market.opening_auction_price_lots, 0);
+    }
+    assert_eq!(seq_num().await, 2);
+    assert_eq!(leaf_count(bids).await, 1);
+    assert_eq!(leaf_count(asks).await, 1);
+
+    send_tx(
+        solana,
+        PerpConsumeEventsInstruction {
+            perp_market,
+            mango_accounts: vec![account_0, account_1],
+        },
+    )
+    .await
+    .unwrap();
+
+    let quote_native = 2.0 * (price_lots + 1) as f64 * 10.0;
+    let mango_account_0 = solana.get_account::<MangoAccount>(account_0).await;
+    assert_eq!(mango_account_0.perps[0].base_position_lots(), 2);
+    assert_eq!(mango_account_0.perps[0].bids_base_lots, 1);
+    assert_eq_fixed_f64!(
+        mango_account_0.perps[0].quote_position_native(),
+        -quote_native,
+        0.001
+    );
+    let mango_account_1 = solana.get_account::<MangoAccount>(account_1).await;
+    assert_eq!(mango_account_1.perps[0].base_position_lots(), -2);
+    assert_eq!(mango_account_1.perps[0].asks_base_lots, 1);
+    assert_eq_fixed_f64!(
+        mango_account_1.perps[0].quote_position_native(),
+        quote_native,
+        0.001
+    );
+    for account in [mango_account_0, mango_account_1] {
+        let open_orders = account
+            .perp_open_orders
+            .iter()
+            .filter(|oo| oo.market != FREE_ORDER_SLOT)
+            .count();
+        assert_eq!(open_orders, 1);
+    }
+
+    //
+    // TEST: After the auction, orders match again
+    //
+    send_tx(solana, place(account_0, Side::Bid, price_lots + 1, 1))
+        .await
+        .unwrap();
+    assert_eq!(seq_num().await, 3);
+    assert_eq!(leaf_count(asks).await, 0);
+
+    //
+    // TEST: Leaving reduce-only mode starts a new auction
+    //
+    for reduce_only in [true, false] {
+        send_tx(
+            solana,
+            PerpMakeReduceOnly {
+                group,
+                admin,
+                perp_market,
+                reduce_only,
+                force_close: false,
+            },
+        )
+        .await
+        .unwrap();
+    }
+    let perp_market_data = solana.get_account::<PerpMarket>(perp_market).await;
+    assert!(perp_market_data.is_in_opening_auction());
+
+    // without an auction duration, reopening is immediate
+    send_tx(
+        solana,
+        PerpSetOpeningAuctionDuration {
+            group,
+            admin,
+            perp_market,
+            duration: 0,
+        },
+    )
+    .await
+    .unwrap();
+    solana
+        .advance_clock_to(perp_market_data.opening_auction_end_ts as i64)
+        .await;
+    send_tx(
+        solana,
+        PerpClearOpeningAuctionInstruction {
+            perp_market,
+            limit: 10,
+        },
+    )
+    .await
+    .unwrap();
+    for reduce_only in [true, false] {
+        send_tx(
+            solana,
+            PerpMakeReduceOnly {
+                group,
+                admin,
+                perp_market,
+                reduce_only,
+                force_close: false,
+            },
+        )
+        .await
+        .unwrap();
+    }
+    let perp_market_data = solana.get_account::<PerpMarket>(perp_market).await;
+    assert!(!perp_market_data.is_in_opening_auction());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_perp_opening_auction_fees() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let solana = &context.solana.clone();
+
+    let admin = TestKeypair::new();
+    let owner = context.users[0].key;
+    let payer = context.users[1].key;
+    let mints = &context.mints[0..2];
+
+    //
+    // SETUP: Create a group, two accounts and a perp market with maker rebates
+    //
+
+    let GroupWithTokens { group, tokens, .. } = GroupWithTokensConfig {
+        admin,
+        payer,
+        mints: mints.to_vec(),
+        ..GroupWithTokensConfig::default()
+    }
+    .create(solana)
+    .await;
+
+    let account_0 =
+        create_funded_account(&solana, group, owner, 0, &context.users[1], mints, 1000, 0).await;
+    let account_1 =
+        create_funded_account(&solana, group, owner, 1, &context.users[1], mints, 1000, 0).await;
+
+    let mango_v4::accounts::PerpCreateMarket { perp_market, .. } = send_tx(
+        solana,
+        PerpCreateMarketInstruction {
+            group,
+            admin,
+            payer,
+            perp_market_index: 0,
+            quote_lot_size: 10,
+            base_lot_size: 100,
+            maint_base_asset_weight: 0.975,
+            init_base_asset_weight: 0.95,
+            maint_base_liab_weight: 1.025,
+            init_base_liab_weight: 1.05,
+            base_liquidation_fee: 0.012,
+            maker_fee: -0.001,
+            taker_fee: 0.002,
+            opening_auction_duration: 100,
+            ..PerpCreateMarketInstruction::with_new_book_and_queue(&solana, &tokens[0]).await
+        },
+    )
+    .await
+    .unwrap();
+
+    let (price_lots, auction_end_ts) = {
+        let perp_market = solana.get_account::<PerpMarket>(perp_market).await;
+        (
+            perp_market.native_price_to_lot(I80F48::ONE),
+            perp_market.opening_auction_end_ts,
+        )
+    };
+
+    //
+    // TEST: The order that was placed later pays the taker fee
+    //
+    send_tx(
+        solana,
+        PerpPlaceOrderInstruction {
+            account: account_0,
+            perp_market,
+            owner,
+            side: Side::Bid,
+            price_lots,
+            max_base_lots: 1,
+            ..PerpPlaceOrderInstruction::default()
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        PerpPlaceOrderInstruction {
+            account: account_1,
+            perp_market,
+            owner,
+            side: Side::Ask,
+            price_lots,
+            max_base_lots: 1,
+            ..PerpPlaceOrderInstruction::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    solana.advance_clock_to(auction_end_ts as i64).await;
+    send_tx(
+        solana,
+        PerpClearOpeningAuctionInstruction {
+            perp_market,
+            limit: 10,
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        PerpConsumeEventsInstruction {
+            perp_market,
+            mango_accounts: vec![account_0, account_1],
+        },
+    )
+    .await
+    .unwrap();
+
+    let quote_native = price_lots as f64 * 10.0;
+    let mango_account_0 = solana.get_account::<MangoAccount>(account_0).await;
+    assert_eq!(mango_account_0.perps[0].base_position_lots(), 1);
+    assert_eq_fixed_f64!(
+        mango_account_0.perps[0].quote_position_native(),
+        -quote_native * (1.0 - 0.001),
+        0.001
+    );
+    let mango_account_1 = solana.get_account::<MangoAccount>(account_1).await;
+    assert_eq!(mango_account_1.perps[0].base_position_lots(), -1);
+    assert_eq_fixed_f64!(
+        mango_account_1.perps[0].quote_position_native(),
+        quote_native * (1.0 - 0.002),
+        0.001
+    );
+
+    // the taker fee pays for the maker rebate
+    let perp_market_data = solana.get_account::<PerpMarket>(perp_market).await;
+    assert_eq_fixed_f64!(perp_market_data.fees_accrued, quote_native * 0.001, 0.001);
+
+    Ok(())
+}
@@ -0,0 +1,164 @@
+use super::*;
+use mango_v4::accounts_ix::PerpBatchOrder;
+
+#[tokio::test]
+async fn test_perp_place_orders_batch() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let solana = &context.solana.clone();
+
+    let admin = TestKeypair::new();
+    let owner = context.users[0].key;
+    let payer = context.users[1].key;
+    let mints = &context.mints[0..2];
+
+    //
+    // SETUP: Create a group, an account and a perp market
+    //
+
+    let GroupWithTokens { group, tokens, .. } = GroupWithTokensConfig {
+        admin,
+        payer,
+        mints: mints.to_vec(),
+        ..GroupWithTokensConfig::default()
+    }
+    .create(solana)
+    .await;
+
+    let account_0 =
+        create_funded_account(&solana, group, owner, 0, &context.users[1], mints, 1000, 0).await;
+
+    let mango_v4::accounts::PerpCreateMarket {
+        perp_market,
+        bids,
+        asks,
+        ..
+    } = send_tx(
+        solana,
+        PerpCreateMarketInstruction {
+            group,
+            admin,
+            payer,
+            perp_market_index: 0,
+            quote_lot_size: 10,
+            base_lot_size: 100,
+            maint_base_asset_weight: 0.975,
+            init_base_asset_weight: 0.95,
+            maint_base_liab_weight: 1.025,
+            init_base_liab_weight: 1.05,
+            base_liquidation_fee: 0.012,
+            maker_fee: 0.0,
+            taker_fee: 0.0,
+            ..PerpCreateMarketInstruction::with_new_book_and_queue(&solana, &tokens[0]).await
+        },
+    )
+    .await
+    .unwrap();
+
+    let price_lots = {
+        let perp_market = solana.get_account::<PerpMarket>(perp_market).await;
+        perp_market.native_price_to_lot(I80F48::ONE)
+    };
+
+    let order = |side, price_lots, max_base_lots, client_order_id| PerpBatchOrder {
+        side,
+        price_lots,
+        max_base_lots,
+        order_type: PlaceOrderType::PostOnly,
+        client_order_id,
+    };
+
+    //
+    // TEST: Place a quote with several levels
+    //
+    send_tx(
+        solana,
+        PerpPlaceOrdersBatchInstruction {
+            account: account_0,
+            perp_market,
+            owner,
+            orders: vec![
+                order(Side::Bid, price_lots - 1, 1, 1),
+                order(Side::Bid, price_lots - 2, 2, 2),
+                order(Side::Ask, price_lots + 1, 1, 3),
+            ],
+            cancel_all_first: false,
+            limit: 10,
+        },
+    )
+    .await
+    .unwrap();
+    check_prev_instruction_post_health(&solana, account_0).await;
+
+    let mango_account_0 = solana.get_account::<MangoAccount>(account_0).await;
+    assert_eq!(mango_account_0.perps[0].bids_base_lots, 3);
+    assert_eq!(mango_account_0.perps[0].asks_base_lots, 1);
+    assert_eq!(
+        solana
+            .get_account_boxed::<BookSideAccount>(bids)
+            .await
+            .roots[0]
+            .leaf_count,
+        2
+    );
+    assert_eq!(
+        solana
+            .get_account_boxed::<BookSideAccount>(asks)
+            .await
+            .roots[0]
+            .leaf_count,
+        1
+    );
+
+    //
+    // TEST: Replace the quote, cancelling the old orders first
+    //
+    send_tx(
+        solana,
+        PerpPlaceOrdersBatchInstruction {
+            account: account_0,
+            perp_market,
+            owner,
+            orders: vec![
+                order(Side::Bid, price_lots - 1, 2, 4),
+                order(Side::Ask, price_lots + 2, 2, 5),
+            ],
+            cancel_all_first: true,
+            limit: 10,
+        },
+    )
+    .await
+    .unwrap();
+
+    let mango_account_0 = solana.get_account::<MangoAccount>(account_0).await;
+    assert_eq!(mango_account_0.perps[0].bids_base_lots, 2);
+    assert_eq!(mango_account_0.perps[0].asks_base_lots, 2);
+    let client_ids = mango_account_0
+        .perp_open_orders
+        .iter()
+        .filter(|oo| oo.market != FREE_ORDER_SLOT)
+        .map(|oo| oo.client_id)
+        .sorted()
+        .collect_vec();
+    assert_eq!(client_ids, vec![4, 5]);
+
+    //
+    // TEST: Batches are limited in size
+    //
+    assert!(send_tx(
+        solana,
+        PerpPlaceOrdersBatchInstruction {
+            account: account_0,
+            perp_market,
+            owner,
+            orders: (0..17)
+                .map(|i| order(Side::Bid, price_lots - 1, 1, 10 + i))
+                .collect(),
+            cancel_all_first: true,
+            limit: 10,
+        },
+    )
+    .await
+    .is_err());
+
+    Ok(())
+}
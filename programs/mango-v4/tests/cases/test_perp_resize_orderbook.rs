@@ -0,0 +1,262 @@
+use super::*;
+
+#[tokio::test]
+async fn test_perp_resize_orderbook() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let solana = &context.solana.clone();
+
+    let admin = TestKeypair::new();
+    let owner = context.users[0].key;
+    let payer = context.users[1].key;
+    let mints = &context.mints[0..2];
+
+    //
+    // SETUP: Create a group, accounts and a perp market with the default capacities
+    //
+
+    let GroupWithTokens { group, tokens, .. } = GroupWithTokensConfig {
+        admin,
+        payer,
+        mints: mints.to_vec(),
+        ..GroupWithTokensConfig::default()
+    }
+    .create(solana)
+    .await;
+
+    let account_0 =
+        create_funded_account(&solana, group, owner, 0, &context.users[1], mints, 1000, 0).await;
+    let account_1 =
+        create_funded_account(&solana, group, owner, 1, &context.users[1], mints, 1000, 0).await;
+
+    let mango_v4::accounts::PerpCreateMarket {
+        perp_market,
+        bids,
+        asks,
+        event_queue,
+        ..
+    } = send_tx(
+        solana,
+        PerpCreateMarketInstruction {
+            group,
+            admin,
+            payer,
+            perp_market_index: 0,
+            quote_lot_size: 10,
+            base_lot_size: 100,
+            maint_base_asset_weight: 0.975,
+            init_base_asset_weight: 0.95,
+            maint_base_liab_weight: 1.025,
+            init_base_liab_weight: 1.05,
+            base_liquidation_fee: 0.012,
+            maker_fee: 0.0,
+            taker_fee: 0.0,
+            ..PerpCreateMarketInstruction::with_new_book_and_queue(&solana, &tokens[0]).await
+        },
+    )
+    .await
+    .unwrap();
+
+    let account_len =
+        |address: Pubkey| async move { solana.get_account_data(address).await.unwrap().len() };
+    assert_eq!(
+        account_len(bids).await,
+        BookSide::account_size(MAX_ORDERTREE_NODES)
+    );
+    assert_eq!(
+        account_len(event_queue).await,
+        EventQueue::account_size(MAX_NUM_EVENTS as usize)
+    );
+
+    let price_lots = {
+        let perp_market = solana.get_account::<PerpMarket>(perp_market).await;
+        perp_market.native_price_to_lot(I80F48::ONE)
+    };
+
+    let place_order = |account: Pubkey, side: Side, price_lots: i64| {
+        send_tx(
+            solana,
+            PerpPlaceOrderInstruction {
+                account,
+                perp_market,
+                owner,
+                side,
+                price_lots,
+                max_base_lots: 1,
+                ..PerpPlaceOrderInstruction::default()
+            },
+        )
+    };
+    let consume_events = || async move {
+        send_tx(
+            solana,
+            PerpConsumeEventsInstruction {
+                perp_market,
+                mango_accounts: vec![account_0, account_1],
+            },
+        )
+        .await
+        .unwrap();
+    };
+
+    // Make sure the event queue head is not at the start of the buffer
+    place_order(account_0, Side::Bid, price_lots).await.unwrap();
+    place_order(account_1, Side::Ask, price_lots).await.unwrap();
+    consume_events().await;
+    place_order(account_0, Side::Bid, price_lots).await.unwrap();
+    place_order(account_1, Side::Ask, price_lots).await.unwrap();
+    // and leave an order on the book
+    place_order(account_0, Side::Bid, price_lots - 1)
+        .await
+        .unwrap();
+
+    //
+    // TEST: Growing takes several instructions when the size increase is large
+    //
+    let bookside_capacity = MAX_ORDERTREE_NODES as u32 + 200;
+    let event_queue_capacity = MAX_NUM_EVENTS + 100;
+    let resize = |bookside_capacity: u32, event_queue_capacity: u32| {
+        send_tx(
+            solana,
+            PerpResizeOrderbookInstruction {
+                admin,
+                payer,
+                perp_market,
+                bookside_capacity,
+                event_queue_capacity,
+            },
+        )
+    };
+
+    resize(bookside_capacity, event_queue_capacity)
+        .await
+        .unwrap();
+    assert!(account_len(bids).await > BookSide::account_size(MAX_ORDERTREE_NODES));
+    assert!(account_len(bids).await < BookSide::account_size(bookside_capacity as usize));
+
+    for _ in 0..2 {
+        resize(bookside_capacity, event_queue_capacity)
+            .await
+            .unwrap();
+    }
+    for address in [bids, asks] {
+        assert_eq!(
+            account_len(address).await,
+            BookSide::account_size(bookside_capacity as usize)
+        );
+    }
+    assert_eq!(
+        account_len(event_queue).await,
+        EventQueue::account_size(event_queue_capacity as usize)
+    );
+
+    // the order and the pending fill survived
+    let bids_data = solana.get_account_data(bids).await.unwrap();
+    let bookside = BookSide::from_account_data(&bids_data).unwrap();
+    assert_eq!(bookside.capacity(), bookside_capacity as usize);
+    assert_eq!(bookside.iter_valid(0, price_lots).count(), 1);
+
+    let event_queue_data = solana.get_account_data(event_queue).await.unwrap();
+    let queue = EventQueue::from_account_data(&event_queue_data).unwrap();
+    assert_eq!(queue.capacity(), event_queue_capacity as usize);
+    assert_eq!(queue.header.head(), 0);
+    assert_eq!(queue.len(), 1);
+
+    consume_events().await;
+    let mango_account_0 = solana.get_account::<MangoAccount>(account_0).await;
+    assert_eq!(mango_account_0.perps[0].base_position_lots(), 2);
+    let mango_account_1 = solana.get_account::<MangoAccount>(account_1).await;
+    assert_eq!(mango_account_1.perps[0].base_position_lots(), -2);
+
+    // trading continues normally
+    place_order(account_1, Side::Ask, price_lots - 1)
+        .await
+        .unwrap();
+    consume_events().await;
+    let mango_account_0 = solana.get_account::<MangoAccount>(account_0).await;
+    assert_eq!(mango_account_0.perps[0].base_position_lots(), 3);
+
+    //
+    // TEST: Capacities can't decrease
+    //
+    assert!(resize(MAX_ORDERTREE_NODES as u32, event_queue_capacity)
+        .await
+        .is_err());
+
+    //
+    // TEST: Markets can be created with larger capacities
+    //
+    let mango_v4::accounts::PerpCreateMarket {
+        perp_market: perp_market_1,
+        bids: bids_1,
+        event_queue: event_queue_1,
+        ..
+    } = send_tx(
+        solana,
+        PerpCreateMarketInstruction {
+            group,
+            admin,
+            payer,
+            perp_market_index: 1,
+            quote_lot_size: 10,
+            base_lot_size: 100,
+            maint_base_asset_weight: 0.975,
+            init_base_asset_weight: 0.95,
+            maint_base_liab_weight: 1.025,
+            init_base_liab_weight: 1.05,
+            base_liquidation_fee: 0.012,
+            maker_fee: 0.0,
+            taker_fee: 0.0,
+            ..PerpCreateMarketInstruction::with_new_book_and_queue_capacity(
+                &solana, &tokens[1], 4096, 2048,
+            )
+            .await
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(account_len(bids_1).await, BookSide::account_size(4096));
+    assert_eq!(
+        account_len(event_queue_1).await,
+        EventQueue::account_size(2048)
+    );
+
+    send_tx(
+        solana,
+        PerpPlaceOrderInstruction {
+            account: account_0,
+            perp_market: perp_market_1,
+            owner,
+            side: Side::Bid,
+            price_lots,
+            max_base_lots: 1,
+            ..PerpPlaceOrderInstruction::default()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        solana
+            .get_account_boxed::<BookSideAccount>(bids_1)
+            .await
+            .roots[0]
+            .leaf_count,
+        1
+    );
+
+    //
+    // TEST: The account sizes must match the requested capacities
+    //
+    let mut create_market = PerpCreateMarketInstruction {
+        group,
+        admin,
+        payer,
+        perp_market_index: 2,
+        quote_lot_size: 10,
+        base_lot_size: 100,
+        ..PerpCreateMarketInstruction::with_new_book_and_queue(&solana, &tokens[1]).await
+    };
+    create_market.bookside_capacity = 2048;
+    assert!(send_tx(solana, create_market).await.is_err());
+
+    Ok(())
+}
@@ -0,0 +1,665 @@
+use super::*;
+
+#[tokio::test]
+async fn test_perp_scheduled_order_twap() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let solana = &context.solana.clone();
+
+    let admin = TestKeypair::new();
+    let owner = context.users[0].key;
+    let payer = context.users[1].key;
+    let mints = &context.mints[0..2];
+
+    //
+    // SETUP: Create a group, a perp market and accounts
+    //
+
+    let GroupWithTokens { group, tokens, .. } = GroupWithTokensConfig {
+        admin,
+        payer,
+        mints: mints.to_vec(),
+        ..GroupWithTokensConfig::default()
+    }
+    .create(solana)
+    .await;
+
+    let deposit_amount = 100_000;
+    let maker = create_funded_account(
+        &solana,
+        group,
+        owner,
+        0,
+        &context.users[1],
+        mints,
+        deposit_amount,
+        0,
+    )
+    .await;
+    let account = create_funded_account(
+        &solana,
+        group,
+        owner,
+        1,
+        &context.users[1],
+        mints,
+        deposit_amount,
+        0,
+    )
+    .await;
+    let caller =
+        create_funded_account(&solana, group, owner, 2, &context.users[1], &[], 0, 0).await;
+
+    send_tx(
+        solana,
+        AccountExpandInstruction {
+            account_num: 1,
+            token_count: 8,
+            serum3_count: 4,
+            perp_count: 4,
+            perp_oo_count: 16,
+            token_conditional_swap_count: 1,
+            perp_scheduled_order_count: 2,
+            group,
+            owner,
+            payer,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let mango_v4::accounts::PerpCreateMarket { perp_market, .. } = send_tx(
+        solana,
+        PerpCreateMarketInstruction {
+            group,
+            admin,
+            payer,
+            perp_market_index: 0,
+            quote_lot_size: 10,
+            base_lot_size: 100,
+            maint_base_asset_weight: 0.975,
+            init_base_asset_weight: 0.95,
+            maint_base_liab_weight: 1.025,
+            init_base_liab_weight: 1.05,
+            base_liquidation_fee: 0.012,
+            maker_fee: 0.0,
+            taker_fee: 0.0,
+            ..PerpCreateMarketInstruction::with_new_book_and_queue(&solana, &tokens[0]).await
+        },
+    )
+    .await
+    .unwrap();
+
+    let price_lots = {
+        let perp_market = solana.get_account::<PerpMarket>(perp_market).await;
+        perp_market.native_price_to_lot(I80F48::ONE)
+    };
+
+    send_tx(
+        solana,
+        PerpPlaceOrderInstruction {
+            account: maker,
+            perp_market,
+            owner,
+            side: Side::Ask,
+            price_lots,
+            max_base_lots: 10,
+            ..PerpPlaceOrderInstruction::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    //
+    // TEST: Create a twap that buys 5 lots, 2 lots at a time
+    //
+    let start_time = solana.clock_timestamp().await;
+    send_tx(
+        solana,
+        PerpScheduledOrderCreateInstruction {
+            account,
+            owner,
+            perp_market,
+            side: Side::Bid,
+            order_type: PerpScheduledOrderType::Twap,
+            max_base_lots: 5,
+            child_base_lots: 2,
+            price_lots,
+            interval_seconds: 60,
+            ..PerpScheduledOrderCreateInstruction::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let account_data = get_mango_account(solana, account).await;
+    let scheduled = account_data.perp_scheduled_order_by_index(0).unwrap();
+    assert!(scheduled.is_configured());
+    assert_eq!(scheduled.id, 0);
+    assert_eq!(account_data.active_perp_scheduled_orders().count(), 1);
+
+    //
+    // TEST: Trigger the first child order, the caller gets the incentive
+    //
+    let trigger = PerpScheduledOrderTriggerInstruction {
+        account,
+        caller,
+        caller_owner: owner,
+        index: 0,
+    };
+    send_tx(solana, trigger.clone()).await.unwrap();
+
+    let account_data = get_mango_account(solana, account).await;
+    let scheduled = account_data.perp_scheduled_order_by_index(0).unwrap();
+    assert_eq!(scheduled.executed_base_lots, 2);
+    assert_eq!(scheduled.next_trigger_timestamp, start_time + 60);
+    assert_eq!(
+        account_data
+            .perp_position(0)
+            .unwrap()
+            .effective_base_position_lots(),
+        2
+    );
+    assert_eq!(
+        account_position(solana, caller, tokens[0].bank).await,
+        PERP_SCHEDULED_ORDER_INCENTIVE as i64
+    );
+
+    //
+    // TEST: Can't trigger again before the interval passed
+    //
+    assert_mango_error(
+        &send_tx(solana, trigger.clone()).await,
+        MangoError::PerpScheduledOrderNotTriggerable.into(),
+        "before interval".to_string(),
+    );
+
+    //
+    // TEST: Trigger the remaining child orders, the order is removed when done
+    //
+    solana.set_clock_timestamp(start_time + 60).await;
+    send_tx(solana, trigger.clone()).await.unwrap();
+    solana.set_clock_timestamp(start_time + 120).await;
+    send_tx(solana, trigger.clone()).await.unwrap();
+
+    let account_data = get_mango_account(solana, account).await;
+    assert_eq!(
+        account_data
+            .perp_position(0)
+            .unwrap()
+            .effective_base_position_lots(),
+        5
+    );
+    assert_eq!(account_data.active_perp_scheduled_orders().count(), 0);
+
+    //
+    // TEST: Triggers that fill nothing fail and pay no incentive
+    //
+    send_tx(
+        solana,
+        PerpScheduledOrderCreateInstruction {
+            account,
+            owner,
+            perp_market,
+            side: Side::Bid,
+            order_type: PerpScheduledOrderType::Twap,
+            max_base_lots: 2,
+            child_base_lots: 1,
+            price_lots: price_lots / 2,
+            interval_seconds: 60,
+            ..PerpScheduledOrderCreateInstruction::default()
+        },
+    )
+    .await
+    .unwrap();
+    let caller_balance = account_position(solana, caller, tokens[0].bank).await;
+    assert_mango_error(
+        &send_tx(solana, trigger.clone()).await,
+        MangoError::PerpScheduledOrderNotTriggerable.into(),
+        "no fill".to_string(),
+    );
+    assert_eq!(
+        account_position(solana, caller, tokens[0].bank).await,
+        caller_balance
+    );
+
+    let account_data = get_mango_account(solana, account).await;
+    let scheduled = *account_data.perp_scheduled_order_by_index(0).unwrap();
+    send_tx(
+        solana,
+        PerpScheduledOrderCancelInstruction {
+            account,
+            owner,
+            perp_market,
+            index: 0,
+            id: scheduled.id,
+        },
+    )
+    .await
+    .unwrap();
+
+    //
+    // TEST: Cancelling an iceberg also cancels its resting child order
+    //
+    send_tx(
+        solana,
+        PerpScheduledOrderCreateInstruction {
+            account,
+            owner,
+            perp_market,
+            side: Side::Ask,
+            order_type: PerpScheduledOrderType::Iceberg,
+            max_base_lots: 5,
+            child_base_lots: 1,
+            price_lots: price_lots * 2,
+            ..PerpScheduledOrderCreateInstruction::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let account_data = get_mango_account(solana, account).await;
+    let scheduled = *account_data.perp_scheduled_order_by_index(0).unwrap();
+    assert_eq!(scheduled.id, 2);
+
+    send_tx(solana, trigger.clone()).await.unwrap();
+    let account_data = get_mango_account(solana, account).await;
+    assert_eq!(account_data.perp_position(0).unwrap().asks_base_lots, 1);
+    assert_eq!(
+        account_position(solana, caller, tokens[0].bank).await,
+        caller_balance + PERP_SCHEDULED_ORDER_INCENTIVE as i64
+    );
+
+    send_tx(
+        solana,
+        PerpScheduledOrderCancelInstruction {
+            account,
+            owner,
+            perp_market,
+            index: 0,
+            id: scheduled.id,
+        },
+    )
+    .await
+    .unwrap();
+
+    let account_data = get_mango_account(solana, account).await;
+    assert_eq!(account_data.active_perp_scheduled_orders().count(), 0);
+    assert_eq!(account_data.perp_position(0).unwrap().asks_base_lots, 0);
+    assert!(account_data.all_perp_orders().all(|oo| !oo.is_active()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_perp_scheduled_order_iceberg_client_order_id() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let solana = &context.solana.clone();
+
+    let admin = TestKeypair::new();
+    let owner = context.users[0].key;
+    let payer = context.users[1].key;
+    let mints = &context.mints[0..2];
+
+    //
+    // SETUP: Create a group, a perp market and accounts
+    //
+
+    let GroupWithTokens { group, tokens, .. } = GroupWithTokensConfig {
+        admin,
+        payer,
+        mints: mints.to_vec(),
+        ..GroupWithTokensConfig::default()
+    }
+    .create(solana)
+    .await;
+
+    let account = create_funded_account(
+        &solana,
+        group,
+        owner,
+        0,
+        &context.users[1],
+        mints,
+        100_000,
+        0,
+    )
+    .await;
+    let caller =
+        create_funded_account(&solana, group, owner, 1, &context.users[1], &[], 0, 0).await;
+
+    send_tx(
+        solana,
+        AccountExpandInstruction {
+            account_num: 0,
+            token_count: 8,
+            serum3_count: 4,
+            perp_count: 4,
+            perp_oo_count: 16,
+            token_conditional_swap_count: 1,
+            perp_scheduled_order_count: 2,
+            group,
+            owner,
+            payer,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let mango_v4::accounts::PerpCreateMarket { perp_market, .. } = send_tx(
+        solana,
+        PerpCreateMarketInstruction {
+            group,
+            admin,
+            payer,
+            perp_market_index: 0,
+            quote_lot_size: 10,
+            base_lot_size: 100,
+            maint_base_asset_weight: 0.975,
+            init_base_asset_weight: 0.95,
+            maint_base_liab_weight: 1.025,
+            init_base_liab_weight: 1.05,
+            base_liquidation_fee: 0.012,
+            maker_fee: 0.0,
+            taker_fee: 0.0,
+            ..PerpCreateMarketInstruction::with_new_book_and_queue(&solana, &tokens[0]).await
+        },
+    )
+    .await
+    .unwrap();
+
+    let price_lots = {
+        let perp_market = solana.get_account::<PerpMarket>(perp_market).await;
+        perp_market.native_price_to_lot(I80F48::ONE)
+    };
+
+    //
+    // SETUP: The user has an order with client order id 0, the same as the iceberg's id
+    //
+    send_tx(
+        solana,
+        PerpPlaceOrderInstruction {
+            account,
+            perp_market,
+            owner,
+            side: Side::Ask,
+            price_lots: price_lots * 3,
+            max_base_lots: 2,
+            client_order_id: 0,
+            ..PerpPlaceOrderInstruction::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    send_tx(
+        solana,
+        PerpScheduledOrderCreateInstruction {
+            account,
+            owner,
+            perp_market,
+            side: Side::Ask,
+            order_type: PerpScheduledOrderType::Iceberg,
+            max_base_lots: 5,
+            child_base_lots: 1,
+            price_lots: price_lots * 2,
+            ..PerpScheduledOrderCreateInstruction::default()
+        },
+    )
+    .await
+    .unwrap();
+    let account_data = get_mango_account(solana, account).await;
+    let scheduled = *account_data.perp_scheduled_order_by_index(0).unwrap();
+    assert_eq!(scheduled.id, 0);
+
+    //
+    // TEST: The user's order doesn't block the iceberg's child order
+    //
+    let trigger = PerpScheduledOrderTriggerInstruction {
+        account,
+        caller,
+        caller_owner: owner,
+        index: 0,
+    };
+    send_tx(solana, trigger.clone()).await.unwrap();
+    let account_data = get_mango_account(solana, account).await;
+    assert_eq!(account_data.perp_position(0).unwrap().asks_base_lots, 3);
+
+    //
+    // TEST: The child order does block the next one while it rests on the book
+    //
+    solana.advance_clock().await;
+    assert_mango_error(
+        &send_tx(solana, trigger.clone()).await,
+        MangoError::PerpScheduledOrderNotTriggerable.into(),
+        "child on the book".to_string(),
+    );
+
+    //
+    // TEST: Cancelling the iceberg leaves the user's order alone
+    //
+    send_tx(
+        solana,
+        PerpScheduledOrderCancelInstruction {
+            account,
+            owner,
+            perp_market,
+            index: 0,
+            id: scheduled.id,
+        },
+    )
+    .await
+    .unwrap();
+
+    let account_data = get_mango_account(solana, account).await;
+    assert_eq!(account_data.perp_position(0).unwrap().asks_base_lots, 2);
+    let user_order = account_data
+        .perp_find_order_with_client_order_id(0, 0)
+        .unwrap()
+        .1;
+    assert_eq!(user_order.quantity, 2);
+    assert_eq!(
+        account_data
+            .all_perp_orders()
+            .filter(|oo| oo.is_active())
+            .count(),
+        1
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_perp_conditional_order_stop_loss() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let solana = &context.solana.clone();
+
+    let admin = TestKeypair::new();
+    let owner = context.users[0].key;
+    let payer = context.users[1].key;
+    let mints = &context.mints[0..2];
+
+    //
+    // SETUP: Create a group, a perp market and accounts
+    //
+
+    let GroupWithTokens { group, tokens, .. } = GroupWithTokensConfig {
+        admin,
+        payer,
+        mints: mints.to_vec(),
+        ..GroupWithTokensConfig::default()
+    }
+    .create(solana)
+    .await;
+
+    let deposit_amount = 100_000;
+    let maker = create_funded_account(
+        &solana,
+        group,
+        owner,
+        0,
+        &context.users[1],
+        mints,
+        deposit_amount,
+        0,
+    )
+    .await;
+    let account = create_funded_account(
+        &solana,
+        group,
+        owner,
+        1,
+        &context.users[1],
+        mints,
+        deposit_amount,
+        0,
+    )
+    .await;
+    let caller =
+        create_funded_account(&solana, group, owner, 2, &context.users[1], &[], 0, 0).await;
+
+    send_tx(
+        solana,
+        AccountExpandInstruction {
+            account_num: 1,
+            token_count: 8,
+            serum3_count: 4,
+            perp_count: 4,
+            perp_oo_count: 16,
+            token_conditional_swap_count: 1,
+            perp_scheduled_order_count: 1,
+            group,
+            owner,
+            payer,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let mango_v4::accounts::PerpCreateMarket { perp_market, .. } = send_tx(
+        solana,
+        PerpCreateMarketInstruction {
+            group,
+            admin,
+            payer,
+            perp_market_index: 0,
+            quote_lot_size: 10,
+            base_lot_size: 100,
+            maint_base_asset_weight: 0.975,
+            init_base_asset_weight: 0.95,
+            maint_base_liab_weight: 1.025,
+            init_base_liab_weight: 1.05,
+            base_liquidation_fee: 0.012,
+            maker_fee: 0.0,
+            taker_fee: 0.0,
+            ..PerpCreateMarketInstruction::with_new_book_and_queue(&solana, &tokens[1]).await
+        },
+    )
+    .await
+    .unwrap();
+
+    let price_lots = |price: f64| async move {
+        let perp_market = solana.get_account::<PerpMarket>(perp_market).await;
+        perp_market.native_price_to_lot(I80F48::from_num(price))
+    };
+
+    // Open a long position of 2 lots
+    send_tx(
+        solana,
+        PerpPlaceOrderInstruction {
+            account: maker,
+            perp_market,
+            owner,
+            side: Side::Ask,
+            price_lots: price_lots(1.0).await,
+            max_base_lots: 2,
+            ..PerpPlaceOrderInstruction::default()
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        PerpPlaceOrderInstruction {
+            account,
+            perp_market,
+            owner,
+            side: Side::Bid,
+            price_lots: price_lots(1.0).await,
+            max_base_lots: 2,
+            ..PerpPlaceOrderInstruction::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    //
+    // TEST: Create a stop loss that closes the long position below 0.9
+    //
+    send_tx(
+        solana,
+        PerpConditionalOrderCreateInstruction {
+            account,
+            owner,
+            perp_market,
+            side: Side::Ask,
+            order_type: PerpScheduledOrderType::StopLoss,
+            trigger_price_lots: price_lots(0.9).await,
+            max_slippage: 0.05,
+            ..PerpConditionalOrderCreateInstruction::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let trigger = PerpScheduledOrderTriggerInstruction {
+        account,
+        caller,
+        caller_owner: owner,
+        index: 0,
+    };
+
+    //
+    // TEST: Can't trigger while the oracle is above the trigger price
+    //
+    assert_mango_error(
+        &send_tx(solana, trigger.clone()).await,
+        MangoError::PerpConditionalOrderPriceNotTriggered.into(),
+        "price not reached".to_string(),
+    );
+
+    //
+    // TEST: Once the oracle drops, the position is closed against the book
+    //
+    set_perp_stub_oracle_price(solana, group, perp_market, &tokens[1], admin, 0.85).await;
+    send_tx(
+        solana,
+        PerpPlaceOrderInstruction {
+            account: maker,
+            perp_market,
+            owner,
+            side: Side::Bid,
+            price_lots: price_lots(0.84).await,
+            max_base_lots: 5,
+            ..PerpPlaceOrderInstruction::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    send_tx(solana, trigger.clone()).await.unwrap();
+
+    let account_data = get_mango_account(solana, account).await;
+    assert_eq!(
+        account_data
+            .perp_position(0)
+            .unwrap()
+            .effective_base_position_lots(),
+        0
+    );
+    assert_eq!(account_data.active_perp_scheduled_orders().count(), 0);
+
+    Ok(())
+}
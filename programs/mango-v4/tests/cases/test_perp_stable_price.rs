@@ -0,0 +1,237 @@
+use super::*;
+
+#[tokio::test]
+async fn test_perp_stable_price_funding_and_settle() -> Result<(), TransportError> {
+    let mut test_builder = TestContextBuilder::new();
+    test_builder.test().set_compute_max_units(90_000); // the divisions in perp_max_settle are costly!
+    let context = test_builder.start_default().await;
+    let solana = &context.solana.clone();
+
+    let admin = TestKeypair::new();
+    let owner = context.users[0].key;
+    let payer = context.users[1].key;
+    let mints = &context.mints[0..=1];
+
+    let initial_token_deposit = 100_000;
+
+    //
+    // SETUP: Create a group, accounts and a perp market
+    //
+
+    let GroupWithTokens { group, tokens, .. } = GroupWithTokensConfig {
+        admin,
+        payer,
+        mints: mints.to_vec(),
+        ..GroupWithTokensConfig::default()
+    }
+    .create(solana)
+    .await;
+
+    let settler =
+        create_funded_account(&solana, group, owner, 251, &context.users[1], &[], 0, 0).await;
+    let settler_owner = owner.clone();
+
+    let account_0 = create_funded_account(
+        &solana,
+        group,
+        owner,
+        0,
+        &context.users[1],
+        &mints[0..1],
+        initial_token_deposit,
+        0,
+    )
+    .await;
+    let account_1 = create_funded_account(
+        &solana,
+        group,
+        owner,
+        1,
+        &context.users[1],
+        &mints[0..1],
+        initial_token_deposit,
+        0,
+    )
+    .await;
+
+    let mango_v4::accounts::PerpCreateMarket { perp_market, .. } = send_tx(
+        solana,
+        PerpCreateMarketInstruction {
+            group,
+            admin,
+            payer,
+            perp_market_index: 0,
+            quote_lot_size: 10,
+            base_lot_size: 100,
+            maint_base_asset_weight: 0.975,
+            init_base_asset_weight: 0.95,
+            maint_base_liab_weight: 1.025,
+            init_base_liab_weight: 1.05,
+            base_liquidation_fee: 0.012,
+            settle_pnl_limit_factor: 0.2,
+            settle_pnl_limit_window_size_ts: 24 * 60 * 60,
+            ..PerpCreateMarketInstruction::with_new_book_and_queue(&solana, &tokens[1]).await
+        },
+    )
+    .await
+    .unwrap();
+
+    send_tx(
+        solana,
+        PerpEdit {
+            group,
+            admin,
+            perp_market,
+            options: mango_v4::instruction::PerpEditMarket {
+                min_funding_opt: Some(-0.05),
+                impact_quantity_opt: Some(1),
+                funding_stable_price_weight_opt: Some(1.0),
+                settle_stable_price_weight_opt: Some(0.5),
+                ..perp_edit_instruction_default()
+            },
+        },
+    )
+    .await
+    .unwrap();
+
+    set_perp_stub_oracle_price(solana, group, perp_market, &tokens[1], admin, 1000.0).await;
+    let price_lots = {
+        let perp_market = solana.get_account::<PerpMarket>(perp_market).await;
+        perp_market.native_price_to_lot(I80F48::from(1000))
+    };
+
+    //
+    // SETUP: Create a position and a book with a mid price of 1000
+    //
+    for (account, side, price_lots, client_order_id) in [
+        (account_0, Side::Bid, price_lots, 0),
+        (account_1, Side::Ask, price_lots, 1),
+        (account_0, Side::Bid, price_lots - 10, 2),
+        (account_1, Side::Ask, price_lots + 10, 3),
+    ] {
+        send_tx(
+            solana,
+            PerpPlaceOrderInstruction {
+                account,
+                perp_market,
+                owner,
+                side,
+                price_lots,
+                max_base_lots: 1,
+                client_order_id,
+                ..PerpPlaceOrderInstruction::default()
+            },
+        )
+        .await
+        .unwrap();
+    }
+    send_tx(
+        solana,
+        PerpConsumeEventsInstruction {
+            perp_market,
+            mango_accounts: vec![account_0, account_1],
+        },
+    )
+    .await
+    .unwrap();
+
+    let mango_account_0 = solana.get_account::<MangoAccount>(account_0).await;
+    assert_eq!(mango_account_0.perps[0].base_position_lots(), 1);
+
+    // Spike the oracle without touching the perp stable price
+    set_bank_stub_oracle_price(solana, group, &tokens[1], admin, 1100.0).await;
+    let update_funding = PerpUpdateFundingInstruction {
+        perp_market,
+        bank: tokens[1].bank,
+        oracle: tokens[1].oracle,
+    };
+
+    //
+    // TEST: Funding against the stable price ignores the spike
+    //
+    let long_funding_before = solana
+        .get_account::<PerpMarket>(perp_market)
+        .await
+        .long_funding;
+    solana.advance_clock().await;
+    send_tx(solana, update_funding.clone()).await.unwrap();
+    let long_funding_after = solana
+        .get_account::<PerpMarket>(perp_market)
+        .await
+        .long_funding;
+    assert_eq!(long_funding_after, long_funding_before);
+
+    //
+    // TEST: Funding against the oracle price follows the spike
+    //
+    send_tx(
+        solana,
+        PerpEdit {
+            group,
+            admin,
+            perp_market,
+            options: mango_v4::instruction::PerpEditMarket {
+                funding_stable_price_weight_opt: Some(0.0),
+                ..perp_edit_instruction_default()
+            },
+        },
+    )
+    .await
+    .unwrap();
+    solana.advance_clock().await;
+    send_tx(solana, update_funding.clone()).await.unwrap();
+    let perp_market_data = solana.get_account::<PerpMarket>(perp_market).await;
+    assert!(perp_market_data.long_funding < long_funding_after);
+
+    //
+    // TEST: Settlement uses the blend of oracle and stable price
+    //
+    let stable_price = perp_market_data.stable_price();
+    assert!(stable_price < 1050);
+    let settle_price = (I80F48::from(1100) + stable_price) / 2;
+    let expected_settle = {
+        let mango_account_0 = solana.get_account::<MangoAccount>(account_0).await;
+        let mango_account_1 = solana.get_account::<MangoAccount>(account_1).await;
+        let pos_0 = &mango_account_0.perps[0];
+        let pos_1 = &mango_account_1.perps[0];
+        let pnl_0 = pos_0
+            .unsettled_pnl(&perp_market_data, settle_price)
+            .unwrap()
+            - pos_0.unsettled_funding(&perp_market_data);
+        let pnl_1 = pos_1
+            .unsettled_pnl(&perp_market_data, settle_price)
+            .unwrap()
+            - pos_1.unsettled_funding(&perp_market_data);
+        pnl_0.min(-pnl_1)
+    };
+    assert!(expected_settle > 0 && expected_settle < 7_500);
+
+    send_tx(
+        solana,
+        PerpSettlePnlInstruction {
+            settler,
+            settler_owner,
+            account_a: account_0,
+            account_b: account_1,
+            perp_market,
+        },
+    )
+    .await
+    .unwrap();
+
+    let bank = solana.get_account::<Bank>(tokens[0].bank).await;
+    let mango_account_0 = solana.get_account::<MangoAccount>(account_0).await;
+    let mango_account_1 = solana.get_account::<MangoAccount>(account_1).await;
+    assert_eq_fixed_f64!(
+        mango_account_0.tokens[0].native(&bank),
+        initial_token_deposit as f64 + expected_settle.to_num::<f64>(),
+        0.01
+    );
+    assert_eq_fixed_f64!(
+        mango_account_1.tokens[0].native(&bank),
+        initial_token_deposit as f64 - expected_settle.to_num::<f64>(),
+        0.01
+    );
+
+    Ok(())
+}
@@ -0,0 +1,225 @@
+use super::*;
+
+#[tokio::test]
+async fn test_portfolio_margin() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let solana = &context.solana.clone();
+
+    let admin = TestKeypair::new();
+    let owner = context.users[0].key;
+    let payer = context.users[1].key;
+    let mints = &context.mints[0..2];
+
+    //
+    // SETUP: Create a group, a perp market on token 1 and accounts
+    //
+
+    let GroupWithTokens { group, tokens, .. } = GroupWithTokensConfig {
+        admin,
+        payer,
+        mints: mints.to_vec(),
+        ..GroupWithTokensConfig::default()
+    }
+    .create(solana)
+    .await;
+
+    let deposit_amount = 100_000;
+    let maker = create_funded_account(
+        &solana,
+        group,
+        owner,
+        0,
+        &context.users[1],
+        mints,
+        deposit_amount,
+        0,
+    )
+    .await;
+    let account = create_funded_account(
+        &solana,
+        group,
+        owner,
+        1,
+        &context.users[1],
+        mints,
+        deposit_amount,
+        0,
+    )
+    .await;
+
+    let mango_v4::accounts::PerpCreateMarket { perp_market, .. } = send_tx(
+        solana,
+        PerpCreateMarketInstruction {
+            group,
+            admin,
+            payer,
+            perp_market_index: 0,
+            quote_lot_size: 10,
+            base_lot_size: 100,
+            maint_base_asset_weight: 0.975,
+            init_base_asset_weight: 0.95,
+            maint_base_liab_weight: 1.025,
+            init_base_liab_weight: 1.05,
+            base_liquidation_fee: 0.012,
+            maker_fee: 0.0,
+            taker_fee: 0.0,
+            ..PerpCreateMarketInstruction::with_new_book_and_queue(&solana, &tokens[1]).await
+        },
+    )
+    .await
+    .unwrap();
+
+    //
+    // TEST: The settle token can't be the portfolio margin token
+    //
+    let res = send_tx(
+        solana,
+        GroupEditPortfolioMarginPairInstruction {
+            group,
+            admin,
+            perp_market,
+            token_index: Some(tokens[0].index),
+        },
+    )
+    .await;
+    assert!(res.is_err());
+
+    send_tx(
+        solana,
+        GroupEditPortfolioMarginPairInstruction {
+            group,
+            admin,
+            perp_market,
+            token_index: Some(tokens[1].index),
+        },
+    )
+    .await
+    .unwrap();
+    let group_data = solana.get_account::<Group>(group).await;
+    assert_eq!(
+        group_data.portfolio_margin_token_index(0),
+        Some(tokens[1].index)
+    );
+    let perp_market_data = solana.get_account::<PerpMarket>(perp_market).await;
+    assert_eq!(
+        perp_market_data.portfolio_margin_token_index(),
+        Some(tokens[1].index)
+    );
+
+    //
+    // SETUP: Short the perp against the account's token 1 deposit
+    //
+    let price_lots = {
+        let perp_market = solana.get_account::<PerpMarket>(perp_market).await;
+        perp_market.native_price_to_lot(I80F48::ONE)
+    };
+    send_tx(
+        solana,
+        PerpPlaceOrderInstruction {
+            account: maker,
+            perp_market,
+            owner,
+            side: Side::Bid,
+            price_lots,
+            max_base_lots: 5,
+            ..PerpPlaceOrderInstruction::default()
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        PerpPlaceOrderInstruction {
+            account,
+            perp_market,
+            owner,
+            side: Side::Ask,
+            price_lots,
+            max_base_lots: 5,
+            ..PerpPlaceOrderInstruction::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let regular_health = account_init_health(solana, account).await;
+
+    //
+    // TEST: Opting in adds health for the hedged 500 native tokens
+    //
+    send_tx(
+        solana,
+        AccountSetPortfolioMarginInstruction {
+            account,
+            owner,
+            enabled: true,
+        },
+    )
+    .await
+    .unwrap();
+    assert!(get_mango_account(solana, account)
+        .await
+        .fixed
+        .is_portfolio_margin());
+
+    let portfolio_health = account_init_health(solana, account).await;
+    // at least the perp liab weight haircut of the hedged lots is credited
+    assert!(portfolio_health >= regular_health + 500.0 * 0.05 - 0.1);
+
+    //
+    // TEST: Opting out restores the regular health
+    //
+    send_tx(
+        solana,
+        AccountSetPortfolioMarginInstruction {
+            account,
+            owner,
+            enabled: false,
+        },
+    )
+    .await
+    .unwrap();
+    assert!(!get_mango_account(solana, account)
+        .await
+        .fixed
+        .is_portfolio_margin());
+    assert_eq_f64!(
+        account_init_health(solana, account).await,
+        regular_health,
+        0.1
+    );
+
+    //
+    // TEST: Removing the pair from the group also removes the credit
+    //
+    send_tx(
+        solana,
+        AccountSetPortfolioMarginInstruction {
+            account,
+            owner,
+            enabled: true,
+        },
+    )
+    .await
+    .unwrap();
+    send_tx(
+        solana,
+        GroupEditPortfolioMarginPairInstruction {
+            group,
+            admin,
+            perp_market,
+            token_index: None,
+        },
+    )
+    .await
+    .unwrap();
+    let group_data = solana.get_account::<Group>(group).await;
+    assert_eq!(group_data.portfolio_margin_token_index(0), None);
+    assert_eq_f64!(
+        account_init_health(solana, account).await,
+        regular_health,
+        0.1
+    );
+
+    Ok(())
+}
@@ -578,3 +578,163 @@ async fn test_perp_reduce_only() -> Result<(), TransportError> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_circuit_breaker_reduce_only() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let solana = &context.solana.clone();
+
+    let admin = TestKeypair::new();
+    let owner = context.users[0].key;
+    let payer = context.users[1].key;
+    let mints = &context.mints[0..=1];
+    let payer_mint_accounts = &context.users[1].token_accounts[0..=1];
+
+    //
+    // SETUP: Create a group and an account
+    //
+
+    let GroupWithTokens { group, tokens, .. } = GroupWithTokensConfig {
+        admin,
+        payer,
+        mints: mints.to_vec(),
+        ..GroupWithTokensConfig::default()
+    }
+    .create(solana)
+    .await;
+
+    let account = create_funded_account(
+        &solana,
+        group,
+        owner,
+        0,
+        &context.users[1],
+        mints,
+        10_000,
+        0,
+    )
+    .await;
+
+    send_tx(
+        solana,
+        TokenEdit {
+            group,
+            admin,
+            mint: mints[0].pubkey,
+            fallback_oracle: Pubkey::default(),
+            options: mango_v4::instruction::TokenEdit {
+                circuit_breaker_max_price_move_opt: Some(0.2),
+                circuit_breaker_cooldown_seconds_opt: Some(600),
+                ..token_edit_instruction_default()
+            },
+        },
+    )
+    .await
+    .unwrap();
+
+    let deposit = TokenDepositInstruction {
+        amount: 10,
+        reduce_only: false,
+        account,
+        owner,
+        token_account: payer_mint_accounts[0],
+        token_authority: payer,
+        bank_index: 0,
+    };
+    let update_index = TokenUpdateIndexAndRateInstruction {
+        mint_info: tokens[0].mint_info,
+    };
+
+    //
+    // TEST: Small moves don't trip the circuit breaker
+    //
+    set_bank_stub_oracle_price(solana, group, &tokens[0], admin, 1.1).await;
+    solana.advance_clock().await;
+    send_tx(solana, update_index.clone()).await.unwrap();
+    let bank = solana.get_account::<Bank>(tokens[0].bank).await;
+    assert!(!bank.is_circuit_breaker_tripped());
+    send_tx(solana, deposit.clone()).await.unwrap();
+
+    // both deposits count as collateral
+    assert!(account_init_health(solana, account).await > 6000.0 + 0.6 * 10_000.0);
+
+    //
+    // TEST: A large move makes borrows reduce-only, deposits and withdraws still work
+    //
+    set_bank_stub_oracle_price(solana, group, &tokens[0], admin, 1.5).await;
+    solana.advance_clock().await;
+    send_tx(solana, update_index.clone()).await.unwrap();
+    let bank = solana.get_account::<Bank>(tokens[0].bank).await;
+    assert!(bank.is_circuit_breaker_tripped());
+    assert!(!bank.are_deposits_reduce_only());
+    assert!(bank.are_borrows_reduce_only());
+    send_tx(solana, deposit.clone()).await.unwrap();
+
+    // the tripped bank's deposits don't count as collateral: only token 1 is left
+    assert_eq_f64!(account_init_health(solana, account).await, 6000.0, 0.1);
+
+    let withdraw = |amount| TokenWithdrawInstruction {
+        amount,
+        allow_borrow: true,
+        account,
+        owner,
+        token_account: payer_mint_accounts[0],
+        bank_index: 0,
+    };
+    send_tx(solana, withdraw(10)).await.unwrap();
+    assert_mango_error(
+        &send_tx(solana, withdraw(100_000)).await,
+        MangoError::TokenInReduceOnlyMode.into(),
+        "no borrows while the circuit breaker is tripped".to_string(),
+    );
+
+    // the circuit breaker stays tripped during the cooldown, even if the price recovers
+    set_bank_stub_oracle_price(solana, group, &tokens[0], admin, 1.0).await;
+    solana.advance_clock().await;
+    send_tx(solana, update_index.clone()).await.unwrap();
+    let bank = solana.get_account::<Bank>(tokens[0].bank).await;
+    assert!(bank.is_circuit_breaker_tripped());
+
+    //
+    // TEST: After the cooldown the circuit breaker is released
+    //
+    solana
+        .advance_clock_to(bank.circuit_breaker_tripped_until as i64)
+        .await;
+    send_tx(solana, update_index.clone()).await.unwrap();
+    let bank = solana.get_account::<Bank>(tokens[0].bank).await;
+    assert!(!bank.is_circuit_breaker_tripped());
+    send_tx(solana, deposit.clone()).await.unwrap();
+
+    // deposits count as collateral again
+    assert!(account_init_health(solana, account).await > 6000.0 + 0.6 * 10_000.0);
+
+    //
+    // TEST: The admin can reset a tripped circuit breaker
+    //
+    set_bank_stub_oracle_price(solana, group, &tokens[0], admin, 0.5).await;
+    solana.advance_clock().await;
+    send_tx(solana, update_index.clone()).await.unwrap();
+    let bank = solana.get_account::<Bank>(tokens[0].bank).await;
+    assert!(bank.is_circuit_breaker_tripped());
+
+    send_tx(
+        solana,
+        TokenEdit {
+            group,
+            admin,
+            mint: mints[0].pubkey,
+            fallback_oracle: Pubkey::default(),
+            options: mango_v4::instruction::TokenEdit {
+                reset_circuit_breaker: true,
+                ..token_edit_instruction_default()
+            },
+        },
+    )
+    .await
+    .unwrap();
+    let bank = solana.get_account::<Bank>(tokens[0].bank).await;
+    assert!(!bank.is_circuit_breaker_tripped());
+
+    Ok(())
+}
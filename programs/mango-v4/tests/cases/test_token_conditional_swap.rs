@@ -117,6 +117,7 @@ async fn test_token_conditional_swap_basic() -> Result<(), TransportError> {
             group,
             owner,
             payer,
+            ..Default::default()
         },
     )
     .await
@@ -411,6 +412,7 @@ async fn test_token_conditional_swap_linear_auction() -> Result<(), TransportErr
             group,
             owner,
             payer,
+            ..Default::default()
         },
     )
     .await
@@ -644,6 +646,7 @@ async fn test_token_conditional_swap_premium_auction() -> Result<(), TransportEr
             group,
             owner,
             payer,
+            ..Default::default()
         },
     )
     .await
@@ -1076,3 +1079,146 @@ async fn test_token_conditional_swap_deposit_limit() -> Result<(), TransportErro
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_token_conditional_swap_borrow_limit() -> Result<(), TransportError> {
+    let context = TestContext::new().await;
+    let solana = &context.solana.clone();
+
+    let admin = TestKeypair::new();
+    let owner = context.users[0].key;
+    let payer = context.users[1].key;
+    let mints = &context.mints[0..2];
+
+    //
+    // SETUP: Create a group, account, tokens
+    //
+
+    let mango_setup::GroupWithTokens { group, tokens, .. } = mango_setup::GroupWithTokensConfig {
+        admin,
+        payer,
+        mints: mints.to_vec(),
+        ..mango_setup::GroupWithTokensConfig::default()
+    }
+    .create(solana)
+    .await;
+    let quote_token = &tokens[0];
+    let base_token = &tokens[1];
+
+    let deposit_amount = 1_000f64;
+    let account = create_funded_account(
+        &solana,
+        group,
+        owner,
+        0,
+        &context.users[1],
+        &mints[..1],
+        deposit_amount as u64,
+        0,
+    )
+    .await;
+    let liqor = create_funded_account(
+        &solana,
+        group,
+        owner,
+        1,
+        &context.users[1],
+        mints,
+        deposit_amount as u64,
+        0,
+    )
+    .await;
+    // has no quote, so paying the buy token creates a borrow
+    let liqor_without_quote = create_funded_account(
+        &solana,
+        group,
+        owner,
+        2,
+        &context.users[1],
+        &mints[1..],
+        deposit_amount as u64,
+        0,
+    )
+    .await;
+
+    let set_borrow_limit = |mint: Pubkey, borrow_limit: u64| TokenEdit {
+        group,
+        admin,
+        mint,
+        fallback_oracle: Pubkey::default(),
+        options: mango_v4::instruction::TokenEdit {
+            borrow_limit_opt: Some(borrow_limit),
+            ..token_edit_instruction_default()
+        },
+    };
+
+    //
+    // SETUP: Sell base token from "account", borrowing it
+    //
+    send_tx(
+        solana,
+        TokenConditionalSwapCreateInstruction {
+            account,
+            owner,
+            buy_mint: quote_token.mint.pubkey,
+            sell_mint: base_token.mint.pubkey,
+            max_buy: 900,
+            max_sell: 900,
+            price_lower_limit: 0.0,
+            price_upper_limit: 10.0,
+            price_premium_rate: 0.01,
+            allow_creating_deposits: true,
+            allow_creating_borrows: true,
+        },
+    )
+    .await
+    .unwrap();
+
+    let trigger = |liqor, max_buy_token_to_liqee, max_sell_token_to_liqor| {
+        TokenConditionalSwapTriggerInstruction {
+            liqee: account,
+            liqor,
+            liqor_owner: owner,
+            index: 0,
+            max_buy_token_to_liqee,
+            max_sell_token_to_liqor,
+            min_buy_token: 1,
+            min_taker_price: 0.0,
+        }
+    };
+
+    //
+    // TEST: The liqor can't borrow the buy token beyond its borrow limit
+    //
+    send_tx(solana, set_borrow_limit(quote_token.mint.pubkey, 100))
+        .await
+        .unwrap();
+
+    send_tx_expect_error!(
+        solana,
+        trigger(liqor_without_quote, 150, 100000),
+        MangoError::BankBorrowLimit,
+    );
+    send_tx(solana, trigger(liqor_without_quote, 50, 100000))
+        .await
+        .unwrap();
+
+    //
+    // TEST: The liqee can't borrow the sell token beyond its borrow limit
+    //
+    send_tx(solana, set_borrow_limit(base_token.mint.pubkey, 500))
+        .await
+        .unwrap();
+
+    send_tx_expect_error!(
+        solana,
+        trigger(liqor, 100000, 500),
+        MangoError::BankBorrowLimit,
+    );
+    send_tx(solana, trigger(liqor, 100000, 400)).await.unwrap();
+
+    let base_bank = solana.get_account::<Bank>(base_token.bank).await;
+    assert!(base_bank.native_borrows() <= 500);
+
+    Ok(())
+}
@@ -7,7 +7,8 @@ use anchor_spl::token::{Token, TokenAccount};
 use fixed::types::I80F48;
 use itertools::Itertools;
 use mango_v4::accounts_ix::{
-    HealthCheckKind, InterestRateParams, Serum3OrderType, Serum3SelfTradeBehavior, Serum3Side,
+    HealthCheckKind, InterestRateParams, PerpBatchOrder, Serum3OrderType, Serum3SelfTradeBehavior,
+    Serum3Side,
 };
 use mango_v4::state::{MangoAccount, MangoAccountValue};
 use solana_program::instruction::Instruction;
@@ -310,6 +311,7 @@ async fn derive_health_check_remaining_account_metas(
     }
 
     let serum_oos = account.active_serum3_orders().map(|&s| s.open_orders);
+    let openbook_oos = account.active_openbook_v2_orders().map(|&s| s.open_orders);
 
     let to_account_meta = |pubkey| AccountMeta {
         pubkey,
@@ -328,6 +330,7 @@ async fn derive_health_check_remaining_account_metas(
         .chain(perp_markets.map(to_account_meta))
         .chain(perp_oracles.into_iter().map(to_account_meta))
         .chain(serum_oos.map(to_account_meta))
+        .chain(openbook_oos.map(to_account_meta))
         .collect()
 }
 
@@ -376,6 +379,10 @@ async fn derive_liquidation_remaining_account_metas(
         .active_serum3_orders()
         .chain(liqor.active_serum3_orders())
         .map(|&s| s.open_orders);
+    let openbook_oos = liqee
+        .active_openbook_v2_orders()
+        .chain(liqor.active_openbook_v2_orders())
+        .map(|&s| s.open_orders);
 
     let to_account_meta = |pubkey| AccountMeta {
         pubkey,
@@ -394,6 +401,7 @@ async fn derive_liquidation_remaining_account_metas(
         .chain(perp_markets.into_iter().map(to_account_meta))
         .chain(perp_oracles.into_iter().map(to_account_meta))
         .chain(serum_oos.map(to_account_meta))
+        .chain(openbook_oos.map(to_account_meta))
         .collect()
 }
 
@@ -848,6 +856,72 @@ impl ClientInstruction for TokenWithdrawInstruction {
     }
 }
 
+pub struct TokenTransferInstruction {
+    pub amount: u64,
+
+    pub source: Pubkey,
+    pub destination: Pubkey,
+    pub owner: TestKeypair,
+    pub mint: Pubkey,
+    pub bank_index: usize,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for TokenTransferInstruction {
+    type Accounts = mango_v4::accounts::TokenTransfer;
+    type Instruction = mango_v4::instruction::TokenTransfer;
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+        let instruction = Self::Instruction {
+            amount: self.amount,
+        };
+
+        let source = account_loader
+            .load_mango_account(&self.source)
+            .await
+            .unwrap();
+        let mint_info = Pubkey::find_program_address(
+            &[
+                b"MintInfo".as_ref(),
+                source.fixed.group.as_ref(),
+                self.mint.as_ref(),
+            ],
+            &program_id,
+        )
+        .0;
+        let mint_info: MintInfo = account_loader.load(&mint_info).await.unwrap();
+
+        let health_check_metas = derive_health_check_remaining_account_metas(
+            account_loader,
+            &source,
+            Some(mint_info.banks[self.bank_index]),
+            false,
+            None,
+        )
+        .await;
+
+        let accounts = Self::Accounts {
+            group: source.fixed.group,
+            source: self.source,
+            destination: self.destination,
+            owner: self.owner.pubkey(),
+            bank: mint_info.banks[self.bank_index],
+            oracle: mint_info.oracle,
+        };
+
+        let mut instruction = make_instruction(program_id, &accounts, &instruction);
+        instruction.accounts.extend(health_check_metas.into_iter());
+
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.owner]
+    }
+}
+
 #[derive(Clone)]
 pub struct TokenDepositInstruction {
     pub amount: u64,
@@ -1038,6 +1112,9 @@ impl ClientInstruction for TokenRegisterInstruction {
             oracle_config: OracleConfigParams {
                 conf_filter: 0.1,
                 max_staleness_slots: None,
+                max_staleness_seconds: None,
+                risk_increasing_max_staleness_slots: None,
+                risk_increasing_max_staleness_seconds: None,
             },
             interest_rate_params: InterestRateParams {
                 adjustment_factor: self.adjustment_factor,
@@ -1326,6 +1403,17 @@ pub fn token_edit_instruction_default() -> mango_v4::instruction::TokenEdit {
         collateral_fee_per_day_opt: None,
         force_withdraw_opt: None,
         tier_opt: None,
+        circuit_breaker_max_price_move_opt: None,
+        circuit_breaker_cooldown_seconds_opt: None,
+        reset_circuit_breaker: false,
+        interest_rate_model_opt: None,
+        interest_controller_kp_opt: None,
+        interest_controller_ki_opt: None,
+        interest_controller_max_daily_change_opt: None,
+        borrow_limit_opt: None,
+        isolated_collateral_opt: None,
+        isolated_borrow_token_indexes_opt: None,
+        isolated_debt_ceiling_quote_opt: None,
     }
 }
 
@@ -1738,6 +1826,154 @@ impl ClientInstruction for StubOracleCreate {
     }
 }
 
+pub struct OracleAggregatorCreateInstruction {
+    pub oracle: TestKeypair,
+    pub admin: TestKeypair,
+    pub payer: TestKeypair,
+    pub group: Pubkey,
+    pub mint: Pubkey,
+    pub source_oracles: Vec<Pubkey>,
+    pub min_quorum: u8,
+    pub max_divergence: f64,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for OracleAggregatorCreateInstruction {
+    type Accounts = mango_v4::accounts::OracleAggregatorCreate;
+    type Instruction = mango_v4::instruction::OracleAggregatorCreate;
+
+    async fn to_instruction(
+        &self,
+        _loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, Instruction) {
+        let program_id = mango_v4::id();
+        let instruction = Self::Instruction {
+            min_quorum: self.min_quorum,
+            max_divergence: self.max_divergence,
+            source_oracle_config: OracleConfigParams {
+                conf_filter: 0.1,
+                max_staleness_slots: None,
+                max_staleness_seconds: None,
+                risk_increasing_max_staleness_slots: None,
+                risk_increasing_max_staleness_seconds: None,
+            },
+        };
+
+        let accounts = Self::Accounts {
+            group: self.group,
+            oracle: self.oracle.pubkey(),
+            mint: self.mint,
+            admin: self.admin.pubkey(),
+            payer: self.payer.pubkey(),
+            system_program: System::id(),
+        };
+
+        let mut instruction = make_instruction(program_id, &accounts, &instruction);
+        instruction
+            .accounts
+            .extend(self.source_oracles.iter().map(|&pubkey| AccountMeta {
+                pubkey,
+                is_writable: false,
+                is_signer: false,
+            }));
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.payer, self.admin, self.oracle]
+    }
+}
+
+pub struct OracleAggregatorEditInstruction {
+    pub oracle: Pubkey,
+    pub group: Pubkey,
+    pub admin: TestKeypair,
+    pub min_quorum_opt: Option<u8>,
+    pub source_oracles_opt: Option<Vec<Pubkey>>,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for OracleAggregatorEditInstruction {
+    type Accounts = mango_v4::accounts::OracleAggregatorEdit;
+    type Instruction = mango_v4::instruction::OracleAggregatorEdit;
+
+    async fn to_instruction(
+        &self,
+        _loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, Instruction) {
+        let program_id = mango_v4::id();
+        let instruction = Self::Instruction {
+            min_quorum_opt: self.min_quorum_opt,
+            max_divergence_opt: None,
+            source_oracle_config_opt: None,
+            replace_source_oracles: self.source_oracles_opt.is_some(),
+        };
+
+        let accounts = Self::Accounts {
+            group: self.group,
+            oracle: self.oracle,
+            admin: self.admin.pubkey(),
+        };
+
+        let mut instruction = make_instruction(program_id, &accounts, &instruction);
+        if let Some(source_oracles) = &self.source_oracles_opt {
+            instruction
+                .accounts
+                .extend(source_oracles.iter().map(|&pubkey| AccountMeta {
+                    pubkey,
+                    is_writable: false,
+                    is_signer: false,
+                }));
+        }
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.admin]
+    }
+}
+
+pub struct OracleAggregatorUpdateInstruction {
+    pub oracle: Pubkey,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for OracleAggregatorUpdateInstruction {
+    type Accounts = mango_v4::accounts::OracleAggregatorUpdate;
+    type Instruction = mango_v4::instruction::OracleAggregatorUpdate;
+
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, Instruction) {
+        let program_id = mango_v4::id();
+        let instruction = Self::Instruction {};
+
+        let aggregator: OracleAggregator = account_loader.load(&self.oracle).await.unwrap();
+
+        let accounts = Self::Accounts {
+            group: aggregator.group,
+            oracle: self.oracle,
+        };
+
+        let mut instruction = make_instruction(program_id, &accounts, &instruction);
+        instruction
+            .accounts
+            .extend(
+                aggregator
+                    .source_oracles()
+                    .iter()
+                    .map(|&pubkey| AccountMeta {
+                        pubkey,
+                        is_writable: false,
+                        is_signer: false,
+                    }),
+            );
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![]
+    }
+}
+
 pub struct StubOracleCloseInstruction {
     pub oracle: Pubkey,
     pub group: Pubkey,
@@ -1916,6 +2152,135 @@ impl ClientInstruction for GroupEdit {
     }
 }
 
+pub struct GroupEditEmodeCategoryInstruction {
+    pub group: Pubkey,
+    pub admin: TestKeypair,
+    pub category: u8,
+    pub token_indexes: Vec<TokenIndex>,
+    pub maint_asset_weight: f32,
+    pub init_asset_weight: f32,
+    pub maint_liab_weight: f32,
+    pub init_liab_weight: f32,
+    /// Banks of all tokens that were or will be in the category
+    pub banks: Vec<Pubkey>,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for GroupEditEmodeCategoryInstruction {
+    type Accounts = mango_v4::accounts::GroupEdit;
+    type Instruction = mango_v4::instruction::GroupEditEmodeCategory;
+    async fn to_instruction(
+        &self,
+        _account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+        let instruction = Self::Instruction {
+            category: self.category,
+            token_indexes: self.token_indexes.clone(),
+            maint_asset_weight: self.maint_asset_weight,
+            init_asset_weight: self.init_asset_weight,
+            maint_liab_weight: self.maint_liab_weight,
+            init_liab_weight: self.init_liab_weight,
+        };
+
+        let accounts = Self::Accounts {
+            group: self.group,
+            admin: self.admin.pubkey(),
+        };
+
+        let mut instruction = make_instruction(program_id, &accounts, &instruction);
+        for bank in self.banks.iter() {
+            instruction.accounts.push(AccountMeta {
+                pubkey: *bank,
+                is_writable: true,
+                is_signer: false,
+            });
+        }
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.admin]
+    }
+}
+
+pub struct GroupEditPortfolioMarginPairInstruction {
+    pub group: Pubkey,
+    pub admin: TestKeypair,
+    pub perp_market: Pubkey,
+    pub token_index: Option<TokenIndex>,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for GroupEditPortfolioMarginPairInstruction {
+    type Accounts = mango_v4::accounts::GroupEdit;
+    type Instruction = mango_v4::instruction::GroupEditPortfolioMarginPair;
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
+        let instruction = Self::Instruction {
+            perp_market_index: perp_market.perp_market_index,
+            token_index_opt: self.token_index,
+        };
+
+        let accounts = Self::Accounts {
+            group: self.group,
+            admin: self.admin.pubkey(),
+        };
+
+        let mut instruction = make_instruction(program_id, &accounts, &instruction);
+        instruction.accounts.push(AccountMeta {
+            pubkey: self.perp_market,
+            is_writable: true,
+            is_signer: false,
+        });
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.admin]
+    }
+}
+
+pub struct GroupEditPerpFeeTierInstruction {
+    pub group: Pubkey,
+    pub admin: TestKeypair,
+    pub tier: u8,
+    pub min_volume_quote: u64,
+    pub maker_fee_discount: f32,
+    pub taker_fee_discount: f32,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for GroupEditPerpFeeTierInstruction {
+    type Accounts = mango_v4::accounts::GroupEdit;
+    type Instruction = mango_v4::instruction::GroupEditPerpFeeTier;
+    async fn to_instruction(
+        &self,
+        _account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+        let instruction = Self::Instruction {
+            tier: self.tier,
+            min_volume_quote: self.min_volume_quote,
+            maker_fee_discount: self.maker_fee_discount,
+            taker_fee_discount: self.taker_fee_discount,
+        };
+
+        let accounts = Self::Accounts {
+            group: self.group,
+            admin: self.admin.pubkey(),
+        };
+
+        let instruction = make_instruction(program_id, &accounts, &instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.admin]
+    }
+}
+
 pub struct GroupChangeInsuranceFund {
     pub group: Pubkey,
     pub admin: TestKeypair,
@@ -2042,6 +2407,9 @@ pub struct AccountCreateInstruction {
     pub perp_count: u8,
     pub perp_oo_count: u8,
     pub token_conditional_swap_count: u8,
+    pub openbook_v2_count: u8,
+    pub fixed_term_count: u8,
+    pub perp_scheduled_order_count: u8,
     pub group: Pubkey,
     pub owner: TestKeypair,
     pub payer: TestKeypair,
@@ -2055,6 +2423,9 @@ impl Default for AccountCreateInstruction {
             perp_count: 4,
             perp_oo_count: 16,
             token_conditional_swap_count: 1,
+            openbook_v2_count: 0,
+            fixed_term_count: 0,
+            perp_scheduled_order_count: 0,
             group: Default::default(),
             owner: Default::default(),
             payer: Default::default(),
@@ -2063,8 +2434,8 @@ impl Default for AccountCreateInstruction {
 }
 #[async_trait::async_trait(?Send)]
 impl ClientInstruction for AccountCreateInstruction {
-    type Accounts = mango_v4::accounts::AccountCreate;
-    type Instruction = mango_v4::instruction::AccountCreateV2;
+    type Accounts = mango_v4::accounts::AccountCreateV4;
+    type Instruction = mango_v4::instruction::AccountCreateV4;
     async fn to_instruction(
         &self,
         _account_loader: &(impl ClientAccountLoader + 'async_trait),
@@ -2077,6 +2448,9 @@ impl ClientInstruction for AccountCreateInstruction {
             perp_count: self.perp_count,
             perp_oo_count: self.perp_oo_count,
             token_conditional_swap_count: self.token_conditional_swap_count,
+            openbook_v2_count: self.openbook_v2_count,
+            fixed_term_count: self.fixed_term_count,
+            perp_scheduled_order_count: self.perp_scheduled_order_count,
             name: "my_mango_account".to_string(),
         };
 
@@ -2119,11 +2493,14 @@ pub struct AccountExpandInstruction {
     pub perp_count: u8,
     pub perp_oo_count: u8,
     pub token_conditional_swap_count: u8,
+    pub openbook_v2_count: u8,
+    pub fixed_term_count: u8,
+    pub perp_scheduled_order_count: u8,
 }
 #[async_trait::async_trait(?Send)]
 impl ClientInstruction for AccountExpandInstruction {
     type Accounts = mango_v4::accounts::AccountExpand;
-    type Instruction = mango_v4::instruction::AccountExpandV2;
+    type Instruction = mango_v4::instruction::AccountExpandV4;
     async fn to_instruction(
         &self,
         _account_loader: &(impl ClientAccountLoader + 'async_trait),
@@ -2135,6 +2512,9 @@ impl ClientInstruction for AccountExpandInstruction {
             perp_count: self.perp_count,
             perp_oo_count: self.perp_oo_count,
             token_conditional_swap_count: self.token_conditional_swap_count,
+            openbook_v2_count: self.openbook_v2_count,
+            fixed_term_count: self.fixed_term_count,
+            perp_scheduled_order_count: self.perp_scheduled_order_count,
         };
 
         let account = Pubkey::find_program_address(
@@ -2251,6 +2631,144 @@ impl ClientInstruction for AccountEditInstruction {
     }
 }
 
+pub struct AccountSetPortfolioMarginInstruction {
+    pub account: Pubkey,
+    pub owner: TestKeypair,
+    pub enabled: bool,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for AccountSetPortfolioMarginInstruction {
+    type Accounts = mango_v4::accounts::AccountSetPortfolioMargin;
+    type Instruction = mango_v4::instruction::AccountSetPortfolioMargin;
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+        let instruction = Self::Instruction {
+            enabled: self.enabled,
+        };
+
+        let account = account_loader
+            .load_mango_account(&self.account)
+            .await
+            .unwrap();
+
+        let health_check_metas = derive_health_check_remaining_account_metas(
+            account_loader,
+            &account,
+            None,
+            false,
+            None,
+        )
+        .await;
+
+        let accounts = Self::Accounts {
+            group: account.fixed.group,
+            account: self.account,
+            owner: self.owner.pubkey(),
+        };
+
+        let mut instruction = make_instruction(program_id, &accounts, &instruction);
+        instruction.accounts.extend(health_check_metas.into_iter());
+
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.owner]
+    }
+}
+
+pub struct AccountSetEmodeCategoryInstruction {
+    pub account: Pubkey,
+    pub owner: TestKeypair,
+    pub category: u8,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for AccountSetEmodeCategoryInstruction {
+    type Accounts = mango_v4::accounts::AccountSetEmodeCategory;
+    type Instruction = mango_v4::instruction::AccountSetEmodeCategory;
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+        let instruction = Self::Instruction {
+            category: self.category,
+        };
+
+        let account = account_loader
+            .load_mango_account(&self.account)
+            .await
+            .unwrap();
+
+        let health_check_metas = derive_health_check_remaining_account_metas(
+            account_loader,
+            &account,
+            None,
+            false,
+            None,
+        )
+        .await;
+
+        let accounts = Self::Accounts {
+            group: account.fixed.group,
+            account: self.account,
+            owner: self.owner.pubkey(),
+        };
+
+        let mut instruction = make_instruction(program_id, &accounts, &instruction);
+        instruction.accounts.extend(health_check_metas.into_iter());
+
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.owner]
+    }
+}
+
+pub struct AccountEditDelegateScopeInstruction {
+    pub account: Pubkey,
+    pub owner: TestKeypair,
+    pub temporary_delegate: bool,
+    pub scope: DelegateScope,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for AccountEditDelegateScopeInstruction {
+    type Accounts = mango_v4::accounts::AccountEditDelegateScope;
+    type Instruction = mango_v4::instruction::AccountEditDelegateScope;
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+        let instruction = Self::Instruction {
+            temporary_delegate: self.temporary_delegate,
+            scope: self.scope,
+        };
+
+        let account = account_loader
+            .load_mango_account(&self.account)
+            .await
+            .unwrap();
+
+        let accounts = Self::Accounts {
+            group: account.fixed.group,
+            account: self.account,
+            owner: self.owner.pubkey(),
+        };
+
+        let instruction = make_instruction(program_id, &accounts, &instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.owner]
+    }
+}
+
 pub struct AccountCloseInstruction {
     pub group: Pubkey,
     pub account: Pubkey,
@@ -3095,171 +3613,336 @@ impl ClientInstruction for Serum3LiqForceCancelOrdersInstruction {
     }
 }
 
-pub struct TokenForceCloseBorrowsWithTokenInstruction {
-    pub liqee: Pubkey,
-    pub liqor: Pubkey,
-    pub liqor_owner: TestKeypair,
+pub struct OpenbookV2RegisterMarketInstruction {
+    pub group: Pubkey,
+    pub admin: TestKeypair,
+    pub payer: TestKeypair,
 
-    pub asset_token_index: TokenIndex,
-    pub asset_bank_index: usize,
-    pub liab_token_index: TokenIndex,
-    pub liab_bank_index: usize,
-    pub max_liab_transfer: u64,
+    pub openbook_v2_program: Pubkey,
+    pub openbook_v2_market_external: Pubkey,
+
+    pub base_bank: Pubkey,
+    pub quote_bank: Pubkey,
+
+    pub market_index: OpenbookV2MarketIndex,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for TokenForceCloseBorrowsWithTokenInstruction {
-    type Accounts = mango_v4::accounts::TokenForceCloseBorrowsWithToken;
-    type Instruction = mango_v4::instruction::TokenForceCloseBorrowsWithToken;
+impl ClientInstruction for OpenbookV2RegisterMarketInstruction {
+    type Accounts = mango_v4::accounts::OpenbookV2RegisterMarket;
+    type Instruction = mango_v4::instruction::OpenbookV2RegisterMarket;
     async fn to_instruction(
         &self,
-        account_loader: &(impl ClientAccountLoader + 'async_trait),
+        _account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
         let instruction = Self::Instruction {
-            asset_token_index: self.asset_token_index,
-            liab_token_index: self.liab_token_index,
-            max_liab_transfer: self.max_liab_transfer,
+            market_index: self.market_index,
+            name: "UUU/usdc".to_string(),
         };
 
-        let liqee = account_loader
-            .load_mango_account(&self.liqee)
-            .await
-            .unwrap();
-        let liqor = account_loader
-            .load_mango_account(&self.liqor)
-            .await
-            .unwrap();
-        let health_check_metas = derive_liquidation_remaining_account_metas(
-            account_loader,
-            &liqee,
-            &liqor,
-            self.asset_token_index,
-            self.asset_bank_index,
-            self.liab_token_index,
-            self.liab_bank_index,
+        let openbook_v2_market = Pubkey::find_program_address(
+            &[
+                b"OpenbookV2Market".as_ref(),
+                self.group.as_ref(),
+                self.openbook_v2_market_external.as_ref(),
+            ],
+            &program_id,
         )
-        .await;
+        .0;
+
+        let index_reservation = Pubkey::find_program_address(
+            &[
+                b"OpenbookV2Index".as_ref(),
+                self.group.as_ref(),
+                &self.market_index.to_le_bytes(),
+            ],
+            &program_id,
+        )
+        .0;
 
         let accounts = Self::Accounts {
-            group: liqee.fixed.group,
-            liqee: self.liqee,
-            liqor: self.liqor,
-            liqor_owner: self.liqor_owner.pubkey(),
+            group: self.group,
+            admin: self.admin.pubkey(),
+            openbook_v2_program: self.openbook_v2_program,
+            openbook_v2_market_external: self.openbook_v2_market_external,
+            openbook_v2_market,
+            index_reservation,
+            base_bank: self.base_bank,
+            quote_bank: self.quote_bank,
+            payer: self.payer.pubkey(),
+            system_program: System::id(),
         };
 
-        let mut instruction = make_instruction(program_id, &accounts, &instruction);
-        instruction.accounts.extend(health_check_metas.into_iter());
-
+        let instruction = make_instruction(program_id, &accounts, &instruction);
         (accounts, instruction)
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.liqor_owner]
+        vec![self.admin, self.payer]
     }
 }
 
-pub struct TokenForceWithdrawInstruction {
-    pub account: Pubkey,
-    pub bank: Pubkey,
-    pub target: Pubkey,
+pub struct OpenbookV2EditMarketInstruction {
+    pub group: Pubkey,
+    pub admin: TestKeypair,
+    pub market: Pubkey,
+    pub reduce_only_opt: Option<bool>,
+    pub force_close_opt: Option<bool>,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for TokenForceWithdrawInstruction {
-    type Accounts = mango_v4::accounts::TokenForceWithdraw;
-    type Instruction = mango_v4::instruction::TokenForceWithdraw;
+impl ClientInstruction for OpenbookV2EditMarketInstruction {
+    type Accounts = mango_v4::accounts::OpenbookV2EditMarket;
+    type Instruction = mango_v4::instruction::OpenbookV2EditMarket;
     async fn to_instruction(
         &self,
-        account_loader: &(impl ClientAccountLoader + 'async_trait),
+        _account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
-        let instruction = Self::Instruction {};
-
-        let account = account_loader
-            .load_mango_account(&self.account)
-            .await
-            .unwrap();
-        let bank = account_loader.load::<Bank>(&self.bank).await.unwrap();
-        let health_check_metas = derive_health_check_remaining_account_metas(
-            account_loader,
-            &account,
-            None,
-            false,
-            None,
-        )
-        .await;
+        let instruction = Self::Instruction {
+            reduce_only_opt: self.reduce_only_opt,
+            force_close_opt: self.force_close_opt,
+        };
 
         let accounts = Self::Accounts {
-            group: account.fixed.group,
-            account: self.account,
-            bank: self.bank,
-            vault: bank.vault,
-            oracle: bank.oracle,
-            owner_ata_token_account: self.target,
-            alternate_owner_token_account: self.target,
-            token_program: Token::id(),
+            group: self.group,
+            admin: self.admin.pubkey(),
+            market: self.market,
         };
 
-        let mut instruction = make_instruction(program_id, &accounts, &instruction);
-        instruction.accounts.extend(health_check_metas.into_iter());
-
+        let instruction = make_instruction(program_id, &accounts, &instruction);
         (accounts, instruction)
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![]
+        vec![self.admin]
     }
 }
 
-pub struct TokenLiqWithTokenInstruction {
-    pub liqee: Pubkey,
-    pub liqor: Pubkey,
-    pub liqor_owner: TestKeypair,
-
-    pub asset_token_index: TokenIndex,
-    pub asset_bank_index: usize,
-    pub liab_token_index: TokenIndex,
-    pub liab_bank_index: usize,
-    pub max_liab_transfer: I80F48,
+pub struct OpenbookV2DeregisterMarketInstruction {
+    pub group: Pubkey,
+    pub admin: TestKeypair,
+    pub openbook_v2_market_external: Pubkey,
+    pub sol_destination: Pubkey,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for TokenLiqWithTokenInstruction {
-    type Accounts = mango_v4::accounts::TokenLiqWithToken;
-    type Instruction = mango_v4::instruction::TokenLiqWithToken;
+impl ClientInstruction for OpenbookV2DeregisterMarketInstruction {
+    type Accounts = mango_v4::accounts::OpenbookV2DeregisterMarket;
+    type Instruction = mango_v4::instruction::OpenbookV2DeregisterMarket;
     async fn to_instruction(
         &self,
         account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
-        let instruction = Self::Instruction {
-            asset_token_index: self.asset_token_index,
-            liab_token_index: self.liab_token_index,
-            max_liab_transfer: self.max_liab_transfer,
+        let instruction = Self::Instruction {};
+
+        let openbook_v2_market = Pubkey::find_program_address(
+            &[
+                b"OpenbookV2Market".as_ref(),
+                self.group.as_ref(),
+                self.openbook_v2_market_external.as_ref(),
+            ],
+            &program_id,
+        )
+        .0;
+        let openbook_v2_market_data: OpenbookV2Market =
+            account_loader.load(&openbook_v2_market).await.unwrap();
+
+        let index_reservation = Pubkey::find_program_address(
+            &[
+                b"OpenbookV2Index".as_ref(),
+                self.group.as_ref(),
+                &openbook_v2_market_data.market_index.to_le_bytes(),
+            ],
+            &program_id,
+        )
+        .0;
+
+        let accounts = Self::Accounts {
+            group: self.group,
+            admin: self.admin.pubkey(),
+            openbook_v2_market,
+            index_reservation,
+            sol_destination: self.sol_destination,
+            token_program: Token::id(),
         };
 
-        let liqee = account_loader
-            .load_mango_account(&self.liqee)
+        let instruction = make_instruction(program_id, &accounts, &instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.admin]
+    }
+}
+
+pub struct OpenbookV2CreateOpenOrdersInstruction {
+    pub account: Pubkey,
+    pub openbook_v2_market: Pubkey,
+    pub owner: TestKeypair,
+    pub payer: TestKeypair,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for OpenbookV2CreateOpenOrdersInstruction {
+    type Accounts = mango_v4::accounts::OpenbookV2CreateOpenOrders;
+    type Instruction = mango_v4::instruction::OpenbookV2CreateOpenOrders;
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+
+        let account: MangoAccount = account_loader.load(&self.account).await.unwrap();
+        let openbook_v2_market: OpenbookV2Market =
+            account_loader.load(&self.openbook_v2_market).await.unwrap();
+        let openbook_v2_program = openbook_v2_market.openbook_v2_program;
+
+        // The group owns all open orders accounts, numbered by the indexer's counter
+        let open_orders_indexer = Pubkey::find_program_address(
+            &[b"OpenOrdersIndexer".as_ref(), account.group.as_ref()],
+            &openbook_v2_program,
+        )
+        .0;
+        let account_num = account_loader
+            .load::<openbook_v2::state::OpenOrdersIndexer>(&open_orders_indexer)
+            .await
+            .map(|indexer| indexer.created_counter)
+            .unwrap_or(0)
+            + 1;
+        let open_orders = Pubkey::find_program_address(
+            &[
+                b"OpenOrders".as_ref(),
+                account.group.as_ref(),
+                &account_num.to_le_bytes(),
+            ],
+            &openbook_v2_program,
+        )
+        .0;
+
+        let instruction = Self::Instruction { account_num };
+
+        let accounts = Self::Accounts {
+            group: account.group,
+            account: self.account,
+            authority: self.owner.pubkey(),
+            openbook_v2_market: self.openbook_v2_market,
+            openbook_v2_program,
+            openbook_v2_market_external: openbook_v2_market.openbook_v2_market_external,
+            open_orders_indexer,
+            open_orders,
+            payer: self.payer.pubkey(),
+            system_program: System::id(),
+            rent: sysvar::rent::Rent::id(),
+        };
+
+        let instruction = make_instruction(program_id, &accounts, &instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.owner, self.payer]
+    }
+}
+
+pub struct OpenbookV2PlaceOrderInstruction {
+    pub side: openbook_v2::state::Side,
+    pub limit_price: u64,
+    pub max_base_qty: u64,
+    pub max_native_quote_qty_including_fees: u64,
+    pub self_trade_behavior: openbook_v2::state::SelfTradeBehavior,
+    pub order_type: openbook_v2::state::PlaceOrderType,
+    pub client_order_id: u64,
+    pub limit: u16,
+
+    pub account: Pubkey,
+    pub owner: TestKeypair,
+
+    pub openbook_v2_market: Pubkey,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for OpenbookV2PlaceOrderInstruction {
+    type Accounts = mango_v4::accounts::OpenbookV2PlaceOrder;
+    type Instruction = mango_v4::instruction::OpenbookV2PlaceOrder;
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+        let instruction = Self::Instruction {
+            side: self.side as u8,
+            limit_price: self.limit_price,
+            max_base_qty: self.max_base_qty,
+            max_native_quote_qty_including_fees: self.max_native_quote_qty_including_fees,
+            self_trade_behavior: self.self_trade_behavior as u8,
+            order_type: self.order_type as u8,
+            client_order_id: self.client_order_id,
+            limit: self.limit,
+        };
+
+        let account = account_loader
+            .load_mango_account(&self.account)
             .await
             .unwrap();
-        let liqor = account_loader
-            .load_mango_account(&self.liqor)
+        let openbook_v2_market: OpenbookV2Market =
+            account_loader.load(&self.openbook_v2_market).await.unwrap();
+        let open_orders = account
+            .openbook_v2_orders(openbook_v2_market.market_index)
+            .unwrap()
+            .open_orders;
+        let quote_info = get_mint_info_by_token_index(
+            account_loader,
+            &account,
+            openbook_v2_market.quote_token_index,
+        )
+        .await;
+        let base_info = get_mint_info_by_token_index(
+            account_loader,
+            &account,
+            openbook_v2_market.base_token_index,
+        )
+        .await;
+
+        let market_external: openbook_v2::state::Market = account_loader
+            .load(&openbook_v2_market.openbook_v2_market_external)
             .await
             .unwrap();
-        let health_check_metas = derive_liquidation_remaining_account_metas(
+
+        let mut health_check_metas = derive_health_check_remaining_account_metas(
             account_loader,
-            &liqee,
-            &liqor,
-            self.asset_token_index,
-            self.asset_bank_index,
-            self.liab_token_index,
-            self.liab_bank_index,
+            &account,
+            None,
+            false,
+            None,
         )
         .await;
 
+        let (payer_info, receiver_info) = &match self.side {
+            openbook_v2::state::Side::Bid => (&quote_info, &base_info),
+            openbook_v2::state::Side::Ask => (&base_info, &quote_info),
+        };
+
+        let receiver_active_index = account
+            .active_token_positions()
+            .position(|tp| tp.token_index == receiver_info.token_index)
+            .unwrap();
+        health_check_metas[receiver_active_index].is_writable = true;
+
         let accounts = Self::Accounts {
-            group: liqee.fixed.group,
-            liqee: self.liqee,
-            liqor: self.liqor,
-            liqor_owner: self.liqor_owner.pubkey(),
+            group: account.fixed.group,
+            account: self.account,
+            authority: self.owner.pubkey(),
+            open_orders,
+            openbook_v2_market: self.openbook_v2_market,
+            openbook_v2_program: openbook_v2_market.openbook_v2_program,
+            openbook_v2_market_external: openbook_v2_market.openbook_v2_market_external,
+            bids: market_external.bids,
+            asks: market_external.asks,
+            event_heap: market_external.event_heap,
+            market_base_vault: market_external.market_base_vault,
+            market_quote_vault: market_external.market_quote_vault,
+            payer_bank: payer_info.first_bank(),
+            payer_vault: payer_info.first_vault(),
+            payer_oracle: payer_info.oracle,
+            token_program: Token::id(),
         };
 
         let mut instruction = make_instruction(program_id, &accounts, &instruction);
@@ -3269,32 +3952,36 @@ impl ClientInstruction for TokenLiqWithTokenInstruction {
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.liqor_owner]
+        vec![self.owner]
     }
 }
 
-pub struct TokenLiqBankruptcyInstruction {
+pub struct TokenForceCloseBorrowsWithTokenInstruction {
     pub liqee: Pubkey,
     pub liqor: Pubkey,
     pub liqor_owner: TestKeypair,
 
-    pub max_liab_transfer: I80F48,
-    pub liab_mint_info: Pubkey,
+    pub asset_token_index: TokenIndex,
+    pub asset_bank_index: usize,
+    pub liab_token_index: TokenIndex,
+    pub liab_bank_index: usize,
+    pub max_liab_transfer: u64,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for TokenLiqBankruptcyInstruction {
-    type Accounts = mango_v4::accounts::TokenLiqBankruptcy;
-    type Instruction = mango_v4::instruction::TokenLiqBankruptcy;
+impl ClientInstruction for TokenForceCloseBorrowsWithTokenInstruction {
+    type Accounts = mango_v4::accounts::TokenForceCloseBorrowsWithToken;
+    type Instruction = mango_v4::instruction::TokenForceCloseBorrowsWithToken;
     async fn to_instruction(
         &self,
         account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
         let instruction = Self::Instruction {
+            asset_token_index: self.asset_token_index,
+            liab_token_index: self.liab_token_index,
             max_liab_transfer: self.max_liab_transfer,
         };
 
-        let liab_mint_info: MintInfo = account_loader.load(&self.liab_mint_info).await.unwrap();
         let liqee = account_loader
             .load_mango_account(&self.liqee)
             .await
@@ -3303,55 +3990,25 @@ impl ClientInstruction for TokenLiqBankruptcyInstruction {
             .load_mango_account(&self.liqor)
             .await
             .unwrap();
-
-        let group_key = liqee.fixed.group;
-        let group: Group = account_loader.load(&group_key).await.unwrap();
-
-        let insurance_mint_info = Pubkey::find_program_address(
-            &[
-                b"MintInfo".as_ref(),
-                liqee.fixed.group.as_ref(),
-                group.insurance_mint.as_ref(),
-            ],
-            &program_id,
-        )
-        .0;
-        let insurance_mint_info: MintInfo =
-            account_loader.load(&insurance_mint_info).await.unwrap();
-
         let health_check_metas = derive_liquidation_remaining_account_metas(
             account_loader,
             &liqee,
             &liqor,
-            insurance_mint_info.token_index,
-            0,
-            liab_mint_info.token_index,
-            0,
+            self.asset_token_index,
+            self.asset_bank_index,
+            self.liab_token_index,
+            self.liab_bank_index,
         )
         .await;
 
         let accounts = Self::Accounts {
-            group: group_key,
+            group: liqee.fixed.group,
             liqee: self.liqee,
             liqor: self.liqor,
             liqor_owner: self.liqor_owner.pubkey(),
-            liab_mint_info: self.liab_mint_info,
-            quote_vault: insurance_mint_info.first_vault(),
-            insurance_vault: group.insurance_vault,
-            token_program: Token::id(),
         };
 
         let mut instruction = make_instruction(program_id, &accounts, &instruction);
-        let mut bank_ams = liab_mint_info
-            .banks()
-            .iter()
-            .map(|bank| AccountMeta {
-                pubkey: *bank,
-                is_signer: false,
-                is_writable: true,
-            })
-            .collect::<Vec<_>>();
-        instruction.accounts.append(&mut bank_ams);
         instruction.accounts.extend(health_check_metas.into_iter());
 
         (accounts, instruction)
@@ -3362,123 +4019,1206 @@ impl ClientInstruction for TokenLiqBankruptcyInstruction {
     }
 }
 
-#[derive(Default)]
-pub struct PerpCreateMarketInstruction {
-    pub group: Pubkey,
-    pub admin: TestKeypair,
-    pub oracle: Pubkey,
-    pub bids: Pubkey,
-    pub asks: Pubkey,
-    pub event_queue: Pubkey,
-    pub payer: TestKeypair,
-    pub settle_token_index: TokenIndex,
-    pub perp_market_index: PerpMarketIndex,
-    pub base_decimals: u8,
-    pub quote_lot_size: i64,
-    pub base_lot_size: i64,
-    pub maint_base_asset_weight: f32,
-    pub init_base_asset_weight: f32,
-    pub maint_base_liab_weight: f32,
-    pub init_base_liab_weight: f32,
-    pub maint_overall_asset_weight: f32,
-    pub init_overall_asset_weight: f32,
-    pub base_liquidation_fee: f32,
-    pub positive_pnl_liquidation_fee: f32,
-    pub maker_fee: f32,
-    pub taker_fee: f32,
-    pub group_insurance_fund: bool,
-    pub fee_penalty: f32,
-    pub settle_fee_flat: f32,
-    pub settle_fee_amount_threshold: f32,
-    pub settle_fee_fraction_low_health: f32,
-    pub settle_pnl_limit_factor: f32,
-    pub settle_pnl_limit_window_size_ts: u64,
-    pub platform_liquidation_fee: f32,
+pub struct TokenForceWithdrawInstruction {
+    pub account: Pubkey,
+    pub bank: Pubkey,
+    pub target: Pubkey,
 }
-impl PerpCreateMarketInstruction {
-    pub async fn with_new_book_and_queue(
-        solana: &SolanaCookie,
-        base: &super::mango_setup::Token,
-    ) -> Self {
-        PerpCreateMarketInstruction {
-            bids: solana
-                .create_account_for_type::<BookSide>(&mango_v4::id())
-                .await,
-            asks: solana
-                .create_account_for_type::<BookSide>(&mango_v4::id())
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for TokenForceWithdrawInstruction {
+    type Accounts = mango_v4::accounts::TokenForceWithdraw;
+    type Instruction = mango_v4::instruction::TokenForceWithdraw;
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+        let instruction = Self::Instruction {};
+
+        let account = account_loader
+            .load_mango_account(&self.account)
+            .await
+            .unwrap();
+        let bank = account_loader.load::<Bank>(&self.bank).await.unwrap();
+        let health_check_metas = derive_health_check_remaining_account_metas(
+            account_loader,
+            &account,
+            None,
+            false,
+            None,
+        )
+        .await;
+
+        let accounts = Self::Accounts {
+            group: account.fixed.group,
+            account: self.account,
+            bank: self.bank,
+            vault: bank.vault,
+            oracle: bank.oracle,
+            owner_ata_token_account: self.target,
+            alternate_owner_token_account: self.target,
+            token_program: Token::id(),
+        };
+
+        let mut instruction = make_instruction(program_id, &accounts, &instruction);
+        instruction.accounts.extend(health_check_metas.into_iter());
+
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![]
+    }
+}
+
+pub struct TokenLiqWithTokenInstruction {
+    pub liqee: Pubkey,
+    pub liqor: Pubkey,
+    pub liqor_owner: TestKeypair,
+
+    pub asset_token_index: TokenIndex,
+    pub asset_bank_index: usize,
+    pub liab_token_index: TokenIndex,
+    pub liab_bank_index: usize,
+    pub max_liab_transfer: I80F48,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for TokenLiqWithTokenInstruction {
+    type Accounts = mango_v4::accounts::TokenLiqWithToken;
+    type Instruction = mango_v4::instruction::TokenLiqWithToken;
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+        let instruction = Self::Instruction {
+            asset_token_index: self.asset_token_index,
+            liab_token_index: self.liab_token_index,
+            max_liab_transfer: self.max_liab_transfer,
+        };
+
+        let liqee = account_loader
+            .load_mango_account(&self.liqee)
+            .await
+            .unwrap();
+        let liqor = account_loader
+            .load_mango_account(&self.liqor)
+            .await
+            .unwrap();
+        let health_check_metas = derive_liquidation_remaining_account_metas(
+            account_loader,
+            &liqee,
+            &liqor,
+            self.asset_token_index,
+            self.asset_bank_index,
+            self.liab_token_index,
+            self.liab_bank_index,
+        )
+        .await;
+
+        let accounts = Self::Accounts {
+            group: liqee.fixed.group,
+            liqee: self.liqee,
+            liqor: self.liqor,
+            liqor_owner: self.liqor_owner.pubkey(),
+        };
+
+        let mut instruction = make_instruction(program_id, &accounts, &instruction);
+        instruction.accounts.extend(health_check_metas.into_iter());
+
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.liqor_owner]
+    }
+}
+
+pub struct TokenLiqBankruptcyInstruction {
+    pub liqee: Pubkey,
+    pub liqor: Pubkey,
+    pub liqor_owner: TestKeypair,
+
+    pub max_liab_transfer: I80F48,
+    pub liab_mint_info: Pubkey,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for TokenLiqBankruptcyInstruction {
+    type Accounts = mango_v4::accounts::TokenLiqBankruptcy;
+    type Instruction = mango_v4::instruction::TokenLiqBankruptcy;
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+        let instruction = Self::Instruction {
+            max_liab_transfer: self.max_liab_transfer,
+        };
+
+        let liab_mint_info: MintInfo = account_loader.load(&self.liab_mint_info).await.unwrap();
+        let liqee = account_loader
+            .load_mango_account(&self.liqee)
+            .await
+            .unwrap();
+        let liqor = account_loader
+            .load_mango_account(&self.liqor)
+            .await
+            .unwrap();
+
+        let group_key = liqee.fixed.group;
+        let group: Group = account_loader.load(&group_key).await.unwrap();
+
+        let insurance_mint_info = Pubkey::find_program_address(
+            &[
+                b"MintInfo".as_ref(),
+                liqee.fixed.group.as_ref(),
+                group.insurance_mint.as_ref(),
+            ],
+            &program_id,
+        )
+        .0;
+        let insurance_mint_info: MintInfo =
+            account_loader.load(&insurance_mint_info).await.unwrap();
+
+        let health_check_metas = derive_liquidation_remaining_account_metas(
+            account_loader,
+            &liqee,
+            &liqor,
+            insurance_mint_info.token_index,
+            0,
+            liab_mint_info.token_index,
+            0,
+        )
+        .await;
+
+        let accounts = Self::Accounts {
+            group: group_key,
+            liqee: self.liqee,
+            liqor: self.liqor,
+            liqor_owner: self.liqor_owner.pubkey(),
+            liab_mint_info: self.liab_mint_info,
+            quote_vault: insurance_mint_info.first_vault(),
+            insurance_vault: group.insurance_vault,
+            token_program: Token::id(),
+        };
+
+        let mut instruction = make_instruction(program_id, &accounts, &instruction);
+        let mut bank_ams = liab_mint_info
+            .banks()
+            .iter()
+            .map(|bank| AccountMeta {
+                pubkey: *bank,
+                is_signer: false,
+                is_writable: true,
+            })
+            .collect::<Vec<_>>();
+        instruction.accounts.append(&mut bank_ams);
+        instruction.accounts.extend(health_check_metas.into_iter());
+
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.liqor_owner]
+    }
+}
+
+#[derive(Default)]
+pub struct PerpCreateMarketInstruction {
+    pub group: Pubkey,
+    pub admin: TestKeypair,
+    pub oracle: Pubkey,
+    pub bids: Pubkey,
+    pub asks: Pubkey,
+    pub event_queue: Pubkey,
+    pub payer: TestKeypair,
+    pub settle_token_index: TokenIndex,
+    pub perp_market_index: PerpMarketIndex,
+    pub base_decimals: u8,
+    pub quote_lot_size: i64,
+    pub base_lot_size: i64,
+    pub maint_base_asset_weight: f32,
+    pub init_base_asset_weight: f32,
+    pub maint_base_liab_weight: f32,
+    pub init_base_liab_weight: f32,
+    pub maint_overall_asset_weight: f32,
+    pub init_overall_asset_weight: f32,
+    pub base_liquidation_fee: f32,
+    pub positive_pnl_liquidation_fee: f32,
+    pub maker_fee: f32,
+    pub taker_fee: f32,
+    pub group_insurance_fund: bool,
+    pub fee_penalty: f32,
+    pub settle_fee_flat: f32,
+    pub settle_fee_amount_threshold: f32,
+    pub settle_fee_fraction_low_health: f32,
+    pub settle_pnl_limit_factor: f32,
+    pub settle_pnl_limit_window_size_ts: u64,
+    pub platform_liquidation_fee: f32,
+    pub bookside_capacity: u32,
+    pub event_queue_capacity: u32,
+    pub opening_auction_duration: u32,
+}
+impl PerpCreateMarketInstruction {
+    pub async fn with_new_book_and_queue(
+        solana: &SolanaCookie,
+        base: &super::mango_setup::Token,
+    ) -> Self {
+        Self::with_new_book_and_queue_capacity(
+            solana,
+            base,
+            MAX_ORDERTREE_NODES as u32,
+            MAX_NUM_EVENTS,
+        )
+        .await
+    }
+
+    pub async fn with_new_book_and_queue_capacity(
+        solana: &SolanaCookie,
+        base: &super::mango_setup::Token,
+        bookside_capacity: u32,
+        event_queue_capacity: u32,
+    ) -> Self {
+        let bookside_size = BookSide::account_size(bookside_capacity as usize);
+        PerpCreateMarketInstruction {
+            bids: solana
+                .create_account_from_len(&mango_v4::id(), bookside_size)
+                .await,
+            asks: solana
+                .create_account_from_len(&mango_v4::id(), bookside_size)
                 .await,
             event_queue: solana
-                .create_account_for_type::<EventQueue>(&mango_v4::id())
+                .create_account_from_len(
+                    &mango_v4::id(),
+                    EventQueue::account_size(event_queue_capacity as usize),
+                )
                 .await,
             oracle: base.oracle,
             base_decimals: base.mint.decimals,
+            bookside_capacity,
+            event_queue_capacity,
             ..PerpCreateMarketInstruction::default()
         }
     }
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for PerpCreateMarketInstruction {
-    type Accounts = mango_v4::accounts::PerpCreateMarket;
-    type Instruction = mango_v4::instruction::PerpCreateMarket;
+impl ClientInstruction for PerpCreateMarketInstruction {
+    type Accounts = mango_v4::accounts::PerpCreateMarket;
+    type Instruction = mango_v4::instruction::PerpCreateMarket;
+    async fn to_instruction(
+        &self,
+        _loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+        let instruction = Self::Instruction {
+            name: "UUU-PERP".to_string(),
+            oracle_config: OracleConfigParams {
+                conf_filter: 0.1,
+                max_staleness_slots: None,
+                max_staleness_seconds: None,
+                risk_increasing_max_staleness_slots: None,
+                risk_increasing_max_staleness_seconds: None,
+            },
+            settle_token_index: self.settle_token_index,
+            perp_market_index: self.perp_market_index,
+            quote_lot_size: self.quote_lot_size,
+            base_lot_size: self.base_lot_size,
+            maint_base_asset_weight: self.maint_base_asset_weight,
+            init_base_asset_weight: self.init_base_asset_weight,
+            maint_base_liab_weight: self.maint_base_liab_weight,
+            init_base_liab_weight: self.init_base_liab_weight,
+            maint_overall_asset_weight: self.maint_overall_asset_weight,
+            init_overall_asset_weight: self.init_overall_asset_weight,
+            base_liquidation_fee: self.base_liquidation_fee,
+            maker_fee: self.maker_fee,
+            taker_fee: self.taker_fee,
+            max_funding: 0.05,
+            min_funding: 0.05,
+            impact_quantity: 100,
+            base_decimals: self.base_decimals,
+            group_insurance_fund: self.group_insurance_fund,
+            fee_penalty: self.fee_penalty,
+            settle_fee_flat: self.settle_fee_flat,
+            settle_fee_amount_threshold: self.settle_fee_amount_threshold,
+            settle_fee_fraction_low_health: self.settle_fee_fraction_low_health,
+            settle_pnl_limit_factor: self.settle_pnl_limit_factor,
+            settle_pnl_limit_window_size_ts: self.settle_pnl_limit_window_size_ts,
+            positive_pnl_liquidation_fee: self.positive_pnl_liquidation_fee,
+            platform_liquidation_fee: self.platform_liquidation_fee,
+            bookside_capacity: self.bookside_capacity,
+            event_queue_capacity: self.event_queue_capacity,
+            opening_auction_duration: self.opening_auction_duration,
+        };
+
+        let perp_market = Pubkey::find_program_address(
+            &[
+                b"PerpMarket".as_ref(),
+                self.group.as_ref(),
+                self.perp_market_index.to_le_bytes().as_ref(),
+            ],
+            &program_id,
+        )
+        .0;
+
+        let accounts = Self::Accounts {
+            group: self.group,
+            admin: self.admin.pubkey(),
+            oracle: self.oracle,
+            perp_market,
+            bids: self.bids,
+            asks: self.asks,
+            event_queue: self.event_queue,
+            payer: self.payer.pubkey(),
+            system_program: System::id(),
+        };
+
+        let instruction = make_instruction(program_id, &accounts, &instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.admin, self.payer]
+    }
+}
+
+pub fn perp_edit_instruction_default() -> mango_v4::instruction::PerpEditMarket {
+    mango_v4::instruction::PerpEditMarket {
+        oracle_opt: None,
+        oracle_config_opt: None,
+        base_decimals_opt: None,
+        maint_base_asset_weight_opt: None,
+        init_base_asset_weight_opt: None,
+        maint_base_liab_weight_opt: None,
+        init_base_liab_weight_opt: None,
+        maint_overall_asset_weight_opt: None,
+        init_overall_asset_weight_opt: None,
+        base_liquidation_fee_opt: None,
+        maker_fee_opt: None,
+        taker_fee_opt: None,
+        min_funding_opt: None,
+        max_funding_opt: None,
+        impact_quantity_opt: None,
+        group_insurance_fund_opt: None,
+        fee_penalty_opt: None,
+        settle_fee_flat_opt: None,
+        settle_fee_amount_threshold_opt: None,
+        settle_fee_fraction_low_health_opt: None,
+        stable_price_delay_interval_seconds_opt: None,
+        stable_price_delay_growth_limit_opt: None,
+        stable_price_growth_limit_opt: None,
+        settle_pnl_limit_factor_opt: None,
+        settle_pnl_limit_window_size_ts_opt: None,
+        reduce_only_opt: None,
+        reset_stable_price: false,
+        positive_pnl_liquidation_fee_opt: None,
+        name_opt: None,
+        force_close_opt: None,
+        platform_liquidation_fee_opt: None,
+        funding_stable_price_weight_opt: None,
+        settle_stable_price_weight_opt: None,
+        opening_auction_duration_opt: None,
+    }
+}
+
+pub struct PerpEdit {
+    pub group: Pubkey,
+    pub admin: TestKeypair,
+    pub perp_market: Pubkey,
+    pub options: mango_v4::instruction::PerpEditMarket,
+}
+
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for PerpEdit {
+    type Accounts = mango_v4::accounts::PerpEditMarket;
+    type Instruction = mango_v4::instruction::PerpEditMarket;
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+
+        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
+
+        let accounts = Self::Accounts {
+            group: self.group,
+            admin: self.admin.pubkey(),
+            perp_market: self.perp_market,
+            oracle: perp_market.oracle,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, &self.options);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.admin]
+    }
+}
+
+pub struct PerpResetStablePriceModel {
+    pub group: Pubkey,
+    pub admin: TestKeypair,
+    pub perp_market: Pubkey,
+}
+
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for PerpResetStablePriceModel {
+    type Accounts = mango_v4::accounts::PerpEditMarket;
+    type Instruction = mango_v4::instruction::PerpEditMarket;
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+
+        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
+
+        let instruction = Self::Instruction {
+            reset_stable_price: true,
+            ..perp_edit_instruction_default()
+        };
+
+        let accounts = Self::Accounts {
+            group: self.group,
+            admin: self.admin.pubkey(),
+            perp_market: self.perp_market,
+            oracle: perp_market.oracle,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, &instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.admin]
+    }
+}
+
+pub struct PerpSetSettleLimitWindow {
+    pub group: Pubkey,
+    pub admin: TestKeypair,
+    pub perp_market: Pubkey,
+    pub window_size_ts: u64,
+}
+
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for PerpSetSettleLimitWindow {
+    type Accounts = mango_v4::accounts::PerpEditMarket;
+    type Instruction = mango_v4::instruction::PerpEditMarket;
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+
+        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
+
+        let instruction = Self::Instruction {
+            settle_pnl_limit_window_size_ts_opt: Some(self.window_size_ts),
+            ..perp_edit_instruction_default()
+        };
+
+        let accounts = Self::Accounts {
+            group: self.group,
+            admin: self.admin.pubkey(),
+            perp_market: self.perp_market,
+            oracle: perp_market.oracle,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, &instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.admin]
+    }
+}
+
+pub struct PerpMakeReduceOnly {
+    pub group: Pubkey,
+    pub admin: TestKeypair,
+    pub perp_market: Pubkey,
+    pub reduce_only: bool,
+    pub force_close: bool,
+}
+
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for PerpMakeReduceOnly {
+    type Accounts = mango_v4::accounts::PerpEditMarket;
+    type Instruction = mango_v4::instruction::PerpEditMarket;
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+
+        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
+
+        let instruction = Self::Instruction {
+            reduce_only_opt: Some(self.reduce_only),
+            force_close_opt: Some(self.force_close),
+            ..perp_edit_instruction_default()
+        };
+
+        let accounts = Self::Accounts {
+            group: self.group,
+            admin: self.admin.pubkey(),
+            perp_market: self.perp_market,
+            oracle: perp_market.oracle,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, &instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.admin]
+    }
+}
+
+pub struct PerpSetOpeningAuctionDuration {
+    pub group: Pubkey,
+    pub admin: TestKeypair,
+    pub perp_market: Pubkey,
+    pub duration: u32,
+}
+
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for PerpSetOpeningAuctionDuration {
+    type Accounts = mango_v4::accounts::PerpEditMarket;
+    type Instruction = mango_v4::instruction::PerpEditMarket;
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+
+        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
+
+        let instruction = Self::Instruction {
+            opening_auction_duration_opt: Some(self.duration),
+            ..perp_edit_instruction_default()
+        };
+
+        let accounts = Self::Accounts {
+            group: self.group,
+            admin: self.admin.pubkey(),
+            perp_market: self.perp_market,
+            oracle: perp_market.oracle,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, &instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.admin]
+    }
+}
+
+pub struct PerpChangeWeights {
+    pub group: Pubkey,
+    pub admin: TestKeypair,
+    pub perp_market: Pubkey,
+    pub init_overall_asset_weight: f32,
+    pub maint_overall_asset_weight: f32,
+}
+
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for PerpChangeWeights {
+    type Accounts = mango_v4::accounts::PerpEditMarket;
+    type Instruction = mango_v4::instruction::PerpEditMarket;
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+
+        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
+
+        let instruction = Self::Instruction {
+            init_overall_asset_weight_opt: Some(self.init_overall_asset_weight),
+            maint_overall_asset_weight_opt: Some(self.maint_overall_asset_weight),
+            ..perp_edit_instruction_default()
+        };
+
+        let accounts = Self::Accounts {
+            group: self.group,
+            admin: self.admin.pubkey(),
+            perp_market: self.perp_market,
+            oracle: perp_market.oracle,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, &instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.admin]
+    }
+}
+
+pub struct PerpResizeOrderbookInstruction {
+    pub admin: TestKeypair,
+    pub payer: TestKeypair,
+    pub perp_market: Pubkey,
+    pub bookside_capacity: u32,
+    pub event_queue_capacity: u32,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for PerpResizeOrderbookInstruction {
+    type Accounts = mango_v4::accounts::PerpResizeOrderbook;
+    type Instruction = mango_v4::instruction::PerpResizeOrderbook;
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+        let instruction = Self::Instruction {
+            bookside_capacity: self.bookside_capacity,
+            event_queue_capacity: self.event_queue_capacity,
+        };
+        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
+
+        let accounts = Self::Accounts {
+            group: perp_market.group,
+            admin: self.admin.pubkey(),
+            perp_market: self.perp_market,
+            bids: perp_market.bids,
+            asks: perp_market.asks,
+            event_queue: perp_market.event_queue,
+            payer: self.payer.pubkey(),
+            system_program: System::id(),
+        };
+
+        let instruction = make_instruction(program_id, &accounts, &instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.admin, self.payer]
+    }
+}
+
+pub struct PerpCloseMarketInstruction {
+    pub admin: TestKeypair,
+    pub perp_market: Pubkey,
+    pub sol_destination: Pubkey,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for PerpCloseMarketInstruction {
+    type Accounts = mango_v4::accounts::PerpCloseMarket;
+    type Instruction = mango_v4::instruction::PerpCloseMarket;
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+        let instruction = Self::Instruction {};
+        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
+
+        let accounts = Self::Accounts {
+            group: perp_market.group,
+            admin: self.admin.pubkey(),
+            perp_market: self.perp_market,
+            bids: perp_market.bids,
+            asks: perp_market.asks,
+            event_queue: perp_market.event_queue,
+            token_program: Token::id(),
+            sol_destination: self.sol_destination,
+        };
+
+        let instruction = make_instruction(program_id, &accounts, &instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.admin]
+    }
+}
+
+pub struct PerpDeactivatePositionInstruction {
+    pub account: Pubkey,
+    pub perp_market: Pubkey,
+    pub owner: TestKeypair,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for PerpDeactivatePositionInstruction {
+    type Accounts = mango_v4::accounts::PerpDeactivatePosition;
+    type Instruction = mango_v4::instruction::PerpDeactivatePosition;
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
+
+        let instruction = Self::Instruction {};
+        let accounts = Self::Accounts {
+            group: perp_market.group,
+            account: self.account,
+            perp_market: self.perp_market,
+            owner: self.owner.pubkey(),
+        };
+
+        let instruction = make_instruction(program_id, &accounts, &instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.owner]
+    }
+}
+
+pub struct PerpSetIsolatedInstruction {
+    pub account: Pubkey,
+    pub perp_market: Pubkey,
+    pub owner: TestKeypair,
+    pub isolated: bool,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for PerpSetIsolatedInstruction {
+    type Accounts = mango_v4::accounts::PerpSetIsolated;
+    type Instruction = mango_v4::instruction::PerpSetIsolated;
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
+
+        let instruction = Self::Instruction {
+            isolated: self.isolated,
+        };
+        let accounts = Self::Accounts {
+            group: perp_market.group,
+            account: self.account,
+            perp_market: self.perp_market,
+            owner: self.owner.pubkey(),
+        };
+
+        let instruction = make_instruction(program_id, &accounts, &instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.owner]
+    }
+}
+
+pub struct PerpIsolatedTransferInstruction {
+    pub account: Pubkey,
+    pub perp_market: Pubkey,
+    pub owner: TestKeypair,
+    pub amount: i64,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for PerpIsolatedTransferInstruction {
+    type Accounts = mango_v4::accounts::PerpIsolatedTransfer;
+    type Instruction = mango_v4::instruction::PerpIsolatedTransfer;
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
+        let account = account_loader
+            .load_mango_account(&self.account)
+            .await
+            .unwrap();
+        let health_check_metas = derive_health_check_remaining_account_metas(
+            account_loader,
+            &account,
+            None,
+            false,
+            Some(perp_market.perp_market_index),
+        )
+        .await;
+
+        let instruction = Self::Instruction {
+            amount: self.amount,
+        };
+        let accounts = Self::Accounts {
+            group: perp_market.group,
+            account: self.account,
+            perp_market: self.perp_market,
+            owner: self.owner.pubkey(),
+        };
+
+        let mut instruction = make_instruction(program_id, &accounts, &instruction);
+        instruction.accounts.extend(health_check_metas);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.owner]
+    }
+}
+
+pub struct PerpPlaceOrderInstruction {
+    pub account: Pubkey,
+    pub perp_market: Pubkey,
+    pub owner: TestKeypair,
+    pub side: Side,
+    pub price_lots: i64,
+    pub max_base_lots: i64,
+    pub max_quote_lots: i64,
+    pub reduce_only: bool,
+    pub client_order_id: u64,
+    pub self_trade_behavior: SelfTradeBehavior,
+    pub limit: u8,
+}
+impl Default for PerpPlaceOrderInstruction {
+    fn default() -> Self {
+        Self {
+            account: Pubkey::default(),
+            perp_market: Pubkey::default(),
+            owner: TestKeypair::default(),
+            side: Side::Bid,
+            price_lots: 0,
+            max_base_lots: i64::MAX,
+            max_quote_lots: i64::MAX,
+            reduce_only: false,
+            client_order_id: 0,
+            self_trade_behavior: SelfTradeBehavior::DecrementTake,
+            limit: 10,
+        }
+    }
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for PerpPlaceOrderInstruction {
+    type Accounts = mango_v4::accounts::PerpPlaceOrder;
+    type Instruction = mango_v4::instruction::PerpPlaceOrderV2;
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+        let instruction = Self::Instruction {
+            side: self.side,
+            price_lots: self.price_lots,
+            max_base_lots: self.max_base_lots,
+            max_quote_lots: self.max_quote_lots,
+            client_order_id: self.client_order_id,
+            order_type: PlaceOrderType::Limit,
+            self_trade_behavior: self.self_trade_behavior,
+            reduce_only: self.reduce_only,
+            expiry_timestamp: 0,
+            limit: self.limit,
+        };
+
+        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
+        let account = account_loader
+            .load_mango_account(&self.account)
+            .await
+            .unwrap();
+        let health_check_metas = derive_health_check_remaining_account_metas(
+            account_loader,
+            &account,
+            None,
+            false,
+            Some(perp_market.perp_market_index),
+        )
+        .await;
+
+        let accounts = Self::Accounts {
+            group: account.fixed.group,
+            account: self.account,
+            perp_market: self.perp_market,
+            bids: perp_market.bids,
+            asks: perp_market.asks,
+            event_queue: perp_market.event_queue,
+            oracle: perp_market.oracle,
+            owner: self.owner.pubkey(),
+        };
+        let mut instruction = make_instruction(program_id, &accounts, &instruction);
+        instruction.accounts.extend(health_check_metas);
+
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.owner]
+    }
+}
+
+pub struct PerpPlaceOrdersBatchInstruction {
+    pub account: Pubkey,
+    pub perp_market: Pubkey,
+    pub owner: TestKeypair,
+    pub orders: Vec<PerpBatchOrder>,
+    pub cancel_all_first: bool,
+    pub limit: u8,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for PerpPlaceOrdersBatchInstruction {
+    type Accounts = mango_v4::accounts::PerpPlaceOrder;
+    type Instruction = mango_v4::instruction::PerpPlaceOrdersBatch;
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+        let instruction = Self::Instruction {
+            orders: self.orders.clone(),
+            cancel_all_first: self.cancel_all_first,
+            expiry_timestamp: 0,
+            limit: self.limit,
+        };
+
+        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
+        let account = account_loader
+            .load_mango_account(&self.account)
+            .await
+            .unwrap();
+        let health_check_metas = derive_health_check_remaining_account_metas(
+            account_loader,
+            &account,
+            None,
+            false,
+            Some(perp_market.perp_market_index),
+        )
+        .await;
+
+        let accounts = Self::Accounts {
+            group: account.fixed.group,
+            account: self.account,
+            perp_market: self.perp_market,
+            bids: perp_market.bids,
+            asks: perp_market.asks,
+            event_queue: perp_market.event_queue,
+            oracle: perp_market.oracle,
+            owner: self.owner.pubkey(),
+        };
+        let mut instruction = make_instruction(program_id, &accounts, &instruction);
+        instruction.accounts.extend(health_check_metas);
+
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.owner]
+    }
+}
+
+pub struct PerpEditOrderInstruction {
+    pub account: Pubkey,
+    pub perp_market: Pubkey,
+    pub owner: TestKeypair,
+    pub order_id: Option<u128>,
+    pub client_order_id: Option<u64>,
+    pub price_lots: i64,
+    pub max_base_lots: i64,
+    pub self_trade_behavior: SelfTradeBehavior,
+    pub reduce_only: bool,
+    pub max_oracle_staleness_slots: i32,
+    pub limit: u8,
+}
+impl Default for PerpEditOrderInstruction {
+    fn default() -> Self {
+        Self {
+            account: Pubkey::default(),
+            perp_market: Pubkey::default(),
+            owner: TestKeypair::default(),
+            order_id: None,
+            client_order_id: None,
+            price_lots: 0,
+            max_base_lots: 0,
+            self_trade_behavior: SelfTradeBehavior::DecrementTake,
+            reduce_only: false,
+            max_oracle_staleness_slots: -1,
+            limit: 10,
+        }
+    }
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for PerpEditOrderInstruction {
+    type Accounts = mango_v4::accounts::PerpPlaceOrder;
+    type Instruction = mango_v4::instruction::PerpEditOrder;
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+        let instruction = Self::Instruction {
+            order_id_opt: self.order_id,
+            client_order_id_opt: self.client_order_id,
+            price_lots: self.price_lots,
+            max_base_lots: self.max_base_lots,
+            self_trade_behavior: self.self_trade_behavior,
+            reduce_only: self.reduce_only,
+            max_oracle_staleness_slots: self.max_oracle_staleness_slots,
+            limit: self.limit,
+        };
+
+        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
+        let account = account_loader
+            .load_mango_account(&self.account)
+            .await
+            .unwrap();
+        let health_check_metas = derive_health_check_remaining_account_metas(
+            account_loader,
+            &account,
+            None,
+            false,
+            Some(perp_market.perp_market_index),
+        )
+        .await;
+
+        let accounts = Self::Accounts {
+            group: account.fixed.group,
+            account: self.account,
+            perp_market: self.perp_market,
+            bids: perp_market.bids,
+            asks: perp_market.asks,
+            event_queue: perp_market.event_queue,
+            oracle: perp_market.oracle,
+            owner: self.owner.pubkey(),
+        };
+        let mut instruction = make_instruction(program_id, &accounts, &instruction);
+        instruction.accounts.extend(health_check_metas);
+
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.owner]
+    }
+}
+
+pub struct PerpPlaceOrderPeggedInstruction {
+    pub account: Pubkey,
+    pub perp_market: Pubkey,
+    pub owner: TestKeypair,
+    pub side: Side,
+    pub price_offset: i64,
+    pub max_base_lots: i64,
+    pub max_quote_lots: i64,
+    pub client_order_id: u64,
+    pub peg_limit: i64,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for PerpPlaceOrderPeggedInstruction {
+    type Accounts = mango_v4::accounts::PerpPlaceOrder;
+    type Instruction = mango_v4::instruction::PerpPlaceOrderPeggedV2;
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+        let instruction = Self::Instruction {
+            side: self.side,
+            price_offset_lots: self.price_offset,
+            peg_limit: self.peg_limit,
+            max_base_lots: self.max_base_lots,
+            max_quote_lots: self.max_quote_lots,
+            client_order_id: self.client_order_id,
+            order_type: PlaceOrderType::Limit,
+            reduce_only: false,
+            expiry_timestamp: 0,
+            self_trade_behavior: SelfTradeBehavior::DecrementTake,
+            limit: 10,
+            max_oracle_staleness_slots: -1,
+        };
+
+        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
+        let account = account_loader
+            .load_mango_account(&self.account)
+            .await
+            .unwrap();
+        let health_check_metas = derive_health_check_remaining_account_metas(
+            account_loader,
+            &account,
+            None,
+            false,
+            Some(perp_market.perp_market_index),
+        )
+        .await;
+
+        let accounts = Self::Accounts {
+            group: account.fixed.group,
+            account: self.account,
+            perp_market: self.perp_market,
+            bids: perp_market.bids,
+            asks: perp_market.asks,
+            event_queue: perp_market.event_queue,
+            oracle: perp_market.oracle,
+            owner: self.owner.pubkey(),
+        };
+        let mut instruction = make_instruction(program_id, &accounts, &instruction);
+        instruction.accounts.extend(health_check_metas);
+
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.owner]
+    }
+}
+
+pub struct PerpCancelOrderInstruction {
+    pub account: Pubkey,
+    pub perp_market: Pubkey,
+    pub owner: TestKeypair,
+    pub order_id: u128,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for PerpCancelOrderInstruction {
+    type Accounts = mango_v4::accounts::PerpCancelOrder;
+    type Instruction = mango_v4::instruction::PerpCancelOrder;
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+        let instruction = Self::Instruction {
+            order_id: self.order_id,
+        };
+        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
+        let accounts = Self::Accounts {
+            group: perp_market.group,
+            account: self.account,
+            perp_market: self.perp_market,
+            bids: perp_market.bids,
+            asks: perp_market.asks,
+            owner: self.owner.pubkey(),
+        };
+
+        let instruction = make_instruction(program_id, &accounts, &instruction);
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.owner]
+    }
+}
+
+pub struct PerpCancelOrderByClientOrderIdInstruction {
+    pub account: Pubkey,
+    pub perp_market: Pubkey,
+    pub owner: TestKeypair,
+    pub client_order_id: u64,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for PerpCancelOrderByClientOrderIdInstruction {
+    type Accounts = mango_v4::accounts::PerpCancelOrderByClientOrderId;
+    type Instruction = mango_v4::instruction::PerpCancelOrderByClientOrderId;
     async fn to_instruction(
         &self,
-        _loader: &(impl ClientAccountLoader + 'async_trait),
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
         let instruction = Self::Instruction {
-            name: "UUU-PERP".to_string(),
-            oracle_config: OracleConfigParams {
-                conf_filter: 0.1,
-                max_staleness_slots: None,
-            },
-            settle_token_index: self.settle_token_index,
-            perp_market_index: self.perp_market_index,
-            quote_lot_size: self.quote_lot_size,
-            base_lot_size: self.base_lot_size,
-            maint_base_asset_weight: self.maint_base_asset_weight,
-            init_base_asset_weight: self.init_base_asset_weight,
-            maint_base_liab_weight: self.maint_base_liab_weight,
-            init_base_liab_weight: self.init_base_liab_weight,
-            maint_overall_asset_weight: self.maint_overall_asset_weight,
-            init_overall_asset_weight: self.init_overall_asset_weight,
-            base_liquidation_fee: self.base_liquidation_fee,
-            maker_fee: self.maker_fee,
-            taker_fee: self.taker_fee,
-            max_funding: 0.05,
-            min_funding: 0.05,
-            impact_quantity: 100,
-            base_decimals: self.base_decimals,
-            group_insurance_fund: self.group_insurance_fund,
-            fee_penalty: self.fee_penalty,
-            settle_fee_flat: self.settle_fee_flat,
-            settle_fee_amount_threshold: self.settle_fee_amount_threshold,
-            settle_fee_fraction_low_health: self.settle_fee_fraction_low_health,
-            settle_pnl_limit_factor: self.settle_pnl_limit_factor,
-            settle_pnl_limit_window_size_ts: self.settle_pnl_limit_window_size_ts,
-            positive_pnl_liquidation_fee: self.positive_pnl_liquidation_fee,
-            platform_liquidation_fee: self.platform_liquidation_fee,
+            client_order_id: self.client_order_id,
         };
-
-        let perp_market = Pubkey::find_program_address(
-            &[
-                b"PerpMarket".as_ref(),
-                self.group.as_ref(),
-                self.perp_market_index.to_le_bytes().as_ref(),
-            ],
-            &program_id,
-        )
-        .0;
-
+        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
         let accounts = Self::Accounts {
-            group: self.group,
-            admin: self.admin.pubkey(),
-            oracle: self.oracle,
-            perp_market,
-            bids: self.bids,
-            asks: self.asks,
-            event_queue: self.event_queue,
-            payer: self.payer.pubkey(),
-            system_program: System::id(),
+            group: perp_market.group,
+            account: self.account,
+            perp_market: self.perp_market,
+            bids: perp_market.bids,
+            asks: perp_market.asks,
+            owner: self.owner.pubkey(),
         };
 
         let instruction = make_instruction(program_id, &accounts, &instruction);
@@ -3486,74 +5226,34 @@ impl ClientInstruction for PerpCreateMarketInstruction {
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.admin, self.payer]
-    }
-}
-
-fn perp_edit_instruction_default() -> mango_v4::instruction::PerpEditMarket {
-    mango_v4::instruction::PerpEditMarket {
-        oracle_opt: None,
-        oracle_config_opt: None,
-        base_decimals_opt: None,
-        maint_base_asset_weight_opt: None,
-        init_base_asset_weight_opt: None,
-        maint_base_liab_weight_opt: None,
-        init_base_liab_weight_opt: None,
-        maint_overall_asset_weight_opt: None,
-        init_overall_asset_weight_opt: None,
-        base_liquidation_fee_opt: None,
-        maker_fee_opt: None,
-        taker_fee_opt: None,
-        min_funding_opt: None,
-        max_funding_opt: None,
-        impact_quantity_opt: None,
-        group_insurance_fund_opt: None,
-        fee_penalty_opt: None,
-        settle_fee_flat_opt: None,
-        settle_fee_amount_threshold_opt: None,
-        settle_fee_fraction_low_health_opt: None,
-        stable_price_delay_interval_seconds_opt: None,
-        stable_price_delay_growth_limit_opt: None,
-        stable_price_growth_limit_opt: None,
-        settle_pnl_limit_factor_opt: None,
-        settle_pnl_limit_window_size_ts_opt: None,
-        reduce_only_opt: None,
-        reset_stable_price: false,
-        positive_pnl_liquidation_fee_opt: None,
-        name_opt: None,
-        force_close_opt: None,
-        platform_liquidation_fee_opt: None,
+        vec![self.owner]
     }
 }
 
-pub struct PerpResetStablePriceModel {
-    pub group: Pubkey,
-    pub admin: TestKeypair,
+pub struct PerpCancelAllOrdersInstruction {
+    pub account: Pubkey,
     pub perp_market: Pubkey,
+    pub owner: TestKeypair,
+    pub limit: u8,
 }
-
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for PerpResetStablePriceModel {
-    type Accounts = mango_v4::accounts::PerpEditMarket;
-    type Instruction = mango_v4::instruction::PerpEditMarket;
+impl ClientInstruction for PerpCancelAllOrdersInstruction {
+    type Accounts = mango_v4::accounts::PerpCancelAllOrders;
+    type Instruction = mango_v4::instruction::PerpCancelAllOrders;
     async fn to_instruction(
         &self,
         account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
-
+        let instruction = Self::Instruction { limit: self.limit };
         let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
-
-        let instruction = Self::Instruction {
-            reset_stable_price: true,
-            ..perp_edit_instruction_default()
-        };
-
         let accounts = Self::Accounts {
-            group: self.group,
-            admin: self.admin.pubkey(),
+            group: perp_market.group,
+            account: self.account,
             perp_market: self.perp_market,
-            oracle: perp_market.oracle,
+            bids: perp_market.bids,
+            asks: perp_market.asks,
+            owner: self.owner.pubkey(),
         };
 
         let instruction = make_instruction(program_id, &accounts, &instruction);
@@ -3561,81 +5261,71 @@ impl ClientInstruction for PerpResetStablePriceModel {
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.admin]
+        vec![self.owner]
     }
 }
 
-pub struct PerpSetSettleLimitWindow {
-    pub group: Pubkey,
-    pub admin: TestKeypair,
+pub struct PerpConsumeEventsInstruction {
     pub perp_market: Pubkey,
-    pub window_size_ts: u64,
+    pub mango_accounts: Vec<Pubkey>,
 }
-
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for PerpSetSettleLimitWindow {
-    type Accounts = mango_v4::accounts::PerpEditMarket;
-    type Instruction = mango_v4::instruction::PerpEditMarket;
+impl ClientInstruction for PerpConsumeEventsInstruction {
+    type Accounts = mango_v4::accounts::PerpConsumeEvents;
+    type Instruction = mango_v4::instruction::PerpConsumeEvents;
     async fn to_instruction(
         &self,
         account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
+        let instruction = Self::Instruction { limit: 10 };
 
         let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
-
-        let instruction = Self::Instruction {
-            settle_pnl_limit_window_size_ts_opt: Some(self.window_size_ts),
-            ..perp_edit_instruction_default()
-        };
-
         let accounts = Self::Accounts {
-            group: self.group,
-            admin: self.admin.pubkey(),
+            group: perp_market.group,
             perp_market: self.perp_market,
-            oracle: perp_market.oracle,
+            event_queue: perp_market.event_queue,
         };
 
-        let instruction = make_instruction(program_id, &accounts, &instruction);
+        let mut instruction = make_instruction(program_id, &accounts, &instruction);
+        instruction
+            .accounts
+            .extend(self.mango_accounts.iter().map(|ma| AccountMeta {
+                pubkey: *ma,
+                is_signer: false,
+                is_writable: true,
+            }));
         (accounts, instruction)
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.admin]
+        vec![]
     }
 }
 
-pub struct PerpMakeReduceOnly {
-    pub group: Pubkey,
-    pub admin: TestKeypair,
+#[derive(Clone)]
+pub struct PerpUpdateFundingInstruction {
     pub perp_market: Pubkey,
-    pub reduce_only: bool,
-    pub force_close: bool,
+    pub bank: Pubkey,
+    pub oracle: Pubkey,
 }
-
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for PerpMakeReduceOnly {
-    type Accounts = mango_v4::accounts::PerpEditMarket;
-    type Instruction = mango_v4::instruction::PerpEditMarket;
+impl ClientInstruction for PerpUpdateFundingInstruction {
+    type Accounts = mango_v4::accounts::PerpUpdateFunding;
+    type Instruction = mango_v4::instruction::PerpUpdateFunding;
     async fn to_instruction(
         &self,
         account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
-
+        let instruction = Self::Instruction {};
         let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
-
-        let instruction = Self::Instruction {
-            reduce_only_opt: Some(self.reduce_only),
-            force_close_opt: Some(self.force_close),
-            ..perp_edit_instruction_default()
-        };
-
         let accounts = Self::Accounts {
-            group: self.group,
-            admin: self.admin.pubkey(),
+            group: perp_market.group,
             perp_market: self.perp_market,
-            oracle: perp_market.oracle,
+            bids: perp_market.bids,
+            asks: perp_market.asks,
+            oracle: self.oracle,
         };
 
         let instruction = make_instruction(program_id, &accounts, &instruction);
@@ -3643,40 +5333,31 @@ impl ClientInstruction for PerpMakeReduceOnly {
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.admin]
+        vec![]
     }
 }
 
-pub struct PerpChangeWeights {
-    pub group: Pubkey,
-    pub admin: TestKeypair,
+pub struct PerpClearOpeningAuctionInstruction {
     pub perp_market: Pubkey,
-    pub init_overall_asset_weight: f32,
-    pub maint_overall_asset_weight: f32,
+    pub limit: u8,
 }
-
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for PerpChangeWeights {
-    type Accounts = mango_v4::accounts::PerpEditMarket;
-    type Instruction = mango_v4::instruction::PerpEditMarket;
+impl ClientInstruction for PerpClearOpeningAuctionInstruction {
+    type Accounts = mango_v4::accounts::PerpClearOpeningAuction;
+    type Instruction = mango_v4::instruction::PerpClearOpeningAuction;
     async fn to_instruction(
         &self,
         account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
-
+        let instruction = Self::Instruction { limit: self.limit };
         let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
-
-        let instruction = Self::Instruction {
-            init_overall_asset_weight_opt: Some(self.init_overall_asset_weight),
-            maint_overall_asset_weight_opt: Some(self.maint_overall_asset_weight),
-            ..perp_edit_instruction_default()
-        };
-
         let accounts = Self::Accounts {
-            group: self.group,
-            admin: self.admin.pubkey(),
+            group: perp_market.group,
             perp_market: self.perp_market,
+            bids: perp_market.bids,
+            asks: perp_market.asks,
+            event_queue: perp_market.event_queue,
             oracle: perp_market.oracle,
         };
 
@@ -3685,130 +5366,129 @@ impl ClientInstruction for PerpChangeWeights {
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.admin]
+        vec![]
     }
 }
 
-pub struct PerpCloseMarketInstruction {
-    pub admin: TestKeypair,
+pub struct PerpSettlePnlInstruction {
+    pub settler: Pubkey,
+    pub settler_owner: TestKeypair,
+    pub account_a: Pubkey,
+    pub account_b: Pubkey,
     pub perp_market: Pubkey,
-    pub sol_destination: Pubkey,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for PerpCloseMarketInstruction {
-    type Accounts = mango_v4::accounts::PerpCloseMarket;
-    type Instruction = mango_v4::instruction::PerpCloseMarket;
+impl ClientInstruction for PerpSettlePnlInstruction {
+    type Accounts = mango_v4::accounts::PerpSettlePnl;
+    type Instruction = mango_v4::instruction::PerpSettlePnl;
     async fn to_instruction(
         &self,
         account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
         let instruction = Self::Instruction {};
+
         let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
+        let account_a = account_loader
+            .load_mango_account(&self.account_a)
+            .await
+            .unwrap();
+        let account_b = account_loader
+            .load_mango_account(&self.account_b)
+            .await
+            .unwrap();
+        let health_check_metas = derive_liquidation_remaining_account_metas(
+            account_loader,
+            &account_a,
+            &account_b,
+            TokenIndex::MAX,
+            0,
+            TokenIndex::MAX,
+            0,
+        )
+        .await;
+        let settle_mint_info = get_mint_info_by_token_index(
+            account_loader,
+            &account_a,
+            perp_market.settle_token_index,
+        )
+        .await;
 
         let accounts = Self::Accounts {
             group: perp_market.group,
-            admin: self.admin.pubkey(),
+            settler: self.settler,
+            settler_owner: self.settler_owner.pubkey(),
             perp_market: self.perp_market,
-            bids: perp_market.bids,
-            asks: perp_market.asks,
-            event_queue: perp_market.event_queue,
-            token_program: Token::id(),
-            sol_destination: self.sol_destination,
+            account_a: self.account_a,
+            account_b: self.account_b,
+            oracle: perp_market.oracle,
+            settle_bank: settle_mint_info.first_bank(),
+            settle_oracle: settle_mint_info.oracle,
         };
 
-        let instruction = make_instruction(program_id, &accounts, &instruction);
+        let mut instruction = make_instruction(program_id, &accounts, &instruction);
+        instruction.accounts.extend(health_check_metas);
+
         (accounts, instruction)
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.admin]
+        vec![self.settler_owner]
     }
 }
 
-pub struct PerpDeactivatePositionInstruction {
-    pub account: Pubkey,
+pub struct PerpForceClosePositionInstruction {
+    pub account_a: Pubkey,
+    pub account_b: Pubkey,
     pub perp_market: Pubkey,
-    pub owner: TestKeypair,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for PerpDeactivatePositionInstruction {
-    type Accounts = mango_v4::accounts::PerpDeactivatePosition;
-    type Instruction = mango_v4::instruction::PerpDeactivatePosition;
+impl ClientInstruction for PerpForceClosePositionInstruction {
+    type Accounts = mango_v4::accounts::PerpForceClosePosition;
+    type Instruction = mango_v4::instruction::PerpForceClosePosition;
     async fn to_instruction(
         &self,
         account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
+        let instruction = Self::Instruction {};
+
         let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
 
-        let instruction = Self::Instruction {};
         let accounts = Self::Accounts {
             group: perp_market.group,
-            account: self.account,
             perp_market: self.perp_market,
-            owner: self.owner.pubkey(),
+            account_a: self.account_a,
+            account_b: self.account_b,
+            oracle: perp_market.oracle,
         };
 
         let instruction = make_instruction(program_id, &accounts, &instruction);
+
         (accounts, instruction)
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.owner]
+        vec![]
     }
 }
 
-pub struct PerpPlaceOrderInstruction {
+pub struct PerpSettleFeesInstruction {
     pub account: Pubkey,
     pub perp_market: Pubkey,
-    pub owner: TestKeypair,
-    pub side: Side,
-    pub price_lots: i64,
-    pub max_base_lots: i64,
-    pub max_quote_lots: i64,
-    pub reduce_only: bool,
-    pub client_order_id: u64,
-    pub self_trade_behavior: SelfTradeBehavior,
-    pub limit: u8,
-}
-impl Default for PerpPlaceOrderInstruction {
-    fn default() -> Self {
-        Self {
-            account: Pubkey::default(),
-            perp_market: Pubkey::default(),
-            owner: TestKeypair::default(),
-            side: Side::Bid,
-            price_lots: 0,
-            max_base_lots: i64::MAX,
-            max_quote_lots: i64::MAX,
-            reduce_only: false,
-            client_order_id: 0,
-            self_trade_behavior: SelfTradeBehavior::DecrementTake,
-            limit: 10,
-        }
-    }
+    pub max_settle_amount: u64,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for PerpPlaceOrderInstruction {
-    type Accounts = mango_v4::accounts::PerpPlaceOrder;
-    type Instruction = mango_v4::instruction::PerpPlaceOrderV2;
+impl ClientInstruction for PerpSettleFeesInstruction {
+    type Accounts = mango_v4::accounts::PerpSettleFees;
+    type Instruction = mango_v4::instruction::PerpSettleFees;
     async fn to_instruction(
         &self,
         account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
         let instruction = Self::Instruction {
-            side: self.side,
-            price_lots: self.price_lots,
-            max_base_lots: self.max_base_lots,
-            max_quote_lots: self.max_quote_lots,
-            client_order_id: self.client_order_id,
-            order_type: PlaceOrderType::Limit,
-            self_trade_behavior: self.self_trade_behavior,
-            reduce_only: self.reduce_only,
-            expiry_timestamp: 0,
-            limit: self.limit,
+            max_settle_amount: self.max_settle_amount,
         };
 
         let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
@@ -3824,16 +5504,17 @@ impl ClientInstruction for PerpPlaceOrderInstruction {
             Some(perp_market.perp_market_index),
         )
         .await;
+        let settle_mint_info =
+            get_mint_info_by_token_index(account_loader, &account, perp_market.settle_token_index)
+                .await;
 
         let accounts = Self::Accounts {
-            group: account.fixed.group,
-            account: self.account,
+            group: perp_market.group,
             perp_market: self.perp_market,
-            bids: perp_market.bids,
-            asks: perp_market.asks,
-            event_queue: perp_market.event_queue,
+            account: self.account,
             oracle: perp_market.oracle,
-            owner: self.owner.pubkey(),
+            settle_bank: settle_mint_info.first_bank(),
+            settle_oracle: settle_mint_info.oracle,
         };
         let mut instruction = make_instruction(program_id, &accounts, &instruction);
         instruction.accounts.extend(health_check_metas);
@@ -3842,44 +5523,24 @@ impl ClientInstruction for PerpPlaceOrderInstruction {
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.owner]
+        vec![]
     }
 }
 
-pub struct PerpPlaceOrderPeggedInstruction {
+pub struct PerpLiqForceCancelOrdersInstruction {
     pub account: Pubkey,
     pub perp_market: Pubkey,
-    pub owner: TestKeypair,
-    pub side: Side,
-    pub price_offset: i64,
-    pub max_base_lots: i64,
-    pub max_quote_lots: i64,
-    pub client_order_id: u64,
-    pub peg_limit: i64,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for PerpPlaceOrderPeggedInstruction {
-    type Accounts = mango_v4::accounts::PerpPlaceOrder;
-    type Instruction = mango_v4::instruction::PerpPlaceOrderPeggedV2;
+impl ClientInstruction for PerpLiqForceCancelOrdersInstruction {
+    type Accounts = mango_v4::accounts::PerpLiqForceCancelOrders;
+    type Instruction = mango_v4::instruction::PerpLiqForceCancelOrders;
     async fn to_instruction(
         &self,
         account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
-        let instruction = Self::Instruction {
-            side: self.side,
-            price_offset_lots: self.price_offset,
-            peg_limit: self.peg_limit,
-            max_base_lots: self.max_base_lots,
-            max_quote_lots: self.max_quote_lots,
-            client_order_id: self.client_order_id,
-            order_type: PlaceOrderType::Limit,
-            reduce_only: false,
-            expiry_timestamp: 0,
-            self_trade_behavior: SelfTradeBehavior::DecrementTake,
-            limit: 10,
-            max_oracle_staleness_slots: -1,
-        };
+        let instruction = Self::Instruction { limit: 10 };
 
         let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
         let account = account_loader
@@ -3891,19 +5552,16 @@ impl ClientInstruction for PerpPlaceOrderPeggedInstruction {
             &account,
             None,
             false,
-            Some(perp_market.perp_market_index),
+            None,
         )
         .await;
 
         let accounts = Self::Accounts {
             group: account.fixed.group,
-            account: self.account,
             perp_market: self.perp_market,
+            account: self.account,
             bids: perp_market.bids,
             asks: perp_market.asks,
-            event_queue: perp_market.event_queue,
-            oracle: perp_market.oracle,
-            owner: self.owner.pubkey(),
         };
         let mut instruction = make_instruction(program_id, &accounts, &instruction);
         instruction.accounts.extend(health_check_metas);
@@ -3912,149 +5570,182 @@ impl ClientInstruction for PerpPlaceOrderPeggedInstruction {
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.owner]
+        vec![]
     }
 }
 
-pub struct PerpCancelOrderInstruction {
-    pub account: Pubkey,
+pub struct PerpLiqBaseOrPositivePnlInstruction {
+    pub liqor: Pubkey,
+    pub liqor_owner: TestKeypair,
+    pub liqee: Pubkey,
     pub perp_market: Pubkey,
-    pub owner: TestKeypair,
-    pub order_id: u128,
+    pub max_base_transfer: i64,
+    pub max_pnl_transfer: u64,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for PerpCancelOrderInstruction {
-    type Accounts = mango_v4::accounts::PerpCancelOrder;
-    type Instruction = mango_v4::instruction::PerpCancelOrder;
+impl ClientInstruction for PerpLiqBaseOrPositivePnlInstruction {
+    type Accounts = mango_v4::accounts::PerpLiqBaseOrPositivePnl;
+    type Instruction = mango_v4::instruction::PerpLiqBaseOrPositivePnl;
     async fn to_instruction(
         &self,
         account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
         let instruction = Self::Instruction {
-            order_id: self.order_id,
-        };
-        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
-        let accounts = Self::Accounts {
-            group: perp_market.group,
-            account: self.account,
-            perp_market: self.perp_market,
-            bids: perp_market.bids,
-            asks: perp_market.asks,
-            owner: self.owner.pubkey(),
+            max_base_transfer: self.max_base_transfer,
+            max_pnl_transfer: self.max_pnl_transfer,
         };
 
-        let instruction = make_instruction(program_id, &accounts, &instruction);
-        (accounts, instruction)
-    }
+        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
+        let group_key = perp_market.group;
+        let liqor = account_loader
+            .load_mango_account(&self.liqor)
+            .await
+            .unwrap();
+        let liqee = account_loader
+            .load_mango_account(&self.liqee)
+            .await
+            .unwrap();
+        let health_check_metas = derive_liquidation_remaining_account_metas(
+            account_loader,
+            &liqee,
+            &liqor,
+            TokenIndex::MAX,
+            0,
+            TokenIndex::MAX,
+            0,
+        )
+        .await;
 
-    fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.owner]
-    }
-}
+        let settle_mint_info =
+            get_mint_info_by_token_index(account_loader, &liqee, perp_market.settle_token_index)
+                .await;
 
-pub struct PerpCancelOrderByClientOrderIdInstruction {
-    pub account: Pubkey,
-    pub perp_market: Pubkey,
-    pub owner: TestKeypair,
-    pub client_order_id: u64,
-}
-#[async_trait::async_trait(?Send)]
-impl ClientInstruction for PerpCancelOrderByClientOrderIdInstruction {
-    type Accounts = mango_v4::accounts::PerpCancelOrderByClientOrderId;
-    type Instruction = mango_v4::instruction::PerpCancelOrderByClientOrderId;
-    async fn to_instruction(
-        &self,
-        account_loader: &(impl ClientAccountLoader + 'async_trait),
-    ) -> (Self::Accounts, instruction::Instruction) {
-        let program_id = mango_v4::id();
-        let instruction = Self::Instruction {
-            client_order_id: self.client_order_id,
-        };
-        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
         let accounts = Self::Accounts {
-            group: perp_market.group,
-            account: self.account,
+            group: group_key,
             perp_market: self.perp_market,
-            bids: perp_market.bids,
-            asks: perp_market.asks,
-            owner: self.owner.pubkey(),
+            oracle: perp_market.oracle,
+            liqor: self.liqor,
+            liqor_owner: self.liqor_owner.pubkey(),
+            liqee: self.liqee,
+            settle_bank: settle_mint_info.first_bank(),
+            settle_vault: settle_mint_info.first_vault(),
+            settle_oracle: settle_mint_info.oracle,
         };
+        let mut instruction = make_instruction(program_id, &accounts, &instruction);
+        instruction.accounts.extend(health_check_metas);
 
-        let instruction = make_instruction(program_id, &accounts, &instruction);
         (accounts, instruction)
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.owner]
+        vec![self.liqor_owner]
     }
 }
 
-pub struct PerpCancelAllOrdersInstruction {
-    pub account: Pubkey,
+pub struct PerpLiqNegativePnlOrBankruptcyInstruction {
+    pub liqor: Pubkey,
+    pub liqor_owner: TestKeypair,
+    pub liqee: Pubkey,
     pub perp_market: Pubkey,
-    pub owner: TestKeypair,
-    pub limit: u8,
+    pub max_liab_transfer: u64,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for PerpCancelAllOrdersInstruction {
-    type Accounts = mango_v4::accounts::PerpCancelAllOrders;
-    type Instruction = mango_v4::instruction::PerpCancelAllOrders;
+impl ClientInstruction for PerpLiqNegativePnlOrBankruptcyInstruction {
+    type Accounts = mango_v4::accounts::PerpLiqNegativePnlOrBankruptcyV2;
+    type Instruction = mango_v4::instruction::PerpLiqNegativePnlOrBankruptcyV2;
     async fn to_instruction(
         &self,
         account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
-        let instruction = Self::Instruction { limit: self.limit };
+        let instruction = Self::Instruction {
+            max_liab_transfer: self.max_liab_transfer,
+        };
+
         let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
+        let liqor = account_loader
+            .load_mango_account(&self.liqor)
+            .await
+            .unwrap();
+        let liqee = account_loader
+            .load_mango_account(&self.liqee)
+            .await
+            .unwrap();
+
+        let group_key = liqee.fixed.group;
+        let group: Group = account_loader.load(&group_key).await.unwrap();
+
+        let insurance_mint_info = Pubkey::find_program_address(
+            &[
+                b"MintInfo".as_ref(),
+                liqee.fixed.group.as_ref(),
+                group.insurance_mint.as_ref(),
+            ],
+            &program_id,
+        )
+        .0;
+        let insurance_mint_info: MintInfo =
+            account_loader.load(&insurance_mint_info).await.unwrap();
+
+        let health_check_metas = derive_liquidation_remaining_account_metas(
+            account_loader,
+            &liqee,
+            &liqor,
+            insurance_mint_info.token_index,
+            0,
+            TokenIndex::MAX,
+            0,
+        )
+        .await;
+
+        let settle_mint_info =
+            get_mint_info_by_token_index(account_loader, &liqee, perp_market.settle_token_index)
+                .await;
+
         let accounts = Self::Accounts {
-            group: perp_market.group,
-            account: self.account,
+            group: group_key,
+            liqor: self.liqor,
+            liqor_owner: self.liqor_owner.pubkey(),
+            liqee: self.liqee,
             perp_market: self.perp_market,
-            bids: perp_market.bids,
-            asks: perp_market.asks,
-            owner: self.owner.pubkey(),
+            oracle: perp_market.oracle,
+            settle_bank: settle_mint_info.first_bank(),
+            settle_vault: settle_mint_info.first_vault(),
+            settle_oracle: settle_mint_info.oracle,
+            insurance_vault: group.insurance_vault,
+            insurance_bank: insurance_mint_info.first_bank(),
+            insurance_bank_vault: insurance_mint_info.first_vault(),
+            insurance_oracle: insurance_mint_info.oracle,
+            token_program: Token::id(),
         };
+        let mut instruction = make_instruction(program_id, &accounts, &instruction);
+        instruction.accounts.extend(health_check_metas);
 
-        let instruction = make_instruction(program_id, &accounts, &instruction);
         (accounts, instruction)
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.owner]
+        vec![self.liqor_owner]
     }
 }
 
-pub struct PerpConsumeEventsInstruction {
-    pub perp_market: Pubkey,
-    pub mango_accounts: Vec<Pubkey>,
-}
+pub struct BenchmarkInstruction {}
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for PerpConsumeEventsInstruction {
-    type Accounts = mango_v4::accounts::PerpConsumeEvents;
-    type Instruction = mango_v4::instruction::PerpConsumeEvents;
+impl ClientInstruction for BenchmarkInstruction {
+    type Accounts = mango_v4::accounts::Benchmark;
+    type Instruction = mango_v4::instruction::Benchmark;
     async fn to_instruction(
         &self,
-        account_loader: &(impl ClientAccountLoader + 'async_trait),
+        _loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
-        let instruction = Self::Instruction { limit: 10 };
-
-        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
+        let instruction = Self::Instruction {};
         let accounts = Self::Accounts {
-            group: perp_market.group,
-            perp_market: self.perp_market,
-            event_queue: perp_market.event_queue,
+            dummy: Pubkey::new_unique(),
         };
 
-        let mut instruction = make_instruction(program_id, &accounts, &instruction);
-        instruction
-            .accounts
-            .extend(self.mango_accounts.iter().map(|ma| AccountMeta {
-                pubkey: *ma,
-                is_signer: false,
-                is_writable: true,
-            }));
+        let instruction = make_instruction(program_id, &accounts, &instruction);
         (accounts, instruction)
     }
 
@@ -4062,32 +5753,42 @@ impl ClientInstruction for PerpConsumeEventsInstruction {
         vec![]
     }
 }
-
-pub struct PerpUpdateFundingInstruction {
-    pub perp_market: Pubkey,
-    pub bank: Pubkey,
-    pub oracle: Pubkey,
+#[derive(Clone)]
+pub struct TokenUpdateIndexAndRateInstruction {
+    pub mint_info: Pubkey,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for PerpUpdateFundingInstruction {
-    type Accounts = mango_v4::accounts::PerpUpdateFunding;
-    type Instruction = mango_v4::instruction::PerpUpdateFunding;
+impl ClientInstruction for TokenUpdateIndexAndRateInstruction {
+    type Accounts = mango_v4::accounts::TokenUpdateIndexAndRate;
+    type Instruction = mango_v4::instruction::TokenUpdateIndexAndRate;
     async fn to_instruction(
         &self,
-        account_loader: &(impl ClientAccountLoader + 'async_trait),
+        loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
         let instruction = Self::Instruction {};
-        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
+
+        let mint_info: MintInfo = loader.load(&self.mint_info).await.unwrap();
+
         let accounts = Self::Accounts {
-            group: perp_market.group,
-            perp_market: self.perp_market,
-            bids: perp_market.bids,
-            asks: perp_market.asks,
-            oracle: self.oracle,
+            group: mint_info.group,
+            mint_info: self.mint_info,
+            oracle: mint_info.oracle,
+            instructions: solana_program::sysvar::instructions::id(),
         };
 
-        let instruction = make_instruction(program_id, &accounts, &instruction);
+        let mut instruction = make_instruction(program_id, &accounts, &instruction);
+        let mut bank_ams = mint_info
+            .banks()
+            .iter()
+            .map(|bank| AccountMeta {
+                pubkey: *bank,
+                is_signer: false,
+                is_writable: true,
+            })
+            .collect::<Vec<_>>();
+        instruction.accounts.append(&mut bank_ams);
+
         (accounts, instruction)
     }
 
@@ -4096,82 +5797,56 @@ impl ClientInstruction for PerpUpdateFundingInstruction {
     }
 }
 
-pub struct PerpSettlePnlInstruction {
-    pub settler: Pubkey,
-    pub settler_owner: TestKeypair,
-    pub account_a: Pubkey,
-    pub account_b: Pubkey,
-    pub perp_market: Pubkey,
+pub struct TokenUpdateIndexAndRateResilientInstruction {
+    pub mint_info: Pubkey,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for PerpSettlePnlInstruction {
-    type Accounts = mango_v4::accounts::PerpSettlePnl;
-    type Instruction = mango_v4::instruction::PerpSettlePnl;
+impl ClientInstruction for TokenUpdateIndexAndRateResilientInstruction {
+    type Accounts = mango_v4::accounts::TokenUpdateIndexAndRate;
+    type Instruction = mango_v4::instruction::TokenUpdateIndexAndRateResilient;
     async fn to_instruction(
         &self,
-        account_loader: &(impl ClientAccountLoader + 'async_trait),
+        loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
         let instruction = Self::Instruction {};
 
-        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
-        let account_a = account_loader
-            .load_mango_account(&self.account_a)
-            .await
-            .unwrap();
-        let account_b = account_loader
-            .load_mango_account(&self.account_b)
-            .await
-            .unwrap();
-        let health_check_metas = derive_liquidation_remaining_account_metas(
-            account_loader,
-            &account_a,
-            &account_b,
-            TokenIndex::MAX,
-            0,
-            TokenIndex::MAX,
-            0,
-        )
-        .await;
-        let settle_mint_info = get_mint_info_by_token_index(
-            account_loader,
-            &account_a,
-            perp_market.settle_token_index,
-        )
-        .await;
+        let mint_info: MintInfo = loader.load(&self.mint_info).await.unwrap();
 
         let accounts = Self::Accounts {
-            group: perp_market.group,
-            settler: self.settler,
-            settler_owner: self.settler_owner.pubkey(),
-            perp_market: self.perp_market,
-            account_a: self.account_a,
-            account_b: self.account_b,
-            oracle: perp_market.oracle,
-            settle_bank: settle_mint_info.first_bank(),
-            settle_oracle: settle_mint_info.oracle,
+            group: mint_info.group,
+            mint_info: self.mint_info,
+            oracle: mint_info.oracle,
+            instructions: solana_program::sysvar::instructions::id(),
         };
 
         let mut instruction = make_instruction(program_id, &accounts, &instruction);
-        instruction.accounts.extend(health_check_metas);
+        let mut bank_ams = mint_info
+            .banks()
+            .iter()
+            .map(|bank| AccountMeta {
+                pubkey: *bank,
+                is_signer: false,
+                is_writable: true,
+            })
+            .collect::<Vec<_>>();
+        instruction.accounts.append(&mut bank_ams);
 
         (accounts, instruction)
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.settler_owner]
+        vec![]
     }
 }
 
-pub struct PerpForceClosePositionInstruction {
-    pub account_a: Pubkey,
-    pub account_b: Pubkey,
-    pub perp_market: Pubkey,
+pub struct ComputeAccountDataInstruction {
+    pub account: Pubkey,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for PerpForceClosePositionInstruction {
-    type Accounts = mango_v4::accounts::PerpForceClosePosition;
-    type Instruction = mango_v4::instruction::PerpForceClosePosition;
+impl ClientInstruction for ComputeAccountDataInstruction {
+    type Accounts = mango_v4::accounts::ComputeAccountData;
+    type Instruction = mango_v4::instruction::ComputeAccountData;
     async fn to_instruction(
         &self,
         account_loader: &(impl ClientAccountLoader + 'async_trait),
@@ -4179,17 +5854,27 @@ impl ClientInstruction for PerpForceClosePositionInstruction {
         let program_id = mango_v4::id();
         let instruction = Self::Instruction {};
 
-        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
+        let account = account_loader
+            .load_mango_account(&self.account)
+            .await
+            .unwrap();
+
+        let health_check_metas = derive_health_check_remaining_account_metas(
+            account_loader,
+            &account,
+            None,
+            false,
+            None,
+        )
+        .await;
 
         let accounts = Self::Accounts {
-            group: perp_market.group,
-            perp_market: self.perp_market,
-            account_a: self.account_a,
-            account_b: self.account_b,
-            oracle: perp_market.oracle,
+            group: account.fixed.group,
+            account: self.account,
         };
 
-        let instruction = make_instruction(program_id, &accounts, &instruction);
+        let mut instruction = make_instruction(program_id, &accounts, &instruction);
+        instruction.accounts.extend(health_check_metas.into_iter());
 
         (accounts, instruction)
     }
@@ -4199,51 +5884,42 @@ impl ClientInstruction for PerpForceClosePositionInstruction {
     }
 }
 
-pub struct PerpSettleFeesInstruction {
+pub struct HealthRegionBeginInstruction {
     pub account: Pubkey,
-    pub perp_market: Pubkey,
-    pub max_settle_amount: u64,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for PerpSettleFeesInstruction {
-    type Accounts = mango_v4::accounts::PerpSettleFees;
-    type Instruction = mango_v4::instruction::PerpSettleFees;
+impl ClientInstruction for HealthRegionBeginInstruction {
+    type Accounts = mango_v4::accounts::HealthRegionBegin;
+    type Instruction = mango_v4::instruction::HealthRegionBegin;
     async fn to_instruction(
         &self,
         account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
-        let instruction = Self::Instruction {
-            max_settle_amount: self.max_settle_amount,
-        };
+        let instruction = Self::Instruction {};
 
-        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
         let account = account_loader
             .load_mango_account(&self.account)
             .await
             .unwrap();
+
         let health_check_metas = derive_health_check_remaining_account_metas(
             account_loader,
             &account,
             None,
             false,
-            Some(perp_market.perp_market_index),
+            None,
         )
         .await;
-        let settle_mint_info =
-            get_mint_info_by_token_index(account_loader, &account, perp_market.settle_token_index)
-                .await;
 
         let accounts = Self::Accounts {
-            group: perp_market.group,
-            perp_market: self.perp_market,
+            group: account.fixed.group,
+            instructions: solana_program::sysvar::instructions::id(),
             account: self.account,
-            oracle: perp_market.oracle,
-            settle_bank: settle_mint_info.first_bank(),
-            settle_oracle: settle_mint_info.oracle,
         };
+
         let mut instruction = make_instruction(program_id, &accounts, &instruction);
-        instruction.accounts.extend(health_check_metas);
+        instruction.accounts.extend(health_check_metas.into_iter());
 
         (accounts, instruction)
     }
@@ -4253,44 +5929,41 @@ impl ClientInstruction for PerpSettleFeesInstruction {
     }
 }
 
-pub struct PerpLiqForceCancelOrdersInstruction {
+pub struct HealthRegionEndInstruction {
     pub account: Pubkey,
-    pub perp_market: Pubkey,
+    pub affected_bank: Option<Pubkey>,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for PerpLiqForceCancelOrdersInstruction {
-    type Accounts = mango_v4::accounts::PerpLiqForceCancelOrders;
-    type Instruction = mango_v4::instruction::PerpLiqForceCancelOrders;
+impl ClientInstruction for HealthRegionEndInstruction {
+    type Accounts = mango_v4::accounts::HealthRegionEnd;
+    type Instruction = mango_v4::instruction::HealthRegionEnd;
     async fn to_instruction(
         &self,
         account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
-        let instruction = Self::Instruction { limit: 10 };
+        let instruction = Self::Instruction {};
 
-        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
         let account = account_loader
             .load_mango_account(&self.account)
             .await
             .unwrap();
+
         let health_check_metas = derive_health_check_remaining_account_metas(
             account_loader,
             &account,
-            None,
+            self.affected_bank,
             false,
             None,
         )
         .await;
 
         let accounts = Self::Accounts {
-            group: account.fixed.group,
-            perp_market: self.perp_market,
             account: self.account,
-            bids: perp_market.bids,
-            asks: perp_market.asks,
         };
+
         let mut instruction = make_instruction(program_id, &accounts, &instruction);
-        instruction.accounts.extend(health_check_metas);
+        instruction.accounts.extend(health_check_metas.into_iter());
 
         (accounts, instruction)
     }
@@ -4300,175 +5973,144 @@ impl ClientInstruction for PerpLiqForceCancelOrdersInstruction {
     }
 }
 
-pub struct PerpLiqBaseOrPositivePnlInstruction {
-    pub liqor: Pubkey,
-    pub liqor_owner: TestKeypair,
-    pub liqee: Pubkey,
-    pub perp_market: Pubkey,
-    pub max_base_transfer: i64,
-    pub max_pnl_transfer: u64,
+pub struct AltSetInstruction {
+    pub group: Pubkey,
+    pub admin: TestKeypair,
+    pub address_lookup_table: Pubkey,
+    pub index: u8,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for PerpLiqBaseOrPositivePnlInstruction {
-    type Accounts = mango_v4::accounts::PerpLiqBaseOrPositivePnl;
-    type Instruction = mango_v4::instruction::PerpLiqBaseOrPositivePnl;
+impl ClientInstruction for AltSetInstruction {
+    type Accounts = mango_v4::accounts::AltSet;
+    type Instruction = mango_v4::instruction::AltSet;
     async fn to_instruction(
         &self,
-        account_loader: &(impl ClientAccountLoader + 'async_trait),
+        _account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
-        let instruction = Self::Instruction {
-            max_base_transfer: self.max_base_transfer,
-            max_pnl_transfer: self.max_pnl_transfer,
-        };
-
-        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
-        let group_key = perp_market.group;
-        let liqor = account_loader
-            .load_mango_account(&self.liqor)
-            .await
-            .unwrap();
-        let liqee = account_loader
-            .load_mango_account(&self.liqee)
-            .await
-            .unwrap();
-        let health_check_metas = derive_liquidation_remaining_account_metas(
-            account_loader,
-            &liqee,
-            &liqor,
-            TokenIndex::MAX,
-            0,
-            TokenIndex::MAX,
-            0,
-        )
-        .await;
-
-        let settle_mint_info =
-            get_mint_info_by_token_index(account_loader, &liqee, perp_market.settle_token_index)
-                .await;
+        let instruction = Self::Instruction { index: self.index };
 
         let accounts = Self::Accounts {
-            group: group_key,
-            perp_market: self.perp_market,
-            oracle: perp_market.oracle,
-            liqor: self.liqor,
-            liqor_owner: self.liqor_owner.pubkey(),
-            liqee: self.liqee,
-            settle_bank: settle_mint_info.first_bank(),
-            settle_vault: settle_mint_info.first_vault(),
-            settle_oracle: settle_mint_info.oracle,
+            group: self.group,
+            admin: self.admin.pubkey(),
+            address_lookup_table: self.address_lookup_table,
         };
-        let mut instruction = make_instruction(program_id, &accounts, &instruction);
-        instruction.accounts.extend(health_check_metas);
 
+        let instruction = make_instruction(program_id, &accounts, &instruction);
         (accounts, instruction)
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.liqor_owner]
+        vec![self.admin]
     }
 }
 
-pub struct PerpLiqNegativePnlOrBankruptcyInstruction {
-    pub liqor: Pubkey,
-    pub liqor_owner: TestKeypair,
-    pub liqee: Pubkey,
-    pub perp_market: Pubkey,
-    pub max_liab_transfer: u64,
+pub struct AltExtendInstruction {
+    pub group: Pubkey,
+    pub admin: TestKeypair,
+    pub payer: TestKeypair,
+    pub address_lookup_table: Pubkey,
+    pub index: u8,
+    pub new_addresses: Vec<Pubkey>,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for PerpLiqNegativePnlOrBankruptcyInstruction {
-    type Accounts = mango_v4::accounts::PerpLiqNegativePnlOrBankruptcyV2;
-    type Instruction = mango_v4::instruction::PerpLiqNegativePnlOrBankruptcyV2;
+impl ClientInstruction for AltExtendInstruction {
+    type Accounts = mango_v4::accounts::AltExtend;
+    type Instruction = mango_v4::instruction::AltExtend;
     async fn to_instruction(
         &self,
-        account_loader: &(impl ClientAccountLoader + 'async_trait),
+        _account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
         let instruction = Self::Instruction {
-            max_liab_transfer: self.max_liab_transfer,
+            index: self.index,
+            new_addresses: self.new_addresses.clone(),
         };
 
-        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
-        let liqor = account_loader
-            .load_mango_account(&self.liqor)
-            .await
-            .unwrap();
-        let liqee = account_loader
-            .load_mango_account(&self.liqee)
-            .await
-            .unwrap();
-
-        let group_key = liqee.fixed.group;
-        let group: Group = account_loader.load(&group_key).await.unwrap();
-
-        let insurance_mint_info = Pubkey::find_program_address(
-            &[
-                b"MintInfo".as_ref(),
-                liqee.fixed.group.as_ref(),
-                group.insurance_mint.as_ref(),
-            ],
-            &program_id,
-        )
-        .0;
-        let insurance_mint_info: MintInfo =
-            account_loader.load(&insurance_mint_info).await.unwrap();
-
-        let health_check_metas = derive_liquidation_remaining_account_metas(
-            account_loader,
-            &liqee,
-            &liqor,
-            insurance_mint_info.token_index,
-            0,
-            TokenIndex::MAX,
-            0,
-        )
-        .await;
-
-        let settle_mint_info =
-            get_mint_info_by_token_index(account_loader, &liqee, perp_market.settle_token_index)
-                .await;
-
         let accounts = Self::Accounts {
-            group: group_key,
-            liqor: self.liqor,
-            liqor_owner: self.liqor_owner.pubkey(),
-            liqee: self.liqee,
-            perp_market: self.perp_market,
-            oracle: perp_market.oracle,
-            settle_bank: settle_mint_info.first_bank(),
-            settle_vault: settle_mint_info.first_vault(),
-            settle_oracle: settle_mint_info.oracle,
-            insurance_vault: group.insurance_vault,
-            insurance_bank: insurance_mint_info.first_bank(),
-            insurance_bank_vault: insurance_mint_info.first_vault(),
-            insurance_oracle: insurance_mint_info.oracle,
-            token_program: Token::id(),
+            group: self.group,
+            admin: self.admin.pubkey(),
+            payer: self.payer.pubkey(),
+            address_lookup_table: self.address_lookup_table,
         };
-        let mut instruction = make_instruction(program_id, &accounts, &instruction);
-        instruction.accounts.extend(health_check_metas);
 
+        let instruction = make_instruction(program_id, &accounts, &instruction);
         (accounts, instruction)
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.liqor_owner]
+        vec![self.admin, self.payer]
     }
 }
 
-pub struct BenchmarkInstruction {}
-#[async_trait::async_trait(?Send)]
-impl ClientInstruction for BenchmarkInstruction {
-    type Accounts = mango_v4::accounts::Benchmark;
-    type Instruction = mango_v4::instruction::Benchmark;
-    async fn to_instruction(
-        &self,
-        _loader: &(impl ClientAccountLoader + 'async_trait),
-    ) -> (Self::Accounts, instruction::Instruction) {
-        let program_id = mango_v4::id();
-        let instruction = Self::Instruction {};
+#[derive(Clone)]
+pub struct TokenConditionalSwapCreateInstruction {
+    pub account: Pubkey,
+    pub owner: TestKeypair,
+    pub buy_mint: Pubkey,
+    pub sell_mint: Pubkey,
+    pub max_buy: u64,
+    pub max_sell: u64,
+    pub price_lower_limit: f64,
+    pub price_upper_limit: f64,
+    pub price_premium_rate: f64,
+    pub allow_creating_deposits: bool,
+    pub allow_creating_borrows: bool,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for TokenConditionalSwapCreateInstruction {
+    type Accounts = mango_v4::accounts::TokenConditionalSwapCreate;
+    type Instruction = mango_v4::instruction::TokenConditionalSwapCreateV2;
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+        let instruction = Self::Instruction {
+            max_buy: self.max_buy,
+            max_sell: self.max_sell,
+            expiry_timestamp: u64::MAX,
+            price_lower_limit: self.price_lower_limit,
+            price_upper_limit: self.price_upper_limit,
+            price_premium_rate: self.price_premium_rate,
+            allow_creating_deposits: self.allow_creating_deposits,
+            allow_creating_borrows: self.allow_creating_borrows,
+            display_price_style: TokenConditionalSwapDisplayPriceStyle::SellTokenPerBuyToken,
+            intention: TokenConditionalSwapIntention::Unknown,
+        };
+
+        let account = account_loader
+            .load_mango_account(&self.account)
+            .await
+            .unwrap();
+
+        let buy_mint_info_address = Pubkey::find_program_address(
+            &[
+                b"MintInfo".as_ref(),
+                account.fixed.group.as_ref(),
+                self.buy_mint.as_ref(),
+            ],
+            &program_id,
+        )
+        .0;
+        let sell_mint_info_address = Pubkey::find_program_address(
+            &[
+                b"MintInfo".as_ref(),
+                account.fixed.group.as_ref(),
+                self.sell_mint.as_ref(),
+            ],
+            &program_id,
+        )
+        .0;
+        let buy_mint_info: MintInfo = account_loader.load(&buy_mint_info_address).await.unwrap();
+        let sell_mint_info: MintInfo = account_loader.load(&sell_mint_info_address).await.unwrap();
+
         let accounts = Self::Accounts {
-            dummy: Pubkey::new_unique(),
+            group: account.fixed.group,
+            account: self.account,
+            authority: self.owner.pubkey(),
+            buy_bank: buy_mint_info.first_bank(),
+            sell_bank: sell_mint_info.first_bank(),
         };
 
         let instruction = make_instruction(program_id, &accounts, &instruction);
@@ -4476,413 +6118,451 @@ impl ClientInstruction for BenchmarkInstruction {
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![]
+        vec![self.owner]
     }
 }
-pub struct TokenUpdateIndexAndRateInstruction {
-    pub mint_info: Pubkey,
+
+#[derive(Clone)]
+pub struct TokenConditionalSwapCreateLinearAuctionInstruction {
+    pub account: Pubkey,
+    pub owner: TestKeypair,
+    pub buy_mint: Pubkey,
+    pub sell_mint: Pubkey,
+    pub max_buy: u64,
+    pub max_sell: u64,
+    pub price_start: f64,
+    pub price_end: f64,
+    pub allow_creating_deposits: bool,
+    pub allow_creating_borrows: bool,
+    pub start_timestamp: u64,
+    pub duration_seconds: u64,
+    pub expiry_timestamp: u64,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for TokenUpdateIndexAndRateInstruction {
-    type Accounts = mango_v4::accounts::TokenUpdateIndexAndRate;
-    type Instruction = mango_v4::instruction::TokenUpdateIndexAndRate;
+impl ClientInstruction for TokenConditionalSwapCreateLinearAuctionInstruction {
+    type Accounts = mango_v4::accounts::TokenConditionalSwapCreate;
+    type Instruction = mango_v4::instruction::TokenConditionalSwapCreateLinearAuction;
     async fn to_instruction(
         &self,
-        loader: &(impl ClientAccountLoader + 'async_trait),
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
-        let instruction = Self::Instruction {};
+        let instruction = Self::Instruction {
+            max_buy: self.max_buy,
+            max_sell: self.max_sell,
+            expiry_timestamp: self.expiry_timestamp,
+            price_start: self.price_start,
+            price_end: self.price_end,
+            allow_creating_deposits: self.allow_creating_deposits,
+            allow_creating_borrows: self.allow_creating_borrows,
+            display_price_style: TokenConditionalSwapDisplayPriceStyle::SellTokenPerBuyToken,
+            start_timestamp: self.start_timestamp,
+            duration_seconds: self.duration_seconds,
+        };
 
-        let mint_info: MintInfo = loader.load(&self.mint_info).await.unwrap();
+        let account = account_loader
+            .load_mango_account(&self.account)
+            .await
+            .unwrap();
+
+        let buy_mint_info_address = Pubkey::find_program_address(
+            &[
+                b"MintInfo".as_ref(),
+                account.fixed.group.as_ref(),
+                self.buy_mint.as_ref(),
+            ],
+            &program_id,
+        )
+        .0;
+        let sell_mint_info_address = Pubkey::find_program_address(
+            &[
+                b"MintInfo".as_ref(),
+                account.fixed.group.as_ref(),
+                self.sell_mint.as_ref(),
+            ],
+            &program_id,
+        )
+        .0;
+        let buy_mint_info: MintInfo = account_loader.load(&buy_mint_info_address).await.unwrap();
+        let sell_mint_info: MintInfo = account_loader.load(&sell_mint_info_address).await.unwrap();
 
         let accounts = Self::Accounts {
-            group: mint_info.group,
-            mint_info: self.mint_info,
-            oracle: mint_info.oracle,
-            instructions: solana_program::sysvar::instructions::id(),
+            group: account.fixed.group,
+            account: self.account,
+            authority: self.owner.pubkey(),
+            buy_bank: buy_mint_info.first_bank(),
+            sell_bank: sell_mint_info.first_bank(),
         };
 
-        let mut instruction = make_instruction(program_id, &accounts, &instruction);
-        let mut bank_ams = mint_info
-            .banks()
-            .iter()
-            .map(|bank| AccountMeta {
-                pubkey: *bank,
-                is_signer: false,
-                is_writable: true,
-            })
-            .collect::<Vec<_>>();
-        instruction.accounts.append(&mut bank_ams);
-
+        let instruction = make_instruction(program_id, &accounts, &instruction);
         (accounts, instruction)
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![]
+        vec![self.owner]
     }
 }
 
-pub struct TokenUpdateIndexAndRateResilientInstruction {
-    pub mint_info: Pubkey,
+#[derive(Clone)]
+pub struct TokenConditionalSwapCreatePremiumAuctionInstruction {
+    pub account: Pubkey,
+    pub owner: TestKeypair,
+    pub buy_mint: Pubkey,
+    pub sell_mint: Pubkey,
+    pub max_buy: u64,
+    pub max_sell: u64,
+    pub price_lower_limit: f64,
+    pub price_upper_limit: f64,
+    pub max_price_premium_rate: f64,
+    pub allow_creating_deposits: bool,
+    pub allow_creating_borrows: bool,
+    pub duration_seconds: u64,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for TokenUpdateIndexAndRateResilientInstruction {
-    type Accounts = mango_v4::accounts::TokenUpdateIndexAndRate;
-    type Instruction = mango_v4::instruction::TokenUpdateIndexAndRateResilient;
+impl ClientInstruction for TokenConditionalSwapCreatePremiumAuctionInstruction {
+    type Accounts = mango_v4::accounts::TokenConditionalSwapCreate;
+    type Instruction = mango_v4::instruction::TokenConditionalSwapCreatePremiumAuction;
     async fn to_instruction(
         &self,
-        loader: &(impl ClientAccountLoader + 'async_trait),
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
-        let instruction = Self::Instruction {};
+        let instruction = Self::Instruction {
+            max_buy: self.max_buy,
+            max_sell: self.max_sell,
+            expiry_timestamp: u64::MAX,
+            price_lower_limit: self.price_lower_limit,
+            price_upper_limit: self.price_upper_limit,
+            max_price_premium_rate: self.max_price_premium_rate,
+            allow_creating_deposits: self.allow_creating_deposits,
+            allow_creating_borrows: self.allow_creating_borrows,
+            display_price_style: TokenConditionalSwapDisplayPriceStyle::SellTokenPerBuyToken,
+            intention: TokenConditionalSwapIntention::Unknown,
+            duration_seconds: self.duration_seconds,
+        };
 
-        let mint_info: MintInfo = loader.load(&self.mint_info).await.unwrap();
+        let account = account_loader
+            .load_mango_account(&self.account)
+            .await
+            .unwrap();
+
+        let buy_mint_info_address = Pubkey::find_program_address(
+            &[
+                b"MintInfo".as_ref(),
+                account.fixed.group.as_ref(),
+                self.buy_mint.as_ref(),
+            ],
+            &program_id,
+        )
+        .0;
+        let sell_mint_info_address = Pubkey::find_program_address(
+            &[
+                b"MintInfo".as_ref(),
+                account.fixed.group.as_ref(),
+                self.sell_mint.as_ref(),
+            ],
+            &program_id,
+        )
+        .0;
+        let buy_mint_info: MintInfo = account_loader.load(&buy_mint_info_address).await.unwrap();
+        let sell_mint_info: MintInfo = account_loader.load(&sell_mint_info_address).await.unwrap();
 
         let accounts = Self::Accounts {
-            group: mint_info.group,
-            mint_info: self.mint_info,
-            oracle: mint_info.oracle,
-            instructions: solana_program::sysvar::instructions::id(),
+            group: account.fixed.group,
+            account: self.account,
+            authority: self.owner.pubkey(),
+            buy_bank: buy_mint_info.first_bank(),
+            sell_bank: sell_mint_info.first_bank(),
         };
 
-        let mut instruction = make_instruction(program_id, &accounts, &instruction);
-        let mut bank_ams = mint_info
-            .banks()
-            .iter()
-            .map(|bank| AccountMeta {
-                pubkey: *bank,
-                is_signer: false,
-                is_writable: true,
-            })
-            .collect::<Vec<_>>();
-        instruction.accounts.append(&mut bank_ams);
-
+        let instruction = make_instruction(program_id, &accounts, &instruction);
         (accounts, instruction)
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![]
+        vec![self.owner]
     }
 }
 
-pub struct ComputeAccountDataInstruction {
+#[derive(Clone)]
+pub struct TokenConditionalSwapCancelInstruction {
     pub account: Pubkey,
+    pub owner: TestKeypair,
+    pub index: u8,
+    pub id: u64,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for ComputeAccountDataInstruction {
-    type Accounts = mango_v4::accounts::ComputeAccountData;
-    type Instruction = mango_v4::instruction::ComputeAccountData;
+impl ClientInstruction for TokenConditionalSwapCancelInstruction {
+    type Accounts = mango_v4::accounts::TokenConditionalSwapCancel;
+    type Instruction = mango_v4::instruction::TokenConditionalSwapCancel;
     async fn to_instruction(
         &self,
         account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
-        let instruction = Self::Instruction {};
+        let instruction = Self::Instruction {
+            token_conditional_swap_index: self.index,
+            token_conditional_swap_id: self.id,
+        };
 
         let account = account_loader
             .load_mango_account(&self.account)
             .await
             .unwrap();
+        let tcs = account.token_conditional_swap_by_id(self.id).unwrap().1;
 
-        let health_check_metas = derive_health_check_remaining_account_metas(
-            account_loader,
-            &account,
-            None,
-            false,
-            None,
-        )
-        .await;
+        let buy_mint_info =
+            get_mint_info_by_token_index(account_loader, &account, tcs.buy_token_index).await;
+        let sell_mint_info =
+            get_mint_info_by_token_index(account_loader, &account, tcs.sell_token_index).await;
 
         let accounts = Self::Accounts {
             group: account.fixed.group,
             account: self.account,
+            authority: self.owner.pubkey(),
+            buy_bank: buy_mint_info.first_bank(),
+            sell_bank: sell_mint_info.first_bank(),
         };
 
-        let mut instruction = make_instruction(program_id, &accounts, &instruction);
-        instruction.accounts.extend(health_check_metas.into_iter());
-
+        let instruction = make_instruction(program_id, &accounts, &instruction);
         (accounts, instruction)
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![]
+        vec![self.owner]
     }
 }
 
-pub struct HealthRegionBeginInstruction {
-    pub account: Pubkey,
+#[derive(Clone)]
+pub struct TokenConditionalSwapTriggerInstruction {
+    pub liqee: Pubkey,
+    pub liqor: Pubkey,
+    pub liqor_owner: TestKeypair,
+    pub index: u8,
+    pub max_buy_token_to_liqee: u64,
+    pub max_sell_token_to_liqor: u64,
+    pub min_buy_token: u64,
+    pub min_taker_price: f32,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for HealthRegionBeginInstruction {
-    type Accounts = mango_v4::accounts::HealthRegionBegin;
-    type Instruction = mango_v4::instruction::HealthRegionBegin;
+impl ClientInstruction for TokenConditionalSwapTriggerInstruction {
+    type Accounts = mango_v4::accounts::TokenConditionalSwapTrigger;
+    type Instruction = mango_v4::instruction::TokenConditionalSwapTriggerV2;
     async fn to_instruction(
         &self,
         account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
-        let instruction = Self::Instruction {};
 
-        let account = account_loader
-            .load_mango_account(&self.account)
+        let liqee = account_loader
+            .load_mango_account(&self.liqee)
+            .await
+            .unwrap();
+        let liqor = account_loader
+            .load_mango_account(&self.liqor)
             .await
             .unwrap();
 
-        let health_check_metas = derive_health_check_remaining_account_metas(
+        let tcs = liqee
+            .token_conditional_swap_by_index(self.index.into())
+            .unwrap()
+            .clone();
+
+        let instruction = Self::Instruction {
+            token_conditional_swap_index: self.index,
+            token_conditional_swap_id: tcs.id,
+            max_buy_token_to_liqee: self.max_buy_token_to_liqee,
+            max_sell_token_to_liqor: self.max_sell_token_to_liqor,
+            min_buy_token: self.min_buy_token,
+            min_taker_price: self.min_taker_price,
+        };
+
+        let health_check_metas = derive_liquidation_remaining_account_metas(
             account_loader,
-            &account,
-            None,
-            false,
-            None,
+            &liqee,
+            &liqor,
+            tcs.buy_token_index,
+            0,
+            tcs.sell_token_index,
+            0,
         )
         .await;
 
         let accounts = Self::Accounts {
-            group: account.fixed.group,
-            instructions: solana_program::sysvar::instructions::id(),
-            account: self.account,
+            group: liqee.fixed.group,
+            liqee: self.liqee,
+            liqor: self.liqor,
+            liqor_authority: self.liqor_owner.pubkey(),
         };
 
         let mut instruction = make_instruction(program_id, &accounts, &instruction);
         instruction.accounts.extend(health_check_metas.into_iter());
-
         (accounts, instruction)
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![]
+        vec![self.liqor_owner]
     }
 }
 
-pub struct HealthRegionEndInstruction {
-    pub account: Pubkey,
-    pub affected_bank: Option<Pubkey>,
+#[derive(Clone)]
+pub struct TokenConditionalSwapStartInstruction {
+    pub liqee: Pubkey,
+    pub liqor: Pubkey,
+    pub liqor_owner: TestKeypair,
+    pub index: u8,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for HealthRegionEndInstruction {
-    type Accounts = mango_v4::accounts::HealthRegionEnd;
-    type Instruction = mango_v4::instruction::HealthRegionEnd;
+impl ClientInstruction for TokenConditionalSwapStartInstruction {
+    type Accounts = mango_v4::accounts::TokenConditionalSwapStart;
+    type Instruction = mango_v4::instruction::TokenConditionalSwapStart;
     async fn to_instruction(
         &self,
         account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
-        let instruction = Self::Instruction {};
 
-        let account = account_loader
-            .load_mango_account(&self.account)
+        let liqee = account_loader
+            .load_mango_account(&self.liqee)
             .await
             .unwrap();
 
+        let tcs = liqee
+            .token_conditional_swap_by_index(self.index.into())
+            .unwrap()
+            .clone();
+
+        let sell_mint_info =
+            get_mint_info_by_token_index(account_loader, &liqee, tcs.sell_token_index).await;
+
+        let instruction = Self::Instruction {
+            token_conditional_swap_index: self.index,
+            token_conditional_swap_id: tcs.id,
+        };
+
         let health_check_metas = derive_health_check_remaining_account_metas(
             account_loader,
-            &account,
-            self.affected_bank,
-            false,
+            &liqee,
+            Some(sell_mint_info.first_bank()),
+            true,
             None,
         )
         .await;
 
         let accounts = Self::Accounts {
-            account: self.account,
+            group: liqee.fixed.group,
+            liqee: self.liqee,
+            liqor: self.liqor,
+            liqor_authority: self.liqor_owner.pubkey(),
         };
 
         let mut instruction = make_instruction(program_id, &accounts, &instruction);
         instruction.accounts.extend(health_check_metas.into_iter());
-
         (accounts, instruction)
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![]
+        vec![self.liqor_owner]
     }
 }
 
-pub struct AltSetInstruction {
-    pub group: Pubkey,
-    pub admin: TestKeypair,
-    pub address_lookup_table: Pubkey,
-    pub index: u8,
+#[derive(Clone)]
+pub struct TokenChargeCollateralFeesInstruction {
+    pub account: Pubkey,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for AltSetInstruction {
-    type Accounts = mango_v4::accounts::AltSet;
-    type Instruction = mango_v4::instruction::AltSet;
+impl ClientInstruction for TokenChargeCollateralFeesInstruction {
+    type Accounts = mango_v4::accounts::TokenChargeCollateralFees;
+    type Instruction = mango_v4::instruction::TokenChargeCollateralFees;
     async fn to_instruction(
         &self,
-        _account_loader: &(impl ClientAccountLoader + 'async_trait),
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
-        let instruction = Self::Instruction { index: self.index };
-
-        let accounts = Self::Accounts {
-            group: self.group,
-            admin: self.admin.pubkey(),
-            address_lookup_table: self.address_lookup_table,
-        };
 
-        let instruction = make_instruction(program_id, &accounts, &instruction);
-        (accounts, instruction)
-    }
+        let account = account_loader
+            .load_mango_account(&self.account)
+            .await
+            .unwrap();
 
-    fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.admin]
-    }
-}
+        let instruction = Self::Instruction {};
 
-pub struct AltExtendInstruction {
-    pub group: Pubkey,
-    pub admin: TestKeypair,
-    pub payer: TestKeypair,
-    pub address_lookup_table: Pubkey,
-    pub index: u8,
-    pub new_addresses: Vec<Pubkey>,
-}
-#[async_trait::async_trait(?Send)]
-impl ClientInstruction for AltExtendInstruction {
-    type Accounts = mango_v4::accounts::AltExtend;
-    type Instruction = mango_v4::instruction::AltExtend;
-    async fn to_instruction(
-        &self,
-        _account_loader: &(impl ClientAccountLoader + 'async_trait),
-    ) -> (Self::Accounts, instruction::Instruction) {
-        let program_id = mango_v4::id();
-        let instruction = Self::Instruction {
-            index: self.index,
-            new_addresses: self.new_addresses.clone(),
-        };
+        let health_check_metas =
+            derive_health_check_remaining_account_metas(account_loader, &account, None, true, None)
+                .await;
 
         let accounts = Self::Accounts {
-            group: self.group,
-            admin: self.admin.pubkey(),
-            payer: self.payer.pubkey(),
-            address_lookup_table: self.address_lookup_table,
+            group: account.fixed.group,
+            account: self.account,
         };
 
-        let instruction = make_instruction(program_id, &accounts, &instruction);
+        let mut instruction = make_instruction(program_id, &accounts, &instruction);
+        instruction.accounts.extend(health_check_metas.into_iter());
         (accounts, instruction)
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.admin, self.payer]
+        vec![]
     }
 }
 
 #[derive(Clone)]
-pub struct TokenConditionalSwapCreateInstruction {
-    pub account: Pubkey,
-    pub owner: TestKeypair,
-    pub buy_mint: Pubkey,
-    pub sell_mint: Pubkey,
-    pub max_buy: u64,
-    pub max_sell: u64,
-    pub price_lower_limit: f64,
-    pub price_upper_limit: f64,
-    pub price_premium_rate: f64,
-    pub allow_creating_deposits: bool,
-    pub allow_creating_borrows: bool,
+pub struct HealthAccountSkipping<T: ClientInstruction> {
+    pub inner: T,
+    pub skip_banks: Vec<Pubkey>,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for TokenConditionalSwapCreateInstruction {
-    type Accounts = mango_v4::accounts::TokenConditionalSwapCreate;
-    type Instruction = mango_v4::instruction::TokenConditionalSwapCreateV2;
+impl<T: ClientInstruction> ClientInstruction for HealthAccountSkipping<T> {
+    type Accounts = T::Accounts;
+    type Instruction = T::Instruction;
     async fn to_instruction(
         &self,
         account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
-        let program_id = mango_v4::id();
-        let instruction = Self::Instruction {
-            max_buy: self.max_buy,
-            max_sell: self.max_sell,
-            expiry_timestamp: u64::MAX,
-            price_lower_limit: self.price_lower_limit,
-            price_upper_limit: self.price_upper_limit,
-            price_premium_rate: self.price_premium_rate,
-            allow_creating_deposits: self.allow_creating_deposits,
-            allow_creating_borrows: self.allow_creating_borrows,
-            display_price_style: TokenConditionalSwapDisplayPriceStyle::SellTokenPerBuyToken,
-            intention: TokenConditionalSwapIntention::Unknown,
-        };
+        let (accounts, mut instruction) = self.inner.to_instruction(account_loader).await;
 
-        let account = account_loader
-            .load_mango_account(&self.account)
-            .await
-            .unwrap();
+        let ams = &mut instruction.accounts;
+        for bank_pk in &self.skip_banks {
+            let bank_pos =
+                ams.len() - 1 - ams.iter().rev().position(|m| m.pubkey == *bank_pk).unwrap();
+            ams.remove(bank_pos);
 
-        let buy_mint_info_address = Pubkey::find_program_address(
-            &[
-                b"MintInfo".as_ref(),
-                account.fixed.group.as_ref(),
-                self.buy_mint.as_ref(),
-            ],
-            &program_id,
-        )
-        .0;
-        let sell_mint_info_address = Pubkey::find_program_address(
-            &[
-                b"MintInfo".as_ref(),
-                account.fixed.group.as_ref(),
-                self.sell_mint.as_ref(),
-            ],
-            &program_id,
-        )
-        .0;
-        let buy_mint_info: MintInfo = account_loader.load(&buy_mint_info_address).await.unwrap();
-        let sell_mint_info: MintInfo = account_loader.load(&sell_mint_info_address).await.unwrap();
+            let bank = account_loader.load::<Bank>(&bank_pk).await.unwrap();
+            let oracle_pk = bank.oracle;
 
-        let accounts = Self::Accounts {
-            group: account.fixed.group,
-            account: self.account,
-            authority: self.owner.pubkey(),
-            buy_bank: buy_mint_info.first_bank(),
-            sell_bank: sell_mint_info.first_bank(),
-        };
+            let oracle_pos = bank_pos
+                + ams[bank_pos..]
+                    .iter()
+                    .position(|m| m.pubkey == oracle_pk)
+                    .unwrap();
+            ams.remove(oracle_pos);
+        }
 
-        let instruction = make_instruction(program_id, &accounts, &instruction);
         (accounts, instruction)
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.owner]
+        self.inner.signers()
     }
 }
 
-#[derive(Clone)]
-pub struct TokenConditionalSwapCreateLinearAuctionInstruction {
+#[derive(Default)]
+pub struct SequenceCheckInstruction {
     pub account: Pubkey,
     pub owner: TestKeypair,
-    pub buy_mint: Pubkey,
-    pub sell_mint: Pubkey,
-    pub max_buy: u64,
-    pub max_sell: u64,
-    pub price_start: f64,
-    pub price_end: f64,
-    pub allow_creating_deposits: bool,
-    pub allow_creating_borrows: bool,
-    pub start_timestamp: u64,
-    pub duration_seconds: u64,
-    pub expiry_timestamp: u64,
+    pub expected_sequence_number: u8,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for TokenConditionalSwapCreateLinearAuctionInstruction {
-    type Accounts = mango_v4::accounts::TokenConditionalSwapCreate;
-    type Instruction = mango_v4::instruction::TokenConditionalSwapCreateLinearAuction;
+impl ClientInstruction for SequenceCheckInstruction {
+    type Accounts = mango_v4::accounts::SequenceCheck;
+    type Instruction = mango_v4::instruction::SequenceCheck;
     async fn to_instruction(
         &self,
         account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
         let instruction = Self::Instruction {
-            max_buy: self.max_buy,
-            max_sell: self.max_sell,
-            expiry_timestamp: self.expiry_timestamp,
-            price_start: self.price_start,
-            price_end: self.price_end,
-            allow_creating_deposits: self.allow_creating_deposits,
-            allow_creating_borrows: self.allow_creating_borrows,
-            display_price_style: TokenConditionalSwapDisplayPriceStyle::SellTokenPerBuyToken,
-            start_timestamp: self.start_timestamp,
-            duration_seconds: self.duration_seconds,
+            expected_sequence_number: self.expected_sequence_number,
         };
 
         let account = account_loader
@@ -4890,33 +6570,10 @@ impl ClientInstruction for TokenConditionalSwapCreateLinearAuctionInstruction {
             .await
             .unwrap();
 
-        let buy_mint_info_address = Pubkey::find_program_address(
-            &[
-                b"MintInfo".as_ref(),
-                account.fixed.group.as_ref(),
-                self.buy_mint.as_ref(),
-            ],
-            &program_id,
-        )
-        .0;
-        let sell_mint_info_address = Pubkey::find_program_address(
-            &[
-                b"MintInfo".as_ref(),
-                account.fixed.group.as_ref(),
-                self.sell_mint.as_ref(),
-            ],
-            &program_id,
-        )
-        .0;
-        let buy_mint_info: MintInfo = account_loader.load(&buy_mint_info_address).await.unwrap();
-        let sell_mint_info: MintInfo = account_loader.load(&sell_mint_info_address).await.unwrap();
-
         let accounts = Self::Accounts {
             group: account.fixed.group,
             account: self.account,
-            authority: self.owner.pubkey(),
-            buy_bank: buy_mint_info.first_bank(),
-            sell_bank: sell_mint_info.first_bank(),
+            owner: self.owner.pubkey(),
         };
 
         let instruction = make_instruction(program_id, &accounts, &instruction);
@@ -4928,42 +6585,24 @@ impl ClientInstruction for TokenConditionalSwapCreateLinearAuctionInstruction {
     }
 }
 
-#[derive(Clone)]
-pub struct TokenConditionalSwapCreatePremiumAuctionInstruction {
+pub struct HealthCheckInstruction {
     pub account: Pubkey,
     pub owner: TestKeypair,
-    pub buy_mint: Pubkey,
-    pub sell_mint: Pubkey,
-    pub max_buy: u64,
-    pub max_sell: u64,
-    pub price_lower_limit: f64,
-    pub price_upper_limit: f64,
-    pub max_price_premium_rate: f64,
-    pub allow_creating_deposits: bool,
-    pub allow_creating_borrows: bool,
-    pub duration_seconds: u64,
+    pub min_health_value: f64,
+    pub check_kind: HealthCheckKind,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for TokenConditionalSwapCreatePremiumAuctionInstruction {
-    type Accounts = mango_v4::accounts::TokenConditionalSwapCreate;
-    type Instruction = mango_v4::instruction::TokenConditionalSwapCreatePremiumAuction;
+impl ClientInstruction for HealthCheckInstruction {
+    type Accounts = mango_v4::accounts::HealthCheck;
+    type Instruction = mango_v4::instruction::HealthCheck;
     async fn to_instruction(
         &self,
         account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
         let instruction = Self::Instruction {
-            max_buy: self.max_buy,
-            max_sell: self.max_sell,
-            expiry_timestamp: u64::MAX,
-            price_lower_limit: self.price_lower_limit,
-            price_upper_limit: self.price_upper_limit,
-            max_price_premium_rate: self.max_price_premium_rate,
-            allow_creating_deposits: self.allow_creating_deposits,
-            allow_creating_borrows: self.allow_creating_borrows,
-            display_price_style: TokenConditionalSwapDisplayPriceStyle::SellTokenPerBuyToken,
-            intention: TokenConditionalSwapIntention::Unknown,
-            duration_seconds: self.duration_seconds,
+            min_health_value: self.min_health_value,
+            check_kind: self.check_kind,
         };
 
         let account = account_loader
@@ -4971,33 +6610,78 @@ impl ClientInstruction for TokenConditionalSwapCreatePremiumAuctionInstruction {
             .await
             .unwrap();
 
-        let buy_mint_info_address = Pubkey::find_program_address(
-            &[
-                b"MintInfo".as_ref(),
-                account.fixed.group.as_ref(),
-                self.buy_mint.as_ref(),
-            ],
-            &program_id,
-        )
-        .0;
-        let sell_mint_info_address = Pubkey::find_program_address(
-            &[
-                b"MintInfo".as_ref(),
-                account.fixed.group.as_ref(),
-                self.sell_mint.as_ref(),
-            ],
-            &program_id,
-        )
-        .0;
-        let buy_mint_info: MintInfo = account_loader.load(&buy_mint_info_address).await.unwrap();
-        let sell_mint_info: MintInfo = account_loader.load(&sell_mint_info_address).await.unwrap();
-
         let accounts = Self::Accounts {
             group: account.fixed.group,
             account: self.account,
-            authority: self.owner.pubkey(),
-            buy_bank: buy_mint_info.first_bank(),
-            sell_bank: sell_mint_info.first_bank(),
+        };
+
+        let health_check_metas = derive_health_check_remaining_account_metas(
+            account_loader,
+            &account,
+            None,
+            false,
+            None,
+        )
+        .await;
+
+        let mut instruction = make_instruction(program_id, &accounts, &instruction);
+        instruction.accounts.extend(health_check_metas.into_iter());
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![]
+    }
+}
+
+pub fn fixed_term_market_address(group: Pubkey, market_index: FixedTermMarketIndex) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"FixedTermMarket".as_ref(),
+            group.as_ref(),
+            &market_index.to_le_bytes(),
+        ],
+        &mango_v4::id(),
+    )
+    .0
+}
+
+pub struct FixedTermMarketCreateInstruction {
+    pub group: Pubkey,
+    pub admin: TestKeypair,
+    pub payer: TestKeypair,
+    pub bank: Pubkey,
+    pub market_index: FixedTermMarketIndex,
+    pub maturity_timestamp: u64,
+    pub lend_rate: f32,
+    pub borrow_rate: f32,
+    pub max_lent_native: u64,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for FixedTermMarketCreateInstruction {
+    type Accounts = mango_v4::accounts::FixedTermMarketCreate;
+    type Instruction = mango_v4::instruction::FixedTermMarketCreate;
+    async fn to_instruction(
+        &self,
+        _account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+        let instruction = Self::Instruction {
+            market_index: self.market_index,
+            name: "UUU-fixed".to_string(),
+            maturity_timestamp: self.maturity_timestamp,
+            lend_rate: self.lend_rate,
+            borrow_rate: self.borrow_rate,
+            max_lent_native: self.max_lent_native,
+        };
+
+        let accounts = Self::Accounts {
+            group: self.group,
+            admin: self.admin.pubkey(),
+            bank: self.bank,
+            fixed_term_market: fixed_term_market_address(self.group, self.market_index),
+            payer: self.payer.pubkey(),
+            system_program: System::id(),
         };
 
         let instruction = make_instruction(program_id, &accounts, &instruction);
@@ -5005,51 +6689,56 @@ impl ClientInstruction for TokenConditionalSwapCreatePremiumAuctionInstruction {
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.owner]
+        vec![self.admin, self.payer]
     }
 }
 
-#[derive(Clone)]
-pub struct TokenConditionalSwapCancelInstruction {
+pub struct FixedTermLendInstruction {
     pub account: Pubkey,
     pub owner: TestKeypair,
-    pub index: u8,
-    pub id: u64,
+    pub fixed_term_market: Pubkey,
+    pub amount: u64,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for TokenConditionalSwapCancelInstruction {
-    type Accounts = mango_v4::accounts::TokenConditionalSwapCancel;
-    type Instruction = mango_v4::instruction::TokenConditionalSwapCancel;
+impl ClientInstruction for FixedTermLendInstruction {
+    type Accounts = mango_v4::accounts::FixedTermLend;
+    type Instruction = mango_v4::instruction::FixedTermLend;
     async fn to_instruction(
         &self,
         account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
         let instruction = Self::Instruction {
-            token_conditional_swap_index: self.index,
-            token_conditional_swap_id: self.id,
+            amount: self.amount,
         };
 
         let account = account_loader
             .load_mango_account(&self.account)
             .await
             .unwrap();
-        let tcs = account.token_conditional_swap_by_id(self.id).unwrap().1;
+        let market: FixedTermMarket = account_loader.load(&self.fixed_term_market).await.unwrap();
+        let mint_info =
+            get_mint_info_by_token_index(account_loader, &account, market.token_index).await;
 
-        let buy_mint_info =
-            get_mint_info_by_token_index(account_loader, &account, tcs.buy_token_index).await;
-        let sell_mint_info =
-            get_mint_info_by_token_index(account_loader, &account, tcs.sell_token_index).await;
+        let health_check_metas = derive_health_check_remaining_account_metas(
+            account_loader,
+            &account,
+            None,
+            false,
+            None,
+        )
+        .await;
 
         let accounts = Self::Accounts {
             group: account.fixed.group,
             account: self.account,
-            authority: self.owner.pubkey(),
-            buy_bank: buy_mint_info.first_bank(),
-            sell_bank: sell_mint_info.first_bank(),
+            owner: self.owner.pubkey(),
+            fixed_term_market: self.fixed_term_market,
+            bank: mint_info.first_bank(),
         };
 
-        let instruction = make_instruction(program_id, &accounts, &instruction);
+        let mut instruction = make_instruction(program_id, &accounts, &instruction);
+        instruction.accounts.extend(health_check_metas.into_iter());
         (accounts, instruction)
     }
 
@@ -5058,66 +6747,49 @@ impl ClientInstruction for TokenConditionalSwapCancelInstruction {
     }
 }
 
-#[derive(Clone)]
-pub struct TokenConditionalSwapTriggerInstruction {
-    pub liqee: Pubkey,
-    pub liqor: Pubkey,
-    pub liqor_owner: TestKeypair,
-    pub index: u8,
-    pub max_buy_token_to_liqee: u64,
-    pub max_sell_token_to_liqor: u64,
-    pub min_buy_token: u64,
-    pub min_taker_price: f32,
+pub struct FixedTermBorrowInstruction {
+    pub account: Pubkey,
+    pub owner: TestKeypair,
+    pub fixed_term_market: Pubkey,
+    pub amount: u64,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for TokenConditionalSwapTriggerInstruction {
-    type Accounts = mango_v4::accounts::TokenConditionalSwapTrigger;
-    type Instruction = mango_v4::instruction::TokenConditionalSwapTriggerV2;
+impl ClientInstruction for FixedTermBorrowInstruction {
+    type Accounts = mango_v4::accounts::FixedTermBorrow;
+    type Instruction = mango_v4::instruction::FixedTermBorrow;
     async fn to_instruction(
         &self,
         account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
+        let instruction = Self::Instruction {
+            amount: self.amount,
+        };
 
-        let liqee = account_loader
-            .load_mango_account(&self.liqee)
-            .await
-            .unwrap();
-        let liqor = account_loader
-            .load_mango_account(&self.liqor)
+        let account = account_loader
+            .load_mango_account(&self.account)
             .await
             .unwrap();
+        let market: FixedTermMarket = account_loader.load(&self.fixed_term_market).await.unwrap();
+        let mint_info =
+            get_mint_info_by_token_index(account_loader, &account, market.token_index).await;
 
-        let tcs = liqee
-            .token_conditional_swap_by_index(self.index.into())
-            .unwrap()
-            .clone();
-
-        let instruction = Self::Instruction {
-            token_conditional_swap_index: self.index,
-            token_conditional_swap_id: tcs.id,
-            max_buy_token_to_liqee: self.max_buy_token_to_liqee,
-            max_sell_token_to_liqor: self.max_sell_token_to_liqor,
-            min_buy_token: self.min_buy_token,
-            min_taker_price: self.min_taker_price,
-        };
-
-        let health_check_metas = derive_liquidation_remaining_account_metas(
+        let health_check_metas = derive_health_check_remaining_account_metas(
             account_loader,
-            &liqee,
-            &liqor,
-            tcs.buy_token_index,
-            0,
-            tcs.sell_token_index,
-            0,
+            &account,
+            Some(mint_info.first_bank()),
+            false,
+            None,
         )
         .await;
 
         let accounts = Self::Accounts {
-            group: liqee.fixed.group,
-            liqee: self.liqee,
-            liqor: self.liqor,
-            liqor_authority: self.liqor_owner.pubkey(),
+            group: account.fixed.group,
+            account: self.account,
+            owner: self.owner.pubkey(),
+            fixed_term_market: self.fixed_term_market,
+            bank: mint_info.first_bank(),
+            oracle: mint_info.oracle,
         };
 
         let mut instruction = make_instruction(program_id, &accounts, &instruction);
@@ -5126,59 +6798,47 @@ impl ClientInstruction for TokenConditionalSwapTriggerInstruction {
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.liqor_owner]
+        vec![self.owner]
     }
 }
 
-#[derive(Clone)]
-pub struct TokenConditionalSwapStartInstruction {
-    pub liqee: Pubkey,
-    pub liqor: Pubkey,
-    pub liqor_owner: TestKeypair,
-    pub index: u8,
+pub struct FixedTermSettleInstruction {
+    pub account: Pubkey,
+    pub fixed_term_market: Pubkey,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for TokenConditionalSwapStartInstruction {
-    type Accounts = mango_v4::accounts::TokenConditionalSwapStart;
-    type Instruction = mango_v4::instruction::TokenConditionalSwapStart;
+impl ClientInstruction for FixedTermSettleInstruction {
+    type Accounts = mango_v4::accounts::FixedTermSettle;
+    type Instruction = mango_v4::instruction::FixedTermSettle;
     async fn to_instruction(
         &self,
         account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
+        let instruction = Self::Instruction {};
 
-        let liqee = account_loader
-            .load_mango_account(&self.liqee)
+        let account = account_loader
+            .load_mango_account(&self.account)
             .await
             .unwrap();
-
-        let tcs = liqee
-            .token_conditional_swap_by_index(self.index.into())
-            .unwrap()
-            .clone();
-
-        let sell_mint_info =
-            get_mint_info_by_token_index(account_loader, &liqee, tcs.sell_token_index).await;
-
-        let instruction = Self::Instruction {
-            token_conditional_swap_index: self.index,
-            token_conditional_swap_id: tcs.id,
-        };
+        let market: FixedTermMarket = account_loader.load(&self.fixed_term_market).await.unwrap();
+        let mint_info =
+            get_mint_info_by_token_index(account_loader, &account, market.token_index).await;
 
         let health_check_metas = derive_health_check_remaining_account_metas(
             account_loader,
-            &liqee,
-            Some(sell_mint_info.first_bank()),
-            true,
+            &account,
+            None,
+            false,
             None,
         )
         .await;
 
         let accounts = Self::Accounts {
-            group: liqee.fixed.group,
-            liqee: self.liqee,
-            liqor: self.liqor,
-            liqor_authority: self.liqor_owner.pubkey(),
+            group: account.fixed.group,
+            account: self.account,
+            fixed_term_market: self.fixed_term_market,
+            bank: mint_info.first_bank(),
         };
 
         let mut instruction = make_instruction(program_id, &accounts, &instruction);
@@ -5187,107 +6847,165 @@ impl ClientInstruction for TokenConditionalSwapStartInstruction {
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![self.liqor_owner]
+        vec![]
     }
 }
 
-#[derive(Clone)]
-pub struct TokenChargeCollateralFeesInstruction {
+pub struct PerpScheduledOrderCreateInstruction {
     pub account: Pubkey,
+    pub owner: TestKeypair,
+    pub perp_market: Pubkey,
+    pub side: Side,
+    pub order_type: PerpScheduledOrderType,
+    pub max_base_lots: i64,
+    pub child_base_lots: i64,
+    pub price_lots: i64,
+    pub interval_seconds: u64,
+    pub expiry_timestamp: u64,
+    pub reduce_only: bool,
+}
+impl Default for PerpScheduledOrderCreateInstruction {
+    fn default() -> Self {
+        Self {
+            account: Pubkey::default(),
+            owner: TestKeypair::default(),
+            perp_market: Pubkey::default(),
+            side: Side::Bid,
+            order_type: PerpScheduledOrderType::Twap,
+            max_base_lots: 0,
+            child_base_lots: 0,
+            price_lots: 0,
+            interval_seconds: 0,
+            expiry_timestamp: u64::MAX,
+            reduce_only: false,
+        }
+    }
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for TokenChargeCollateralFeesInstruction {
-    type Accounts = mango_v4::accounts::TokenChargeCollateralFees;
-    type Instruction = mango_v4::instruction::TokenChargeCollateralFees;
+impl ClientInstruction for PerpScheduledOrderCreateInstruction {
+    type Accounts = mango_v4::accounts::PerpScheduledOrderCreate;
+    type Instruction = mango_v4::instruction::PerpScheduledOrderCreate;
     async fn to_instruction(
         &self,
         account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
+        let instruction = Self::Instruction {
+            side: self.side,
+            order_type: self.order_type,
+            max_base_lots: self.max_base_lots,
+            child_base_lots: self.child_base_lots,
+            price_lots: self.price_lots,
+            interval_seconds: self.interval_seconds,
+            expiry_timestamp: self.expiry_timestamp,
+            reduce_only: self.reduce_only,
+        };
 
         let account = account_loader
             .load_mango_account(&self.account)
             .await
             .unwrap();
 
-        let instruction = Self::Instruction {};
-
-        let health_check_metas =
-            derive_health_check_remaining_account_metas(account_loader, &account, None, true, None)
-                .await;
-
         let accounts = Self::Accounts {
             group: account.fixed.group,
             account: self.account,
+            authority: self.owner.pubkey(),
+            perp_market: self.perp_market,
         };
 
-        let mut instruction = make_instruction(program_id, &accounts, &instruction);
-        instruction.accounts.extend(health_check_metas.into_iter());
+        let instruction = make_instruction(program_id, &accounts, &instruction);
         (accounts, instruction)
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![]
+        vec![self.owner]
     }
 }
 
-#[derive(Clone)]
-pub struct HealthAccountSkipping<T: ClientInstruction> {
-    pub inner: T,
-    pub skip_banks: Vec<Pubkey>,
+pub struct PerpConditionalOrderCreateInstruction {
+    pub account: Pubkey,
+    pub owner: TestKeypair,
+    pub perp_market: Pubkey,
+    pub side: Side,
+    pub order_type: PerpScheduledOrderType,
+    pub max_base_lots: i64,
+    pub trigger_price_lots: i64,
+    pub max_slippage: f32,
+    pub expiry_timestamp: u64,
+}
+impl Default for PerpConditionalOrderCreateInstruction {
+    fn default() -> Self {
+        Self {
+            account: Pubkey::default(),
+            owner: TestKeypair::default(),
+            perp_market: Pubkey::default(),
+            side: Side::Ask,
+            order_type: PerpScheduledOrderType::StopLoss,
+            max_base_lots: i64::MAX,
+            trigger_price_lots: 0,
+            max_slippage: 0.0,
+            expiry_timestamp: u64::MAX,
+        }
+    }
 }
 #[async_trait::async_trait(?Send)]
-impl<T: ClientInstruction> ClientInstruction for HealthAccountSkipping<T> {
-    type Accounts = T::Accounts;
-    type Instruction = T::Instruction;
+impl ClientInstruction for PerpConditionalOrderCreateInstruction {
+    type Accounts = mango_v4::accounts::PerpConditionalOrderCreate;
+    type Instruction = mango_v4::instruction::PerpConditionalOrderCreate;
     async fn to_instruction(
         &self,
         account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
-        let (accounts, mut instruction) = self.inner.to_instruction(account_loader).await;
-
-        let ams = &mut instruction.accounts;
-        for bank_pk in &self.skip_banks {
-            let bank_pos =
-                ams.len() - 1 - ams.iter().rev().position(|m| m.pubkey == *bank_pk).unwrap();
-            ams.remove(bank_pos);
+        let program_id = mango_v4::id();
+        let instruction = Self::Instruction {
+            side: self.side,
+            order_type: self.order_type,
+            max_base_lots: self.max_base_lots,
+            trigger_price_lots: self.trigger_price_lots,
+            max_slippage: self.max_slippage,
+            expiry_timestamp: self.expiry_timestamp,
+        };
 
-            let bank = account_loader.load::<Bank>(&bank_pk).await.unwrap();
-            let oracle_pk = bank.oracle;
+        let account = account_loader
+            .load_mango_account(&self.account)
+            .await
+            .unwrap();
 
-            let oracle_pos = bank_pos
-                + ams[bank_pos..]
-                    .iter()
-                    .position(|m| m.pubkey == oracle_pk)
-                    .unwrap();
-            ams.remove(oracle_pos);
-        }
+        let accounts = Self::Accounts {
+            group: account.fixed.group,
+            account: self.account,
+            authority: self.owner.pubkey(),
+            perp_market: self.perp_market,
+        };
 
+        let instruction = make_instruction(program_id, &accounts, &instruction);
         (accounts, instruction)
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        self.inner.signers()
+        vec![self.owner]
     }
 }
 
-#[derive(Default)]
-pub struct SequenceCheckInstruction {
+pub struct PerpScheduledOrderCancelInstruction {
     pub account: Pubkey,
     pub owner: TestKeypair,
-    pub expected_sequence_number: u8,
+    pub perp_market: Pubkey,
+    pub index: u8,
+    pub id: u64,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for SequenceCheckInstruction {
-    type Accounts = mango_v4::accounts::SequenceCheck;
-    type Instruction = mango_v4::instruction::SequenceCheck;
+impl ClientInstruction for PerpScheduledOrderCancelInstruction {
+    type Accounts = mango_v4::accounts::PerpScheduledOrderCancel;
+    type Instruction = mango_v4::instruction::PerpScheduledOrderCancel;
     async fn to_instruction(
         &self,
         account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
         let instruction = Self::Instruction {
-            expected_sequence_number: self.expected_sequence_number,
+            perp_scheduled_order_index: self.index,
+            perp_scheduled_order_id: self.id,
         };
 
         let account = account_loader
@@ -5295,10 +7013,15 @@ impl ClientInstruction for SequenceCheckInstruction {
             .await
             .unwrap();
 
+        let perp_market: PerpMarket = account_loader.load(&self.perp_market).await.unwrap();
+
         let accounts = Self::Accounts {
             group: account.fixed.group,
             account: self.account,
-            owner: self.owner.pubkey(),
+            authority: self.owner.pubkey(),
+            perp_market: self.perp_market,
+            bids: perp_market.bids,
+            asks: perp_market.asks,
         };
 
         let instruction = make_instruction(program_id, &accounts, &instruction);
@@ -5310,51 +7033,166 @@ impl ClientInstruction for SequenceCheckInstruction {
     }
 }
 
-pub struct HealthCheckInstruction {
+#[derive(Clone)]
+pub struct PerpScheduledOrderTriggerInstruction {
     pub account: Pubkey,
-    pub owner: TestKeypair,
-    pub min_health_value: f64,
-    pub check_kind: HealthCheckKind,
+    pub caller: Pubkey,
+    pub caller_owner: TestKeypair,
+    pub index: u8,
 }
 #[async_trait::async_trait(?Send)]
-impl ClientInstruction for HealthCheckInstruction {
-    type Accounts = mango_v4::accounts::HealthCheck;
-    type Instruction = mango_v4::instruction::HealthCheck;
+impl ClientInstruction for PerpScheduledOrderTriggerInstruction {
+    type Accounts = mango_v4::accounts::PerpScheduledOrderTrigger;
+    type Instruction = mango_v4::instruction::PerpScheduledOrderTrigger;
     async fn to_instruction(
         &self,
         account_loader: &(impl ClientAccountLoader + 'async_trait),
     ) -> (Self::Accounts, instruction::Instruction) {
         let program_id = mango_v4::id();
-        let instruction = Self::Instruction {
-            min_health_value: self.min_health_value,
-            check_kind: self.check_kind,
-        };
 
         let account = account_loader
             .load_mango_account(&self.account)
             .await
             .unwrap();
+        let scheduled = *account
+            .perp_scheduled_order_by_index(self.index.into())
+            .unwrap();
+
+        let instruction = Self::Instruction {
+            perp_scheduled_order_index: self.index,
+            perp_scheduled_order_id: scheduled.id,
+            limit: 10,
+        };
+
+        let perp_market_address = Pubkey::find_program_address(
+            &[
+                b"PerpMarket".as_ref(),
+                account.fixed.group.as_ref(),
+                &scheduled.market_index.to_le_bytes(),
+            ],
+            &program_id,
+        )
+        .0;
+        let perp_market: PerpMarket = account_loader.load(&perp_market_address).await.unwrap();
+        let settle_mint_info =
+            get_mint_info_by_token_index(account_loader, &account, perp_market.settle_token_index)
+                .await;
+
+        let health_check_metas = derive_health_check_remaining_account_metas(
+            account_loader,
+            &account,
+            Some(settle_mint_info.first_bank()),
+            true,
+            Some(scheduled.market_index),
+        )
+        .await;
 
         let accounts = Self::Accounts {
             group: account.fixed.group,
             account: self.account,
+            caller: self.caller,
+            caller_authority: self.caller_owner.pubkey(),
+            perp_market: perp_market_address,
+            bids: perp_market.bids,
+            asks: perp_market.asks,
+            event_queue: perp_market.event_queue,
+            oracle: perp_market.oracle,
+            settle_bank: settle_mint_info.first_bank(),
+        };
+
+        let mut instruction = make_instruction(program_id, &accounts, &instruction);
+        instruction.accounts.extend(health_check_metas.into_iter());
+        (accounts, instruction)
+    }
+
+    fn signers(&self) -> Vec<TestKeypair> {
+        vec![self.caller_owner]
+    }
+}
+
+pub struct PerpPlaceSpreadOrderInstruction {
+    pub account: Pubkey,
+    pub owner: TestKeypair,
+    pub perp_market_a: Pubkey,
+    pub side_a: Side,
+    pub price_lots_a: i64,
+    pub max_base_lots_a: i64,
+    pub perp_market_b: Pubkey,
+    pub side_b: Side,
+    pub price_lots_b: i64,
+    pub leg_b_ratio: f64,
+}
+#[async_trait::async_trait(?Send)]
+impl ClientInstruction for PerpPlaceSpreadOrderInstruction {
+    type Accounts = mango_v4::accounts::PerpPlaceSpreadOrder;
+    type Instruction = mango_v4::instruction::PerpPlaceSpreadOrder;
+    async fn to_instruction(
+        &self,
+        account_loader: &(impl ClientAccountLoader + 'async_trait),
+    ) -> (Self::Accounts, instruction::Instruction) {
+        let program_id = mango_v4::id();
+        let instruction = Self::Instruction {
+            side_a: self.side_a,
+            price_lots_a: self.price_lots_a,
+            max_base_lots_a: self.max_base_lots_a,
+            side_b: self.side_b,
+            price_lots_b: self.price_lots_b,
+            leg_b_ratio: self.leg_b_ratio,
+            limit: 10,
         };
 
+        let perp_market_a: PerpMarket = account_loader.load(&self.perp_market_a).await.unwrap();
+        let perp_market_b: PerpMarket = account_loader.load(&self.perp_market_b).await.unwrap();
+        let account = account_loader
+            .load_mango_account(&self.account)
+            .await
+            .unwrap();
+
+        // Both perp positions are created in the same order as in the instruction
+        let mut adjusted_account = account.clone();
+        adjusted_account
+            .ensure_perp_position(
+                perp_market_a.perp_market_index,
+                perp_market_a.settle_token_index,
+            )
+            .unwrap();
+        adjusted_account
+            .ensure_perp_position(
+                perp_market_b.perp_market_index,
+                perp_market_b.settle_token_index,
+            )
+            .unwrap();
         let health_check_metas = derive_health_check_remaining_account_metas(
             account_loader,
-            &account,
+            &adjusted_account,
             None,
             false,
             None,
         )
         .await;
 
+        let accounts = Self::Accounts {
+            group: account.fixed.group,
+            account: self.account,
+            owner: self.owner.pubkey(),
+            perp_market_a: self.perp_market_a,
+            bids_a: perp_market_a.bids,
+            asks_a: perp_market_a.asks,
+            event_queue_a: perp_market_a.event_queue,
+            oracle_a: perp_market_a.oracle,
+            perp_market_b: self.perp_market_b,
+            bids_b: perp_market_b.bids,
+            asks_b: perp_market_b.asks,
+            event_queue_b: perp_market_b.event_queue,
+            oracle_b: perp_market_b.oracle,
+        };
+
         let mut instruction = make_instruction(program_id, &accounts, &instruction);
         instruction.accounts.extend(health_check_metas.into_iter());
         (accounts, instruction)
     }
 
     fn signers(&self) -> Vec<TestKeypair> {
-        vec![]
+        vec![self.owner]
     }
 }
@@ -0,0 +1,81 @@
+#![allow(dead_code)]
+
+use std::sync::Arc;
+
+use bytemuck::Zeroable;
+use openbook_v2::state::Market;
+use solana_sdk::pubkey::Pubkey;
+
+use super::*;
+
+#[derive(Clone, Debug)]
+pub struct OpenbookV2MarketCookie {
+    pub market: Pubkey,
+    pub bids: Pubkey,
+    pub asks: Pubkey,
+    pub event_heap: Pubkey,
+    pub market_base_vault: Pubkey,
+    pub market_quote_vault: Pubkey,
+    pub base_mint: MintCookie,
+    pub quote_mint: MintCookie,
+}
+
+pub struct OpenbookV2Cookie {
+    pub solana: Arc<solana::SolanaCookie>,
+    pub program_id: Pubkey,
+}
+
+impl OpenbookV2Cookie {
+    /// Writes an openbook_v2 market account directly, without its book accounts.
+    ///
+    /// Enough for the Mango instructions that only read the market or create
+    /// open orders accounts. `modify` can change the market before it's stored.
+    pub async fn create_test_market(
+        &self,
+        base_mint: &MintCookie,
+        quote_mint: &MintCookie,
+        modify: impl FnOnce(&mut Market),
+    ) -> OpenbookV2MarketCookie {
+        let market = self
+            .solana
+            .create_account_for_type::<Market>(&self.program_id)
+            .await;
+        let (market_authority, _) =
+            Pubkey::find_program_address(&[b"Market".as_ref(), market.as_ref()], &self.program_id);
+        let market_base_vault = self
+            .solana
+            .create_token_account(&market_authority, base_mint.pubkey)
+            .await;
+        let market_quote_vault = self
+            .solana
+            .create_token_account(&market_authority, quote_mint.pubkey)
+            .await;
+
+        let mut data = Market::zeroed();
+        data.market_authority = market_authority;
+        data.base_mint = base_mint.pubkey;
+        data.quote_mint = quote_mint.pubkey;
+        data.base_decimals = base_mint.decimals;
+        data.quote_decimals = quote_mint.decimals;
+        data.base_lot_size = base_mint.base_lot as i64;
+        data.quote_lot_size = base_mint.quote_lot as i64;
+        data.bids = Pubkey::new_unique();
+        data.asks = Pubkey::new_unique();
+        data.event_heap = Pubkey::new_unique();
+        data.market_base_vault = market_base_vault;
+        data.market_quote_vault = market_quote_vault;
+        modify(&mut data);
+        self.solana.set_account(market, &data).await;
+
+        OpenbookV2MarketCookie {
+            market,
+            bids: data.bids,
+            asks: data.asks,
+            event_heap: data.event_heap,
+            market_base_vault,
+            market_quote_vault,
+            base_mint: *base_mint,
+            quote_mint: *quote_mint,
+        }
+    }
+}
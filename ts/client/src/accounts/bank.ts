@@ -10,11 +10,17 @@ export type TokenIndex = number & As<'token-index'>;
 export type OracleConfigDto = {
   confFilter: I80F48Dto;
   maxStalenessSlots: BN;
+  maxStalenessSeconds: BN;
+  riskIncreasingMaxStalenessSlots: BN;
+  riskIncreasingMaxStalenessSeconds: BN;
 };
 
 export type OracleConfig = {
   confFilter: I80F48;
   maxStalenessSlots: BN;
+  maxStalenessSeconds: BN;
+  riskIncreasingMaxStalenessSlots: BN;
+  riskIncreasingMaxStalenessSeconds: BN;
 };
 
 export type StablePriceModel = {
@@ -297,6 +303,11 @@ export class Bank implements BankForHealth {
     this.oracleConfig = {
       confFilter: I80F48.from(oracleConfig.confFilter),
       maxStalenessSlots: oracleConfig.maxStalenessSlots,
+      maxStalenessSeconds: oracleConfig.maxStalenessSeconds,
+      riskIncreasingMaxStalenessSlots:
+        oracleConfig.riskIncreasingMaxStalenessSlots,
+      riskIncreasingMaxStalenessSeconds:
+        oracleConfig.riskIncreasingMaxStalenessSeconds,
     } as OracleConfig;
     this.depositIndex = I80F48.from(depositIndex);
     this.borrowIndex = I80F48.from(borrowIndex);
@@ -1,4 +1,4 @@
-import { BN } from '@coral-xyz/anchor';
+import { BN, BorshAccountsCoder } from '@coral-xyz/anchor';
 import { utf8 } from '@coral-xyz/anchor/dist/cjs/utils/bytes';
 import { PublicKey } from '@solana/web3.js';
 import Big from 'big.js';
@@ -223,6 +223,11 @@ export class PerpMarket {
     this.oracleConfig = {
       confFilter: I80F48.from(oracleConfig.confFilter),
       maxStalenessSlots: oracleConfig.maxStalenessSlots,
+      maxStalenessSeconds: oracleConfig.maxStalenessSeconds,
+      riskIncreasingMaxStalenessSlots:
+        oracleConfig.riskIncreasingMaxStalenessSlots,
+      riskIncreasingMaxStalenessSeconds:
+        oracleConfig.riskIncreasingMaxStalenessSeconds,
     } as OracleConfig;
     this.maintBaseAssetWeight = I80F48.from(maintBaseAssetWeight);
     this.initBaseAssetWeight = I80F48.from(initBaseAssetWeight);
@@ -327,8 +332,18 @@ export class PerpMarket {
     forceReload = false,
   ): Promise<BookSide> {
     if (forceReload || !this._asks) {
-      const asks = await client.program.account.bookSide.fetch(this.asks);
-      this._asks = BookSide.from(client, this, BookSideType.asks, asks as any);
+      const ai = await client.program.provider.connection.getAccountInfo(
+        this.asks,
+      );
+      if (!ai) {
+        throw new Error(`Asks ${this.asks.toBase58()} not found!`);
+      }
+      this._asks = BookSide.from(
+        client,
+        this,
+        BookSideType.asks,
+        BookSide.decode(ai.data),
+      );
     }
     return this._asks;
   }
@@ -338,17 +353,31 @@ export class PerpMarket {
     forceReload = false,
   ): Promise<BookSide> {
     if (forceReload || !this._bids) {
-      const bids = await client.program.account.bookSide.fetch(this.bids);
-      this._bids = BookSide.from(client, this, BookSideType.bids, bids as any);
+      const ai = await client.program.provider.connection.getAccountInfo(
+        this.bids,
+      );
+      if (!ai) {
+        throw new Error(`Bids ${this.bids.toBase58()} not found!`);
+      }
+      this._bids = BookSide.from(
+        client,
+        this,
+        BookSideType.bids,
+        BookSide.decode(ai.data),
+      );
     }
     return this._bids;
   }
 
   public async loadEventQueue(client: MangoClient): Promise<PerpEventQueue> {
-    const eventQueue = await client.program.account.eventQueue.fetch(
+    const ai = await client.program.provider.connection.getAccountInfo(
       this.eventQueue,
     );
-    return new PerpEventQueue(client, eventQueue.header, eventQueue.buf);
+    if (!ai) {
+      throw new Error(`Event queue ${this.eventQueue.toBase58()} not found!`);
+    }
+    const { header, buf } = PerpEventQueue.decode(client, ai.data);
+    return new PerpEventQueue(client, header, buf);
   }
 
   public async loadFills(
@@ -613,6 +642,26 @@ interface OrderTreeRoot {
   leafCount: number;
 }
 
+// Defaults match MAX_ORDERTREE_NODES and MAX_NUM_EVENTS in the program
+export const PERP_DEFAULT_BOOKSIDE_CAPACITY = 1024;
+export const PERP_DEFAULT_EVENT_QUEUE_CAPACITY = 488;
+
+/**
+ * Size of a bookside account with `capacity` nodes, see BookSide::account_size()
+ */
+export function perpBookSideAccountSize(capacity: number): number {
+  // discriminator, header, nodes
+  return 8 + 832 + capacity * 120;
+}
+
+/**
+ * Size of an event queue account with `capacity` events, see EventQueue::account_size()
+ */
+export function perpEventQueueAccountSize(capacity: number): number {
+  // discriminator, header, events, reserved
+  return 8 + 16 + capacity * 208 + 64;
+}
+
 export class BookSide {
   private static INNER_NODE_TAG = 1;
   private static LEAF_NODE_TAG = 2;
@@ -663,6 +712,53 @@ export class BookSide {
     this.now = maxTimestamp;
   }
 
+  /**
+   * Decodes bookside account data of any capacity. The IDL layout of the BookSide
+   * account only covers PERP_DEFAULT_BOOKSIDE_CAPACITY nodes.
+   */
+  static decode(data: Buffer): {
+    roots: OrderTreeRoot[];
+    nodes: OrderTreeNodes;
+  } {
+    if (
+      BorshAccountsCoder.accountDiscriminator('BookSide').compare(
+        data.subarray(0, 8),
+      )
+    ) {
+      throw new Error('Not a bookside account!');
+    }
+    // discriminator, then roots, reserved roots and reserved bytes
+    const rootsOffset = 8;
+    const treeOffset = rootsOffset + 6 * 8 + 256;
+    // order tree type, padding, bump index, free list len and head, reserved bytes
+    const nodesOffset = treeOffset + 528;
+    const capacity = Math.floor(
+      (data.length - perpBookSideAccountSize(0)) / 120,
+    );
+    const root = (offset: number): OrderTreeRoot => ({
+      maybeNode: data.readUInt32LE(offset),
+      leafCount: data.readUInt32LE(offset + 4),
+    });
+    const nodes: any[] = [];
+    for (let i = 0; i < capacity; i++) {
+      const offset = nodesOffset + i * 120;
+      // like the AnyNode layout: tag, data, then padding for alignment
+      nodes.push({
+        tag: data.readUInt8(offset),
+        data: Array.from(data.subarray(offset + 1, offset + 112)),
+      });
+    }
+    return {
+      roots: [root(rootsOffset), root(rootsOffset + 8)],
+      nodes: {
+        bumpIndex: data.readUInt32LE(treeOffset + 4),
+        freeListLen: data.readUInt32LE(treeOffset + 8),
+        freeListHead: data.readUInt32LE(treeOffset + 12),
+        nodes: nodes as [any],
+      },
+    };
+  }
+
   static getPriceFromKey(key: BN): BN {
     return key.ushrn(64);
   }
@@ -1076,6 +1172,43 @@ export class PerpEventQueue {
     });
   }
 
+  /**
+   * Decodes event queue account data of any capacity. The IDL layout of the EventQueue
+   * account only covers PERP_DEFAULT_EVENT_QUEUE_CAPACITY events.
+   */
+  static decode(
+    client: MangoClient,
+    data: Buffer,
+  ): {
+    header: { head: number; count: number; seqNum: BN };
+    buf: { eventType: number; padding: number[] }[];
+  } {
+    if (
+      BorshAccountsCoder.accountDiscriminator('EventQueue').compare(
+        data.subarray(0, 8),
+      )
+    ) {
+      throw new Error('Not an event queue account!');
+    }
+    const header = (client.program as any)._coder.types.typeLayouts
+      .get('EventQueueHeader')
+      .decode(data, 8);
+    // discriminator and header, then the events and reserved bytes
+    const eventsOffset = 8 + 16;
+    const capacity = Math.floor(
+      (data.length - perpEventQueueAccountSize(0)) / 208,
+    );
+    const buf: { eventType: number; padding: number[] }[] = [];
+    for (let i = 0; i < capacity; i++) {
+      const offset = eventsOffset + i * 208;
+      buf.push({
+        eventType: data.readUInt8(offset),
+        padding: Array.from(data.subarray(offset + 1, offset + 208)),
+      });
+    }
+    return { header, buf };
+  }
+
   public getUnconsumedEvents(): (OutEvent | FillEvent | LiquidateEvent)[] {
     const events: (OutEvent | FillEvent | LiquidateEvent)[] = [];
     const head = this.head;
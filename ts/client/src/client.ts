@@ -53,12 +53,16 @@ import { StubOracle, createFallbackOracleMap } from './accounts/oracle';
 import {
   FillEvent,
   OutEvent,
+  PERP_DEFAULT_BOOKSIDE_CAPACITY,
+  PERP_DEFAULT_EVENT_QUEUE_CAPACITY,
   PerpEventQueue,
   PerpMarket,
   PerpMarketIndex,
   PerpOrderSide,
   PerpOrderType,
   PerpSelfTradeBehavior,
+  perpBookSideAccountSize,
+  perpEventQueueAccountSize,
 } from './accounts/perp';
 import {
   MarketIndex,
@@ -612,6 +616,17 @@ export class MangoClient {
         params.collateralFeePerDay,
         params.forceWithdraw,
         params.tier,
+        params.circuitBreakerMaxPriceMove,
+        params.circuitBreakerCooldownSeconds,
+        params.resetCircuitBreaker ?? false,
+        params.interestRateModel,
+        params.interestControllerKp,
+        params.interestControllerKi,
+        params.interestControllerMaxDailyChange,
+        params.borrowLimit,
+        params.isolatedCollateral,
+        params.isolatedBorrowTokenIndexes,
+        params.isolatedDebtCeilingQuote,
       )
       .accounts({
         group: group.publicKey,
@@ -2740,17 +2755,16 @@ export class MangoClient {
     settlePnlLimitWindowSize: number,
     positivePnlLiquidationFee: number,
     platformLiquidationFee: number,
+    booksideCapacity = PERP_DEFAULT_BOOKSIDE_CAPACITY,
+    eventQueueCapacity = PERP_DEFAULT_EVENT_QUEUE_CAPACITY,
+    openingAuctionDuration = 0,
   ): Promise<MangoSignatureStatus> {
     const bids = new Keypair();
     const asks = new Keypair();
     const eventQueue = new Keypair();
 
-    const bookSideSize = (this.program as any)._coder.accounts.size(
-      (this.program.account.bookSide as any)._idlAccount,
-    );
-    const eventQueueSize = (this.program as any)._coder.accounts.size(
-      (this.program.account.eventQueue as any)._idlAccount,
-    );
+    const bookSideSize = perpBookSideAccountSize(booksideCapacity);
+    const eventQueueSize = perpEventQueueAccountSize(eventQueueCapacity);
 
     const ix = await this.program.methods
       .perpCreateMarket(
@@ -2782,6 +2796,9 @@ export class MangoClient {
         new BN(settlePnlLimitWindowSize),
         positivePnlLiquidationFee,
         platformLiquidationFee,
+        booksideCapacity,
+        eventQueueCapacity,
+        openingAuctionDuration,
       )
       .accounts({
         group: group.publicKey,
@@ -2878,6 +2895,9 @@ export class MangoClient {
         params.name,
         params.forceClose,
         params.platformLiquidationFee,
+        params.fundingStablePriceWeight,
+        params.settleStablePriceWeight,
+        params.openingAuctionDuration,
       )
       .accounts({
         group: group.publicKey,
@@ -39,6 +39,9 @@ export const DefaultTokenRegisterParams: TokenRegisterParams = {
   oracleConfig: {
     confFilter: 0.3,
     maxStalenessSlots: null,
+    maxStalenessSeconds: null,
+    riskIncreasingMaxStalenessSlots: null,
+    riskIncreasingMaxStalenessSeconds: null,
   },
   groupInsuranceFund: false,
   interestRateParams: {
@@ -121,6 +124,17 @@ export interface TokenEditParams {
   collateralFeePerDay: number | null;
   tier: string | null;
   forceWithdraw: boolean | null;
+  circuitBreakerMaxPriceMove: number | null;
+  circuitBreakerCooldownSeconds: number | null;
+  resetCircuitBreaker: boolean | null;
+  interestRateModel: number | null;
+  interestControllerKp: number | null;
+  interestControllerKi: number | null;
+  interestControllerMaxDailyChange: number | null;
+  borrowLimit: BN | null;
+  isolatedCollateral: boolean | null;
+  isolatedBorrowTokenIndexes: number[] | null;
+  isolatedDebtCeilingQuote: BN | null;
 }
 
 export const NullTokenEditParams: TokenEditParams = {
@@ -166,6 +180,17 @@ export const NullTokenEditParams: TokenEditParams = {
   collateralFeePerDay: null,
   tier: null,
   forceWithdraw: null,
+  circuitBreakerMaxPriceMove: null,
+  circuitBreakerCooldownSeconds: null,
+  resetCircuitBreaker: null,
+  interestRateModel: null,
+  interestControllerKp: null,
+  interestControllerKi: null,
+  interestControllerMaxDailyChange: null,
+  borrowLimit: null,
+  isolatedCollateral: null,
+  isolatedBorrowTokenIndexes: null,
+  isolatedDebtCeilingQuote: null,
 };
 
 export interface PerpEditParams {
@@ -200,6 +225,9 @@ export interface PerpEditParams {
   name: string | null;
   forceClose: boolean | null;
   platformLiquidationFee: number | null;
+  fundingStablePriceWeight: number | null;
+  settleStablePriceWeight: number | null;
+  openingAuctionDuration: number | null;
 }
 
 export const NullPerpEditParams: PerpEditParams = {
@@ -234,6 +262,9 @@ export const NullPerpEditParams: PerpEditParams = {
   name: null,
   forceClose: null,
   platformLiquidationFee: null,
+  fundingStablePriceWeight: null,
+  settleStablePriceWeight: null,
+  openingAuctionDuration: null,
 };
 
 // Use with TrueIxGateParams and buildIxGate
@@ -317,6 +348,30 @@ export interface IxGateParams {
   SequenceCheck: boolean;
   HealthCheck: boolean;
   GroupChangeInsuranceFund: boolean;
+  FixedTermMarketCreate: boolean;
+  FixedTermLend: boolean;
+  FixedTermBorrow: boolean;
+  FixedTermSettle: boolean;
+  PerpScheduledOrderCreate: boolean;
+  PerpScheduledOrderCancel: boolean;
+  PerpScheduledOrderTrigger: boolean;
+  PerpConditionalOrderCreate: boolean;
+  PerpPlaceSpreadOrder: boolean;
+  AccountSetPortfolioMargin: boolean;
+  PerpSetIsolated: boolean;
+  PerpIsolatedTransfer: boolean;
+  TokenTransfer: boolean;
+  AccountEditDelegateScope: boolean;
+  OracleAggregatorCreate: boolean;
+  OracleAggregatorEdit: boolean;
+  OracleAggregatorUpdate: boolean;
+  ClmmTwapOracleCreate: boolean;
+  ClmmTwapOracleUpdate: boolean;
+  OpenbookV2MidOracleCreate: boolean;
+  OpenbookV2MidOracleUpdate: boolean;
+  AccountSetEmodeCategory: boolean;
+  PerpResizeOrderbook: boolean;
+  PerpClearOpeningAuction: boolean;
 }
 
 // Default with all ixs enabled, use with buildIxGate
@@ -400,6 +455,30 @@ export const TrueIxGateParams: IxGateParams = {
   SequenceCheck: true,
   HealthCheck: true,
   GroupChangeInsuranceFund: true,
+  FixedTermMarketCreate: true,
+  FixedTermLend: true,
+  FixedTermBorrow: true,
+  FixedTermSettle: true,
+  PerpScheduledOrderCreate: true,
+  PerpScheduledOrderCancel: true,
+  PerpScheduledOrderTrigger: true,
+  PerpConditionalOrderCreate: true,
+  PerpPlaceSpreadOrder: true,
+  AccountSetPortfolioMargin: true,
+  PerpSetIsolated: true,
+  PerpIsolatedTransfer: true,
+  TokenTransfer: true,
+  AccountEditDelegateScope: true,
+  OracleAggregatorCreate: true,
+  OracleAggregatorEdit: true,
+  OracleAggregatorUpdate: true,
+  ClmmTwapOracleCreate: true,
+  ClmmTwapOracleUpdate: true,
+  OpenbookV2MidOracleCreate: true,
+  OpenbookV2MidOracleUpdate: true,
+  AccountSetEmodeCategory: true,
+  PerpResizeOrderbook: true,
+  PerpClearOpeningAuction: true,
 };
 
 // build ix gate e.g. buildIxGate(Builder(TrueIxGateParams).TokenDeposit(false).build()).toNumber(),
@@ -493,6 +572,30 @@ export function buildIxGate(p: IxGateParams): BN {
   toggleIx(ixGate, p, 'SequenceCheck', 73);
   toggleIx(ixGate, p, 'HealthCheck', 74);
   toggleIx(ixGate, p, 'GroupChangeInsuranceFund', 76);
+  toggleIx(ixGate, p, 'FixedTermMarketCreate', 77);
+  toggleIx(ixGate, p, 'FixedTermLend', 78);
+  toggleIx(ixGate, p, 'FixedTermBorrow', 79);
+  toggleIx(ixGate, p, 'FixedTermSettle', 80);
+  toggleIx(ixGate, p, 'PerpScheduledOrderCreate', 81);
+  toggleIx(ixGate, p, 'PerpScheduledOrderCancel', 82);
+  toggleIx(ixGate, p, 'PerpScheduledOrderTrigger', 83);
+  toggleIx(ixGate, p, 'PerpConditionalOrderCreate', 84);
+  toggleIx(ixGate, p, 'PerpPlaceSpreadOrder', 85);
+  toggleIx(ixGate, p, 'AccountSetPortfolioMargin', 86);
+  toggleIx(ixGate, p, 'PerpSetIsolated', 87);
+  toggleIx(ixGate, p, 'PerpIsolatedTransfer', 88);
+  toggleIx(ixGate, p, 'TokenTransfer', 89);
+  toggleIx(ixGate, p, 'AccountEditDelegateScope', 90);
+  toggleIx(ixGate, p, 'OracleAggregatorCreate', 91);
+  toggleIx(ixGate, p, 'OracleAggregatorEdit', 92);
+  toggleIx(ixGate, p, 'OracleAggregatorUpdate', 93);
+  toggleIx(ixGate, p, 'ClmmTwapOracleCreate', 94);
+  toggleIx(ixGate, p, 'ClmmTwapOracleUpdate', 95);
+  toggleIx(ixGate, p, 'OpenbookV2MidOracleCreate', 96);
+  toggleIx(ixGate, p, 'OpenbookV2MidOracleUpdate', 97);
+  toggleIx(ixGate, p, 'AccountSetEmodeCategory', 98);
+  toggleIx(ixGate, p, 'PerpResizeOrderbook', 99);
+  toggleIx(ixGate, p, 'PerpClearOpeningAuction', 100);
 
   return ixGate;
 }
@@ -286,6 +286,118 @@ export type MangoV4 = {
         }
       ]
     },
+    {
+      "name": "groupEditEmodeCategory",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "admin"
+          ]
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "category",
+          "type": "u8"
+        },
+        {
+          "name": "tokenIndexes",
+          "type": {
+            "vec": "u16"
+          }
+        },
+        {
+          "name": "maintAssetWeight",
+          "type": "f32"
+        },
+        {
+          "name": "initAssetWeight",
+          "type": "f32"
+        },
+        {
+          "name": "maintLiabWeight",
+          "type": "f32"
+        },
+        {
+          "name": "initLiabWeight",
+          "type": "f32"
+        }
+      ]
+    },
+    {
+      "name": "groupEditPerpFeeTier",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "admin"
+          ]
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "tier",
+          "type": "u8"
+        },
+        {
+          "name": "minVolumeQuote",
+          "type": "u64"
+        },
+        {
+          "name": "makerFeeDiscount",
+          "type": "f32"
+        },
+        {
+          "name": "takerFeeDiscount",
+          "type": "f32"
+        }
+      ]
+    },
+    {
+      "name": "groupEditPortfolioMarginPair",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "admin"
+          ]
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "perpMarketIndex",
+          "type": "u16"
+        },
+        {
+          "name": "tokenIndexOpt",
+          "type": {
+            "option": "u16"
+          }
+        }
+      ]
+    },
     {
       "name": "groupWithdrawInsuranceFund",
       "accounts": [
@@ -1163,6 +1275,72 @@ export type MangoV4 = {
           "type": {
             "option": "string"
           }
+        },
+        {
+          "name": "circuitBreakerMaxPriceMoveOpt",
+          "type": {
+            "option": "f32"
+          }
+        },
+        {
+          "name": "circuitBreakerCooldownSecondsOpt",
+          "type": {
+            "option": "u32"
+          }
+        },
+        {
+          "name": "resetCircuitBreaker",
+          "type": "bool"
+        },
+        {
+          "name": "interestRateModelOpt",
+          "type": {
+            "option": "u8"
+          }
+        },
+        {
+          "name": "interestControllerKpOpt",
+          "type": {
+            "option": "f32"
+          }
+        },
+        {
+          "name": "interestControllerKiOpt",
+          "type": {
+            "option": "f32"
+          }
+        },
+        {
+          "name": "interestControllerMaxDailyChangeOpt",
+          "type": {
+            "option": "f32"
+          }
+        },
+        {
+          "name": "borrowLimitOpt",
+          "type": {
+            "option": "u64"
+          }
+        },
+        {
+          "name": "isolatedCollateralOpt",
+          "type": {
+            "option": "bool"
+          }
+        },
+        {
+          "name": "isolatedBorrowTokenIndexesOpt",
+          "type": {
+            "option": {
+              "vec": "u16"
+            }
+          }
+        },
+        {
+          "name": "isolatedDebtCeilingQuoteOpt",
+          "type": {
+            "option": "u64"
+          }
         }
       ]
     },
@@ -1562,7 +1740,7 @@ export type MangoV4 = {
       ]
     },
     {
-      "name": "accountExpand",
+      "name": "accountCreateV3",
       "accounts": [
         {
           "name": "group",
@@ -1573,10 +1751,30 @@ export type MangoV4 = {
           "name": "account",
           "isMut": true,
           "isSigner": false,
-          "relations": [
-            "group",
-            "owner"
-          ]
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "type": "string",
+                "value": "MangoAccount"
+              },
+              {
+                "kind": "account",
+                "type": "publicKey",
+                "path": "group"
+              },
+              {
+                "kind": "account",
+                "type": "publicKey",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "type": "u32",
+                "path": "account_num"
+              }
+            ]
+          }
         },
         {
           "name": "owner",
@@ -1595,6 +1793,10 @@ export type MangoV4 = {
         }
       ],
       "args": [
+        {
+          "name": "accountNum",
+          "type": "u32"
+        },
         {
           "name": "tokenCount",
           "type": "u8"
@@ -1610,11 +1812,287 @@ export type MangoV4 = {
         {
           "name": "perpOoCount",
           "type": "u8"
+        },
+        {
+          "name": "tokenConditionalSwapCount",
+          "type": "u8"
+        },
+        {
+          "name": "openbookV2Count",
+          "type": "u8"
+        },
+        {
+          "name": "name",
+          "type": "string"
         }
       ]
     },
     {
-      "name": "accountExpandV2",
+      "name": "accountCreateV4",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "type": "string",
+                "value": "MangoAccount"
+              },
+              {
+                "kind": "account",
+                "type": "publicKey",
+                "path": "group"
+              },
+              {
+                "kind": "account",
+                "type": "publicKey",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "type": "u32",
+                "path": "account_num"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "accountNum",
+          "type": "u32"
+        },
+        {
+          "name": "tokenCount",
+          "type": "u8"
+        },
+        {
+          "name": "serum3Count",
+          "type": "u8"
+        },
+        {
+          "name": "perpCount",
+          "type": "u8"
+        },
+        {
+          "name": "perpOoCount",
+          "type": "u8"
+        },
+        {
+          "name": "tokenConditionalSwapCount",
+          "type": "u8"
+        },
+        {
+          "name": "openbookV2Count",
+          "type": "u8"
+        },
+        {
+          "name": "fixedTermCount",
+          "type": "u8"
+        },
+        {
+          "name": "perpScheduledOrderCount",
+          "type": "u8"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "accountExpand",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "owner"
+          ]
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "tokenCount",
+          "type": "u8"
+        },
+        {
+          "name": "serum3Count",
+          "type": "u8"
+        },
+        {
+          "name": "perpCount",
+          "type": "u8"
+        },
+        {
+          "name": "perpOoCount",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "accountExpandV2",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "owner"
+          ]
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "tokenCount",
+          "type": "u8"
+        },
+        {
+          "name": "serum3Count",
+          "type": "u8"
+        },
+        {
+          "name": "perpCount",
+          "type": "u8"
+        },
+        {
+          "name": "perpOoCount",
+          "type": "u8"
+        },
+        {
+          "name": "tokenConditionalSwapCount",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "accountExpandV3",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "owner"
+          ]
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "tokenCount",
+          "type": "u8"
+        },
+        {
+          "name": "serum3Count",
+          "type": "u8"
+        },
+        {
+          "name": "perpCount",
+          "type": "u8"
+        },
+        {
+          "name": "perpOoCount",
+          "type": "u8"
+        },
+        {
+          "name": "tokenConditionalSwapCount",
+          "type": "u8"
+        },
+        {
+          "name": "openbookV2Count",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "accountExpandV4",
       "accounts": [
         {
           "name": "group",
@@ -1666,6 +2144,18 @@ export type MangoV4 = {
         {
           "name": "tokenConditionalSwapCount",
           "type": "u8"
+        },
+        {
+          "name": "openbookV2Count",
+          "type": "u8"
+        },
+        {
+          "name": "fixedTermCount",
+          "type": "u8"
+        },
+        {
+          "name": "perpScheduledOrderCount",
+          "type": "u8"
         }
       ]
     },
@@ -1777,6 +2267,102 @@ export type MangoV4 = {
         }
       ]
     },
+    {
+      "name": "accountSetPortfolioMargin",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "owner"
+          ]
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "enabled",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "accountSetEmodeCategory",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "owner"
+          ]
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "category",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "accountEditDelegateScope",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "owner"
+          ]
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "temporaryDelegate",
+          "type": "bool"
+        },
+        {
+          "name": "scope",
+          "type": {
+            "defined": "DelegateScope"
+          }
+        }
+      ]
+    },
     {
       "name": "accountClose",
       "accounts": [
@@ -1941,7 +2527,7 @@ export type MangoV4 = {
       ]
     },
     {
-      "name": "stubOracleCreate",
+      "name": "oracleAggregatorCreate",
       "accounts": [
         {
           "name": "group",
@@ -1979,15 +2565,23 @@ export type MangoV4 = {
       ],
       "args": [
         {
-          "name": "price",
+          "name": "minQuorum",
+          "type": "u8"
+        },
+        {
+          "name": "maxDivergence",
+          "type": "f64"
+        },
+        {
+          "name": "sourceOracleConfig",
           "type": {
-            "defined": "I80F48"
+            "defined": "OracleConfigParams"
           }
         }
       ]
     },
     {
-      "name": "stubOracleClose",
+      "name": "oracleAggregatorEdit",
       "accounts": [
         {
           "name": "group",
@@ -2009,35 +2603,42 @@ export type MangoV4 = {
           "relations": [
             "group"
           ]
+        }
+      ],
+      "args": [
+        {
+          "name": "minQuorumOpt",
+          "type": {
+            "option": "u8"
+          }
         },
         {
-          "name": "solDestination",
-          "isMut": true,
-          "isSigner": false
+          "name": "maxDivergenceOpt",
+          "type": {
+            "option": "f64"
+          }
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
+          "name": "sourceOracleConfigOpt",
+          "type": {
+            "option": {
+              "defined": "OracleConfigParams"
+            }
+          }
+        },
+        {
+          "name": "replaceSourceOracles",
+          "type": "bool"
         }
-      ],
-      "args": []
+      ]
     },
     {
-      "name": "stubOracleSet",
+      "name": "oracleAggregatorUpdate",
       "accounts": [
         {
           "name": "group",
           "isMut": false,
-          "isSigner": false,
-          "relations": [
-            "admin"
-          ]
-        },
-        {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
+          "isSigner": false
         },
         {
           "name": "oracle",
@@ -2048,17 +2649,10 @@ export type MangoV4 = {
           ]
         }
       ],
-      "args": [
-        {
-          "name": "price",
-          "type": {
-            "defined": "I80F48"
-          }
-        }
-      ]
+      "args": []
     },
     {
-      "name": "stubOracleSetTest",
+      "name": "clmmTwapOracleCreate",
       "accounts": [
         {
           "name": "group",
@@ -2068,41 +2662,46 @@ export type MangoV4 = {
             "admin"
           ]
         },
+        {
+          "name": "oracle",
+          "isMut": true,
+          "isSigner": true
+        },
         {
           "name": "admin",
           "isMut": false,
           "isSigner": true
         },
         {
-          "name": "oracle",
+          "name": "pool",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "observation",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
           "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group"
-          ]
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "price",
-          "type": {
-            "defined": "I80F48"
-          }
-        },
-        {
-          "name": "lastUpdateSlot",
+          "name": "twapWindowSeconds",
           "type": "u64"
-        },
-        {
-          "name": "deviation",
-          "type": {
-            "defined": "I80F48"
-          }
         }
       ]
     },
     {
-      "name": "tokenDeposit",
+      "name": "clmmTwapOracleUpdate",
       "accounts": [
         {
           "name": "group",
@@ -2110,67 +2709,83 @@ export type MangoV4 = {
           "isSigner": false
         },
         {
-          "name": "account",
+          "name": "oracle",
           "isMut": true,
           "isSigner": false,
           "relations": [
-            "group"
+            "group",
+            "pool",
+            "observation"
           ]
         },
         {
-          "name": "owner",
+          "name": "pool",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "bank",
-          "isMut": true,
+          "name": "observation",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "openbookV2MidOracleCreate",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
           "isSigner": false,
           "relations": [
-            "group",
-            "vault",
-            "oracle"
+            "admin"
           ]
         },
         {
-          "name": "vault",
+          "name": "oracle",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "oracle",
+          "name": "admin",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "tokenAccount",
-          "isMut": true,
-          "isSigner": false
+          "name": "market",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "bids",
+            "asks"
+          ]
         },
         {
-          "name": "tokenAuthority",
+          "name": "bids",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "asks",
           "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
         },
         {
-          "name": "reduceOnly",
-          "type": "bool"
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "tokenDepositIntoExisting",
+      "name": "openbookV2MidOracleUpdate",
       "accounts": [
         {
           "name": "group",
@@ -2178,102 +2793,368 @@ export type MangoV4 = {
           "isSigner": false
         },
         {
-          "name": "account",
+          "name": "oracle",
           "isMut": true,
           "isSigner": false,
           "relations": [
-            "group"
+            "group",
+            "market",
+            "bids",
+            "asks"
           ]
         },
         {
-          "name": "bank",
-          "isMut": true,
+          "name": "market",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "bids",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "asks",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "stubOracleCreate",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
           "isSigner": false,
           "relations": [
-            "group",
-            "vault",
-            "oracle"
+            "admin"
           ]
         },
         {
-          "name": "vault",
+          "name": "oracle",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "oracle",
+          "name": "admin",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "tokenAccount",
-          "isMut": true,
+          "name": "mint",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenAuthority",
-          "isMut": false,
+          "name": "payer",
+          "isMut": true,
           "isSigner": true
         },
         {
-          "name": "tokenProgram",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "amount",
-          "type": "u64"
-        },
-        {
-          "name": "reduceOnly",
-          "type": "bool"
+          "name": "price",
+          "type": {
+            "defined": "I80F48"
+          }
         }
       ]
     },
     {
-      "name": "tokenWithdraw",
+      "name": "stubOracleClose",
       "accounts": [
         {
           "name": "group",
           "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "account",
-          "isMut": true,
           "isSigner": false,
           "relations": [
-            "group"
+            "admin"
           ]
         },
         {
-          "name": "owner",
+          "name": "admin",
           "isMut": false,
           "isSigner": true
         },
         {
-          "name": "bank",
+          "name": "oracle",
           "isMut": true,
           "isSigner": false,
           "relations": [
-            "group",
-            "vault",
-            "oracle"
+            "group"
           ]
         },
         {
-          "name": "vault",
+          "name": "solDestination",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "oracle",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
-        },
-        {
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "stubOracleSet",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "admin"
+          ]
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "oracle",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "price",
+          "type": {
+            "defined": "I80F48"
+          }
+        }
+      ]
+    },
+    {
+      "name": "stubOracleSetTest",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "admin"
+          ]
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "oracle",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "price",
+          "type": {
+            "defined": "I80F48"
+          }
+        },
+        {
+          "name": "lastUpdateSlot",
+          "type": "u64"
+        },
+        {
+          "name": "deviation",
+          "type": {
+            "defined": "I80F48"
+          }
+        }
+      ]
+    },
+    {
+      "name": "tokenDeposit",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "bank",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "vault",
+            "oracle"
+          ]
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "oracle",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "reduceOnly",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "tokenDepositIntoExisting",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "bank",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "vault",
+            "oracle"
+          ]
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "oracle",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAccount",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "reduceOnly",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "tokenWithdraw",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "bank",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "vault",
+            "oracle"
+          ]
+        },
+        {
+          "name": "vault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "oracle",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
           "name": "tokenAccount",
           "isMut": true,
           "isSigner": false
@@ -2295,6 +3176,57 @@ export type MangoV4 = {
         }
       ]
     },
+    {
+      "name": "tokenTransfer",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "source",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "destination",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "bank",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "oracle"
+          ]
+        },
+        {
+          "name": "oracle",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
     {
       "name": "flashLoanBegin",
       "accounts": [
@@ -4226,6 +5158,18 @@ export type MangoV4 = {
         {
           "name": "platformLiquidationFee",
           "type": "f32"
+        },
+        {
+          "name": "booksideCapacity",
+          "type": "u32"
+        },
+        {
+          "name": "eventQueueCapacity",
+          "type": "u32"
+        },
+        {
+          "name": "openingAuctionDuration",
+          "type": "u32"
         }
       ]
     },
@@ -4446,6 +5390,88 @@ export type MangoV4 = {
           "type": {
             "option": "f32"
           }
+        },
+        {
+          "name": "fundingStablePriceWeightOpt",
+          "type": {
+            "option": "f32"
+          }
+        },
+        {
+          "name": "settleStablePriceWeightOpt",
+          "type": {
+            "option": "f32"
+          }
+        },
+        {
+          "name": "openingAuctionDurationOpt",
+          "type": {
+            "option": "u32"
+          }
+        }
+      ]
+    },
+    {
+      "name": "perpResizeOrderbook",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "admin"
+          ]
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "perpMarket",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "bids",
+            "asks",
+            "event_queue"
+          ]
+        },
+        {
+          "name": "bids",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "asks",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "eventQueue",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "booksideCapacity",
+          "type": "u32"
+        },
+        {
+          "name": "eventQueueCapacity",
+          "type": "u32"
         }
       ]
     },
@@ -4537,7 +5563,7 @@ export type MangoV4 = {
       "args": []
     },
     {
-      "name": "perpPlaceOrder",
+      "name": "perpSetIsolated",
       "accounts": [
         {
           "name": "group",
@@ -4559,85 +5585,22 @@ export type MangoV4 = {
         },
         {
           "name": "perpMarket",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false,
           "relations": [
-            "group",
-            "bids",
-            "asks",
-            "event_queue",
-            "oracle"
+            "group"
           ]
-        },
-        {
-          "name": "bids",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "asks",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "eventQueue",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "oracle",
-          "isMut": false,
-          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "side",
-          "type": {
-            "defined": "Side"
-          }
-        },
-        {
-          "name": "priceLots",
-          "type": "i64"
-        },
-        {
-          "name": "maxBaseLots",
-          "type": "i64"
-        },
-        {
-          "name": "maxQuoteLots",
-          "type": "i64"
-        },
-        {
-          "name": "clientOrderId",
-          "type": "u64"
-        },
-        {
-          "name": "orderType",
-          "type": {
-            "defined": "PlaceOrderType"
-          }
-        },
-        {
-          "name": "reduceOnly",
+          "name": "isolated",
           "type": "bool"
-        },
-        {
-          "name": "expiryTimestamp",
-          "type": "u64"
-        },
-        {
-          "name": "limit",
-          "type": "u8"
         }
-      ],
-      "returns": {
-        "option": "u128"
-      }
+      ]
     },
     {
-      "name": "perpPlaceOrderV2",
+      "name": "perpIsolatedTransfer",
       "accounts": [
         {
           "name": "group",
@@ -4659,91 +5622,22 @@ export type MangoV4 = {
         },
         {
           "name": "perpMarket",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false,
           "relations": [
-            "group",
-            "bids",
-            "asks",
-            "event_queue",
-            "oracle"
+            "group"
           ]
-        },
-        {
-          "name": "bids",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "asks",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "eventQueue",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "oracle",
-          "isMut": false,
-          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "side",
-          "type": {
-            "defined": "Side"
-          }
-        },
-        {
-          "name": "priceLots",
-          "type": "i64"
-        },
-        {
-          "name": "maxBaseLots",
-          "type": "i64"
-        },
-        {
-          "name": "maxQuoteLots",
+          "name": "amount",
           "type": "i64"
-        },
-        {
-          "name": "clientOrderId",
-          "type": "u64"
-        },
-        {
-          "name": "orderType",
-          "type": {
-            "defined": "PlaceOrderType"
-          }
-        },
-        {
-          "name": "selfTradeBehavior",
-          "type": {
-            "defined": "SelfTradeBehavior"
-          }
-        },
-        {
-          "name": "reduceOnly",
-          "type": "bool"
-        },
-        {
-          "name": "expiryTimestamp",
-          "type": "u64"
-        },
-        {
-          "name": "limit",
-          "type": "u8"
         }
-      ],
-      "returns": {
-        "option": "u128"
-      }
+      ]
     },
     {
-      "name": "perpPlaceOrderPegged",
+      "name": "perpPlaceOrder",
       "accounts": [
         {
           "name": "group",
@@ -4804,11 +5698,7 @@ export type MangoV4 = {
           }
         },
         {
-          "name": "priceOffsetLots",
-          "type": "i64"
-        },
-        {
-          "name": "pegLimit",
+          "name": "priceLots",
           "type": "i64"
         },
         {
@@ -4840,10 +5730,6 @@ export type MangoV4 = {
         {
           "name": "limit",
           "type": "u8"
-        },
-        {
-          "name": "maxOracleStalenessSlots",
-          "type": "i32"
         }
       ],
       "returns": {
@@ -4851,7 +5737,7 @@ export type MangoV4 = {
       }
     },
     {
-      "name": "perpPlaceOrderPeggedV2",
+      "name": "perpPlaceOrderV2",
       "accounts": [
         {
           "name": "group",
@@ -4912,11 +5798,7 @@ export type MangoV4 = {
           }
         },
         {
-          "name": "priceOffsetLots",
-          "type": "i64"
-        },
-        {
-          "name": "pegLimit",
+          "name": "priceLots",
           "type": "i64"
         },
         {
@@ -4954,10 +5836,6 @@ export type MangoV4 = {
         {
           "name": "limit",
           "type": "u8"
-        },
-        {
-          "name": "maxOracleStalenessSlots",
-          "type": "i32"
         }
       ],
       "returns": {
@@ -4965,7 +5843,7 @@ export type MangoV4 = {
       }
     },
     {
-      "name": "perpCancelOrder",
+      "name": "perpPlaceOrderPegged",
       "accounts": [
         {
           "name": "group",
@@ -4992,7 +5870,9 @@ export type MangoV4 = {
           "relations": [
             "group",
             "bids",
-            "asks"
+            "asks",
+            "event_queue",
+            "oracle"
           ]
         },
         {
@@ -5004,17 +5884,74 @@ export type MangoV4 = {
           "name": "asks",
           "isMut": true,
           "isSigner": false
+        },
+        {
+          "name": "eventQueue",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "oracle",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "orderId",
-          "type": "u128"
+          "name": "side",
+          "type": {
+            "defined": "Side"
+          }
+        },
+        {
+          "name": "priceOffsetLots",
+          "type": "i64"
+        },
+        {
+          "name": "pegLimit",
+          "type": "i64"
+        },
+        {
+          "name": "maxBaseLots",
+          "type": "i64"
+        },
+        {
+          "name": "maxQuoteLots",
+          "type": "i64"
+        },
+        {
+          "name": "clientOrderId",
+          "type": "u64"
+        },
+        {
+          "name": "orderType",
+          "type": {
+            "defined": "PlaceOrderType"
+          }
+        },
+        {
+          "name": "reduceOnly",
+          "type": "bool"
+        },
+        {
+          "name": "expiryTimestamp",
+          "type": "u64"
+        },
+        {
+          "name": "limit",
+          "type": "u8"
+        },
+        {
+          "name": "maxOracleStalenessSlots",
+          "type": "i32"
         }
-      ]
+      ],
+      "returns": {
+        "option": "u128"
+      }
     },
     {
-      "name": "perpCancelOrderByClientOrderId",
+      "name": "perpPlaceOrderPeggedV2",
       "accounts": [
         {
           "name": "group",
@@ -5041,7 +5978,9 @@ export type MangoV4 = {
           "relations": [
             "group",
             "bids",
-            "asks"
+            "asks",
+            "event_queue",
+            "oracle"
           ]
         },
         {
@@ -5053,17 +5992,80 @@ export type MangoV4 = {
           "name": "asks",
           "isMut": true,
           "isSigner": false
+        },
+        {
+          "name": "eventQueue",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "oracle",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
+        {
+          "name": "side",
+          "type": {
+            "defined": "Side"
+          }
+        },
+        {
+          "name": "priceOffsetLots",
+          "type": "i64"
+        },
+        {
+          "name": "pegLimit",
+          "type": "i64"
+        },
+        {
+          "name": "maxBaseLots",
+          "type": "i64"
+        },
+        {
+          "name": "maxQuoteLots",
+          "type": "i64"
+        },
         {
           "name": "clientOrderId",
           "type": "u64"
+        },
+        {
+          "name": "orderType",
+          "type": {
+            "defined": "PlaceOrderType"
+          }
+        },
+        {
+          "name": "selfTradeBehavior",
+          "type": {
+            "defined": "SelfTradeBehavior"
+          }
+        },
+        {
+          "name": "reduceOnly",
+          "type": "bool"
+        },
+        {
+          "name": "expiryTimestamp",
+          "type": "u64"
+        },
+        {
+          "name": "limit",
+          "type": "u8"
+        },
+        {
+          "name": "maxOracleStalenessSlots",
+          "type": "i32"
         }
-      ]
+      ],
+      "returns": {
+        "option": "u128"
+      }
     },
     {
-      "name": "perpCancelAllOrders",
+      "name": "perpPlaceSpreadOrder",
       "accounts": [
         {
           "name": "group",
@@ -5084,43 +6086,35 @@ export type MangoV4 = {
           "isSigner": true
         },
         {
-          "name": "perpMarket",
+          "name": "perpMarketA",
           "isMut": true,
           "isSigner": false,
           "relations": [
-            "group",
-            "bids",
-            "asks"
+            "group"
           ]
         },
         {
-          "name": "bids",
+          "name": "bidsA",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "asks",
+          "name": "asksA",
           "isMut": true,
           "isSigner": false
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "limit",
-          "type": "u8"
-        }
-      ]
-    },
-    {
-      "name": "perpCancelAllOrdersBySide",
-      "accounts": [
+          "name": "eventQueueA",
+          "isMut": true,
+          "isSigner": false
+        },
         {
-          "name": "group",
+          "name": "oracleA",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "account",
+          "name": "perpMarketB",
           "isMut": true,
           "isSigner": false,
           "relations": [
@@ -5128,40 +6122,55 @@ export type MangoV4 = {
           ]
         },
         {
-          "name": "owner",
-          "isMut": false,
-          "isSigner": true
+          "name": "bidsB",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "perpMarket",
+          "name": "asksB",
           "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group",
-            "bids",
-            "asks"
-          ]
+          "isSigner": false
         },
         {
-          "name": "bids",
+          "name": "eventQueueB",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "asks",
-          "isMut": true,
+          "name": "oracleB",
+          "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "sideOption",
+          "name": "sideA",
           "type": {
-            "option": {
-              "defined": "Side"
-            }
+            "defined": "Side"
+          }
+        },
+        {
+          "name": "priceLotsA",
+          "type": "i64"
+        },
+        {
+          "name": "maxBaseLotsA",
+          "type": "i64"
+        },
+        {
+          "name": "sideB",
+          "type": {
+            "defined": "Side"
           }
         },
+        {
+          "name": "priceLotsB",
+          "type": "i64"
+        },
+        {
+          "name": "legBRatio",
+          "type": "f64"
+        },
         {
           "name": "limit",
           "type": "u8"
@@ -5169,7 +6178,7 @@ export type MangoV4 = {
       ]
     },
     {
-      "name": "perpConsumeEvents",
+      "name": "perpPlaceOrdersBatch",
       "accounts": [
         {
           "name": "group",
@@ -5177,34 +6186,17 @@ export type MangoV4 = {
           "isSigner": false
         },
         {
-          "name": "perpMarket",
+          "name": "account",
           "isMut": true,
           "isSigner": false,
           "relations": [
-            "group",
-            "event_queue"
+            "group"
           ]
         },
         {
-          "name": "eventQueue",
-          "isMut": true,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "limit",
-          "type": "u64"
-        }
-      ]
-    },
-    {
-      "name": "perpUpdateFunding",
-      "accounts": [
-        {
-          "name": "group",
+          "name": "owner",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
           "name": "perpMarket",
@@ -5214,6 +6206,7 @@ export type MangoV4 = {
             "group",
             "bids",
             "asks",
+            "event_queue",
             "oracle"
           ]
         },
@@ -5227,16 +6220,42 @@ export type MangoV4 = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "eventQueue",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "oracle",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "orders",
+          "type": {
+            "vec": {
+              "defined": "PerpBatchOrder"
+            }
+          }
+        },
+        {
+          "name": "cancelAllFirst",
+          "type": "bool"
+        },
+        {
+          "name": "expiryTimestamp",
+          "type": "u64"
+        },
+        {
+          "name": "limit",
+          "type": "u8"
+        }
+      ]
     },
     {
-      "name": "perpSettlePnl",
+      "name": "perpEditOrder",
       "accounts": [
         {
           "name": "group",
@@ -5244,7 +6263,7 @@ export type MangoV4 = {
           "isSigner": false
         },
         {
-          "name": "settler",
+          "name": "account",
           "isMut": true,
           "isSigner": false,
           "relations": [
@@ -5252,114 +6271,95 @@ export type MangoV4 = {
           ]
         },
         {
-          "name": "settlerOwner",
+          "name": "owner",
           "isMut": false,
           "isSigner": true
         },
         {
           "name": "perpMarket",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false,
           "relations": [
             "group",
+            "bids",
+            "asks",
+            "event_queue",
             "oracle"
           ]
         },
         {
-          "name": "accountA",
+          "name": "bids",
           "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group"
-          ]
+          "isSigner": false
         },
         {
-          "name": "accountB",
+          "name": "asks",
           "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group"
-          ]
-        },
-        {
-          "name": "oracle",
-          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "settleBank",
+          "name": "eventQueue",
           "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group"
-          ]
+          "isSigner": false
         },
         {
-          "name": "settleOracle",
+          "name": "oracle",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": []
-    },
-    {
-      "name": "perpForceClosePosition",
-      "accounts": [
+      "args": [
         {
-          "name": "group",
-          "isMut": false,
-          "isSigner": false
+          "name": "orderIdOpt",
+          "type": {
+            "option": "u128"
+          }
         },
         {
-          "name": "perpMarket",
-          "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group",
-            "oracle"
-          ]
+          "name": "clientOrderIdOpt",
+          "type": {
+            "option": "u64"
+          }
         },
         {
-          "name": "accountA",
-          "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group"
-          ]
+          "name": "priceLots",
+          "type": "i64"
         },
         {
-          "name": "accountB",
-          "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group"
-          ]
+          "name": "maxBaseLots",
+          "type": "i64"
         },
         {
-          "name": "oracle",
-          "isMut": false,
-          "isSigner": false
+          "name": "selfTradeBehavior",
+          "type": {
+            "defined": "SelfTradeBehavior"
+          }
+        },
+        {
+          "name": "reduceOnly",
+          "type": "bool"
+        },
+        {
+          "name": "maxOracleStalenessSlots",
+          "type": "i32"
+        },
+        {
+          "name": "limit",
+          "type": "u8"
         }
       ],
-      "args": []
+      "returns": {
+        "option": "u128"
+      }
     },
     {
-      "name": "perpSettleFees",
+      "name": "perpCancelOrder",
       "accounts": [
         {
           "name": "group",
           "isMut": false,
           "isSigner": false
         },
-        {
-          "name": "perpMarket",
-          "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group",
-            "oracle"
-          ]
-        },
         {
           "name": "account",
           "isMut": true,
@@ -5369,33 +6369,40 @@ export type MangoV4 = {
           ]
         },
         {
-          "name": "oracle",
+          "name": "owner",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "settleBank",
+          "name": "perpMarket",
           "isMut": true,
           "isSigner": false,
           "relations": [
-            "group"
+            "group",
+            "bids",
+            "asks"
           ]
         },
         {
-          "name": "settleOracle",
-          "isMut": false,
+          "name": "bids",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "asks",
+          "isMut": true,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "maxSettleAmount",
-          "type": "u64"
+          "name": "orderId",
+          "type": "u128"
         }
       ]
     },
     {
-      "name": "perpLiqBaseOrPositivePnl",
+      "name": "perpCancelOrderByClientOrderId",
       "accounts": [
         {
           "name": "group",
@@ -5403,21 +6410,7 @@ export type MangoV4 = {
           "isSigner": false
         },
         {
-          "name": "perpMarket",
-          "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group",
-            "oracle"
-          ]
-        },
-        {
-          "name": "oracle",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "liqor",
+          "name": "account",
           "isMut": true,
           "isSigner": false,
           "relations": [
@@ -5425,50 +6418,40 @@ export type MangoV4 = {
           ]
         },
         {
-          "name": "liqorOwner",
+          "name": "owner",
           "isMut": false,
           "isSigner": true
         },
         {
-          "name": "liqee",
-          "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group"
-          ]
-        },
-        {
-          "name": "settleBank",
+          "name": "perpMarket",
           "isMut": true,
           "isSigner": false,
           "relations": [
-            "group"
+            "group",
+            "bids",
+            "asks"
           ]
         },
         {
-          "name": "settleVault",
+          "name": "bids",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "settleOracle",
-          "isMut": false,
+          "name": "asks",
+          "isMut": true,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "maxBaseTransfer",
-          "type": "i64"
-        },
-        {
-          "name": "maxPnlTransfer",
+          "name": "clientOrderId",
           "type": "u64"
         }
       ]
     },
     {
-      "name": "perpLiqForceCancelOrders",
+      "name": "perpCancelAllOrders",
       "accounts": [
         {
           "name": "group",
@@ -5483,6 +6466,11 @@ export type MangoV4 = {
             "group"
           ]
         },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
         {
           "name": "perpMarket",
           "isMut": true,
@@ -5512,18 +6500,15 @@ export type MangoV4 = {
       ]
     },
     {
-      "name": "perpLiqNegativePnlOrBankruptcy",
+      "name": "perpCancelAllOrdersBySide",
       "accounts": [
         {
           "name": "group",
           "isMut": false,
-          "isSigner": false,
-          "relations": [
-            "insurance_vault"
-          ]
+          "isSigner": false
         },
         {
-          "name": "liqor",
+          "name": "account",
           "isMut": true,
           "isSigner": false,
           "relations": [
@@ -5531,76 +6516,527 @@ export type MangoV4 = {
           ]
         },
         {
-          "name": "liqorOwner",
+          "name": "owner",
           "isMut": false,
           "isSigner": true
         },
-        {
-          "name": "liqee",
-          "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group"
-          ]
-        },
         {
           "name": "perpMarket",
           "isMut": true,
           "isSigner": false,
           "relations": [
             "group",
-            "oracle"
+            "bids",
+            "asks"
           ]
         },
         {
-          "name": "oracle",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "settleBank",
+          "name": "bids",
           "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group"
-          ]
+          "isSigner": false
         },
         {
-          "name": "settleVault",
+          "name": "asks",
           "isMut": true,
           "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "sideOption",
+          "type": {
+            "option": {
+              "defined": "Side"
+            }
+          }
         },
         {
-          "name": "settleOracle",
+          "name": "limit",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "perpConsumeEvents",
+      "accounts": [
+        {
+          "name": "group",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "insuranceVault",
+          "name": "perpMarket",
           "isMut": true,
-          "isSigner": false
+          "isSigner": false,
+          "relations": [
+            "group",
+            "event_queue"
+          ]
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
+          "name": "eventQueue",
+          "isMut": true,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "maxLiabTransfer",
+          "name": "limit",
           "type": "u64"
         }
       ]
     },
     {
-      "name": "perpLiqNegativePnlOrBankruptcyV2",
+      "name": "perpUpdateFunding",
       "accounts": [
         {
           "name": "group",
           "isMut": false,
-          "isSigner": false,
-          "relations": [
+          "isSigner": false
+        },
+        {
+          "name": "perpMarket",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "bids",
+            "asks",
+            "oracle"
+          ]
+        },
+        {
+          "name": "bids",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "asks",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "oracle",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "perpClearOpeningAuction",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "perpMarket",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "bids",
+            "asks",
+            "event_queue",
+            "oracle"
+          ]
+        },
+        {
+          "name": "bids",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "asks",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "eventQueue",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "oracle",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "limit",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "perpSettlePnl",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "settler",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "settlerOwner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "perpMarket",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "oracle"
+          ]
+        },
+        {
+          "name": "accountA",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "accountB",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "oracle",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "settleBank",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "settleOracle",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "perpForceClosePosition",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "perpMarket",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "oracle"
+          ]
+        },
+        {
+          "name": "accountA",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "accountB",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "oracle",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "perpSettleFees",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "perpMarket",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "oracle"
+          ]
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "oracle",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "settleBank",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "settleOracle",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "maxSettleAmount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "perpLiqBaseOrPositivePnl",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "perpMarket",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "oracle"
+          ]
+        },
+        {
+          "name": "oracle",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqor",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "liqorOwner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "liqee",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "settleBank",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "settleVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "settleOracle",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "maxBaseTransfer",
+          "type": "i64"
+        },
+        {
+          "name": "maxPnlTransfer",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "perpLiqForceCancelOrders",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "perpMarket",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "bids",
+            "asks"
+          ]
+        },
+        {
+          "name": "bids",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "asks",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "limit",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "perpLiqNegativePnlOrBankruptcy",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "insurance_vault"
+          ]
+        },
+        {
+          "name": "liqor",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "liqorOwner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "liqee",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "perpMarket",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "oracle"
+          ]
+        },
+        {
+          "name": "oracle",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "settleBank",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "settleVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "settleOracle",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "insuranceVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "maxLiabTransfer",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "perpLiqNegativePnlOrBankruptcyV2",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
             "insurance_vault"
           ]
         },
@@ -6573,7 +8009,7 @@ export type MangoV4 = {
           "isSigner": false
         },
         {
-          "name": "openOrders",
+          "name": "openOrdersIndexer",
           "isMut": true,
           "isSigner": false,
           "pda": {
@@ -6581,17 +8017,36 @@ export type MangoV4 = {
               {
                 "kind": "const",
                 "type": "string",
-                "value": "OpenOrders"
+                "value": "OpenOrdersIndexer"
               },
               {
                 "kind": "account",
                 "type": "publicKey",
-                "path": "openbook_v2_market"
+                "path": "group"
+              }
+            ],
+            "programId": {
+              "kind": "account",
+              "type": "publicKey",
+              "path": "openbook_v2_program"
+            }
+          }
+        },
+        {
+          "name": "openOrders",
+          "isMut": true,
+          "isSigner": false,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "type": "string",
+                "value": "OpenOrders"
               },
               {
                 "kind": "account",
                 "type": "publicKey",
-                "path": "openbook_v2_market_external"
+                "path": "group"
               },
               {
                 "kind": "arg",
@@ -6670,6 +8125,30 @@ export type MangoV4 = {
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "openOrdersIndexer",
+          "isMut": true,
+          "isSigner": false,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "type": "string",
+                "value": "OpenOrdersIndexer"
+              },
+              {
+                "kind": "account",
+                "type": "publicKey",
+                "path": "group"
+              }
+            ],
+            "programId": {
+              "kind": "account",
+              "type": "publicKey",
+              "path": "openbook_v2_program"
+            }
+          }
+        },
         {
           "name": "openOrders",
           "isMut": true,
@@ -6679,6 +8158,16 @@ export type MangoV4 = {
           "name": "solDestination",
           "isMut": true,
           "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": []
@@ -6712,7 +8201,12 @@ export type MangoV4 = {
         {
           "name": "openbookV2Market",
           "isMut": false,
-          "isSigner": false
+          "isSigner": false,
+          "relations": [
+            "group",
+            "openbook_v2_program",
+            "openbook_v2_market_external"
+          ]
         },
         {
           "name": "openbookV2Program",
@@ -6726,7 +8220,9 @@ export type MangoV4 = {
           "relations": [
             "bids",
             "asks",
-            "event_heap"
+            "event_heap",
+            "market_base_vault",
+            "market_quote_vault"
           ]
         },
         {
@@ -6754,11 +8250,6 @@ export type MangoV4 = {
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "marketVaultSigner",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "payerBank",
           "isMut": true,
@@ -6845,6 +8336,11 @@ export type MangoV4 = {
           "isMut": false,
           "isSigner": true
         },
+        {
+          "name": "openOrders",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "openbookV2Market",
           "isMut": false,
@@ -6867,7 +8363,9 @@ export type MangoV4 = {
           "relations": [
             "bids",
             "asks",
-            "event_heap"
+            "event_heap",
+            "market_base_vault",
+            "market_quote_vault"
           ]
         },
         {
@@ -6885,11 +8383,6 @@ export type MangoV4 = {
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "marketRequestQueue",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "marketBaseVault",
           "isMut": true,
@@ -6900,11 +8393,6 @@ export type MangoV4 = {
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "marketVaultSigner",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "payerBank",
           "isMut": true,
@@ -7009,7 +8497,7 @@ export type MangoV4 = {
         },
         {
           "name": "openbookV2MarketExternal",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false,
           "relations": [
             "bids",
@@ -7056,8 +8544,11 @@ export type MangoV4 = {
         },
         {
           "name": "authority",
-          "isMut": false,
-          "isSigner": true
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Also pays openbook v2's penalty for settling fills that are still on the event heap"
+          ]
         },
         {
           "name": "openOrders",
@@ -7082,7 +8573,12 @@ export type MangoV4 = {
         {
           "name": "openbookV2MarketExternal",
           "isMut": true,
-          "isSigner": false
+          "isSigner": false,
+          "relations": [
+            "market_authority",
+            "market_base_vault",
+            "market_quote_vault"
+          ]
         },
         {
           "name": "marketBaseVault",
@@ -7095,12 +8591,9 @@ export type MangoV4 = {
           "isSigner": false
         },
         {
-          "name": "marketVaultSigner",
+          "name": "marketAuthority",
           "isMut": false,
-          "isSigner": false,
-          "docs": [
-            "needed for the automatic settle_funds call"
-          ]
+          "isSigner": false
         },
         {
           "name": "quoteBank",
@@ -7142,6 +8635,11 @@ export type MangoV4 = {
           "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
@@ -7189,12 +8687,15 @@ export type MangoV4 = {
         },
         {
           "name": "openbookV2MarketExternal",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false,
           "relations": [
             "bids",
             "asks",
-            "event_heap"
+            "event_heap",
+            "market_authority",
+            "market_base_vault",
+            "market_quote_vault"
           ]
         },
         {
@@ -7223,7 +8724,7 @@ export type MangoV4 = {
           "isSigner": false
         },
         {
-          "name": "marketVaultSigner",
+          "name": "marketAuthority",
           "isMut": false,
           "isSigner": false
         },
@@ -7253,10 +8754,23 @@ export type MangoV4 = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Pays openbook v2's penalty for settling fills that are still on the event heap"
+          ]
+        },
         {
           "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
@@ -7309,7 +8823,7 @@ export type MangoV4 = {
         },
         {
           "name": "openbookV2MarketExternal",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false,
           "relations": [
             "bids",
@@ -7335,157 +8849,717 @@ export type MangoV4 = {
       ]
     },
     {
-      "name": "benchmark",
+      "name": "fixedTermMarketCreate",
       "docs": [
         "",
-        "benchmark",
+        "Fixed term lending",
         ""
       ],
       "accounts": [
         {
-          "name": "dummy",
+          "name": "group",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "admin"
+          ]
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "bank",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "fixedTermMarket",
+          "isMut": true,
+          "isSigner": false,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "type": "string",
+                "value": "FixedTermMarket"
+              },
+              {
+                "kind": "account",
+                "type": "publicKey",
+                "path": "group"
+              },
+              {
+                "kind": "arg",
+                "type": "u16",
+                "path": "market_index"
+              }
+            ]
+          }
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "marketIndex",
+          "type": "u16"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        },
+        {
+          "name": "maturityTimestamp",
+          "type": "u64"
+        },
+        {
+          "name": "lendRate",
+          "type": "f32"
+        },
+        {
+          "name": "borrowRate",
+          "type": "f32"
+        },
+        {
+          "name": "maxLentNative",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "fixedTermMarketEdit",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "admin"
+          ]
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "fixedTermMarket",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "nameOpt",
+          "type": {
+            "option": "string"
+          }
+        },
+        {
+          "name": "lendRateOpt",
+          "type": {
+            "option": "f32"
+          }
+        },
+        {
+          "name": "borrowRateOpt",
+          "type": {
+            "option": "f32"
+          }
+        },
+        {
+          "name": "maxLentNativeOpt",
+          "type": {
+            "option": "u64"
+          }
+        },
+        {
+          "name": "reduceOnlyOpt",
+          "type": {
+            "option": "bool"
+          }
+        }
+      ]
+    },
+    {
+      "name": "fixedTermLend",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "fixedTermMarket",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "bank",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "fixedTermBorrow",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "fixedTermMarket",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "bank",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "oracle"
+          ]
+        },
+        {
+          "name": "oracle",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "fixedTermSettle",
+      "accounts": [
+        {
+          "name": "group",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "fixedTermMarket",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "bank",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
         }
       ],
       "args": []
-    }
-  ],
-  "accounts": [
+    },
     {
-      "name": "bank",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "group",
-            "type": "publicKey"
-          },
-          {
-            "name": "name",
-            "type": {
-              "array": [
-                "u8",
-                16
-              ]
-            }
-          },
-          {
-            "name": "mint",
-            "type": "publicKey"
-          },
-          {
-            "name": "vault",
-            "type": "publicKey"
-          },
-          {
-            "name": "oracle",
-            "type": "publicKey"
-          },
-          {
-            "name": "oracleConfig",
-            "type": {
-              "defined": "OracleConfig"
-            }
-          },
-          {
-            "name": "stablePriceModel",
-            "type": {
-              "defined": "StablePriceModel"
-            }
-          },
-          {
-            "name": "depositIndex",
-            "docs": [
-              "the index used to scale the value of an IndexedPosition",
-              "TODO: should always be >= 0, add checks?"
-            ],
-            "type": {
-              "defined": "I80F48"
-            }
-          },
-          {
-            "name": "borrowIndex",
-            "type": {
-              "defined": "I80F48"
-            }
-          },
-          {
-            "name": "indexedDeposits",
-            "docs": [
-              "deposits/borrows for this bank",
-              "",
-              "Note that these may become negative. It's perfectly fine for users to borrow one one bank",
-              "(increasing indexed_borrows there) and paying back on another (possibly decreasing indexed_borrows",
-              "below zero).",
-              "",
-              "The vault amount is not deducable from these values.",
-              "",
-              "These become meaningful when summed over all banks (like in update_index_and_rate)."
-            ],
-            "type": {
-              "defined": "I80F48"
-            }
-          },
-          {
-            "name": "indexedBorrows",
-            "type": {
-              "defined": "I80F48"
-            }
-          },
-          {
-            "name": "indexLastUpdated",
-            "type": "u64"
-          },
-          {
-            "name": "bankRateLastUpdated",
-            "type": "u64"
-          },
-          {
-            "name": "avgUtilization",
-            "type": {
-              "defined": "I80F48"
-            }
-          },
-          {
-            "name": "adjustmentFactor",
-            "type": {
-              "defined": "I80F48"
-            }
-          },
-          {
-            "name": "util0",
-            "docs": [
-              "The unscaled borrow interest curve is defined as continuous piecewise linear with the points:",
-              "",
-              "- 0% util: zero_util_rate",
-              "- util0% util: rate0",
-              "- util1% util: rate1",
-              "- 100% util: max_rate",
-              "",
-              "The final rate is this unscaled curve multiplied by interest_curve_scaling."
-            ],
-            "type": {
-              "defined": "I80F48"
-            }
-          },
-          {
-            "name": "rate0",
-            "type": {
-              "defined": "I80F48"
-            }
-          },
-          {
-            "name": "util1",
-            "type": {
-              "defined": "I80F48"
-            }
-          },
-          {
-            "name": "rate1",
-            "type": {
-              "defined": "I80F48"
-            }
+      "name": "perpScheduledOrderCreate",
+      "docs": [
+        "",
+        "Perp scheduled orders",
+        ""
+      ],
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "perpMarket",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "side",
+          "type": {
+            "defined": "Side"
+          }
+        },
+        {
+          "name": "orderType",
+          "type": {
+            "defined": "PerpScheduledOrderType"
+          }
+        },
+        {
+          "name": "maxBaseLots",
+          "type": "i64"
+        },
+        {
+          "name": "childBaseLots",
+          "type": "i64"
+        },
+        {
+          "name": "priceLots",
+          "type": "i64"
+        },
+        {
+          "name": "intervalSeconds",
+          "type": "u64"
+        },
+        {
+          "name": "expiryTimestamp",
+          "type": "u64"
+        },
+        {
+          "name": "reduceOnly",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "perpConditionalOrderCreate",
+      "docs": [
+        "Stop-loss and take-profit orders use the scheduled order storage and",
+        "are cancelled and triggered with the scheduled order instructions."
+      ],
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "perpMarket",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "side",
+          "type": {
+            "defined": "Side"
+          }
+        },
+        {
+          "name": "orderType",
+          "type": {
+            "defined": "PerpScheduledOrderType"
+          }
+        },
+        {
+          "name": "maxBaseLots",
+          "type": "i64"
+        },
+        {
+          "name": "triggerPriceLots",
+          "type": "i64"
+        },
+        {
+          "name": "maxSlippage",
+          "type": "f32"
+        },
+        {
+          "name": "expiryTimestamp",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "perpScheduledOrderCancel",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "perpMarket",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The market of the scheduled order, for cancelling a resting iceberg child order"
+          ],
+          "relations": [
+            "group",
+            "bids",
+            "asks"
+          ]
+        },
+        {
+          "name": "bids",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "asks",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "perpScheduledOrderIndex",
+          "type": "u8"
+        },
+        {
+          "name": "perpScheduledOrderId",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "perpScheduledOrderTrigger",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "caller",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "callerAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "perpMarket",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "bids",
+            "asks",
+            "event_queue",
+            "oracle"
+          ]
+        },
+        {
+          "name": "bids",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "asks",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "eventQueue",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "oracle",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "settleBank",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "perpScheduledOrderIndex",
+          "type": "u8"
+        },
+        {
+          "name": "perpScheduledOrderId",
+          "type": "u64"
+        },
+        {
+          "name": "limit",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "benchmark",
+      "docs": [
+        "",
+        "benchmark",
+        ""
+      ],
+      "accounts": [
+        {
+          "name": "dummy",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    }
+  ],
+  "accounts": [
+    {
+      "name": "bank",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "group",
+            "type": "publicKey"
+          },
+          {
+            "name": "name",
+            "type": {
+              "array": [
+                "u8",
+                16
+              ]
+            }
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "vault",
+            "type": "publicKey"
+          },
+          {
+            "name": "oracle",
+            "type": "publicKey"
+          },
+          {
+            "name": "oracleConfig",
+            "type": {
+              "defined": "OracleConfig"
+            }
+          },
+          {
+            "name": "stablePriceModel",
+            "type": {
+              "defined": "StablePriceModel"
+            }
+          },
+          {
+            "name": "depositIndex",
+            "docs": [
+              "the index used to scale the value of an IndexedPosition",
+              "TODO: should always be >= 0, add checks?"
+            ],
+            "type": {
+              "defined": "I80F48"
+            }
+          },
+          {
+            "name": "borrowIndex",
+            "type": {
+              "defined": "I80F48"
+            }
+          },
+          {
+            "name": "indexedDeposits",
+            "docs": [
+              "deposits/borrows for this bank",
+              "",
+              "Note that these may become negative. It's perfectly fine for users to borrow one one bank",
+              "(increasing indexed_borrows there) and paying back on another (possibly decreasing indexed_borrows",
+              "below zero).",
+              "",
+              "The vault amount is not deducable from these values.",
+              "",
+              "These become meaningful when summed over all banks (like in update_index_and_rate)."
+            ],
+            "type": {
+              "defined": "I80F48"
+            }
+          },
+          {
+            "name": "indexedBorrows",
+            "type": {
+              "defined": "I80F48"
+            }
+          },
+          {
+            "name": "indexLastUpdated",
+            "type": "u64"
+          },
+          {
+            "name": "bankRateLastUpdated",
+            "type": "u64"
+          },
+          {
+            "name": "avgUtilization",
+            "type": {
+              "defined": "I80F48"
+            }
+          },
+          {
+            "name": "adjustmentFactor",
+            "type": {
+              "defined": "I80F48"
+            }
+          },
+          {
+            "name": "util0",
+            "docs": [
+              "The unscaled borrow interest curve is defined as continuous piecewise linear with the points:",
+              "",
+              "- 0% util: zero_util_rate",
+              "- util0% util: rate0",
+              "- util1% util: rate1",
+              "- 100% util: max_rate",
+              "",
+              "The final rate is this unscaled curve multiplied by interest_curve_scaling."
+            ],
+            "type": {
+              "defined": "I80F48"
+            }
+          },
+          {
+            "name": "rate0",
+            "type": {
+              "defined": "I80F48"
+            }
+          },
+          {
+            "name": "util1",
+            "type": {
+              "defined": "I80F48"
+            }
+          },
+          {
+            "name": "rate1",
+            "type": {
+              "defined": "I80F48"
+            }
           },
           {
             "name": "maxRate",
@@ -7832,102 +9906,511 @@ export type MangoV4 = {
             "type": "f32"
           },
           {
-            "name": "reserved",
-            "type": {
-              "array": [
-                "u8",
-                1900
-              ]
-            }
-          }
-        ]
-      }
-    },
-    {
-      "name": "group",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "creator",
-            "type": "publicKey"
-          },
-          {
-            "name": "groupNum",
+            "name": "circuitBreakerCooldownSeconds",
+            "docs": [
+              "How long the bank stays reduce-only after the circuit breaker trips."
+            ],
             "type": "u32"
           },
           {
-            "name": "admin",
-            "type": "publicKey"
+            "name": "circuitBreakerTrippedUntil",
+            "docs": [
+              "While non-zero, the circuit breaker is tripped and borrows are reduce-only.",
+              "Cleared by token_update_index_and_rate once this timestamp has passed."
+            ],
+            "type": "u64"
           },
           {
-            "name": "fastListingAdmin",
-            "type": "publicKey"
+            "name": "circuitBreakerMaxPriceMove",
+            "docs": [
+              "Maximal relative difference between the oracle price and the latest delay price",
+              "of the stable price model before the circuit breaker trips. Zero disables it."
+            ],
+            "type": "f32"
           },
           {
-            "name": "mngoTokenIndex",
-            "type": "u16"
+            "name": "interestRateModel",
+            "docs": [
+              "Selects how rate0 and rate1 are adjusted over time, see InterestRateModel"
+            ],
+            "type": "u8"
           },
           {
-            "name": "padding",
+            "name": "padding2",
             "type": {
               "array": [
                 "u8",
-                2
+                3
               ]
             }
           },
           {
-            "name": "insuranceVault",
-            "type": "publicKey"
+            "name": "interestControllerKp",
+            "docs": [
+              "Gains of the rate controller: the relative daily change of rate0 and rate1 is",
+              "kp * error + ki * integral, with error = avg_utilization - interest_target_utilization."
+            ],
+            "type": "f32"
           },
           {
-            "name": "insuranceMint",
-            "type": "publicKey"
+            "name": "interestControllerKi",
+            "type": "f32"
           },
           {
-            "name": "bump",
-            "type": "u8"
+            "name": "interestControllerMaxDailyChange",
+            "docs": [
+              "Bound on the relative change of rate0 and rate1 per day, must be less than 1"
+            ],
+            "type": "f32"
           },
           {
-            "name": "testing",
-            "type": "u8"
+            "name": "interestControllerError",
+            "docs": [
+              "Utilization error at the last controller update"
+            ],
+            "type": "f32"
           },
           {
-            "name": "version",
-            "type": "u8"
+            "name": "interestControllerIntegral",
+            "docs": [
+              "Accumulated utilization error, in utilization * days, bounded to [-1, 1]"
+            ],
+            "type": "f64"
           },
           {
-            "name": "buybackFees",
-            "type": "u8"
+            "name": "borrowLimit",
+            "docs": [
+              "Limit on the total native borrows of the bank, zero means none.",
+              "",
+              "The limit is checked per bank. It can only be set on tokens with a single",
+              "bank and no banks can be added while it is set, so it bounds the",
+              "outstanding borrows of the whole token."
+            ],
+            "type": "u64"
           },
           {
-            "name": "buybackFeesMngoBonusFactor",
-            "type": "f32"
+            "name": "isolatedCollateral",
+            "docs": [
+              "If set, deposits of this token only count as collateral for borrows of the",
+              "tokens in isolated_borrow_token_indexes, up to isolated_debt_ceiling_quote."
+            ],
+            "type": "u8"
           },
           {
-            "name": "addressLookupTables",
+            "name": "padding3",
             "type": {
               "array": [
-                "publicKey",
-                20
+                "u8",
+                1
               ]
             }
           },
           {
-            "name": "securityAdmin",
-            "type": "publicKey"
+            "name": "isolatedBorrowTokenIndexes",
+            "docs": [
+              "Tokens that isolated collateral may back, unused entries are TokenIndex::MAX"
+            ],
+            "type": {
+              "array": [
+                "u16",
+                7
+              ]
+            }
           },
           {
-            "name": "depositLimitQuote",
+            "name": "isolatedDebtCeilingQuote",
+            "docs": [
+              "Maximal health-weighted value of borrows, in native quote, that isolated",
+              "collateral of this token may back across all accounts, see isolated_debt_quote.",
+              "",
+              "Combine with deposit_limit to bound the total exposure to the token."
+            ],
             "type": "u64"
           },
           {
-            "name": "ixGate",
-            "type": "u128"
-          },
-          {
-            "name": "buybackFeesSwapMangoAccount",
+            "name": "emodeCategory",
+            "docs": [
+              "E-mode category of the token, zero means none.",
+              "",
+              "The category's boosted weights are copied from the group, see EmodeCategory."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "padding4",
+            "type": {
+              "array": [
+                "u8",
+                3
+              ]
+            }
+          },
+          {
+            "name": "emodeMaintAssetWeight",
+            "type": "f32"
+          },
+          {
+            "name": "emodeInitAssetWeight",
+            "type": "f32"
+          },
+          {
+            "name": "emodeMaintLiabWeight",
+            "type": "f32"
+          },
+          {
+            "name": "emodeInitLiabWeight",
+            "type": "f32"
+          },
+          {
+            "name": "padding5",
+            "type": {
+              "array": [
+                "u8",
+                4
+              ]
+            }
+          },
+          {
+            "name": "fixedTermBorrowsNative",
+            "docs": [
+              "Amount owed at maturity to fixed term markets of this token.",
+              "",
+              "Counts toward borrow_limit like regular borrows."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "isolatedDebtQuote",
+            "docs": [
+              "Health-weighted value of borrows, in native quote, backed by isolated collateral",
+              "of this token, summed over all accounts.",
+              "",
+              "Each account's share is recorded in its token position and refreshed whenever",
+              "a borrow or withdraw checks its health, see update_isolated_debt(). Can't grow",
+              "beyond isolated_debt_ceiling_quote."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                1784
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "clmmTwapOracle",
+      "docs": [
+        "A time-weighted average price derived from a raydium CLMM pool's observations.",
+        "",
+        "The pool's sqrt_price can be moved freely within a transaction, so the instantaneous",
+        "CLMM oracles are easy to manipulate. This oracle instead averages the pool's recorded",
+        "tick accumulator over `twap_window_seconds`. It is refreshed permissionlessly with",
+        "clmm_twap_oracle_update and then reads like the CLMM oracles: `price` is multiplied by",
+        "the usdc or sol oracle price of `quote_mint`.",
+        "",
+        "Orca whirlpools don't record an observation history and can't back this oracle."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "group",
+            "type": "publicKey"
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "pool",
+            "type": "publicKey"
+          },
+          {
+            "name": "observation",
+            "type": "publicKey"
+          },
+          {
+            "name": "quoteMint",
+            "docs": [
+              "The pool token whose usdc or sol oracle converts `price` to usd"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "price",
+            "docs": [
+              "Price of one native `mint` token in native `quote_mint` tokens"
+            ],
+            "type": {
+              "defined": "I80F48"
+            }
+          },
+          {
+            "name": "twapWindowSeconds",
+            "type": "u64"
+          },
+          {
+            "name": "observedWindowSeconds",
+            "docs": [
+              "Length of the window used for the last update, at least twap_window_seconds"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lastUpdateSlot",
+            "type": "u64"
+          },
+          {
+            "name": "lastUpdateTs",
+            "type": "u64"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                144
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "fixedTermMarket",
+      "docs": [
+        "A market for lending or borrowing one token at a fixed rate until a fixed maturity.",
+        "",
+        "Lenders move deposits out of their token position into the market, borrowers take",
+        "the lent tokens into their token position. Both sides lock in the amount that is",
+        "owed at maturity when they enter, so later rate changes only affect new loans.",
+        "",
+        "After maturity, anyone can settle the positions back into the regular token positions.",
+        "Lender interest is funded by borrower interest only, see lend_interest_at_maturity().",
+        "The difference between borrower and lender interest stays in the bank vault."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "group",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenIndex",
+            "type": "u16"
+          },
+          {
+            "name": "marketIndex",
+            "type": "u16"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "reduceOnly",
+            "type": "u8"
+          },
+          {
+            "name": "padding1",
+            "type": {
+              "array": [
+                "u8",
+                2
+              ]
+            }
+          },
+          {
+            "name": "name",
+            "type": {
+              "array": [
+                "u8",
+                16
+              ]
+            }
+          },
+          {
+            "name": "maturityTimestamp",
+            "docs": [
+              "Timestamp after which positions can be settled"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lendRate",
+            "docs": [
+              "Annualized simple interest rate earned by lenders"
+            ],
+            "type": {
+              "defined": "I80F48"
+            }
+          },
+          {
+            "name": "borrowRate",
+            "docs": [
+              "Annualized simple interest rate paid by borrowers, always >= lend_rate"
+            ],
+            "type": {
+              "defined": "I80F48"
+            }
+          },
+          {
+            "name": "maxLentNative",
+            "docs": [
+              "Limit on total_lent_native, to bound how much the bank may owe to lenders"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "totalLentNative",
+            "docs": [
+              "Outstanding principal lent into the market"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "totalBorrowedNative",
+            "docs": [
+              "Outstanding principal borrowed from the market, always <= total_lent_native"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "totalLentAtMaturityNative",
+            "docs": [
+              "Outstanding amount owed to lenders at maturity"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "totalBorrowedAtMaturityNative",
+            "docs": [
+              "Outstanding amount owed by borrowers at maturity"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "registrationTime",
+            "type": "u64"
+          },
+          {
+            "name": "totalLendInterestNative",
+            "docs": [
+              "Interest promised to all lenders, never decreases"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "totalBorrowInterestNative",
+            "docs": [
+              "Interest owed by all borrowers, never decreases"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                496
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "group",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "creator",
+            "type": "publicKey"
+          },
+          {
+            "name": "groupNum",
+            "type": "u32"
+          },
+          {
+            "name": "admin",
+            "type": "publicKey"
+          },
+          {
+            "name": "fastListingAdmin",
+            "type": "publicKey"
+          },
+          {
+            "name": "mngoTokenIndex",
+            "type": "u16"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                2
+              ]
+            }
+          },
+          {
+            "name": "insuranceVault",
+            "type": "publicKey"
+          },
+          {
+            "name": "insuranceMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "testing",
+            "type": "u8"
+          },
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "buybackFees",
+            "type": "u8"
+          },
+          {
+            "name": "buybackFeesMngoBonusFactor",
+            "type": "f32"
+          },
+          {
+            "name": "addressLookupTables",
+            "type": {
+              "array": [
+                "publicKey",
+                20
+              ]
+            }
+          },
+          {
+            "name": "securityAdmin",
+            "type": "publicKey"
+          },
+          {
+            "name": "depositLimitQuote",
+            "type": "u64"
+          },
+          {
+            "name": "ixGate",
+            "type": "u128"
+          },
+          {
+            "name": "buybackFeesSwapMangoAccount",
             "type": "publicKey"
           },
           {
@@ -7980,12 +10463,55 @@ export type MangoV4 = {
             ],
             "type": "u64"
           },
+          {
+            "name": "emodeCategories",
+            "docs": [
+              "E-mode categories, the category with id i is stored at index i - 1"
+            ],
+            "type": {
+              "array": [
+                {
+                  "defined": "EmodeCategory"
+                },
+                4
+              ]
+            }
+          },
+          {
+            "name": "perpFeeTiers",
+            "docs": [
+              "Perp fee tiers selected by trailing account volume, the tier with id i is",
+              "stored at index i - 1"
+            ],
+            "type": {
+              "array": [
+                {
+                  "defined": "PerpFeeTier"
+                },
+                4
+              ]
+            }
+          },
+          {
+            "name": "portfolioMarginPairs",
+            "docs": [
+              "Tokens and perp markets with the same underlying, for portfolio margin accounts"
+            ],
+            "type": {
+              "array": [
+                {
+                  "defined": "PortfolioMarginPair"
+                },
+                16
+              ]
+            }
+          },
           {
             "name": "reserved",
             "type": {
               "array": [
                 "u8",
-                1800
+                1160
               ]
             }
           }
@@ -8117,12 +10643,83 @@ export type MangoV4 = {
             ],
             "type": "u64"
           },
+          {
+            "name": "nextPerpScheduledOrderId",
+            "docs": [
+              "Next id to use when adding a perp scheduled order"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "portfolioMargin",
+            "docs": [
+              "Opt-in portfolio margining: spot balances and perp positions on the same",
+              "underlying (see PerpMarket::portfolio_margin_token_index) are netted for health."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "emodeCategory",
+            "docs": [
+              "Opt-in e-mode category, zero means none, see Group::emode_categories"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "padding12",
+            "type": {
+              "array": [
+                "u8",
+                6
+              ]
+            }
+          },
+          {
+            "name": "delegateScope",
+            "docs": [
+              "Restrictions on what the delegate may do, see DelegateScope"
+            ],
+            "type": {
+              "defined": "DelegateScope"
+            }
+          },
+          {
+            "name": "temporaryDelegateScope",
+            "docs": [
+              "Restrictions on what the temporary delegate may do"
+            ],
+            "type": {
+              "defined": "DelegateScope"
+            }
+          },
+          {
+            "name": "perpFeeVolumeCurrent",
+            "docs": [
+              "Perp volume (quote native) in the current fee tier volume window.",
+              "Used for selecting the account's perp fee tier, see Group::perp_fee_tiers."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "perpFeeVolumePrevious",
+            "docs": [
+              "Perp volume (quote native) in the previous fee tier volume window."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "perpFeeVolumeWindowEnd",
+            "docs": [
+              "End timestamp of the current fee tier volume window."
+            ],
+            "type": "u64"
+          },
           {
             "name": "reserved",
             "type": {
               "array": [
                 "u8",
-                152
+                80
               ]
             }
           },
@@ -8199,6 +10796,42 @@ export type MangoV4 = {
               }
             }
           },
+          {
+            "name": "padding9",
+            "type": "u32"
+          },
+          {
+            "name": "openbookV2",
+            "type": {
+              "vec": {
+                "defined": "OpenbookV2Orders"
+              }
+            }
+          },
+          {
+            "name": "padding10",
+            "type": "u32"
+          },
+          {
+            "name": "fixedTermPositions",
+            "type": {
+              "vec": {
+                "defined": "FixedTermPosition"
+              }
+            }
+          },
+          {
+            "name": "padding11",
+            "type": "u32"
+          },
+          {
+            "name": "perpScheduledOrders",
+            "type": {
+              "vec": {
+                "defined": "PerpScheduledOrder"
+              }
+            }
+          },
           {
             "name": "reservedDynamic",
             "type": {
@@ -8393,7 +11026,16 @@ export type MangoV4 = {
       }
     },
     {
-      "name": "stubOracle",
+      "name": "openbookV2MidOracle",
+      "docs": [
+        "The mid price of an openbook v2 market's best bid and ask.",
+        "",
+        "The book is spread over the market, bids and asks accounts, so it can't be read from a",
+        "single oracle account. This oracle caches the mid price instead and is refreshed",
+        "permissionlessly with openbook_v2_mid_oracle_update. It reads like the CLMM oracles:",
+        "`price` is multiplied by the usdc or sol oracle price of `quote_mint`. Half the spread",
+        "is reported as the deviation, so the bank's conf_filter bounds the accepted spread."
+      ],
       "type": {
         "kind": "struct",
         "fields": [
@@ -8405,32 +11047,57 @@ export type MangoV4 = {
             "name": "mint",
             "type": "publicKey"
           },
+          {
+            "name": "market",
+            "type": "publicKey"
+          },
+          {
+            "name": "bids",
+            "type": "publicKey"
+          },
+          {
+            "name": "asks",
+            "type": "publicKey"
+          },
+          {
+            "name": "quoteMint",
+            "docs": [
+              "The market's quote token, whose usdc or sol oracle converts `price` to usd"
+            ],
+            "type": "publicKey"
+          },
           {
             "name": "price",
+            "docs": [
+              "Mid price of one native `mint` token in native `quote_mint` tokens"
+            ],
             "type": {
               "defined": "I80F48"
             }
           },
           {
-            "name": "lastUpdateTs",
-            "type": "i64"
+            "name": "deviation",
+            "docs": [
+              "Half the bid-ask spread, in the same unit as `price`"
+            ],
+            "type": {
+              "defined": "I80F48"
+            }
           },
           {
             "name": "lastUpdateSlot",
             "type": "u64"
           },
           {
-            "name": "deviation",
-            "type": {
-              "defined": "I80F48"
-            }
+            "name": "lastUpdateTs",
+            "type": "u64"
           },
           {
             "name": "reserved",
             "type": {
               "array": [
                 "u8",
-                104
+                128
               ]
             }
           }
@@ -8438,48 +11105,214 @@ export type MangoV4 = {
       }
     },
     {
-      "name": "bookSide",
+      "name": "oracleAggregator",
+      "docs": [
+        "An oracle whose price is the median of several source oracles.",
+        "",
+        "Banks and perp markets use it like any other oracle by setting it as their `oracle`.",
+        "The price is refreshed permissionlessly with oracle_aggregator_update, which reads",
+        "all source oracles, drops the stale ones and the ones that diverge too much from the",
+        "median, and only publishes a price if at least `min_quorum` sources remain."
+      ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "roots",
-            "type": {
-              "array": [
-                {
-                  "defined": "OrderTreeRoot"
-                },
-                2
-              ]
-            }
+            "name": "group",
+            "type": "publicKey"
           },
           {
-            "name": "reservedRoots",
-            "type": {
-              "array": [
-                {
-                  "defined": "OrderTreeRoot"
-                },
-                4
-              ]
-            }
+            "name": "mint",
+            "type": "publicKey"
           },
           {
-            "name": "reserved",
+            "name": "price",
+            "docs": [
+              "Price of one native base token in native quote tokens, for `mint_decimals`"
+            ],
             "type": {
-              "array": [
-                "u8",
-                256
-              ]
+              "defined": "I80F48"
             }
           },
           {
-            "name": "nodes",
+            "name": "deviation",
+            "docs": [
+              "Largest difference between the median and a source that was used"
+            ],
             "type": {
-              "defined": "OrderTreeNodes"
+              "defined": "I80F48"
             }
-          }
-        ]
+          },
+          {
+            "name": "lastUpdateSlot",
+            "docs": [
+              "Oldest update slot of the sources that were used"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lastUpdateTs",
+            "type": "u64"
+          },
+          {
+            "name": "oldestSourcePublishTs",
+            "docs": [
+              "Oldest publish time of the sources that were used and report one, 0 if none do"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "sourceOracleConfig",
+            "docs": [
+              "Confidence and staleness limits that each source must pass to be used"
+            ],
+            "type": {
+              "defined": "OracleConfig"
+            }
+          },
+          {
+            "name": "maxDivergence",
+            "docs": [
+              "Sources further than this fraction from the median are ignored"
+            ],
+            "type": "f64"
+          },
+          {
+            "name": "minQuorum",
+            "docs": [
+              "Minimum number of fresh, agreeing sources needed to publish a price"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "numOracles",
+            "type": "u8"
+          },
+          {
+            "name": "mintDecimals",
+            "type": "u8"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                5
+              ]
+            }
+          },
+          {
+            "name": "oracles",
+            "docs": [
+              "Source oracles, only the first num_oracles are used"
+            ],
+            "type": {
+              "array": [
+                "publicKey",
+                8
+              ]
+            }
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                120
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "stubOracle",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "group",
+            "type": "publicKey"
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "price",
+            "type": {
+              "defined": "I80F48"
+            }
+          },
+          {
+            "name": "lastUpdateTs",
+            "type": "i64"
+          },
+          {
+            "name": "lastUpdateSlot",
+            "type": "u64"
+          },
+          {
+            "name": "deviation",
+            "type": {
+              "defined": "I80F48"
+            }
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                104
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "bookSide",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "roots",
+            "type": {
+              "array": [
+                {
+                  "defined": "OrderTreeRoot"
+                },
+                2
+              ]
+            }
+          },
+          {
+            "name": "reservedRoots",
+            "type": {
+              "array": [
+                {
+                  "defined": "OrderTreeRoot"
+                },
+                4
+              ]
+            }
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                256
+              ]
+            }
+          },
+          {
+            "name": "nodes",
+            "type": {
+              "defined": "OrderTreeNodes"
+            }
+          }
+        ]
       }
     },
     {
@@ -8937,12 +11770,84 @@ export type MangoV4 = {
               "defined": "I80F48"
             }
           },
+          {
+            "name": "portfolioMarginTokenIndex",
+            "docs": [
+              "Token with the same underlying as this market's base, copied from",
+              "Group::portfolio_margin_pairs. Only used if portfolio_margin_active is 1."
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "portfolioMarginActive",
+            "type": "u8"
+          },
+          {
+            "name": "padding5",
+            "type": {
+              "array": [
+                "u8",
+                5
+              ]
+            }
+          },
+          {
+            "name": "fundingStablePriceWeight",
+            "docs": [
+              "Weight of the stable price in the reference price that funding is computed",
+              "against. Zero uses the oracle price only, one uses the stable price only."
+            ],
+            "type": "f32"
+          },
+          {
+            "name": "settleStablePriceWeight",
+            "docs": [
+              "Weight of the stable price in the price that pnl is settled at, see",
+              "funding_stable_price_weight. The settle limit itself is always based on the",
+              "stable price."
+            ],
+            "type": "f32"
+          },
+          {
+            "name": "openingAuctionDuration",
+            "docs": [
+              "Length in seconds of the opening auction that starts when the market is created",
+              "or leaves reduce-only mode. Zero disables opening auctions."
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "padding6",
+            "type": {
+              "array": [
+                "u8",
+                4
+              ]
+            }
+          },
+          {
+            "name": "openingAuctionEndTs",
+            "docs": [
+              "While non-zero, the market is in an opening auction: orders are posted without",
+              "matching. After this timestamp perp_clear_opening_auction executes the crossing",
+              "orders at a uniform price and resets it to zero."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "openingAuctionPriceLots",
+            "docs": [
+              "Clearing price of the opening auction, zero until the first clearing call",
+              "computed it"
+            ],
+            "type": "i64"
+          },
           {
             "name": "reserved",
             "type": {
               "array": [
                 "u8",
-                1848
+                1808
               ]
             }
           }
@@ -9070,6 +11975,44 @@ export type MangoV4 = {
     }
   ],
   "types": [
+    {
+      "name": "PerpBatchOrder",
+      "docs": [
+        "One order of a PerpPlaceOrdersBatch instruction"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "side",
+            "type": {
+              "defined": "Side"
+            }
+          },
+          {
+            "name": "priceLots",
+            "docs": [
+              "Price in lots, see perp_place_order"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "maxBaseLots",
+            "type": "i64"
+          },
+          {
+            "name": "orderType",
+            "type": {
+              "defined": "PlaceOrderType"
+            }
+          },
+          {
+            "name": "clientOrderId",
+            "type": "u64"
+          }
+        ]
+      }
+    },
     {
       "name": "InterestRateParams",
       "type": {
@@ -9330,6 +12273,127 @@ export type MangoV4 = {
         ]
       }
     },
+    {
+      "name": "DelegateScope",
+      "docs": [
+        "Restrictions that apply to a delegate of a MangoAccount.",
+        "",
+        "A scope with permissions == 0 is unrestricted. That's the default for delegates that were",
+        "set up before scopes existed."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "permissions",
+            "docs": [
+              "Bitmask of DelegatePermission values"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "perpMarketIndex",
+            "docs": [
+              "Perp market the delegate may use, PerpMarketIndex::MAX for any"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "spotMarketIndex",
+            "docs": [
+              "Serum3 or openbook v2 market the delegate may use, u16::MAX for any"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                2
+              ]
+            }
+          },
+          {
+            "name": "maxNotionalPerOrder",
+            "docs": [
+              "Maximum size of each order placed by the delegate, in native units of the",
+              "market's quote or settle token. Zero for no limit.",
+              "",
+              "Orders are checked one at a time: this doesn't limit the delegate's total exposure."
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "EmodeCategory",
+      "docs": [
+        "A group of correlated tokens, like SOL and its liquid staking tokens.",
+        "",
+        "Accounts that opted into a category and only have exposure to its tokens use the",
+        "category's boosted weights for health, see HealthCache::emode_credit().",
+        "",
+        "The weights are copied to the banks of the tokens, since health computations only",
+        "have access to banks."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tokenIndexes",
+            "docs": [
+              "Tokens in the category, only the first token_count entries are used"
+            ],
+            "type": {
+              "array": [
+                "u16",
+                8
+              ]
+            }
+          },
+          {
+            "name": "tokenCount",
+            "type": "u8"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                3
+              ]
+            }
+          },
+          {
+            "name": "maintAssetWeight",
+            "type": "f32"
+          },
+          {
+            "name": "initAssetWeight",
+            "type": "f32"
+          },
+          {
+            "name": "maintLiabWeight",
+            "type": "f32"
+          },
+          {
+            "name": "initLiabWeight",
+            "type": "f32"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                28
+              ]
+            }
+          }
+        ]
+      }
+    },
     {
       "name": "TokenPosition",
       "type": {
@@ -9381,12 +12445,20 @@ export type MangoV4 = {
             "name": "cumulativeBorrowInterest",
             "type": "f64"
           },
+          {
+            "name": "isolatedDebtQuote",
+            "docs": [
+              "For isolated collateral tokens: this account's share of Bank::isolated_debt_quote,",
+              "as of the last time it was updated, see update_isolated_debt()."
+            ],
+            "type": "u64"
+          },
           {
             "name": "reserved",
             "type": {
               "array": [
                 "u8",
-                128
+                120
               ]
             }
           }
@@ -9503,6 +12575,189 @@ export type MangoV4 = {
         ]
       }
     },
+    {
+      "name": "OpenbookV2Orders",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "openOrders",
+            "type": "publicKey"
+          },
+          {
+            "name": "baseBorrowsWithoutFee",
+            "docs": [
+              "Tracks the amount of borrows that have flowed into the open orders account.",
+              "These borrows did not have the loan origination fee applied, and that may happen",
+              "later (in openbook_v2_settle_funds) if we can guarantee that the funds were used.",
+              "In particular a place-on-book, cancel, settle should not cost fees."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "quoteBorrowsWithoutFee",
+            "type": "u64"
+          },
+          {
+            "name": "marketIndex",
+            "type": "u16"
+          },
+          {
+            "name": "baseTokenIndex",
+            "docs": [
+              "Store the base/quote token index, so health computations don't need",
+              "to get passed the static OpenbookV2Market to find which tokens a market",
+              "uses and look up the correct oracles."
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "quoteTokenIndex",
+            "type": "u16"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                2
+              ]
+            }
+          },
+          {
+            "name": "highestPlacedBidInv",
+            "docs": [
+              "Track the highest/lowest placed bid and ask, in native/native units.",
+              "",
+              "Works like the same fields on Serum3Orders. 0 is a special \"unset\" state."
+            ],
+            "type": "f64"
+          },
+          {
+            "name": "lowestPlacedBidInv",
+            "type": "f64"
+          },
+          {
+            "name": "lowestPlacedAsk",
+            "type": "f64"
+          },
+          {
+            "name": "highestPlacedAsk",
+            "type": "f64"
+          },
+          {
+            "name": "potentialBaseTokens",
+            "docs": [
+              "An overestimate of the amount of tokens that might flow out of the open orders account.",
+              "",
+              "The bank still considers these amounts user deposits (see Bank::potential_serum_tokens)",
+              "and that value needs to be updated in conjunction with these numbers."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "potentialQuoteTokens",
+            "type": "u64"
+          },
+          {
+            "name": "baseLotSize",
+            "docs": [
+              "Lot sizes of the openbook v2 market, copied on creation.",
+              "",
+              "The open orders account tracks reserved amounts in lots, so health needs these",
+              "to compute native amounts without being passed the market."
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "quoteLotSize",
+            "type": "i64"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                96
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "FixedTermPosition",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketIndex",
+            "type": "u16"
+          },
+          {
+            "name": "tokenIndex",
+            "docs": [
+              "Store the token index, so health computations don't need to get passed",
+              "the FixedTermMarket to find which token a position is in."
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                4
+              ]
+            }
+          },
+          {
+            "name": "maturityTimestamp",
+            "docs": [
+              "Copied from the market, the position can be settled after this time"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lentNative",
+            "docs": [
+              "Principal lent into the market"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lentAtMaturityNative",
+            "docs": [
+              "Amount that will be credited to the token position at maturity"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "borrowedNative",
+            "docs": [
+              "Principal borrowed from the market"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "borrowedAtMaturityNative",
+            "docs": [
+              "Amount that will be debited from the token position at maturity"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                64
+              ]
+            }
+          }
+        ]
+      }
+    },
     {
       "name": "PerpPosition",
       "type": {
@@ -9734,12 +12989,41 @@ export type MangoV4 = {
               "defined": "I80F48"
             }
           },
+          {
+            "name": "isolated",
+            "docs": [
+              "Whether the position is isolated: it may only use isolated_collateral_native as",
+              "collateral and doesn't contribute to the health of the rest of the account."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "padding2",
+            "type": {
+              "array": [
+                "u8",
+                7
+              ]
+            }
+          },
+          {
+            "name": "isolatedCollateralNative",
+            "docs": [
+              "Settle token native allocated to an isolated position.",
+              "",
+              "This is a part of the account's settle token balance that only backs this position.",
+              "Settling pnl of the position moves funds in and out of it."
+            ],
+            "type": {
+              "defined": "I80F48"
+            }
+          },
           {
             "name": "reserved",
             "type": {
               "array": [
                 "u8",
-                88
+                64
               ]
             }
           }
@@ -9891,12 +13175,57 @@ export type MangoV4 = {
             "name": "lastCollateralFeeCharge",
             "type": "u64"
           },
+          {
+            "name": "nextPerpScheduledOrderId",
+            "type": "u64"
+          },
+          {
+            "name": "portfolioMargin",
+            "type": "u8"
+          },
+          {
+            "name": "emodeCategory",
+            "type": "u8"
+          },
+          {
+            "name": "padding12",
+            "type": {
+              "array": [
+                "u8",
+                6
+              ]
+            }
+          },
+          {
+            "name": "delegateScope",
+            "type": {
+              "defined": "DelegateScope"
+            }
+          },
+          {
+            "name": "temporaryDelegateScope",
+            "type": {
+              "defined": "DelegateScope"
+            }
+          },
+          {
+            "name": "perpFeeVolumeCurrent",
+            "type": "u64"
+          },
+          {
+            "name": "perpFeeVolumePrevious",
+            "type": "u64"
+          },
+          {
+            "name": "perpFeeVolumeWindowEnd",
+            "type": "u64"
+          },
           {
             "name": "reserved",
             "type": {
               "array": [
                 "u8",
-                152
+                80
               ]
             }
           }
@@ -9916,6 +13245,30 @@ export type MangoV4 = {
           },
           {
             "name": "maxStalenessSlots",
+            "docs": [
+              "Negative values disable the staleness check"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "maxStalenessSeconds",
+            "docs": [
+              "Maximum age of the oracle's publish time, for oracles that report one.",
+              "",
+              "Zero means the limit is derived from max_staleness_slots, assuming 450ms per slot."
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "riskIncreasingMaxStalenessSlots",
+            "docs": [
+              "Stricter limits applied on top of the ones above for risk-increasing actions,",
+              "like borrowing or placing orders. Zero means no additional limit."
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "riskIncreasingMaxStalenessSeconds",
             "type": "i64"
           },
           {
@@ -9923,7 +13276,7 @@ export type MangoV4 = {
             "type": {
               "array": [
                 "u8",
-                72
+                48
               ]
             }
           }
@@ -9944,6 +13297,82 @@ export type MangoV4 = {
             "type": {
               "option": "u32"
             }
+          },
+          {
+            "name": "maxStalenessSeconds",
+            "type": {
+              "option": "u32"
+            }
+          },
+          {
+            "name": "riskIncreasingMaxStalenessSlots",
+            "type": {
+              "option": "u32"
+            }
+          },
+          {
+            "name": "riskIncreasingMaxStalenessSeconds",
+            "type": {
+              "option": "u32"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "OrderTreeNodes",
+      "docs": [
+        "A binary tree on AnyNode::key()",
+        "",
+        "The key encodes the price in the top 64 bits."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "orderTreeType",
+            "type": "u8"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                3
+              ]
+            }
+          },
+          {
+            "name": "bumpIndex",
+            "type": "u32"
+          },
+          {
+            "name": "freeListLen",
+            "type": "u32"
+          },
+          {
+            "name": "freeListHead",
+            "type": "u32"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                512
+              ]
+            }
+          },
+          {
+            "name": "nodes",
+            "type": {
+              "array": [
+                {
+                  "defined": "AnyNode"
+                },
+                1024
+              ]
+            }
           }
         ]
       }
@@ -10057,13 +13486,12 @@ export type MangoV4 = {
             "type": "u8"
           },
           {
-            "name": "padding",
-            "type": {
-              "array": [
-                "u8",
-                1
-              ]
-            }
+            "name": "feeTier",
+            "docs": [
+              "Perp fee tier of the owner at the time the order was placed, see",
+              "Group::perp_fee_tiers. Determines the maker fee when the order is filled."
+            ],
+            "type": "u8"
           },
           {
             "name": "timeInForce",
@@ -10180,64 +13608,6 @@ export type MangoV4 = {
         ]
       }
     },
-    {
-      "name": "OrderTreeNodes",
-      "docs": [
-        "A binary tree on AnyNode::key()",
-        "",
-        "The key encodes the price in the top 64 bits."
-      ],
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "orderTreeType",
-            "type": "u8"
-          },
-          {
-            "name": "padding",
-            "type": {
-              "array": [
-                "u8",
-                3
-              ]
-            }
-          },
-          {
-            "name": "bumpIndex",
-            "type": "u32"
-          },
-          {
-            "name": "freeListLen",
-            "type": "u32"
-          },
-          {
-            "name": "freeListHead",
-            "type": "u32"
-          },
-          {
-            "name": "reserved",
-            "type": {
-              "array": [
-                "u8",
-                512
-              ]
-            }
-          },
-          {
-            "name": "nodes",
-            "type": {
-              "array": [
-                {
-                  "defined": "AnyNode"
-                },
-                1024
-              ]
-            }
-          }
-        ]
-      }
-    },
     {
       "name": "EventQueueHeader",
       "type": {
@@ -10339,13 +13709,8 @@ export type MangoV4 = {
             "type": "publicKey"
           },
           {
-            "name": "padding3",
-            "type": {
-              "array": [
-                "u8",
-                16
-              ]
-            }
+            "name": "takerOrderId",
+            "type": "u128"
           },
           {
             "name": "takerClientOrderId",
@@ -10375,12 +13740,32 @@ export type MangoV4 = {
             "name": "takerFee",
             "type": "f32"
           },
+          {
+            "name": "makerFeeTier",
+            "type": "u8"
+          },
+          {
+            "name": "takerFeeTier",
+            "type": "u8"
+          },
+          {
+            "name": "auction",
+            "type": "u8"
+          },
+          {
+            "name": "takerSlot",
+            "type": "u8"
+          },
+          {
+            "name": "takerOut",
+            "type": "u8"
+          },
           {
             "name": "reserved",
             "type": {
               "array": [
                 "u8",
-                8
+                3
               ]
             }
           }
@@ -10445,6 +13830,245 @@ export type MangoV4 = {
         ]
       }
     },
+    {
+      "name": "PerpFeeTier",
+      "docs": [
+        "A perp fee tier, selected by an account's trailing perp volume.",
+        "",
+        "The discounts are subtracted from a market's maker_fee and taker_fee. Taker fees",
+        "never become negative, maker fees may turn into (bigger) rebates."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "minVolumeQuote",
+            "docs": [
+              "Trailing perp volume (in quote native) required for this tier.",
+              "A value of 0 means the tier is unused."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "makerFeeDiscount",
+            "type": "f32"
+          },
+          {
+            "name": "takerFeeDiscount",
+            "type": "f32"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                16
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "PerpScheduledOrder",
+      "docs": [
+        "A large perp order that gets split into child orders over time, or a",
+        "conditional order that reduces a perp position once the oracle crosses a threshold.",
+        "",
+        "Child orders are placed by permissionless callers of the perp_scheduled_order_trigger",
+        "instruction, who receive PERP_SCHEDULED_ORDER_INCENTIVE in the market's settle token",
+        "for each trigger that fills or places a non-zero amount."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "id",
+            "type": "u64"
+          },
+          {
+            "name": "maxBaseLots",
+            "docs": [
+              "Total base lots to execute over all child orders"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "executedBaseLots",
+            "docs": [
+              "Base lots that were already filled (Twap) or placed on the book (Iceberg)"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "childBaseLots",
+            "docs": [
+              "Maximum base lots per child order"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "priceLots",
+            "docs": [
+              "Limit price for all child orders, in lots (unused for conditional orders)"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "intervalSeconds",
+            "docs": [
+              "Minimum time between two child orders"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "nextTriggerTimestamp",
+            "docs": [
+              "Child orders can be triggered at or after this timestamp"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "expiryTimestamp",
+            "docs": [
+              "Timestamp until which the scheduled order is valid"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "marketIndex",
+            "type": "u16"
+          },
+          {
+            "name": "isConfigured",
+            "docs": [
+              "If this struct is in use. (scheduled orders are stored in a static-length array)"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "side",
+            "docs": [
+              "Stores a Side enum value"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "orderType",
+            "docs": [
+              "Stores a PerpScheduledOrderType enum value"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "reduceOnly",
+            "type": "u8"
+          },
+          {
+            "name": "hasChildOrder",
+            "docs": [
+              "Whether an iceberg child order may be resting on the book, see child_order_seq_num"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                1
+              ]
+            }
+          },
+          {
+            "name": "triggerPriceLots",
+            "docs": [
+              "Oracle price at which conditional orders become triggerable, in lots"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "maxSlippage",
+            "docs": [
+              "Worst acceptable execution price of conditional orders, relative to the",
+              "oracle price at trigger time. 0.01 means 1%."
+            ],
+            "type": "f32"
+          },
+          {
+            "name": "padding2",
+            "type": {
+              "array": [
+                "u8",
+                4
+              ]
+            }
+          },
+          {
+            "name": "childOrderSeqNum",
+            "docs": [
+              "Sequence number of the last iceberg child order placed on the book.",
+              "",
+              "Order ids are unique per market by their sequence number, see node_key_seq_num(),",
+              "so the child is found by it instead of by its client order id, which the user",
+              "may be using for other orders."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                40
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "PortfolioMarginPair",
+      "docs": [
+        "Links a token to a perp market with the same underlying, like SOL and SOL-PERP.",
+        "",
+        "For accounts that opted into portfolio margining, spot balances in the token offset",
+        "opposite perp base positions before weights are applied, see",
+        "HealthCache::portfolio_margin_hedges().",
+        "",
+        "The token index is copied to the perp market, since health computations only have",
+        "access to perp markets."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tokenIndex",
+            "type": "u16"
+          },
+          {
+            "name": "perpMarketIndex",
+            "type": "u16"
+          },
+          {
+            "name": "active",
+            "docs": [
+              "1 if the pair is in use. Zeroed entries would otherwise link token 0 and perp 0."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                11
+              ]
+            }
+          }
+        ]
+      }
+    },
     {
       "name": "StablePriceModel",
       "docs": [
@@ -10966,6 +14590,67 @@ export type MangoV4 = {
           },
           {
             "name": "TokenConditionalSwapTrigger"
+          },
+          {
+            "name": "OpenbookV2SettleFunds"
+          }
+        ]
+      }
+    },
+    {
+      "name": "InterestRateModel",
+      "docs": [
+        "How a bank adjusts its interest rate curve over time"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "CurveScaling"
+          },
+          {
+            "name": "Controller"
+          }
+        ]
+      }
+    },
+    {
+      "name": "DelegatePermission",
+      "docs": [
+        "Actions a scoped delegate may be allowed to take, stored as bits in DelegateScope::permissions."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "PerpTrade"
+          },
+          {
+            "name": "PerpCancel"
+          },
+          {
+            "name": "SpotTrade"
+          },
+          {
+            "name": "SpotCancel"
+          },
+          {
+            "name": "TokenConditionalSwap"
+          },
+          {
+            "name": "Swap"
+          },
+          {
+            "name": "FixedTerm"
+          },
+          {
+            "name": "TokenTransfer"
+          },
+          {
+            "name": "Liquidate"
+          },
+          {
+            "name": "Withdraw"
           }
         ]
       }
@@ -11217,6 +14902,78 @@ export type MangoV4 = {
           },
           {
             "name": "GroupChangeInsuranceFund"
+          },
+          {
+            "name": "FixedTermMarketCreate"
+          },
+          {
+            "name": "FixedTermLend"
+          },
+          {
+            "name": "FixedTermBorrow"
+          },
+          {
+            "name": "FixedTermSettle"
+          },
+          {
+            "name": "PerpScheduledOrderCreate"
+          },
+          {
+            "name": "PerpScheduledOrderCancel"
+          },
+          {
+            "name": "PerpScheduledOrderTrigger"
+          },
+          {
+            "name": "PerpConditionalOrderCreate"
+          },
+          {
+            "name": "PerpPlaceSpreadOrder"
+          },
+          {
+            "name": "AccountSetPortfolioMargin"
+          },
+          {
+            "name": "PerpSetIsolated"
+          },
+          {
+            "name": "PerpIsolatedTransfer"
+          },
+          {
+            "name": "TokenTransfer"
+          },
+          {
+            "name": "AccountEditDelegateScope"
+          },
+          {
+            "name": "OracleAggregatorCreate"
+          },
+          {
+            "name": "OracleAggregatorEdit"
+          },
+          {
+            "name": "OracleAggregatorUpdate"
+          },
+          {
+            "name": "ClmmTwapOracleCreate"
+          },
+          {
+            "name": "ClmmTwapOracleUpdate"
+          },
+          {
+            "name": "OpenbookV2MidOracleCreate"
+          },
+          {
+            "name": "OpenbookV2MidOracleUpdate"
+          },
+          {
+            "name": "AccountSetEmodeCategory"
+          },
+          {
+            "name": "PerpResizeOrderbook"
+          },
+          {
+            "name": "PerpClearOpeningAuction"
           }
         ]
       }
@@ -11238,6 +14995,23 @@ export type MangoV4 = {
         ]
       }
     },
+    {
+      "name": "OracleFreshness",
+      "docs": [
+        "Which staleness limits of an OracleConfig apply to an oracle read"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Health"
+          },
+          {
+            "name": "RiskIncreasing"
+          }
+        ]
+      }
+    },
     {
       "name": "OracleType",
       "type": {
@@ -11266,6 +15040,18 @@ export type MangoV4 = {
           },
           {
             "name": "PythV2"
+          },
+          {
+            "name": "Aggregated"
+          },
+          {
+            "name": "ClmmTwap"
+          },
+          {
+            "name": "MeteoraDLMM"
+          },
+          {
+            "name": "OpenbookV2Mid"
           }
         ]
       }
@@ -11512,6 +15298,26 @@ export type MangoV4 = {
         ]
       }
     },
+    {
+      "name": "PerpScheduledOrderType",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Twap"
+          },
+          {
+            "name": "Iceberg"
+          },
+          {
+            "name": "StopLoss"
+          },
+          {
+            "name": "TakeProfit"
+          }
+        ]
+      }
+    },
     {
       "name": "TokenConditionalSwapDisplayPriceStyle",
       "type": {
@@ -12106,6 +15912,111 @@ export type MangoV4 = {
         }
       ]
     },
+    {
+      "name": "FillLogV4",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "takerSide",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "makerSlot",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "makerOut",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "seqNum",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "maker",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "makerClientOrderId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "makerFee",
+          "type": "f32",
+          "index": false
+        },
+        {
+          "name": "makerFeeTier",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "makerTimestamp",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "taker",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "takerClientOrderId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "takerFee",
+          "type": "f32",
+          "index": false
+        },
+        {
+          "name": "takerFeeTier",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "quantity",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "makerClosedPnl",
+          "type": "f64",
+          "index": false
+        },
+        {
+          "name": "takerClosedPnl",
+          "type": "f64",
+          "index": false
+        }
+      ]
+    },
     {
       "name": "PerpUpdateFundingLog",
       "fields": [
@@ -12389,7 +16300,7 @@ export type MangoV4 = {
       ]
     },
     {
-      "name": "TokenLiqWithTokenLog",
+      "name": "UpdateRateLogV3",
       "fields": [
         {
           "name": "mangoGroup",
@@ -12397,54 +16308,124 @@ export type MangoV4 = {
           "index": false
         },
         {
-          "name": "liqee",
-          "type": "publicKey",
+          "name": "tokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "liqor",
-          "type": "publicKey",
+          "name": "rate0",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "assetTokenIndex",
-          "type": "u16",
+          "name": "util0",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "liabTokenIndex",
-          "type": "u16",
+          "name": "rate1",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "assetTransfer",
+          "name": "util1",
           "type": "i128",
           "index": false
         },
         {
-          "name": "liabTransfer",
+          "name": "maxRate",
           "type": "i128",
           "index": false
         },
         {
-          "name": "assetPrice",
-          "type": "i128",
+          "name": "curveScaling",
+          "type": "f64",
           "index": false
         },
         {
-          "name": "liabPrice",
-          "type": "i128",
+          "name": "targetUtilization",
+          "type": "f32",
           "index": false
         },
         {
-          "name": "bankruptcy",
-          "type": "bool",
+          "name": "interestRateModel",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "controllerError",
+          "type": "f32",
+          "index": false
+        },
+        {
+          "name": "controllerIntegral",
+          "type": "f64",
+          "index": false
+        },
+        {
+          "name": "controllerDailyChange",
+          "type": "f64",
           "index": false
         }
       ]
     },
     {
-      "name": "TokenLiqWithTokenLogV2",
+      "name": "TokenLiqWithTokenLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "liqee",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "liqor",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "assetTokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "liabTokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "assetTransfer",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "liabTransfer",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "assetPrice",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "liabPrice",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "bankruptcy",
+          "type": "bool",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "TokenLiqWithTokenLogV2",
       "fields": [
         {
           "name": "mangoGroup",
@@ -13415,7 +17396,7 @@ export type MangoV4 = {
       ]
     },
     {
-      "name": "PerpForceClosePositionLog",
+      "name": "PerpBatchOrderLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -13423,39 +17404,39 @@ export type MangoV4 = {
           "index": false
         },
         {
-          "name": "perpMarketIndex",
-          "type": "u16",
+          "name": "mangoAccount",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "accountA",
-          "type": "publicKey",
+          "name": "perpMarketIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "accountB",
-          "type": "publicKey",
+          "name": "batchIndex",
+          "type": "u8",
           "index": false
         },
         {
-          "name": "baseTransfer",
-          "type": "i64",
+          "name": "clientOrderId",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "quoteTransfer",
-          "type": "i128",
+          "name": "posted",
+          "type": "bool",
           "index": false
         },
         {
-          "name": "price",
-          "type": "i128",
+          "name": "orderId",
+          "type": "u128",
           "index": false
         }
       ]
     },
     {
-      "name": "TokenForceCloseBorrowsWithTokenLog",
+      "name": "PerpOpeningAuctionLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -13463,54 +17444,69 @@ export type MangoV4 = {
           "index": false
         },
         {
-          "name": "liqor",
-          "type": "publicKey",
+          "name": "perpMarketIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "liqee",
-          "type": "publicKey",
+          "name": "priceLots",
+          "type": "i64",
           "index": false
         },
         {
-          "name": "assetTokenIndex",
-          "type": "u16",
+          "name": "fills",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "liabTokenIndex",
+          "name": "done",
+          "type": "bool",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PerpForceClosePositionLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "perpMarketIndex",
           "type": "u16",
           "index": false
         },
         {
-          "name": "assetTransfer",
-          "type": "i128",
+          "name": "accountA",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "liabTransfer",
-          "type": "i128",
+          "name": "accountB",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "assetPrice",
-          "type": "i128",
+          "name": "baseTransfer",
+          "type": "i64",
           "index": false
         },
         {
-          "name": "liabPrice",
+          "name": "quoteTransfer",
           "type": "i128",
           "index": false
         },
         {
-          "name": "feeFactor",
+          "name": "price",
           "type": "i128",
           "index": false
         }
       ]
     },
     {
-      "name": "TokenForceCloseBorrowsWithTokenLogV2",
+      "name": "TokenForceCloseBorrowsWithTokenLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -13538,17 +17534,7 @@ export type MangoV4 = {
           "index": false
         },
         {
-          "name": "assetTransferFromLiqee",
-          "type": "i128",
-          "index": false
-        },
-        {
-          "name": "assetTransferToLiqor",
-          "type": "i128",
-          "index": false
-        },
-        {
-          "name": "assetLiquidationFee",
+          "name": "assetTransfer",
           "type": "i128",
           "index": false
         },
@@ -13575,7 +17561,7 @@ export type MangoV4 = {
       ]
     },
     {
-      "name": "TokenConditionalSwapCreateLog",
+      "name": "TokenForceCloseBorrowsWithTokenLogV2",
       "fields": [
         {
           "name": "mangoGroup",
@@ -13583,169 +17569,234 @@ export type MangoV4 = {
           "index": false
         },
         {
-          "name": "mangoAccount",
+          "name": "liqor",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "id",
-          "type": "u64",
-          "index": false
-        },
-        {
-          "name": "maxBuy",
-          "type": "u64",
-          "index": false
-        },
-        {
-          "name": "maxSell",
-          "type": "u64",
-          "index": false
-        },
-        {
-          "name": "expiryTimestamp",
-          "type": "u64",
-          "index": false
-        },
-        {
-          "name": "priceLowerLimit",
-          "type": "f64",
-          "index": false
-        },
-        {
-          "name": "priceUpperLimit",
-          "type": "f64",
-          "index": false
-        },
-        {
-          "name": "pricePremiumRate",
-          "type": "f64",
-          "index": false
-        },
-        {
-          "name": "takerFeeRate",
-          "type": "f32",
-          "index": false
-        },
-        {
-          "name": "makerFeeRate",
-          "type": "f32",
+          "name": "liqee",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "buyTokenIndex",
+          "name": "assetTokenIndex",
           "type": "u16",
           "index": false
         },
         {
-          "name": "sellTokenIndex",
+          "name": "liabTokenIndex",
           "type": "u16",
           "index": false
         },
         {
-          "name": "allowCreatingDeposits",
-          "type": "bool",
-          "index": false
-        },
-        {
-          "name": "allowCreatingBorrows",
-          "type": "bool",
-          "index": false
-        }
-      ]
-    },
-    {
-      "name": "TokenConditionalSwapCreateLogV2",
-      "fields": [
-        {
-          "name": "mangoGroup",
-          "type": "publicKey",
-          "index": false
-        },
-        {
-          "name": "mangoAccount",
-          "type": "publicKey",
-          "index": false
-        },
-        {
-          "name": "id",
-          "type": "u64",
-          "index": false
-        },
-        {
-          "name": "maxBuy",
-          "type": "u64",
-          "index": false
-        },
-        {
-          "name": "maxSell",
-          "type": "u64",
-          "index": false
-        },
-        {
-          "name": "expiryTimestamp",
-          "type": "u64",
-          "index": false
-        },
-        {
-          "name": "priceLowerLimit",
-          "type": "f64",
-          "index": false
-        },
-        {
-          "name": "priceUpperLimit",
-          "type": "f64",
-          "index": false
-        },
-        {
-          "name": "pricePremiumRate",
-          "type": "f64",
-          "index": false
-        },
-        {
-          "name": "takerFeeRate",
-          "type": "f32",
-          "index": false
-        },
-        {
-          "name": "makerFeeRate",
-          "type": "f32",
+          "name": "assetTransferFromLiqee",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "buyTokenIndex",
-          "type": "u16",
+          "name": "assetTransferToLiqor",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "sellTokenIndex",
-          "type": "u16",
+          "name": "assetLiquidationFee",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "allowCreatingDeposits",
-          "type": "bool",
+          "name": "liabTransfer",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "allowCreatingBorrows",
-          "type": "bool",
+          "name": "assetPrice",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "displayPriceStyle",
-          "type": "u8",
+          "name": "liabPrice",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "intention",
-          "type": "u8",
+          "name": "feeFactor",
+          "type": "i128",
           "index": false
         }
       ]
     },
     {
-      "name": "TokenConditionalSwapCreateLogV3",
+      "name": "TokenConditionalSwapCreateLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mangoAccount",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "id",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "maxBuy",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "maxSell",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "expiryTimestamp",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "priceLowerLimit",
+          "type": "f64",
+          "index": false
+        },
+        {
+          "name": "priceUpperLimit",
+          "type": "f64",
+          "index": false
+        },
+        {
+          "name": "pricePremiumRate",
+          "type": "f64",
+          "index": false
+        },
+        {
+          "name": "takerFeeRate",
+          "type": "f32",
+          "index": false
+        },
+        {
+          "name": "makerFeeRate",
+          "type": "f32",
+          "index": false
+        },
+        {
+          "name": "buyTokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "sellTokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "allowCreatingDeposits",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "allowCreatingBorrows",
+          "type": "bool",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "TokenConditionalSwapCreateLogV2",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mangoAccount",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "id",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "maxBuy",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "maxSell",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "expiryTimestamp",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "priceLowerLimit",
+          "type": "f64",
+          "index": false
+        },
+        {
+          "name": "priceUpperLimit",
+          "type": "f64",
+          "index": false
+        },
+        {
+          "name": "pricePremiumRate",
+          "type": "f64",
+          "index": false
+        },
+        {
+          "name": "takerFeeRate",
+          "type": "f32",
+          "index": false
+        },
+        {
+          "name": "makerFeeRate",
+          "type": "f32",
+          "index": false
+        },
+        {
+          "name": "buyTokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "sellTokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "allowCreatingDeposits",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "allowCreatingBorrows",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "displayPriceStyle",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "intention",
+          "type": "u8",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "TokenConditionalSwapCreateLogV3",
       "fields": [
         {
           "name": "mangoGroup",
@@ -14213,56 +18264,676 @@ export type MangoV4 = {
           "index": false
         }
       ]
-    }
-  ],
-  "errors": [
-    {
-      "code": 6000,
-      "name": "SomeError",
-      "msg": ""
     },
     {
-      "code": 6001,
-      "name": "NotImplementedError",
-      "msg": ""
+      "name": "OpenbookV2RegisterMarketLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "openbookMarket",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "baseTokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "quoteTokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "openbookProgram",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "openbookMarketExternal",
+          "type": "publicKey",
+          "index": false
+        }
+      ]
     },
     {
-      "code": 6002,
-      "name": "MathError",
-      "msg": "checked math error"
+      "name": "OpenbookV2OpenOrdersBalanceLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mangoAccount",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "baseTokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "quoteTokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "baseTotal",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "baseFree",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "quoteTotal",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "quoteFree",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "referrerRebatesAvailable",
+          "type": "u64",
+          "index": false
+        }
+      ]
     },
     {
-      "code": 6003,
-      "name": "UnexpectedOracle",
-      "msg": ""
+      "name": "FixedTermMarketCreateLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "fixedTermMarket",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "tokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "maturityTimestamp",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "lendRate",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "borrowRate",
+          "type": "i128",
+          "index": false
+        }
+      ]
     },
     {
-      "code": 6004,
-      "name": "UnknownOracleType",
-      "msg": "oracle type cannot be determined"
+      "name": "FixedTermLoanLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mangoAccount",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "tokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "isBorrow",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "amountAtMaturity",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "maturityTimestamp",
+          "type": "u64",
+          "index": false
+        }
+      ]
     },
     {
-      "code": 6005,
-      "name": "InvalidFlashLoanTargetCpiProgram",
-      "msg": ""
+      "name": "FixedTermSettleLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mangoAccount",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "tokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "lentAtMaturity",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "borrowedAtMaturity",
+          "type": "u64",
+          "index": false
+        }
+      ]
     },
     {
-      "code": 6006,
-      "name": "HealthMustBePositive",
-      "msg": "health must be positive"
+      "name": "PerpScheduledOrderCreateLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mangoAccount",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "id",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "marketIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "side",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "orderType",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "reduceOnly",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "maxBaseLots",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "childBaseLots",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "priceLots",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "intervalSeconds",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "expiryTimestamp",
+          "type": "u64",
+          "index": false
+        }
+      ]
     },
     {
-      "code": 6007,
-      "name": "HealthMustBePositiveOrIncrease",
-      "msg": "health must be positive or not decrease"
+      "name": "PerpScheduledOrderCancelLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mangoAccount",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "id",
+          "type": "u64",
+          "index": false
+        }
+      ]
     },
     {
-      "code": 6008,
-      "name": "HealthMustBeNegative",
-      "msg": "health must be negative"
+      "name": "PerpScheduledOrderTriggerLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mangoAccount",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "caller",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "id",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "marketIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "childBaseLots",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "executedBaseLots",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "incentiveTokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "incentiveAmount",
+          "type": "u64",
+          "index": false
+        }
+      ]
     },
     {
-      "code": 6009,
+      "name": "PerpConditionalOrderCreateLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mangoAccount",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "id",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "marketIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "orderType",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "side",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "maxBaseLots",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "triggerPriceLots",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "maxSlippage",
+          "type": "f32",
+          "index": false
+        },
+        {
+          "name": "expiryTimestamp",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PerpConditionalOrderTriggerLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mangoAccount",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "caller",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "id",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "marketIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "orderType",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "side",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "oraclePriceLots",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "triggerPriceLots",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "limitPriceLots",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "filledBaseLots",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "incentiveTokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "incentiveAmount",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PerpSpreadOrderLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mangoAccount",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketIndexA",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "marketIndexB",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "sideA",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "sideB",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "filledBaseLotsA",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "filledBaseLotsB",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PerpIsolatedMarginLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mangoAccount",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "isolated",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "isolatedCollateral",
+          "type": "i128",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "TokenTransferLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "sourceAccount",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "destinationAccount",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "signer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "tokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "quantity",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "i128",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "TokenCircuitBreakerLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "tokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "referencePrice",
+          "type": "f64",
+          "index": false
+        },
+        {
+          "name": "trippedUntil",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "SomeError",
+      "msg": ""
+    },
+    {
+      "code": 6001,
+      "name": "NotImplementedError",
+      "msg": ""
+    },
+    {
+      "code": 6002,
+      "name": "MathError",
+      "msg": "checked math error"
+    },
+    {
+      "code": 6003,
+      "name": "UnexpectedOracle",
+      "msg": ""
+    },
+    {
+      "code": 6004,
+      "name": "UnknownOracleType",
+      "msg": "oracle type cannot be determined"
+    },
+    {
+      "code": 6005,
+      "name": "InvalidFlashLoanTargetCpiProgram",
+      "msg": ""
+    },
+    {
+      "code": 6006,
+      "name": "HealthMustBePositive",
+      "msg": "health must be positive"
+    },
+    {
+      "code": 6007,
+      "name": "HealthMustBePositiveOrIncrease",
+      "msg": "health must be positive or not decrease"
+    },
+    {
+      "code": 6008,
+      "name": "HealthMustBeNegative",
+      "msg": "health must be negative"
+    },
+    {
+      "code": 6009,
       "name": "IsBankrupt",
       "msg": "the account is bankrupt"
     },
@@ -14580,6 +19251,141 @@ export type MangoV4 = {
       "code": 6072,
       "name": "InvalidHealth",
       "msg": "invalid health"
+    },
+    {
+      "code": 6073,
+      "name": "NoFreeOpenbookV2OpenOrdersIndex",
+      "msg": "no free openbook v2 open orders index"
+    },
+    {
+      "code": 6074,
+      "name": "OpenbookV2OpenOrdersExistAlready",
+      "msg": "openbook v2 open orders exist already"
+    },
+    {
+      "code": 6075,
+      "name": "HasOpenOrUnsettledOpenbookV2Orders",
+      "msg": "there are open or unsettled openbook v2 orders"
+    },
+    {
+      "code": 6076,
+      "name": "NoFreeFixedTermPositionIndex",
+      "msg": "no free fixed term position index"
+    },
+    {
+      "code": 6077,
+      "name": "FixedTermMarketMatured",
+      "msg": "fixed term market has matured"
+    },
+    {
+      "code": 6078,
+      "name": "FixedTermMarketNotMatured",
+      "msg": "fixed term market has not matured yet"
+    },
+    {
+      "code": 6079,
+      "name": "FixedTermMarketInsufficientLiquidity",
+      "msg": "fixed term market does not have enough lent tokens available"
+    },
+    {
+      "code": 6080,
+      "name": "FixedTermMarketLendLimitExceeded",
+      "msg": "fixed term market lend limit exceeded"
+    },
+    {
+      "code": 6081,
+      "name": "PerpScheduledOrderNotSet",
+      "msg": "perp scheduled order is not set"
+    },
+    {
+      "code": 6082,
+      "name": "PerpScheduledOrderExpired",
+      "msg": "perp scheduled order is expired"
+    },
+    {
+      "code": 6083,
+      "name": "PerpScheduledOrderNotTriggerable",
+      "msg": "perp scheduled order can't be triggered yet"
+    },
+    {
+      "code": 6084,
+      "name": "PerpScheduledOrderIndexIdMismatch",
+      "msg": "perp scheduled order index and id don't match"
+    },
+    {
+      "code": 6085,
+      "name": "PerpConditionalOrderPriceNotTriggered",
+      "msg": "perp conditional order oracle price condition is not met"
+    },
+    {
+      "code": 6086,
+      "name": "PerpSpreadLegNotFilled",
+      "msg": "the second leg of a perp spread order could not be filled completely"
+    },
+    {
+      "code": 6087,
+      "name": "PerpPositionNotIsolated",
+      "msg": "perp position is not isolated"
+    },
+    {
+      "code": 6088,
+      "name": "IsolatedPerpHealthMustBePositiveOrIncrease",
+      "msg": "isolated perp position health must be positive or not decrease"
+    },
+    {
+      "code": 6089,
+      "name": "DelegatePermissionDenied",
+      "msg": "the delegate is not permitted to take this action"
+    },
+    {
+      "code": 6090,
+      "name": "DelegateOrderNotionalExceeded",
+      "msg": "the order exceeds the delegate's notional limit"
+    },
+    {
+      "code": 6091,
+      "name": "OracleQuorumNotReached",
+      "msg": "not enough fresh and agreeing source oracles"
+    },
+    {
+      "code": 6092,
+      "name": "BankBorrowLimit",
+      "msg": "borrow crosses the token's borrow limit"
+    },
+    {
+      "code": 6093,
+      "name": "IsolatedCollateralBorrowNotAllowed",
+      "msg": "isolated collateral cannot back borrows of this token"
+    },
+    {
+      "code": 6094,
+      "name": "InvalidEmodeCategory",
+      "msg": "invalid e-mode category"
+    },
+    {
+      "code": 6095,
+      "name": "InvalidPerpFeeTier",
+      "msg": "invalid perp fee tier"
+    },
+    {
+      "code": 6096,
+      "name": "PerpOpeningAuctionOrderNotPostable",
+      "msg": "orders that can't rest on the book are not allowed during a perp opening auction"
+    },
+    {
+      "code": 6097,
+      "name": "PerpOpeningAuctionNotActive",
+      "msg": "perp market is not in an opening auction"
+    },
+    {
+      "code": 6098,
+      "name": "PerpOpeningAuctionNotEnded",
+      "msg": "perp opening auction has not ended yet"
+    },
+    {
+      "code": 6099,
+      "name": "IsolatedDebtCeilingReached",
+      "msg": "the token's isolated collateral backs the maximum amount of borrows"
     }
   ]
 };
@@ -14872,6 +19678,118 @@ export const IDL: MangoV4 = {
         }
       ]
     },
+    {
+      "name": "groupEditEmodeCategory",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "admin"
+          ]
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "category",
+          "type": "u8"
+        },
+        {
+          "name": "tokenIndexes",
+          "type": {
+            "vec": "u16"
+          }
+        },
+        {
+          "name": "maintAssetWeight",
+          "type": "f32"
+        },
+        {
+          "name": "initAssetWeight",
+          "type": "f32"
+        },
+        {
+          "name": "maintLiabWeight",
+          "type": "f32"
+        },
+        {
+          "name": "initLiabWeight",
+          "type": "f32"
+        }
+      ]
+    },
+    {
+      "name": "groupEditPerpFeeTier",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "admin"
+          ]
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "tier",
+          "type": "u8"
+        },
+        {
+          "name": "minVolumeQuote",
+          "type": "u64"
+        },
+        {
+          "name": "makerFeeDiscount",
+          "type": "f32"
+        },
+        {
+          "name": "takerFeeDiscount",
+          "type": "f32"
+        }
+      ]
+    },
+    {
+      "name": "groupEditPortfolioMarginPair",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "admin"
+          ]
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        }
+      ],
+      "args": [
+        {
+          "name": "perpMarketIndex",
+          "type": "u16"
+        },
+        {
+          "name": "tokenIndexOpt",
+          "type": {
+            "option": "u16"
+          }
+        }
+      ]
+    },
     {
       "name": "groupWithdrawInsuranceFund",
       "accounts": [
@@ -15749,6 +20667,72 @@ export const IDL: MangoV4 = {
           "type": {
             "option": "string"
           }
+        },
+        {
+          "name": "circuitBreakerMaxPriceMoveOpt",
+          "type": {
+            "option": "f32"
+          }
+        },
+        {
+          "name": "circuitBreakerCooldownSecondsOpt",
+          "type": {
+            "option": "u32"
+          }
+        },
+        {
+          "name": "resetCircuitBreaker",
+          "type": "bool"
+        },
+        {
+          "name": "interestRateModelOpt",
+          "type": {
+            "option": "u8"
+          }
+        },
+        {
+          "name": "interestControllerKpOpt",
+          "type": {
+            "option": "f32"
+          }
+        },
+        {
+          "name": "interestControllerKiOpt",
+          "type": {
+            "option": "f32"
+          }
+        },
+        {
+          "name": "interestControllerMaxDailyChangeOpt",
+          "type": {
+            "option": "f32"
+          }
+        },
+        {
+          "name": "borrowLimitOpt",
+          "type": {
+            "option": "u64"
+          }
+        },
+        {
+          "name": "isolatedCollateralOpt",
+          "type": {
+            "option": "bool"
+          }
+        },
+        {
+          "name": "isolatedBorrowTokenIndexesOpt",
+          "type": {
+            "option": {
+              "vec": "u16"
+            }
+          }
+        },
+        {
+          "name": "isolatedDebtCeilingQuoteOpt",
+          "type": {
+            "option": "u64"
+          }
         }
       ]
     },
@@ -16148,7 +21132,7 @@ export const IDL: MangoV4 = {
       ]
     },
     {
-      "name": "accountExpand",
+      "name": "accountCreateV3",
       "accounts": [
         {
           "name": "group",
@@ -16159,10 +21143,30 @@ export const IDL: MangoV4 = {
           "name": "account",
           "isMut": true,
           "isSigner": false,
-          "relations": [
-            "group",
-            "owner"
-          ]
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "type": "string",
+                "value": "MangoAccount"
+              },
+              {
+                "kind": "account",
+                "type": "publicKey",
+                "path": "group"
+              },
+              {
+                "kind": "account",
+                "type": "publicKey",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "type": "u32",
+                "path": "account_num"
+              }
+            ]
+          }
         },
         {
           "name": "owner",
@@ -16181,6 +21185,10 @@ export const IDL: MangoV4 = {
         }
       ],
       "args": [
+        {
+          "name": "accountNum",
+          "type": "u32"
+        },
         {
           "name": "tokenCount",
           "type": "u8"
@@ -16196,11 +21204,23 @@ export const IDL: MangoV4 = {
         {
           "name": "perpOoCount",
           "type": "u8"
+        },
+        {
+          "name": "tokenConditionalSwapCount",
+          "type": "u8"
+        },
+        {
+          "name": "openbookV2Count",
+          "type": "u8"
+        },
+        {
+          "name": "name",
+          "type": "string"
         }
       ]
     },
     {
-      "name": "accountExpandV2",
+      "name": "accountCreateV4",
       "accounts": [
         {
           "name": "group",
@@ -16211,10 +21231,30 @@ export const IDL: MangoV4 = {
           "name": "account",
           "isMut": true,
           "isSigner": false,
-          "relations": [
-            "group",
-            "owner"
-          ]
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "type": "string",
+                "value": "MangoAccount"
+              },
+              {
+                "kind": "account",
+                "type": "publicKey",
+                "path": "group"
+              },
+              {
+                "kind": "account",
+                "type": "publicKey",
+                "path": "owner"
+              },
+              {
+                "kind": "arg",
+                "type": "u32",
+                "path": "account_num"
+              }
+            ]
+          }
         },
         {
           "name": "owner",
@@ -16233,6 +21273,10 @@ export const IDL: MangoV4 = {
         }
       ],
       "args": [
+        {
+          "name": "accountNum",
+          "type": "u32"
+        },
         {
           "name": "tokenCount",
           "type": "u8"
@@ -16252,11 +21296,27 @@ export const IDL: MangoV4 = {
         {
           "name": "tokenConditionalSwapCount",
           "type": "u8"
+        },
+        {
+          "name": "openbookV2Count",
+          "type": "u8"
+        },
+        {
+          "name": "fixedTermCount",
+          "type": "u8"
+        },
+        {
+          "name": "perpScheduledOrderCount",
+          "type": "u8"
+        },
+        {
+          "name": "name",
+          "type": "string"
         }
       ]
     },
     {
-      "name": "accountSizeMigration",
+      "name": "accountExpand",
       "accounts": [
         {
           "name": "group",
@@ -16268,9 +21328,15 @@ export const IDL: MangoV4 = {
           "isMut": true,
           "isSigner": false,
           "relations": [
-            "group"
+            "group",
+            "owner"
           ]
         },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
         {
           "name": "payer",
           "isMut": true,
@@ -16282,10 +21348,27 @@ export const IDL: MangoV4 = {
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "tokenCount",
+          "type": "u8"
+        },
+        {
+          "name": "serum3Count",
+          "type": "u8"
+        },
+        {
+          "name": "perpCount",
+          "type": "u8"
+        },
+        {
+          "name": "perpOoCount",
+          "type": "u8"
+        }
+      ]
     },
     {
-      "name": "accountEdit",
+      "name": "accountExpandV2",
       "accounts": [
         {
           "name": "group",
@@ -16305,37 +21388,43 @@ export const IDL: MangoV4 = {
           "name": "owner",
           "isMut": false,
           "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "nameOpt",
-          "type": {
-            "option": "string"
-          }
+          "name": "tokenCount",
+          "type": "u8"
         },
         {
-          "name": "delegateOpt",
-          "type": {
-            "option": "publicKey"
-          }
+          "name": "serum3Count",
+          "type": "u8"
         },
         {
-          "name": "temporaryDelegateOpt",
-          "type": {
-            "option": "publicKey"
-          }
+          "name": "perpCount",
+          "type": "u8"
         },
         {
-          "name": "temporaryDelegateExpiryOpt",
-          "type": {
-            "option": "u64"
-          }
+          "name": "perpOoCount",
+          "type": "u8"
+        },
+        {
+          "name": "tokenConditionalSwapCount",
+          "type": "u8"
         }
       ]
     },
     {
-      "name": "accountToggleFreeze",
+      "name": "accountExpandV3",
       "accounts": [
         {
           "name": "group",
@@ -16347,24 +21436,55 @@ export const IDL: MangoV4 = {
           "isMut": true,
           "isSigner": false,
           "relations": [
-            "group"
+            "group",
+            "owner"
           ]
         },
         {
-          "name": "admin",
+          "name": "owner",
           "isMut": false,
           "isSigner": true
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "freeze",
-          "type": "bool"
+          "name": "tokenCount",
+          "type": "u8"
+        },
+        {
+          "name": "serum3Count",
+          "type": "u8"
+        },
+        {
+          "name": "perpCount",
+          "type": "u8"
+        },
+        {
+          "name": "perpOoCount",
+          "type": "u8"
+        },
+        {
+          "name": "tokenConditionalSwapCount",
+          "type": "u8"
+        },
+        {
+          "name": "openbookV2Count",
+          "type": "u8"
         }
       ]
     },
     {
-      "name": "accountClose",
+      "name": "accountExpandV4",
       "accounts": [
         {
           "name": "group",
@@ -16386,67 +21506,61 @@ export const IDL: MangoV4 = {
           "isSigner": true
         },
         {
-          "name": "solDestination",
+          "name": "payer",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "tokenProgram",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "forceClose",
-          "type": "bool"
-        }
-      ]
-    },
-    {
-      "name": "accountBuybackFeesWithMngo",
-      "accounts": [
+          "name": "tokenCount",
+          "type": "u8"
+        },
         {
-          "name": "group",
-          "isMut": false,
-          "isSigner": false
+          "name": "serum3Count",
+          "type": "u8"
         },
         {
-          "name": "account",
-          "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group"
-          ]
+          "name": "perpCount",
+          "type": "u8"
         },
         {
-          "name": "owner",
-          "isMut": false,
-          "isSigner": true
+          "name": "perpOoCount",
+          "type": "u8"
         },
         {
-          "name": "daoAccount",
-          "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group"
-          ]
+          "name": "tokenConditionalSwapCount",
+          "type": "u8"
         },
         {
-          "name": "mngoBank",
-          "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group"
-          ]
+          "name": "openbookV2Count",
+          "type": "u8"
         },
         {
-          "name": "mngoOracle",
+          "name": "fixedTermCount",
+          "type": "u8"
+        },
+        {
+          "name": "perpScheduledOrderCount",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "accountSizeMigration",
+      "accounts": [
+        {
+          "name": "group",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "feesBank",
+          "name": "account",
           "isMut": true,
           "isSigner": false,
           "relations": [
@@ -16454,20 +21568,20 @@ export const IDL: MangoV4 = {
           ]
         },
         {
-          "name": "feesOracle",
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "maxBuybackUsd",
-          "type": "u64"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "sequenceCheck",
+      "name": "accountEdit",
       "accounts": [
         {
           "name": "group",
@@ -16491,13 +21605,33 @@ export const IDL: MangoV4 = {
       ],
       "args": [
         {
-          "name": "expectedSequenceNumber",
-          "type": "u8"
+          "name": "nameOpt",
+          "type": {
+            "option": "string"
+          }
+        },
+        {
+          "name": "delegateOpt",
+          "type": {
+            "option": "publicKey"
+          }
+        },
+        {
+          "name": "temporaryDelegateOpt",
+          "type": {
+            "option": "publicKey"
+          }
+        },
+        {
+          "name": "temporaryDelegateExpiryOpt",
+          "type": {
+            "option": "u64"
+          }
         }
       ]
     },
     {
-      "name": "healthCheck",
+      "name": "accountToggleFreeze",
       "accounts": [
         {
           "name": "group",
@@ -16511,184 +21645,158 @@ export const IDL: MangoV4 = {
           "relations": [
             "group"
           ]
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
         }
       ],
       "args": [
         {
-          "name": "minHealthValue",
-          "type": "f64"
-        },
-        {
-          "name": "checkKind",
-          "type": {
-            "defined": "HealthCheckKind"
-          }
+          "name": "freeze",
+          "type": "bool"
         }
       ]
     },
     {
-      "name": "stubOracleCreate",
+      "name": "accountSetPortfolioMargin",
       "accounts": [
         {
           "name": "group",
           "isMut": false,
-          "isSigner": false,
-          "relations": [
-            "admin"
-          ]
-        },
-        {
-          "name": "oracle",
-          "isMut": true,
-          "isSigner": true
-        },
-        {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "mint",
-          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "payer",
+          "name": "account",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false,
+          "relations": [
+            "group",
+            "owner"
+          ]
         },
         {
-          "name": "systemProgram",
+          "name": "owner",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         }
       ],
       "args": [
         {
-          "name": "price",
-          "type": {
-            "defined": "I80F48"
-          }
+          "name": "enabled",
+          "type": "bool"
         }
       ]
     },
     {
-      "name": "stubOracleClose",
+      "name": "accountSetEmodeCategory",
       "accounts": [
         {
           "name": "group",
           "isMut": false,
-          "isSigner": false,
-          "relations": [
-            "admin"
-          ]
-        },
-        {
-          "name": "admin",
-          "isMut": false,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "oracle",
+          "name": "account",
           "isMut": true,
           "isSigner": false,
           "relations": [
-            "group"
+            "group",
+            "owner"
           ]
         },
         {
-          "name": "solDestination",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
+          "name": "owner",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "category",
+          "type": "u8"
+        }
+      ]
     },
     {
-      "name": "stubOracleSet",
+      "name": "accountEditDelegateScope",
       "accounts": [
         {
           "name": "group",
           "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
           "isSigner": false,
           "relations": [
-            "admin"
+            "group",
+            "owner"
           ]
         },
         {
-          "name": "admin",
+          "name": "owner",
           "isMut": false,
           "isSigner": true
-        },
-        {
-          "name": "oracle",
-          "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group"
-          ]
         }
       ],
       "args": [
         {
-          "name": "price",
+          "name": "temporaryDelegate",
+          "type": "bool"
+        },
+        {
+          "name": "scope",
           "type": {
-            "defined": "I80F48"
+            "defined": "DelegateScope"
           }
         }
       ]
     },
     {
-      "name": "stubOracleSetTest",
+      "name": "accountClose",
       "accounts": [
         {
           "name": "group",
           "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
           "isSigner": false,
           "relations": [
-            "admin"
+            "group",
+            "owner"
           ]
         },
         {
-          "name": "admin",
+          "name": "owner",
           "isMut": false,
           "isSigner": true
         },
         {
-          "name": "oracle",
+          "name": "solDestination",
           "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group"
-          ]
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "price",
-          "type": {
-            "defined": "I80F48"
-          }
-        },
-        {
-          "name": "lastUpdateSlot",
-          "type": "u64"
-        },
-        {
-          "name": "deviation",
-          "type": {
-            "defined": "I80F48"
-          }
+          "name": "forceClose",
+          "type": "bool"
         }
       ]
     },
     {
-      "name": "tokenDeposit",
+      "name": "accountBuybackFeesWithMngo",
       "accounts": [
         {
           "name": "group",
@@ -16709,54 +21817,49 @@ export const IDL: MangoV4 = {
           "isSigner": true
         },
         {
-          "name": "bank",
+          "name": "daoAccount",
           "isMut": true,
           "isSigner": false,
           "relations": [
-            "group",
-            "vault",
-            "oracle"
+            "group"
           ]
         },
         {
-          "name": "vault",
+          "name": "mngoBank",
           "isMut": true,
-          "isSigner": false
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
         },
         {
-          "name": "oracle",
+          "name": "mngoOracle",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenAccount",
+          "name": "feesBank",
           "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "tokenAuthority",
-          "isMut": false,
-          "isSigner": true
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
         },
         {
-          "name": "tokenProgram",
+          "name": "feesOracle",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "amount",
+          "name": "maxBuybackUsd",
           "type": "u64"
-        },
-        {
-          "name": "reduceOnly",
-          "type": "bool"
         }
       ]
     },
     {
-      "name": "tokenDepositIntoExisting",
+      "name": "sequenceCheck",
       "accounts": [
         {
           "name": "group",
@@ -16767,59 +21870,26 @@ export const IDL: MangoV4 = {
           "name": "account",
           "isMut": true,
           "isSigner": false,
-          "relations": [
-            "group"
-          ]
-        },
-        {
-          "name": "bank",
-          "isMut": true,
-          "isSigner": false,
           "relations": [
             "group",
-            "vault",
-            "oracle"
+            "owner"
           ]
         },
         {
-          "name": "vault",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "oracle",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenAccount",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "tokenAuthority",
+          "name": "owner",
           "isMut": false,
           "isSigner": true
-        },
-        {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "amount",
-          "type": "u64"
-        },
-        {
-          "name": "reduceOnly",
-          "type": "bool"
+          "name": "expectedSequenceNumber",
+          "type": "u8"
         }
       ]
     },
     {
-      "name": "tokenWithdraw",
+      "name": "healthCheck",
       "accounts": [
         {
           "name": "group",
@@ -16833,374 +21903,409 @@ export const IDL: MangoV4 = {
           "relations": [
             "group"
           ]
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "owner",
-          "isMut": false,
-          "isSigner": true
+          "name": "minHealthValue",
+          "type": "f64"
         },
         {
-          "name": "bank",
-          "isMut": true,
+          "name": "checkKind",
+          "type": {
+            "defined": "HealthCheckKind"
+          }
+        }
+      ]
+    },
+    {
+      "name": "oracleAggregatorCreate",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
           "isSigner": false,
           "relations": [
-            "group",
-            "vault",
-            "oracle"
+            "admin"
           ]
         },
         {
-          "name": "vault",
+          "name": "oracle",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "oracle",
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "mint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenAccount",
+          "name": "payer",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "tokenProgram",
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "amount",
-          "type": "u64"
+          "name": "minQuorum",
+          "type": "u8"
         },
         {
-          "name": "allowBorrow",
-          "type": "bool"
+          "name": "maxDivergence",
+          "type": "f64"
+        },
+        {
+          "name": "sourceOracleConfig",
+          "type": {
+            "defined": "OracleConfigParams"
+          }
         }
       ]
     },
     {
-      "name": "flashLoanBegin",
+      "name": "oracleAggregatorEdit",
       "accounts": [
         {
-          "name": "account",
+          "name": "group",
           "isMut": false,
-          "isSigner": false
+          "isSigner": false,
+          "relations": [
+            "admin"
+          ]
         },
         {
-          "name": "owner",
+          "name": "admin",
           "isMut": false,
           "isSigner": true
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "instructions",
-          "isMut": false,
+          "name": "oracle",
+          "isMut": true,
           "isSigner": false,
-          "docs": [
-            "Instructions Sysvar for instruction introspection"
+          "relations": [
+            "group"
           ]
         }
       ],
       "args": [
         {
-          "name": "loanAmounts",
+          "name": "minQuorumOpt",
           "type": {
-            "vec": "u64"
+            "option": "u8"
+          }
+        },
+        {
+          "name": "maxDivergenceOpt",
+          "type": {
+            "option": "f64"
+          }
+        },
+        {
+          "name": "sourceOracleConfigOpt",
+          "type": {
+            "option": {
+              "defined": "OracleConfigParams"
+            }
           }
+        },
+        {
+          "name": "replaceSourceOracles",
+          "type": "bool"
         }
       ]
     },
     {
-      "name": "flashLoanSwapBegin",
-      "docs": [
-        "A version of flash_loan_begin that's specialized for swaps and needs fewer",
-        "bytes in the transaction"
-      ],
+      "name": "oracleAggregatorUpdate",
       "accounts": [
         {
-          "name": "account",
+          "name": "group",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "owner",
+          "name": "oracle",
           "isMut": true,
-          "isSigner": true
-        },
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "clmmTwapOracleCreate",
+      "accounts": [
         {
-          "name": "inputMint",
+          "name": "group",
           "isMut": false,
-          "isSigner": false
+          "isSigner": false,
+          "relations": [
+            "admin"
+          ]
         },
         {
-          "name": "outputMint",
-          "isMut": false,
-          "isSigner": false
+          "name": "oracle",
+          "isMut": true,
+          "isSigner": true
         },
         {
-          "name": "systemProgram",
+          "name": "admin",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "tokenProgram",
+          "name": "pool",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "associatedTokenProgram",
+          "name": "observation",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "instructions",
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
           "isMut": false,
-          "isSigner": false,
-          "docs": [
-            "Instructions Sysvar for instruction introspection"
-          ]
+          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "loanAmount",
+          "name": "twapWindowSeconds",
           "type": "u64"
         }
       ]
     },
     {
-      "name": "flashLoanEnd",
+      "name": "clmmTwapOracleUpdate",
       "accounts": [
         {
-          "name": "account",
-          "isMut": true,
+          "name": "group",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "owner",
+          "name": "oracle",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "pool",
+            "observation"
+          ]
+        },
+        {
+          "name": "pool",
           "isMut": false,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "observation",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "flashLoanType",
-          "type": {
-            "defined": "FlashLoanType"
-          }
-        }
-      ]
+      "args": []
     },
     {
-      "name": "flashLoanEndV2",
+      "name": "openbookV2MidOracleCreate",
       "accounts": [
         {
-          "name": "account",
+          "name": "group",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "admin"
+          ]
+        },
+        {
+          "name": "oracle",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "owner",
+          "name": "admin",
           "isMut": false,
           "isSigner": true
         },
         {
-          "name": "tokenProgram",
+          "name": "market",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "bids",
+            "asks"
+          ]
+        },
+        {
+          "name": "bids",
           "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "numLoans",
-          "type": "u8"
+          "name": "asks",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "flashLoanType",
-          "type": {
-            "defined": "FlashLoanType"
-          }
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "healthRegionBegin",
+      "name": "openbookV2MidOracleUpdate",
       "accounts": [
-        {
-          "name": "instructions",
-          "isMut": false,
-          "isSigner": false,
-          "docs": [
-            "Instructions Sysvar for instruction introspection"
-          ]
-        },
         {
           "name": "group",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "account",
+          "name": "oracle",
           "isMut": true,
           "isSigner": false,
           "relations": [
-            "group"
+            "group",
+            "market",
+            "bids",
+            "asks"
           ]
+        },
+        {
+          "name": "market",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "bids",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "asks",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": []
     },
     {
-      "name": "healthRegionEnd",
-      "accounts": [
-        {
-          "name": "account",
-          "isMut": true,
-          "isSigner": false
-        }
-      ],
-      "args": []
-    },
-    {
-      "name": "serum3RegisterMarket",
-      "docs": [
-        "",
-        "Serum",
-        ""
-      ],
+      "name": "stubOracleCreate",
       "accounts": [
         {
           "name": "group",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "admin",
           "isMut": false,
-          "isSigner": true,
-          "docs": [
-            "group admin or fast listing admin, checked at #1"
+          "isSigner": false,
+          "relations": [
+            "admin"
           ]
         },
         {
-          "name": "serumProgram",
+          "name": "oracle",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "admin",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "serumMarketExternal",
+          "name": "mint",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "serumMarket",
+          "name": "payer",
           "isMut": true,
-          "isSigner": false,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "type": "string",
-                "value": "Serum3Market"
-              },
-              {
-                "kind": "account",
-                "type": "publicKey",
-                "path": "group"
-              },
-              {
-                "kind": "account",
-                "type": "publicKey",
-                "path": "serum_market_external"
-              }
-            ]
-          }
+          "isSigner": true
         },
         {
-          "name": "indexReservation",
-          "isMut": true,
-          "isSigner": false,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "type": "string",
-                "value": "Serum3Index"
-              },
-              {
-                "kind": "account",
-                "type": "publicKey",
-                "path": "group"
-              },
-              {
-                "kind": "arg",
-                "type": "u16",
-                "path": "market_index"
-              }
-            ]
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "price",
+          "type": {
+            "defined": "I80F48"
           }
-        },
+        }
+      ]
+    },
+    {
+      "name": "stubOracleClose",
+      "accounts": [
         {
-          "name": "quoteBank",
+          "name": "group",
           "isMut": false,
           "isSigner": false,
           "relations": [
-            "group"
+            "admin"
           ]
         },
         {
-          "name": "baseBank",
+          "name": "admin",
           "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "oracle",
+          "isMut": true,
           "isSigner": false,
           "relations": [
             "group"
           ]
         },
         {
-          "name": "payer",
+          "name": "solDestination",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
-          "name": "systemProgram",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "marketIndex",
-          "type": "u16"
-        },
-        {
-          "name": "name",
-          "type": "string"
-        },
-        {
-          "name": "oraclePriceBand",
-          "type": "f32"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "serum3EditMarket",
+      "name": "stubOracleSet",
       "accounts": [
         {
           "name": "group",
           "isMut": false,
-          "isSigner": false
+          "isSigner": false,
+          "relations": [
+            "admin"
+          ]
         },
         {
           "name": "admin",
@@ -17208,7 +22313,7 @@ export const IDL: MangoV4 = {
           "isSigner": true
         },
         {
-          "name": "market",
+          "name": "oracle",
           "isMut": true,
           "isSigner": false,
           "relations": [
@@ -17218,37 +22323,19 @@ export const IDL: MangoV4 = {
       ],
       "args": [
         {
-          "name": "reduceOnlyOpt",
-          "type": {
-            "option": "bool"
-          }
-        },
-        {
-          "name": "forceCloseOpt",
-          "type": {
-            "option": "bool"
-          }
-        },
-        {
-          "name": "nameOpt",
-          "type": {
-            "option": "string"
-          }
-        },
-        {
-          "name": "oraclePriceBandOpt",
+          "name": "price",
           "type": {
-            "option": "f32"
+            "defined": "I80F48"
           }
         }
       ]
     },
     {
-      "name": "serum3DeregisterMarket",
+      "name": "stubOracleSetTest",
       "accounts": [
         {
           "name": "group",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false,
           "relations": [
             "admin"
@@ -17260,36 +22347,35 @@ export const IDL: MangoV4 = {
           "isSigner": true
         },
         {
-          "name": "serumMarket",
+          "name": "oracle",
           "isMut": true,
           "isSigner": false,
           "relations": [
             "group"
           ]
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "indexReservation",
-          "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group"
-          ]
+          "name": "price",
+          "type": {
+            "defined": "I80F48"
+          }
         },
         {
-          "name": "solDestination",
-          "isMut": true,
-          "isSigner": false
+          "name": "lastUpdateSlot",
+          "type": "u64"
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
-          "isSigner": false
+          "name": "deviation",
+          "type": {
+            "defined": "I80F48"
+          }
         }
-      ],
-      "args": []
+      ]
     },
     {
-      "name": "serum3CreateOpenOrders",
+      "name": "tokenDeposit",
       "accounts": [
         {
           "name": "group",
@@ -17310,69 +22396,54 @@ export const IDL: MangoV4 = {
           "isSigner": true
         },
         {
-          "name": "serumMarket",
-          "isMut": false,
+          "name": "bank",
+          "isMut": true,
           "isSigner": false,
           "relations": [
             "group",
-            "serum_program",
-            "serum_market_external"
+            "vault",
+            "oracle"
           ]
         },
         {
-          "name": "serumProgram",
-          "isMut": false,
+          "name": "vault",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "serumMarketExternal",
+          "name": "oracle",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "openOrders",
+          "name": "tokenAccount",
           "isMut": true,
-          "isSigner": false,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "type": "string",
-                "value": "Serum3OO"
-              },
-              {
-                "kind": "account",
-                "type": "publicKey",
-                "path": "account"
-              },
-              {
-                "kind": "account",
-                "type": "publicKey",
-                "path": "serum_market"
-              }
-            ]
-          }
+          "isSigner": false
         },
         {
-          "name": "payer",
-          "isMut": true,
+          "name": "tokenAuthority",
+          "isMut": false,
           "isSigner": true
         },
         {
-          "name": "systemProgram",
+          "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
         },
         {
-          "name": "rent",
-          "isMut": false,
-          "isSigner": false
+          "name": "reduceOnly",
+          "type": "bool"
         }
-      ],
-      "args": []
+      ]
     },
     {
-      "name": "serum3CloseOpenOrders",
+      "name": "tokenDepositIntoExisting",
       "accounts": [
         {
           "name": "group",
@@ -17388,45 +22459,54 @@ export const IDL: MangoV4 = {
           ]
         },
         {
-          "name": "owner",
-          "isMut": false,
-          "isSigner": true
-        },
-        {
-          "name": "serumMarket",
-          "isMut": false,
+          "name": "bank",
+          "isMut": true,
           "isSigner": false,
           "relations": [
             "group",
-            "serum_program",
-            "serum_market_external"
+            "vault",
+            "oracle"
           ]
         },
         {
-          "name": "serumProgram",
-          "isMut": false,
+          "name": "vault",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "serumMarketExternal",
+          "name": "oracle",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "openOrders",
+          "name": "tokenAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "solDestination",
-          "isMut": true,
+          "name": "tokenAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "reduceOnly",
+          "type": "bool"
+        }
+      ]
     },
     {
-      "name": "serum3PlaceOrder",
+      "name": "tokenWithdraw",
       "accounts": [
         {
           "name": "group",
@@ -17447,299 +22527,507 @@ export const IDL: MangoV4 = {
           "isSigner": true
         },
         {
-          "name": "openOrders",
+          "name": "bank",
           "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "serumMarket",
-          "isMut": false,
           "isSigner": false,
           "relations": [
             "group",
-            "serum_program",
-            "serum_market_external"
+            "vault",
+            "oracle"
           ]
         },
         {
-          "name": "serumProgram",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "serumMarketExternal",
+          "name": "vault",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "marketBids",
-          "isMut": true,
+          "name": "oracle",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "marketAsks",
+          "name": "tokenAccount",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "marketEventQueue",
-          "isMut": true,
+          "name": "tokenProgram",
+          "isMut": false,
           "isSigner": false
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "marketRequestQueue",
-          "isMut": true,
-          "isSigner": false
+          "name": "amount",
+          "type": "u64"
         },
         {
-          "name": "marketBaseVault",
-          "isMut": true,
-          "isSigner": false
-        },
+          "name": "allowBorrow",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "tokenTransfer",
+      "accounts": [
         {
-          "name": "marketQuoteVault",
-          "isMut": true,
+          "name": "group",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "marketVaultSigner",
-          "isMut": false,
+          "name": "source",
+          "isMut": true,
           "isSigner": false,
-          "docs": [
-            "needed for the automatic settle_funds call"
+          "relations": [
+            "group"
           ]
         },
         {
-          "name": "payerBank",
+          "name": "destination",
           "isMut": true,
           "isSigner": false,
-          "docs": [
-            "The bank that pays for the order, if necessary"
-          ],
           "relations": [
             "group"
           ]
         },
         {
-          "name": "payerVault",
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "bank",
           "isMut": true,
           "isSigner": false,
-          "docs": [
-            "The bank vault that pays for the order, if necessary"
+          "relations": [
+            "group",
+            "oracle"
           ]
         },
         {
-          "name": "payerOracle",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "tokenProgram",
+          "name": "oracle",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "side",
-          "type": {
-            "defined": "Serum3Side"
-          }
-        },
-        {
-          "name": "limitPrice",
-          "type": "u64"
-        },
-        {
-          "name": "maxBaseQty",
-          "type": "u64"
-        },
-        {
-          "name": "maxNativeQuoteQtyIncludingFees",
-          "type": "u64"
-        },
-        {
-          "name": "selfTradeBehavior",
-          "type": {
-            "defined": "Serum3SelfTradeBehavior"
-          }
-        },
-        {
-          "name": "orderType",
-          "type": {
-            "defined": "Serum3OrderType"
-          }
-        },
-        {
-          "name": "clientOrderId",
+          "name": "amount",
           "type": "u64"
-        },
-        {
-          "name": "limit",
-          "type": "u16"
         }
       ]
     },
     {
-      "name": "serum3PlaceOrderV2",
-      "docs": [
-        "requires the receiver_bank in the health account list to be writable"
-      ],
+      "name": "flashLoanBegin",
       "accounts": [
         {
-          "name": "group",
+          "name": "account",
           "isMut": false,
           "isSigner": false
         },
-        {
-          "name": "account",
-          "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group"
-          ]
-        },
         {
           "name": "owner",
           "isMut": false,
           "isSigner": true
         },
         {
-          "name": "openOrders",
-          "isMut": true,
+          "name": "tokenProgram",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "serumMarket",
+          "name": "instructions",
           "isMut": false,
           "isSigner": false,
-          "relations": [
-            "group",
-            "serum_program",
-            "serum_market_external"
+          "docs": [
+            "Instructions Sysvar for instruction introspection"
           ]
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "serumProgram",
-          "isMut": false,
-          "isSigner": false
-        },
+          "name": "loanAmounts",
+          "type": {
+            "vec": "u64"
+          }
+        }
+      ]
+    },
+    {
+      "name": "flashLoanSwapBegin",
+      "docs": [
+        "A version of flash_loan_begin that's specialized for swaps and needs fewer",
+        "bytes in the transaction"
+      ],
+      "accounts": [
         {
-          "name": "serumMarketExternal",
-          "isMut": true,
+          "name": "account",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "marketBids",
+          "name": "owner",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "marketAsks",
-          "isMut": true,
+          "name": "inputMint",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "marketEventQueue",
-          "isMut": true,
+          "name": "outputMint",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "marketRequestQueue",
-          "isMut": true,
+          "name": "systemProgram",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "marketBaseVault",
-          "isMut": true,
+          "name": "tokenProgram",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "marketQuoteVault",
-          "isMut": true,
+          "name": "associatedTokenProgram",
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "marketVaultSigner",
+          "name": "instructions",
           "isMut": false,
           "isSigner": false,
           "docs": [
-            "needed for the automatic settle_funds call"
+            "Instructions Sysvar for instruction introspection"
           ]
-        },
+        }
+      ],
+      "args": [
         {
-          "name": "payerBank",
-          "isMut": true,
-          "isSigner": false,
-          "docs": [
-            "The bank that pays for the order, if necessary"
-          ],
-          "relations": [
-            "group"
+          "name": "loanAmount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "flashLoanEnd",
+      "accounts": [
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "flashLoanType",
+          "type": {
+            "defined": "FlashLoanType"
+          }
+        }
+      ]
+    },
+    {
+      "name": "flashLoanEndV2",
+      "accounts": [
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "numLoans",
+          "type": "u8"
+        },
+        {
+          "name": "flashLoanType",
+          "type": {
+            "defined": "FlashLoanType"
+          }
+        }
+      ]
+    },
+    {
+      "name": "healthRegionBegin",
+      "accounts": [
+        {
+          "name": "instructions",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "Instructions Sysvar for instruction introspection"
           ]
         },
         {
-          "name": "payerVault",
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
           "isMut": true,
           "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "healthRegionEnd",
+      "accounts": [
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "serum3RegisterMarket",
+      "docs": [
+        "",
+        "Serum",
+        ""
+      ],
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true,
           "docs": [
-            "The bank vault that pays for the order, if necessary"
+            "group admin or fast listing admin, checked at #1"
           ]
         },
         {
-          "name": "payerOracle",
+          "name": "serumProgram",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "tokenProgram",
+          "name": "serumMarketExternal",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "serumMarket",
+          "isMut": true,
+          "isSigner": false,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "type": "string",
+                "value": "Serum3Market"
+              },
+              {
+                "kind": "account",
+                "type": "publicKey",
+                "path": "group"
+              },
+              {
+                "kind": "account",
+                "type": "publicKey",
+                "path": "serum_market_external"
+              }
+            ]
+          }
+        },
+        {
+          "name": "indexReservation",
+          "isMut": true,
+          "isSigner": false,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "type": "string",
+                "value": "Serum3Index"
+              },
+              {
+                "kind": "account",
+                "type": "publicKey",
+                "path": "group"
+              },
+              {
+                "kind": "arg",
+                "type": "u16",
+                "path": "market_index"
+              }
+            ]
+          }
+        },
+        {
+          "name": "quoteBank",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "baseBank",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
           "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "side",
-          "type": {
-            "defined": "Serum3Side"
-          }
+          "name": "marketIndex",
+          "type": "u16"
         },
         {
-          "name": "limitPrice",
-          "type": "u64"
+          "name": "name",
+          "type": "string"
         },
         {
-          "name": "maxBaseQty",
-          "type": "u64"
+          "name": "oraclePriceBand",
+          "type": "f32"
+        }
+      ]
+    },
+    {
+      "name": "serum3EditMarket",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
         },
         {
-          "name": "maxNativeQuoteQtyIncludingFees",
-          "type": "u64"
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
         },
         {
-          "name": "selfTradeBehavior",
+          "name": "market",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "reduceOnlyOpt",
           "type": {
-            "defined": "Serum3SelfTradeBehavior"
+            "option": "bool"
           }
         },
         {
-          "name": "orderType",
+          "name": "forceCloseOpt",
           "type": {
-            "defined": "Serum3OrderType"
+            "option": "bool"
           }
         },
         {
-          "name": "clientOrderId",
-          "type": "u64"
+          "name": "nameOpt",
+          "type": {
+            "option": "string"
+          }
         },
         {
-          "name": "limit",
-          "type": "u16"
+          "name": "oraclePriceBandOpt",
+          "type": {
+            "option": "f32"
+          }
         }
       ]
     },
     {
-      "name": "serum3CancelOrder",
+      "name": "serum3DeregisterMarket",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "admin"
+          ]
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "serumMarket",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "indexReservation",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "solDestination",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "serum3CreateOpenOrders",
       "accounts": [
         {
           "name": "group",
@@ -17759,11 +23047,6 @@ export const IDL: MangoV4 = {
           "isMut": false,
           "isSigner": true
         },
-        {
-          "name": "openOrders",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "serumMarket",
           "isMut": false,
@@ -17781,40 +23064,53 @@ export const IDL: MangoV4 = {
         },
         {
           "name": "serumMarketExternal",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "marketBids",
+          "name": "openOrders",
           "isMut": true,
-          "isSigner": false
+          "isSigner": false,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "type": "string",
+                "value": "Serum3OO"
+              },
+              {
+                "kind": "account",
+                "type": "publicKey",
+                "path": "account"
+              },
+              {
+                "kind": "account",
+                "type": "publicKey",
+                "path": "serum_market"
+              }
+            ]
+          }
         },
         {
-          "name": "marketAsks",
+          "name": "payer",
           "isMut": true,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "marketEventQueue",
-          "isMut": true,
+          "name": "systemProgram",
+          "isMut": false,
           "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "side",
-          "type": {
-            "defined": "Serum3Side"
-          }
         },
         {
-          "name": "orderId",
-          "type": "u128"
+          "name": "rent",
+          "isMut": false,
+          "isSigner": false
         }
-      ]
+      ],
+      "args": []
     },
     {
-      "name": "serum3CancelOrderByClientOrderId",
+      "name": "serum3CloseOpenOrders",
       "accounts": [
         {
           "name": "group",
@@ -17834,11 +23130,6 @@ export const IDL: MangoV4 = {
           "isMut": false,
           "isSigner": true
         },
-        {
-          "name": "openOrders",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "serumMarket",
           "isMut": false,
@@ -17856,34 +23147,24 @@ export const IDL: MangoV4 = {
         },
         {
           "name": "serumMarketExternal",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "marketBids",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "marketAsks",
+          "name": "openOrders",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "marketEventQueue",
+          "name": "solDestination",
           "isMut": true,
           "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "clientOrderId",
-          "type": "u64"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "serum3CancelAllOrders",
+      "name": "serum3PlaceOrder",
       "accounts": [
         {
           "name": "group",
@@ -17892,7 +23173,7 @@ export const IDL: MangoV4 = {
         },
         {
           "name": "account",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false,
           "relations": [
             "group"
@@ -17942,22 +23223,105 @@ export const IDL: MangoV4 = {
           "name": "marketEventQueue",
           "isMut": true,
           "isSigner": false
+        },
+        {
+          "name": "marketRequestQueue",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketBaseVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketQuoteVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketVaultSigner",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "needed for the automatic settle_funds call"
+          ]
+        },
+        {
+          "name": "payerBank",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The bank that pays for the order, if necessary"
+          ],
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "payerVault",
+          "isMut": true,
+          "isSigner": false,
+          "docs": [
+            "The bank vault that pays for the order, if necessary"
+          ]
+        },
+        {
+          "name": "payerOracle",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
+        {
+          "name": "side",
+          "type": {
+            "defined": "Serum3Side"
+          }
+        },
+        {
+          "name": "limitPrice",
+          "type": "u64"
+        },
+        {
+          "name": "maxBaseQty",
+          "type": "u64"
+        },
+        {
+          "name": "maxNativeQuoteQtyIncludingFees",
+          "type": "u64"
+        },
+        {
+          "name": "selfTradeBehavior",
+          "type": {
+            "defined": "Serum3SelfTradeBehavior"
+          }
+        },
+        {
+          "name": "orderType",
+          "type": {
+            "defined": "Serum3OrderType"
+          }
+        },
+        {
+          "name": "clientOrderId",
+          "type": "u64"
+        },
         {
           "name": "limit",
-          "type": "u8"
+          "type": "u16"
         }
       ]
     },
     {
-      "name": "serum3SettleFunds",
+      "name": "serum3PlaceOrderV2",
       "docs": [
-        "Deprecated instruction that used to settles all free funds from the OpenOrders account",
-        "into the MangoAccount.",
-        "",
-        "Any serum \"referrer rebates\" (ui fees) are considered Mango fees."
+        "requires the receiver_bank in the health account list to be writable"
       ],
       "accounts": [
         {
@@ -18003,6 +23367,26 @@ export const IDL: MangoV4 = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "marketBids",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketAsks",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketEventQueue",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketRequestQueue",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "marketBaseVault",
           "isMut": true,
@@ -18022,17 +23406,371 @@ export const IDL: MangoV4 = {
           ]
         },
         {
-          "name": "quoteBank",
+          "name": "payerBank",
           "isMut": true,
           "isSigner": false,
+          "docs": [
+            "The bank that pays for the order, if necessary"
+          ],
           "relations": [
             "group"
           ]
         },
         {
-          "name": "quoteVault",
+          "name": "payerVault",
           "isMut": true,
-          "isSigner": false
+          "isSigner": false,
+          "docs": [
+            "The bank vault that pays for the order, if necessary"
+          ]
+        },
+        {
+          "name": "payerOracle",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "side",
+          "type": {
+            "defined": "Serum3Side"
+          }
+        },
+        {
+          "name": "limitPrice",
+          "type": "u64"
+        },
+        {
+          "name": "maxBaseQty",
+          "type": "u64"
+        },
+        {
+          "name": "maxNativeQuoteQtyIncludingFees",
+          "type": "u64"
+        },
+        {
+          "name": "selfTradeBehavior",
+          "type": {
+            "defined": "Serum3SelfTradeBehavior"
+          }
+        },
+        {
+          "name": "orderType",
+          "type": {
+            "defined": "Serum3OrderType"
+          }
+        },
+        {
+          "name": "clientOrderId",
+          "type": "u64"
+        },
+        {
+          "name": "limit",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "serum3CancelOrder",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "openOrders",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "serumMarket",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "serum_program",
+            "serum_market_external"
+          ]
+        },
+        {
+          "name": "serumProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "serumMarketExternal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketBids",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketAsks",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketEventQueue",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "side",
+          "type": {
+            "defined": "Serum3Side"
+          }
+        },
+        {
+          "name": "orderId",
+          "type": "u128"
+        }
+      ]
+    },
+    {
+      "name": "serum3CancelOrderByClientOrderId",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "openOrders",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "serumMarket",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "serum_program",
+            "serum_market_external"
+          ]
+        },
+        {
+          "name": "serumProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "serumMarketExternal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketBids",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketAsks",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketEventQueue",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "clientOrderId",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "serum3CancelAllOrders",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "openOrders",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "serumMarket",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "serum_program",
+            "serum_market_external"
+          ]
+        },
+        {
+          "name": "serumProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "serumMarketExternal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketBids",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketAsks",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketEventQueue",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "limit",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "serum3SettleFunds",
+      "docs": [
+        "Deprecated instruction that used to settles all free funds from the OpenOrders account",
+        "into the MangoAccount.",
+        "",
+        "Any serum \"referrer rebates\" (ui fees) are considered Mango fees."
+      ],
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "openOrders",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "serumMarket",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "serum_program",
+            "serum_market_external"
+          ]
+        },
+        {
+          "name": "serumProgram",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "serumMarketExternal",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketBaseVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketQuoteVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "marketVaultSigner",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "needed for the automatic settle_funds call"
+          ]
+        },
+        {
+          "name": "quoteBank",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "quoteVault",
+          "isMut": true,
+          "isSigner": false
         },
         {
           "name": "baseBank",
@@ -18812,6 +24550,18 @@ export const IDL: MangoV4 = {
         {
           "name": "platformLiquidationFee",
           "type": "f32"
+        },
+        {
+          "name": "booksideCapacity",
+          "type": "u32"
+        },
+        {
+          "name": "eventQueueCapacity",
+          "type": "u32"
+        },
+        {
+          "name": "openingAuctionDuration",
+          "type": "u32"
         }
       ]
     },
@@ -19032,6 +24782,88 @@ export const IDL: MangoV4 = {
           "type": {
             "option": "f32"
           }
+        },
+        {
+          "name": "fundingStablePriceWeightOpt",
+          "type": {
+            "option": "f32"
+          }
+        },
+        {
+          "name": "settleStablePriceWeightOpt",
+          "type": {
+            "option": "f32"
+          }
+        },
+        {
+          "name": "openingAuctionDurationOpt",
+          "type": {
+            "option": "u32"
+          }
+        }
+      ]
+    },
+    {
+      "name": "perpResizeOrderbook",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "admin"
+          ]
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "perpMarket",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "bids",
+            "asks",
+            "event_queue"
+          ]
+        },
+        {
+          "name": "bids",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "asks",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "eventQueue",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "booksideCapacity",
+          "type": "u32"
+        },
+        {
+          "name": "eventQueueCapacity",
+          "type": "u32"
         }
       ]
     },
@@ -19123,7 +24955,7 @@ export const IDL: MangoV4 = {
       "args": []
     },
     {
-      "name": "perpPlaceOrder",
+      "name": "perpSetIsolated",
       "accounts": [
         {
           "name": "group",
@@ -19145,85 +24977,22 @@ export const IDL: MangoV4 = {
         },
         {
           "name": "perpMarket",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false,
           "relations": [
-            "group",
-            "bids",
-            "asks",
-            "event_queue",
-            "oracle"
+            "group"
           ]
-        },
-        {
-          "name": "bids",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "asks",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "eventQueue",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "oracle",
-          "isMut": false,
-          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "side",
-          "type": {
-            "defined": "Side"
-          }
-        },
-        {
-          "name": "priceLots",
-          "type": "i64"
-        },
-        {
-          "name": "maxBaseLots",
-          "type": "i64"
-        },
-        {
-          "name": "maxQuoteLots",
-          "type": "i64"
-        },
-        {
-          "name": "clientOrderId",
-          "type": "u64"
-        },
-        {
-          "name": "orderType",
-          "type": {
-            "defined": "PlaceOrderType"
-          }
-        },
-        {
-          "name": "reduceOnly",
+          "name": "isolated",
           "type": "bool"
-        },
-        {
-          "name": "expiryTimestamp",
-          "type": "u64"
-        },
-        {
-          "name": "limit",
-          "type": "u8"
         }
-      ],
-      "returns": {
-        "option": "u128"
-      }
+      ]
     },
     {
-      "name": "perpPlaceOrderV2",
+      "name": "perpIsolatedTransfer",
       "accounts": [
         {
           "name": "group",
@@ -19245,91 +25014,22 @@ export const IDL: MangoV4 = {
         },
         {
           "name": "perpMarket",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false,
           "relations": [
-            "group",
-            "bids",
-            "asks",
-            "event_queue",
-            "oracle"
+            "group"
           ]
-        },
-        {
-          "name": "bids",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "asks",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "eventQueue",
-          "isMut": true,
-          "isSigner": false
-        },
-        {
-          "name": "oracle",
-          "isMut": false,
-          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "side",
-          "type": {
-            "defined": "Side"
-          }
-        },
-        {
-          "name": "priceLots",
-          "type": "i64"
-        },
-        {
-          "name": "maxBaseLots",
-          "type": "i64"
-        },
-        {
-          "name": "maxQuoteLots",
+          "name": "amount",
           "type": "i64"
-        },
-        {
-          "name": "clientOrderId",
-          "type": "u64"
-        },
-        {
-          "name": "orderType",
-          "type": {
-            "defined": "PlaceOrderType"
-          }
-        },
-        {
-          "name": "selfTradeBehavior",
-          "type": {
-            "defined": "SelfTradeBehavior"
-          }
-        },
-        {
-          "name": "reduceOnly",
-          "type": "bool"
-        },
-        {
-          "name": "expiryTimestamp",
-          "type": "u64"
-        },
-        {
-          "name": "limit",
-          "type": "u8"
         }
-      ],
-      "returns": {
-        "option": "u128"
-      }
+      ]
     },
     {
-      "name": "perpPlaceOrderPegged",
+      "name": "perpPlaceOrder",
       "accounts": [
         {
           "name": "group",
@@ -19390,11 +25090,7 @@ export const IDL: MangoV4 = {
           }
         },
         {
-          "name": "priceOffsetLots",
-          "type": "i64"
-        },
-        {
-          "name": "pegLimit",
+          "name": "priceLots",
           "type": "i64"
         },
         {
@@ -19426,10 +25122,6 @@ export const IDL: MangoV4 = {
         {
           "name": "limit",
           "type": "u8"
-        },
-        {
-          "name": "maxOracleStalenessSlots",
-          "type": "i32"
         }
       ],
       "returns": {
@@ -19437,7 +25129,7 @@ export const IDL: MangoV4 = {
       }
     },
     {
-      "name": "perpPlaceOrderPeggedV2",
+      "name": "perpPlaceOrderV2",
       "accounts": [
         {
           "name": "group",
@@ -19498,11 +25190,7 @@ export const IDL: MangoV4 = {
           }
         },
         {
-          "name": "priceOffsetLots",
-          "type": "i64"
-        },
-        {
-          "name": "pegLimit",
+          "name": "priceLots",
           "type": "i64"
         },
         {
@@ -19540,10 +25228,6 @@ export const IDL: MangoV4 = {
         {
           "name": "limit",
           "type": "u8"
-        },
-        {
-          "name": "maxOracleStalenessSlots",
-          "type": "i32"
         }
       ],
       "returns": {
@@ -19551,7 +25235,7 @@ export const IDL: MangoV4 = {
       }
     },
     {
-      "name": "perpCancelOrder",
+      "name": "perpPlaceOrderPegged",
       "accounts": [
         {
           "name": "group",
@@ -19578,7 +25262,9 @@ export const IDL: MangoV4 = {
           "relations": [
             "group",
             "bids",
-            "asks"
+            "asks",
+            "event_queue",
+            "oracle"
           ]
         },
         {
@@ -19590,17 +25276,74 @@ export const IDL: MangoV4 = {
           "name": "asks",
           "isMut": true,
           "isSigner": false
+        },
+        {
+          "name": "eventQueue",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "oracle",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "orderId",
-          "type": "u128"
+          "name": "side",
+          "type": {
+            "defined": "Side"
+          }
+        },
+        {
+          "name": "priceOffsetLots",
+          "type": "i64"
+        },
+        {
+          "name": "pegLimit",
+          "type": "i64"
+        },
+        {
+          "name": "maxBaseLots",
+          "type": "i64"
+        },
+        {
+          "name": "maxQuoteLots",
+          "type": "i64"
+        },
+        {
+          "name": "clientOrderId",
+          "type": "u64"
+        },
+        {
+          "name": "orderType",
+          "type": {
+            "defined": "PlaceOrderType"
+          }
+        },
+        {
+          "name": "reduceOnly",
+          "type": "bool"
+        },
+        {
+          "name": "expiryTimestamp",
+          "type": "u64"
+        },
+        {
+          "name": "limit",
+          "type": "u8"
+        },
+        {
+          "name": "maxOracleStalenessSlots",
+          "type": "i32"
         }
-      ]
+      ],
+      "returns": {
+        "option": "u128"
+      }
     },
     {
-      "name": "perpCancelOrderByClientOrderId",
+      "name": "perpPlaceOrderPeggedV2",
       "accounts": [
         {
           "name": "group",
@@ -19627,7 +25370,9 @@ export const IDL: MangoV4 = {
           "relations": [
             "group",
             "bids",
-            "asks"
+            "asks",
+            "event_queue",
+            "oracle"
           ]
         },
         {
@@ -19639,17 +25384,80 @@ export const IDL: MangoV4 = {
           "name": "asks",
           "isMut": true,
           "isSigner": false
+        },
+        {
+          "name": "eventQueue",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "oracle",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
+        {
+          "name": "side",
+          "type": {
+            "defined": "Side"
+          }
+        },
+        {
+          "name": "priceOffsetLots",
+          "type": "i64"
+        },
+        {
+          "name": "pegLimit",
+          "type": "i64"
+        },
+        {
+          "name": "maxBaseLots",
+          "type": "i64"
+        },
+        {
+          "name": "maxQuoteLots",
+          "type": "i64"
+        },
         {
           "name": "clientOrderId",
           "type": "u64"
+        },
+        {
+          "name": "orderType",
+          "type": {
+            "defined": "PlaceOrderType"
+          }
+        },
+        {
+          "name": "selfTradeBehavior",
+          "type": {
+            "defined": "SelfTradeBehavior"
+          }
+        },
+        {
+          "name": "reduceOnly",
+          "type": "bool"
+        },
+        {
+          "name": "expiryTimestamp",
+          "type": "u64"
+        },
+        {
+          "name": "limit",
+          "type": "u8"
+        },
+        {
+          "name": "maxOracleStalenessSlots",
+          "type": "i32"
         }
-      ]
+      ],
+      "returns": {
+        "option": "u128"
+      }
     },
     {
-      "name": "perpCancelAllOrders",
+      "name": "perpPlaceSpreadOrder",
       "accounts": [
         {
           "name": "group",
@@ -19670,43 +25478,35 @@ export const IDL: MangoV4 = {
           "isSigner": true
         },
         {
-          "name": "perpMarket",
+          "name": "perpMarketA",
           "isMut": true,
           "isSigner": false,
           "relations": [
-            "group",
-            "bids",
-            "asks"
+            "group"
           ]
         },
         {
-          "name": "bids",
+          "name": "bidsA",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "asks",
+          "name": "asksA",
           "isMut": true,
           "isSigner": false
-        }
-      ],
-      "args": [
+        },
         {
-          "name": "limit",
-          "type": "u8"
-        }
-      ]
-    },
-    {
-      "name": "perpCancelAllOrdersBySide",
-      "accounts": [
+          "name": "eventQueueA",
+          "isMut": true,
+          "isSigner": false
+        },
         {
-          "name": "group",
+          "name": "oracleA",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "account",
+          "name": "perpMarketB",
           "isMut": true,
           "isSigner": false,
           "relations": [
@@ -19714,40 +25514,55 @@ export const IDL: MangoV4 = {
           ]
         },
         {
-          "name": "owner",
-          "isMut": false,
-          "isSigner": true
+          "name": "bidsB",
+          "isMut": true,
+          "isSigner": false
         },
         {
-          "name": "perpMarket",
+          "name": "asksB",
           "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group",
-            "bids",
-            "asks"
-          ]
+          "isSigner": false
         },
         {
-          "name": "bids",
+          "name": "eventQueueB",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "asks",
-          "isMut": true,
+          "name": "oracleB",
+          "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "sideOption",
+          "name": "sideA",
           "type": {
-            "option": {
-              "defined": "Side"
-            }
+            "defined": "Side"
+          }
+        },
+        {
+          "name": "priceLotsA",
+          "type": "i64"
+        },
+        {
+          "name": "maxBaseLotsA",
+          "type": "i64"
+        },
+        {
+          "name": "sideB",
+          "type": {
+            "defined": "Side"
           }
         },
+        {
+          "name": "priceLotsB",
+          "type": "i64"
+        },
+        {
+          "name": "legBRatio",
+          "type": "f64"
+        },
         {
           "name": "limit",
           "type": "u8"
@@ -19755,7 +25570,7 @@ export const IDL: MangoV4 = {
       ]
     },
     {
-      "name": "perpConsumeEvents",
+      "name": "perpPlaceOrdersBatch",
       "accounts": [
         {
           "name": "group",
@@ -19763,34 +25578,17 @@ export const IDL: MangoV4 = {
           "isSigner": false
         },
         {
-          "name": "perpMarket",
+          "name": "account",
           "isMut": true,
           "isSigner": false,
           "relations": [
-            "group",
-            "event_queue"
+            "group"
           ]
         },
         {
-          "name": "eventQueue",
-          "isMut": true,
-          "isSigner": false
-        }
-      ],
-      "args": [
-        {
-          "name": "limit",
-          "type": "u64"
-        }
-      ]
-    },
-    {
-      "name": "perpUpdateFunding",
-      "accounts": [
-        {
-          "name": "group",
+          "name": "owner",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
           "name": "perpMarket",
@@ -19800,6 +25598,7 @@ export const IDL: MangoV4 = {
             "group",
             "bids",
             "asks",
+            "event_queue",
             "oracle"
           ]
         },
@@ -19813,16 +25612,42 @@ export const IDL: MangoV4 = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "eventQueue",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "oracle",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "orders",
+          "type": {
+            "vec": {
+              "defined": "PerpBatchOrder"
+            }
+          }
+        },
+        {
+          "name": "cancelAllFirst",
+          "type": "bool"
+        },
+        {
+          "name": "expiryTimestamp",
+          "type": "u64"
+        },
+        {
+          "name": "limit",
+          "type": "u8"
+        }
+      ]
     },
     {
-      "name": "perpSettlePnl",
+      "name": "perpEditOrder",
       "accounts": [
         {
           "name": "group",
@@ -19830,7 +25655,7 @@ export const IDL: MangoV4 = {
           "isSigner": false
         },
         {
-          "name": "settler",
+          "name": "account",
           "isMut": true,
           "isSigner": false,
           "relations": [
@@ -19838,114 +25663,95 @@ export const IDL: MangoV4 = {
           ]
         },
         {
-          "name": "settlerOwner",
+          "name": "owner",
           "isMut": false,
           "isSigner": true
         },
         {
           "name": "perpMarket",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false,
           "relations": [
             "group",
+            "bids",
+            "asks",
+            "event_queue",
             "oracle"
           ]
         },
         {
-          "name": "accountA",
+          "name": "bids",
           "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group"
-          ]
+          "isSigner": false
         },
         {
-          "name": "accountB",
+          "name": "asks",
           "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group"
-          ]
-        },
-        {
-          "name": "oracle",
-          "isMut": false,
           "isSigner": false
         },
         {
-          "name": "settleBank",
+          "name": "eventQueue",
           "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group"
-          ]
+          "isSigner": false
         },
         {
-          "name": "settleOracle",
+          "name": "oracle",
           "isMut": false,
           "isSigner": false
         }
       ],
-      "args": []
-    },
-    {
-      "name": "perpForceClosePosition",
-      "accounts": [
+      "args": [
         {
-          "name": "group",
-          "isMut": false,
-          "isSigner": false
+          "name": "orderIdOpt",
+          "type": {
+            "option": "u128"
+          }
         },
         {
-          "name": "perpMarket",
-          "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group",
-            "oracle"
-          ]
+          "name": "clientOrderIdOpt",
+          "type": {
+            "option": "u64"
+          }
         },
         {
-          "name": "accountA",
-          "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group"
-          ]
+          "name": "priceLots",
+          "type": "i64"
         },
         {
-          "name": "accountB",
-          "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group"
-          ]
+          "name": "maxBaseLots",
+          "type": "i64"
         },
         {
-          "name": "oracle",
-          "isMut": false,
-          "isSigner": false
+          "name": "selfTradeBehavior",
+          "type": {
+            "defined": "SelfTradeBehavior"
+          }
+        },
+        {
+          "name": "reduceOnly",
+          "type": "bool"
+        },
+        {
+          "name": "maxOracleStalenessSlots",
+          "type": "i32"
+        },
+        {
+          "name": "limit",
+          "type": "u8"
         }
       ],
-      "args": []
+      "returns": {
+        "option": "u128"
+      }
     },
     {
-      "name": "perpSettleFees",
+      "name": "perpCancelOrder",
       "accounts": [
         {
           "name": "group",
           "isMut": false,
           "isSigner": false
         },
-        {
-          "name": "perpMarket",
-          "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group",
-            "oracle"
-          ]
-        },
         {
           "name": "account",
           "isMut": true,
@@ -19955,33 +25761,40 @@ export const IDL: MangoV4 = {
           ]
         },
         {
-          "name": "oracle",
+          "name": "owner",
           "isMut": false,
-          "isSigner": false
+          "isSigner": true
         },
         {
-          "name": "settleBank",
+          "name": "perpMarket",
           "isMut": true,
           "isSigner": false,
           "relations": [
-            "group"
+            "group",
+            "bids",
+            "asks"
           ]
         },
         {
-          "name": "settleOracle",
-          "isMut": false,
+          "name": "bids",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "asks",
+          "isMut": true,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "maxSettleAmount",
-          "type": "u64"
+          "name": "orderId",
+          "type": "u128"
         }
       ]
     },
     {
-      "name": "perpLiqBaseOrPositivePnl",
+      "name": "perpCancelOrderByClientOrderId",
       "accounts": [
         {
           "name": "group",
@@ -19989,21 +25802,7 @@ export const IDL: MangoV4 = {
           "isSigner": false
         },
         {
-          "name": "perpMarket",
-          "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group",
-            "oracle"
-          ]
-        },
-        {
-          "name": "oracle",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "liqor",
+          "name": "account",
           "isMut": true,
           "isSigner": false,
           "relations": [
@@ -20011,50 +25810,40 @@ export const IDL: MangoV4 = {
           ]
         },
         {
-          "name": "liqorOwner",
+          "name": "owner",
           "isMut": false,
           "isSigner": true
         },
         {
-          "name": "liqee",
-          "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group"
-          ]
-        },
-        {
-          "name": "settleBank",
+          "name": "perpMarket",
           "isMut": true,
           "isSigner": false,
           "relations": [
-            "group"
+            "group",
+            "bids",
+            "asks"
           ]
         },
         {
-          "name": "settleVault",
+          "name": "bids",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "settleOracle",
-          "isMut": false,
+          "name": "asks",
+          "isMut": true,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "maxBaseTransfer",
-          "type": "i64"
-        },
-        {
-          "name": "maxPnlTransfer",
+          "name": "clientOrderId",
           "type": "u64"
         }
       ]
     },
     {
-      "name": "perpLiqForceCancelOrders",
+      "name": "perpCancelAllOrders",
       "accounts": [
         {
           "name": "group",
@@ -20069,6 +25858,11 @@ export const IDL: MangoV4 = {
             "group"
           ]
         },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
         {
           "name": "perpMarket",
           "isMut": true,
@@ -20098,18 +25892,15 @@ export const IDL: MangoV4 = {
       ]
     },
     {
-      "name": "perpLiqNegativePnlOrBankruptcy",
+      "name": "perpCancelAllOrdersBySide",
       "accounts": [
         {
           "name": "group",
           "isMut": false,
-          "isSigner": false,
-          "relations": [
-            "insurance_vault"
-          ]
+          "isSigner": false
         },
         {
-          "name": "liqor",
+          "name": "account",
           "isMut": true,
           "isSigner": false,
           "relations": [
@@ -20117,76 +25908,527 @@ export const IDL: MangoV4 = {
           ]
         },
         {
-          "name": "liqorOwner",
+          "name": "owner",
           "isMut": false,
           "isSigner": true
         },
-        {
-          "name": "liqee",
-          "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group"
-          ]
-        },
         {
           "name": "perpMarket",
           "isMut": true,
           "isSigner": false,
           "relations": [
             "group",
-            "oracle"
+            "bids",
+            "asks"
           ]
         },
         {
-          "name": "oracle",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "settleBank",
+          "name": "bids",
           "isMut": true,
-          "isSigner": false,
-          "relations": [
-            "group"
-          ]
+          "isSigner": false
         },
         {
-          "name": "settleVault",
+          "name": "asks",
           "isMut": true,
           "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "sideOption",
+          "type": {
+            "option": {
+              "defined": "Side"
+            }
+          }
         },
         {
-          "name": "settleOracle",
+          "name": "limit",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "perpConsumeEvents",
+      "accounts": [
+        {
+          "name": "group",
           "isMut": false,
           "isSigner": false
         },
         {
-          "name": "insuranceVault",
+          "name": "perpMarket",
           "isMut": true,
-          "isSigner": false
+          "isSigner": false,
+          "relations": [
+            "group",
+            "event_queue"
+          ]
         },
         {
-          "name": "tokenProgram",
-          "isMut": false,
+          "name": "eventQueue",
+          "isMut": true,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "maxLiabTransfer",
+          "name": "limit",
           "type": "u64"
         }
       ]
     },
     {
-      "name": "perpLiqNegativePnlOrBankruptcyV2",
+      "name": "perpUpdateFunding",
       "accounts": [
         {
           "name": "group",
           "isMut": false,
-          "isSigner": false,
-          "relations": [
+          "isSigner": false
+        },
+        {
+          "name": "perpMarket",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "bids",
+            "asks",
+            "oracle"
+          ]
+        },
+        {
+          "name": "bids",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "asks",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "oracle",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "perpClearOpeningAuction",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "perpMarket",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "bids",
+            "asks",
+            "event_queue",
+            "oracle"
+          ]
+        },
+        {
+          "name": "bids",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "asks",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "eventQueue",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "oracle",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "limit",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "perpSettlePnl",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "settler",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "settlerOwner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "perpMarket",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "oracle"
+          ]
+        },
+        {
+          "name": "accountA",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "accountB",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "oracle",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "settleBank",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "settleOracle",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "perpForceClosePosition",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "perpMarket",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "oracle"
+          ]
+        },
+        {
+          "name": "accountA",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "accountB",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "oracle",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "perpSettleFees",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "perpMarket",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "oracle"
+          ]
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "oracle",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "settleBank",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "settleOracle",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "maxSettleAmount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "perpLiqBaseOrPositivePnl",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "perpMarket",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "oracle"
+          ]
+        },
+        {
+          "name": "oracle",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "liqor",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "liqorOwner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "liqee",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "settleBank",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "settleVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "settleOracle",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "maxBaseTransfer",
+          "type": "i64"
+        },
+        {
+          "name": "maxPnlTransfer",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "perpLiqForceCancelOrders",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "perpMarket",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "bids",
+            "asks"
+          ]
+        },
+        {
+          "name": "bids",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "asks",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "limit",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "perpLiqNegativePnlOrBankruptcy",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "insurance_vault"
+          ]
+        },
+        {
+          "name": "liqor",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "liqorOwner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "liqee",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "perpMarket",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "oracle"
+          ]
+        },
+        {
+          "name": "oracle",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "settleBank",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "settleVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "settleOracle",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "insuranceVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "tokenProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "maxLiabTransfer",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "perpLiqNegativePnlOrBankruptcyV2",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
             "insurance_vault"
           ]
         },
@@ -21159,7 +27401,7 @@ export const IDL: MangoV4 = {
           "isSigner": false
         },
         {
-          "name": "openOrders",
+          "name": "openOrdersIndexer",
           "isMut": true,
           "isSigner": false,
           "pda": {
@@ -21167,17 +27409,36 @@ export const IDL: MangoV4 = {
               {
                 "kind": "const",
                 "type": "string",
-                "value": "OpenOrders"
+                "value": "OpenOrdersIndexer"
               },
               {
                 "kind": "account",
                 "type": "publicKey",
-                "path": "openbook_v2_market"
+                "path": "group"
+              }
+            ],
+            "programId": {
+              "kind": "account",
+              "type": "publicKey",
+              "path": "openbook_v2_program"
+            }
+          }
+        },
+        {
+          "name": "openOrders",
+          "isMut": true,
+          "isSigner": false,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "type": "string",
+                "value": "OpenOrders"
               },
               {
                 "kind": "account",
                 "type": "publicKey",
-                "path": "openbook_v2_market_external"
+                "path": "group"
               },
               {
                 "kind": "arg",
@@ -21256,6 +27517,30 @@ export const IDL: MangoV4 = {
           "isMut": false,
           "isSigner": false
         },
+        {
+          "name": "openOrdersIndexer",
+          "isMut": true,
+          "isSigner": false,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "type": "string",
+                "value": "OpenOrdersIndexer"
+              },
+              {
+                "kind": "account",
+                "type": "publicKey",
+                "path": "group"
+              }
+            ],
+            "programId": {
+              "kind": "account",
+              "type": "publicKey",
+              "path": "openbook_v2_program"
+            }
+          }
+        },
         {
           "name": "openOrders",
           "isMut": true,
@@ -21265,6 +27550,16 @@ export const IDL: MangoV4 = {
           "name": "solDestination",
           "isMut": true,
           "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": []
@@ -21298,7 +27593,12 @@ export const IDL: MangoV4 = {
         {
           "name": "openbookV2Market",
           "isMut": false,
-          "isSigner": false
+          "isSigner": false,
+          "relations": [
+            "group",
+            "openbook_v2_program",
+            "openbook_v2_market_external"
+          ]
         },
         {
           "name": "openbookV2Program",
@@ -21312,7 +27612,9 @@ export const IDL: MangoV4 = {
           "relations": [
             "bids",
             "asks",
-            "event_heap"
+            "event_heap",
+            "market_base_vault",
+            "market_quote_vault"
           ]
         },
         {
@@ -21340,11 +27642,6 @@ export const IDL: MangoV4 = {
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "marketVaultSigner",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "payerBank",
           "isMut": true,
@@ -21431,6 +27728,11 @@ export const IDL: MangoV4 = {
           "isMut": false,
           "isSigner": true
         },
+        {
+          "name": "openOrders",
+          "isMut": true,
+          "isSigner": false
+        },
         {
           "name": "openbookV2Market",
           "isMut": false,
@@ -21453,7 +27755,9 @@ export const IDL: MangoV4 = {
           "relations": [
             "bids",
             "asks",
-            "event_heap"
+            "event_heap",
+            "market_base_vault",
+            "market_quote_vault"
           ]
         },
         {
@@ -21471,11 +27775,6 @@ export const IDL: MangoV4 = {
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "marketRequestQueue",
-          "isMut": true,
-          "isSigner": false
-        },
         {
           "name": "marketBaseVault",
           "isMut": true,
@@ -21486,11 +27785,6 @@ export const IDL: MangoV4 = {
           "isMut": true,
           "isSigner": false
         },
-        {
-          "name": "marketVaultSigner",
-          "isMut": false,
-          "isSigner": false
-        },
         {
           "name": "payerBank",
           "isMut": true,
@@ -21595,7 +27889,7 @@ export const IDL: MangoV4 = {
         },
         {
           "name": "openbookV2MarketExternal",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false,
           "relations": [
             "bids",
@@ -21642,8 +27936,11 @@ export const IDL: MangoV4 = {
         },
         {
           "name": "authority",
-          "isMut": false,
-          "isSigner": true
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Also pays openbook v2's penalty for settling fills that are still on the event heap"
+          ]
         },
         {
           "name": "openOrders",
@@ -21668,7 +27965,12 @@ export const IDL: MangoV4 = {
         {
           "name": "openbookV2MarketExternal",
           "isMut": true,
-          "isSigner": false
+          "isSigner": false,
+          "relations": [
+            "market_authority",
+            "market_base_vault",
+            "market_quote_vault"
+          ]
         },
         {
           "name": "marketBaseVault",
@@ -21681,12 +27983,9 @@ export const IDL: MangoV4 = {
           "isSigner": false
         },
         {
-          "name": "marketVaultSigner",
+          "name": "marketAuthority",
           "isMut": false,
-          "isSigner": false,
-          "docs": [
-            "needed for the automatic settle_funds call"
-          ]
+          "isSigner": false
         },
         {
           "name": "quoteBank",
@@ -21728,6 +28027,11 @@ export const IDL: MangoV4 = {
           "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
@@ -21775,12 +28079,15 @@ export const IDL: MangoV4 = {
         },
         {
           "name": "openbookV2MarketExternal",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false,
           "relations": [
             "bids",
             "asks",
-            "event_heap"
+            "event_heap",
+            "market_authority",
+            "market_base_vault",
+            "market_quote_vault"
           ]
         },
         {
@@ -21809,7 +28116,7 @@ export const IDL: MangoV4 = {
           "isSigner": false
         },
         {
-          "name": "marketVaultSigner",
+          "name": "marketAuthority",
           "isMut": false,
           "isSigner": false
         },
@@ -21839,10 +28146,23 @@ export const IDL: MangoV4 = {
           "isMut": true,
           "isSigner": false
         },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true,
+          "docs": [
+            "Pays openbook v2's penalty for settling fills that are still on the event heap"
+          ]
+        },
         {
           "name": "tokenProgram",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": [
@@ -21895,7 +28215,7 @@ export const IDL: MangoV4 = {
         },
         {
           "name": "openbookV2MarketExternal",
-          "isMut": false,
+          "isMut": true,
           "isSigner": false,
           "relations": [
             "bids",
@@ -21921,157 +28241,717 @@ export const IDL: MangoV4 = {
       ]
     },
     {
-      "name": "benchmark",
+      "name": "fixedTermMarketCreate",
       "docs": [
         "",
-        "benchmark",
+        "Fixed term lending",
         ""
       ],
       "accounts": [
         {
-          "name": "dummy",
+          "name": "group",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "admin"
+          ]
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "bank",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "fixedTermMarket",
+          "isMut": true,
+          "isSigner": false,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "type": "string",
+                "value": "FixedTermMarket"
+              },
+              {
+                "kind": "account",
+                "type": "publicKey",
+                "path": "group"
+              },
+              {
+                "kind": "arg",
+                "type": "u16",
+                "path": "market_index"
+              }
+            ]
+          }
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "marketIndex",
+          "type": "u16"
+        },
+        {
+          "name": "name",
+          "type": "string"
+        },
+        {
+          "name": "maturityTimestamp",
+          "type": "u64"
+        },
+        {
+          "name": "lendRate",
+          "type": "f32"
+        },
+        {
+          "name": "borrowRate",
+          "type": "f32"
+        },
+        {
+          "name": "maxLentNative",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "fixedTermMarketEdit",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "admin"
+          ]
+        },
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "fixedTermMarket",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "nameOpt",
+          "type": {
+            "option": "string"
+          }
+        },
+        {
+          "name": "lendRateOpt",
+          "type": {
+            "option": "f32"
+          }
+        },
+        {
+          "name": "borrowRateOpt",
+          "type": {
+            "option": "f32"
+          }
+        },
+        {
+          "name": "maxLentNativeOpt",
+          "type": {
+            "option": "u64"
+          }
+        },
+        {
+          "name": "reduceOnlyOpt",
+          "type": {
+            "option": "bool"
+          }
+        }
+      ]
+    },
+    {
+      "name": "fixedTermLend",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "fixedTermMarket",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "bank",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "fixedTermBorrow",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "owner",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "fixedTermMarket",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "bank",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "oracle"
+          ]
+        },
+        {
+          "name": "oracle",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "fixedTermSettle",
+      "accounts": [
+        {
+          "name": "group",
           "isMut": false,
           "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "fixedTermMarket",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "bank",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
         }
       ],
       "args": []
-    }
-  ],
-  "accounts": [
+    },
     {
-      "name": "bank",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "group",
-            "type": "publicKey"
-          },
-          {
-            "name": "name",
-            "type": {
-              "array": [
-                "u8",
-                16
-              ]
-            }
-          },
-          {
-            "name": "mint",
-            "type": "publicKey"
-          },
-          {
-            "name": "vault",
-            "type": "publicKey"
-          },
-          {
-            "name": "oracle",
-            "type": "publicKey"
-          },
-          {
-            "name": "oracleConfig",
-            "type": {
-              "defined": "OracleConfig"
-            }
-          },
-          {
-            "name": "stablePriceModel",
-            "type": {
-              "defined": "StablePriceModel"
-            }
-          },
-          {
-            "name": "depositIndex",
-            "docs": [
-              "the index used to scale the value of an IndexedPosition",
-              "TODO: should always be >= 0, add checks?"
-            ],
-            "type": {
-              "defined": "I80F48"
-            }
-          },
-          {
-            "name": "borrowIndex",
-            "type": {
-              "defined": "I80F48"
-            }
-          },
-          {
-            "name": "indexedDeposits",
-            "docs": [
-              "deposits/borrows for this bank",
-              "",
-              "Note that these may become negative. It's perfectly fine for users to borrow one one bank",
-              "(increasing indexed_borrows there) and paying back on another (possibly decreasing indexed_borrows",
-              "below zero).",
-              "",
-              "The vault amount is not deducable from these values.",
-              "",
-              "These become meaningful when summed over all banks (like in update_index_and_rate)."
-            ],
-            "type": {
-              "defined": "I80F48"
-            }
-          },
-          {
-            "name": "indexedBorrows",
-            "type": {
-              "defined": "I80F48"
-            }
-          },
-          {
-            "name": "indexLastUpdated",
-            "type": "u64"
-          },
-          {
-            "name": "bankRateLastUpdated",
-            "type": "u64"
-          },
-          {
-            "name": "avgUtilization",
-            "type": {
-              "defined": "I80F48"
-            }
-          },
-          {
-            "name": "adjustmentFactor",
-            "type": {
-              "defined": "I80F48"
-            }
-          },
-          {
-            "name": "util0",
-            "docs": [
-              "The unscaled borrow interest curve is defined as continuous piecewise linear with the points:",
-              "",
-              "- 0% util: zero_util_rate",
-              "- util0% util: rate0",
-              "- util1% util: rate1",
-              "- 100% util: max_rate",
-              "",
-              "The final rate is this unscaled curve multiplied by interest_curve_scaling."
-            ],
-            "type": {
-              "defined": "I80F48"
-            }
-          },
-          {
-            "name": "rate0",
-            "type": {
-              "defined": "I80F48"
-            }
-          },
-          {
-            "name": "util1",
-            "type": {
-              "defined": "I80F48"
-            }
-          },
-          {
-            "name": "rate1",
-            "type": {
-              "defined": "I80F48"
-            }
+      "name": "perpScheduledOrderCreate",
+      "docs": [
+        "",
+        "Perp scheduled orders",
+        ""
+      ],
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "perpMarket",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "side",
+          "type": {
+            "defined": "Side"
+          }
+        },
+        {
+          "name": "orderType",
+          "type": {
+            "defined": "PerpScheduledOrderType"
+          }
+        },
+        {
+          "name": "maxBaseLots",
+          "type": "i64"
+        },
+        {
+          "name": "childBaseLots",
+          "type": "i64"
+        },
+        {
+          "name": "priceLots",
+          "type": "i64"
+        },
+        {
+          "name": "intervalSeconds",
+          "type": "u64"
+        },
+        {
+          "name": "expiryTimestamp",
+          "type": "u64"
+        },
+        {
+          "name": "reduceOnly",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "perpConditionalOrderCreate",
+      "docs": [
+        "Stop-loss and take-profit orders use the scheduled order storage and",
+        "are cancelled and triggered with the scheduled order instructions."
+      ],
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "perpMarket",
+          "isMut": false,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "side",
+          "type": {
+            "defined": "Side"
+          }
+        },
+        {
+          "name": "orderType",
+          "type": {
+            "defined": "PerpScheduledOrderType"
+          }
+        },
+        {
+          "name": "maxBaseLots",
+          "type": "i64"
+        },
+        {
+          "name": "triggerPriceLots",
+          "type": "i64"
+        },
+        {
+          "name": "maxSlippage",
+          "type": "f32"
+        },
+        {
+          "name": "expiryTimestamp",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "perpScheduledOrderCancel",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "authority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "perpMarket",
+          "isMut": false,
+          "isSigner": false,
+          "docs": [
+            "The market of the scheduled order, for cancelling a resting iceberg child order"
+          ],
+          "relations": [
+            "group",
+            "bids",
+            "asks"
+          ]
+        },
+        {
+          "name": "bids",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "asks",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "perpScheduledOrderIndex",
+          "type": "u8"
+        },
+        {
+          "name": "perpScheduledOrderId",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "perpScheduledOrderTrigger",
+      "accounts": [
+        {
+          "name": "group",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "account",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "caller",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        },
+        {
+          "name": "callerAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "perpMarket",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group",
+            "bids",
+            "asks",
+            "event_queue",
+            "oracle"
+          ]
+        },
+        {
+          "name": "bids",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "asks",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "eventQueue",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "oracle",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "settleBank",
+          "isMut": true,
+          "isSigner": false,
+          "relations": [
+            "group"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "perpScheduledOrderIndex",
+          "type": "u8"
+        },
+        {
+          "name": "perpScheduledOrderId",
+          "type": "u64"
+        },
+        {
+          "name": "limit",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "benchmark",
+      "docs": [
+        "",
+        "benchmark",
+        ""
+      ],
+      "accounts": [
+        {
+          "name": "dummy",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    }
+  ],
+  "accounts": [
+    {
+      "name": "bank",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "group",
+            "type": "publicKey"
+          },
+          {
+            "name": "name",
+            "type": {
+              "array": [
+                "u8",
+                16
+              ]
+            }
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "vault",
+            "type": "publicKey"
+          },
+          {
+            "name": "oracle",
+            "type": "publicKey"
+          },
+          {
+            "name": "oracleConfig",
+            "type": {
+              "defined": "OracleConfig"
+            }
+          },
+          {
+            "name": "stablePriceModel",
+            "type": {
+              "defined": "StablePriceModel"
+            }
+          },
+          {
+            "name": "depositIndex",
+            "docs": [
+              "the index used to scale the value of an IndexedPosition",
+              "TODO: should always be >= 0, add checks?"
+            ],
+            "type": {
+              "defined": "I80F48"
+            }
+          },
+          {
+            "name": "borrowIndex",
+            "type": {
+              "defined": "I80F48"
+            }
+          },
+          {
+            "name": "indexedDeposits",
+            "docs": [
+              "deposits/borrows for this bank",
+              "",
+              "Note that these may become negative. It's perfectly fine for users to borrow one one bank",
+              "(increasing indexed_borrows there) and paying back on another (possibly decreasing indexed_borrows",
+              "below zero).",
+              "",
+              "The vault amount is not deducable from these values.",
+              "",
+              "These become meaningful when summed over all banks (like in update_index_and_rate)."
+            ],
+            "type": {
+              "defined": "I80F48"
+            }
+          },
+          {
+            "name": "indexedBorrows",
+            "type": {
+              "defined": "I80F48"
+            }
+          },
+          {
+            "name": "indexLastUpdated",
+            "type": "u64"
+          },
+          {
+            "name": "bankRateLastUpdated",
+            "type": "u64"
+          },
+          {
+            "name": "avgUtilization",
+            "type": {
+              "defined": "I80F48"
+            }
+          },
+          {
+            "name": "adjustmentFactor",
+            "type": {
+              "defined": "I80F48"
+            }
+          },
+          {
+            "name": "util0",
+            "docs": [
+              "The unscaled borrow interest curve is defined as continuous piecewise linear with the points:",
+              "",
+              "- 0% util: zero_util_rate",
+              "- util0% util: rate0",
+              "- util1% util: rate1",
+              "- 100% util: max_rate",
+              "",
+              "The final rate is this unscaled curve multiplied by interest_curve_scaling."
+            ],
+            "type": {
+              "defined": "I80F48"
+            }
+          },
+          {
+            "name": "rate0",
+            "type": {
+              "defined": "I80F48"
+            }
+          },
+          {
+            "name": "util1",
+            "type": {
+              "defined": "I80F48"
+            }
+          },
+          {
+            "name": "rate1",
+            "type": {
+              "defined": "I80F48"
+            }
           },
           {
             "name": "maxRate",
@@ -22418,102 +29298,511 @@ export const IDL: MangoV4 = {
             "type": "f32"
           },
           {
-            "name": "reserved",
-            "type": {
-              "array": [
-                "u8",
-                1900
-              ]
-            }
-          }
-        ]
-      }
-    },
-    {
-      "name": "group",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "creator",
-            "type": "publicKey"
-          },
-          {
-            "name": "groupNum",
+            "name": "circuitBreakerCooldownSeconds",
+            "docs": [
+              "How long the bank stays reduce-only after the circuit breaker trips."
+            ],
             "type": "u32"
           },
           {
-            "name": "admin",
-            "type": "publicKey"
+            "name": "circuitBreakerTrippedUntil",
+            "docs": [
+              "While non-zero, the circuit breaker is tripped and borrows are reduce-only.",
+              "Cleared by token_update_index_and_rate once this timestamp has passed."
+            ],
+            "type": "u64"
           },
           {
-            "name": "fastListingAdmin",
-            "type": "publicKey"
+            "name": "circuitBreakerMaxPriceMove",
+            "docs": [
+              "Maximal relative difference between the oracle price and the latest delay price",
+              "of the stable price model before the circuit breaker trips. Zero disables it."
+            ],
+            "type": "f32"
           },
           {
-            "name": "mngoTokenIndex",
-            "type": "u16"
+            "name": "interestRateModel",
+            "docs": [
+              "Selects how rate0 and rate1 are adjusted over time, see InterestRateModel"
+            ],
+            "type": "u8"
           },
           {
-            "name": "padding",
+            "name": "padding2",
             "type": {
               "array": [
                 "u8",
-                2
+                3
               ]
             }
           },
           {
-            "name": "insuranceVault",
-            "type": "publicKey"
+            "name": "interestControllerKp",
+            "docs": [
+              "Gains of the rate controller: the relative daily change of rate0 and rate1 is",
+              "kp * error + ki * integral, with error = avg_utilization - interest_target_utilization."
+            ],
+            "type": "f32"
           },
           {
-            "name": "insuranceMint",
-            "type": "publicKey"
+            "name": "interestControllerKi",
+            "type": "f32"
           },
           {
-            "name": "bump",
-            "type": "u8"
+            "name": "interestControllerMaxDailyChange",
+            "docs": [
+              "Bound on the relative change of rate0 and rate1 per day, must be less than 1"
+            ],
+            "type": "f32"
           },
           {
-            "name": "testing",
-            "type": "u8"
+            "name": "interestControllerError",
+            "docs": [
+              "Utilization error at the last controller update"
+            ],
+            "type": "f32"
           },
           {
-            "name": "version",
-            "type": "u8"
+            "name": "interestControllerIntegral",
+            "docs": [
+              "Accumulated utilization error, in utilization * days, bounded to [-1, 1]"
+            ],
+            "type": "f64"
           },
           {
-            "name": "buybackFees",
-            "type": "u8"
+            "name": "borrowLimit",
+            "docs": [
+              "Limit on the total native borrows of the bank, zero means none.",
+              "",
+              "The limit is checked per bank. It can only be set on tokens with a single",
+              "bank and no banks can be added while it is set, so it bounds the",
+              "outstanding borrows of the whole token."
+            ],
+            "type": "u64"
           },
           {
-            "name": "buybackFeesMngoBonusFactor",
-            "type": "f32"
+            "name": "isolatedCollateral",
+            "docs": [
+              "If set, deposits of this token only count as collateral for borrows of the",
+              "tokens in isolated_borrow_token_indexes, up to isolated_debt_ceiling_quote."
+            ],
+            "type": "u8"
           },
           {
-            "name": "addressLookupTables",
+            "name": "padding3",
             "type": {
               "array": [
-                "publicKey",
-                20
+                "u8",
+                1
               ]
             }
           },
           {
-            "name": "securityAdmin",
-            "type": "publicKey"
+            "name": "isolatedBorrowTokenIndexes",
+            "docs": [
+              "Tokens that isolated collateral may back, unused entries are TokenIndex::MAX"
+            ],
+            "type": {
+              "array": [
+                "u16",
+                7
+              ]
+            }
           },
           {
-            "name": "depositLimitQuote",
+            "name": "isolatedDebtCeilingQuote",
+            "docs": [
+              "Maximal health-weighted value of borrows, in native quote, that isolated",
+              "collateral of this token may back across all accounts, see isolated_debt_quote.",
+              "",
+              "Combine with deposit_limit to bound the total exposure to the token."
+            ],
             "type": "u64"
           },
           {
-            "name": "ixGate",
-            "type": "u128"
-          },
-          {
-            "name": "buybackFeesSwapMangoAccount",
+            "name": "emodeCategory",
+            "docs": [
+              "E-mode category of the token, zero means none.",
+              "",
+              "The category's boosted weights are copied from the group, see EmodeCategory."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "padding4",
+            "type": {
+              "array": [
+                "u8",
+                3
+              ]
+            }
+          },
+          {
+            "name": "emodeMaintAssetWeight",
+            "type": "f32"
+          },
+          {
+            "name": "emodeInitAssetWeight",
+            "type": "f32"
+          },
+          {
+            "name": "emodeMaintLiabWeight",
+            "type": "f32"
+          },
+          {
+            "name": "emodeInitLiabWeight",
+            "type": "f32"
+          },
+          {
+            "name": "padding5",
+            "type": {
+              "array": [
+                "u8",
+                4
+              ]
+            }
+          },
+          {
+            "name": "fixedTermBorrowsNative",
+            "docs": [
+              "Amount owed at maturity to fixed term markets of this token.",
+              "",
+              "Counts toward borrow_limit like regular borrows."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "isolatedDebtQuote",
+            "docs": [
+              "Health-weighted value of borrows, in native quote, backed by isolated collateral",
+              "of this token, summed over all accounts.",
+              "",
+              "Each account's share is recorded in its token position and refreshed whenever",
+              "a borrow or withdraw checks its health, see update_isolated_debt(). Can't grow",
+              "beyond isolated_debt_ceiling_quote."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                1784
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "clmmTwapOracle",
+      "docs": [
+        "A time-weighted average price derived from a raydium CLMM pool's observations.",
+        "",
+        "The pool's sqrt_price can be moved freely within a transaction, so the instantaneous",
+        "CLMM oracles are easy to manipulate. This oracle instead averages the pool's recorded",
+        "tick accumulator over `twap_window_seconds`. It is refreshed permissionlessly with",
+        "clmm_twap_oracle_update and then reads like the CLMM oracles: `price` is multiplied by",
+        "the usdc or sol oracle price of `quote_mint`.",
+        "",
+        "Orca whirlpools don't record an observation history and can't back this oracle."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "group",
+            "type": "publicKey"
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "pool",
+            "type": "publicKey"
+          },
+          {
+            "name": "observation",
+            "type": "publicKey"
+          },
+          {
+            "name": "quoteMint",
+            "docs": [
+              "The pool token whose usdc or sol oracle converts `price` to usd"
+            ],
+            "type": "publicKey"
+          },
+          {
+            "name": "price",
+            "docs": [
+              "Price of one native `mint` token in native `quote_mint` tokens"
+            ],
+            "type": {
+              "defined": "I80F48"
+            }
+          },
+          {
+            "name": "twapWindowSeconds",
+            "type": "u64"
+          },
+          {
+            "name": "observedWindowSeconds",
+            "docs": [
+              "Length of the window used for the last update, at least twap_window_seconds"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lastUpdateSlot",
+            "type": "u64"
+          },
+          {
+            "name": "lastUpdateTs",
+            "type": "u64"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                144
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "fixedTermMarket",
+      "docs": [
+        "A market for lending or borrowing one token at a fixed rate until a fixed maturity.",
+        "",
+        "Lenders move deposits out of their token position into the market, borrowers take",
+        "the lent tokens into their token position. Both sides lock in the amount that is",
+        "owed at maturity when they enter, so later rate changes only affect new loans.",
+        "",
+        "After maturity, anyone can settle the positions back into the regular token positions.",
+        "Lender interest is funded by borrower interest only, see lend_interest_at_maturity().",
+        "The difference between borrower and lender interest stays in the bank vault."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "group",
+            "type": "publicKey"
+          },
+          {
+            "name": "tokenIndex",
+            "type": "u16"
+          },
+          {
+            "name": "marketIndex",
+            "type": "u16"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "reduceOnly",
+            "type": "u8"
+          },
+          {
+            "name": "padding1",
+            "type": {
+              "array": [
+                "u8",
+                2
+              ]
+            }
+          },
+          {
+            "name": "name",
+            "type": {
+              "array": [
+                "u8",
+                16
+              ]
+            }
+          },
+          {
+            "name": "maturityTimestamp",
+            "docs": [
+              "Timestamp after which positions can be settled"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lendRate",
+            "docs": [
+              "Annualized simple interest rate earned by lenders"
+            ],
+            "type": {
+              "defined": "I80F48"
+            }
+          },
+          {
+            "name": "borrowRate",
+            "docs": [
+              "Annualized simple interest rate paid by borrowers, always >= lend_rate"
+            ],
+            "type": {
+              "defined": "I80F48"
+            }
+          },
+          {
+            "name": "maxLentNative",
+            "docs": [
+              "Limit on total_lent_native, to bound how much the bank may owe to lenders"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "totalLentNative",
+            "docs": [
+              "Outstanding principal lent into the market"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "totalBorrowedNative",
+            "docs": [
+              "Outstanding principal borrowed from the market, always <= total_lent_native"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "totalLentAtMaturityNative",
+            "docs": [
+              "Outstanding amount owed to lenders at maturity"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "totalBorrowedAtMaturityNative",
+            "docs": [
+              "Outstanding amount owed by borrowers at maturity"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "registrationTime",
+            "type": "u64"
+          },
+          {
+            "name": "totalLendInterestNative",
+            "docs": [
+              "Interest promised to all lenders, never decreases"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "totalBorrowInterestNative",
+            "docs": [
+              "Interest owed by all borrowers, never decreases"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                496
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "group",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "creator",
+            "type": "publicKey"
+          },
+          {
+            "name": "groupNum",
+            "type": "u32"
+          },
+          {
+            "name": "admin",
+            "type": "publicKey"
+          },
+          {
+            "name": "fastListingAdmin",
+            "type": "publicKey"
+          },
+          {
+            "name": "mngoTokenIndex",
+            "type": "u16"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                2
+              ]
+            }
+          },
+          {
+            "name": "insuranceVault",
+            "type": "publicKey"
+          },
+          {
+            "name": "insuranceMint",
+            "type": "publicKey"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "testing",
+            "type": "u8"
+          },
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "buybackFees",
+            "type": "u8"
+          },
+          {
+            "name": "buybackFeesMngoBonusFactor",
+            "type": "f32"
+          },
+          {
+            "name": "addressLookupTables",
+            "type": {
+              "array": [
+                "publicKey",
+                20
+              ]
+            }
+          },
+          {
+            "name": "securityAdmin",
+            "type": "publicKey"
+          },
+          {
+            "name": "depositLimitQuote",
+            "type": "u64"
+          },
+          {
+            "name": "ixGate",
+            "type": "u128"
+          },
+          {
+            "name": "buybackFeesSwapMangoAccount",
             "type": "publicKey"
           },
           {
@@ -22566,12 +29855,55 @@ export const IDL: MangoV4 = {
             ],
             "type": "u64"
           },
+          {
+            "name": "emodeCategories",
+            "docs": [
+              "E-mode categories, the category with id i is stored at index i - 1"
+            ],
+            "type": {
+              "array": [
+                {
+                  "defined": "EmodeCategory"
+                },
+                4
+              ]
+            }
+          },
+          {
+            "name": "perpFeeTiers",
+            "docs": [
+              "Perp fee tiers selected by trailing account volume, the tier with id i is",
+              "stored at index i - 1"
+            ],
+            "type": {
+              "array": [
+                {
+                  "defined": "PerpFeeTier"
+                },
+                4
+              ]
+            }
+          },
+          {
+            "name": "portfolioMarginPairs",
+            "docs": [
+              "Tokens and perp markets with the same underlying, for portfolio margin accounts"
+            ],
+            "type": {
+              "array": [
+                {
+                  "defined": "PortfolioMarginPair"
+                },
+                16
+              ]
+            }
+          },
           {
             "name": "reserved",
             "type": {
               "array": [
                 "u8",
-                1800
+                1160
               ]
             }
           }
@@ -22703,12 +30035,83 @@ export const IDL: MangoV4 = {
             ],
             "type": "u64"
           },
+          {
+            "name": "nextPerpScheduledOrderId",
+            "docs": [
+              "Next id to use when adding a perp scheduled order"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "portfolioMargin",
+            "docs": [
+              "Opt-in portfolio margining: spot balances and perp positions on the same",
+              "underlying (see PerpMarket::portfolio_margin_token_index) are netted for health."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "emodeCategory",
+            "docs": [
+              "Opt-in e-mode category, zero means none, see Group::emode_categories"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "padding12",
+            "type": {
+              "array": [
+                "u8",
+                6
+              ]
+            }
+          },
+          {
+            "name": "delegateScope",
+            "docs": [
+              "Restrictions on what the delegate may do, see DelegateScope"
+            ],
+            "type": {
+              "defined": "DelegateScope"
+            }
+          },
+          {
+            "name": "temporaryDelegateScope",
+            "docs": [
+              "Restrictions on what the temporary delegate may do"
+            ],
+            "type": {
+              "defined": "DelegateScope"
+            }
+          },
+          {
+            "name": "perpFeeVolumeCurrent",
+            "docs": [
+              "Perp volume (quote native) in the current fee tier volume window.",
+              "Used for selecting the account's perp fee tier, see Group::perp_fee_tiers."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "perpFeeVolumePrevious",
+            "docs": [
+              "Perp volume (quote native) in the previous fee tier volume window."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "perpFeeVolumeWindowEnd",
+            "docs": [
+              "End timestamp of the current fee tier volume window."
+            ],
+            "type": "u64"
+          },
           {
             "name": "reserved",
             "type": {
               "array": [
                 "u8",
-                152
+                80
               ]
             }
           },
@@ -22785,6 +30188,42 @@ export const IDL: MangoV4 = {
               }
             }
           },
+          {
+            "name": "padding9",
+            "type": "u32"
+          },
+          {
+            "name": "openbookV2",
+            "type": {
+              "vec": {
+                "defined": "OpenbookV2Orders"
+              }
+            }
+          },
+          {
+            "name": "padding10",
+            "type": "u32"
+          },
+          {
+            "name": "fixedTermPositions",
+            "type": {
+              "vec": {
+                "defined": "FixedTermPosition"
+              }
+            }
+          },
+          {
+            "name": "padding11",
+            "type": "u32"
+          },
+          {
+            "name": "perpScheduledOrders",
+            "type": {
+              "vec": {
+                "defined": "PerpScheduledOrder"
+              }
+            }
+          },
           {
             "name": "reservedDynamic",
             "type": {
@@ -22979,7 +30418,16 @@ export const IDL: MangoV4 = {
       }
     },
     {
-      "name": "stubOracle",
+      "name": "openbookV2MidOracle",
+      "docs": [
+        "The mid price of an openbook v2 market's best bid and ask.",
+        "",
+        "The book is spread over the market, bids and asks accounts, so it can't be read from a",
+        "single oracle account. This oracle caches the mid price instead and is refreshed",
+        "permissionlessly with openbook_v2_mid_oracle_update. It reads like the CLMM oracles:",
+        "`price` is multiplied by the usdc or sol oracle price of `quote_mint`. Half the spread",
+        "is reported as the deviation, so the bank's conf_filter bounds the accepted spread."
+      ],
       "type": {
         "kind": "struct",
         "fields": [
@@ -22991,32 +30439,57 @@ export const IDL: MangoV4 = {
             "name": "mint",
             "type": "publicKey"
           },
+          {
+            "name": "market",
+            "type": "publicKey"
+          },
+          {
+            "name": "bids",
+            "type": "publicKey"
+          },
+          {
+            "name": "asks",
+            "type": "publicKey"
+          },
+          {
+            "name": "quoteMint",
+            "docs": [
+              "The market's quote token, whose usdc or sol oracle converts `price` to usd"
+            ],
+            "type": "publicKey"
+          },
           {
             "name": "price",
+            "docs": [
+              "Mid price of one native `mint` token in native `quote_mint` tokens"
+            ],
             "type": {
               "defined": "I80F48"
             }
           },
           {
-            "name": "lastUpdateTs",
-            "type": "i64"
+            "name": "deviation",
+            "docs": [
+              "Half the bid-ask spread, in the same unit as `price`"
+            ],
+            "type": {
+              "defined": "I80F48"
+            }
           },
           {
             "name": "lastUpdateSlot",
             "type": "u64"
           },
           {
-            "name": "deviation",
-            "type": {
-              "defined": "I80F48"
-            }
+            "name": "lastUpdateTs",
+            "type": "u64"
           },
           {
             "name": "reserved",
             "type": {
               "array": [
                 "u8",
-                104
+                128
               ]
             }
           }
@@ -23024,48 +30497,214 @@ export const IDL: MangoV4 = {
       }
     },
     {
-      "name": "bookSide",
+      "name": "oracleAggregator",
+      "docs": [
+        "An oracle whose price is the median of several source oracles.",
+        "",
+        "Banks and perp markets use it like any other oracle by setting it as their `oracle`.",
+        "The price is refreshed permissionlessly with oracle_aggregator_update, which reads",
+        "all source oracles, drops the stale ones and the ones that diverge too much from the",
+        "median, and only publishes a price if at least `min_quorum` sources remain."
+      ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "roots",
-            "type": {
-              "array": [
-                {
-                  "defined": "OrderTreeRoot"
-                },
-                2
-              ]
-            }
+            "name": "group",
+            "type": "publicKey"
           },
           {
-            "name": "reservedRoots",
-            "type": {
-              "array": [
-                {
-                  "defined": "OrderTreeRoot"
-                },
-                4
-              ]
-            }
+            "name": "mint",
+            "type": "publicKey"
           },
           {
-            "name": "reserved",
+            "name": "price",
+            "docs": [
+              "Price of one native base token in native quote tokens, for `mint_decimals`"
+            ],
             "type": {
-              "array": [
-                "u8",
-                256
-              ]
+              "defined": "I80F48"
             }
           },
           {
-            "name": "nodes",
+            "name": "deviation",
+            "docs": [
+              "Largest difference between the median and a source that was used"
+            ],
             "type": {
-              "defined": "OrderTreeNodes"
+              "defined": "I80F48"
             }
-          }
-        ]
+          },
+          {
+            "name": "lastUpdateSlot",
+            "docs": [
+              "Oldest update slot of the sources that were used"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lastUpdateTs",
+            "type": "u64"
+          },
+          {
+            "name": "oldestSourcePublishTs",
+            "docs": [
+              "Oldest publish time of the sources that were used and report one, 0 if none do"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "sourceOracleConfig",
+            "docs": [
+              "Confidence and staleness limits that each source must pass to be used"
+            ],
+            "type": {
+              "defined": "OracleConfig"
+            }
+          },
+          {
+            "name": "maxDivergence",
+            "docs": [
+              "Sources further than this fraction from the median are ignored"
+            ],
+            "type": "f64"
+          },
+          {
+            "name": "minQuorum",
+            "docs": [
+              "Minimum number of fresh, agreeing sources needed to publish a price"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "numOracles",
+            "type": "u8"
+          },
+          {
+            "name": "mintDecimals",
+            "type": "u8"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                5
+              ]
+            }
+          },
+          {
+            "name": "oracles",
+            "docs": [
+              "Source oracles, only the first num_oracles are used"
+            ],
+            "type": {
+              "array": [
+                "publicKey",
+                8
+              ]
+            }
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                120
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "stubOracle",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "group",
+            "type": "publicKey"
+          },
+          {
+            "name": "mint",
+            "type": "publicKey"
+          },
+          {
+            "name": "price",
+            "type": {
+              "defined": "I80F48"
+            }
+          },
+          {
+            "name": "lastUpdateTs",
+            "type": "i64"
+          },
+          {
+            "name": "lastUpdateSlot",
+            "type": "u64"
+          },
+          {
+            "name": "deviation",
+            "type": {
+              "defined": "I80F48"
+            }
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                104
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "bookSide",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "roots",
+            "type": {
+              "array": [
+                {
+                  "defined": "OrderTreeRoot"
+                },
+                2
+              ]
+            }
+          },
+          {
+            "name": "reservedRoots",
+            "type": {
+              "array": [
+                {
+                  "defined": "OrderTreeRoot"
+                },
+                4
+              ]
+            }
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                256
+              ]
+            }
+          },
+          {
+            "name": "nodes",
+            "type": {
+              "defined": "OrderTreeNodes"
+            }
+          }
+        ]
       }
     },
     {
@@ -23523,12 +31162,84 @@ export const IDL: MangoV4 = {
               "defined": "I80F48"
             }
           },
+          {
+            "name": "portfolioMarginTokenIndex",
+            "docs": [
+              "Token with the same underlying as this market's base, copied from",
+              "Group::portfolio_margin_pairs. Only used if portfolio_margin_active is 1."
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "portfolioMarginActive",
+            "type": "u8"
+          },
+          {
+            "name": "padding5",
+            "type": {
+              "array": [
+                "u8",
+                5
+              ]
+            }
+          },
+          {
+            "name": "fundingStablePriceWeight",
+            "docs": [
+              "Weight of the stable price in the reference price that funding is computed",
+              "against. Zero uses the oracle price only, one uses the stable price only."
+            ],
+            "type": "f32"
+          },
+          {
+            "name": "settleStablePriceWeight",
+            "docs": [
+              "Weight of the stable price in the price that pnl is settled at, see",
+              "funding_stable_price_weight. The settle limit itself is always based on the",
+              "stable price."
+            ],
+            "type": "f32"
+          },
+          {
+            "name": "openingAuctionDuration",
+            "docs": [
+              "Length in seconds of the opening auction that starts when the market is created",
+              "or leaves reduce-only mode. Zero disables opening auctions."
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "padding6",
+            "type": {
+              "array": [
+                "u8",
+                4
+              ]
+            }
+          },
+          {
+            "name": "openingAuctionEndTs",
+            "docs": [
+              "While non-zero, the market is in an opening auction: orders are posted without",
+              "matching. After this timestamp perp_clear_opening_auction executes the crossing",
+              "orders at a uniform price and resets it to zero."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "openingAuctionPriceLots",
+            "docs": [
+              "Clearing price of the opening auction, zero until the first clearing call",
+              "computed it"
+            ],
+            "type": "i64"
+          },
           {
             "name": "reserved",
             "type": {
               "array": [
                 "u8",
-                1848
+                1808
               ]
             }
           }
@@ -23656,6 +31367,44 @@ export const IDL: MangoV4 = {
     }
   ],
   "types": [
+    {
+      "name": "PerpBatchOrder",
+      "docs": [
+        "One order of a PerpPlaceOrdersBatch instruction"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "side",
+            "type": {
+              "defined": "Side"
+            }
+          },
+          {
+            "name": "priceLots",
+            "docs": [
+              "Price in lots, see perp_place_order"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "maxBaseLots",
+            "type": "i64"
+          },
+          {
+            "name": "orderType",
+            "type": {
+              "defined": "PlaceOrderType"
+            }
+          },
+          {
+            "name": "clientOrderId",
+            "type": "u64"
+          }
+        ]
+      }
+    },
     {
       "name": "InterestRateParams",
       "type": {
@@ -23916,6 +31665,127 @@ export const IDL: MangoV4 = {
         ]
       }
     },
+    {
+      "name": "DelegateScope",
+      "docs": [
+        "Restrictions that apply to a delegate of a MangoAccount.",
+        "",
+        "A scope with permissions == 0 is unrestricted. That's the default for delegates that were",
+        "set up before scopes existed."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "permissions",
+            "docs": [
+              "Bitmask of DelegatePermission values"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "perpMarketIndex",
+            "docs": [
+              "Perp market the delegate may use, PerpMarketIndex::MAX for any"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "spotMarketIndex",
+            "docs": [
+              "Serum3 or openbook v2 market the delegate may use, u16::MAX for any"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                2
+              ]
+            }
+          },
+          {
+            "name": "maxNotionalPerOrder",
+            "docs": [
+              "Maximum size of each order placed by the delegate, in native units of the",
+              "market's quote or settle token. Zero for no limit.",
+              "",
+              "Orders are checked one at a time: this doesn't limit the delegate's total exposure."
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "EmodeCategory",
+      "docs": [
+        "A group of correlated tokens, like SOL and its liquid staking tokens.",
+        "",
+        "Accounts that opted into a category and only have exposure to its tokens use the",
+        "category's boosted weights for health, see HealthCache::emode_credit().",
+        "",
+        "The weights are copied to the banks of the tokens, since health computations only",
+        "have access to banks."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tokenIndexes",
+            "docs": [
+              "Tokens in the category, only the first token_count entries are used"
+            ],
+            "type": {
+              "array": [
+                "u16",
+                8
+              ]
+            }
+          },
+          {
+            "name": "tokenCount",
+            "type": "u8"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                3
+              ]
+            }
+          },
+          {
+            "name": "maintAssetWeight",
+            "type": "f32"
+          },
+          {
+            "name": "initAssetWeight",
+            "type": "f32"
+          },
+          {
+            "name": "maintLiabWeight",
+            "type": "f32"
+          },
+          {
+            "name": "initLiabWeight",
+            "type": "f32"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                28
+              ]
+            }
+          }
+        ]
+      }
+    },
     {
       "name": "TokenPosition",
       "type": {
@@ -23967,12 +31837,20 @@ export const IDL: MangoV4 = {
             "name": "cumulativeBorrowInterest",
             "type": "f64"
           },
+          {
+            "name": "isolatedDebtQuote",
+            "docs": [
+              "For isolated collateral tokens: this account's share of Bank::isolated_debt_quote,",
+              "as of the last time it was updated, see update_isolated_debt()."
+            ],
+            "type": "u64"
+          },
           {
             "name": "reserved",
             "type": {
               "array": [
                 "u8",
-                128
+                120
               ]
             }
           }
@@ -24089,6 +31967,189 @@ export const IDL: MangoV4 = {
         ]
       }
     },
+    {
+      "name": "OpenbookV2Orders",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "openOrders",
+            "type": "publicKey"
+          },
+          {
+            "name": "baseBorrowsWithoutFee",
+            "docs": [
+              "Tracks the amount of borrows that have flowed into the open orders account.",
+              "These borrows did not have the loan origination fee applied, and that may happen",
+              "later (in openbook_v2_settle_funds) if we can guarantee that the funds were used.",
+              "In particular a place-on-book, cancel, settle should not cost fees."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "quoteBorrowsWithoutFee",
+            "type": "u64"
+          },
+          {
+            "name": "marketIndex",
+            "type": "u16"
+          },
+          {
+            "name": "baseTokenIndex",
+            "docs": [
+              "Store the base/quote token index, so health computations don't need",
+              "to get passed the static OpenbookV2Market to find which tokens a market",
+              "uses and look up the correct oracles."
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "quoteTokenIndex",
+            "type": "u16"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                2
+              ]
+            }
+          },
+          {
+            "name": "highestPlacedBidInv",
+            "docs": [
+              "Track the highest/lowest placed bid and ask, in native/native units.",
+              "",
+              "Works like the same fields on Serum3Orders. 0 is a special \"unset\" state."
+            ],
+            "type": "f64"
+          },
+          {
+            "name": "lowestPlacedBidInv",
+            "type": "f64"
+          },
+          {
+            "name": "lowestPlacedAsk",
+            "type": "f64"
+          },
+          {
+            "name": "highestPlacedAsk",
+            "type": "f64"
+          },
+          {
+            "name": "potentialBaseTokens",
+            "docs": [
+              "An overestimate of the amount of tokens that might flow out of the open orders account.",
+              "",
+              "The bank still considers these amounts user deposits (see Bank::potential_serum_tokens)",
+              "and that value needs to be updated in conjunction with these numbers."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "potentialQuoteTokens",
+            "type": "u64"
+          },
+          {
+            "name": "baseLotSize",
+            "docs": [
+              "Lot sizes of the openbook v2 market, copied on creation.",
+              "",
+              "The open orders account tracks reserved amounts in lots, so health needs these",
+              "to compute native amounts without being passed the market."
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "quoteLotSize",
+            "type": "i64"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                96
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "FixedTermPosition",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "marketIndex",
+            "type": "u16"
+          },
+          {
+            "name": "tokenIndex",
+            "docs": [
+              "Store the token index, so health computations don't need to get passed",
+              "the FixedTermMarket to find which token a position is in."
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                4
+              ]
+            }
+          },
+          {
+            "name": "maturityTimestamp",
+            "docs": [
+              "Copied from the market, the position can be settled after this time"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lentNative",
+            "docs": [
+              "Principal lent into the market"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "lentAtMaturityNative",
+            "docs": [
+              "Amount that will be credited to the token position at maturity"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "borrowedNative",
+            "docs": [
+              "Principal borrowed from the market"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "borrowedAtMaturityNative",
+            "docs": [
+              "Amount that will be debited from the token position at maturity"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                64
+              ]
+            }
+          }
+        ]
+      }
+    },
     {
       "name": "PerpPosition",
       "type": {
@@ -24320,12 +32381,41 @@ export const IDL: MangoV4 = {
               "defined": "I80F48"
             }
           },
+          {
+            "name": "isolated",
+            "docs": [
+              "Whether the position is isolated: it may only use isolated_collateral_native as",
+              "collateral and doesn't contribute to the health of the rest of the account."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "padding2",
+            "type": {
+              "array": [
+                "u8",
+                7
+              ]
+            }
+          },
+          {
+            "name": "isolatedCollateralNative",
+            "docs": [
+              "Settle token native allocated to an isolated position.",
+              "",
+              "This is a part of the account's settle token balance that only backs this position.",
+              "Settling pnl of the position moves funds in and out of it."
+            ],
+            "type": {
+              "defined": "I80F48"
+            }
+          },
           {
             "name": "reserved",
             "type": {
               "array": [
                 "u8",
-                88
+                64
               ]
             }
           }
@@ -24477,12 +32567,57 @@ export const IDL: MangoV4 = {
             "name": "lastCollateralFeeCharge",
             "type": "u64"
           },
+          {
+            "name": "nextPerpScheduledOrderId",
+            "type": "u64"
+          },
+          {
+            "name": "portfolioMargin",
+            "type": "u8"
+          },
+          {
+            "name": "emodeCategory",
+            "type": "u8"
+          },
+          {
+            "name": "padding12",
+            "type": {
+              "array": [
+                "u8",
+                6
+              ]
+            }
+          },
+          {
+            "name": "delegateScope",
+            "type": {
+              "defined": "DelegateScope"
+            }
+          },
+          {
+            "name": "temporaryDelegateScope",
+            "type": {
+              "defined": "DelegateScope"
+            }
+          },
+          {
+            "name": "perpFeeVolumeCurrent",
+            "type": "u64"
+          },
+          {
+            "name": "perpFeeVolumePrevious",
+            "type": "u64"
+          },
+          {
+            "name": "perpFeeVolumeWindowEnd",
+            "type": "u64"
+          },
           {
             "name": "reserved",
             "type": {
               "array": [
                 "u8",
-                152
+                80
               ]
             }
           }
@@ -24502,6 +32637,30 @@ export const IDL: MangoV4 = {
           },
           {
             "name": "maxStalenessSlots",
+            "docs": [
+              "Negative values disable the staleness check"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "maxStalenessSeconds",
+            "docs": [
+              "Maximum age of the oracle's publish time, for oracles that report one.",
+              "",
+              "Zero means the limit is derived from max_staleness_slots, assuming 450ms per slot."
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "riskIncreasingMaxStalenessSlots",
+            "docs": [
+              "Stricter limits applied on top of the ones above for risk-increasing actions,",
+              "like borrowing or placing orders. Zero means no additional limit."
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "riskIncreasingMaxStalenessSeconds",
             "type": "i64"
           },
           {
@@ -24509,7 +32668,7 @@ export const IDL: MangoV4 = {
             "type": {
               "array": [
                 "u8",
-                72
+                48
               ]
             }
           }
@@ -24530,6 +32689,82 @@ export const IDL: MangoV4 = {
             "type": {
               "option": "u32"
             }
+          },
+          {
+            "name": "maxStalenessSeconds",
+            "type": {
+              "option": "u32"
+            }
+          },
+          {
+            "name": "riskIncreasingMaxStalenessSlots",
+            "type": {
+              "option": "u32"
+            }
+          },
+          {
+            "name": "riskIncreasingMaxStalenessSeconds",
+            "type": {
+              "option": "u32"
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "OrderTreeNodes",
+      "docs": [
+        "A binary tree on AnyNode::key()",
+        "",
+        "The key encodes the price in the top 64 bits."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "orderTreeType",
+            "type": "u8"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                3
+              ]
+            }
+          },
+          {
+            "name": "bumpIndex",
+            "type": "u32"
+          },
+          {
+            "name": "freeListLen",
+            "type": "u32"
+          },
+          {
+            "name": "freeListHead",
+            "type": "u32"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                512
+              ]
+            }
+          },
+          {
+            "name": "nodes",
+            "type": {
+              "array": [
+                {
+                  "defined": "AnyNode"
+                },
+                1024
+              ]
+            }
           }
         ]
       }
@@ -24643,13 +32878,12 @@ export const IDL: MangoV4 = {
             "type": "u8"
           },
           {
-            "name": "padding",
-            "type": {
-              "array": [
-                "u8",
-                1
-              ]
-            }
+            "name": "feeTier",
+            "docs": [
+              "Perp fee tier of the owner at the time the order was placed, see",
+              "Group::perp_fee_tiers. Determines the maker fee when the order is filled."
+            ],
+            "type": "u8"
           },
           {
             "name": "timeInForce",
@@ -24766,64 +33000,6 @@ export const IDL: MangoV4 = {
         ]
       }
     },
-    {
-      "name": "OrderTreeNodes",
-      "docs": [
-        "A binary tree on AnyNode::key()",
-        "",
-        "The key encodes the price in the top 64 bits."
-      ],
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "orderTreeType",
-            "type": "u8"
-          },
-          {
-            "name": "padding",
-            "type": {
-              "array": [
-                "u8",
-                3
-              ]
-            }
-          },
-          {
-            "name": "bumpIndex",
-            "type": "u32"
-          },
-          {
-            "name": "freeListLen",
-            "type": "u32"
-          },
-          {
-            "name": "freeListHead",
-            "type": "u32"
-          },
-          {
-            "name": "reserved",
-            "type": {
-              "array": [
-                "u8",
-                512
-              ]
-            }
-          },
-          {
-            "name": "nodes",
-            "type": {
-              "array": [
-                {
-                  "defined": "AnyNode"
-                },
-                1024
-              ]
-            }
-          }
-        ]
-      }
-    },
     {
       "name": "EventQueueHeader",
       "type": {
@@ -24925,13 +33101,8 @@ export const IDL: MangoV4 = {
             "type": "publicKey"
           },
           {
-            "name": "padding3",
-            "type": {
-              "array": [
-                "u8",
-                16
-              ]
-            }
+            "name": "takerOrderId",
+            "type": "u128"
           },
           {
             "name": "takerClientOrderId",
@@ -24961,12 +33132,32 @@ export const IDL: MangoV4 = {
             "name": "takerFee",
             "type": "f32"
           },
+          {
+            "name": "makerFeeTier",
+            "type": "u8"
+          },
+          {
+            "name": "takerFeeTier",
+            "type": "u8"
+          },
+          {
+            "name": "auction",
+            "type": "u8"
+          },
+          {
+            "name": "takerSlot",
+            "type": "u8"
+          },
+          {
+            "name": "takerOut",
+            "type": "u8"
+          },
           {
             "name": "reserved",
             "type": {
               "array": [
                 "u8",
-                8
+                3
               ]
             }
           }
@@ -25031,6 +33222,245 @@ export const IDL: MangoV4 = {
         ]
       }
     },
+    {
+      "name": "PerpFeeTier",
+      "docs": [
+        "A perp fee tier, selected by an account's trailing perp volume.",
+        "",
+        "The discounts are subtracted from a market's maker_fee and taker_fee. Taker fees",
+        "never become negative, maker fees may turn into (bigger) rebates."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "minVolumeQuote",
+            "docs": [
+              "Trailing perp volume (in quote native) required for this tier.",
+              "A value of 0 means the tier is unused."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "makerFeeDiscount",
+            "type": "f32"
+          },
+          {
+            "name": "takerFeeDiscount",
+            "type": "f32"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                16
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "PerpScheduledOrder",
+      "docs": [
+        "A large perp order that gets split into child orders over time, or a",
+        "conditional order that reduces a perp position once the oracle crosses a threshold.",
+        "",
+        "Child orders are placed by permissionless callers of the perp_scheduled_order_trigger",
+        "instruction, who receive PERP_SCHEDULED_ORDER_INCENTIVE in the market's settle token",
+        "for each trigger that fills or places a non-zero amount."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "id",
+            "type": "u64"
+          },
+          {
+            "name": "maxBaseLots",
+            "docs": [
+              "Total base lots to execute over all child orders"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "executedBaseLots",
+            "docs": [
+              "Base lots that were already filled (Twap) or placed on the book (Iceberg)"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "childBaseLots",
+            "docs": [
+              "Maximum base lots per child order"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "priceLots",
+            "docs": [
+              "Limit price for all child orders, in lots (unused for conditional orders)"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "intervalSeconds",
+            "docs": [
+              "Minimum time between two child orders"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "nextTriggerTimestamp",
+            "docs": [
+              "Child orders can be triggered at or after this timestamp"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "expiryTimestamp",
+            "docs": [
+              "Timestamp until which the scheduled order is valid"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "marketIndex",
+            "type": "u16"
+          },
+          {
+            "name": "isConfigured",
+            "docs": [
+              "If this struct is in use. (scheduled orders are stored in a static-length array)"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "side",
+            "docs": [
+              "Stores a Side enum value"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "orderType",
+            "docs": [
+              "Stores a PerpScheduledOrderType enum value"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "reduceOnly",
+            "type": "u8"
+          },
+          {
+            "name": "hasChildOrder",
+            "docs": [
+              "Whether an iceberg child order may be resting on the book, see child_order_seq_num"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                1
+              ]
+            }
+          },
+          {
+            "name": "triggerPriceLots",
+            "docs": [
+              "Oracle price at which conditional orders become triggerable, in lots"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "maxSlippage",
+            "docs": [
+              "Worst acceptable execution price of conditional orders, relative to the",
+              "oracle price at trigger time. 0.01 means 1%."
+            ],
+            "type": "f32"
+          },
+          {
+            "name": "padding2",
+            "type": {
+              "array": [
+                "u8",
+                4
+              ]
+            }
+          },
+          {
+            "name": "childOrderSeqNum",
+            "docs": [
+              "Sequence number of the last iceberg child order placed on the book.",
+              "",
+              "Order ids are unique per market by their sequence number, see node_key_seq_num(),",
+              "so the child is found by it instead of by its client order id, which the user",
+              "may be using for other orders."
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                40
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "PortfolioMarginPair",
+      "docs": [
+        "Links a token to a perp market with the same underlying, like SOL and SOL-PERP.",
+        "",
+        "For accounts that opted into portfolio margining, spot balances in the token offset",
+        "opposite perp base positions before weights are applied, see",
+        "HealthCache::portfolio_margin_hedges().",
+        "",
+        "The token index is copied to the perp market, since health computations only have",
+        "access to perp markets."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "tokenIndex",
+            "type": "u16"
+          },
+          {
+            "name": "perpMarketIndex",
+            "type": "u16"
+          },
+          {
+            "name": "active",
+            "docs": [
+              "1 if the pair is in use. Zeroed entries would otherwise link token 0 and perp 0."
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "reserved",
+            "type": {
+              "array": [
+                "u8",
+                11
+              ]
+            }
+          }
+        ]
+      }
+    },
     {
       "name": "StablePriceModel",
       "docs": [
@@ -25552,6 +33982,67 @@ export const IDL: MangoV4 = {
           },
           {
             "name": "TokenConditionalSwapTrigger"
+          },
+          {
+            "name": "OpenbookV2SettleFunds"
+          }
+        ]
+      }
+    },
+    {
+      "name": "InterestRateModel",
+      "docs": [
+        "How a bank adjusts its interest rate curve over time"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "CurveScaling"
+          },
+          {
+            "name": "Controller"
+          }
+        ]
+      }
+    },
+    {
+      "name": "DelegatePermission",
+      "docs": [
+        "Actions a scoped delegate may be allowed to take, stored as bits in DelegateScope::permissions."
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "PerpTrade"
+          },
+          {
+            "name": "PerpCancel"
+          },
+          {
+            "name": "SpotTrade"
+          },
+          {
+            "name": "SpotCancel"
+          },
+          {
+            "name": "TokenConditionalSwap"
+          },
+          {
+            "name": "Swap"
+          },
+          {
+            "name": "FixedTerm"
+          },
+          {
+            "name": "TokenTransfer"
+          },
+          {
+            "name": "Liquidate"
+          },
+          {
+            "name": "Withdraw"
           }
         ]
       }
@@ -25803,6 +34294,78 @@ export const IDL: MangoV4 = {
           },
           {
             "name": "GroupChangeInsuranceFund"
+          },
+          {
+            "name": "FixedTermMarketCreate"
+          },
+          {
+            "name": "FixedTermLend"
+          },
+          {
+            "name": "FixedTermBorrow"
+          },
+          {
+            "name": "FixedTermSettle"
+          },
+          {
+            "name": "PerpScheduledOrderCreate"
+          },
+          {
+            "name": "PerpScheduledOrderCancel"
+          },
+          {
+            "name": "PerpScheduledOrderTrigger"
+          },
+          {
+            "name": "PerpConditionalOrderCreate"
+          },
+          {
+            "name": "PerpPlaceSpreadOrder"
+          },
+          {
+            "name": "AccountSetPortfolioMargin"
+          },
+          {
+            "name": "PerpSetIsolated"
+          },
+          {
+            "name": "PerpIsolatedTransfer"
+          },
+          {
+            "name": "TokenTransfer"
+          },
+          {
+            "name": "AccountEditDelegateScope"
+          },
+          {
+            "name": "OracleAggregatorCreate"
+          },
+          {
+            "name": "OracleAggregatorEdit"
+          },
+          {
+            "name": "OracleAggregatorUpdate"
+          },
+          {
+            "name": "ClmmTwapOracleCreate"
+          },
+          {
+            "name": "ClmmTwapOracleUpdate"
+          },
+          {
+            "name": "OpenbookV2MidOracleCreate"
+          },
+          {
+            "name": "OpenbookV2MidOracleUpdate"
+          },
+          {
+            "name": "AccountSetEmodeCategory"
+          },
+          {
+            "name": "PerpResizeOrderbook"
+          },
+          {
+            "name": "PerpClearOpeningAuction"
           }
         ]
       }
@@ -25824,6 +34387,23 @@ export const IDL: MangoV4 = {
         ]
       }
     },
+    {
+      "name": "OracleFreshness",
+      "docs": [
+        "Which staleness limits of an OracleConfig apply to an oracle read"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Health"
+          },
+          {
+            "name": "RiskIncreasing"
+          }
+        ]
+      }
+    },
     {
       "name": "OracleType",
       "type": {
@@ -25852,6 +34432,18 @@ export const IDL: MangoV4 = {
           },
           {
             "name": "PythV2"
+          },
+          {
+            "name": "Aggregated"
+          },
+          {
+            "name": "ClmmTwap"
+          },
+          {
+            "name": "MeteoraDLMM"
+          },
+          {
+            "name": "OpenbookV2Mid"
           }
         ]
       }
@@ -26098,6 +34690,26 @@ export const IDL: MangoV4 = {
         ]
       }
     },
+    {
+      "name": "PerpScheduledOrderType",
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "Twap"
+          },
+          {
+            "name": "Iceberg"
+          },
+          {
+            "name": "StopLoss"
+          },
+          {
+            "name": "TakeProfit"
+          }
+        ]
+      }
+    },
     {
       "name": "TokenConditionalSwapDisplayPriceStyle",
       "type": {
@@ -26152,30 +34764,855 @@ export const IDL: MangoV4 = {
       "name": "MangoAccountData",
       "fields": [
         {
-          "name": "initHealth",
-          "type": {
-            "defined": "I80F48"
-          },
+          "name": "initHealth",
+          "type": {
+            "defined": "I80F48"
+          },
+          "index": false
+        },
+        {
+          "name": "maintHealth",
+          "type": {
+            "defined": "I80F48"
+          },
+          "index": false
+        },
+        {
+          "name": "equity",
+          "type": {
+            "defined": "Equity"
+          },
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PerpBalanceLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mangoAccount",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "basePosition",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "quotePosition",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "longSettledFunding",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "shortSettledFunding",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "longFunding",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "shortFunding",
+          "type": "i128",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "TokenBalanceLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mangoAccount",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "tokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "indexedPosition",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "depositIndex",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "borrowIndex",
+          "type": "i128",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "FlashLoanLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mangoAccount",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "tokenLoanDetails",
+          "type": {
+            "vec": {
+              "defined": "FlashLoanTokenDetail"
+            }
+          },
+          "index": false
+        },
+        {
+          "name": "flashLoanType",
+          "type": {
+            "defined": "FlashLoanType"
+          },
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "FlashLoanLogV2",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mangoAccount",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "tokenLoanDetails",
+          "type": {
+            "vec": {
+              "defined": "FlashLoanTokenDetailV2"
+            }
+          },
+          "index": false
+        },
+        {
+          "name": "flashLoanType",
+          "type": {
+            "defined": "FlashLoanType"
+          },
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "FlashLoanLogV3",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mangoAccount",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "tokenLoanDetails",
+          "type": {
+            "vec": {
+              "defined": "FlashLoanTokenDetailV3"
+            }
+          },
+          "index": false
+        },
+        {
+          "name": "flashLoanType",
+          "type": {
+            "defined": "FlashLoanType"
+          },
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "WithdrawLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mangoAccount",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "signer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "tokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "quantity",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "i128",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "DepositLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "mangoAccount",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "signer",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "tokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "quantity",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "i128",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "FillLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "takerSide",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "makerSlot",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "makerOut",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "seqNum",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "maker",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "makerOrderId",
+          "type": "u128",
+          "index": false
+        },
+        {
+          "name": "makerFee",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "makerTimestamp",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "taker",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "takerOrderId",
+          "type": "u128",
+          "index": false
+        },
+        {
+          "name": "takerClientOrderId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "takerFee",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "quantity",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "FillLogV2",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "takerSide",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "makerSlot",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "makerOut",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "seqNum",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "maker",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "makerClientOrderId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "makerFee",
+          "type": "f32",
+          "index": false
+        },
+        {
+          "name": "makerTimestamp",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "taker",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "takerClientOrderId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "takerFee",
+          "type": "f32",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "quantity",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "FillLogV3",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "takerSide",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "makerSlot",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "makerOut",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "seqNum",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "maker",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "makerClientOrderId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "makerFee",
+          "type": "f32",
+          "index": false
+        },
+        {
+          "name": "makerTimestamp",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "taker",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "takerClientOrderId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "takerFee",
+          "type": "f32",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "quantity",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "makerClosedPnl",
+          "type": "f64",
+          "index": false
+        },
+        {
+          "name": "takerClosedPnl",
+          "type": "f64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "FillLogV4",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "takerSide",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "makerSlot",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "makerOut",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "seqNum",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "maker",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "makerClientOrderId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "makerFee",
+          "type": "f32",
+          "index": false
+        },
+        {
+          "name": "makerFeeTier",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "makerTimestamp",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "taker",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "takerClientOrderId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "takerFee",
+          "type": "f32",
+          "index": false
+        },
+        {
+          "name": "takerFeeTier",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "quantity",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "makerClosedPnl",
+          "type": "f64",
+          "index": false
+        },
+        {
+          "name": "takerClosedPnl",
+          "type": "f64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PerpUpdateFundingLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "longFunding",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "shortFunding",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "oracleSlot",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "stablePrice",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "feesAccrued",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "feesSettled",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "openInterest",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "instantaneousFundingRate",
+          "type": "i128",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PerpUpdateFundingLogV2",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "marketIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "longFunding",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "shortFunding",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "oracleSlot",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "oracleConfidence",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "oracleType",
+          "type": {
+            "defined": "OracleType"
+          },
+          "index": false
+        },
+        {
+          "name": "stablePrice",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "feesAccrued",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "feesSettled",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "openInterest",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "instantaneousFundingRate",
+          "type": "i128",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "UpdateIndexLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "tokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "depositIndex",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "borrowIndex",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "avgUtilization",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "price",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "stablePrice",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "collectedFees",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "loanFeeRate",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "maintHealth",
-          "type": {
-            "defined": "I80F48"
-          },
+          "name": "totalBorrows",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "equity",
-          "type": {
-            "defined": "Equity"
-          },
+          "name": "totalDeposits",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "borrowRate",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "depositRate",
+          "type": "i128",
           "index": false
         }
       ]
     },
     {
-      "name": "PerpBalanceLog",
+      "name": "UpdateRateLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -26183,49 +35620,79 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "mangoAccount",
+          "name": "tokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "rate0",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "rate1",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "maxRate",
+          "type": "i128",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "UpdateRateLogV2",
+      "fields": [
+        {
+          "name": "mangoGroup",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "marketIndex",
+          "name": "tokenIndex",
           "type": "u16",
           "index": false
         },
         {
-          "name": "basePosition",
-          "type": "i64",
+          "name": "rate0",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "quotePosition",
+          "name": "util0",
           "type": "i128",
           "index": false
         },
         {
-          "name": "longSettledFunding",
+          "name": "rate1",
           "type": "i128",
           "index": false
         },
         {
-          "name": "shortSettledFunding",
+          "name": "util1",
           "type": "i128",
           "index": false
         },
         {
-          "name": "longFunding",
+          "name": "maxRate",
           "type": "i128",
           "index": false
         },
         {
-          "name": "shortFunding",
-          "type": "i128",
+          "name": "curveScaling",
+          "type": "f64",
+          "index": false
+        },
+        {
+          "name": "targetUtilization",
+          "type": "f32",
           "index": false
         }
       ]
     },
     {
-      "name": "TokenBalanceLog",
+      "name": "UpdateRateLogV3",
       "fields": [
         {
           "name": "mangoGroup",
@@ -26233,34 +35700,69 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "mangoAccount",
-          "type": "publicKey",
+          "name": "tokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "tokenIndex",
-          "type": "u16",
+          "name": "rate0",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "indexedPosition",
+          "name": "util0",
           "type": "i128",
           "index": false
         },
         {
-          "name": "depositIndex",
+          "name": "rate1",
           "type": "i128",
           "index": false
         },
         {
-          "name": "borrowIndex",
+          "name": "util1",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "maxRate",
           "type": "i128",
           "index": false
+        },
+        {
+          "name": "curveScaling",
+          "type": "f64",
+          "index": false
+        },
+        {
+          "name": "targetUtilization",
+          "type": "f32",
+          "index": false
+        },
+        {
+          "name": "interestRateModel",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "controllerError",
+          "type": "f32",
+          "index": false
+        },
+        {
+          "name": "controllerIntegral",
+          "type": "f64",
+          "index": false
+        },
+        {
+          "name": "controllerDailyChange",
+          "type": "f64",
+          "index": false
         }
       ]
     },
     {
-      "name": "FlashLoanLog",
+      "name": "TokenLiqWithTokenLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -26268,30 +35770,54 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "mangoAccount",
+          "name": "liqee",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "tokenLoanDetails",
-          "type": {
-            "vec": {
-              "defined": "FlashLoanTokenDetail"
-            }
-          },
+          "name": "liqor",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "flashLoanType",
-          "type": {
-            "defined": "FlashLoanType"
-          },
+          "name": "assetTokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "liabTokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "assetTransfer",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "liabTransfer",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "assetPrice",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "liabPrice",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "bankruptcy",
+          "type": "bool",
           "index": false
         }
       ]
     },
     {
-      "name": "FlashLoanLogV2",
+      "name": "TokenLiqWithTokenLogV2",
       "fields": [
         {
           "name": "mangoGroup",
@@ -26299,30 +35825,64 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "mangoAccount",
+          "name": "liqee",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "tokenLoanDetails",
-          "type": {
-            "vec": {
-              "defined": "FlashLoanTokenDetailV2"
-            }
-          },
+          "name": "liqor",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "flashLoanType",
-          "type": {
-            "defined": "FlashLoanType"
-          },
+          "name": "assetTokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "liabTokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "assetTransferFromLiqee",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "assetTransferToLiqor",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "assetLiquidationFee",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "liabTransfer",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "assetPrice",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "liabPrice",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "bankruptcy",
+          "type": "bool",
           "index": false
         }
       ]
     },
     {
-      "name": "FlashLoanLogV3",
+      "name": "Serum3OpenOrdersBalanceLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -26335,25 +35895,44 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "tokenLoanDetails",
-          "type": {
-            "vec": {
-              "defined": "FlashLoanTokenDetailV3"
-            }
-          },
+          "name": "baseTokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "flashLoanType",
-          "type": {
-            "defined": "FlashLoanType"
-          },
+          "name": "quoteTokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "baseTotal",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "baseFree",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "quoteTotal",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "quoteFree",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "referrerRebatesAccrued",
+          "type": "u64",
           "index": false
         }
       ]
     },
     {
-      "name": "WithdrawLog",
+      "name": "Serum3OpenOrdersBalanceLogV2",
       "fields": [
         {
           "name": "mangoGroup",
@@ -26366,29 +35945,49 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "signer",
-          "type": "publicKey",
+          "name": "marketIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "tokenIndex",
+          "name": "baseTokenIndex",
           "type": "u16",
           "index": false
         },
         {
-          "name": "quantity",
+          "name": "quoteTokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "baseTotal",
           "type": "u64",
           "index": false
         },
         {
-          "name": "price",
-          "type": "i128",
+          "name": "baseFree",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "quoteTotal",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "quoteFree",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "referrerRebatesAccrued",
+          "type": "u64",
           "index": false
         }
       ]
     },
     {
-      "name": "DepositLog",
+      "name": "WithdrawLoanOriginationFeeLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -26400,30 +35999,27 @@ export const IDL: MangoV4 = {
           "type": "publicKey",
           "index": false
         },
-        {
-          "name": "signer",
-          "type": "publicKey",
-          "index": false
-        },
         {
           "name": "tokenIndex",
           "type": "u16",
           "index": false
         },
         {
-          "name": "quantity",
-          "type": "u64",
+          "name": "loanOriginationFee",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "price",
-          "type": "i128",
+          "name": "instruction",
+          "type": {
+            "defined": "LoanOriginationFeeInstruction"
+          },
           "index": false
         }
       ]
     },
     {
-      "name": "FillLog",
+      "name": "WithdrawLoanLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -26431,89 +36027,103 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "marketIndex",
-          "type": "u16",
+          "name": "mangoAccount",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "takerSide",
-          "type": "u8",
+          "name": "tokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "makerSlot",
-          "type": "u8",
+          "name": "loanAmount",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "makerOut",
-          "type": "bool",
+          "name": "loanOriginationFee",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "timestamp",
-          "type": "u64",
+          "name": "instruction",
+          "type": {
+            "defined": "LoanOriginationFeeInstruction"
+          },
           "index": false
         },
         {
-          "name": "seqNum",
-          "type": "u64",
+          "name": "price",
+          "type": {
+            "option": "i128"
+          },
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "TokenLiqBankruptcyLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "maker",
+          "name": "liqee",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "makerOrderId",
-          "type": "u128",
+          "name": "liqor",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "makerFee",
-          "type": "i128",
+          "name": "liabTokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "makerTimestamp",
-          "type": "u64",
+          "name": "initialLiabNative",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "taker",
-          "type": "publicKey",
+          "name": "liabPrice",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "takerOrderId",
-          "type": "u128",
+          "name": "insuranceTokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "takerClientOrderId",
-          "type": "u64",
+          "name": "insuranceTransfer",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "takerFee",
+          "name": "socializedLoss",
           "type": "i128",
           "index": false
         },
         {
-          "name": "price",
-          "type": "i64",
+          "name": "startingLiabDepositIndex",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "quantity",
-          "type": "i64",
+          "name": "endingLiabDepositIndex",
+          "type": "i128",
           "index": false
         }
       ]
     },
     {
-      "name": "FillLogV2",
+      "name": "DeactivateTokenPositionLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -26521,84 +36131,109 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "marketIndex",
+          "name": "mangoAccount",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "tokenIndex",
           "type": "u16",
           "index": false
         },
         {
-          "name": "takerSide",
-          "type": "u8",
+          "name": "cumulativeDepositInterest",
+          "type": "f64",
           "index": false
         },
         {
-          "name": "makerSlot",
-          "type": "u8",
+          "name": "cumulativeBorrowInterest",
+          "type": "f64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "DeactivatePerpPositionLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "makerOut",
-          "type": "bool",
+          "name": "mangoAccount",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "timestamp",
-          "type": "u64",
+          "name": "marketIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "seqNum",
-          "type": "u64",
+          "name": "cumulativeLongFunding",
+          "type": "f64",
           "index": false
         },
         {
-          "name": "maker",
-          "type": "publicKey",
+          "name": "cumulativeShortFunding",
+          "type": "f64",
           "index": false
         },
         {
-          "name": "makerClientOrderId",
+          "name": "makerVolume",
           "type": "u64",
           "index": false
         },
         {
-          "name": "makerFee",
-          "type": "f32",
+          "name": "takerVolume",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "makerTimestamp",
-          "type": "u64",
+          "name": "perpSpotTransfers",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "TokenMetaDataLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "taker",
+          "name": "mint",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "takerClientOrderId",
-          "type": "u64",
+          "name": "tokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "takerFee",
-          "type": "f32",
+          "name": "mintDecimals",
+          "type": "u8",
           "index": false
         },
         {
-          "name": "price",
-          "type": "i64",
+          "name": "oracle",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "quantity",
-          "type": "i64",
+          "name": "mintInfo",
+          "type": "publicKey",
           "index": false
         }
       ]
     },
     {
-      "name": "FillLogV3",
+      "name": "TokenMetaDataLogV2",
       "fields": [
         {
           "name": "mangoGroup",
@@ -26606,94 +36241,119 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "marketIndex",
+          "name": "mint",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "tokenIndex",
           "type": "u16",
           "index": false
         },
         {
-          "name": "takerSide",
+          "name": "mintDecimals",
           "type": "u8",
           "index": false
         },
         {
-          "name": "makerSlot",
-          "type": "u8",
+          "name": "oracle",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "fallbackOracle",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "makerOut",
-          "type": "bool",
+          "name": "mintInfo",
+          "type": "publicKey",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PerpMarketMetaDataLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "timestamp",
-          "type": "u64",
+          "name": "perpMarket",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "seqNum",
-          "type": "u64",
+          "name": "perpMarketIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "maker",
-          "type": "publicKey",
+          "name": "baseDecimals",
+          "type": "u8",
           "index": false
         },
         {
-          "name": "makerClientOrderId",
-          "type": "u64",
+          "name": "baseLotSize",
+          "type": "i64",
           "index": false
         },
         {
-          "name": "makerFee",
-          "type": "f32",
+          "name": "quoteLotSize",
+          "type": "i64",
           "index": false
         },
         {
-          "name": "makerTimestamp",
-          "type": "u64",
+          "name": "oracle",
+          "type": "publicKey",
           "index": false
-        },
+        }
+      ]
+    },
+    {
+      "name": "Serum3RegisterMarketLog",
+      "fields": [
         {
-          "name": "taker",
+          "name": "mangoGroup",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "takerClientOrderId",
-          "type": "u64",
+          "name": "serumMarket",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "takerFee",
-          "type": "f32",
+          "name": "marketIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "price",
-          "type": "i64",
+          "name": "baseTokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "quantity",
-          "type": "i64",
+          "name": "quoteTokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "makerClosedPnl",
-          "type": "f64",
+          "name": "serumProgram",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "takerClosedPnl",
-          "type": "f64",
+          "name": "serumProgramExternal",
+          "type": "publicKey",
           "index": false
         }
       ]
     },
     {
-      "name": "PerpUpdateFundingLog",
+      "name": "PerpLiqBaseOrPositivePnlLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -26701,59 +36361,49 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "marketIndex",
+          "name": "perpMarketIndex",
           "type": "u16",
           "index": false
         },
         {
-          "name": "longFunding",
-          "type": "i128",
-          "index": false
-        },
-        {
-          "name": "shortFunding",
-          "type": "i128",
+          "name": "liqor",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "price",
-          "type": "i128",
+          "name": "liqee",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "oracleSlot",
-          "type": "u64",
+          "name": "baseTransfer",
+          "type": "i64",
           "index": false
         },
         {
-          "name": "stablePrice",
+          "name": "quoteTransfer",
           "type": "i128",
           "index": false
         },
         {
-          "name": "feesAccrued",
+          "name": "pnlTransfer",
           "type": "i128",
           "index": false
         },
         {
-          "name": "feesSettled",
+          "name": "pnlSettleLimitTransfer",
           "type": "i128",
           "index": false
         },
         {
-          "name": "openInterest",
-          "type": "i64",
-          "index": false
-        },
-        {
-          "name": "instantaneousFundingRate",
+          "name": "price",
           "type": "i128",
           "index": false
         }
       ]
     },
     {
-      "name": "PerpUpdateFundingLogV2",
+      "name": "PerpLiqBaseOrPositivePnlLogV2",
       "fields": [
         {
           "name": "mangoGroup",
@@ -26761,71 +36411,59 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "marketIndex",
+          "name": "perpMarketIndex",
           "type": "u16",
           "index": false
         },
         {
-          "name": "longFunding",
-          "type": "i128",
-          "index": false
-        },
-        {
-          "name": "shortFunding",
-          "type": "i128",
+          "name": "liqor",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "price",
-          "type": "i128",
+          "name": "liqee",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "oracleSlot",
-          "type": "u64",
+          "name": "baseTransferLiqee",
+          "type": "i64",
           "index": false
         },
         {
-          "name": "oracleConfidence",
+          "name": "quoteTransferLiqee",
           "type": "i128",
           "index": false
         },
         {
-          "name": "oracleType",
-          "type": {
-            "defined": "OracleType"
-          },
-          "index": false
-        },
-        {
-          "name": "stablePrice",
+          "name": "quoteTransferLiqor",
           "type": "i128",
           "index": false
         },
         {
-          "name": "feesAccrued",
+          "name": "quotePlatformFee",
           "type": "i128",
           "index": false
         },
         {
-          "name": "feesSettled",
+          "name": "pnlTransfer",
           "type": "i128",
           "index": false
         },
         {
-          "name": "openInterest",
-          "type": "i64",
+          "name": "pnlSettleLimitTransfer",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "instantaneousFundingRate",
+          "name": "price",
           "type": "i128",
           "index": false
         }
       ]
     },
     {
-      "name": "UpdateIndexLog",
+      "name": "PerpLiqBaseOrPositivePnlLogV3",
       "fields": [
         {
           "name": "mangoGroup",
@@ -26833,69 +36471,64 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "tokenIndex",
+          "name": "perpMarketIndex",
           "type": "u16",
           "index": false
         },
         {
-          "name": "depositIndex",
-          "type": "i128",
-          "index": false
-        },
-        {
-          "name": "borrowIndex",
-          "type": "i128",
+          "name": "liqor",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "avgUtilization",
-          "type": "i128",
+          "name": "liqee",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "price",
-          "type": "i128",
+          "name": "baseTransferLiqee",
+          "type": "i64",
           "index": false
         },
         {
-          "name": "stablePrice",
+          "name": "quoteTransferLiqee",
           "type": "i128",
           "index": false
         },
         {
-          "name": "collectedFees",
+          "name": "quoteTransferLiqor",
           "type": "i128",
           "index": false
         },
         {
-          "name": "loanFeeRate",
+          "name": "quotePlatformFee",
           "type": "i128",
           "index": false
         },
         {
-          "name": "totalBorrows",
+          "name": "pnlTransfer",
           "type": "i128",
           "index": false
         },
         {
-          "name": "totalDeposits",
-          "type": "i128",
+          "name": "pnlSettleLimitTransferRecurring",
+          "type": "i64",
           "index": false
         },
         {
-          "name": "borrowRate",
-          "type": "i128",
+          "name": "pnlSettleLimitTransferOneshot",
+          "type": "i64",
           "index": false
         },
         {
-          "name": "depositRate",
+          "name": "price",
           "type": "i128",
           "index": false
         }
       ]
     },
     {
-      "name": "UpdateRateLog",
+      "name": "PerpLiqBankruptcyLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -26903,79 +36536,54 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "tokenIndex",
-          "type": "u16",
-          "index": false
-        },
-        {
-          "name": "rate0",
-          "type": "i128",
-          "index": false
-        },
-        {
-          "name": "rate1",
-          "type": "i128",
+          "name": "liqee",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "maxRate",
-          "type": "i128",
-          "index": false
-        }
-      ]
-    },
-    {
-      "name": "UpdateRateLogV2",
-      "fields": [
-        {
-          "name": "mangoGroup",
+          "name": "liqor",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "tokenIndex",
+          "name": "perpMarketIndex",
           "type": "u16",
           "index": false
         },
         {
-          "name": "rate0",
+          "name": "insuranceTransfer",
           "type": "i128",
           "index": false
         },
         {
-          "name": "util0",
+          "name": "socializedLoss",
           "type": "i128",
           "index": false
         },
         {
-          "name": "rate1",
+          "name": "startingLongFunding",
           "type": "i128",
           "index": false
         },
         {
-          "name": "util1",
+          "name": "startingShortFunding",
           "type": "i128",
           "index": false
         },
         {
-          "name": "maxRate",
+          "name": "endingLongFunding",
           "type": "i128",
           "index": false
         },
         {
-          "name": "curveScaling",
-          "type": "f64",
-          "index": false
-        },
-        {
-          "name": "targetUtilization",
-          "type": "f32",
+          "name": "endingShortFunding",
+          "type": "i128",
           "index": false
         }
       ]
     },
     {
-      "name": "TokenLiqWithTokenLog",
+      "name": "PerpLiqNegativePnlOrBankruptcyLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -26993,44 +36601,59 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "assetTokenIndex",
+          "name": "perpMarketIndex",
           "type": "u16",
           "index": false
         },
         {
-          "name": "liabTokenIndex",
-          "type": "u16",
+          "name": "settlement",
+          "type": "i128",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PerpSettlePnlLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "assetTransfer",
-          "type": "i128",
+          "name": "mangoAccountA",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "liabTransfer",
-          "type": "i128",
+          "name": "mangoAccountB",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "assetPrice",
-          "type": "i128",
+          "name": "perpMarketIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "liabPrice",
+          "name": "settlement",
           "type": "i128",
           "index": false
         },
         {
-          "name": "bankruptcy",
-          "type": "bool",
+          "name": "settler",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "fee",
+          "type": "i128",
           "index": false
         }
       ]
     },
     {
-      "name": "TokenLiqWithTokenLogV2",
+      "name": "PerpSettleFeesLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -27038,64 +36661,79 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "liqee",
+          "name": "mangoAccount",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "liqor",
-          "type": "publicKey",
+          "name": "perpMarketIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "assetTokenIndex",
-          "type": "u16",
+          "name": "settlement",
+          "type": "i128",
           "index": false
-        },
+        }
+      ]
+    },
+    {
+      "name": "AccountBuybackFeesWithMngoLog",
+      "fields": [
         {
-          "name": "liabTokenIndex",
-          "type": "u16",
+          "name": "mangoGroup",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "assetTransferFromLiqee",
-          "type": "i128",
+          "name": "mangoAccount",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "assetTransferToLiqor",
+          "name": "buybackFees",
           "type": "i128",
           "index": false
         },
         {
-          "name": "assetLiquidationFee",
+          "name": "buybackMngo",
           "type": "i128",
           "index": false
         },
         {
-          "name": "liabTransfer",
+          "name": "mngoBuybackPrice",
           "type": "i128",
           "index": false
         },
         {
-          "name": "assetPrice",
+          "name": "oraclePrice",
           "type": "i128",
           "index": false
+        }
+      ]
+    },
+    {
+      "name": "FilledPerpOrderLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
+          "index": false
         },
         {
-          "name": "liabPrice",
-          "type": "i128",
+          "name": "perpMarketIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "bankruptcy",
-          "type": "bool",
+          "name": "seqNum",
+          "type": "u64",
           "index": false
         }
       ]
     },
     {
-      "name": "Serum3OpenOrdersBalanceLog",
+      "name": "PerpTakerTradeLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -27108,44 +36746,49 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "baseTokenIndex",
+          "name": "perpMarketIndex",
           "type": "u16",
           "index": false
         },
         {
-          "name": "quoteTokenIndex",
-          "type": "u16",
+          "name": "takerSide",
+          "type": "u8",
           "index": false
         },
         {
-          "name": "baseTotal",
-          "type": "u64",
+          "name": "totalBaseLotsTaken",
+          "type": "i64",
           "index": false
         },
         {
-          "name": "baseFree",
-          "type": "u64",
+          "name": "totalBaseLotsDecremented",
+          "type": "i64",
           "index": false
         },
         {
-          "name": "quoteTotal",
-          "type": "u64",
+          "name": "totalQuoteLotsTaken",
+          "type": "i64",
           "index": false
         },
         {
-          "name": "quoteFree",
-          "type": "u64",
+          "name": "totalQuoteLotsDecremented",
+          "type": "i64",
           "index": false
         },
         {
-          "name": "referrerRebatesAccrued",
-          "type": "u64",
+          "name": "takerFeesPaid",
+          "type": "i128",
+          "index": false
+        },
+        {
+          "name": "feePenalty",
+          "type": "i128",
           "index": false
         }
       ]
     },
     {
-      "name": "Serum3OpenOrdersBalanceLogV2",
+      "name": "PerpBatchOrderLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -27158,49 +36801,34 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "marketIndex",
-          "type": "u16",
-          "index": false
-        },
-        {
-          "name": "baseTokenIndex",
-          "type": "u16",
-          "index": false
-        },
-        {
-          "name": "quoteTokenIndex",
+          "name": "perpMarketIndex",
           "type": "u16",
           "index": false
         },
         {
-          "name": "baseTotal",
-          "type": "u64",
-          "index": false
-        },
-        {
-          "name": "baseFree",
-          "type": "u64",
+          "name": "batchIndex",
+          "type": "u8",
           "index": false
         },
         {
-          "name": "quoteTotal",
+          "name": "clientOrderId",
           "type": "u64",
           "index": false
         },
         {
-          "name": "quoteFree",
-          "type": "u64",
+          "name": "posted",
+          "type": "bool",
           "index": false
         },
         {
-          "name": "referrerRebatesAccrued",
-          "type": "u64",
+          "name": "orderId",
+          "type": "u128",
           "index": false
         }
       ]
     },
     {
-      "name": "WithdrawLoanOriginationFeeLog",
+      "name": "PerpOpeningAuctionLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -27208,31 +36836,29 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "mangoAccount",
-          "type": "publicKey",
+          "name": "perpMarketIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "tokenIndex",
-          "type": "u16",
+          "name": "priceLots",
+          "type": "i64",
           "index": false
         },
         {
-          "name": "loanOriginationFee",
-          "type": "i128",
+          "name": "fills",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "instruction",
-          "type": {
-            "defined": "LoanOriginationFeeInstruction"
-          },
+          "name": "done",
+          "type": "bool",
           "index": false
         }
       ]
     },
     {
-      "name": "WithdrawLoanLog",
+      "name": "PerpForceClosePositionLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -27240,43 +36866,39 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "mangoAccount",
-          "type": "publicKey",
+          "name": "perpMarketIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "tokenIndex",
-          "type": "u16",
+          "name": "accountA",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "loanAmount",
-          "type": "i128",
+          "name": "accountB",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "loanOriginationFee",
-          "type": "i128",
+          "name": "baseTransfer",
+          "type": "i64",
           "index": false
         },
         {
-          "name": "instruction",
-          "type": {
-            "defined": "LoanOriginationFeeInstruction"
-          },
+          "name": "quoteTransfer",
+          "type": "i128",
           "index": false
         },
         {
           "name": "price",
-          "type": {
-            "option": "i128"
-          },
+          "type": "i128",
           "index": false
         }
       ]
     },
     {
-      "name": "TokenLiqBankruptcyLog",
+      "name": "TokenForceCloseBorrowsWithTokenLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -27284,59 +36906,54 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "liqee",
+          "name": "liqor",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "liqor",
+          "name": "liqee",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "liabTokenIndex",
+          "name": "assetTokenIndex",
           "type": "u16",
           "index": false
         },
         {
-          "name": "initialLiabNative",
-          "type": "i128",
+          "name": "liabTokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "liabPrice",
+          "name": "assetTransfer",
           "type": "i128",
           "index": false
         },
         {
-          "name": "insuranceTokenIndex",
-          "type": "u16",
-          "index": false
-        },
-        {
-          "name": "insuranceTransfer",
+          "name": "liabTransfer",
           "type": "i128",
           "index": false
         },
         {
-          "name": "socializedLoss",
+          "name": "assetPrice",
           "type": "i128",
           "index": false
         },
         {
-          "name": "startingLiabDepositIndex",
+          "name": "liabPrice",
           "type": "i128",
           "index": false
         },
         {
-          "name": "endingLiabDepositIndex",
+          "name": "feeFactor",
           "type": "i128",
           "index": false
         }
       ]
     },
     {
-      "name": "DeactivateTokenPositionLog",
+      "name": "TokenForceCloseBorrowsWithTokenLogV2",
       "fields": [
         {
           "name": "mangoGroup",
@@ -27344,109 +36961,64 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "mangoAccount",
-          "type": "publicKey",
-          "index": false
-        },
-        {
-          "name": "tokenIndex",
-          "type": "u16",
-          "index": false
-        },
-        {
-          "name": "cumulativeDepositInterest",
-          "type": "f64",
-          "index": false
-        },
-        {
-          "name": "cumulativeBorrowInterest",
-          "type": "f64",
-          "index": false
-        }
-      ]
-    },
-    {
-      "name": "DeactivatePerpPositionLog",
-      "fields": [
-        {
-          "name": "mangoGroup",
+          "name": "liqor",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "mangoAccount",
+          "name": "liqee",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "marketIndex",
+          "name": "assetTokenIndex",
           "type": "u16",
           "index": false
         },
         {
-          "name": "cumulativeLongFunding",
-          "type": "f64",
-          "index": false
-        },
-        {
-          "name": "cumulativeShortFunding",
-          "type": "f64",
-          "index": false
-        },
-        {
-          "name": "makerVolume",
-          "type": "u64",
-          "index": false
-        },
-        {
-          "name": "takerVolume",
-          "type": "u64",
+          "name": "liabTokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "perpSpotTransfers",
-          "type": "i64",
+          "name": "assetTransferFromLiqee",
+          "type": "i128",
           "index": false
-        }
-      ]
-    },
-    {
-      "name": "TokenMetaDataLog",
-      "fields": [
+        },
         {
-          "name": "mangoGroup",
-          "type": "publicKey",
+          "name": "assetTransferToLiqor",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "mint",
-          "type": "publicKey",
+          "name": "assetLiquidationFee",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "tokenIndex",
-          "type": "u16",
+          "name": "liabTransfer",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "mintDecimals",
-          "type": "u8",
+          "name": "assetPrice",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "oracle",
-          "type": "publicKey",
+          "name": "liabPrice",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "mintInfo",
-          "type": "publicKey",
+          "name": "feeFactor",
+          "type": "i128",
           "index": false
         }
       ]
     },
     {
-      "name": "TokenMetaDataLogV2",
+      "name": "TokenConditionalSwapCreateLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -27454,79 +37026,79 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "mint",
+          "name": "mangoAccount",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "tokenIndex",
-          "type": "u16",
+          "name": "id",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "mintDecimals",
-          "type": "u8",
+          "name": "maxBuy",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "oracle",
-          "type": "publicKey",
+          "name": "maxSell",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "fallbackOracle",
-          "type": "publicKey",
+          "name": "expiryTimestamp",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "mintInfo",
-          "type": "publicKey",
+          "name": "priceLowerLimit",
+          "type": "f64",
           "index": false
-        }
-      ]
-    },
-    {
-      "name": "PerpMarketMetaDataLog",
-      "fields": [
+        },
         {
-          "name": "mangoGroup",
-          "type": "publicKey",
+          "name": "priceUpperLimit",
+          "type": "f64",
           "index": false
         },
         {
-          "name": "perpMarket",
-          "type": "publicKey",
+          "name": "pricePremiumRate",
+          "type": "f64",
           "index": false
         },
         {
-          "name": "perpMarketIndex",
-          "type": "u16",
+          "name": "takerFeeRate",
+          "type": "f32",
           "index": false
         },
         {
-          "name": "baseDecimals",
-          "type": "u8",
+          "name": "makerFeeRate",
+          "type": "f32",
           "index": false
         },
         {
-          "name": "baseLotSize",
-          "type": "i64",
+          "name": "buyTokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "quoteLotSize",
-          "type": "i64",
+          "name": "sellTokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "oracle",
-          "type": "publicKey",
+          "name": "allowCreatingDeposits",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "allowCreatingBorrows",
+          "type": "bool",
           "index": false
         }
       ]
     },
     {
-      "name": "Serum3RegisterMarketLog",
+      "name": "TokenConditionalSwapCreateLogV2",
       "fields": [
         {
           "name": "mangoGroup",
@@ -27534,89 +37106,89 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "serumMarket",
+          "name": "mangoAccount",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "marketIndex",
-          "type": "u16",
+          "name": "id",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "baseTokenIndex",
-          "type": "u16",
+          "name": "maxBuy",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "quoteTokenIndex",
-          "type": "u16",
+          "name": "maxSell",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "serumProgram",
-          "type": "publicKey",
+          "name": "expiryTimestamp",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "serumProgramExternal",
-          "type": "publicKey",
+          "name": "priceLowerLimit",
+          "type": "f64",
           "index": false
-        }
-      ]
-    },
-    {
-      "name": "PerpLiqBaseOrPositivePnlLog",
-      "fields": [
+        },
         {
-          "name": "mangoGroup",
-          "type": "publicKey",
+          "name": "priceUpperLimit",
+          "type": "f64",
           "index": false
         },
         {
-          "name": "perpMarketIndex",
-          "type": "u16",
+          "name": "pricePremiumRate",
+          "type": "f64",
           "index": false
         },
         {
-          "name": "liqor",
-          "type": "publicKey",
+          "name": "takerFeeRate",
+          "type": "f32",
           "index": false
         },
         {
-          "name": "liqee",
-          "type": "publicKey",
+          "name": "makerFeeRate",
+          "type": "f32",
           "index": false
         },
         {
-          "name": "baseTransfer",
-          "type": "i64",
+          "name": "buyTokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "quoteTransfer",
-          "type": "i128",
+          "name": "sellTokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "pnlTransfer",
-          "type": "i128",
+          "name": "allowCreatingDeposits",
+          "type": "bool",
           "index": false
         },
         {
-          "name": "pnlSettleLimitTransfer",
-          "type": "i128",
+          "name": "allowCreatingBorrows",
+          "type": "bool",
           "index": false
         },
         {
-          "name": "price",
-          "type": "i128",
+          "name": "displayPriceStyle",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "intention",
+          "type": "u8",
           "index": false
         }
       ]
     },
     {
-      "name": "PerpLiqBaseOrPositivePnlLogV2",
+      "name": "TokenConditionalSwapCreateLogV3",
       "fields": [
         {
           "name": "mangoGroup",
@@ -27624,124 +37196,104 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "perpMarketIndex",
-          "type": "u16",
-          "index": false
-        },
-        {
-          "name": "liqor",
-          "type": "publicKey",
-          "index": false
-        },
-        {
-          "name": "liqee",
+          "name": "mangoAccount",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "baseTransferLiqee",
-          "type": "i64",
+          "name": "id",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "quoteTransferLiqee",
-          "type": "i128",
+          "name": "maxBuy",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "quoteTransferLiqor",
-          "type": "i128",
+          "name": "maxSell",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "quotePlatformFee",
-          "type": "i128",
+          "name": "expiryTimestamp",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "pnlTransfer",
-          "type": "i128",
+          "name": "priceLowerLimit",
+          "type": "f64",
           "index": false
         },
         {
-          "name": "pnlSettleLimitTransfer",
-          "type": "i128",
+          "name": "priceUpperLimit",
+          "type": "f64",
           "index": false
         },
         {
-          "name": "price",
-          "type": "i128",
-          "index": false
-        }
-      ]
-    },
-    {
-      "name": "PerpLiqBaseOrPositivePnlLogV3",
-      "fields": [
-        {
-          "name": "mangoGroup",
-          "type": "publicKey",
+          "name": "pricePremiumRate",
+          "type": "f64",
           "index": false
         },
         {
-          "name": "perpMarketIndex",
-          "type": "u16",
+          "name": "takerFeeRate",
+          "type": "f32",
           "index": false
         },
         {
-          "name": "liqor",
-          "type": "publicKey",
+          "name": "makerFeeRate",
+          "type": "f32",
           "index": false
         },
         {
-          "name": "liqee",
-          "type": "publicKey",
+          "name": "buyTokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "baseTransferLiqee",
-          "type": "i64",
+          "name": "sellTokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "quoteTransferLiqee",
-          "type": "i128",
+          "name": "allowCreatingDeposits",
+          "type": "bool",
           "index": false
         },
         {
-          "name": "quoteTransferLiqor",
-          "type": "i128",
+          "name": "allowCreatingBorrows",
+          "type": "bool",
           "index": false
         },
         {
-          "name": "quotePlatformFee",
-          "type": "i128",
+          "name": "displayPriceStyle",
+          "type": "u8",
           "index": false
         },
         {
-          "name": "pnlTransfer",
-          "type": "i128",
+          "name": "intention",
+          "type": "u8",
           "index": false
         },
         {
-          "name": "pnlSettleLimitTransferRecurring",
-          "type": "i64",
+          "name": "tcsType",
+          "type": "u8",
           "index": false
         },
         {
-          "name": "pnlSettleLimitTransferOneshot",
-          "type": "i64",
+          "name": "startTimestamp",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "price",
-          "type": "i128",
+          "name": "durationSeconds",
+          "type": "u64",
           "index": false
         }
       ]
     },
     {
-      "name": "PerpLiqBankruptcyLog",
+      "name": "TokenConditionalSwapTriggerLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -27759,44 +37311,59 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "perpMarketIndex",
+          "name": "tokenConditionalSwapId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "buyTokenIndex",
           "type": "u16",
           "index": false
         },
         {
-          "name": "insuranceTransfer",
-          "type": "i128",
+          "name": "sellTokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "socializedLoss",
-          "type": "i128",
+          "name": "buyAmount",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "startingLongFunding",
-          "type": "i128",
+          "name": "sellAmount",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "startingShortFunding",
-          "type": "i128",
+          "name": "makerFee",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "endingLongFunding",
+          "name": "takerFee",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "buyTokenPrice",
           "type": "i128",
           "index": false
         },
         {
-          "name": "endingShortFunding",
+          "name": "sellTokenPrice",
           "type": "i128",
           "index": false
+        },
+        {
+          "name": "closed",
+          "type": "bool",
+          "index": false
         }
       ]
     },
     {
-      "name": "PerpLiqNegativePnlOrBankruptcyLog",
+      "name": "TokenConditionalSwapTriggerLogV2",
       "fields": [
         {
           "name": "mangoGroup",
@@ -27814,84 +37381,69 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "perpMarketIndex",
-          "type": "u16",
+          "name": "tokenConditionalSwapId",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "settlement",
-          "type": "i128",
-          "index": false
-        }
-      ]
-    },
-    {
-      "name": "PerpSettlePnlLog",
-      "fields": [
-        {
-          "name": "mangoGroup",
-          "type": "publicKey",
+          "name": "buyTokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "mangoAccountA",
-          "type": "publicKey",
+          "name": "sellTokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "mangoAccountB",
-          "type": "publicKey",
+          "name": "buyAmount",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "perpMarketIndex",
-          "type": "u16",
+          "name": "sellAmount",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "settlement",
-          "type": "i128",
+          "name": "makerFee",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "settler",
-          "type": "publicKey",
+          "name": "takerFee",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "fee",
+          "name": "buyTokenPrice",
           "type": "i128",
           "index": false
-        }
-      ]
-    },
-    {
-      "name": "PerpSettleFeesLog",
-      "fields": [
+        },
         {
-          "name": "mangoGroup",
-          "type": "publicKey",
+          "name": "sellTokenPrice",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "mangoAccount",
-          "type": "publicKey",
+          "name": "closed",
+          "type": "bool",
           "index": false
         },
         {
-          "name": "perpMarketIndex",
-          "type": "u16",
+          "name": "displayPriceStyle",
+          "type": "u8",
           "index": false
         },
         {
-          "name": "settlement",
-          "type": "i128",
+          "name": "intention",
+          "type": "u8",
           "index": false
         }
       ]
     },
     {
-      "name": "AccountBuybackFeesWithMngoLog",
+      "name": "TokenConditionalSwapTriggerLogV3",
       "fields": [
         {
           "name": "mangoGroup",
@@ -27899,109 +37451,109 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "mangoAccount",
+          "name": "liqee",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "buybackFees",
-          "type": "i128",
+          "name": "liqor",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "buybackMngo",
-          "type": "i128",
+          "name": "tokenConditionalSwapId",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "mngoBuybackPrice",
-          "type": "i128",
+          "name": "buyTokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "oraclePrice",
-          "type": "i128",
+          "name": "sellTokenIndex",
+          "type": "u16",
           "index": false
-        }
-      ]
-    },
-    {
-      "name": "FilledPerpOrderLog",
-      "fields": [
+        },
         {
-          "name": "mangoGroup",
-          "type": "publicKey",
+          "name": "buyAmount",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "perpMarketIndex",
-          "type": "u16",
+          "name": "sellAmount",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "seqNum",
+          "name": "makerFee",
           "type": "u64",
           "index": false
-        }
-      ]
-    },
-    {
-      "name": "PerpTakerTradeLog",
-      "fields": [
+        },
         {
-          "name": "mangoGroup",
-          "type": "publicKey",
+          "name": "takerFee",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "mangoAccount",
-          "type": "publicKey",
+          "name": "buyTokenPrice",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "perpMarketIndex",
-          "type": "u16",
+          "name": "sellTokenPrice",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "takerSide",
-          "type": "u8",
+          "name": "closed",
+          "type": "bool",
           "index": false
         },
         {
-          "name": "totalBaseLotsTaken",
-          "type": "i64",
+          "name": "displayPriceStyle",
+          "type": "u8",
           "index": false
         },
         {
-          "name": "totalBaseLotsDecremented",
-          "type": "i64",
+          "name": "intention",
+          "type": "u8",
           "index": false
         },
         {
-          "name": "totalQuoteLotsTaken",
-          "type": "i64",
+          "name": "tcsType",
+          "type": "u8",
           "index": false
         },
         {
-          "name": "totalQuoteLotsDecremented",
-          "type": "i64",
+          "name": "startTimestamp",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "TokenConditionalSwapCancelLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "takerFeesPaid",
-          "type": "i128",
+          "name": "mangoAccount",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "feePenalty",
-          "type": "i128",
+          "name": "id",
+          "type": "u64",
           "index": false
         }
       ]
     },
     {
-      "name": "PerpForceClosePositionLog",
+      "name": "TokenConditionalSwapStartLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -28009,39 +37561,34 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "perpMarketIndex",
-          "type": "u16",
-          "index": false
-        },
-        {
-          "name": "accountA",
+          "name": "mangoAccount",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "accountB",
+          "name": "caller",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "baseTransfer",
-          "type": "i64",
+          "name": "tokenConditionalSwapId",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "quoteTransfer",
-          "type": "i128",
+          "name": "incentiveTokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "price",
-          "type": "i128",
+          "name": "incentiveAmount",
+          "type": "u64",
           "index": false
         }
       ]
     },
     {
-      "name": "TokenForceCloseBorrowsWithTokenLog",
+      "name": "TokenCollateralFeeLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -28049,54 +37596,34 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "liqor",
-          "type": "publicKey",
-          "index": false
-        },
-        {
-          "name": "liqee",
+          "name": "mangoAccount",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "assetTokenIndex",
-          "type": "u16",
-          "index": false
-        },
-        {
-          "name": "liabTokenIndex",
+          "name": "tokenIndex",
           "type": "u16",
           "index": false
         },
         {
-          "name": "assetTransfer",
-          "type": "i128",
-          "index": false
-        },
-        {
-          "name": "liabTransfer",
-          "type": "i128",
-          "index": false
-        },
-        {
-          "name": "assetPrice",
+          "name": "assetUsageFraction",
           "type": "i128",
           "index": false
         },
         {
-          "name": "liabPrice",
+          "name": "fee",
           "type": "i128",
           "index": false
         },
         {
-          "name": "feeFactor",
+          "name": "price",
           "type": "i128",
           "index": false
         }
       ]
     },
     {
-      "name": "TokenForceCloseBorrowsWithTokenLogV2",
+      "name": "ForceWithdrawLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -28104,64 +37631,74 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "liqor",
+          "name": "mangoAccount",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "liqee",
-          "type": "publicKey",
+          "name": "tokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "assetTokenIndex",
-          "type": "u16",
+          "name": "quantity",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "liabTokenIndex",
-          "type": "u16",
+          "name": "price",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "assetTransferFromLiqee",
-          "type": "i128",
+          "name": "toTokenAccount",
+          "type": "publicKey",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "OpenbookV2RegisterMarketLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "assetTransferToLiqor",
-          "type": "i128",
+          "name": "openbookMarket",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "assetLiquidationFee",
-          "type": "i128",
+          "name": "marketIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "liabTransfer",
-          "type": "i128",
+          "name": "baseTokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "assetPrice",
-          "type": "i128",
+          "name": "quoteTokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "liabPrice",
-          "type": "i128",
+          "name": "openbookProgram",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "feeFactor",
-          "type": "i128",
+          "name": "openbookMarketExternal",
+          "type": "publicKey",
           "index": false
         }
       ]
     },
     {
-      "name": "TokenConditionalSwapCreateLog",
+      "name": "OpenbookV2OpenOrdersBalanceLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -28174,74 +37711,49 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "id",
-          "type": "u64",
+          "name": "marketIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "maxBuy",
-          "type": "u64",
+          "name": "baseTokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "maxSell",
-          "type": "u64",
+          "name": "quoteTokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "expiryTimestamp",
+          "name": "baseTotal",
           "type": "u64",
           "index": false
         },
         {
-          "name": "priceLowerLimit",
-          "type": "f64",
-          "index": false
-        },
-        {
-          "name": "priceUpperLimit",
-          "type": "f64",
-          "index": false
-        },
-        {
-          "name": "pricePremiumRate",
-          "type": "f64",
-          "index": false
-        },
-        {
-          "name": "takerFeeRate",
-          "type": "f32",
-          "index": false
-        },
-        {
-          "name": "makerFeeRate",
-          "type": "f32",
-          "index": false
-        },
-        {
-          "name": "buyTokenIndex",
-          "type": "u16",
+          "name": "baseFree",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "sellTokenIndex",
-          "type": "u16",
+          "name": "quoteTotal",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "allowCreatingDeposits",
-          "type": "bool",
+          "name": "quoteFree",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "allowCreatingBorrows",
-          "type": "bool",
+          "name": "referrerRebatesAvailable",
+          "type": "u64",
           "index": false
         }
       ]
     },
     {
-      "name": "TokenConditionalSwapCreateLogV2",
+      "name": "FixedTermMarketCreateLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -28249,89 +37761,84 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "mangoAccount",
+          "name": "fixedTermMarket",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "id",
-          "type": "u64",
-          "index": false
-        },
-        {
-          "name": "maxBuy",
-          "type": "u64",
+          "name": "marketIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "maxSell",
-          "type": "u64",
+          "name": "tokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "expiryTimestamp",
+          "name": "maturityTimestamp",
           "type": "u64",
           "index": false
         },
         {
-          "name": "priceLowerLimit",
-          "type": "f64",
-          "index": false
-        },
-        {
-          "name": "priceUpperLimit",
-          "type": "f64",
+          "name": "lendRate",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "pricePremiumRate",
-          "type": "f64",
+          "name": "borrowRate",
+          "type": "i128",
           "index": false
-        },
+        }
+      ]
+    },
+    {
+      "name": "FixedTermLoanLog",
+      "fields": [
         {
-          "name": "takerFeeRate",
-          "type": "f32",
+          "name": "mangoGroup",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "makerFeeRate",
-          "type": "f32",
+          "name": "mangoAccount",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "buyTokenIndex",
+          "name": "marketIndex",
           "type": "u16",
           "index": false
         },
         {
-          "name": "sellTokenIndex",
+          "name": "tokenIndex",
           "type": "u16",
           "index": false
         },
         {
-          "name": "allowCreatingDeposits",
+          "name": "isBorrow",
           "type": "bool",
           "index": false
         },
         {
-          "name": "allowCreatingBorrows",
-          "type": "bool",
+          "name": "amount",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "displayPriceStyle",
-          "type": "u8",
+          "name": "amountAtMaturity",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "intention",
-          "type": "u8",
+          "name": "maturityTimestamp",
+          "type": "u64",
           "index": false
         }
       ]
     },
     {
-      "name": "TokenConditionalSwapCreateLogV3",
+      "name": "FixedTermSettleLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -28344,99 +37851,94 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "id",
-          "type": "u64",
+          "name": "marketIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "maxBuy",
-          "type": "u64",
+          "name": "tokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "maxSell",
+          "name": "lentAtMaturity",
           "type": "u64",
           "index": false
         },
         {
-          "name": "expiryTimestamp",
+          "name": "borrowedAtMaturity",
           "type": "u64",
           "index": false
-        },
-        {
-          "name": "priceLowerLimit",
-          "type": "f64",
-          "index": false
-        },
-        {
-          "name": "priceUpperLimit",
-          "type": "f64",
-          "index": false
-        },
+        }
+      ]
+    },
+    {
+      "name": "PerpScheduledOrderCreateLog",
+      "fields": [
         {
-          "name": "pricePremiumRate",
-          "type": "f64",
+          "name": "mangoGroup",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "takerFeeRate",
-          "type": "f32",
+          "name": "mangoAccount",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "makerFeeRate",
-          "type": "f32",
+          "name": "id",
+          "type": "u64",
           "index": false
         },
         {
-          "name": "buyTokenIndex",
+          "name": "marketIndex",
           "type": "u16",
           "index": false
         },
         {
-          "name": "sellTokenIndex",
-          "type": "u16",
+          "name": "side",
+          "type": "u8",
           "index": false
         },
         {
-          "name": "allowCreatingDeposits",
-          "type": "bool",
+          "name": "orderType",
+          "type": "u8",
           "index": false
         },
         {
-          "name": "allowCreatingBorrows",
+          "name": "reduceOnly",
           "type": "bool",
           "index": false
         },
         {
-          "name": "displayPriceStyle",
-          "type": "u8",
+          "name": "maxBaseLots",
+          "type": "i64",
           "index": false
         },
         {
-          "name": "intention",
-          "type": "u8",
+          "name": "childBaseLots",
+          "type": "i64",
           "index": false
         },
         {
-          "name": "tcsType",
-          "type": "u8",
+          "name": "priceLots",
+          "type": "i64",
           "index": false
         },
         {
-          "name": "startTimestamp",
+          "name": "intervalSeconds",
           "type": "u64",
           "index": false
         },
         {
-          "name": "durationSeconds",
+          "name": "expiryTimestamp",
           "type": "u64",
           "index": false
         }
       ]
     },
     {
-      "name": "TokenConditionalSwapTriggerLog",
+      "name": "PerpScheduledOrderCancelLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -28444,69 +37946,69 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "liqee",
-          "type": "publicKey",
-          "index": false
-        },
-        {
-          "name": "liqor",
+          "name": "mangoAccount",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "tokenConditionalSwapId",
+          "name": "id",
           "type": "u64",
           "index": false
-        },
+        }
+      ]
+    },
+    {
+      "name": "PerpScheduledOrderTriggerLog",
+      "fields": [
         {
-          "name": "buyTokenIndex",
-          "type": "u16",
+          "name": "mangoGroup",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "sellTokenIndex",
-          "type": "u16",
+          "name": "mangoAccount",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "buyAmount",
-          "type": "u64",
+          "name": "caller",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "sellAmount",
+          "name": "id",
           "type": "u64",
           "index": false
         },
         {
-          "name": "makerFee",
-          "type": "u64",
+          "name": "marketIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "takerFee",
-          "type": "u64",
+          "name": "childBaseLots",
+          "type": "i64",
           "index": false
         },
         {
-          "name": "buyTokenPrice",
-          "type": "i128",
+          "name": "executedBaseLots",
+          "type": "i64",
           "index": false
         },
         {
-          "name": "sellTokenPrice",
-          "type": "i128",
+          "name": "incentiveTokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "closed",
-          "type": "bool",
+          "name": "incentiveAmount",
+          "type": "u64",
           "index": false
         }
       ]
     },
     {
-      "name": "TokenConditionalSwapTriggerLogV2",
+      "name": "PerpConditionalOrderCreateLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -28514,79 +38016,54 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "liqee",
-          "type": "publicKey",
-          "index": false
-        },
-        {
-          "name": "liqor",
+          "name": "mangoAccount",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "tokenConditionalSwapId",
+          "name": "id",
           "type": "u64",
           "index": false
         },
         {
-          "name": "buyTokenIndex",
-          "type": "u16",
-          "index": false
-        },
-        {
-          "name": "sellTokenIndex",
+          "name": "marketIndex",
           "type": "u16",
           "index": false
         },
         {
-          "name": "buyAmount",
-          "type": "u64",
-          "index": false
-        },
-        {
-          "name": "sellAmount",
-          "type": "u64",
-          "index": false
-        },
-        {
-          "name": "makerFee",
-          "type": "u64",
-          "index": false
-        },
-        {
-          "name": "takerFee",
-          "type": "u64",
+          "name": "orderType",
+          "type": "u8",
           "index": false
         },
         {
-          "name": "buyTokenPrice",
-          "type": "i128",
+          "name": "side",
+          "type": "u8",
           "index": false
         },
         {
-          "name": "sellTokenPrice",
-          "type": "i128",
+          "name": "maxBaseLots",
+          "type": "i64",
           "index": false
         },
         {
-          "name": "closed",
-          "type": "bool",
+          "name": "triggerPriceLots",
+          "type": "i64",
           "index": false
         },
         {
-          "name": "displayPriceStyle",
-          "type": "u8",
+          "name": "maxSlippage",
+          "type": "f32",
           "index": false
         },
         {
-          "name": "intention",
-          "type": "u8",
+          "name": "expiryTimestamp",
+          "type": "u64",
           "index": false
         }
       ]
     },
     {
-      "name": "TokenConditionalSwapTriggerLogV3",
+      "name": "PerpConditionalOrderTriggerLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -28594,109 +38071,114 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "liqee",
+          "name": "mangoAccount",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "liqor",
+          "name": "caller",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "tokenConditionalSwapId",
+          "name": "id",
           "type": "u64",
           "index": false
         },
         {
-          "name": "buyTokenIndex",
+          "name": "marketIndex",
           "type": "u16",
           "index": false
         },
         {
-          "name": "sellTokenIndex",
-          "type": "u16",
+          "name": "orderType",
+          "type": "u8",
           "index": false
         },
         {
-          "name": "buyAmount",
-          "type": "u64",
+          "name": "side",
+          "type": "u8",
           "index": false
         },
         {
-          "name": "sellAmount",
-          "type": "u64",
+          "name": "oraclePriceLots",
+          "type": "i64",
           "index": false
         },
         {
-          "name": "makerFee",
-          "type": "u64",
+          "name": "triggerPriceLots",
+          "type": "i64",
           "index": false
         },
         {
-          "name": "takerFee",
-          "type": "u64",
+          "name": "limitPriceLots",
+          "type": "i64",
           "index": false
         },
         {
-          "name": "buyTokenPrice",
-          "type": "i128",
+          "name": "filledBaseLots",
+          "type": "i64",
           "index": false
         },
         {
-          "name": "sellTokenPrice",
-          "type": "i128",
+          "name": "incentiveTokenIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "closed",
-          "type": "bool",
+          "name": "incentiveAmount",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PerpSpreadOrderLog",
+      "fields": [
+        {
+          "name": "mangoGroup",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "displayPriceStyle",
-          "type": "u8",
+          "name": "mangoAccount",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "intention",
-          "type": "u8",
+          "name": "marketIndexA",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "tcsType",
-          "type": "u8",
+          "name": "marketIndexB",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "startTimestamp",
-          "type": "u64",
+          "name": "sideA",
+          "type": "u8",
           "index": false
-        }
-      ]
-    },
-    {
-      "name": "TokenConditionalSwapCancelLog",
-      "fields": [
+        },
         {
-          "name": "mangoGroup",
-          "type": "publicKey",
+          "name": "sideB",
+          "type": "u8",
           "index": false
         },
         {
-          "name": "mangoAccount",
-          "type": "publicKey",
+          "name": "filledBaseLotsA",
+          "type": "i64",
           "index": false
         },
         {
-          "name": "id",
-          "type": "u64",
+          "name": "filledBaseLotsB",
+          "type": "i64",
           "index": false
         }
       ]
     },
     {
-      "name": "TokenConditionalSwapStartLog",
+      "name": "PerpIsolatedMarginLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -28709,29 +38191,24 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "caller",
-          "type": "publicKey",
-          "index": false
-        },
-        {
-          "name": "tokenConditionalSwapId",
-          "type": "u64",
+          "name": "marketIndex",
+          "type": "u16",
           "index": false
         },
         {
-          "name": "incentiveTokenIndex",
-          "type": "u16",
+          "name": "isolated",
+          "type": "bool",
           "index": false
         },
         {
-          "name": "incentiveAmount",
-          "type": "u64",
+          "name": "isolatedCollateral",
+          "type": "i128",
           "index": false
         }
       ]
     },
     {
-      "name": "TokenCollateralFeeLog",
+      "name": "TokenTransferLog",
       "fields": [
         {
           "name": "mangoGroup",
@@ -28739,23 +38216,28 @@ export const IDL: MangoV4 = {
           "index": false
         },
         {
-          "name": "mangoAccount",
+          "name": "sourceAccount",
           "type": "publicKey",
           "index": false
         },
         {
-          "name": "tokenIndex",
-          "type": "u16",
+          "name": "destinationAccount",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "assetUsageFraction",
-          "type": "i128",
+          "name": "signer",
+          "type": "publicKey",
           "index": false
         },
         {
-          "name": "fee",
-          "type": "i128",
+          "name": "tokenIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "quantity",
+          "type": "u64",
           "index": false
         },
         {
@@ -28766,36 +38248,31 @@ export const IDL: MangoV4 = {
       ]
     },
     {
-      "name": "ForceWithdrawLog",
+      "name": "TokenCircuitBreakerLog",
       "fields": [
         {
           "name": "mangoGroup",
           "type": "publicKey",
           "index": false
         },
-        {
-          "name": "mangoAccount",
-          "type": "publicKey",
-          "index": false
-        },
         {
           "name": "tokenIndex",
           "type": "u16",
           "index": false
         },
         {
-          "name": "quantity",
-          "type": "u64",
+          "name": "price",
+          "type": "i128",
           "index": false
         },
         {
-          "name": "price",
-          "type": "i128",
+          "name": "referencePrice",
+          "type": "f64",
           "index": false
         },
         {
-          "name": "toTokenAccount",
-          "type": "publicKey",
+          "name": "trippedUntil",
+          "type": "u64",
           "index": false
         }
       ]
@@ -29166,6 +38643,141 @@ export const IDL: MangoV4 = {
       "code": 6072,
       "name": "InvalidHealth",
       "msg": "invalid health"
+    },
+    {
+      "code": 6073,
+      "name": "NoFreeOpenbookV2OpenOrdersIndex",
+      "msg": "no free openbook v2 open orders index"
+    },
+    {
+      "code": 6074,
+      "name": "OpenbookV2OpenOrdersExistAlready",
+      "msg": "openbook v2 open orders exist already"
+    },
+    {
+      "code": 6075,
+      "name": "HasOpenOrUnsettledOpenbookV2Orders",
+      "msg": "there are open or unsettled openbook v2 orders"
+    },
+    {
+      "code": 6076,
+      "name": "NoFreeFixedTermPositionIndex",
+      "msg": "no free fixed term position index"
+    },
+    {
+      "code": 6077,
+      "name": "FixedTermMarketMatured",
+      "msg": "fixed term market has matured"
+    },
+    {
+      "code": 6078,
+      "name": "FixedTermMarketNotMatured",
+      "msg": "fixed term market has not matured yet"
+    },
+    {
+      "code": 6079,
+      "name": "FixedTermMarketInsufficientLiquidity",
+      "msg": "fixed term market does not have enough lent tokens available"
+    },
+    {
+      "code": 6080,
+      "name": "FixedTermMarketLendLimitExceeded",
+      "msg": "fixed term market lend limit exceeded"
+    },
+    {
+      "code": 6081,
+      "name": "PerpScheduledOrderNotSet",
+      "msg": "perp scheduled order is not set"
+    },
+    {
+      "code": 6082,
+      "name": "PerpScheduledOrderExpired",
+      "msg": "perp scheduled order is expired"
+    },
+    {
+      "code": 6083,
+      "name": "PerpScheduledOrderNotTriggerable",
+      "msg": "perp scheduled order can't be triggered yet"
+    },
+    {
+      "code": 6084,
+      "name": "PerpScheduledOrderIndexIdMismatch",
+      "msg": "perp scheduled order index and id don't match"
+    },
+    {
+      "code": 6085,
+      "name": "PerpConditionalOrderPriceNotTriggered",
+      "msg": "perp conditional order oracle price condition is not met"
+    },
+    {
+      "code": 6086,
+      "name": "PerpSpreadLegNotFilled",
+      "msg": "the second leg of a perp spread order could not be filled completely"
+    },
+    {
+      "code": 6087,
+      "name": "PerpPositionNotIsolated",
+      "msg": "perp position is not isolated"
+    },
+    {
+      "code": 6088,
+      "name": "IsolatedPerpHealthMustBePositiveOrIncrease",
+      "msg": "isolated perp position health must be positive or not decrease"
+    },
+    {
+      "code": 6089,
+      "name": "DelegatePermissionDenied",
+      "msg": "the delegate is not permitted to take this action"
+    },
+    {
+      "code": 6090,
+      "name": "DelegateOrderNotionalExceeded",
+      "msg": "the order exceeds the delegate's notional limit"
+    },
+    {
+      "code": 6091,
+      "name": "OracleQuorumNotReached",
+      "msg": "not enough fresh and agreeing source oracles"
+    },
+    {
+      "code": 6092,
+      "name": "BankBorrowLimit",
+      "msg": "borrow crosses the token's borrow limit"
+    },
+    {
+      "code": 6093,
+      "name": "IsolatedCollateralBorrowNotAllowed",
+      "msg": "isolated collateral cannot back borrows of this token"
+    },
+    {
+      "code": 6094,
+      "name": "InvalidEmodeCategory",
+      "msg": "invalid e-mode category"
+    },
+    {
+      "code": 6095,
+      "name": "InvalidPerpFeeTier",
+      "msg": "invalid perp fee tier"
+    },
+    {
+      "code": 6096,
+      "name": "PerpOpeningAuctionOrderNotPostable",
+      "msg": "orders that can't rest on the book are not allowed during a perp opening auction"
+    },
+    {
+      "code": 6097,
+      "name": "PerpOpeningAuctionNotActive",
+      "msg": "perp market is not in an opening auction"
+    },
+    {
+      "code": 6098,
+      "name": "PerpOpeningAuctionNotEnded",
+      "msg": "perp opening auction has not ended yet"
+    },
+    {
+      "code": 6099,
+      "name": "IsolatedDebtCeilingReached",
+      "msg": "the token's isolated collateral backs the maximum amount of borrows"
     }
   ]
 };
@@ -47,4 +47,7 @@ export class InterestRateParams {
 export class OracleConfigParams {
   confFilter: number;
   maxStalenessSlots: number | null;
+  maxStalenessSeconds?: number | null;
+  riskIncreasingMaxStalenessSlots?: number | null;
+  riskIncreasingMaxStalenessSeconds?: number | null;
 }